@@ -0,0 +1,308 @@
+"use strict";
+
+// The dashboard keeps the tokens of the `/picker token` command on the browser, renewing the
+// access token with the refresh token once it expires.
+const TOKENS_KEY = "team-event-picker.tokens";
+
+const EVENTS_QUERY = `
+  query Events($query: String) {
+    channel {
+      id
+      events(query: $query, first: 100) {
+        id
+        name
+        timestamp
+        repeat
+      }
+    }
+  }
+`;
+
+const EVENT_QUERY = `
+  query Event($id: Int!) {
+    event(id: $id) {
+      id
+      name
+      timestamp
+      timezone
+      repeat
+      endsAt
+      participants {
+        user
+        picked
+        unavailableUntil
+      }
+      history(limit: 50) {
+        user
+        timestamp
+        source
+        partner
+        noShow
+      }
+    }
+  }
+`;
+
+const $ = (id) => document.getElementById(id);
+
+function loadTokens() {
+  try {
+    return JSON.parse(localStorage.getItem(TOKENS_KEY));
+  } catch {
+    return null;
+  }
+}
+
+function saveTokens(tokens) {
+  if (tokens) {
+    localStorage.setItem(TOKENS_KEY, JSON.stringify(tokens));
+  } else {
+    localStorage.removeItem(TOKENS_KEY);
+  }
+}
+
+async function refreshTokens() {
+  const tokens = loadTokens();
+  if (!tokens) {
+    return false;
+  }
+  const response = await fetch("/api/auth/refresh", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ refresh_token: tokens.refresh_token }),
+  });
+  if (!response.ok) {
+    saveTokens(null);
+    return false;
+  }
+  const body = await response.json();
+  saveTokens({ access_token: body.access_token, refresh_token: body.refresh_token });
+  return true;
+}
+
+async function api(path, options = {}, retry = true) {
+  const tokens = loadTokens();
+  if (!tokens) {
+    throw new Error("unauthorized");
+  }
+  const response = await fetch(path, {
+    ...options,
+    headers: {
+      ...options.headers,
+      Authorization: `Bearer ${tokens.access_token}`,
+    },
+  });
+  if (response.status === 401 && retry && (await refreshTokens())) {
+    return api(path, options, false);
+  }
+  if (response.status === 401) {
+    saveTokens(null);
+    throw new Error("unauthorized");
+  }
+  if (!response.ok) {
+    throw new Error(await errorMessage(response));
+  }
+  return response;
+}
+
+async function errorMessage(response) {
+  try {
+    const body = await response.json();
+    return body.error || body.message || response.statusText;
+  } catch {
+    return response.statusText;
+  }
+}
+
+async function graphql(query, variables) {
+  const response = await api("/api/v1/graphql", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ query, variables }),
+  });
+  const body = await response.json();
+  if (body.errors && body.errors.length) {
+    throw new Error(body.errors[0].message);
+  }
+  return body.data;
+}
+
+function formatDate(timestamp) {
+  return new Date(timestamp * 1000).toLocaleString();
+}
+
+function show(section) {
+  for (const id of ["login", "events", "event", "audit"]) {
+    $(id).hidden = id !== section;
+  }
+  $("logout").hidden = section === "login";
+  $("message").textContent = "";
+}
+
+function element(tag, text, className) {
+  const node = document.createElement(tag);
+  if (text !== undefined) {
+    node.textContent = text;
+  }
+  if (className) {
+    node.className = className;
+  }
+  return node;
+}
+
+async function renderEvents() {
+  show("events");
+  const data = await graphql(EVENTS_QUERY, { query: $("search").value || null });
+  const list = $("event-list");
+  list.replaceChildren();
+  for (const event of data.channel.events) {
+    const item = element("li");
+    const link = element("a", event.name);
+    link.href = `#events/${event.id}`;
+    item.append(link, element("div", `${formatDate(event.timestamp)} · ${event.repeat}`));
+    list.append(item);
+  }
+  if (!data.channel.events.length) {
+    list.append(element("li", "There are no events on the channel."));
+  }
+}
+
+async function renderEvent(id) {
+  show("event");
+  const data = await graphql(EVENT_QUERY, { id });
+  const event = data.event;
+  if (!event) {
+    throw new Error("the event was not found");
+  }
+
+  $("event-name").textContent = event.name;
+  const ends = event.endsAt ? ` until ${formatDate(event.endsAt)}` : "";
+  $("event-details").textContent =
+    `${formatDate(event.timestamp)} (${event.timezone}) · ${event.repeat}${ends}`;
+
+  const now = Date.now() / 1000;
+  const participants = $("participants");
+  participants.replaceChildren();
+  for (const participant of event.participants) {
+    const away = participant.unavailableUntil && participant.unavailableUntil > now;
+    const status = away ? " (away)" : participant.picked ? " (picked)" : "";
+    participants.append(
+      element("li", `<@${participant.user}>${status}`, participant.picked ? "picked" : "")
+    );
+  }
+
+  const history = $("history");
+  history.replaceChildren();
+  for (const pick of event.history) {
+    const row = element("tr");
+    const picked = pick.partner ? `<@${pick.user}> & <@${pick.partner}>` : `<@${pick.user}>`;
+    row.append(
+      element("td", formatDate(pick.timestamp)),
+      element("td", picked),
+      element("td", pick.noShow ? `${pick.source} · No-show` : pick.source)
+    );
+    history.append(row);
+  }
+
+  $("pick").onclick = () =>
+    run(async () => {
+      const response = await api(`/api/v1/events/${id}/pick`, {
+        method: "POST",
+        headers: { "Idempotency-Key": crypto.randomUUID() },
+      });
+      const body = await response.json();
+      await renderEvent(id);
+      $("message").textContent = `Picked <@${body.picked}>, ${body.left_count} left.`;
+    });
+  $("download").onclick = () =>
+    run(async () => {
+      const response = await api(`/api/v1/events/${id}/history.csv`);
+      const link = element("a");
+      link.href = URL.createObjectURL(await response.blob());
+      link.download = `event-${id}-history.csv`;
+      link.click();
+      URL.revokeObjectURL(link.href);
+    });
+  $("delete").onclick = () =>
+    run(async () => {
+      if (!confirm(`Delete the event ${event.name}?`)) {
+        return;
+      }
+      await api(`/api/v1/events/${id}`, { method: "DELETE" });
+      location.hash = "";
+    });
+}
+
+async function renderAudit() {
+  show("audit");
+  const response = await api("/api/v1/audit?limit=50");
+  const records = (await response.json()).data;
+  const list = $("audit-list");
+  list.replaceChildren();
+  for (const record of records) {
+    const user = record.user ? `<@${record.user}>` : "The app";
+    const event = record.event ? ` on event ${record.event}` : "";
+    list.append(element("li", `${formatDate(record.timestamp)} · ${user} ${record.action}${event}`));
+  }
+}
+
+async function run(action) {
+  try {
+    await action();
+  } catch (err) {
+    if (err.message === "unauthorized") {
+      show("login");
+    } else {
+      $("message").textContent = err.message;
+    }
+  }
+}
+
+function route() {
+  if (!loadTokens()) {
+    show("login");
+    return;
+  }
+  const hash = location.hash.replace(/^#/, "");
+  const event = hash.match(/^events\/(\d+)$/);
+  if (event) {
+    run(() => renderEvent(Number(event[1])));
+  } else if (hash === "audit") {
+    run(renderAudit);
+  } else {
+    run(renderEvents);
+  }
+}
+
+$("login-form").addEventListener("submit", (e) => {
+  e.preventDefault();
+  const form = new FormData(e.target);
+  saveTokens({
+    access_token: form.get("access_token").trim(),
+    refresh_token: form.get("refresh_token").trim(),
+  });
+  e.target.reset();
+  route();
+});
+
+$("logout").addEventListener("click", async () => {
+  const tokens = loadTokens();
+  saveTokens(null);
+  if (tokens) {
+    await fetch("/api/auth/logout", {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ refresh_token: tokens.refresh_token }),
+    });
+  }
+  route();
+});
+
+let searchTimeout;
+$("search").addEventListener("input", () => {
+  clearTimeout(searchTimeout);
+  searchTimeout = setTimeout(() => run(renderEvents), 300);
+});
+
+window.addEventListener("hashchange", route);
+route();
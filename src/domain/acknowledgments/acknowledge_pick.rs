@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::domain::entities::PendingAcknowledgment;
+use crate::repository::acknowledgment::Repository;
+use crate::repository::errors::{DeleteError, FindError};
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    pub user: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    /// The pick belongs to another participant, who is the only one acknowledging it.
+    Forbidden(String),
+    Unknown,
+}
+
+/// Acknowledges the pending pick of the event, so that it is not repicked once it expires.
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<PendingAcknowledgment, Error> {
+    let acknowledgment = repo
+        .find_acknowledgment(req.event, req.channel.clone())
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    if acknowledgment.user != req.user {
+        return Err(Error::Forbidden(acknowledgment.user));
+    }
+
+    repo.delete_acknowledgment(req.event, req.channel)
+        .await
+        .map_err(|err| match err {
+            DeleteError::NotFound => Error::NotFound,
+            DeleteError::Unknown => Error::Unknown,
+        })
+}
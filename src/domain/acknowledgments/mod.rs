@@ -0,0 +1,3 @@
+pub mod acknowledge_pick;
+pub mod repick_unacknowledged;
+pub mod save_acknowledgment;
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{Auth, PendingAcknowledgment};
+use crate::domain::events::repick_participant;
+use crate::domain::helpers::participant::last_picked;
+use crate::helpers::secret::SecretString;
+use crate::repository::{acknowledgment, auth, event, pick_history};
+
+pub struct Request {
+    /// The timestamp (in seconds) up to which the pending acknowledgments are expired.
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub repicks: Vec<Repick>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Repick {
+    pub event_id: u32,
+    pub event_name: String,
+    pub channel_id: String,
+    pub team_id: String,
+    pub user_id: String,
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner_id: Option<String>,
+    /// The participant who did not acknowledge the pick.
+    pub replaced: String,
+    pub left_count: usize,
+    /// The timestamp of the pick message to update.
+    pub message_ts: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Looked up again when the notification is posted, so that it is not stored with it.
+    #[serde(skip)]
+    pub access_token: SecretString,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Unknown,
+}
+
+/// Repicks the events whose picked participants did not acknowledge their pick in time.
+///
+/// The expired acknowledgments are dropped, and their events are only repicked while the
+/// unacknowledged participant is still their latest pick (i.e. nobody repicked or cancelled it in
+/// the meantime).
+pub async fn execute(
+    acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+    event_repo: Arc<dyn event::Repository>,
+    auth_repo: Arc<dyn auth::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let expired = acknowledgment_repo
+        .find_expired_acknowledgments_unprotected(req.timestamp)
+        .await
+        .map_err(|err| {
+            log::error!("could not find the expired acknowledgments: {:?}", err);
+            Error::Unknown
+        })?;
+    if expired.is_empty() {
+        return Ok(Response { repicks: vec![] });
+    }
+
+    let tokens: HashMap<String, Auth> = auth_repo
+        .find_all_by_team(expired.iter().map(|ack| ack.team_id.clone()).collect())
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .flat_map(|auth| {
+            auth.team_ids()
+                .into_iter()
+                .map(move |team| (team, auth.clone()))
+        })
+        .collect();
+
+    let mut repicks: Vec<Repick> = vec![];
+    for acknowledgment in expired.into_iter() {
+        // Dropping it first prevents another run from repicking the event twice.
+        if let Err(err) = acknowledgment_repo
+            .delete_acknowledgment(acknowledgment.event, acknowledgment.channel.clone())
+            .await
+        {
+            log::info!(
+                "ignoring repick: acknowledgment of event {} is gone: {:?}",
+                acknowledgment.event,
+                err
+            );
+            continue;
+        }
+        let access_token = match tokens.get(&acknowledgment.team_id) {
+            Some(auth) => auth.access_token.clone(),
+            None => {
+                log::error!(
+                    "ignoring repick: could not find access token for team id {} while repicking the event {}",
+                    acknowledgment.team_id,
+                    acknowledgment.event
+                );
+                continue;
+            }
+        };
+        if let Some(repick) = repick(
+            event_repo.clone(),
+            history_repo.clone(),
+            &acknowledgment,
+            access_token,
+        )
+        .await
+        {
+            repicks.push(repick);
+        }
+    }
+
+    Ok(Response { repicks })
+}
+
+async fn repick(
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    acknowledgment: &PendingAcknowledgment,
+    access_token: SecretString,
+) -> Option<Repick> {
+    let event = event_repo
+        .find_event(acknowledgment.event, acknowledgment.channel.clone())
+        .await
+        .map_err(|err| {
+            log::info!(
+                "ignoring repick: could not find event {}: {:?}",
+                acknowledgment.event,
+                err
+            )
+        })
+        .ok()?;
+    if event.inactive {
+        log::info!("ignoring repick: event {} has ended", event.id);
+        return None;
+    }
+    if event.paused {
+        log::info!("ignoring repick: event {} is paused", event.id);
+        return None;
+    }
+    if last_picked(&event.participants).map(|participant| participant.user.as_str())
+        != Some(acknowledgment.user.as_str())
+    {
+        log::info!("ignoring repick: event {} was picked again", event.id);
+        return None;
+    }
+    let left_count = event
+        .participants
+        .iter()
+        .filter(|participant| !participant.picked)
+        .count();
+
+    let response = repick_participant::execute(
+        event_repo,
+        history_repo,
+        repick_participant::Request {
+            event: event.id,
+            channel: event.channel.clone(),
+        },
+    )
+    .await
+    .map_err(|err| log::error!("could not repick event {}: {:?}", event.id, err))
+    .ok()?;
+    if response.name == acknowledgment.user {
+        log::info!(
+            "ignoring repick: event {} has no one left to pick",
+            event.id
+        );
+        return None;
+    }
+
+    Some(Repick {
+        event_id: event.id,
+        event_name: event.name,
+        channel_id: event.channel,
+        team_id: event.team_id,
+        user_id: response.name,
+        partner_id: response.partner,
+        replaced: acknowledgment.user.clone(),
+        left_count,
+        message_ts: acknowledgment.message_ts.clone(),
+        description: event.description,
+        url: event.url,
+        access_token,
+    })
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::domain::entities::PendingAcknowledgment;
+use crate::helpers::date::Date;
+use crate::repository::acknowledgment::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub event_name: String,
+    pub channel: String,
+    pub team_id: String,
+    pub user: String,
+    pub message_ts: String,
+    /// The minutes the participant has to acknowledge the pick.
+    pub minutes: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<PendingAcknowledgment, Error> {
+    repo.save_acknowledgment(PendingAcknowledgment {
+        event: req.event,
+        event_name: req.event_name,
+        channel: req.channel,
+        team_id: req.team_id,
+        user: req.user,
+        message_ts: req.message_ts,
+        expires_at: Date::now().timestamp() + req.minutes as i64 * 60,
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the pending acknowledgment: {:?}", err);
+        Error::Unknown
+    })
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::AuditRecord;
+use crate::repository::audit::Repository;
+use crate::repository::errors::FindAllError;
+
+pub struct Request {
+    pub team_id: String,
+    pub channel: String,
+    pub limit: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<ListResponse<AuditRecord>, Error> {
+    match repo.find_records(req.team_id, req.channel, req.limit).await {
+        Ok(records) => Ok(ListResponse::new(records)),
+        Err(FindAllError::Unknown) => Err(Error::Unknown),
+    }
+}
@@ -0,0 +1,2 @@
+pub mod find_records;
+pub mod save_record;
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{AuditAction, AuditRecord};
+use crate::helpers::date::Date;
+use crate::repository::audit::Repository;
+use crate::repository::errors::InsertError;
+
+pub struct Request {
+    pub team_id: String,
+    pub channel: Option<String>,
+    pub user: Option<String>,
+    pub action: AuditAction,
+    pub event: Option<u32>,
+}
+
+impl From<Request> for AuditRecord {
+    fn from(value: Request) -> Self {
+        Self {
+            team_id: value.team_id,
+            channel: value.channel,
+            user: value.user,
+            action: value.action,
+            event: value.event,
+            timestamp: Date::now().timestamp(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Unknown,
+}
+
+impl From<InsertError> for Error {
+    fn from(value: InsertError) -> Self {
+        match value {
+            InsertError::Conflict | InsertError::Unknown => Error::Unknown,
+        }
+    }
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<AuditRecord, Error> {
+    Ok(repo.insert_record(req.into()).await?)
+}
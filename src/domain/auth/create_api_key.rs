@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::entities::ApiKey;
+use crate::domain::helpers::token;
+use crate::helpers::date::Date;
+use crate::repository::{api_key::Repository, errors::InsertError};
+
+pub struct Request {
+    pub name: String,
+    pub user: String,
+    pub team: String,
+    pub channel: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    /// The value of the key, which is only known when it is created.
+    pub key: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest(String),
+    Unknown,
+}
+
+impl From<InsertError> for Error {
+    fn from(value: InsertError) -> Self {
+        match value {
+            InsertError::Conflict | InsertError::Unknown => Error::Unknown,
+        }
+    }
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = req.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(Error::BadRequest(format!(
+            "the name must have between 1 and {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+
+    let value = format!("{}{}", API_KEY_PREFIX, token::generate());
+    let key = repo
+        .insert_key(ApiKey {
+            id: 0,
+            name: name.to_string(),
+            hash: token::hash(&value),
+            user: req.user,
+            team: req.team,
+            channel: req.channel,
+            created_at: Date::now().timestamp(),
+        })
+        .await?;
+
+    Ok(Response {
+        id: key.id,
+        name: key.name,
+        key: value,
+        created_at: key.created_at,
+    })
+}
+
+/// The prefix of the API keys, telling them apart from the access tokens.
+pub const API_KEY_PREFIX: &str = "tep_";
+
+const MAX_NAME_LENGTH: usize = 80;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use crate::repository::errors::{DeleteError, FindAllError, FindError};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockRepository {
+        keys: Mutex<Vec<ApiKey>>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError> {
+            let mut keys = self.keys.lock().unwrap();
+            let key = ApiKey {
+                id: keys.len() as u32 + 1,
+                ..key
+            };
+            keys.push(key.clone());
+            Ok(key)
+        }
+
+        async fn find_key_by_hash(&self, _hash: String) -> Result<ApiKey, FindError> {
+            Err(FindError::NotFound)
+        }
+
+        async fn find_keys(
+            &self,
+            _team: String,
+            _channel: String,
+        ) -> Result<Vec<ApiKey>, FindAllError> {
+            Ok(vec![])
+        }
+
+        async fn delete_key(
+            &self,
+            _id: u32,
+            _team: String,
+            _channel: String,
+        ) -> Result<ApiKey, DeleteError> {
+            Err(DeleteError::NotFound)
+        }
+
+        async fn delete_team_keys(&self, _teams: Vec<String>) -> Result<u64, DeleteError> {
+            Ok(0)
+        }
+    }
+
+    fn request(name: &str) -> Request {
+        Request {
+            name: String::from(name),
+            user: String::from("U1"),
+            team: String::from("T1"),
+            channel: String::from("C1"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute() {
+        let repo = Arc::new(MockRepository::default());
+
+        let response = execute(repo.clone(), request(" CI ")).await.unwrap();
+        assert_eq!(response.id, 1);
+        assert_eq!(response.name, "CI");
+        assert!(response.key.starts_with(API_KEY_PREFIX));
+
+        // Only the hash of the key is stored.
+        let keys = repo.keys.lock().unwrap().clone();
+        assert_eq!(keys[0].hash, token::hash(&response.key));
+        assert_ne!(keys[0].hash, response.key);
+
+        assert!(matches!(
+            execute(repo.clone(), request(" ")).await,
+            Err(Error::BadRequest(..))
+        ));
+        assert!(matches!(
+            execute(repo, request(&"a".repeat(MAX_NAME_LENGTH + 1))).await,
+            Err(Error::BadRequest(..))
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::domain::entities::RefreshToken;
+use crate::domain::helpers::token;
+use crate::helpers::date::Date;
+use crate::repository::{auth::Repository, errors::InsertError};
+
+pub struct Request {
+    pub user: String,
+    pub team: String,
+    pub channel: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub token: String,
+    pub expires_at: i64,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown,
+}
+
+impl From<InsertError> for Error {
+    fn from(value: InsertError) -> Self {
+        match value {
+            InsertError::Conflict | InsertError::Unknown => Error::Unknown,
+        }
+    }
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let value = token::generate();
+    let expires_at = Date::now().timestamp() + REFRESH_TOKEN_EXPIRATION_DAYS * 24 * 60 * 60;
+    repo.insert_refresh_token(RefreshToken {
+        hash: token::hash(&value),
+        user: req.user,
+        team: req.team,
+        channel: req.channel,
+        expires_at,
+    })
+    .await?;
+
+    Ok(Response {
+        token: value,
+        expires_at,
+    })
+}
+
+/// The number of days the refresh tokens are valid for.
+const REFRESH_TOKEN_EXPIRATION_DAYS: i64 = 90;
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::ApiKey;
+use crate::repository::{api_key::Repository, errors::FindAllError};
+
+pub struct Request {
+    pub team: String,
+    pub channel: String,
+}
+
+/// An API key, without its hash.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub user: String,
+    pub channel: String,
+    pub created_at: i64,
+}
+
+impl From<ApiKey> for Response {
+    fn from(value: ApiKey) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            user: value.user,
+            channel: value.channel,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Finds the API keys of the channel of the team.
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<ListResponse<Response>, Error> {
+    let keys = match repo.find_keys(req.team, req.channel).await {
+        Ok(keys) => keys,
+        Err(FindAllError::Unknown) => return Err(Error::Unknown),
+    };
+    Ok(ListResponse::new(
+        keys.into_iter().map(Into::into).collect(),
+    ))
+}
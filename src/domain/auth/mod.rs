@@ -1,2 +1,10 @@
+pub mod create_api_key;
+pub mod create_refresh_token;
+pub mod find_api_keys;
+pub mod revoke_api_key;
+pub mod revoke_auth;
+pub mod revoke_refresh_token;
+pub mod rotate_refresh_token;
 pub mod save_auth;
+pub mod verify_api_key;
 pub mod verify_auth;
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::repository::{api_key::Repository, errors::DeleteError};
+
+pub struct Request {
+    pub id: u32,
+    pub team: String,
+    pub channel: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+/// Revokes an API key of the channel of the team, so that it no longer authenticates the requests.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    match repo.delete_key(req.id, req.team, req.channel).await {
+        Ok(..) => Ok(()),
+        Err(DeleteError::NotFound) => Err(Error::NotFound),
+        Err(DeleteError::Unknown) => Err(Error::Unknown),
+    }
+}
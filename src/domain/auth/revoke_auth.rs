@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use crate::domain::entities::Auth;
+use crate::repository::{
+    api_key,
+    auth::Repository,
+    errors::{DeleteError, FindError, UpdateError},
+};
+
+pub struct Request {
+    pub team: String,
+    /// The Enterprise Grid organization of the team, when it belongs to one.
+    pub enterprise: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown,
+}
+
+impl From<DeleteError> for Error {
+    fn from(value: DeleteError) -> Self {
+        match value {
+            DeleteError::NotFound | DeleteError::Unknown => Error::Unknown,
+        }
+    }
+}
+
+impl From<UpdateError> for Error {
+    fn from(value: UpdateError) -> Self {
+        match value {
+            UpdateError::Conflict | UpdateError::NotFound | UpdateError::Unknown => Error::Unknown,
+        }
+    }
+}
+
+/// Marks the access token of a team as deleted, so that nothing is posted on its behalf anymore,
+/// returning it. The token of the organization is the one revoked when the application was
+/// installed on the whole organization.
+///
+/// The refresh tokens and the API keys of the teams are deleted as well, so that the API is not
+/// reachable on their behalf anymore. Teams without an access token are otherwise left as they are.
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    api_key_repo: Arc<dyn api_key::Repository>,
+    req: Request,
+) -> Result<Option<Auth>, Error> {
+    let team = req.team.clone();
+    let auth = match find_auth(repo.clone(), req).await {
+        Ok(auth) => Some(
+            repo.update(Auth {
+                deleted: true,
+                ..auth
+            })
+            .await?,
+        ),
+        Err(FindError::NotFound) => None,
+        Err(FindError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let teams = auth
+        .as_ref()
+        .map_or_else(|| vec![team], |auth| auth.team_ids());
+    repo.delete_refresh_tokens(teams.clone()).await?;
+    api_key_repo.delete_team_keys(teams).await?;
+
+    Ok(auth)
+}
+
+async fn find_auth(repo: Arc<dyn Repository>, req: Request) -> Result<Auth, FindError> {
+    match repo.find_by_team(req.team.clone()).await {
+        Err(FindError::NotFound) => (),
+        result => return result,
+    };
+    match repo.find_by_enterprise_team(req.team).await {
+        Err(FindError::NotFound) => (),
+        result => return result,
+    };
+    let auth = repo
+        .find_by_team(req.enterprise.ok_or(FindError::NotFound)?)
+        .await?;
+    if !auth.is_enterprise_install() {
+        return Err(FindError::NotFound);
+    }
+    Ok(auth)
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::domain::helpers::token;
+use crate::repository::{auth::Repository, errors::FindError};
+
+pub struct Request {
+    pub token: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown,
+}
+
+/// Revokes a refresh token. Unknown tokens are considered revoked already.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    match repo.take_refresh_token(token::hash(&req.token)).await {
+        Ok(..) | Err(FindError::NotFound) => Ok(()),
+        Err(FindError::Unknown) => Err(Error::Unknown),
+    }
+}
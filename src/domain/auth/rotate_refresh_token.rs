@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use crate::domain::auth::create_refresh_token;
+use crate::domain::helpers::token;
+use crate::helpers::date::Date;
+use crate::repository::{auth::Repository, errors::FindError};
+
+pub struct Request {
+    pub token: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub user: String,
+    pub team: String,
+    pub channel: String,
+    pub token: String,
+    pub expires_at: i64,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unauthorized,
+    Unknown,
+}
+
+/// Replaces a refresh token by a new one with the same scope, so that each refresh token is used
+/// only once.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let previous = match repo.take_refresh_token(token::hash(&req.token)).await {
+        Ok(previous) => previous,
+        Err(FindError::NotFound) => return Err(Error::Unauthorized),
+        Err(FindError::Unknown) => return Err(Error::Unknown),
+    };
+    if previous.expires_at <= Date::now().timestamp() {
+        return Err(Error::Unauthorized);
+    }
+
+    let request = create_refresh_token::Request {
+        user: previous.user.clone(),
+        team: previous.team.clone(),
+        channel: previous.channel.clone(),
+    };
+    let next = create_refresh_token::execute(repo, request)
+        .await
+        .map_err(|_| Error::Unknown)?;
+
+    Ok(Response {
+        user: previous.user,
+        team: previous.team,
+        channel: previous.channel,
+        token: next.token,
+        expires_at: next.expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::RefreshToken;
+    use crate::repository::auth::MemoryRepository;
+
+    use super::*;
+
+    async fn refresh_token(repo: Arc<MemoryRepository>) -> String {
+        let request = create_refresh_token::Request {
+            user: String::from("U1"),
+            team: String::from("T1"),
+            channel: String::from("C1"),
+        };
+        create_refresh_token::execute(repo, request)
+            .await
+            .unwrap()
+            .token
+    }
+
+    #[tokio::test]
+    async fn test_rotation() {
+        let repo = Arc::new(MemoryRepository::default());
+        let token = refresh_token(repo.clone()).await;
+
+        let response = execute(
+            repo.clone(),
+            Request {
+                token: token.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        // The new token keeps the scope of the previous one.
+        assert_eq!(response.user, "U1");
+        assert_eq!(response.team, "T1");
+        assert_eq!(response.channel, "C1");
+        assert_ne!(response.token, token);
+        let tokens = repo.refresh_tokens.lock().unwrap().clone();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].hash, token::hash(&response.token));
+        assert_eq!(
+            (
+                tokens[0].user.as_str(),
+                tokens[0].team.as_str(),
+                tokens[0].channel.as_str()
+            ),
+            ("U1", "T1", "C1")
+        );
+
+        // Each token is used only once.
+        assert!(matches!(
+            execute(repo.clone(), Request { token }).await,
+            Err(Error::Unauthorized)
+        ));
+        assert!(execute(
+            repo,
+            Request {
+                token: response.token
+            }
+        )
+        .await
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_expired_token() {
+        let repo = Arc::new(MemoryRepository::default());
+        let token = token::generate();
+        repo.insert_refresh_token(RefreshToken {
+            hash: token::hash(&token),
+            user: String::from("U1"),
+            team: String::from("T1"),
+            channel: String::from("C1"),
+            expires_at: Date::now().timestamp() - 1,
+        })
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            execute(repo.clone(), Request { token }).await,
+            Err(Error::Unauthorized)
+        ));
+        // No token is issued in its place.
+        assert!(repo.refresh_tokens.lock().unwrap().is_empty());
+    }
+}
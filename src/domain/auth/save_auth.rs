@@ -6,10 +6,13 @@ use crate::repository::{
 };
 
 use crate::domain::entities::Auth;
+use crate::helpers::secret::SecretString;
 
 pub struct Request {
+    /// The team of the workspace, or the enterprise of the organization-wide installs.
     pub team: String,
-    pub access_token: String,
+    pub enterprise: Option<String>,
+    pub access_token: SecretString,
 }
 
 impl From<Request> for Auth {
@@ -17,6 +20,8 @@ impl From<Request> for Auth {
         Self {
             id: 0,
             team: value.team,
+            enterprise: value.enterprise,
+            teams: vec![],
             access_token: value.access_token,
             deleted: false,
         }
@@ -49,7 +54,15 @@ impl From<UpdateError> for Error {
 
 pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Auth, Error> {
     let result = match repo.clone().find_by_team(req.team.clone()).await {
-        Ok(Auth { id, .. }) => repo.update(Auth { id, ..req.into() }).await?,
+        // The teams mapped to an organization-wide install are kept when it is reinstalled.
+        Ok(Auth { id, teams, .. }) => {
+            repo.update(Auth {
+                id,
+                teams,
+                ..req.into()
+            })
+            .await?
+        }
         Err(err) if err == FindError::NotFound => repo.insert(req.into()).await?,
         Err(..) => return Err(Error::Unknown),
     };
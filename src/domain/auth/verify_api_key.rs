@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::domain::helpers::token;
+use crate::repository::{api_key::Repository, errors::FindError};
+
+pub struct Request {
+    pub key: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+    pub user: String,
+    pub team: String,
+    pub channel: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unauthorized,
+    Unknown,
+}
+
+/// Finds the scope of an API key, rejecting the unknown and revoked ones.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match repo.find_key_by_hash(token::hash(&req.key)).await {
+        Ok(key) => Ok(Response {
+            id: key.id,
+            user: key.user,
+            team: key.team,
+            channel: key.channel,
+        }),
+        Err(FindError::NotFound) => Err(Error::Unauthorized),
+        Err(FindError::Unknown) => Err(Error::Unknown),
+    }
+}
@@ -5,6 +5,8 @@ use crate::repository::{auth::Repository, errors::FindError};
 
 pub struct Request {
     pub team: String,
+    /// The Enterprise Grid organization of the team, when it belongs to one.
+    pub enterprise: Option<String>,
 }
 
 #[derive(Debug)]
@@ -22,6 +24,32 @@ impl From<FindError> for Error {
     }
 }
 
+/// Finds the access token of the team, which is the one of its organization when the application
+/// was installed on the whole organization instead of the workspace.
+///
+/// The teams found through their organization are mapped to it, so that they are found without it
+/// afterwards (e.g. by the scheduler).
 pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Auth, Error> {
-    Ok(repo.clone().find_by_team(req.team.clone()).await?)
+    match repo.find_by_team(req.team.clone()).await {
+        Err(FindError::NotFound) => (),
+        result => return Ok(result?),
+    };
+    match repo.find_by_enterprise_team(req.team.clone()).await {
+        Err(FindError::NotFound) => (),
+        result => return Ok(result?),
+    };
+
+    let enterprise = req.enterprise.ok_or(Error::Unauthorized)?;
+    let auth = repo.find_by_team(enterprise).await?;
+    if !auth.is_enterprise_install() {
+        return Err(Error::Unauthorized);
+    }
+    if let Err(err) = repo.add_enterprise_team(auth.id, req.team.clone()).await {
+        log::error!(
+            "could not map team {} to its organization: {:?}",
+            req.team,
+            err
+        );
+    }
+    Ok(auth)
 }
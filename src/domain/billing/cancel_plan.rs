@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{PlanTier, TeamPlan};
+use crate::repository::errors::FindError;
+use crate::repository::plan::Repository;
+
+pub struct Request {
+    pub subscription_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+/// Moves the team of the subscription back to the free plan, once the subscription ends.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamPlan, Error> {
+    let plan = match repo.find_plan_by_subscription(req.subscription_id).await {
+        Ok(plan) => plan,
+        Err(FindError::NotFound) => return Err(Error::NotFound),
+        Err(FindError::Unknown) => return Err(Error::Unknown),
+    };
+
+    repo.save_plan(TeamPlan {
+        tier: PlanTier::Free,
+        subscription_id: None,
+        ..plan
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not cancel plan: {:?}", err);
+        Error::Unknown
+    })
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{PlanTier, TeamPlan};
+use crate::repository::errors::FindError;
+use crate::repository::plan::Repository;
+
+pub struct Request {
+    pub team_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Finds the plan of the team, which is the free one when the team never upgraded.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamPlan, Error> {
+    match repo.find_plan(req.team_id.clone()).await {
+        Ok(plan) => Ok(plan),
+        Err(FindError::NotFound) => Ok(TeamPlan {
+            team_id: req.team_id,
+            tier: PlanTier::Free,
+            customer_id: None,
+            subscription_id: None,
+        }),
+        Err(FindError::Unknown) => Err(Error::Unknown),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cancel_plan;
+pub mod find_plan;
+pub mod upgrade_plan;
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{PlanTier, TeamPlan};
+use crate::repository::plan::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    pub customer_id: Option<String>,
+    pub subscription_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Moves the team to the paid plan, once its subscription is paid.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamPlan, Error> {
+    repo.save_plan(TeamPlan {
+        team_id: req.team_id,
+        tier: PlanTier::Pro,
+        customer_id: req.customer_id,
+        subscription_id: req.subscription_id,
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not upgrade plan: {:?}", err);
+        Error::Unknown
+    })
+}
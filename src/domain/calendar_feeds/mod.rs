@@ -0,0 +1,3 @@
+pub mod reset_feed;
+pub mod share_feed;
+pub mod verify_feed;
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::domain::entities::CalendarFeed;
+use crate::domain::helpers::token;
+use crate::helpers::date::Date;
+use crate::repository::calendar_feed::Repository;
+
+pub struct Request {
+    pub team: String,
+    pub channel: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Replaces the token of the calendar feed of the channel, so that its previous link no longer
+/// works.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<CalendarFeed, Error> {
+    repo.save_feed(CalendarFeed {
+        team: req.team,
+        channel: req.channel,
+        token: token::generate().into(),
+        created_at: Date::now().timestamp(),
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not reset the calendar feed: {:?}", err);
+        Error::Unknown
+    })
+}
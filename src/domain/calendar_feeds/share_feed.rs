@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::domain::entities::CalendarFeed;
+use crate::domain::helpers::token;
+use crate::helpers::date::Date;
+use crate::repository::calendar_feed::Repository;
+
+pub struct Request {
+    pub team: String,
+    pub channel: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Finds the calendar feed of the channel, creating its token the first time it is shared.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<CalendarFeed, Error> {
+    repo.find_or_insert_feed(CalendarFeed {
+        team: req.team,
+        channel: req.channel,
+        token: token::generate().into(),
+        created_at: Date::now().timestamp(),
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not find the calendar feed: {:?}", err);
+        Error::Unknown
+    })
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::domain::helpers::token;
+use crate::repository::{calendar_feed::Repository, errors::FindError};
+
+pub struct Request {
+    pub channel: String,
+    pub token: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unauthorized,
+    Unknown,
+}
+
+/// Verifies the token is the one of the calendar feed of the channel.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    let feed = match repo.find_feed(req.channel).await {
+        Ok(feed) => feed,
+        Err(FindError::NotFound) => return Err(Error::Unauthorized),
+        Err(FindError::Unknown) => return Err(Error::Unknown),
+    };
+    // The hashes are compared, so that the time taken does not hint at the token.
+    if token::hash(&req.token) != token::hash(feed.token.expose()) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::calendar_feeds::{reset_feed, share_feed};
+    use crate::repository::calendar_feed::MemoryRepository;
+
+    use super::*;
+
+    async fn verify(repo: Arc<MemoryRepository>, channel: &str, token: &str) -> Result<(), Error> {
+        execute(
+            repo,
+            Request {
+                channel: String::from(channel),
+                token: String::from(token),
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_verify_feed() {
+        let repo = Arc::new(MemoryRepository::default());
+        let share = || share_feed::Request {
+            team: String::from("T1"),
+            channel: String::from("C1"),
+        };
+        assert_eq!(
+            verify(repo.clone(), "C1", "").await,
+            Err(Error::Unauthorized)
+        );
+
+        // The token is created once, and shared again afterwards.
+        let feed = share_feed::execute(repo.clone(), share()).await.unwrap();
+        let token = feed.token.expose().to_string();
+        let shared = share_feed::execute(repo.clone(), share()).await.unwrap();
+        assert_eq!(shared.token.expose(), token);
+        assert_eq!(verify(repo.clone(), "C1", &token).await, Ok(()));
+        assert_eq!(
+            verify(repo.clone(), "C2", &token).await,
+            Err(Error::Unauthorized)
+        );
+        assert_eq!(
+            verify(repo.clone(), "C1", "other").await,
+            Err(Error::Unauthorized)
+        );
+
+        // The previous token no longer works once reset.
+        let reset = reset_feed::Request {
+            team: String::from("T1"),
+            channel: String::from("C1"),
+        };
+        let feed = reset_feed::execute(repo.clone(), reset).await.unwrap();
+        assert_ne!(feed.token.expose(), token);
+        assert_eq!(
+            verify(repo.clone(), "C1", &token).await,
+            Err(Error::Unauthorized)
+        );
+        assert_eq!(
+            verify(repo.clone(), "C1", feed.token.expose()).await,
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{audit::find_records, date_format::DateFormat, locale::Locale, timezone::Timezone},
+    repository::audit,
+    slack::helpers,
+    views::audit::{view as audit_view, AuditItemView, AuditView},
+};
+
+const AUDIT_LIMIT: i64 = 20;
+
+pub async fn execute(
+    audit_repo: Arc<dyn audit::Repository>,
+    team_id: String,
+    channel: String,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<serde_json::Value, hyper::StatusCode> {
+    let records = match find_records::execute(
+        audit_repo,
+        find_records::Request {
+            team_id,
+            channel,
+            limit: AUDIT_LIMIT,
+        },
+    )
+    .await
+    {
+        Ok(response) => response.data,
+        Err(err) => {
+            return Err(match err {
+                find_records::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            })
+        }
+    };
+
+    Ok(audit_view(AuditView {
+        records: records
+            .into_iter()
+            .map(|record| AuditItemView {
+                user: record.user,
+                action: record.action,
+                event: record.event,
+                date: helpers::fmt_timestamp(record.timestamp, Timezone::UTC, date_format),
+            })
+            .collect(),
+        locale,
+    }))
+}
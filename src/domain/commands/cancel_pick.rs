@@ -3,7 +3,10 @@ use std::sync::Arc;
 use serde_json::Value;
 
 use crate::{
-    domain::events::{cancel_pick, find_event},
+    domain::{
+        events::{cancel_pick, find_event},
+        locale::Locale,
+    },
     repository::event::Repository,
     slack::helpers::send_post,
     views::cancel_pick::{view as cancel_pick_view, CancelPickView},
@@ -15,6 +18,7 @@ pub async fn execute(
     channel_id: String,
     user_id: String,
     response_url: String,
+    locale: Locale,
 ) -> Result<Option<Value>, hyper::StatusCode> {
     let result = match cancel_pick::execute(
         repo.clone(),
@@ -63,6 +67,7 @@ pub async fn execute(
                 event_name: event.name.clone(),
                 channel_id: event.channel,
                 user_id,
+                locale,
             })
             .to_string(),
         ),
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{date_format::DateFormat, events::event_stats, locale::Locale},
+    repository::{event::Repository, pick_history},
+    slack::helpers,
+    views::event_stats::{view as event_stats_view, EventStatsView, ParticipantStatsView},
+};
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    event_id: u32,
+    channel: String,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<serde_json::Value, hyper::StatusCode> {
+    let stats = match event_stats::execute(
+        repo,
+        history_repo,
+        event_stats::Request {
+            event: event_id,
+            channel,
+        },
+    )
+    .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            return Err(match err {
+                event_stats::Error::NotFound => hyper::StatusCode::NOT_FOUND,
+                event_stats::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            })
+        }
+    };
+
+    Ok(event_stats_view(EventStatsView {
+        event_name: stats.name,
+        participants: stats
+            .participants
+            .into_iter()
+            .map(|participant| ParticipantStatsView {
+                user: participant.user,
+                picks: participant.picks,
+                skips: participant.skips,
+                no_shows: participant.no_shows,
+                last_picked: participant.last_picked_at.map(|timestamp| {
+                    helpers::fmt_timestamp(timestamp, stats.timezone.clone(), date_format)
+                }),
+            })
+            .collect(),
+        locale,
+    }))
+}
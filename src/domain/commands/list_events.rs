@@ -1,35 +1,126 @@
 use std::sync::Arc;
 
 use crate::{
-    domain::events::find_all_events, repository::event::Repository, slack::helpers,
+    domain::{
+        date_format::DateFormat,
+        dtos::PageRequest,
+        events::{count_events, find_all_events},
+        locale::Locale,
+    },
+    repository::event::Repository,
+    slack::helpers,
     views::list_events,
 };
 
-impl From<find_all_events::Response> for list_events::ListEventView {
-    fn from(value: find_all_events::Response) -> Self {
-        Self {
-            id: value.id,
-            name: value.name,
-            date: helpers::fmt_timestamp(value.timestamp, value.timezone),
-            repeat: value.repeat.to_string(),
-        }
+fn to_view(
+    value: find_all_events::Response,
+    date_format: DateFormat,
+    locale: Locale,
+) -> list_events::ListEventView {
+    list_events::ListEventView {
+        id: value.id,
+        name: value.name,
+        date: helpers::fmt_slack_date(value.timestamp, value.timezone, date_format),
+        repeat: list_events::repeat_label(locale, &value.repeat),
     }
 }
 
+/// Lists a page of the channel events, starting at the `cursor` position.
 pub async fn execute(
     repo: Arc<dyn Repository>,
     channel: String,
     reached_limit: bool,
+    cursor: usize,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<serde_json::Value, hyper::StatusCode> {
-    let result = match find_all_events::execute(repo, find_all_events::Request { channel }).await {
-        Ok(response) => response.data,
+    let total = match count_events::execute(
+        repo.clone(),
+        count_events::Request {
+            channel: channel.clone(),
+        },
+    )
+    .await
+    {
+        Ok(response) => response.count as usize,
+        Err(err) => {
+            return Err(match err {
+                count_events::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            })
+        }
+    };
+    let (start, page) = find_page(total, cursor);
+
+    let req = find_all_events::Request {
+        channel,
+        page: Some(PageRequest {
+            cursor: start,
+            limit: LIST_EVENTS_PAGE_SIZE,
+        }),
+    };
+    let events = match find_all_events::execute(repo, req).await {
+        Ok(response) => response
+            .data
+            .into_iter()
+            .map(|event| to_view(event, date_format, locale))
+            .collect(),
         Err(err) => {
             return Err(match err {
                 find_all_events::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
             })
         }
     };
-    let events = result.into_iter().map(|event| event.into()).collect();
 
-    return Ok(list_events::view(events, reached_limit));
+    Ok(list_events::view(events, page, reached_limit, locale))
+}
+
+/// Finds the start of the page at the `cursor` position, moving it to the last page when it is
+/// past the end of the list, and the cursors of the pages around it.
+fn find_page(len: usize, cursor: usize) -> (usize, list_events::ListEventsPage) {
+    let last = len.saturating_sub(1) / LIST_EVENTS_PAGE_SIZE * LIST_EVENTS_PAGE_SIZE;
+    let start = cursor.min(last);
+    let page = list_events::ListEventsPage {
+        previous: if start > 0 {
+            Some(start.saturating_sub(LIST_EVENTS_PAGE_SIZE))
+        } else {
+            None
+        },
+        next: if start + LIST_EVENTS_PAGE_SIZE < len {
+            Some(start + LIST_EVENTS_PAGE_SIZE)
+        } else {
+            None
+        },
+    };
+    (start, page)
+}
+
+/// The number of events listed per page, keeping the message under the Slack blocks limit.
+const LIST_EVENTS_PAGE_SIZE: usize = 10;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_page() {
+        let (start, page) = find_page(0, 0);
+        assert_eq!(start, 0);
+        assert_eq!((page.previous, page.next), (None, None));
+
+        let (start, page) = find_page(25, 0);
+        assert_eq!(start, 0);
+        assert_eq!((page.previous, page.next), (None, Some(10)));
+
+        let (start, page) = find_page(25, 10);
+        assert_eq!(start, 10);
+        assert_eq!((page.previous, page.next), (Some(0), Some(20)));
+
+        let (start, page) = find_page(25, 40);
+        assert_eq!(start, 20);
+        assert_eq!((page.previous, page.next), (Some(10), None));
+
+        let (start, page) = find_page(20, 20);
+        assert_eq!(start, 10);
+        assert_eq!((page.previous, page.next), (Some(0), None));
+    }
 }
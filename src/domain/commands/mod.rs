@@ -1,4 +1,7 @@
+pub mod audit_log;
 pub mod cancel_pick;
+pub mod event_stats;
 pub mod list_events;
+pub mod pick_history;
 pub mod pick_participant;
 pub mod repick_participant;
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{date_format::DateFormat, events::find_event, history::find_picks, locale::Locale},
+    repository::{event::Repository, pick_history},
+    slack::helpers,
+    views::pick_history::{view as pick_history_view, PickHistoryItemView, PickHistoryView},
+};
+
+const HISTORY_LIMIT: u32 = 10;
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    event_id: u32,
+    channel: String,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<serde_json::Value, hyper::StatusCode> {
+    let event = match find_event::execute(
+        repo,
+        find_event::Request {
+            id: event_id,
+            channel: channel.clone(),
+        },
+    )
+    .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            return Err(match err {
+                find_event::Error::NotFound => hyper::StatusCode::NOT_FOUND,
+                find_event::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            })
+        }
+    };
+
+    let picks = match find_picks::execute(
+        history_repo,
+        find_picks::Request {
+            event: event_id,
+            channel,
+            limit: HISTORY_LIMIT,
+        },
+    )
+    .await
+    {
+        Ok(response) => response.data,
+        Err(err) => {
+            return Err(match err {
+                find_picks::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            })
+        }
+    };
+
+    Ok(pick_history_view(PickHistoryView {
+        event_id: event.id,
+        event_name: event.name,
+        picks: picks
+            .into_iter()
+            .map(|pick| PickHistoryItemView {
+                user: pick.user,
+                date: helpers::fmt_slack_date(pick.timestamp, event.timezone.clone(), date_format),
+                source: pick.source,
+                replaced: pick.replaced,
+                partner: pick.partner,
+                no_show: pick.no_show,
+            })
+            .collect(),
+        locale,
+    }))
+}
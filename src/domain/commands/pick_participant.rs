@@ -3,27 +3,40 @@ use std::sync::Arc;
 use serde_json::Value;
 
 use crate::{
+    domain::entities::{PickSource, TeamSettings, Visibility},
     domain::events::{find_event, pick_participant},
     repository::event::Repository,
+    repository::pick_history,
     slack::helpers::send_post,
     views::pick_participant::{
         view as pick_participant_view, PickParticipantSource, PickParticipantView,
     },
 };
 
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     event_id: u32,
     channel_id: String,
     user_id: String,
     response_url: String,
     is_skip: bool,
+    settings: &TeamSettings,
 ) -> Result<Option<Value>, hyper::StatusCode> {
     let result = match pick_participant::execute(
         repo.clone(),
+        history_repo,
         pick_participant::Request {
             event: event_id,
             channel: channel_id.clone(),
+            source: if is_skip {
+                PickSource::Skip
+            } else {
+                PickSource::Manual
+            },
+            dry_run: false,
+            skipped: vec![],
         },
     )
     .await
@@ -71,8 +84,17 @@ pub async fn execute(
                 event_name: event.name.clone(),
                 channel_id: event.channel,
                 user_picked_id: result.id,
+                partner_picked_id: result.partner,
                 user_id,
                 left_count,
+                thread_ts: None,
+                replaced: vec![],
+                locale: settings.locale,
+                message: settings.pick_message.clone(),
+                acknowledge_minutes: None,
+                description: event.description,
+                url: event.url,
+                private: event.visibility == Visibility::Direct,
             })
             .to_string(),
         ),
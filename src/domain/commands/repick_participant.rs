@@ -3,8 +3,13 @@ use std::sync::Arc;
 use serde_json::Value;
 
 use crate::{
-    domain::events::{find_event, repick_participant},
+    domain::{
+        entities::{PickSource, TeamSettings, Visibility},
+        events::{find_event, repick_participant},
+        history::find_picks,
+    },
     repository::event::Repository,
+    repository::pick_history,
     slack::helpers::send_post,
     views::pick_participant::{
         view as pick_participant_view, PickParticipantSource, PickParticipantView,
@@ -13,13 +18,16 @@ use crate::{
 
 pub async fn execute(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     event_id: u32,
     channel_id: String,
     user_id: String,
     response_url: String,
+    settings: &TeamSettings,
 ) -> Result<Option<Value>, hyper::StatusCode> {
     let result = match repick_participant::execute(
         repo.clone(),
+        history_repo.clone(),
         repick_participant::Request {
             event: event_id,
             channel: channel_id.clone(),
@@ -40,7 +48,7 @@ pub async fn execute(
         repo,
         find_event::Request {
             id: event_id,
-            channel: channel_id,
+            channel: channel_id.clone(),
         },
     )
     .await
@@ -61,6 +69,8 @@ pub async fn execute(
         left_count
     );
 
+    let replaced = find_replaced(history_repo, event_id, channel_id).await;
+
     send_post(
         &response_url,
         hyper::Body::from(
@@ -69,9 +79,18 @@ pub async fn execute(
                 event_id: event_id,
                 event_name: event.name.clone(),
                 user_picked_id: result.name,
+                partner_picked_id: result.partner,
                 channel_id: event.channel,
                 user_id,
                 left_count,
+                thread_ts: None,
+                replaced,
+                locale: settings.locale,
+                message: settings.pick_message.clone(),
+                acknowledge_minutes: None,
+                description: event.description,
+                url: event.url,
+                private: event.visibility == Visibility::Direct,
             })
             .to_string(),
         ),
@@ -84,3 +103,41 @@ pub async fn execute(
 
     return Ok(None);
 }
+
+/// Finds the participants replaced by the latest consecutive repicks, oldest first, which were
+/// all shown on the same pick message.
+async fn find_replaced(
+    history_repo: Arc<dyn pick_history::Repository>,
+    event_id: u32,
+    channel_id: String,
+) -> Vec<String> {
+    let picks = match find_picks::execute(
+        history_repo,
+        find_picks::Request {
+            event: event_id,
+            channel: channel_id,
+            limit: REPICK_HISTORY_LIMIT,
+        },
+    )
+    .await
+    {
+        Ok(picks) => picks.data,
+        Err(err) => {
+            log::error!(
+                "could not find the repick history of event {}: {:?}",
+                event_id,
+                err
+            );
+            return vec![];
+        }
+    };
+    let mut replaced: Vec<String> = picks
+        .into_iter()
+        .take_while(|pick| pick.source == PickSource::Repick)
+        .filter_map(|pick| pick.replaced)
+        .collect();
+    replaced.reverse();
+    replaced
+}
+
+const REPICK_HISTORY_LIMIT: u32 = 10;
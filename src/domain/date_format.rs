@@ -0,0 +1,187 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// The clock the times of a team are shown with.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Clock {
+    #[default]
+    H24,
+    H12,
+}
+
+impl Clock {
+    pub fn all() -> [Clock; 2] {
+        [Clock::H24, Clock::H12]
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Clock::H24 => "24",
+            Clock::H12 => "12",
+        }
+    }
+
+    /// The `chrono` format of the times (e.g. `14:15` or `2:15 PM`).
+    pub fn time_pattern(&self) -> &'static str {
+        match self {
+            Clock::H24 => "%H:%M",
+            Clock::H12 => "%-I:%M %p",
+        }
+    }
+}
+
+impl TryFrom<String> for Clock {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim_end_matches('h');
+        Clock::all()
+            .into_iter()
+            .find(|clock| clock.code() == value)
+            .ok_or(())
+    }
+}
+
+/// The order the day, month and year of the dates of a team are shown in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum DateOrder {
+    #[default]
+    Ymd,
+    Dmy,
+    Mdy,
+}
+
+impl DateOrder {
+    pub fn all() -> [DateOrder; 3] {
+        [DateOrder::Ymd, DateOrder::Dmy, DateOrder::Mdy]
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            DateOrder::Ymd => "ymd",
+            DateOrder::Dmy => "dmy",
+            DateOrder::Mdy => "mdy",
+        }
+    }
+
+    /// The `chrono` format of the dates (e.g. `2024-08-26`, `26/08/2024` or `08/26/2024`).
+    pub fn date_pattern(&self) -> &'static str {
+        match self {
+            DateOrder::Ymd => "%Y-%m-%d",
+            DateOrder::Dmy => "%d/%m/%Y",
+            DateOrder::Mdy => "%m/%d/%Y",
+        }
+    }
+
+    /// The `chrono` format of the days, spelled out (e.g. `Monday, August 26`).
+    pub fn day_pattern(&self) -> &'static str {
+        match self {
+            DateOrder::Dmy => "%A, %-d %B",
+            DateOrder::Ymd | DateOrder::Mdy => "%A, %B %-d",
+        }
+    }
+
+    /// The `chrono` format of the months (e.g. `2024-08` or `08/2024`).
+    pub fn month_pattern(&self) -> &'static str {
+        match self {
+            DateOrder::Ymd => "%Y-%m",
+            DateOrder::Dmy | DateOrder::Mdy => "%m/%Y",
+        }
+    }
+}
+
+impl TryFrom<String> for DateOrder {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        DateOrder::all()
+            .into_iter()
+            .find(|order| order.code() == value.to_lowercase())
+            .ok_or(())
+    }
+}
+
+/// How the dates and times are shown to a team.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct DateFormat {
+    #[serde(default)]
+    pub clock: Clock,
+    #[serde(default)]
+    pub order: DateOrder,
+}
+
+impl DateFormat {
+    /// Formats the date and time along with the abbreviation of its timezone (e.g.
+    /// `2024-08-26 14:15 CEST`).
+    pub fn datetime<Tz: TimeZone>(&self, datetime: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        datetime
+            .format(&format!(
+                "{} {} %Z",
+                self.order.date_pattern(),
+                self.clock.time_pattern()
+            ))
+            .to_string()
+    }
+
+    /// Tells the handlers the format through a header, as `clock:order` (e.g. `24:ymd`).
+    pub fn code(&self) -> String {
+        format!("{}:{}", self.clock.code(), self.order.code())
+    }
+}
+
+impl TryFrom<String> for DateFormat {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (clock, order) = value.split_once(':').ok_or(())?;
+        Ok(Self {
+            clock: Clock::try_from(clock.to_string())?,
+            order: DateOrder::try_from(order.to_string())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Europe::Lisbon;
+
+    use super::*;
+
+    #[test]
+    fn test_datetime() {
+        let datetime = Lisbon.timestamp_opt(1724678100, 0).unwrap();
+        assert_eq!(
+            DateFormat::default().datetime(&datetime),
+            "2024-08-26 14:15 WEST"
+        );
+        let format = DateFormat {
+            clock: Clock::H12,
+            order: DateOrder::Mdy,
+        };
+        assert_eq!(format.datetime(&datetime), "08/26/2024 2:15 PM WEST");
+        let format = DateFormat {
+            clock: Clock::H24,
+            order: DateOrder::Dmy,
+        };
+        assert_eq!(format.datetime(&datetime), "26/08/2024 14:15 WEST");
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(Clock::try_from(String::from("12h")), Ok(Clock::H12));
+        assert_eq!(DateOrder::try_from(String::from("DMY")), Ok(DateOrder::Dmy));
+        assert_eq!(Clock::try_from(String::from("13")), Err(()));
+
+        let format = DateFormat {
+            clock: Clock::H12,
+            order: DateOrder::Dmy,
+        };
+        assert_eq!(DateFormat::try_from(format.code()), Ok(format));
+        assert_eq!(DateFormat::try_from(String::from("24")), Err(()));
+    }
+}
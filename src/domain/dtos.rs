@@ -5,8 +5,28 @@ pub struct ListResponse<T> {
     pub data: Vec<T>,
 }
 
+/// A page of a list, starting at the `cursor` position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRequest {
+    pub cursor: usize,
+    pub limit: usize,
+}
+
 impl<T> ListResponse<T> {
     pub fn new(data: Vec<T>) -> ListResponse<T> {
         ListResponse { data }
     }
 }
+
+/// A page of a list, along with the cursor of the next page, when there is one.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PageResponse<T> {
+    pub data: Vec<T>,
+    pub next_cursor: Option<usize>,
+}
+
+impl<T> PageResponse<T> {
+    pub fn new(data: Vec<T>, next_cursor: Option<usize>) -> PageResponse<T> {
+        PageResponse { data, next_cursor }
+    }
+}
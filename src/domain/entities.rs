@@ -1,5 +1,10 @@
+use super::date_format::DateFormat;
+use super::helpers::cron::parse_schedule;
+use super::locale::Locale;
 use super::timezone::Timezone;
 use crate::helpers::date::Date;
+use crate::helpers::secret::SecretString;
+use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Weekday};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -20,6 +25,62 @@ pub struct Event {
     pub channel: String,
     pub team_id: String,
     pub deleted: bool,
+    #[serde(default)]
+    pub pick_mode: PickMode,
+    /// Prevents the last picked participant from being picked again right after a cycle resets.
+    #[serde(default)]
+    pub no_repeat_consecutive: bool,
+    #[serde(default)]
+    pub ends_at: Option<i64>,
+    /// Minutes before each pick to post a reminder on the channel.
+    #[serde(default)]
+    pub reminder_minutes: Option<u32>,
+    /// Posts the scheduled picks as thread replies on a pinned anchor message.
+    #[serde(default)]
+    pub anchor: bool,
+    /// The timestamp of the anchor message, once it is posted.
+    #[serde(default)]
+    pub anchor_ts: Option<String>,
+    /// The other channels the picks are announced on.
+    #[serde(default)]
+    pub notify_channels: Vec<String>,
+    /// Set once the event is past its end date, so it is no longer scheduled.
+    #[serde(default)]
+    pub inactive: bool,
+    /// Skips the occurrences on Saturdays and Sundays. Unset for the events created before it
+    /// could be chosen, which skip them only when repeated daily.
+    #[serde(default)]
+    pub skip_weekends: Option<bool>,
+    /// The days (on the event's timezone) on which the event exceptionally does not happen.
+    #[serde(default)]
+    pub skipped_dates: Vec<NaiveDate>,
+    /// The Slack user group whose members are the participants, synced before each automatic
+    /// pick.
+    #[serde(default)]
+    pub user_group: Option<String>,
+    /// Makes the random picks reproducible, drawing them from this seed along with the event ID
+    /// and the day of the pick.
+    #[serde(default)]
+    pub seed: Option<u32>,
+    /// The user who created the event, told when its picks cannot be posted. Unset for the events
+    /// created before it was recorded.
+    #[serde(default)]
+    pub created_by: Option<String>,
+    /// Set when the owner pauses the event, so it is not scheduled until it is edited again.
+    #[serde(default)]
+    pub paused: bool,
+    /// What the participants are picked for (e.g. the agenda of the meeting).
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The link of the event (e.g. the meeting or its agenda).
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Where the picks of the event are announced.
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Skips the participants whose Slack status is out of office on the automatic picks.
+    #[serde(default)]
+    pub skip_out_of_office: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -61,14 +122,44 @@ impl Event {
                         picked: picked(old.cur_pick, i),
                         created_at: old.timestamp,
                         picked_at: picked_at(old.cur_pick, old.prev_pick, i),
+                        weight: None,
+                        unavailable_until: None,
+                        order: None,
                     }
                 })
                 .collect(),
             channel,
             team_id: old.team_id,
             deleted: old.deleted,
+            pick_mode: PickMode::Random,
+            no_repeat_consecutive: false,
+            ends_at: None,
+            reminder_minutes: None,
+            anchor: false,
+            anchor_ts: None,
+            notify_channels: vec![],
+            inactive: false,
+            skip_weekends: None,
+            skipped_dates: vec![],
+            user_group: None,
+            seed: None,
+            created_by: None,
+            paused: false,
+            description: None,
+            url: None,
+            visibility: Visibility::Channel,
+            skip_out_of_office: false,
         }
     }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        self.ends_at.is_some_and(|ends_at| ends_at < now)
+    }
+
+    pub fn skips_weekends(&self) -> bool {
+        self.skip_weekends
+            .unwrap_or(self.repeat == RepeatPeriod::Daily)
+    }
 }
 
 fn picked(cur_pick: u32, index: usize) -> bool {
@@ -118,6 +209,33 @@ pub struct Participant {
     pub picked: bool,
     pub created_at: i64,
     pub picked_at: Option<i64>,
+    #[serde(default)]
+    pub weight: Option<f64>,
+    #[serde(default)]
+    pub unavailable_until: Option<i64>,
+    /// The position of the participant on the rotation of the round-robin events. The
+    /// participants without it follow the ones with it, in the order they were added in.
+    #[serde(default)]
+    pub order: Option<u32>,
+}
+
+/// Weight used for participants without an explicit weight.
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+/// Weight assigned to part-time participants, so they are picked half as often.
+pub const PART_TIME_WEIGHT: f64 = 0.5;
+
+impl Participant {
+    pub fn weight(&self) -> f64 {
+        self.weight.unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    pub fn is_part_time(&self) -> bool {
+        self.weight() == PART_TIME_WEIGHT
+    }
+
+    pub fn is_away(&self, now: i64) -> bool {
+        self.unavailable_until.is_some_and(|until| until > now)
+    }
 }
 
 impl From<String> for Participant {
@@ -127,10 +245,362 @@ impl From<String> for Participant {
             picked: false,
             created_at: Date::now().timestamp(),
             picked_at: None,
+            weight: None,
+            unavailable_until: None,
+            order: None,
         }
     }
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum PickSource {
+    Manual,
+    Auto,
+    Repick,
+    Skip,
+    /// A participant took the pick in place of the picked one.
+    Volunteer,
+}
+
+impl PickSource {
+    pub fn label(&self) -> String {
+        match self {
+            PickSource::Manual => "Manual Pick",
+            PickSource::Auto => "Automatic scheduler",
+            PickSource::Repick => "Repick",
+            PickSource::Skip => "Skip",
+            PickSource::Volunteer => "Volunteer",
+        }
+        .to_string()
+    }
+}
+
+impl Display for PickSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct PickRecord {
+    pub event: u32,
+    pub channel: String,
+    pub user: String,
+    pub timestamp: i64,
+    pub source: PickSource,
+    /// The participant replaced by this pick, when it comes from a repick, a skip or a volunteer.
+    pub replaced: Option<String>,
+    /// The participant picked together with the user, on the events picking pairs.
+    #[serde(default)]
+    pub partner: Option<String>,
+    /// Set when the picked participant did not show up.
+    #[serde(default)]
+    pub no_show: bool,
+}
+
+/// A scheduled pick waiting for its participant to acknowledge it, which is repicked once it
+/// expires.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct PendingAcknowledgment {
+    pub event: u32,
+    pub event_name: String,
+    pub channel: String,
+    pub team_id: String,
+    pub user: String,
+    /// The timestamp of the pick message, which is updated with the repick.
+    pub message_ts: String,
+    /// The timestamp (in seconds) the pick is repicked at when not acknowledged.
+    pub expires_at: i64,
+}
+
+/// An outbound notification (e.g. the message of a scheduled pick) waiting to be posted. It is
+/// kept until it is posted, so that it is not lost on restarts, and retried when it fails.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct NotificationJob {
+    pub id: u32,
+    pub kind: NotificationKind,
+    pub team_id: String,
+    /// The notification, without its access token, which is looked up when it is posted.
+    pub payload: serde_json::Value,
+    pub attempts: u32,
+    /// The timestamp (in seconds) the job is run at, pushed back after each failed attempt.
+    pub run_at: i64,
+    /// The timestamp (in seconds) the worker running the job holds it until.
+    #[serde(default)]
+    pub locked_until: Option<i64>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl HasId for NotificationJob {
+    fn set_id(&mut self, id: u32) {
+        self.id = id;
+    }
+
+    fn get_id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// A notification that could not be posted, either because retrying it would not help (e.g. the
+/// token was revoked or the channel archived) or because it ran out of attempts. It is kept for
+/// the operators to review and re-drive once the cause is solved.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct FailedNotification {
+    /// The ID of the job the notification was queued as.
+    pub id: u32,
+    pub kind: NotificationKind,
+    pub team_id: String,
+    pub payload: serde_json::Value,
+    pub attempts: u32,
+    pub error: String,
+    /// The timestamp (in seconds) of the last attempt.
+    pub failed_at: i64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    Pick,
+    Reminder,
+    Repick,
+    Recap,
+    /// The pick of a private event sent to a participant once their do not disturb window ends.
+    DirectPick,
+}
+
+/// An action performed by a user, recorded to review the activity of the teams.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct AuditRecord {
+    pub team_id: String,
+    /// The channel the action was performed on, unless it concerns the whole team.
+    pub channel: Option<String>,
+    pub user: Option<String>,
+    pub action: AuditAction,
+    pub event: Option<u32>,
+    pub timestamp: i64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum AuditAction {
+    EventCreated,
+    EventUpdated,
+    EventDeleted,
+    Pick,
+    Skip,
+    Repick,
+    CancelPick,
+    SkipOccurrence,
+    EventPaused,
+    Acknowledge,
+    AcknowledgmentExpired,
+    NoShow,
+    Volunteer,
+    Install,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+}
+
+impl AuditAction {
+    pub fn label(&self) -> String {
+        match self {
+            AuditAction::EventCreated => "created the event",
+            AuditAction::EventUpdated => "edited the event",
+            AuditAction::EventDeleted => "deleted the event",
+            AuditAction::Pick => "picked on the event",
+            AuditAction::Skip => "skipped the pick of the event",
+            AuditAction::Repick => "repicked on the event",
+            AuditAction::CancelPick => "cancelled the pick of the event",
+            AuditAction::SkipOccurrence => "skipped the next occurrence of the event",
+            AuditAction::EventPaused => "paused the event",
+            AuditAction::Acknowledge => "acknowledged the pick of the event",
+            AuditAction::AcknowledgmentExpired => "repicked the unacknowledged pick of the event",
+            AuditAction::NoShow => "marked a no-show on the event",
+            AuditAction::Volunteer => "volunteered for the pick of the event",
+            AuditAction::Install => "installed the application",
+            AuditAction::ApiKeyCreated => "created an API key",
+            AuditAction::ApiKeyRevoked => "revoked an API key",
+        }
+        .to_string()
+    }
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A day on which the events of a team are not automatically picked.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Holiday {
+    pub team_id: String,
+    /// The holiday date, formatted as `YYYY-MM-DD`.
+    pub date: String,
+    pub name: String,
+}
+
+impl Holiday {
+    pub fn naive_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.date, HOLIDAY_DATE_FORMAT).ok()
+    }
+}
+
+pub const HOLIDAY_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The settings shared by all the events of a team.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct TeamSettings {
+    pub team_id: String,
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    /// The language of the messages and forms shown to the team.
+    #[serde(default)]
+    pub locale: Locale,
+    /// The clock and the order of the dates shown to the team.
+    #[serde(default)]
+    pub date_format: DateFormat,
+    /// The message the picks of the team are posted with, in place of the default one. Its
+    /// placeholders (e.g. `{user}`) are replaced by the details of each pick.
+    #[serde(default)]
+    pub pick_message: Option<String>,
+    /// The minutes the participants picked by the scheduler have to acknowledge their pick, before
+    /// someone else is picked. The picks need no acknowledgment when it is `None`.
+    #[serde(default)]
+    pub acknowledge_minutes: Option<u32>,
+    /// The limits set by the operators for the team, in place of the ones of its plan.
+    #[serde(default)]
+    pub limits: TeamLimits,
+    /// Posts a recap of the picks of the month on each channel of the team, once it is over.
+    #[serde(default)]
+    pub monthly_recap: bool,
+    /// Increases the weight of the participants marked as no-show, so that they are picked more
+    /// often to make up for it.
+    #[serde(default)]
+    pub no_show_boost: bool,
+}
+
+impl TeamSettings {
+    /// The settings of a team that did not set any of them yet.
+    pub fn new(team_id: String) -> Self {
+        Self {
+            team_id,
+            quiet_hours: None,
+            locale: Locale::default(),
+            date_format: DateFormat::default(),
+            pick_message: None,
+            acknowledge_minutes: None,
+            limits: TeamLimits::default(),
+            monthly_recap: false,
+            no_show_boost: false,
+        }
+    }
+}
+
+/// The limits of a team that differ from the ones of its plan, each of them following the plan
+/// when `None`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct TeamLimits {
+    /// The number of events each channel of the team can have.
+    #[serde(default)]
+    pub max_events: Option<u32>,
+    /// The number of participants each event of the team can have.
+    #[serde(default)]
+    pub max_participants: Option<u32>,
+}
+
+/// A daily time window during which no automatic posts happen.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct QuietHours {
+    /// The minute of the day, in local time, the quiet hours start at.
+    pub start: u32,
+    /// The minute of the day, in local time, the quiet hours end at. It is before `start` when the
+    /// quiet hours span midnight.
+    pub end: u32,
+    pub timezone: Timezone,
+}
+
+impl QuietHours {
+    pub fn contains(&self, timestamp: i64) -> bool {
+        let local = Date::new(timestamp)
+            .with_timezone(self.timezone.clone())
+            .to_datetime();
+        let minute = local.hour() * 60 + local.minute();
+        if self.start <= self.end {
+            self.start <= minute && minute < self.end
+        } else {
+            minute >= self.start || minute < self.end
+        }
+    }
+
+    /// Finds the first timestamp (in seconds), from `timestamp` on, outside the quiet hours.
+    pub fn next_allowed(&self, timestamp: i64) -> i64 {
+        if !self.contains(timestamp) {
+            return timestamp;
+        }
+        let local = Date::new(timestamp)
+            .with_timezone(self.timezone.clone())
+            .to_datetime()
+            .naive_local();
+        let end = NaiveTime::from_hms_opt(self.end / 60, self.end % 60, 0).unwrap_or_default();
+        let mut end_date = local.date();
+        if local.time() >= end {
+            end_date = end_date.succ_opt().unwrap_or(end_date);
+        }
+        // Ends skipped by a daylight saving time transition are moved forward one hour.
+        let tz = self.timezone.tz();
+        let end = end_date.and_time(end);
+        tz.from_local_datetime(&end)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(end + Duration::hours(1)))
+                    .earliest()
+            })
+            .map_or(timestamp, |datetime| datetime.timestamp())
+    }
+}
+
+impl Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02} - {:02}:{:02} ({})",
+            self.start / 60,
+            self.start % 60,
+            self.end / 60,
+            self.end % 60,
+            self.timezone
+        )
+    }
+}
+
+/// The subscription plan of a team, deciding how many events it can create on each channel.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct TeamPlan {
+    pub team_id: String,
+    pub tier: PlanTier,
+    /// The Stripe customer paying for the plan.
+    #[serde(default)]
+    pub customer_id: Option<String>,
+    /// The Stripe subscription of the plan, ending it once cancelled.
+    #[serde(default)]
+    pub subscription_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum PlanTier {
+    Free,
+    Pro,
+}
+
+/// The next occurrence of an event, as computed by the scheduler.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct ScheduledEvent {
+    pub event: u32,
+    /// The timestamp (in seconds) of the next occurrence.
+    pub next_at: i64,
+}
+
 pub struct EventPick {
     pub event: u32,
     pub cur_pick: u32,
@@ -176,8 +646,17 @@ pub enum RepeatPeriod {
     Weekly(i32),
     Monthly(i32),
     Yearly,
+    /// Repeats every week on the given weekdays.
+    Days(Vec<Weekday>),
+    /// Repeats following a cron expression, evaluated in the event's timezone.
+    Cron(String),
 }
 
+/// Prefix used to serialize the cron expression of a `RepeatPeriod::Cron`.
+pub const CRON_PREFIX: &str = "cron:";
+/// Prefix used to serialize the weekdays of a `RepeatPeriod::Days`.
+pub const DAYS_PREFIX: &str = "days:";
+
 impl RepeatPeriod {
     pub fn label(&self) -> String {
         match self {
@@ -187,6 +666,17 @@ impl RepeatPeriod {
             RepeatPeriod::Monthly(1) => "Monthly",
             RepeatPeriod::Monthly(2) => "Bi-monthly",
             RepeatPeriod::Yearly => "Yearly",
+            RepeatPeriod::Days(weekdays) => {
+                return format!(
+                    "Every {}",
+                    weekdays
+                        .iter()
+                        .map(|weekday| weekday.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            }
+            RepeatPeriod::Cron(expression) => return format!("Custom ({})", expression),
             _ => "None",
         }
         .to_string()
@@ -218,6 +708,26 @@ impl TryFrom<String> for RepeatPeriod {
             "monthly" => Ok(RepeatPeriod::Monthly(1)),
             "monthly_two" => Ok(RepeatPeriod::Monthly(2)),
             "yearly" => Ok(RepeatPeriod::Yearly),
+            value if value.starts_with(DAYS_PREFIX) => {
+                let mut weekdays = value[DAYS_PREFIX.len()..]
+                    .split(',')
+                    .map(|weekday| weekday.trim().parse::<Weekday>())
+                    .collect::<Result<Vec<Weekday>, _>>()
+                    .map_err(|err| log::trace!("invalid weekdays {}: {}", value, err))?;
+                if weekdays.is_empty() {
+                    return Err(());
+                }
+                weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+                weekdays.dedup();
+                Ok(RepeatPeriod::Days(weekdays))
+            }
+            value if value.starts_with(CRON_PREFIX) => {
+                let expression = value[CRON_PREFIX.len()..].trim();
+                parse_schedule(expression).map_err(|err| {
+                    log::trace!("invalid cron expression {}: {}", expression, err);
+                })?;
+                Ok(RepeatPeriod::Cron(expression.to_string()))
+            }
             _ => Err(()),
         }
     }
@@ -235,6 +745,18 @@ impl TryFrom<RepeatPeriod> for String {
             RepeatPeriod::Monthly(1) => "monthly",
             RepeatPeriod::Monthly(2) => "monthly_two",
             RepeatPeriod::Yearly => "yearly",
+            RepeatPeriod::Days(weekdays) => {
+                return Ok(format!(
+                    "{}{}",
+                    DAYS_PREFIX,
+                    weekdays
+                        .iter()
+                        .map(|weekday| weekday.to_string().to_lowercase())
+                        .collect::<Vec<String>>()
+                        .join(",")
+                ))
+            }
+            RepeatPeriod::Cron(expression) => return Ok(format!("{}{}", CRON_PREFIX, expression)),
             _ => return Err(format!("Invalid RepeatPeriod: {:?}", value)),
         }
         .to_string())
@@ -247,14 +769,138 @@ impl Display for RepeatPeriod {
     }
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub enum PickMode {
+    #[default]
+    Random,
+    /// Picks the participants in a fixed order, following the order they were added in.
+    RoundRobin,
+    /// Picks two participants together, not repeating a pair until all of them were picked.
+    Pairs,
+}
+
+impl PickMode {
+    pub fn label(&self) -> String {
+        match self {
+            PickMode::Random => "Random",
+            PickMode::RoundRobin => "Round-robin",
+            PickMode::Pairs => "Pairs",
+        }
+        .to_string()
+    }
+}
+
+impl TryFrom<String> for PickMode {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "random" => Ok(PickMode::Random),
+            "round_robin" => Ok(PickMode::RoundRobin),
+            "pairs" => Ok(PickMode::Pairs),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<PickMode> for String {
+    fn from(value: PickMode) -> Self {
+        match value {
+            PickMode::Random => "random",
+            PickMode::RoundRobin => "round_robin",
+            PickMode::Pairs => "pairs",
+        }
+        .to_string()
+    }
+}
+
+impl Display for PickMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Where the picks of an event are announced.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub enum Visibility {
+    /// The picks are posted on the channel of the event.
+    #[default]
+    Channel,
+    /// The picks are only sent by direct message to the participants, for the sensitive rotations
+    /// (e.g. the backups of the incident commanders).
+    Direct,
+}
+
+impl Visibility {
+    pub fn label(&self) -> String {
+        match self {
+            Visibility::Channel => "Channel",
+            Visibility::Direct => "Direct messages",
+        }
+        .to_string()
+    }
+}
+
+impl TryFrom<String> for Visibility {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "channel" => Ok(Visibility::Channel),
+            "direct" => Ok(Visibility::Direct),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Visibility> for String {
+    fn from(value: Visibility) -> Self {
+        match value {
+            Visibility::Channel => "channel",
+            Visibility::Direct => "direct",
+        }
+        .to_string()
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// The access token of a workspace, or of a whole Enterprise Grid organization when the
+/// application is installed on it.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Auth {
     pub id: u32,
+    /// The team of the workspace, or the enterprise of the organization-wide installs.
     pub team: String,
-    pub access_token: String,
+    /// The enterprise the workspace belongs to, or the organization of the organization-wide
+    /// installs.
+    #[serde(default)]
+    pub enterprise: Option<String>,
+    /// The teams of the organization the organization-wide installs were used on.
+    #[serde(default)]
+    pub teams: Vec<String>,
+    pub access_token: SecretString,
     pub deleted: bool,
 }
 
+impl Auth {
+    /// Whether the token was issued for the whole organization instead of a single workspace.
+    pub fn is_enterprise_install(&self) -> bool {
+        self.enterprise.as_ref() == Some(&self.team)
+    }
+
+    /// The teams the token can be used for.
+    pub fn team_ids(&self) -> Vec<String> {
+        let mut teams = vec![self.team.clone()];
+        teams.extend(self.teams.iter().cloned());
+        teams
+    }
+}
+
 impl HasId for Auth {
     fn set_id(&mut self, id: u32) {
         self.id = id;
@@ -269,8 +915,130 @@ impl Display for Auth {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "team={}, access_token={}, deleted={}",
-            self.team, self.access_token, self.deleted
+            "team={}, enterprise={:?}, access_token={}, deleted={}",
+            self.team, self.enterprise, self.access_token, self.deleted
         )
     }
 }
+
+/// A refresh token of the API, stored by the hash of its value.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RefreshToken {
+    pub hash: String,
+    pub user: String,
+    pub team: String,
+    pub channel: String,
+    pub expires_at: i64,
+}
+
+/// An API key of a team, authenticating the machine clients on the channel it was created on.
+/// It is stored by the hash of its value.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKey {
+    pub id: u32,
+    pub name: String,
+    pub hash: String,
+    /// The user that created the key, who the actions performed with it are recorded for.
+    pub user: String,
+    pub team: String,
+    pub channel: String,
+    pub created_at: i64,
+}
+
+impl HasId for ApiKey {
+    fn set_id(&mut self, id: u32) {
+        self.id = id;
+    }
+
+    fn get_id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// The services the users can connect to the app.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum IntegrationProvider {
+    /// Their Google calendar, whose out of office events skip them on the automatic picks.
+    GoogleCalendar,
+}
+
+impl From<IntegrationProvider> for String {
+    fn from(value: IntegrationProvider) -> Self {
+        match value {
+            IntegrationProvider::GoogleCalendar => String::from("GoogleCalendar"),
+        }
+    }
+}
+
+/// A service a user connected to the app, with the tokens to access it on their behalf.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Integration {
+    pub team: String,
+    pub user: String,
+    pub provider: IntegrationProvider,
+    pub access_token: SecretString,
+    /// Renews the access token once it expires.
+    pub refresh_token: SecretString,
+    /// When the access token expires.
+    pub expires_at: i64,
+    pub created_at: i64,
+}
+
+impl Integration {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// The token of the calendar feed of a channel, shared on its subscription link.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CalendarFeed {
+    pub team: String,
+    pub channel: String,
+    pub token: SecretString,
+    pub created_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quiet_hours(start: u32, end: u32) -> QuietHours {
+        QuietHours {
+            start,
+            end,
+            timezone: Timezone::EST,
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_contains() {
+        // 2023-06-01 07:30 EDT
+        let morning = 1685619000;
+        // 2023-06-01 12:00 EDT
+        let noon = 1685635200;
+        // 2023-06-01 20:00 EDT
+        let night = 1685664000;
+
+        let overnight = quiet_hours(19 * 60, 8 * 60);
+        assert!(overnight.contains(morning));
+        assert!(!overnight.contains(noon));
+        assert!(overnight.contains(night));
+
+        let lunch = quiet_hours(12 * 60, 13 * 60);
+        assert!(!lunch.contains(morning));
+        assert!(lunch.contains(noon));
+        assert!(!lunch.contains(night));
+    }
+
+    #[test]
+    fn test_quiet_hours_next_allowed() {
+        let overnight = quiet_hours(19 * 60, 8 * 60);
+        // 2023-06-01 12:00 EDT stays as it is.
+        assert_eq!(overnight.next_allowed(1685635200), 1685635200);
+        // 2023-06-01 07:30 EDT moves to 2023-06-01 08:00 EDT.
+        assert_eq!(overnight.next_allowed(1685619000), 1685620800);
+        // 2023-06-01 20:00 EDT moves to 2023-06-02 08:00 EDT.
+        assert_eq!(overnight.next_allowed(1685664000), 1685707200);
+    }
+}
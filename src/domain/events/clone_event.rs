@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::domain::entities::{Event, Participant, RepeatPeriod};
+use crate::domain::timezone::Timezone;
+use crate::repository::errors::FindError;
+use crate::repository::event::Repository;
+
+use super::create_event;
+
+pub struct Request {
+    pub id: u32,
+    pub channel: String,
+    pub team_id: String,
+    pub max_events: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub timestamp: i64,
+    pub timezone: Timezone,
+    pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+    pub team_id: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    Forbidden,
+    Unknown,
+}
+
+/// Creates a copy of an event on its channel, with the same participants and schedule. The copy
+/// is named after the event and starts without any picks.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    create_event::validate_channels_count(
+        repo.clone(),
+        req.channel.clone(),
+        req.team_id.clone(),
+        req.max_events,
+    )
+    .await
+    .map_err(|err| match err {
+        create_event::Error::Forbidden => Error::Forbidden,
+        _ => Error::Unknown,
+    })?;
+
+    let event = repo
+        .find_event(req.id, req.channel.clone())
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    let names: Vec<String> = repo
+        .find_all_events(req.channel.clone())
+        .await
+        .map_err(|err| {
+            log::error!(
+                "could not find events of channel {}: {:?}",
+                req.channel,
+                err
+            );
+            Error::Unknown
+        })?
+        .into_iter()
+        .map(|event| event.name)
+        .collect();
+
+    let copy = Event {
+        id: 0,
+        name: copy_name(&event.name, &names),
+        participants: event
+            .participants
+            .into_iter()
+            .map(|participant| Participant {
+                weight: participant.weight,
+                unavailable_until: participant.unavailable_until,
+                order: participant.order,
+                ..participant.user.into()
+            })
+            .collect(),
+        team_id: req.team_id,
+        deleted: false,
+        anchor_ts: None,
+        inactive: false,
+        paused: false,
+        ..event
+    };
+
+    let skip_weekends = copy.skips_weekends();
+    match repo.insert_event(copy).await {
+        Ok(Event {
+            id,
+            name,
+            timestamp,
+            timezone,
+            repeat,
+            ends_at,
+            reminder_minutes,
+            skipped_dates,
+            team_id,
+            ..
+        }) => Ok(Response {
+            id,
+            name,
+            timestamp,
+            timezone,
+            repeat,
+            ends_at,
+            reminder_minutes,
+            skip_weekends,
+            skipped_dates,
+            team_id,
+        }),
+        Err(err) => {
+            log::error!("could not clone event {}: {:?}", req.id, err);
+            Err(Error::Unknown)
+        }
+    }
+}
+
+/// Suffixes the name, numbering the copy when the name of a previous one is already taken.
+fn copy_name(name: &str, names: &[String]) -> String {
+    let mut copy = format!("{} (copy)", name);
+    let mut count = 1;
+    while names.contains(&copy) {
+        count += 1;
+        copy = format!("{} (copy {})", name, count);
+    }
+    copy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_name() {
+        assert_eq!(copy_name("Daily", &[]), "Daily (copy)");
+        assert_eq!(
+            copy_name(
+                "Daily",
+                &[String::from("Daily"), String::from("Daily (copy)")]
+            ),
+            "Daily (copy 2)"
+        );
+        assert_eq!(
+            copy_name(
+                "Daily",
+                &[String::from("Daily (copy)"), String::from("Daily (copy 2)")]
+            ),
+            "Daily (copy 3)"
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::repository::{errors::CountError, event};
 
 pub struct Request {
-    channel: String,
+    pub channel: String,
 }
 
 pub struct Response {
@@ -1,14 +1,18 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_trim::{string_trim, vec_string_trim};
 
-use crate::domain::entities::{Event, RepeatPeriod};
-use crate::domain::helpers::team::is_team_special;
+use crate::domain::entities::{Event, Participant, PickMode, RepeatPeriod, Visibility};
 use crate::domain::timezone::Timezone;
+use crate::helpers::date::Date;
 use crate::repository::errors::{FindError, InsertError};
 use crate::repository::event::Repository;
 
+use super::validation::{self, FieldError};
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct Request {
     #[serde(deserialize_with = "string_trim")]
@@ -18,12 +22,48 @@ pub struct Request {
     pub repeat: String,
     #[serde(deserialize_with = "vec_string_trim")]
     pub participants: Vec<String>,
+    #[serde(default)]
+    pub weights: HashMap<String, f64>,
+    #[serde(default)]
+    pub pick_mode: Option<String>,
+    #[serde(default)]
+    pub no_repeat_consecutive: bool,
+    #[serde(default)]
+    pub ends_at: Option<i64>,
+    #[serde(default)]
+    pub reminder_minutes: Option<u32>,
+    #[serde(default)]
+    pub anchor: bool,
+    #[serde(default)]
+    pub notify_channels: Vec<String>,
+    /// Defaults to skipping the weekends only when the event is repeated daily.
+    #[serde(default)]
+    pub skip_weekends: Option<bool>,
+    /// The ID of the Slack user group the participants are synced with.
+    #[serde(default)]
+    pub user_group: Option<String>,
+    /// The seed of the random picks, making them reproducible.
+    #[serde(default)]
+    pub seed: Option<u32>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub skip_out_of_office: bool,
     #[serde(skip_deserializing)]
     pub channel: String,
     #[serde(skip_deserializing)]
     pub team_id: String,
+    /// The user creating the event.
+    #[serde(skip_deserializing)]
+    pub created_by: Option<String>,
     #[serde(skip_deserializing)]
     pub max_events: u32,
+    #[serde(skip_deserializing)]
+    pub max_participants: u32,
 }
 
 #[derive(Serialize, Debug)]
@@ -32,17 +72,44 @@ pub struct Response {
     pub timestamp: i64,
     pub timezone: Timezone,
     pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+    pub team_id: String,
 }
 
 #[derive(PartialEq, Debug)]
 pub enum Error {
-    BadRequest,
+    /// The fields of the event that are not valid.
+    Invalid(Vec<FieldError>),
     Forbidden,
     Conflict,
     Unknown,
 }
 
-pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+pub async fn execute(repo: Arc<dyn Repository>, mut req: Request) -> Result<Response, Error> {
+    req.description = optional_text(req.description);
+    req.url = optional_text(req.url);
+    let fields = validation::Fields {
+        name: &req.name,
+        timestamp: req.timestamp,
+        timezone: &req.timezone,
+        repeat: &req.repeat,
+        participants: &req.participants,
+        max_participants: req.max_participants,
+        pick_mode: req.pick_mode.as_deref(),
+        ends_at: req.ends_at,
+        reminder_minutes: req.reminder_minutes,
+        description: req.description.as_deref(),
+        url: req.url.as_deref(),
+        visibility: req.visibility.as_deref(),
+    };
+    if let Err(errors) = validation::validate(&fields, Some(Date::now().timestamp())) {
+        log::trace!("could not add event with name {}: {:?}", req.name, errors);
+        return Err(Error::Invalid(errors));
+    }
+
     validate_channels_count(
         repo.clone(),
         req.channel.clone(),
@@ -73,33 +140,72 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
         name: req.name.clone(),
         timestamp: req.timestamp,
         timezone: Timezone::from(req.timezone.clone()),
-        repeat: RepeatPeriod::try_from(req.repeat.clone()).map_err(|err| {
-            log::trace!("could not parse repeat period {}: {:?}", req.repeat, err);
-            Error::BadRequest
-        })?,
+        repeat: RepeatPeriod::try_from(req.repeat.clone())
+            .map_err(|_| Error::Invalid(vec![FieldError::new("repeat", "unknown frequency")]))?,
         participants: vec![],
-        channel: req.channel,
+        channel: req.channel.clone(),
         team_id: req.team_id.clone(),
         deleted: false,
+        pick_mode: match req.pick_mode {
+            Some(pick_mode) => PickMode::try_from(pick_mode).map_err(|_| {
+                Error::Invalid(vec![FieldError::new("pick_mode", "unknown pick mode")])
+            })?,
+            None => PickMode::default(),
+        },
+        no_repeat_consecutive: req.no_repeat_consecutive,
+        ends_at: req.ends_at,
+        reminder_minutes: req.reminder_minutes,
+        anchor: req.anchor,
+        anchor_ts: None,
+        notify_channels: notify_channels(req.notify_channels, &req.channel),
+        inactive: false,
+        skip_weekends: req.skip_weekends,
+        skipped_dates: vec![],
+        user_group: req.user_group,
+        seed: req.seed,
+        created_by: req.created_by,
+        paused: false,
+        description: req.description,
+        url: req.url,
+        visibility: match req.visibility {
+            Some(visibility) => Visibility::try_from(visibility).map_err(|_| {
+                Error::Invalid(vec![FieldError::new("visibility", "unknown visibility")])
+            })?,
+            None => Visibility::default(),
+        },
+        skip_out_of_office: req.skip_out_of_office,
     };
     event.participants = req
         .participants
         .into_iter()
-        .map(|user| user.into())
+        .map(|user| Participant {
+            weight: req.weights.get(&user).copied(),
+            ..user.into()
+        })
         .collect();
 
+    let skip_weekends = event.skips_weekends();
     match repo.insert_event(event).await {
         Ok(Event {
                id,
                timestamp,
                timezone,
                repeat,
+               ends_at,
+               reminder_minutes,
+               skipped_dates,
+               team_id,
                ..
            }) => Ok(Response {
             id,
             timestamp,
             timezone,
             repeat,
+            ends_at,
+            reminder_minutes,
+            skip_weekends,
+            skipped_dates,
+            team_id,
         }),
         Err(err) => Err(match err {
             InsertError::Conflict => Error::Conflict,
@@ -108,27 +214,40 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
     }
 }
 
-async fn validate_channels_count(
+/// Removes the duplicated channels and the channel of the event, which always has the picks.
+pub fn notify_channels(channels: Vec<String>, channel: &str) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+    for notify_channel in channels {
+        if notify_channel != channel && !result.contains(&notify_channel) {
+            result.push(notify_channel);
+        }
+    }
+    result
+}
+
+/// Trims the optional texts of the events, leaving out the empty ones.
+pub fn optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) async fn validate_channels_count(
     repo: Arc<dyn Repository>,
     channel: String,
     team_id: String,
     max_events: u32,
 ) -> Result<(), Error> {
-    if is_team_special(team_id.clone()) {
-        log::trace!(
-            "skipping channels count validation for special team {}",
-            team_id
-        );
-        return Ok(());
-    }
     let count = repo.count_events(channel.clone()).await.map_err(|err| {
         log::error!("counting events for channel {} failed: {:?}", channel, err);
         Error::Unknown
     })?;
-    if count == max_events {
+    // The limits of the team can be lowered below the events it already has.
+    if count >= max_events {
         log::warn!(
-            "could not add more events on channel {}: max channels {} reached",
+            "could not add more events on channel {} of team {}: max events {} reached",
             channel,
+            team_id,
             max_events
         );
         return Err(Error::Forbidden);
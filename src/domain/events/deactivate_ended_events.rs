@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::helpers::date::Date;
+use crate::repository::errors::FindAllError;
+use crate::repository::event::Repository;
+
+#[derive(Serialize, Debug)]
+pub struct Response {
+    pub events: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>) -> Result<Response, Error> {
+    let events = repo
+        .find_all_events_unprotected()
+        .await
+        .map_err(|err| match err {
+            FindAllError::Unknown => Error::Unknown,
+        })?;
+
+    let now = Date::now().timestamp();
+    let mut deactivated = vec![];
+    for mut event in events
+        .into_iter()
+        .filter(|event| !event.inactive && event.has_ended(now))
+    {
+        let id = event.id;
+        event.inactive = true;
+        if let Err(err) = repo.update_event(event).await {
+            log::error!("could not deactivate ended event {}: {:?}", id, err);
+            continue;
+        }
+        deactivated.push(id);
+    }
+
+    Ok(Response {
+        events: deactivated,
+    })
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::entities::{PickRecord, PickSource};
+use crate::domain::timezone::Timezone;
+use crate::repository::errors::{FindAllError, FindError};
+use crate::repository::{event, pick_history};
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub timezone: Timezone,
+    pub participants: Vec<ParticipantStats>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ParticipantStats {
+    pub user: String,
+    pub picks: u32,
+    pub skips: u32,
+    pub no_shows: u32,
+    pub last_picked_at: Option<i64>,
+}
+
+impl From<String> for ParticipantStats {
+    fn from(user: String) -> Self {
+        Self {
+            user,
+            picks: 0,
+            skips: 0,
+            no_shows: 0,
+            last_picked_at: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let event = event_repo
+        .find_event(req.event, req.channel.clone())
+        .await
+        .map_err(|error| match error {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+
+    let picks = history_repo
+        .find_all_picks(req.event, req.channel)
+        .await
+        .map_err(|error| match error {
+            FindAllError::Unknown => Error::Unknown,
+        })?;
+
+    Ok(Response {
+        id: event.id,
+        name: event.name,
+        timezone: event.timezone,
+        participants: aggregate(
+            event.participants.into_iter().map(|p| p.user).collect(),
+            &picks,
+        ),
+    })
+}
+
+/// Aggregates the pick history per participant, sorted by the most picked first.
+///
+/// Picks reverted by a repick are not counted for the replaced participant, while skipped picks are
+/// counted both as a pick and as a skip. The pairs count as a pick for both of their participants.
+/// The picks the participants did not show up for still count as picks.
+fn aggregate(participants: Vec<String>, picks: &[PickRecord]) -> Vec<ParticipantStats> {
+    let mut stats: HashMap<String, ParticipantStats> = participants
+        .iter()
+        .map(|user| (user.clone(), user.clone().into()))
+        .collect();
+
+    for pick in picks.iter() {
+        for user in std::iter::once(&pick.user).chain(pick.partner.as_ref()) {
+            if let Some(participant) = stats.get_mut(user) {
+                participant.picks += 1;
+                participant.last_picked_at = participant.last_picked_at.max(Some(pick.timestamp));
+            }
+        }
+        if pick.no_show {
+            if let Some(participant) = stats.get_mut(&pick.user) {
+                participant.no_shows += 1;
+            }
+        }
+        let replaced = match pick.replaced.as_ref().and_then(|user| stats.get_mut(user)) {
+            Some(replaced) => replaced,
+            None => continue,
+        };
+        match pick.source {
+            PickSource::Repick | PickSource::Volunteer => {
+                replaced.picks = replaced.picks.saturating_sub(1)
+            }
+            PickSource::Skip => replaced.skips += 1,
+            PickSource::Manual | PickSource::Auto => (),
+        }
+    }
+
+    let mut result: Vec<ParticipantStats> = participants
+        .into_iter()
+        .filter_map(|user| stats.remove(&user))
+        .collect();
+    result.sort_by_key(|stats| std::cmp::Reverse(stats.picks));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        user: &str,
+        timestamp: i64,
+        source: PickSource,
+        replaced: Option<&str>,
+    ) -> PickRecord {
+        PickRecord {
+            event: 1,
+            channel: String::from("C04PGARU4K1"),
+            user: user.to_string(),
+            timestamp,
+            source,
+            replaced: replaced.map(|user| user.to_string()),
+            partner: None,
+            no_show: false,
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let participants = vec![
+            String::from("U04PGARU4K1"),
+            String::from("U0797QD5AJZ"),
+            String::from("USLACKBOT"),
+        ];
+        let picks = vec![
+            record("U04PGARU4K1", 1724681700, PickSource::Auto, None),
+            record(
+                "U0797QD5AJZ",
+                1724681760,
+                PickSource::Skip,
+                Some("U04PGARU4K1"),
+            ),
+            record("U04PGARU4K1", 1724681820, PickSource::Manual, None),
+            record(
+                "USLACKBOT",
+                1724681880,
+                PickSource::Repick,
+                Some("U04PGARU4K1"),
+            ),
+            PickRecord {
+                no_show: true,
+                ..record("USLACKBOT", 1724681940, PickSource::Auto, None)
+            },
+        ];
+
+        let result = aggregate(participants, &picks);
+
+        assert_eq!(
+            result,
+            vec![
+                ParticipantStats {
+                    user: String::from("USLACKBOT"),
+                    picks: 2,
+                    skips: 0,
+                    no_shows: 1,
+                    last_picked_at: Some(1724681940),
+                },
+                ParticipantStats {
+                    user: String::from("U04PGARU4K1"),
+                    picks: 1,
+                    skips: 1,
+                    no_shows: 0,
+                    last_picked_at: Some(1724681820),
+                },
+                ParticipantStats {
+                    user: String::from("U0797QD5AJZ"),
+                    picks: 1,
+                    skips: 0,
+                    no_shows: 0,
+                    last_picked_at: Some(1724681760),
+                },
+            ]
+        );
+    }
+}
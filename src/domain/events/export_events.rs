@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::repository::errors::FindAllError;
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub channel: String,
+}
+
+/// The snapshot of the events of a channel, in the format they are imported from.
+#[derive(Serialize, Debug)]
+pub struct Response {
+    pub version: u32,
+    pub events: Vec<ExportedEvent>,
+}
+
+/// The settings of an event, without its picks. Its fields are the ones of the requests creating
+/// the events.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ExportedEvent {
+    pub name: String,
+    pub timestamp: i64,
+    pub timezone: String,
+    pub repeat: String,
+    pub participants: Vec<String>,
+    pub weights: HashMap<String, f64>,
+    pub pick_mode: Option<String>,
+    pub no_repeat_consecutive: bool,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub anchor: bool,
+    pub notify_channels: Vec<String>,
+    pub skip_weekends: bool,
+    pub user_group: Option<String>,
+    pub seed: Option<u32>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub visibility: Option<String>,
+    pub skip_out_of_office: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let events = repo
+        .find_all_events(req.channel.clone())
+        .await
+        .map_err(|err| match err {
+            FindAllError::Unknown => Error::Unknown,
+        })?;
+
+    let mut exported = vec![];
+    for event in events {
+        let skip_weekends = event.skips_weekends();
+        let repeat = match String::try_from(event.repeat) {
+            Ok(repeat) => repeat,
+            Err(err) => {
+                log::warn!("could not export event {}: {}", event.id, err);
+                continue;
+            }
+        };
+        exported.push(ExportedEvent {
+            name: event.name,
+            timestamp: event.timestamp,
+            timezone: event.timezone.into(),
+            repeat,
+            weights: event
+                .participants
+                .iter()
+                .filter_map(|participant| Some((participant.user.clone(), participant.weight?)))
+                .collect(),
+            participants: event
+                .participants
+                .into_iter()
+                .map(|participant| participant.user)
+                .collect(),
+            pick_mode: Some(event.pick_mode.into()),
+            no_repeat_consecutive: event.no_repeat_consecutive,
+            ends_at: event.ends_at,
+            reminder_minutes: event.reminder_minutes,
+            anchor: event.anchor,
+            notify_channels: event.notify_channels,
+            skip_weekends,
+            user_group: event.user_group,
+            seed: event.seed,
+            description: event.description,
+            url: event.url,
+            visibility: Some(event.visibility.into()),
+            skip_out_of_office: event.skip_out_of_office,
+        });
+    }
+
+    Ok(Response {
+        version: SNAPSHOT_VERSION,
+        events: exported,
+    })
+}
+
+/// The version of the snapshots format, increased whenever it changes in a breaking way.
+pub const SNAPSHOT_VERSION: u32 = 1;
@@ -1,15 +1,18 @@
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use serde::Serialize;
 
-use crate::domain::dtos::ListResponse;
+use crate::domain::dtos::{ListResponse, PageRequest};
 use crate::domain::entities::{Participant, RepeatPeriod};
 use crate::domain::timezone::Timezone;
 use crate::repository::errors::FindAllError;
-use crate::repository::event::Repository;
+use crate::repository::event::{EventFilter, Repository};
 
 pub struct Request {
     pub channel: String,
+    /// The page of events to find. All the events are found when not set.
+    pub page: Option<PageRequest>,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -19,6 +22,9 @@ pub struct Response {
     pub timestamp: i64,
     pub timezone: Timezone,
     pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
     pub participants: Vec<Participant>,
 }
 
@@ -31,7 +37,14 @@ pub async fn execute(
     repo: Arc<dyn Repository>,
     req: Request,
 ) -> Result<ListResponse<Response>, Error> {
-    let events = match repo.find_all_events(req.channel).await {
+    let (cursor, limit) = match req.page {
+        Some(page) => (page.cursor as u64, Some(page.limit as u64)),
+        None => (0, None),
+    };
+    let events = match repo
+        .find_events_page(req.channel, cursor, limit, EventFilter::default())
+        .await
+    {
         Err(err) => {
             return match err {
                 FindAllError::Unknown => Err(Error::Unknown),
@@ -43,11 +56,14 @@ pub async fn execute(
         events
             .into_iter()
             .map(|event| Response {
+                skip_weekends: event.skips_weekends(),
                 id: event.id,
                 name: event.name,
                 timestamp: event.timestamp,
                 timezone: event.timezone,
                 repeat: event.repeat,
+                ends_at: event.ends_at,
+                skipped_dates: event.skipped_dates,
                 participants: event.participants,
             })
             .collect(),
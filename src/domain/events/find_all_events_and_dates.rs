@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use serde::Serialize;
 
 use crate::domain::dtos::ListResponse;
@@ -14,6 +15,11 @@ pub struct Response {
     pub timestamp: i64,
     pub timezone: Timezone,
     pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+    pub team_id: String,
 }
 
 #[derive(Debug)]
@@ -34,11 +40,17 @@ pub async fn execute(repo: Arc<dyn Repository>) -> Result<ListResponse<Response>
     Ok(ListResponse::new(
         events
             .into_iter()
+            .filter(|event| !event.inactive && !event.paused)
             .map(|event| Response {
+                skip_weekends: event.skips_weekends(),
                 id: event.id,
                 timestamp: event.timestamp,
                 timezone: event.timezone,
                 repeat: event.repeat,
+                ends_at: event.ends_at,
+                reminder_minutes: event.reminder_minutes,
+                skipped_dates: event.skipped_dates,
+                team_id: event.team_id,
             })
             .collect(),
     ))
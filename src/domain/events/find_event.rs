@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use serde::Serialize;
 
-use crate::domain::entities::{Participant, RepeatPeriod};
+use crate::domain::entities::{Participant, PickMode, RepeatPeriod, Visibility};
 use crate::domain::timezone::Timezone;
 use crate::repository::errors::FindError;
 use crate::repository::event::Repository;
@@ -26,6 +27,21 @@ pub struct Response {
     pub repeat: RepeatPeriod,
     pub participants: Vec<Participant>,
     pub channel: String,
+    pub pick_mode: PickMode,
+    pub no_repeat_consecutive: bool,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub anchor: bool,
+    pub notify_channels: Vec<String>,
+    pub inactive: bool,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+    pub user_group: Option<String>,
+    pub seed: Option<u32>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub visibility: Visibility,
+    pub skip_out_of_office: bool,
 }
 
 pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
@@ -40,6 +56,7 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
     };
 
     Ok(Response {
+        skip_weekends: event.skips_weekends(),
         id: event.id,
         name: event.name,
         timestamp: event.timestamp,
@@ -47,5 +64,19 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
         repeat: event.repeat,
         participants: event.participants,
         channel: req.channel,
+        pick_mode: event.pick_mode,
+        no_repeat_consecutive: event.no_repeat_consecutive,
+        ends_at: event.ends_at,
+        reminder_minutes: event.reminder_minutes,
+        anchor: event.anchor,
+        notify_channels: event.notify_channels,
+        inactive: event.inactive,
+        skipped_dates: event.skipped_dates,
+        user_group: event.user_group,
+        seed: event.seed,
+        description: event.description,
+        url: event.url,
+        visibility: event.visibility,
+        skip_out_of_office: event.skip_out_of_office,
     })
 }
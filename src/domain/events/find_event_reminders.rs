@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::Auth;
+use crate::helpers::secret::SecretString;
+use crate::repository::{auth, event};
+
+pub struct Request {
+    pub events: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub reminders: Vec<Reminder>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Reminder {
+    pub event_id: u32,
+    pub event_name: String,
+    pub channel_id: String,
+    pub team_id: String,
+    pub minutes: u32,
+    /// Looked up again when the notification is posted, so that it is not stored with it.
+    #[serde(skip)]
+    pub access_token: SecretString,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    auth_repo: Arc<dyn auth::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let events = event_repo
+        .find_all_events_by_id_unprotected(req.events)
+        .await
+        .map_err(|err| {
+            log::error!("could not find events to remind: {:?}", err);
+            Error::Unknown
+        })?;
+
+    let tokens: HashMap<String, Auth> = auth_repo
+        .find_all_by_team(events.iter().map(|event| event.team_id.clone()).collect())
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .flat_map(|auth| {
+            auth.team_ids()
+                .into_iter()
+                .map(move |team| (team, auth.clone()))
+        })
+        .collect();
+
+    let mut reminders: Vec<Reminder> = vec![];
+    for event in events.into_iter() {
+        if event.inactive {
+            log::info!("ignoring reminder: event {} has ended", event.id);
+            continue;
+        }
+        if event.paused {
+            log::info!("ignoring reminder: event {} is paused", event.id);
+            continue;
+        }
+        let minutes = match event.reminder_minutes {
+            Some(minutes) => minutes,
+            None => {
+                log::info!("ignoring reminder: event {} has no reminder", event.id);
+                continue;
+            }
+        };
+        let auth = match tokens.get(&event.team_id) {
+            Some(auth) => auth,
+            None => {
+                log::error!(
+                    "could not find access token for team id {} while reminding the event {}",
+                    event.team_id,
+                    event.id
+                );
+                continue;
+            }
+        };
+        reminders.push(Reminder {
+            event_id: event.id,
+            event_name: event.name,
+            channel_id: event.channel,
+            team_id: event.team_id,
+            minutes,
+            access_token: auth.access_token.clone(),
+        });
+    }
+
+    Ok(Response { reminders })
+}
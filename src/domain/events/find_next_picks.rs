@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Participant, PickMode};
+use crate::domain::helpers::participant::rotation;
+use crate::helpers::date::Date;
+use crate::repository::errors::FindError;
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    /// The participants left to pick on the current round. On the round-robin events, they are in
+    /// the order they are picked in.
+    pub left: Vec<String>,
+    /// Whether everyone was picked already, so the participants left are the ones of a new round.
+    pub new_round: bool,
+    /// The participants left to pick that are away, so they are not picked until they are back.
+    pub away: Vec<String>,
+    pub round_robin: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+/// Finds the participants of the event that were not picked yet on the current round.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let event = repo
+        .find_event(req.event, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+
+    let now = Date::now().timestamp();
+    let round_robin = event.pick_mode == PickMode::RoundRobin;
+    let (left, new_round) = next_picks(&event.participants, round_robin);
+    let (away, left) = left.into_iter().partition(|p| p.is_away(now));
+    let users = |participants: Vec<&Participant>| {
+        participants
+            .into_iter()
+            .map(|participant| participant.user.clone())
+            .collect()
+    };
+    Ok(Response {
+        id: event.id,
+        name: event.name,
+        left: users(left),
+        new_round,
+        away: users(away),
+        round_robin,
+    })
+}
+
+/// The participants not picked yet, or all of them when a new round is about to start.
+fn next_picks(participants: &[Participant], round_robin: bool) -> (Vec<&Participant>, bool) {
+    let mut left = participants
+        .iter()
+        .filter(|participant| !participant.picked)
+        .collect::<Vec<&Participant>>();
+    let new_round = left.is_empty();
+    if new_round {
+        left = participants.iter().collect();
+    }
+    if round_robin {
+        left = rotation(left);
+    }
+    (left, new_round)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_picks() {
+        let mut participants: Vec<Participant> = ["U1", "U2", "U3"]
+            .into_iter()
+            .map(|user| Participant::from(user.to_string()))
+            .collect();
+        participants[0].picked = true;
+        participants[1].order = Some(1);
+        participants[2].order = Some(0);
+
+        let users = |(left, new_round): (Vec<&Participant>, bool)| {
+            let users = left.iter().map(|p| p.user.as_str()).collect::<Vec<&str>>();
+            (users.join(","), new_round)
+        };
+        assert_eq!(
+            users(next_picks(&participants, true)),
+            (String::from("U3,U2"), false)
+        );
+
+        for participant in participants.iter_mut() {
+            participant.picked = true;
+        }
+        assert_eq!(
+            users(next_picks(&participants, false)),
+            (String::from("U1,U2,U3"), true)
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::repository::event::Repository;
+
+use super::create_event;
+use super::export_events::SNAPSHOT_VERSION;
+
+pub struct Request {
+    /// The snapshot of the events, as exported from a channel.
+    pub snapshot: String,
+    pub channel: String,
+    pub team_id: String,
+    pub max_events: u32,
+    pub max_participants: u32,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub created: Vec<ImportedEvent>,
+    /// The names of the events that could not be created, along with the reason.
+    pub skipped: Vec<(String, create_event::Error)>,
+}
+
+#[derive(Debug)]
+pub struct ImportedEvent {
+    pub name: String,
+    pub event: create_event::Response,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    version: u32,
+    events: Vec<create_event::Request>,
+}
+
+/// Creates the events of a snapshot on the channel. The events that cannot be created (e.g. an
+/// event with the same name already exists) are skipped, so that the others are still imported.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let snapshot = parse(&req.snapshot)?;
+
+    let mut response = Response {
+        created: vec![],
+        skipped: vec![],
+    };
+    for event in snapshot.events {
+        let name = event.name.clone();
+        let request = create_event::Request {
+            channel: req.channel.clone(),
+            team_id: req.team_id.clone(),
+            max_events: req.max_events,
+            max_participants: req.max_participants,
+            ..event
+        };
+        match create_event::execute(repo.clone(), request).await {
+            Ok(event) => response.created.push(ImportedEvent { name, event }),
+            Err(err) => {
+                log::trace!("could not import event {}: {:?}", name, err);
+                response.skipped.push((name, err));
+            }
+        }
+    }
+    Ok(response)
+}
+
+fn parse(snapshot: &str) -> Result<Snapshot, Error> {
+    let snapshot = serde_json::from_str::<Snapshot>(snapshot).map_err(|err| {
+        log::trace!("invalid events snapshot: {}", err);
+        Error::BadRequest
+    })?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        log::trace!("unsupported events snapshot version {}", snapshot.version);
+        return Err(Error::BadRequest);
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let snapshot = parse(
+            r#"{"version":1,"events":[{"name":" Daily ","timestamp":1686661200,"timezone":"ECT","repeat":"daily","participants":["U1","U2"],"weights":{"U2":0.5}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(snapshot.events.len(), 1);
+        assert_eq!(snapshot.events[0].name, "Daily");
+        assert_eq!(snapshot.events[0].participants, vec!["U1", "U2"]);
+        assert_eq!(snapshot.events[0].weights.get("U2"), Some(&0.5));
+
+        assert_eq!(
+            parse(r#"{"version":2,"events":[]}"#).err(),
+            Some(Error::BadRequest)
+        );
+        assert_eq!(parse("events").err(), Some(Error::BadRequest));
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::domain::entities::DEFAULT_WEIGHT;
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::{event, pick_history};
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    /// The participant who did not show up for their latest pick.
+    pub user: String,
+    /// Increases the weight of the participant, so that they are picked more often.
+    pub boost: bool,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub event_name: String,
+    pub user: String,
+    /// The new weight of the participant, when it was increased.
+    pub weight: Option<f64>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    /// The participant was never picked for the event.
+    NotPicked,
+    /// The latest pick of the participant is already marked as a no-show.
+    AlreadyMarked,
+    Unknown,
+}
+
+/// Marks the latest pick of a participant as a no-show on the history of the event.
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let mut event = event_repo
+        .find_event(req.event, req.channel.clone())
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+
+    let pick = history_repo
+        .mark_no_show(req.event, req.channel, req.user.clone())
+        .await
+        .map_err(|err| match err {
+            UpdateError::NotFound => Error::NotPicked,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        })?;
+    if pick.no_show {
+        return Err(Error::AlreadyMarked);
+    }
+
+    let participant = match event
+        .participants
+        .iter_mut()
+        .find(|participant| participant.user == req.user)
+    {
+        // The participants removed since their pick keep their no-show on the history only.
+        Some(participant) if req.boost => participant,
+        _ => {
+            return Ok(Response {
+                event_name: event.name,
+                user: req.user,
+                weight: None,
+            })
+        }
+    };
+    let weight = boost(participant.weight());
+    participant.weight = Some(weight);
+    event_repo
+        .update_event(event.clone())
+        .await
+        .map_err(|err| {
+            log::error!(
+                "could not boost the weight of {} on event {}: {:?}",
+                req.user,
+                event.id,
+                err
+            );
+            Error::Unknown
+        })?;
+
+    Ok(Response {
+        event_name: event.name,
+        user: req.user,
+        weight: Some(weight),
+    })
+}
+
+/// Increases the weight by a step, up to the maximum one.
+fn boost(weight: f64) -> f64 {
+    (weight + NO_SHOW_WEIGHT_STEP).min(MAX_NO_SHOW_WEIGHT.max(weight))
+}
+
+/// The weight added to the participants on each no-show.
+const NO_SHOW_WEIGHT_STEP: f64 = 0.5;
+
+/// The weight the no-shows increase the participants up to.
+const MAX_NO_SHOW_WEIGHT: f64 = 3.0 * DEFAULT_WEIGHT;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boost() {
+        assert_eq!(boost(DEFAULT_WEIGHT), 1.5);
+        assert_eq!(boost(0.5), 1.0);
+        assert_eq!(boost(2.8), MAX_NO_SHOW_WEIGHT);
+        // The weights already above the maximum are kept.
+        assert_eq!(boost(4.0), 4.0);
+    }
+}
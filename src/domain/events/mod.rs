@@ -1,12 +1,28 @@
 pub mod cancel_pick;
+pub mod clone_event;
 pub mod count_events;
 pub mod create_event;
+pub mod deactivate_ended_events;
 pub mod delete_event;
 pub mod delete_participants;
+pub mod event_stats;
+pub mod export_events;
 pub mod find_all_events;
 pub mod find_all_events_and_dates;
 pub mod find_event;
+pub mod find_event_reminders;
+pub mod find_next_picks;
+pub mod import_events;
+pub mod mark_no_show;
+pub mod pause_event;
 pub mod pick_auto_participants;
 pub mod pick_participant;
+pub mod purge_deleted_events;
 pub mod repick_participant;
+pub mod search_events;
+pub mod set_event_anchor;
+pub mod set_participant_away;
+pub mod skip_next_occurrence;
 pub mod update_event;
+pub mod validation;
+pub mod volunteer;
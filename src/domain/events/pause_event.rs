@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::event;
+
+pub struct Request {
+    pub id: u32,
+    pub channel: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+/// Pauses the event, so that it is not picked for until it is edited again.
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let mut event = event_repo
+        .find_event(req.id, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    event.paused = true;
+
+    event_repo
+        .update_event(event.clone())
+        .await
+        .map_err(|err| {
+            log::error!("could not pause event {}: {:?}", event.id, err);
+            match err {
+                UpdateError::NotFound => Error::NotFound,
+                UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+            }
+        })?;
+
+    Ok(Response {
+        id: event.id,
+        name: event.name,
+    })
+}
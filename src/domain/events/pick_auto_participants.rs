@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::domain::entities::Auth;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{Auth, Event, PickSource, Visibility};
 use crate::domain::events::pick_participant;
-use crate::repository::{auth, event};
+use crate::helpers::date::Date;
+use crate::helpers::secret::SecretString;
+use crate::repository::{auth, calendar, event, pick_history, user_status};
 
 pub struct Request {
     pub events: Vec<u32>,
@@ -12,17 +16,56 @@ pub struct Request {
 #[derive(Debug)]
 pub struct Response {
     pub picks: HashMap<u32, Pick>,
+    /// The events that could not be picked for unexpectedly.
+    pub failures: Vec<Failure>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Pick {
     pub event_id: u32,
     pub event_name: String,
     pub channel_id: String,
     pub user_id: String,
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner_id: Option<String>,
     pub team_id: String,
     pub left_count: usize,
-    pub access_token: String,
+    /// Looked up again when the notification is posted, so that it is not stored with it.
+    #[serde(skip)]
+    pub access_token: SecretString,
+    /// The anchor message to reply on, when the event is in anchor mode.
+    pub anchor: Option<Anchor>,
+    /// The other channels the pick is announced on.
+    pub notify_channels: Vec<String>,
+    /// The user who created the event, told when the pick cannot be posted.
+    #[serde(default)]
+    pub created_by: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Where the pick is announced.
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// The participants of the event, sent the pick by direct message on the private events.
+    #[serde(default)]
+    pub participants: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Failure {
+    pub event_id: u32,
+    pub team_id: String,
+    pub channel_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Anchor {
+    /// The timestamp of the posted anchor message.
+    Posted(String),
+    /// The anchor message is yet to be posted.
+    Missing,
 }
 
 #[derive(PartialEq, Debug)]
@@ -33,6 +76,9 @@ pub enum Error {
 pub async fn execute(
     event_repo: Arc<dyn event::Repository>,
     auth_repo: Arc<dyn auth::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    user_status_repo: Arc<dyn user_status::Repository>,
+    calendar_repo: Arc<dyn calendar::Repository>,
     req: Request,
 ) -> Result<Response, Error> {
     let events = event_repo
@@ -52,21 +98,74 @@ pub async fn execute(
         .await
         .unwrap_or(vec![])
         .into_iter()
-        .map(|auth| (auth.team.clone(), auth))
+        .flat_map(|auth| {
+            auth.team_ids()
+                .into_iter()
+                .map(move |team| (team, auth.clone()))
+        })
         .collect();
 
     let mut picks: HashMap<u32, Pick> = HashMap::new();
+    let mut failures: Vec<Failure> = vec![];
     for event in events.iter() {
+        if event.inactive {
+            log::info!("ignoring pick: event {} has ended", event.id);
+            continue;
+        }
+        if event.paused {
+            log::info!("ignoring pick: event {} is paused", event.id);
+            continue;
+        }
+        let access_token = match tokens.get(&event.team_id) {
+            Some(auth) => auth.access_token.clone(),
+            None => {
+                log::error!(
+                    "ignoring pick: could not find access token for team id {} while picking automatically for the event {}",
+                    event.team_id,
+                    event.id
+                );
+                failures.push(Failure {
+                    event_id: event.id,
+                    team_id: event.team_id.clone(),
+                    channel_id: event.channel.clone(),
+                    reason: String::from("could not find the access token of the team"),
+                });
+                continue;
+            }
+        };
+        let skipped = find_out_of_office(
+            event_repo.clone(),
+            history_repo.clone(),
+            user_status_repo.clone(),
+            calendar_repo.clone(),
+            event,
+            access_token.expose(),
+        )
+        .await;
         let pick = match pick_participant::execute(
             event_repo.clone(),
+            history_repo.clone(),
             pick_participant::Request {
                 event: event.id,
                 channel: event.channel.clone(),
+                source: PickSource::Auto,
+                dry_run: false,
+                skipped,
             },
         )
         .await
         {
             Ok(pick) => pick,
+            Err(pick_participant::Error::Unknown) => {
+                log::error!("ignoring pick: could not pick for event {}", event.id);
+                failures.push(Failure {
+                    event_id: event.id,
+                    team_id: event.team_id.clone(),
+                    channel_id: event.channel.clone(),
+                    reason: String::from("could not pick a participant"),
+                });
+                continue;
+            }
             Err(error) => {
                 log::info!(
                     "ignoring pick: no participants for event {}: err {:?}",
@@ -84,17 +183,114 @@ pub async fn execute(
                 event_name: event.name.clone(),
                 channel_id: event.channel.clone(),
                 user_id: pick.id,
+                partner_id: pick.partner,
                 team_id: event.team_id.clone(),
-                left_count: event.participants.iter().filter(|pick| !pick.picked).count(),
-                access_token: tokens.get(&event.team_id)
-                    .and_then(|auth| Some(auth.access_token.clone()))
-                    .unwrap_or_else(|| {
-                        log::error!("could not find access token for team id {} while picking automatically for the event {}", event.team_id, event.id);
-                        String::from("")
-                    }),
+                left_count: event
+                    .participants
+                    .iter()
+                    .filter(|pick| !pick.picked)
+                    .count(),
+                anchor: if event.anchor {
+                    Some(match &event.anchor_ts {
+                        Some(ts) => Anchor::Posted(ts.clone()),
+                        None => Anchor::Missing,
+                    })
+                } else {
+                    None
+                },
+                notify_channels: event.notify_channels.clone(),
+                created_by: event.created_by.clone(),
+                description: event.description.clone(),
+                url: event.url.clone(),
+                visibility: event.visibility.clone(),
+                participants: event
+                    .participants
+                    .iter()
+                    .map(|participant| participant.user.clone())
+                    .collect(),
+                access_token,
             },
         );
     }
 
-    Ok(Response { picks })
+    Ok(Response { picks, failures })
+}
+
+/// Finds the participants to skip on the pick of the event for being out of office, previewing
+/// the picks until the candidates are not. They are out of office by their Slack status, when the
+/// event skips them, or by their connected calendars.
+///
+/// When every participant is out of office, none of them is skipped so that the event is still
+/// picked for.
+async fn find_out_of_office(
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    user_status_repo: Arc<dyn user_status::Repository>,
+    calendar_repo: Arc<dyn calendar::Repository>,
+    event: &Event,
+    token: &str,
+) -> Vec<String> {
+    let date = Date::now()
+        .with_timezone(event.timezone.clone())
+        .to_datetime()
+        .date_naive();
+    let mut skipped: Vec<String> = vec![];
+    for _ in 0..event.participants.len() {
+        let pick = match pick_participant::execute(
+            event_repo.clone(),
+            history_repo.clone(),
+            pick_participant::Request {
+                event: event.id,
+                channel: event.channel.clone(),
+                source: PickSource::Auto,
+                dry_run: true,
+                skipped: skipped.clone(),
+            },
+        )
+        .await
+        {
+            Ok(pick) => pick,
+            Err(..) => return vec![],
+        };
+        let candidates: Vec<String> = std::iter::once(pick.id).chain(pick.partner).collect();
+        let mut away = match event.skip_out_of_office {
+            true => user_status_repo
+                .find_out_of_office(token, &candidates)
+                .await
+                .unwrap_or_else(|err| {
+                    log::error!(
+                        "could not find the statuses of the participants of event {}: {:?}",
+                        event.id,
+                        err
+                    );
+                    vec![]
+                }),
+            false => vec![],
+        };
+        away.extend(
+            calendar_repo
+                .find_out_of_office(&event.team_id, &candidates, date)
+                .await
+                .unwrap_or_else(|err| {
+                    log::error!(
+                        "could not find the calendars of the participants of event {}: {:?}",
+                        event.id,
+                        err
+                    );
+                    vec![]
+                }),
+        );
+        away.sort();
+        away.dedup();
+        if away.is_empty() {
+            break;
+        }
+        log::info!(
+            "skipping participants {:?} of event {}: out of office",
+            away,
+            event.id
+        );
+        skipped.extend(away);
+    }
+    skipped
 }
@@ -1,25 +1,33 @@
 use std::sync::Arc;
 
-use crate::domain::entities::Participant;
-use crate::domain::helpers::participant::{pick_new, replace_participant};
+use crate::domain::entities::{Event, Participant, PickMode, PickSource};
+use crate::domain::helpers::participant::{
+    event_rng, last_picked, pick_new, pick_pair, replace_participant,
+};
+use crate::domain::history::save_pick;
 use crate::helpers::date::Date;
 use crate::repository::errors::{FindError, UpdateError};
 use crate::repository::event::Repository;
+use crate::repository::pick_history;
 
 pub struct Request {
     pub event: u32,
     pub channel: String,
+    pub source: PickSource,
+    /// Runs the pick without saving it, to preview who would be picked.
+    pub dry_run: bool,
+    /// The participants left out of this pick only (e.g. for being out of office), as if they
+    /// were away.
+    pub skipped: Vec<String>,
 }
 
 #[derive(Debug)]
 pub struct Response {
     pub id: String,
-}
-
-impl From<Participant> for Response {
-    fn from(value: Participant) -> Self {
-        Self { id: value.user }
-    }
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner: Option<String>,
+    /// The participants left to pick on the round after this pick.
+    pub left: Vec<String>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -29,7 +37,11 @@ pub enum Error {
     Unknown,
 }
 
-pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
     let mut event = repo
         .find_event(req.event.clone(), req.channel.clone())
         .await
@@ -43,19 +55,45 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
     if event.participants.len() == 0 {
         return Err(Error::Empty);
     }
+    if let PickMode::Pairs = event.pick_mode {
+        return execute_pair(repo, history_repo, event, req).await;
+    }
 
+    let mut rng = event_rng(&event);
     let mut participants = event.participants;
-    let mut new_pick = pick_new(&participants);
+    let prev_pick = last_picked(&participants).map(|participant| participant.user.clone());
+    let replaced = match req.source {
+        PickSource::Skip => prev_pick.clone(),
+        _ => None,
+    };
+    let mut new_pick = pick_new(
+        &skipping(&participants, &req.skipped),
+        &event.pick_mode,
+        None,
+        &mut rng,
+    )
+    .cloned();
     if let None = new_pick {
+        // A new round starts, keeping when each participant was last picked so that the random
+        // picks prefer the ones picked the longest ago.
         participants = participants
             .into_iter()
             .map(|participant| Participant {
                 picked: false,
-                picked_at: None,
                 ..participant
             })
             .collect();
-        new_pick = pick_new(&participants);
+        let avoid = match event.no_repeat_consecutive {
+            true => prev_pick.as_deref(),
+            false => None,
+        };
+        new_pick = pick_new(
+            &skipping(&participants, &req.skipped),
+            &event.pick_mode,
+            avoid,
+            &mut rng,
+        )
+        .cloned();
     }
     let new_pick = match new_pick {
         Some(participant) => participant,
@@ -69,6 +107,20 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
             ..new_pick.clone()
         },
     );
+    // The skipped participant is preferred on the next rounds, as if they were never picked.
+    if let Some(skipped) = replaced.as_deref().filter(|user| *user != new_pick.user) {
+        if let Some(participant) = event.participants.iter_mut().find(|p| p.user == skipped) {
+            participant.picked_at = None;
+        }
+    }
+    let response = Response {
+        id: new_pick.user.clone(),
+        partner: None,
+        left: left_to_pick(&event.participants),
+    };
+    if req.dry_run {
+        return Ok(response);
+    }
     repo.update_event(event).await.map_err(|error| {
         return match error {
             UpdateError::NotFound => Error::NotFound,
@@ -76,5 +128,152 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
         };
     })?;
 
-    Ok(new_pick.clone().into())
+    if let Err(err) = save_pick::execute(
+        history_repo,
+        save_pick::Request {
+            event: req.event,
+            channel: req.channel,
+            user: new_pick.user.clone(),
+            source: req.source,
+            replaced,
+            partner: None,
+        },
+    )
+    .await
+    {
+        log::error!(
+            "could not save pick history for event {}: {:?}",
+            req.event,
+            err
+        );
+    }
+
+    Ok(response)
+}
+
+/// Picks a pair of participants, not repeating the pairs picked before until all of them were.
+async fn execute_pair(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    mut event: Event,
+    req: Request,
+) -> Result<Response, Error> {
+    let mut picks = history_repo
+        .find_all_picks(req.event, req.channel.clone())
+        .await
+        .map_err(|err| {
+            log::error!(
+                "could not find pick history for event {}: {:?}",
+                req.event,
+                err
+            );
+            Error::Unknown
+        })?;
+    picks.sort_by_key(|pick| pick.timestamp);
+    let paired: Vec<(String, String)> = picks
+        .into_iter()
+        .filter_map(|pick| pick.partner.map(|partner| (pick.user, partner)))
+        .collect();
+
+    let replaced = match req.source {
+        PickSource::Skip | PickSource::Repick => {
+            last_picked(&event.participants).map(|participant| participant.user.clone())
+        }
+        _ => None,
+    };
+    let mut rng = event_rng(&event);
+    let (user, partner) = match pick_pair(
+        &skipping(&event.participants, &req.skipped),
+        &paired,
+        &mut rng,
+    ) {
+        Some((user, partner)) => (user.clone(), partner.clone()),
+        None => return Err(Error::Empty),
+    };
+
+    let mut participants = event.participants;
+    if participants.iter().filter(|p| !p.picked).count() < 2 {
+        participants = participants
+            .into_iter()
+            .map(|participant| Participant {
+                picked: false,
+                ..participant
+            })
+            .collect();
+    }
+    let now = Date::now().timestamp();
+    for participant in [&user, &partner] {
+        participants = replace_participant(
+            participants,
+            Participant {
+                picked: true,
+                picked_at: Some(now),
+                ..participant.clone()
+            },
+        );
+    }
+    event.participants = participants;
+    let left = left_to_pick(&event.participants);
+    if req.dry_run {
+        return Ok(Response {
+            id: user.user,
+            partner: Some(partner.user),
+            left,
+        });
+    }
+    repo.update_event(event)
+        .await
+        .map_err(|error| match error {
+            UpdateError::NotFound => Error::NotFound,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        })?;
+
+    if let Err(err) = save_pick::execute(
+        history_repo,
+        save_pick::Request {
+            event: req.event,
+            channel: req.channel,
+            user: user.user.clone(),
+            source: req.source,
+            replaced,
+            partner: Some(partner.user.clone()),
+        },
+    )
+    .await
+    {
+        log::error!(
+            "could not save pick history for event {}: {:?}",
+            req.event,
+            err
+        );
+    }
+
+    Ok(Response {
+        id: user.user,
+        partner: Some(partner.user),
+        left,
+    })
+}
+
+/// Marks the skipped participants as away, so that they are not picked.
+fn skipping(participants: &[Participant], skipped: &[String]) -> Vec<Participant> {
+    participants
+        .iter()
+        .map(|participant| match skipped.contains(&participant.user) {
+            true => Participant {
+                unavailable_until: Some(i64::MAX),
+                ..participant.clone()
+            },
+            false => participant.clone(),
+        })
+        .collect()
+}
+
+fn left_to_pick(participants: &[Participant]) -> Vec<String> {
+    let now = Date::now().timestamp();
+    participants
+        .iter()
+        .filter(|participant| !participant.picked && !participant.is_away(now))
+        .map(|participant| participant.user.clone())
+        .collect()
 }
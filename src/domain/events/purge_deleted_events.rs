@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::helpers::date::Date;
+use crate::repository::errors::DeleteError;
+use crate::repository::event::Repository;
+
+pub struct Request {
+    /// The number of days the deleted events are kept for.
+    pub retention_days: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Response {
+    pub count: u64,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown,
+}
+
+/// Removes for good the events deleted longer ago than the retention period.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let before = Date::now().timestamp() - req.retention_days as i64 * SECONDS_PER_DAY;
+    let count = repo
+        .purge_deleted_before(before)
+        .await
+        .map_err(|err| match err {
+            DeleteError::NotFound | DeleteError::Unknown => Error::Unknown,
+        })?;
+    Ok(Response { count })
+}
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
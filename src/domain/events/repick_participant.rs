@@ -1,11 +1,13 @@
 use std::sync::Arc;
 
-use crate::domain::entities::Participant;
+use crate::domain::entities::{Participant, PickMode, PickSource};
 use crate::domain::events::pick_participant;
-use crate::domain::helpers::participant::{last_picked, pick_new, replace_participant};
+use crate::domain::helpers::participant::{event_rng, last_picked, pick_new, replace_participant};
+use crate::domain::history::save_pick;
 use crate::helpers::date::Date;
 use crate::repository::errors::{FindError, UpdateError};
 use crate::repository::event::Repository;
+use crate::repository::pick_history;
 
 pub struct Request {
     pub event: u32,
@@ -17,6 +19,9 @@ impl From<Request> for pick_participant::Request {
         Self {
             event: value.event,
             channel: value.channel,
+            source: PickSource::Repick,
+            dry_run: false,
+            skipped: vec![],
         }
     }
 }
@@ -24,11 +29,16 @@ impl From<Request> for pick_participant::Request {
 #[derive(Debug)]
 pub struct Response {
     pub name: String,
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner: Option<String>,
 }
 
 impl From<Participant> for Response {
     fn from(value: Participant) -> Self {
-        Self { name: value.user }
+        Self {
+            name: value.user,
+            partner: None,
+        }
     }
 }
 
@@ -49,7 +59,11 @@ impl From<pick_participant::Error> for Error {
     }
 }
 
-pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
     let mut event = repo
         .find_event(req.event.clone(), req.channel.clone())
         .await
@@ -59,7 +73,16 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
                 FindError::Unknown => Error::Unknown,
             };
         })?;
+    // The pairs are repicked as a whole.
+    if let PickMode::Pairs = event.pick_mode {
+        let response = pick_participant::execute(repo, history_repo, req.into()).await?;
+        return Ok(Response {
+            name: response.id,
+            partner: response.partner,
+        });
+    }
 
+    let mut rng = event_rng(&event);
     let participants = event.participants;
 
     let cur_pick = last_picked(&participants);
@@ -68,7 +91,16 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
     }
     let cur_pick = cur_pick.unwrap();
 
-    let new_pick = match pick_new(&participants) {
+    let prev_pick = match event.no_repeat_consecutive {
+        true => find_prev_pick(history_repo.clone(), req.event, req.channel.clone()).await,
+        false => None,
+    };
+    let new_pick = match pick_new(
+        &participants,
+        &event.pick_mode,
+        prev_pick.as_deref(),
+        &mut rng,
+    ) {
         None => return Ok(cur_pick.clone().into()),
         Some(participant) => participant,
     };
@@ -95,5 +127,40 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
         };
     })?;
 
+    if let Err(err) = save_pick::execute(
+        history_repo,
+        save_pick::Request {
+            event: req.event,
+            channel: req.channel,
+            user: new_pick.user.clone(),
+            source: PickSource::Repick,
+            replaced: Some(cur_pick.user.clone()),
+            partner: None,
+        },
+    )
+    .await
+    {
+        log::error!(
+            "could not save pick history for event {}: {:?}",
+            req.event,
+            err
+        );
+    }
+
     Ok(new_pick.clone().into())
 }
+
+/// Finds the participant picked right before the current pick, using the pick history.
+async fn find_prev_pick(
+    history_repo: Arc<dyn pick_history::Repository>,
+    event: u32,
+    channel: String,
+) -> Option<String> {
+    match history_repo.find_picks(event, channel, 2).await {
+        Ok(picks) => picks.into_iter().nth(1).map(|pick| pick.user),
+        Err(err) => {
+            log::error!("could not find pick history for event {}: {:?}", event, err);
+            None
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::dtos::{PageRequest, PageResponse};
+use crate::domain::entities::{Participant, RepeatPeriod};
+use crate::repository::errors::FindAllError;
+use crate::repository::event::{EventFilter, EventSort, Repository};
+
+pub struct Request {
+    pub channel: String,
+    /// The text to find on the event names or IDs. All events match an empty query.
+    pub query: String,
+    pub page: PageRequest,
+    /// Leaves out the participants that were not picked yet on the current round.
+    pub picked_only: bool,
+    pub sort: EventSort,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+    pub timestamp: i64,
+    pub repeat: RepeatPeriod,
+    pub participants: Vec<Participant>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<PageResponse<Response>, Error> {
+    let filter = EventFilter {
+        query: Some(req.query),
+        sort: req.sort,
+    };
+    // One more event is found to tell whether there is a next page.
+    let mut events = match repo
+        .find_events_page(
+            req.channel,
+            req.page.cursor as u64,
+            Some(req.page.limit as u64 + 1),
+            filter,
+        )
+        .await
+    {
+        Err(err) => {
+            return match err {
+                FindAllError::Unknown => Err(Error::Unknown),
+            }
+        }
+        Ok(events) => events,
+    };
+    let next_cursor = (events.len() > req.page.limit).then_some(req.page.cursor + req.page.limit);
+    events.truncate(req.page.limit);
+
+    Ok(PageResponse::new(
+        events
+            .into_iter()
+            .map(|event| Response {
+                id: event.id,
+                name: event.name,
+                timestamp: event.timestamp,
+                repeat: event.repeat,
+                participants: event
+                    .participants
+                    .into_iter()
+                    .filter(|participant| !req.picked_only || participant.picked)
+                    .collect(),
+            })
+            .collect(),
+        next_cursor,
+    ))
+}
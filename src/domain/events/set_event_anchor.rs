@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::repository::errors::UpdateError;
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub id: u32,
+    /// The timestamp of the posted anchor message.
+    pub anchor_ts: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    repo.update_event_anchor(req.id, req.anchor_ts)
+        .await
+        .map_err(|err| match err {
+            UpdateError::NotFound => Error::NotFound,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        })
+}
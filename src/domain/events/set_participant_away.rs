@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::repository::errors::{FindAllError, UpdateError};
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub channel: String,
+    pub user: String,
+    pub until: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub events: Vec<String>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let events = repo
+        .find_all_events(req.channel.clone())
+        .await
+        .map_err(|error| match error {
+            FindAllError::Unknown => Error::Unknown,
+        })?;
+
+    let mut updated = vec![];
+    for mut event in events.into_iter() {
+        let participant = match event.participants.iter_mut().find(|p| p.user == req.user) {
+            Some(participant) => participant,
+            None => continue,
+        };
+        participant.unavailable_until = req.until;
+
+        let name = event.name.clone();
+        repo.update_event(event).await.map_err(|error| {
+            log::error!(
+                "could not set participant {} away on channel {}: {:?}",
+                req.user,
+                req.channel,
+                error
+            );
+            match error {
+                UpdateError::NotFound => Error::NotFound,
+                UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+            }
+        })?;
+        updated.push(name);
+    }
+
+    if updated.is_empty() {
+        return Err(Error::NotFound);
+    }
+    Ok(Response { events: updated })
+}
+
+/// The participants are set away for a year at most.
+pub const MAX_AWAY_DAYS: i64 = 365;
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+use crate::domain::entities::RepeatPeriod;
+use crate::domain::timezone::Timezone;
+use crate::helpers::date::Date;
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::{event, scheduler};
+
+pub struct Request {
+    pub id: u32,
+    pub channel: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+    /// The day of the skipped occurrence, on the event's timezone.
+    pub skipped: NaiveDate,
+    pub timestamp: i64,
+    pub timezone: Timezone,
+    pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+    pub team_id: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    /// The event does not happen again.
+    NoOccurrence,
+    Unknown,
+}
+
+/// Skips the day of the next occurrence of the event, without changing its schedule. The skipped
+/// days that already passed are dropped.
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    scheduler_repo: Arc<dyn scheduler::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let mut event = event_repo
+        .find_event(req.id, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    let scheduled = scheduler_repo
+        .find_scheduled_event(event.id)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NoOccurrence,
+            FindError::Unknown => Error::Unknown,
+        })?;
+
+    let skipped = local_date(scheduled.next_at, &event.timezone);
+    let today = local_date(Date::now().timestamp(), &event.timezone);
+    event.skipped_dates.retain(|date| *date >= today);
+    if !event.skipped_dates.contains(&skipped) {
+        event.skipped_dates.push(skipped);
+        event.skipped_dates.sort_unstable();
+    }
+
+    event_repo
+        .update_event(event.clone())
+        .await
+        .map_err(|err| {
+            log::error!(
+                "could not skip the next occurrence of event {}: {:?}",
+                event.id,
+                err
+            );
+            match err {
+                UpdateError::NotFound => Error::NotFound,
+                UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+            }
+        })?;
+
+    Ok(Response {
+        id: event.id,
+        skipped,
+        skip_weekends: event.skips_weekends(),
+        timestamp: event.timestamp,
+        timezone: event.timezone,
+        repeat: event.repeat,
+        ends_at: event.ends_at,
+        reminder_minutes: event.reminder_minutes,
+        skipped_dates: event.skipped_dates,
+        team_id: event.team_id,
+    })
+}
+
+fn local_date(timestamp: i64, timezone: &Timezone) -> NaiveDate {
+    Date::new(timestamp)
+        .with_timezone(timezone.clone())
+        .to_datetime()
+        .date_naive()
+}
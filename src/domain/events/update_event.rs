@@ -1,13 +1,19 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_trim::{string_trim, vec_string_trim};
 
-use crate::domain::entities::{Event, Participant, RepeatPeriod};
+use crate::domain::entities::{Event, Participant, PickMode, RepeatPeriod, Visibility};
+use crate::domain::events::create_event::{notify_channels, optional_text};
 use crate::domain::timezone::Timezone;
+use crate::helpers::date::Date;
 use crate::repository::errors::{FindError, UpdateError};
 use crate::repository::event::Repository;
 
+use super::validation::{self, FieldError};
+
 #[derive(Deserialize, Clone)]
 pub struct Request {
     pub id: u32,
@@ -18,8 +24,36 @@ pub struct Request {
     pub repeat: String,
     #[serde(deserialize_with = "vec_string_trim")]
     pub participants: Vec<String>,
+    #[serde(default)]
+    pub weights: HashMap<String, f64>,
+    #[serde(default)]
+    pub pick_mode: Option<String>,
+    #[serde(default)]
+    pub no_repeat_consecutive: Option<bool>,
+    #[serde(default)]
+    pub ends_at: Option<i64>,
+    #[serde(default)]
+    pub reminder_minutes: Option<u32>,
+    #[serde(default)]
+    pub anchor: Option<bool>,
+    #[serde(default)]
+    pub notify_channels: Option<Vec<String>>,
+    #[serde(default)]
+    pub skip_weekends: Option<bool>,
+    #[serde(default)]
+    pub seed: Option<u32>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub skip_out_of_office: Option<bool>,
     #[serde(skip_deserializing)]
     pub channel: String,
+    #[serde(skip_deserializing)]
+    pub max_participants: u32,
 }
 
 #[derive(Serialize, Debug)]
@@ -28,17 +62,23 @@ pub struct Response {
     pub timestamp: i64,
     pub timezone: Timezone,
     pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+    pub team_id: String,
 }
 
 #[derive(PartialEq, Debug)]
 pub enum Error {
-    BadRequest,
+    /// The fields of the event that are not valid.
+    Invalid(Vec<FieldError>),
     Conflict,
     NotFound,
     Unknown,
 }
 
-pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+pub async fn execute(repo: Arc<dyn Repository>, mut req: Request) -> Result<Response, Error> {
     let existing_event = match repo.clone().find_event(req.id.clone(), req.channel).await {
         Ok(event) => event,
         Err(error) => {
@@ -49,35 +89,97 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
         }
     };
 
-    let event = Event {
+    req.description = optional_text(req.description);
+    req.url = optional_text(req.url);
+    let fields = validation::Fields {
+        name: &req.name,
+        timestamp: req.timestamp,
+        timezone: &req.timezone,
+        repeat: &req.repeat,
+        participants: &req.participants,
+        max_participants: req.max_participants,
+        pick_mode: req.pick_mode.as_deref(),
+        ends_at: req.ends_at,
+        reminder_minutes: req.reminder_minutes,
+        description: req.description.as_deref(),
+        url: req.url.as_deref(),
+        visibility: req.visibility.as_deref(),
+    };
+    // The date is only checked when changed, so that the past events can still be edited.
+    let now = (req.timestamp != existing_event.timestamp).then(|| Date::now().timestamp());
+    if let Err(errors) = validation::validate(&fields, now) {
+        log::trace!("could not update event {}: {:?}", req.id, errors);
+        return Err(Error::Invalid(errors));
+    }
+
+    let mut event = Event {
         id: existing_event.id,
         name: req.name.clone(),
         timestamp: req.timestamp,
         timezone: Timezone::from(req.timezone.clone()),
-        repeat: RepeatPeriod::try_from(req.repeat.clone()).map_err(|_| Error::BadRequest)?,
-        participants: [
-            existing_event
-                .participants
-                .into_iter()
-                .filter(|p| !req.participants.contains(&p.user))
-                .collect::<Vec<Participant>>(),
-            req.participants
-                .into_iter()
-                .map(|name| name.into())
-                .collect::<Vec<Participant>>(),
-        ]
-        .concat(),
-        channel: existing_event.channel,
+        repeat: RepeatPeriod::try_from(req.repeat.clone())
+            .map_err(|_| Error::Invalid(vec![FieldError::new("repeat", "unknown frequency")]))?,
+        participants: merge_participants(existing_event.participants, req.participants),
+        channel: existing_event.channel.clone(),
         team_id: existing_event.team_id,
         deleted: false,
+        pick_mode: match req.pick_mode {
+            Some(pick_mode) => PickMode::try_from(pick_mode).map_err(|_| {
+                Error::Invalid(vec![FieldError::new("pick_mode", "unknown pick mode")])
+            })?,
+            None => existing_event.pick_mode,
+        },
+        no_repeat_consecutive: req
+            .no_repeat_consecutive
+            .unwrap_or(existing_event.no_repeat_consecutive),
+        ends_at: req.ends_at,
+        reminder_minutes: req.reminder_minutes,
+        anchor: req.anchor.unwrap_or(existing_event.anchor),
+        anchor_ts: existing_event.anchor_ts,
+        notify_channels: match req.notify_channels {
+            Some(channels) => notify_channels(channels, &existing_event.channel),
+            None => existing_event.notify_channels,
+        },
+        inactive: false,
+        skip_weekends: req.skip_weekends.or(existing_event.skip_weekends),
+        skipped_dates: existing_event.skipped_dates,
+        user_group: existing_event.user_group,
+        seed: req.seed,
+        created_by: existing_event.created_by,
+        // Editing a paused event resumes it.
+        paused: false,
+        description: req.description,
+        url: req.url,
+        visibility: match req.visibility {
+            Some(visibility) => Visibility::try_from(visibility).map_err(|_| {
+                Error::Invalid(vec![FieldError::new("visibility", "unknown visibility")])
+            })?,
+            None => existing_event.visibility,
+        },
+        skip_out_of_office: req
+            .skip_out_of_office
+            .unwrap_or(existing_event.skip_out_of_office),
     };
+    // A new anchor message is posted when the anchor mode is enabled again.
+    if !event.anchor {
+        event.anchor_ts = None;
+    }
+    event.inactive = event.has_ended(Date::now().timestamp());
+    for participant in event.participants.iter_mut() {
+        participant.weight = req.weights.get(&participant.user).copied();
+    }
 
     match repo.update_event(event.clone()).await {
         Ok(..) => Ok(Response {
+            skip_weekends: event.skips_weekends(),
             id: event.id,
             timestamp: event.timestamp,
             timezone: event.timezone,
             repeat: event.repeat,
+            ends_at: event.ends_at,
+            reminder_minutes: event.reminder_minutes,
+            skipped_dates: event.skipped_dates,
+            team_id: event.team_id,
         }),
         Err(err) => Err(match err {
             UpdateError::Conflict => Error::Conflict,
@@ -86,3 +188,134 @@ pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response
         }),
     }
 }
+
+/// Adds the users of the form that are not participants yet. The participants already on the event
+/// keep their state (e.g. their picks, away days and position on the rotation), so that editing the
+/// event does not reset it.
+fn merge_participants(mut participants: Vec<Participant>, users: Vec<String>) -> Vec<Participant> {
+    for user in users {
+        if !participants
+            .iter()
+            .any(|participant| participant.user == user)
+        {
+            participants.push(user.into());
+        }
+    }
+    participants
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::helpers::participant::{last_picked, rotation};
+    use crate::repository::event::{sample_event, MemoryRepository};
+
+    use super::*;
+
+    fn request(participants: &[&str]) -> Request {
+        Request {
+            id: 1,
+            name: String::from("Standup (renamed)"),
+            timestamp: 1723822080,
+            timezone: String::from("UTC"),
+            repeat: String::from("daily"),
+            participants: participants.iter().map(|user| user.to_string()).collect(),
+            weights: HashMap::new(),
+            pick_mode: None,
+            no_repeat_consecutive: None,
+            ends_at: None,
+            reminder_minutes: None,
+            anchor: None,
+            notify_channels: None,
+            skip_weekends: None,
+            seed: None,
+            description: None,
+            url: None,
+            visibility: None,
+            skip_out_of_office: None,
+            channel: String::from("C1"),
+            max_participants: 10,
+        }
+    }
+
+    async fn participants(repo: &MemoryRepository) -> Vec<Participant> {
+        repo.find_event(1, String::from("C1"))
+            .await
+            .unwrap()
+            .participants
+    }
+
+    #[tokio::test]
+    async fn test_keeps_away_participants() {
+        let mut away = Participant::from(String::from("U1"));
+        away.unavailable_until = Some(Date::now().timestamp() + 3600);
+        let repo = Arc::new(MemoryRepository::with_events(vec![sample_event(
+            1,
+            "Standup",
+            vec![away.clone(), Participant::from(String::from("U2"))],
+        )]));
+
+        execute(repo.clone(), request(&["U1", "U2", "U3"]))
+            .await
+            .unwrap();
+
+        let participants = participants(&repo).await;
+        assert_eq!(participants[0], away);
+        assert!(participants[0].is_away(Date::now().timestamp()));
+        // The new users get a default participant.
+        assert_eq!(participants[2].user, "U3");
+        assert_eq!(participants[2].unavailable_until, None);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_rotation_order() {
+        let mut first = Participant::from(String::from("U1"));
+        first.order = Some(1);
+        let mut second = Participant::from(String::from("U2"));
+        second.order = Some(0);
+        let repo = Arc::new(MemoryRepository::with_events(vec![sample_event(
+            1,
+            "Standup",
+            vec![first, second],
+        )]));
+
+        execute(repo.clone(), request(&["U1", "U2"])).await.unwrap();
+
+        let participants = participants(&repo).await;
+        let users = rotation(&participants)
+            .into_iter()
+            .map(|participant| participant.user.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(users, vec!["U2", "U1"]);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_picks() {
+        let participants_before = vec![
+            Participant {
+                picked: true,
+                picked_at: Some(1723822080),
+                ..String::from("U1").into()
+            },
+            Participant {
+                picked: true,
+                picked_at: Some(1724681760),
+                ..String::from("U2").into()
+            },
+            String::from("U3").into(),
+        ];
+        let repo = Arc::new(MemoryRepository::with_events(vec![sample_event(
+            1,
+            "Standup",
+            participants_before.clone(),
+        )]));
+
+        execute(repo.clone(), request(&["U1", "U2", "U3"]))
+            .await
+            .unwrap();
+
+        // The random picks keep preferring the participants picked the longest ago.
+        let participants = participants(&repo).await;
+        assert_eq!(participants, participants_before);
+        assert_eq!(last_picked(&participants).unwrap().user, "U2");
+    }
+}
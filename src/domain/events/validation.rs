@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::domain::entities::{PickMode, RepeatPeriod, Visibility};
+use crate::domain::timezone::Timezone;
+
+/// An invalid field of an event, along with the reason.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+/// The fields of an event that are validated when it is created or updated.
+pub struct Fields<'a> {
+    pub name: &'a str,
+    pub timestamp: i64,
+    pub timezone: &'a str,
+    pub repeat: &'a str,
+    pub participants: &'a [String],
+    /// The number of participants the event can have, on the plan of the team.
+    pub max_participants: u32,
+    pub pick_mode: Option<&'a str>,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub description: Option<&'a str>,
+    pub url: Option<&'a str>,
+    pub visibility: Option<&'a str>,
+}
+
+/// Validates the fields of an event, returning an error for each invalid one.
+///
+/// The events that are not repeated cannot happen before `now`, when given. It is left out when
+/// the date is kept (e.g. renaming an event that already happened).
+pub fn validate(fields: &Fields, now: Option<i64>) -> Result<(), Vec<FieldError>> {
+    let mut errors = vec![];
+
+    let name_length = fields.name.trim().chars().count();
+    if name_length == 0 {
+        errors.push(FieldError::new("name", "the name is empty"));
+    } else if name_length > MAX_NAME_LENGTH {
+        errors.push(FieldError::new(
+            "name",
+            format!("the name has more than {} characters", MAX_NAME_LENGTH),
+        ));
+    }
+
+    let repeat = RepeatPeriod::try_from(fields.repeat.to_string());
+    match (&repeat, now) {
+        (Err(..), _) => errors.push(FieldError::new(
+            "repeat",
+            format!("unknown frequency {}", fields.repeat),
+        )),
+        (Ok(RepeatPeriod::None), Some(now)) if fields.timestamp < now - PAST_GRACE_SECONDS => {
+            errors.push(FieldError::new("timestamp", "the date is in the past"))
+        }
+        _ => (),
+    }
+
+    if !Timezone::all()
+        .into_iter()
+        .any(|timezone| String::from(timezone) == fields.timezone)
+    {
+        errors.push(FieldError::new(
+            "timezone",
+            format!("unknown timezone {}", fields.timezone),
+        ));
+    }
+
+    let mut participants = HashSet::new();
+    if fields.participants.is_empty() {
+        errors.push(FieldError::new("participants", "there are no participants"));
+    } else if fields.participants.len() > fields.max_participants as usize {
+        errors.push(FieldError::new(
+            "participants",
+            format!(
+                "there are more than {} participants",
+                fields.max_participants
+            ),
+        ));
+    } else if fields
+        .participants
+        .iter()
+        .any(|user| user.trim().is_empty())
+    {
+        errors.push(FieldError::new("participants", "a participant is empty"));
+    } else if !fields
+        .participants
+        .iter()
+        .all(|user| participants.insert(user))
+    {
+        errors.push(FieldError::new("participants", "a participant is repeated"));
+    }
+
+    if let Some(pick_mode) = fields.pick_mode {
+        if PickMode::try_from(pick_mode.to_string()).is_err() {
+            errors.push(FieldError::new(
+                "pick_mode",
+                format!("unknown pick mode {}", pick_mode),
+            ));
+        }
+    }
+    if let Some(visibility) = fields.visibility {
+        if Visibility::try_from(visibility.to_string()).is_err() {
+            errors.push(FieldError::new(
+                "visibility",
+                format!("unknown visibility {}", visibility),
+            ));
+        }
+    }
+    if fields
+        .ends_at
+        .is_some_and(|ends_at| ends_at <= fields.timestamp)
+    {
+        errors.push(FieldError::new(
+            "ends_at",
+            "the end date is before the date",
+        ));
+    }
+    if fields.reminder_minutes == Some(0) {
+        errors.push(FieldError::new(
+            "reminder_minutes",
+            "the reminder must be at least one minute before",
+        ));
+    }
+
+    if fields
+        .description
+        .is_some_and(|description| description.chars().count() > MAX_DESCRIPTION_LENGTH)
+    {
+        errors.push(FieldError::new(
+            "description",
+            format!(
+                "the description has more than {} characters",
+                MAX_DESCRIPTION_LENGTH
+            ),
+        ));
+    }
+    if let Some(url) = fields.url {
+        if !is_valid_url(url) {
+            errors.push(FieldError::new("url", format!("invalid link {}", url)));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether the link is a web address that can be linked to on the Slack messages, which cannot
+/// have spaces nor the characters delimiting the links.
+fn is_valid_url(url: &str) -> bool {
+    let address = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"));
+    address.is_some_and(|address| {
+        !address.is_empty()
+            && !address
+                .chars()
+                .any(|c| c.is_whitespace() || matches!(c, '<' | '>' | '|'))
+    })
+}
+
+/// The number of characters the names of the events can have.
+pub const MAX_NAME_LENGTH: usize = 80;
+
+/// The number of characters the descriptions of the events can have.
+pub const MAX_DESCRIPTION_LENGTH: usize = 500;
+
+/// The seconds a date can be in the past, as the dates are picked to the minute.
+const PAST_GRACE_SECONDS: i64 = 60;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let participants = vec![String::from("U1"), String::from("U2")];
+        let mut fields = Fields {
+            name: "Daily",
+            timestamp: 1700000000,
+            timezone: "UTC",
+            repeat: "none",
+            participants: &participants,
+            max_participants: 2,
+            pick_mode: Some("random"),
+            ends_at: None,
+            reminder_minutes: Some(15),
+            description: Some("The daily meeting"),
+            url: Some("https://meet.example.com/daily"),
+            visibility: Some("direct"),
+        };
+        assert_eq!(validate(&fields, Some(1700000000)), Ok(()));
+        // The past dates are only checked when given the current time.
+        assert_eq!(validate(&fields, None), Ok(()));
+
+        let repeated = vec![String::from("U1"), String::from("U1")];
+        let name = "a".repeat(MAX_NAME_LENGTH + 1);
+        fields.name = &name;
+        fields.timezone = "Mars";
+        fields.participants = &repeated;
+        fields.ends_at = Some(1700000000);
+        fields.reminder_minutes = Some(0);
+        let description = "a".repeat(MAX_DESCRIPTION_LENGTH + 1);
+        fields.description = Some(&description);
+        fields.url = Some("meet.example.com/daily");
+        fields.visibility = Some("everyone");
+        let errors = validate(&fields, Some(1700000000 + 3600)).unwrap_err();
+        assert_eq!(
+            errors.iter().map(|err| err.field).collect::<Vec<&str>>(),
+            vec![
+                "name",
+                "timestamp",
+                "timezone",
+                "participants",
+                "visibility",
+                "ends_at",
+                "reminder_minutes",
+                "description",
+                "url"
+            ]
+        );
+
+        // The repeated events can start in the past.
+        fields.repeat = "daily";
+        let errors = validate(&fields, Some(1700000000 + 3600)).unwrap_err();
+        assert!(errors.iter().all(|err| err.field != "timestamp"));
+
+        assert!(is_valid_url("http://example.com"));
+        assert!(!is_valid_url("https://"));
+        assert!(!is_valid_url("https://example.com/a b"));
+        assert!(!is_valid_url("https://example.com|Link"));
+        assert!(!is_valid_url("javascript:alert(1)"));
+
+        fields.max_participants = 1;
+        let errors = validate(&fields, None).unwrap_err();
+        assert!(errors.contains(&FieldError::new(
+            "participants",
+            "there are more than 1 participants"
+        )));
+    }
+}
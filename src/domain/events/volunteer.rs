@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Participant, PickSource, Visibility};
+use crate::domain::helpers::participant::replace_participant;
+use crate::domain::history::save_pick;
+use crate::helpers::date::Date;
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::{event, pick_history};
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    /// The participant taking the pick.
+    pub user: String,
+    /// The picked participant being replaced.
+    pub replaced: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub event_name: String,
+    pub user: String,
+    pub replaced: String,
+    /// The participant picked together with the replaced one, on the events picking pairs.
+    pub partner: Option<String>,
+    pub left_count: usize,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub visibility: Visibility,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    /// The volunteer is not a participant of the event.
+    NotParticipant,
+    /// The volunteer is already on the latest pick of the event.
+    AlreadyPicked,
+    /// The replaced participant is no longer the latest pick of the event (e.g. it was repicked).
+    Stale,
+    Unknown,
+}
+
+/// Lets a participant take the latest pick of an event in place of the picked participant, who is
+/// left to be picked again on the round.
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let mut event = event_repo
+        .find_event(req.event, req.channel.clone())
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    if !event.participants.iter().any(|p| p.user == req.user) {
+        return Err(Error::NotParticipant);
+    }
+
+    let latest = history_repo
+        .find_picks(req.event, req.channel.clone(), 1)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "could not find the latest pick of event {}: {:?}",
+                req.event,
+                err
+            );
+            Error::Unknown
+        })?
+        .into_iter()
+        .next()
+        .ok_or(Error::Stale)?;
+    if latest.user == req.user || latest.partner.as_ref() == Some(&req.user) {
+        return Err(Error::AlreadyPicked);
+    }
+    if latest.user != req.replaced {
+        return Err(Error::Stale);
+    }
+
+    event.participants = swap_pick(
+        event.participants,
+        &req.user,
+        &req.replaced,
+        Date::now().timestamp(),
+    );
+    let left_count = event.participants.iter().filter(|p| !p.picked).count();
+    event_repo
+        .update_event(event.clone())
+        .await
+        .map_err(|err| match err {
+            UpdateError::NotFound => Error::NotFound,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        })?;
+
+    if let Err(err) = save_pick::execute(
+        history_repo,
+        save_pick::Request {
+            event: req.event,
+            channel: req.channel,
+            user: req.user.clone(),
+            source: PickSource::Volunteer,
+            replaced: Some(req.replaced.clone()),
+            partner: latest.partner.clone(),
+        },
+    )
+    .await
+    {
+        log::error!(
+            "could not save pick history for event {}: {:?}",
+            req.event,
+            err
+        );
+    }
+
+    Ok(Response {
+        event_name: event.name,
+        user: req.user,
+        replaced: req.replaced,
+        partner: latest.partner,
+        left_count,
+        description: event.description,
+        url: event.url,
+        visibility: event.visibility,
+    })
+}
+
+/// Marks the volunteer as picked in place of the replaced participant, who is left to be picked.
+fn swap_pick(
+    participants: Vec<Participant>,
+    volunteer: &str,
+    replaced: &str,
+    now: i64,
+) -> Vec<Participant> {
+    let updated: Vec<Participant> = participants
+        .iter()
+        .filter(|p| p.user == volunteer || p.user == replaced)
+        .map(|p| Participant {
+            picked: p.user == volunteer,
+            picked_at: (p.user == volunteer).then_some(now),
+            ..p.clone()
+        })
+        .collect();
+    updated.into_iter().fold(participants, replace_participant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_pick() {
+        let participants = vec![
+            Participant {
+                picked: true,
+                picked_at: Some(100),
+                ..Participant::from(String::from("U1"))
+            },
+            Participant::from(String::from("U2")),
+            Participant::from(String::from("U3")),
+        ];
+        let result = swap_pick(participants, "U2", "U1", 200);
+        assert!(!result[0].picked);
+        assert_eq!(result[0].picked_at, None);
+        assert!(result[1].picked);
+        assert_eq!(result[1].picked_at, Some(200));
+        assert!(!result[2].picked);
+    }
+}
@@ -0,0 +1,25 @@
+use std::str::FromStr;
+
+use cron::Schedule;
+
+/// Parses a cron expression, accepting both the standard five fields format and the extended
+/// format with seconds.
+pub fn parse_schedule(expression: &str) -> Result<Schedule, cron::error::Error> {
+    let expression = expression.trim();
+    if expression.split_whitespace().count() == 5 {
+        return Schedule::from_str(&format!("0 {}", expression));
+    }
+    Schedule::from_str(expression)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_schedule() {
+        assert!(parse_schedule("0 9 * * Mon-Fri").is_ok());
+        assert!(parse_schedule("0 0 9 * * Mon,Wed").is_ok());
+        assert!(parse_schedule("every monday").is_err());
+    }
+}
@@ -1,2 +1,3 @@
+pub mod cron;
 pub mod participant;
-pub mod team;
+pub mod token;
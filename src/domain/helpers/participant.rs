@@ -1,5 +1,9 @@
-use crate::domain::entities::Participant;
-use rand::Rng;
+use crate::domain::entities::{Event, Participant, PickMode};
+use crate::helpers::date::Date;
+use chrono::{Datelike, NaiveDate};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 pub fn last_picked<'a, 'b>(picks: &'a Vec<Participant>) -> Option<&'a Participant>
 where
@@ -15,6 +19,18 @@ where
     });
 }
 
+/// Whether the value has the format of a Slack user ID (e.g. `U0797QD5AJZ`).
+pub fn is_user_id(value: &str) -> bool {
+    (USER_ID_MIN_LEN..=USER_ID_MAX_LEN).contains(&value.len())
+        && (value.starts_with('U') || value.starts_with('W'))
+        && value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+const USER_ID_MIN_LEN: usize = 9;
+const USER_ID_MAX_LEN: usize = 21;
+
 pub fn replace_participant(picks: Vec<Participant>, participant: Participant) -> Vec<Participant> {
     let mut picks = picks.clone();
     if let Some(p) = picks.iter_mut().find(|p| p.user == participant.user) {
@@ -23,23 +39,203 @@ pub fn replace_participant(picks: Vec<Participant>, participant: Participant) ->
     return picks;
 }
 
-pub fn pick_new<'a, 'b>(picks: &'a Vec<Participant>) -> Option<&'b Participant>
+/// Picks a new participant from the ones not picked yet.
+///
+/// The `avoid` participant is only picked when there is no one else left to pick. The random picks
+/// prefer the participants picked the longest ago, across the rounds, and never pick the ones
+/// without weight.
+pub fn pick_new<'a, 'b>(
+    picks: &'a [Participant],
+    mode: &PickMode,
+    avoid: Option<&str>,
+    rng: &mut impl Rng,
+) -> Option<&'b Participant>
 where
     'a: 'b,
 {
-    let unpicked = picks
+    let now = Date::now().timestamp();
+    let mut unpicked = picks
         .iter()
-        .filter(|participant| !participant.picked)
+        .filter(|participant| !participant.picked && !participant.is_away(now))
         .collect::<Vec<&Participant>>();
+    if !matches!(mode, PickMode::RoundRobin) {
+        unpicked.retain(|participant| participant.weight() > 0.0);
+    }
     if unpicked.len() == 0 {
         return None;
     }
-    let random_index = rand::thread_rng().gen_range(0..unpicked.len());
-    return Some(unpicked[random_index]);
+    if let Some(avoid) = avoid {
+        if unpicked.len() > 1 {
+            unpicked.retain(|participant| participant.user != avoid);
+        }
+    }
+    if let PickMode::RoundRobin = mode {
+        return rotation(unpicked).into_iter().next();
+    }
+    let distribution = match WeightedIndex::new(
+        unpicked
+            .iter()
+            .zip(fairness(&unpicked))
+            .map(|(participant, fairness)| participant.weight() * fairness),
+    ) {
+        Ok(distribution) => distribution,
+        Err(err) => {
+            log::warn!(
+                "could not pick from the weights of the participants: {}",
+                err
+            );
+            return None;
+        }
+    };
+    Some(unpicked[distribution.sample(rng)])
+}
+
+/// The generator of the random picks of an event on a day (on the event's timezone). With a seed,
+/// the picks are reproducible: the same seed, event and day always draw the same numbers, so
+/// anyone can verify them. Without it, the picks are unpredictable.
+pub fn pick_rng(seed: Option<u32>, event_id: u32, day: NaiveDate) -> StdRng {
+    let seed = match seed {
+        Some(seed) => seed,
+        None => return StdRng::from_entropy(),
+    };
+    let mut bytes = [0u8; 32];
+    bytes[..4].copy_from_slice(&seed.to_le_bytes());
+    bytes[4..8].copy_from_slice(&event_id.to_le_bytes());
+    bytes[8..12].copy_from_slice(&day.num_days_from_ce().to_le_bytes());
+    StdRng::from_seed(bytes)
+}
+
+/// The generator of the random picks of the event happening now.
+pub fn event_rng(event: &Event) -> StdRng {
+    let today = Date::now()
+        .with_timezone(event.timezone.clone())
+        .to_datetime()
+        .date_naive();
+    pick_rng(event.seed, event.id, today)
+}
+
+/// The factors the weights of the participants are multiplied by, so that the ones picked the
+/// longest ago are preferred. The latest picked participant keeps its weight, while each one
+/// picked before it (or never picked) has it increased by its weight once more, so that the
+/// part-timers waiting as long as the others are still picked half as often.
+fn fairness(picks: &[&Participant]) -> Vec<f64> {
+    picks
+        .iter()
+        .map(|participant| {
+            let newer = picks
+                .iter()
+                .filter(|other| match (other.picked_at, participant.picked_at) {
+                    (Some(other), Some(picked_at)) => other > picked_at,
+                    (Some(..), None) => true,
+                    (None, ..) => false,
+                })
+                .count();
+            1.0 + participant.weight() * newer as f64
+        })
+        .collect()
+}
+
+/// Sorts the participants by their position on the rotation of the round-robin events.
+pub fn rotation<'a, I>(picks: I) -> Vec<&'a Participant>
+where
+    I: IntoIterator<Item = &'a Participant>,
+{
+    let mut picks = picks.into_iter().collect::<Vec<&Participant>>();
+    picks.sort_by_key(|participant| participant.order.unwrap_or(u32::MAX));
+    picks
+}
+
+/// Moves the participant one position up or down on the rotation, setting the positions of all
+/// the participants. Returns `None` when the user is not a participant.
+pub fn move_in_rotation(picks: Vec<Participant>, user: &str, up: bool) -> Option<Vec<Participant>> {
+    let mut users = rotation(&picks)
+        .into_iter()
+        .map(|participant| participant.user.clone())
+        .collect::<Vec<String>>();
+    let index = users.iter().position(|u| u == user)?;
+    match up {
+        true if index > 0 => users.swap(index, index - 1),
+        false if index + 1 < users.len() => users.swap(index, index + 1),
+        _ => (),
+    }
+    Some(
+        picks
+            .into_iter()
+            .map(|participant| Participant {
+                order: users
+                    .iter()
+                    .position(|u| *u == participant.user)
+                    .map(|position| position as u32),
+                ..participant
+            })
+            .collect(),
+    )
+}
+
+/// Picks two participants that were not picked together on the current round of pairs, in the
+/// order they were added in.
+///
+/// The `paired` pairs are the ones picked before, oldest first. Once all the pairs of the
+/// participants are picked, a new round starts, avoiding the latest pair when possible.
+pub fn pick_pair<'a>(
+    picks: &'a [Participant],
+    paired: &[(String, String)],
+    rng: &mut impl Rng,
+) -> Option<(&'a Participant, &'a Participant)> {
+    let now = Date::now().timestamp();
+    let available = picks
+        .iter()
+        .filter(|participant| !participant.is_away(now))
+        .collect::<Vec<&Participant>>();
+    let mut pairs = vec![];
+    for (i, first) in available.iter().enumerate() {
+        for second in available[i + 1..].iter() {
+            pairs.push((*first, *second));
+        }
+    }
+    if pairs.is_empty() {
+        return None;
+    }
+
+    let is_round_over = |round: &[&(String, String)]| {
+        pairs.iter().all(|(first, second)| {
+            round
+                .iter()
+                .any(|pair| is_pair(pair, &first.user, &second.user))
+        })
+    };
+    let mut round: Vec<&(String, String)> = vec![];
+    for pair in paired.iter() {
+        if is_round_over(&round) || round.iter().any(|p| is_pair(p, &pair.0, &pair.1)) {
+            round.clear();
+        }
+        round.push(pair);
+    }
+    if is_round_over(&round) {
+        round = paired.last().into_iter().collect();
+    }
+
+    let mut candidates = pairs
+        .iter()
+        .filter(|(first, second)| !round.iter().any(|p| is_pair(p, &first.user, &second.user)))
+        .collect::<Vec<&(&Participant, &Participant)>>();
+    if candidates.is_empty() {
+        candidates = pairs.iter().collect();
+    }
+    let random_index = rng.gen_range(0..candidates.len());
+    Some(*candidates[random_index])
+}
+
+fn is_pair(pair: &(String, String), first: &str, second: &str) -> bool {
+    (pair.0 == first && pair.1 == second) || (pair.0 == second && pair.1 == first)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use crate::domain::entities::PART_TIME_WEIGHT;
+
     use super::*;
 
     #[test]
@@ -50,21 +246,318 @@ mod tests {
                 picked: false,
                 created_at: 1723822080,
                 picked_at: None,
+                weight: None,
+                unavailable_until: None,
+                order: None,
             },
             Participant {
                 user: String::from("USLACKBOT"),
                 picked: true,
                 created_at: 1723822080,
                 picked_at: Some(1724681700),
+                weight: None,
+                unavailable_until: None,
+                order: None,
             },
             Participant {
                 user: String::from("U0797QD5AJZ"),
                 picked: true,
                 created_at: 1723822080,
                 picked_at: Some(1724681760),
+                weight: None,
+                unavailable_until: None,
+                order: None,
             },
         ];
         let last_picked = last_picked(&picks);
         assert_eq!(last_picked.unwrap().user, "U0797QD5AJZ");
     }
+
+    #[test]
+    fn test_is_user_id() {
+        assert!(is_user_id("U0797QD5AJZ"));
+        assert!(is_user_id("USLACKBOT"));
+        assert!(is_user_id("W012A3CDE"));
+        assert!(!is_user_id("C0797QD5AJZ"));
+        assert!(!is_user_id("u0797qd5ajz"));
+        assert!(!is_user_id("<@U0797QD5AJZ>"));
+        assert!(!is_user_id("U1"));
+    }
+
+    #[test]
+    fn test_pick_new_ignores_zero_weight() {
+        let picks = vec![
+            Participant {
+                user: String::from("U04PGARU4K1"),
+                picked: false,
+                created_at: 1723822080,
+                picked_at: None,
+                weight: Some(0.0),
+                unavailable_until: None,
+                order: None,
+            },
+            Participant {
+                user: String::from("U0797QD5AJZ"),
+                picked: false,
+                created_at: 1723822080,
+                picked_at: None,
+                weight: Some(0.5),
+                unavailable_until: None,
+                order: None,
+            },
+        ];
+        for _ in 0..20 {
+            assert_eq!(
+                pick_new(&picks, &PickMode::Random, None, &mut rand::thread_rng())
+                    .unwrap()
+                    .user,
+                "U0797QD5AJZ"
+            );
+        }
+
+        // The round is over once only the participants without weight are left.
+        let picks = replace_participant(
+            picks.clone(),
+            Participant {
+                picked: true,
+                ..picks[1].clone()
+            },
+        );
+        assert_eq!(
+            pick_new(&picks, &PickMode::Random, None, &mut rand::thread_rng()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pick_new_keeps_part_time_ratio() {
+        let mut picks: Vec<Participant> = vec![
+            String::from("U04PGARU4K1").into(),
+            String::from("U0797QD5AJZ").into(),
+            Participant {
+                weight: Some(PART_TIME_WEIGHT),
+                ..String::from("USLACKBOT").into()
+            },
+        ];
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for round in 0..3000 {
+            let pick = pick_new(&picks, &PickMode::Random, None, &mut rng)
+                .unwrap()
+                .clone();
+            *counts.entry(pick.user.clone()).or_default() += 1;
+            picks = replace_participant(
+                picks,
+                Participant {
+                    picked_at: Some(round),
+                    ..pick
+                },
+            );
+        }
+
+        // The part-timer is picked about half as often as each of the others.
+        for user in ["U04PGARU4K1", "U0797QD5AJZ"] {
+            let ratio = counts["USLACKBOT"] as f64 / counts[user] as f64;
+            assert!((0.4..0.6).contains(&ratio), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_pick_new_skips_away_participants() {
+        let picks = vec![
+            Participant {
+                user: String::from("U04PGARU4K1"),
+                picked: false,
+                created_at: 1723822080,
+                picked_at: None,
+                weight: None,
+                unavailable_until: Some(Date::now().timestamp() + 3600),
+                order: None,
+            },
+            Participant {
+                user: String::from("U0797QD5AJZ"),
+                picked: false,
+                created_at: 1723822080,
+                picked_at: None,
+                weight: None,
+                unavailable_until: Some(Date::now().timestamp() - 3600),
+                order: None,
+            },
+        ];
+        for _ in 0..20 {
+            assert_eq!(
+                pick_new(&picks, &PickMode::Random, None, &mut rand::thread_rng())
+                    .unwrap()
+                    .user,
+                "U0797QD5AJZ"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pick_new_round_robin() {
+        let mut picks: Vec<Participant> = vec![
+            String::from("U04PGARU4K1").into(),
+            String::from("U0797QD5AJZ").into(),
+            String::from("USLACKBOT").into(),
+        ];
+        for expected in ["U04PGARU4K1", "U0797QD5AJZ", "USLACKBOT"] {
+            let pick = pick_new(&picks, &PickMode::RoundRobin, None, &mut rand::thread_rng())
+                .unwrap()
+                .clone();
+            assert_eq!(pick.user, expected);
+            picks = replace_participant(
+                picks,
+                Participant {
+                    picked: true,
+                    ..pick
+                },
+            );
+        }
+        assert_eq!(
+            pick_new(&picks, &PickMode::RoundRobin, None, &mut rand::thread_rng()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pick_new_avoids_participant() {
+        let picks: Vec<Participant> = vec![
+            String::from("U04PGARU4K1").into(),
+            String::from("U0797QD5AJZ").into(),
+        ];
+        for _ in 0..20 {
+            assert_eq!(
+                pick_new(
+                    &picks,
+                    &PickMode::Random,
+                    Some("U04PGARU4K1"),
+                    &mut rand::thread_rng()
+                )
+                .unwrap()
+                .user,
+                "U0797QD5AJZ"
+            );
+        }
+
+        let picks: Vec<Participant> = vec![String::from("U04PGARU4K1").into()];
+        assert_eq!(
+            pick_new(
+                &picks,
+                &PickMode::Random,
+                Some("U04PGARU4K1"),
+                &mut rand::thread_rng()
+            )
+            .unwrap()
+            .user,
+            "U04PGARU4K1"
+        );
+    }
+
+    #[test]
+    fn test_pick_rng_is_reproducible() {
+        let picks: Vec<Participant> = (0..10)
+            .map(|i| Participant::from(format!("U{}", i)))
+            .collect();
+        let day = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        let draw = |seed: u32, event_id: u32, day: NaiveDate| {
+            let mut rng = pick_rng(Some(seed), event_id, day);
+            (0..5)
+                .map(|_| {
+                    pick_new(&picks, &PickMode::Random, None, &mut rng)
+                        .unwrap()
+                        .user
+                        .clone()
+                })
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(draw(42, 1, day), draw(42, 1, day));
+        assert_ne!(draw(42, 1, day), draw(43, 1, day));
+        assert_ne!(draw(42, 1, day), draw(42, 2, day));
+        assert_ne!(draw(42, 1, day), draw(42, 1, day.succ_opt().unwrap()));
+    }
+
+    #[test]
+    fn test_pick_pair_exhausts_pairs() {
+        let picks: Vec<Participant> = vec![
+            String::from("U04PGARU4K1").into(),
+            String::from("U0797QD5AJZ").into(),
+            String::from("USLACKBOT").into(),
+        ];
+        let mut paired: Vec<(String, String)> = vec![];
+        for _ in 0..3 {
+            let (first, second) = pick_pair(&picks, &paired, &mut rand::thread_rng()).unwrap();
+            assert!(!paired
+                .iter()
+                .any(|pair| is_pair(pair, &first.user, &second.user)));
+            paired.push((first.user.clone(), second.user.clone()));
+        }
+
+        // A new round starts, avoiding the latest pair and then the pairs of the new round.
+        let latest = paired.last().unwrap().clone();
+        let (first, second) = pick_pair(&picks, &paired, &mut rand::thread_rng()).unwrap();
+        assert!(!is_pair(&latest, &first.user, &second.user));
+        paired.push((first.user.clone(), second.user.clone()));
+        let (third, fourth) = pick_pair(&picks, &paired, &mut rand::thread_rng()).unwrap();
+        assert!(!is_pair(
+            &(first.user.clone(), second.user.clone()),
+            &third.user,
+            &fourth.user
+        ));
+
+        let picks: Vec<Participant> = vec![String::from("U04PGARU4K1").into()];
+        assert_eq!(pick_pair(&picks, &[], &mut rand::thread_rng()), None);
+    }
+
+    #[test]
+    fn test_move_in_rotation() {
+        let picks: Vec<Participant> = vec![
+            String::from("U04PGARU4K1").into(),
+            String::from("U0797QD5AJZ").into(),
+            String::from("USLACKBOT").into(),
+        ];
+        let picks = move_in_rotation(picks, "USLACKBOT", true).unwrap();
+        let users: Vec<&str> = rotation(&picks).iter().map(|p| p.user.as_str()).collect();
+        assert_eq!(users, vec!["U04PGARU4K1", "USLACKBOT", "U0797QD5AJZ"]);
+        assert_eq!(
+            pick_new(
+                &picks,
+                &PickMode::RoundRobin,
+                Some("U04PGARU4K1"),
+                &mut rand::thread_rng()
+            )
+            .unwrap()
+            .user,
+            "USLACKBOT"
+        );
+
+        let picks = move_in_rotation(picks, "U04PGARU4K1", true).unwrap();
+        let users: Vec<&str> = rotation(&picks).iter().map(|p| p.user.as_str()).collect();
+        assert_eq!(users, vec!["U04PGARU4K1", "USLACKBOT", "U0797QD5AJZ"]);
+        assert!(move_in_rotation(picks, "U1", false).is_none());
+    }
+
+    #[test]
+    fn test_fairness() {
+        let picks: Vec<Participant> = vec![
+            Participant {
+                picked_at: Some(1723822080),
+                ..String::from("U04PGARU4K1").into()
+            },
+            Participant {
+                picked_at: Some(1724681760),
+                ..String::from("U0797QD5AJZ").into()
+            },
+            String::from("USLACKBOT").into(),
+        ];
+        let picks: Vec<&Participant> = picks.iter().collect();
+        assert_eq!(fairness(&picks), vec![2.0, 1.0, 3.0]);
+
+        let part_timer = Participant {
+            weight: Some(PART_TIME_WEIGHT),
+            ..String::from("U1").into()
+        };
+        let picks = [picks[1], &part_timer];
+        assert_eq!(fairness(&picks), vec![1.0, 1.5]);
+    }
 }
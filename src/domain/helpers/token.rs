@@ -0,0 +1,13 @@
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Generates a random opaque token.
+pub fn generate() -> String {
+    let token: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(token)
+}
+
+/// Hashes a token, so that it is stored without its value.
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::repository::errors::FindAllError;
+use crate::repository::pick_history::Repository;
+
+use super::find_picks::Response;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Finds all the picks of an event, from the oldest to the latest.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let mut picks = repo
+        .find_all_picks(req.event, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindAllError::Unknown => Error::Unknown,
+        })?;
+    picks.sort_by_key(|pick| pick.timestamp);
+    Ok(picks.into_iter().map(|pick| pick.into()).collect())
+}
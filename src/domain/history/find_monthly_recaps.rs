@@ -0,0 +1,279 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{Datelike, Months, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{AuditAction, AuditRecord, PickRecord, PickSource};
+use crate::helpers::secret::SecretString;
+use crate::repository::{audit, event, pick_history, settings};
+
+pub struct Request {
+    /// The first day of the month to recap, on UTC.
+    pub month: NaiveDate,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub recaps: Vec<Recap>,
+}
+
+/// The recap of the picks of a channel during a month.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Recap {
+    pub team_id: String,
+    pub channel_id: String,
+    /// The first day of the recapped month.
+    pub month: NaiveDate,
+    pub picks: u32,
+    pub skips: u32,
+    /// The most picked participants, most picked first.
+    pub leaderboard: Vec<Leader>,
+    /// The automatic picks acknowledged by their participants.
+    pub acknowledged: u32,
+    /// The automatic picks repicked as they were not acknowledged in time.
+    pub unacknowledged: u32,
+    /// Looked up again when the notification is posted, so that it is not stored with it.
+    #[serde(skip)]
+    pub access_token: SecretString,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Leader {
+    pub user: String,
+    pub picks: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Recaps the picks of the month on the channels of the teams that enabled the monthly recap. The
+/// channels with no picks during the month are not recapped.
+pub async fn execute(
+    settings_repo: Arc<dyn settings::Repository>,
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let teams: HashSet<String> = settings_repo
+        .find_all_settings_unprotected()
+        .await
+        .map_err(|err| {
+            log::error!("could not find the settings of the teams: {:?}", err);
+            Error::Unknown
+        })?
+        .into_iter()
+        .filter(|settings| settings.monthly_recap)
+        .map(|settings| settings.team_id)
+        .collect();
+    if teams.is_empty() {
+        return Ok(Response { recaps: vec![] });
+    }
+
+    let channels: BTreeSet<(String, String)> = event_repo
+        .find_all_events_unprotected()
+        .await
+        .map_err(|err| {
+            log::error!("could not find the events to recap: {:?}", err);
+            Error::Unknown
+        })?
+        .into_iter()
+        .filter(|event| teams.contains(&event.team_id))
+        .map(|event| (event.team_id, event.channel))
+        .collect();
+
+    let (from, to) = month_range(req.month);
+    let mut recaps: Vec<Recap> = vec![];
+    for (team_id, channel_id) in channels.into_iter() {
+        let picks = match history_repo
+            .find_channel_picks(channel_id.clone(), from, to)
+            .await
+        {
+            Ok(picks) if picks.is_empty() => continue,
+            Ok(picks) => picks,
+            Err(err) => {
+                log::error!(
+                    "could not find the picks of channel {} to recap: {:?}",
+                    channel_id,
+                    err
+                );
+                continue;
+            }
+        };
+        let records = audit_repo
+            .find_records_between(team_id.clone(), channel_id.clone(), from, to)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!(
+                    "could not find the records of channel {} to recap: {:?}",
+                    channel_id,
+                    err
+                );
+                vec![]
+            });
+        recaps.push(recap(team_id, channel_id, req.month, &picks, &records));
+    }
+
+    Ok(Response { recaps })
+}
+
+/// The first day of the month that ended right at the timestamp, when it is the first minute of
+/// a month on UTC.
+pub fn month_ended_at(timestamp: i64) -> Option<NaiveDate> {
+    let date = Utc.timestamp_opt(timestamp, 0).single()?;
+    if date.day() != 1 || date.time() != NaiveTime::MIN {
+        return None;
+    }
+    date.date_naive().checked_sub_months(Months::new(1))
+}
+
+/// The timestamps of the start of the month and of the next one.
+fn month_range(month: NaiveDate) -> (i64, i64) {
+    let start = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc().timestamp();
+    let next = month
+        .checked_add_months(Months::new(1))
+        .unwrap_or(NaiveDate::MAX);
+    (start(month), start(next))
+}
+
+/// Counts the picks of the channel, like the stats of its events: the picks reverted by a repick
+/// are not counted for the replaced participant, and the pairs count for both participants.
+fn recap(
+    team_id: String,
+    channel_id: String,
+    month: NaiveDate,
+    picks: &[PickRecord],
+    records: &[AuditRecord],
+) -> Recap {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    let mut skips = 0;
+    for pick in picks.iter() {
+        for user in std::iter::once(&pick.user).chain(pick.partner.as_ref()) {
+            *counts.entry(user).or_default() += 1;
+        }
+        match (&pick.source, &pick.replaced) {
+            (PickSource::Repick | PickSource::Volunteer, Some(replaced)) => {
+                if let Some(count) = counts.get_mut(replaced.as_str()) {
+                    *count = count.saturating_sub(1);
+                }
+            }
+            (PickSource::Skip, _) => skips += 1,
+            _ => (),
+        }
+    }
+
+    let mut leaderboard: Vec<Leader> = counts
+        .into_iter()
+        .filter(|(_, picks)| *picks > 0)
+        .map(|(user, picks)| Leader {
+            user: user.to_string(),
+            picks,
+        })
+        .collect();
+    leaderboard.sort_by(|a, b| b.picks.cmp(&a.picks).then_with(|| a.user.cmp(&b.user)));
+    leaderboard.truncate(LEADERBOARD_SIZE);
+
+    let count = |action: AuditAction| {
+        records
+            .iter()
+            .filter(|record| record.action == action)
+            .count() as u32
+    };
+    Recap {
+        team_id,
+        channel_id,
+        month,
+        picks: picks.len() as u32,
+        skips,
+        leaderboard,
+        acknowledged: count(AuditAction::Acknowledge),
+        unacknowledged: count(AuditAction::AcknowledgmentExpired),
+        access_token: SecretString::default(),
+    }
+}
+
+/// The number of participants shown on the leaderboard of the recaps.
+const LEADERBOARD_SIZE: usize = 5;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pick(user: &str, source: PickSource, replaced: Option<&str>) -> PickRecord {
+        PickRecord {
+            event: 1,
+            channel: String::from("C1"),
+            user: String::from(user),
+            timestamp: 0,
+            source,
+            replaced: replaced.map(String::from),
+            partner: None,
+            no_show: false,
+        }
+    }
+
+    #[test]
+    fn test_month_ended_at() {
+        let month = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let (from, to) = month_range(month);
+        assert_eq!(month_ended_at(to), Some(month));
+        assert_eq!(month_ended_at(to + 60), None);
+        assert_eq!(month_ended_at(from), NaiveDate::from_ymd_opt(2024, 1, 1));
+        assert_eq!(to - from, 29 * 24 * 60 * 60);
+    }
+
+    #[test]
+    fn test_recap() {
+        let picks = vec![
+            pick("U1", PickSource::Auto, None),
+            pick("U2", PickSource::Skip, Some("U1")),
+            pick("U3", PickSource::Repick, Some("U2")),
+            pick("U2", PickSource::Manual, None),
+        ];
+        let record = |action| AuditRecord {
+            team_id: String::from("T1"),
+            channel: Some(String::from("C1")),
+            user: None,
+            action,
+            event: Some(1),
+            timestamp: 0,
+        };
+        let records = vec![
+            record(AuditAction::Acknowledge),
+            record(AuditAction::Pick),
+            record(AuditAction::AcknowledgmentExpired),
+        ];
+        let month = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+        let recap = recap(
+            String::from("T1"),
+            String::from("C1"),
+            month,
+            &picks,
+            &records,
+        );
+        assert_eq!(recap.picks, 4);
+        assert_eq!(recap.skips, 1);
+        assert_eq!(
+            recap.leaderboard,
+            vec![
+                Leader {
+                    user: String::from("U1"),
+                    picks: 1
+                },
+                Leader {
+                    user: String::from("U2"),
+                    picks: 1
+                },
+                Leader {
+                    user: String::from("U3"),
+                    picks: 1
+                },
+            ]
+        );
+        assert_eq!((recap.acknowledged, recap.unacknowledged), (1, 1));
+    }
+}
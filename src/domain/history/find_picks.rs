@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::{PickRecord, PickSource};
+use crate::repository::errors::FindAllError;
+use crate::repository::pick_history::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    pub limit: u32,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Response {
+    pub user: String,
+    pub timestamp: i64,
+    pub source: PickSource,
+    pub replaced: Option<String>,
+    pub partner: Option<String>,
+    pub no_show: bool,
+}
+
+impl From<PickRecord> for Response {
+    fn from(value: PickRecord) -> Self {
+        Self {
+            user: value.user,
+            timestamp: value.timestamp,
+            source: value.source,
+            replaced: value.replaced,
+            partner: value.partner,
+            no_show: value.no_show,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<ListResponse<Response>, Error> {
+    let picks = match repo.find_picks(req.event, req.channel, req.limit).await {
+        Err(err) => {
+            return match err {
+                FindAllError::Unknown => Err(Error::Unknown),
+            }
+        }
+        Ok(picks) => picks,
+    };
+    Ok(ListResponse::new(
+        picks.into_iter().map(|pick| pick.into()).collect(),
+    ))
+}
@@ -0,0 +1,4 @@
+pub mod find_all_picks;
+pub mod find_monthly_recaps;
+pub mod find_picks;
+pub mod save_pick;
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{PickRecord, PickSource};
+use crate::helpers::date::Date;
+use crate::repository::errors::InsertError;
+use crate::repository::pick_history::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    pub user: String,
+    pub source: PickSource,
+    pub replaced: Option<String>,
+    pub partner: Option<String>,
+}
+
+impl From<Request> for PickRecord {
+    fn from(value: Request) -> Self {
+        Self {
+            event: value.event,
+            channel: value.channel,
+            user: value.user,
+            timestamp: Date::now().timestamp(),
+            source: value.source,
+            replaced: value.replaced,
+            partner: value.partner,
+            no_show: false,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Unknown,
+}
+
+impl From<InsertError> for Error {
+    fn from(value: InsertError) -> Self {
+        match value {
+            InsertError::Conflict | InsertError::Unknown => Error::Unknown,
+        }
+    }
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<PickRecord, Error> {
+    Ok(repo.insert_pick(req.into()).await?)
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+use crate::domain::entities::{Holiday, HOLIDAY_DATE_FORMAT};
+use crate::repository::errors::InsertError;
+use crate::repository::holiday::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    pub date: String,
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest,
+    Conflict,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Holiday, Error> {
+    let date = NaiveDate::parse_from_str(&req.date, HOLIDAY_DATE_FORMAT).map_err(|err| {
+        log::trace!("could not parse holiday date {}: {}", req.date, err);
+        Error::BadRequest
+    })?;
+
+    repo.insert_holiday(Holiday {
+        team_id: req.team_id,
+        date: date.format(HOLIDAY_DATE_FORMAT).to_string(),
+        name: req.name,
+    })
+    .await
+    .map_err(|err| match err {
+        InsertError::Conflict => Error::Conflict,
+        InsertError::Unknown => Error::Unknown,
+    })
+}
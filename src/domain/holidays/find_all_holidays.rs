@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::Holiday;
+use crate::repository::errors::FindAllError;
+use crate::repository::holiday::Repository;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>) -> Result<ListResponse<Holiday>, Error> {
+    match repo.find_all_holidays_unprotected().await {
+        Ok(holidays) => Ok(ListResponse::new(holidays)),
+        Err(FindAllError::Unknown) => Err(Error::Unknown),
+    }
+}
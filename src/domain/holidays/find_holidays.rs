@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::Holiday;
+use crate::repository::errors::FindAllError;
+use crate::repository::holiday::Repository;
+
+pub struct Request {
+    pub team_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<ListResponse<Holiday>, Error> {
+    match repo.find_holidays(req.team_id).await {
+        Ok(holidays) => Ok(ListResponse::new(holidays)),
+        Err(FindAllError::Unknown) => Err(Error::Unknown),
+    }
+}
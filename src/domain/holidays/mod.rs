@@ -0,0 +1,4 @@
+pub mod add_holiday;
+pub mod find_all_holidays;
+pub mod find_holidays;
+pub mod remove_holiday;
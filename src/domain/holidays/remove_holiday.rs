@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::domain::entities::Holiday;
+use crate::repository::errors::DeleteError;
+use crate::repository::holiday::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    pub date: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Holiday, Error> {
+    repo.delete_holiday(req.team_id, req.date)
+        .await
+        .map_err(|err| match err {
+            DeleteError::NotFound => Error::NotFound,
+            DeleteError::Unknown => Error::Unknown,
+        })
+}
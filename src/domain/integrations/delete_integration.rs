@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::domain::entities::IntegrationProvider;
+use crate::repository::errors::DeleteError;
+use crate::repository::integration::Repository;
+
+pub struct Request {
+    pub team: String,
+    pub user: String,
+    pub provider: IntegrationProvider,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+/// Disconnects the service from the user, forgetting their tokens.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    repo.delete_integration(req.team, req.user, req.provider)
+        .await
+        .map(|_| ())
+        .map_err(|err| match err {
+            DeleteError::NotFound => Error::NotFound,
+            DeleteError::Unknown => Error::Unknown,
+        })
+}
@@ -0,0 +1,2 @@
+pub mod delete_integration;
+pub mod save_integration;
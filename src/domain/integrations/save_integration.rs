@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Integration, IntegrationProvider};
+use crate::helpers::date::Date;
+use crate::helpers::secret::SecretString;
+use crate::repository::integration::Repository;
+
+pub struct Request {
+    pub team: String,
+    pub user: String,
+    pub provider: IntegrationProvider,
+    pub access_token: SecretString,
+    pub refresh_token: SecretString,
+    /// The number of seconds the access token is valid for.
+    pub expires_in: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Connects the service to the user, replacing the connection they had to it before.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Integration, Error> {
+    let now = Date::now().timestamp();
+    repo.save_integration(Integration {
+        team: req.team,
+        user: req.user,
+        provider: req.provider,
+        access_token: req.access_token,
+        refresh_token: req.refresh_token,
+        expires_at: now + req.expires_in,
+        created_at: now,
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the integration: {:?}", err);
+        Error::Unknown
+    })
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// The language the messages of a team are shown in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Pt,
+}
+
+impl Locale {
+    pub fn all() -> [Locale; 3] {
+        [Locale::En, Locale::Es, Locale::Pt]
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Pt => "pt",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+            Locale::Pt => "Português",
+        }
+    }
+
+    /// The locale the names of the days and the months are formatted with.
+    pub fn chrono(&self) -> chrono::Locale {
+        match self {
+            Locale::En => chrono::Locale::en_US,
+            Locale::Es => chrono::Locale::es_ES,
+            Locale::Pt => chrono::Locale::pt_PT,
+        }
+    }
+}
+
+impl TryFrom<String> for Locale {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Locale::all()
+            .into_iter()
+            .find(|locale| locale.code() == value.to_lowercase())
+            .ok_or(())
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
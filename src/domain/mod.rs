@@ -1,8 +1,21 @@
+pub mod acknowledgments;
+pub mod audit;
 pub mod auth;
+pub mod billing;
+pub mod calendar_feeds;
+pub mod date_format;
 pub mod dtos;
 pub mod entities;
 pub mod events;
 pub mod helpers;
+pub mod history;
+pub mod holidays;
+pub mod integrations;
+pub mod locale;
+pub mod notifications;
+pub mod participants;
+pub mod scheduler;
+pub mod settings;
 pub mod timezone;
 
 // Commands
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::domain::entities::NotificationJob;
+use crate::helpers::date::Date;
+use crate::repository::notification::Repository;
+
+pub struct Request {
+    /// The seconds the job is held for, after which another worker can claim it.
+    pub lock_seconds: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Claims the next notification due, if any.
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Option<NotificationJob>, Error> {
+    let now = Date::now().timestamp();
+    repo.claim_job(now, now + req.lock_seconds)
+        .await
+        .map_err(|err| {
+            log::error!("could not claim a notification: {:?}", err);
+            Error::Unknown
+        })
+}
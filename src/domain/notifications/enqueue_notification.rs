@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{NotificationJob, NotificationKind};
+use crate::helpers::date::Date;
+use crate::repository::notification::Repository;
+
+pub struct Request {
+    pub kind: NotificationKind,
+    pub team_id: String,
+    pub payload: serde_json::Value,
+    /// The timestamp (in seconds) to post the notification at, right away when unset.
+    pub run_at: Option<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Queues the notification to be posted by the workers, right away unless it is held back.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<NotificationJob, Error> {
+    repo.insert_job(NotificationJob {
+        id: 0,
+        kind: req.kind,
+        team_id: req.team_id,
+        payload: req.payload,
+        attempts: 0,
+        run_at: req.run_at.unwrap_or_else(|| Date::now().timestamp()),
+        locked_until: None,
+        last_error: None,
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not queue the notification: {:?}", err);
+        Error::Unknown
+    })
+}
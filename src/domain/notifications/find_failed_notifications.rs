@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::FailedNotification;
+use crate::repository::errors::FindAllError;
+use crate::repository::notification::Repository;
+
+pub struct Request {
+    /// The team of the notifications, or all of them when not set.
+    pub team_id: Option<String>,
+    pub limit: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<ListResponse<FailedNotification>, Error> {
+    match repo.find_all_failed(req.team_id, req.limit).await {
+        Ok(notifications) => Ok(ListResponse::new(notifications)),
+        Err(FindAllError::Unknown) => Err(Error::Unknown),
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FailedNotification, NotificationJob};
+use crate::helpers::date::Date;
+use crate::repository::notification::Repository;
+
+pub struct Request {
+    pub job: NotificationJob,
+    /// The error of the attempt, when it failed.
+    pub error: Option<String>,
+    /// Whether retrying the notification would fail again (e.g. the channel was archived).
+    pub permanent: bool,
+    /// The number of attempts of each notification, including the first one.
+    pub max_attempts: u32,
+    /// The seconds before retrying a failed notification, doubled on each retry.
+    pub base_delay_seconds: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Response {
+    /// The notification was posted and removed from the queue.
+    Done,
+    /// The notification is retried at the timestamp (in seconds).
+    Retried(i64),
+    /// The notification could not be posted, either because it failed permanently or ran out of
+    /// attempts, and was moved to the dead-letter collection.
+    Failed(FailedNotification),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Removes the notification from the queue once posted, or schedules its retry when it failed.
+/// The notifications that cannot be posted are moved to the dead-letter collection.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let mut job = req.job;
+    let Some(error) = req.error else {
+        repo.delete_job(job.id).await.map_err(|err| {
+            log::error!("could not remove notification {}: {:?}", job.id, err);
+            Error::Unknown
+        })?;
+        return Ok(Response::Done);
+    };
+
+    let now = Date::now().timestamp();
+    job.attempts += 1;
+    if req.permanent || job.attempts >= req.max_attempts {
+        let failed = FailedNotification {
+            id: job.id,
+            kind: job.kind,
+            team_id: job.team_id,
+            payload: job.payload,
+            attempts: job.attempts,
+            error,
+            failed_at: now,
+        };
+        let failed = repo.fail_job(failed).await.map_err(|err| {
+            log::error!("could not save the failed notification: {:?}", err);
+            Error::Unknown
+        })?;
+        return Ok(Response::Failed(failed));
+    }
+
+    job.last_error = Some(error);
+    job.locked_until = None;
+    job.run_at = now + backoff(req.base_delay_seconds, job.attempts);
+    let job = repo.update_job(job).await.map_err(|err| {
+        log::error!("could not save the failed notification: {:?}", err);
+        Error::Unknown
+    })?;
+    Ok(Response::Retried(job.run_at))
+}
+
+/// The seconds before the next attempt, doubled on each one up to an hour.
+fn backoff(base_delay_seconds: i64, attempts: u32) -> i64 {
+    let factor = 2_i64.saturating_pow(attempts.saturating_sub(1));
+    base_delay_seconds
+        .saturating_mul(factor)
+        .min(MAX_DELAY_SECONDS)
+}
+
+const MAX_DELAY_SECONDS: i64 = 60 * 60;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(30, 1), 30);
+        assert_eq!(backoff(30, 2), 60);
+        assert_eq!(backoff(30, 4), 240);
+        assert_eq!(backoff(30, 20), MAX_DELAY_SECONDS);
+        assert_eq!(backoff(30, 200), MAX_DELAY_SECONDS);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod claim_notification;
+pub mod enqueue_notification;
+pub mod find_failed_notifications;
+pub mod finish_notification;
+pub mod redrive_notification;
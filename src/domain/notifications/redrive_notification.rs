@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::domain::entities::NotificationJob;
+use crate::helpers::date::Date;
+use crate::repository::errors::{DeleteError, FindError};
+use crate::repository::notification::Repository;
+
+pub struct Request {
+    pub id: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+/// Queues a failed notification again, with all its attempts, to be posted right away. It is
+/// removed from the dead-letter collection once queued.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<NotificationJob, Error> {
+    let failed = repo.find_failed(req.id).await.map_err(|err| match err {
+        FindError::NotFound => Error::NotFound,
+        FindError::Unknown => Error::Unknown,
+    })?;
+
+    let job = repo
+        .insert_job(NotificationJob {
+            id: 0,
+            kind: failed.kind,
+            team_id: failed.team_id,
+            payload: failed.payload,
+            attempts: 0,
+            run_at: Date::now().timestamp(),
+            locked_until: None,
+            last_error: None,
+        })
+        .await
+        .map_err(|err| {
+            log::error!("could not queue failed notification {}: {:?}", req.id, err);
+            Error::Unknown
+        })?;
+
+    match repo.delete_failed(req.id).await {
+        // Another operator re-drove it meanwhile, and it is posted by both jobs otherwise.
+        Err(DeleteError::NotFound) => {
+            if let Err(err) = repo.delete_job(job.id).await {
+                log::error!(
+                    "could not remove re-driven notification {}: {:?}",
+                    job.id,
+                    err
+                );
+            }
+            Err(Error::NotFound)
+        }
+        Err(err) => {
+            log::error!("could not remove failed notification {}: {:?}", req.id, err);
+            Ok(job)
+        }
+        Ok(()) => Ok(job),
+    }
+}
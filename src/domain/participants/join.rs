@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use crate::domain::helpers::participant::is_user_id;
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    pub user: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    AlreadyParticipant,
+    Unknown,
+}
+
+/// Adds the user to the participants of the event, as if they were never picked.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if !is_user_id(&req.user) {
+        log::trace!(
+            "could not join event {}: invalid user {}",
+            req.event,
+            req.user
+        );
+        return Err(Error::BadRequest);
+    }
+
+    let mut event = repo
+        .find_event(req.event, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    if event.participants.iter().any(|p| p.user == req.user) {
+        return Err(Error::AlreadyParticipant);
+    }
+    event.participants.push(req.user.clone().into());
+
+    let response = Response {
+        id: event.id,
+        name: event.name.clone(),
+    };
+    repo.update_event(event).await.map_err(|err| {
+        log::error!(
+            "could not add participant {} to event {}: {:?}",
+            req.user,
+            req.event,
+            err
+        );
+        match err {
+            UpdateError::NotFound => Error::NotFound,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        }
+    })?;
+    Ok(response)
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::domain::helpers::participant::is_user_id;
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    pub user: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    NotParticipant,
+    Unknown,
+}
+
+/// Removes the user from the participants of the event, along with their picks.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if !is_user_id(&req.user) {
+        log::trace!(
+            "could not leave event {}: invalid user {}",
+            req.event,
+            req.user
+        );
+        return Err(Error::BadRequest);
+    }
+
+    let mut event = repo
+        .find_event(req.event, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    let count = event.participants.len();
+    event.participants.retain(|p| p.user != req.user);
+    if event.participants.len() == count {
+        return Err(Error::NotParticipant);
+    }
+
+    let response = Response {
+        id: event.id,
+        name: event.name.clone(),
+    };
+    repo.update_event(event).await.map_err(|err| {
+        log::error!(
+            "could not remove participant {} from event {}: {:?}",
+            req.user,
+            req.event,
+            err
+        );
+        match err {
+            UpdateError::NotFound => Error::NotFound,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        }
+    })?;
+    Ok(response)
+}
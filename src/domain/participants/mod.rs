@@ -0,0 +1,5 @@
+pub mod join;
+pub mod leave;
+pub mod move_participant;
+pub mod remove_member;
+pub mod sync_group;
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::domain::helpers::participant::move_in_rotation;
+use crate::repository::errors::{FindError, UpdateError};
+use crate::repository::event::Repository;
+
+pub struct Request {
+    pub event: u32,
+    pub channel: String,
+    pub user: String,
+    pub direction: Direction,
+}
+
+pub enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u32,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    NotFound,
+    NotParticipant,
+    Unknown,
+}
+
+/// Moves the participant one position up or down on the rotation of the event, so that teams
+/// control who is picked next on the round-robin events.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let mut event = repo
+        .find_event(req.event, req.channel)
+        .await
+        .map_err(|err| match err {
+            FindError::NotFound => Error::NotFound,
+            FindError::Unknown => Error::Unknown,
+        })?;
+    let up = matches!(req.direction, Direction::Up);
+    event.participants =
+        move_in_rotation(event.participants, &req.user, up).ok_or(Error::NotParticipant)?;
+
+    let id = event.id;
+    repo.update_event(event).await.map_err(|err| {
+        log::error!(
+            "could not move participant {} on event {}: {:?}",
+            req.user,
+            req.event,
+            err
+        );
+        match err {
+            UpdateError::NotFound => Error::NotFound,
+            UpdateError::Conflict | UpdateError::Unknown => Error::Unknown,
+        }
+    })?;
+    Ok(Response { id })
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use crate::domain::entities::AuditAction;
+use crate::repository::errors::UpdateError;
+use crate::repository::{audit, event};
+
+pub struct Request {
+    pub team_id: String,
+    pub channel: String,
+    pub user: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    /// The events the user was removed from.
+    pub events: Vec<RemovedEvent>,
+    /// The users who created the events, to be told about the removal.
+    pub owners: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RemovedEvent {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Unknown,
+}
+
+/// Removes the user from the participants of all the events of the channel, along with their
+/// picks, so that the members who left the channel are not picked anymore.
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let events = event_repo
+        .find_all_events(req.channel.clone())
+        .await
+        .map_err(|err| {
+            log::error!(
+                "could not find the events of channel {}: {:?}",
+                req.channel,
+                err
+            );
+            Error::Unknown
+        })?;
+
+    let mut removed = vec![];
+    for mut event in events.into_iter() {
+        let count = event.participants.len();
+        event.participants.retain(|p| p.user != req.user);
+        if event.participants.len() == count {
+            continue;
+        }
+        let removed_event = RemovedEvent {
+            id: event.id,
+            name: event.name.clone(),
+        };
+        match event_repo.update_event(event).await {
+            Ok(..) => removed.push(removed_event),
+            Err(UpdateError::NotFound) => (),
+            Err(err) => log::error!(
+                "could not remove participant {} from event {}: {:?}",
+                req.user,
+                removed_event.id,
+                err
+            ),
+        }
+    }
+    if removed.is_empty() {
+        return Ok(Response {
+            events: removed,
+            owners: vec![],
+        });
+    }
+
+    let ids = removed.iter().map(|event| event.id).collect();
+    let mut owners: Vec<String> = audit_repo
+        .find_action_records(req.team_id, req.channel, AuditAction::EventCreated, ids)
+        .await
+        .unwrap_or_else(|err| {
+            log::error!("could not find the owners of the events: {:?}", err);
+            vec![]
+        })
+        .into_iter()
+        .filter_map(|record| record.user)
+        .filter(|user| *user != req.user)
+        .collect();
+    owners.sort_unstable();
+    owners.dedup();
+
+    Ok(Response {
+        events: removed,
+        owners,
+    })
+}
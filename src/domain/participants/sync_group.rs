@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::Participant;
+use crate::helpers::secret::SecretString;
+use crate::repository::{auth, event, user_group};
+
+pub struct Request {
+    pub events: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    /// The events whose participants changed.
+    pub synced: Vec<u32>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+    Unknown,
+}
+
+/// Syncs the participants of the events with the members of their user groups. The events without
+/// a user group are left as they are.
+///
+/// The participants that are still members keep their picks, the members that joined are added
+/// and the ones that left are removed. The events whose members could not be found keep their
+/// participants.
+pub async fn execute(
+    event_repo: Arc<dyn event::Repository>,
+    auth_repo: Arc<dyn auth::Repository>,
+    user_group_repo: Arc<dyn user_group::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let events: Vec<_> = event_repo
+        .find_all_events_by_id_unprotected(req.events)
+        .await
+        .map_err(|err| {
+            log::error!("could not find the events to sync: {:?}", err);
+            Error::Unknown
+        })?
+        .into_iter()
+        .filter(|event| event.user_group.is_some() && !event.inactive && !event.paused)
+        .collect();
+    if events.is_empty() {
+        return Ok(Response { synced: vec![] });
+    }
+
+    let tokens: HashMap<String, SecretString> = auth_repo
+        .find_all_by_team(events.iter().map(|event| event.team_id.clone()).collect())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|auth| {
+            auth.team_ids()
+                .into_iter()
+                .map(move |team| (team, auth.access_token.clone()))
+        })
+        .collect();
+
+    let mut synced = vec![];
+    for mut event in events.into_iter() {
+        let group = event.user_group.clone().unwrap_or_default();
+        let token = match tokens.get(&event.team_id) {
+            Some(token) => token,
+            None => {
+                log::error!(
+                    "could not sync event {}: no access token for team {}",
+                    event.id,
+                    event.team_id
+                );
+                continue;
+            }
+        };
+        let members = match user_group_repo.find_members(token.expose(), &group).await {
+            Ok(members) if !members.is_empty() => members,
+            Ok(..) => {
+                log::warn!(
+                    "not syncing event {}: user group {} is empty",
+                    event.id,
+                    group
+                );
+                continue;
+            }
+            Err(err) => {
+                log::error!(
+                    "could not find the members of user group {} of event {}: {:?}",
+                    group,
+                    event.id,
+                    err
+                );
+                continue;
+            }
+        };
+
+        let participants = sync_participants(event.participants.clone(), &members);
+        if participants == event.participants {
+            continue;
+        }
+        event.participants = participants;
+        let id = event.id;
+        match event_repo.update_event(event).await {
+            Ok(..) => synced.push(id),
+            Err(err) => log::error!("could not sync the participants of event {}: {:?}", id, err),
+        }
+    }
+    Ok(Response { synced })
+}
+
+/// Keeps the participants that are members, followed by the members that are not participants yet.
+fn sync_participants(participants: Vec<Participant>, members: &[String]) -> Vec<Participant> {
+    let mut result: Vec<Participant> = participants
+        .into_iter()
+        .filter(|participant| members.contains(&participant.user))
+        .collect();
+    for member in members {
+        if !result.iter().any(|participant| &participant.user == member) {
+            result.push(member.clone().into());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_participants() {
+        let participants = vec![
+            Participant {
+                picked: true,
+                ..String::from("U04PGARU4K1").into()
+            },
+            String::from("U0797QD5AJZ").into(),
+        ];
+        let members = vec![String::from("USLACKBOT"), String::from("U04PGARU4K1")];
+
+        let result = sync_participants(participants, &members);
+        let users: Vec<&str> = result.iter().map(|p| p.user.as_str()).collect();
+        assert_eq!(users, vec!["U04PGARU4K1", "USLACKBOT"]);
+        assert!(result[0].picked);
+        assert!(!result[1].picked);
+    }
+}
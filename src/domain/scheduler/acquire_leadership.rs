@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::helpers::date::Date;
+use crate::repository::errors::UpdateError;
+use crate::repository::scheduler::Repository;
+
+pub struct Request {
+    pub owner: String,
+    pub lease_seconds: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Acquires, or renews, the scheduler leadership for the owner. Returns whether it is the leader.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<bool, Error> {
+    let now = Date::now().timestamp();
+    repo.acquire_lease(req.owner, now, now + req.lease_seconds)
+        .await
+        .map_err(|err| match err {
+            UpdateError::Conflict | UpdateError::NotFound | UpdateError::Unknown => Error::Unknown,
+        })
+}
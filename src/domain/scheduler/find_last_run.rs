@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use crate::repository::errors::FindError;
+use crate::repository::scheduler::Repository;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotFound,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>) -> Result<i64, Error> {
+    repo.find_last_run().await.map_err(|err| match err {
+        FindError::NotFound => Error::NotFound,
+        FindError::Unknown => Error::Unknown,
+    })
+}
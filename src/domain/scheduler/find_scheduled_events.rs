@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::ScheduledEvent;
+use crate::repository::errors::FindAllError;
+use crate::repository::scheduler::Repository;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Finds the next occurrence of the scheduled events, sorted by the soonest first.
+pub async fn execute(repo: Arc<dyn Repository>) -> Result<ListResponse<ScheduledEvent>, Error> {
+    match repo.find_scheduled_events().await {
+        Ok(events) => Ok(ListResponse::new(events)),
+        Err(FindAllError::Unknown) => Err(Error::Unknown),
+    }
+}
@@ -0,0 +1,5 @@
+pub mod acquire_leadership;
+pub mod find_last_run;
+pub mod find_scheduled_events;
+pub mod save_last_run;
+pub mod save_next_occurrence;
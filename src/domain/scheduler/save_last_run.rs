@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::repository::errors::UpdateError;
+use crate::repository::scheduler::Repository;
+
+pub struct Request {
+    pub timestamp: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    repo.save_last_run(req.timestamp)
+        .await
+        .map_err(|err| match err {
+            UpdateError::Conflict | UpdateError::NotFound | UpdateError::Unknown => Error::Unknown,
+        })
+}
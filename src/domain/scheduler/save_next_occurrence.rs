@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::domain::entities::ScheduledEvent;
+use crate::repository::errors::{DeleteError, UpdateError};
+use crate::repository::scheduler::Repository;
+
+pub struct Request {
+    pub event: u32,
+    /// The timestamp (in seconds) of the next occurrence, if the event happens again.
+    pub next_at: Option<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    match req.next_at {
+        Some(next_at) => repo
+            .save_scheduled_event(ScheduledEvent {
+                event: req.event,
+                next_at,
+            })
+            .await
+            .map_err(|err| match err {
+                UpdateError::Conflict | UpdateError::NotFound | UpdateError::Unknown => {
+                    Error::Unknown
+                }
+            }),
+        None => repo
+            .delete_scheduled_event(req.event)
+            .await
+            .map_err(|err| match err {
+                DeleteError::NotFound | DeleteError::Unknown => Error::Unknown,
+            }),
+    }
+}
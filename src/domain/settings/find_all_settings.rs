@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use crate::domain::dtos::ListResponse;
+use crate::domain::entities::TeamSettings;
+use crate::repository::errors::FindAllError;
+use crate::repository::settings::Repository;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>) -> Result<ListResponse<TeamSettings>, Error> {
+    match repo.find_all_settings_unprotected().await {
+        Ok(settings) => Ok(ListResponse::new(settings)),
+        Err(FindAllError::Unknown) => Err(Error::Unknown),
+    }
+}
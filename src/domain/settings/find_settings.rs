@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use crate::domain::entities::TeamSettings;
+use crate::repository::errors::FindError;
+use crate::repository::settings::Repository;
+
+pub struct Request {
+    pub team_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+/// Finds the settings of the team, which are empty when the team did not set them yet.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    match repo.find_settings(req.team_id.clone()).await {
+        Ok(settings) => Ok(settings),
+        Err(FindError::NotFound) => Ok(TeamSettings::new(req.team_id)),
+        Err(FindError::Unknown) => Err(Error::Unknown),
+    }
+}
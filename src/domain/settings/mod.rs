@@ -0,0 +1,10 @@
+pub mod find_all_settings;
+pub mod find_settings;
+pub mod set_acknowledge_minutes;
+pub mod set_date_format;
+pub mod set_limits;
+pub mod set_locale;
+pub mod set_monthly_recap;
+pub mod set_no_show_boost;
+pub mod set_pick_message;
+pub mod set_quiet_hours;
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::domain::entities::TeamSettings;
+use crate::domain::settings::find_settings;
+use crate::repository::settings::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    /// The minutes the picks have to be acknowledged in, or `None` to need no acknowledgment.
+    pub minutes: Option<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    if let Some(minutes) = req.minutes {
+        if minutes == 0 || minutes > MAX_ACKNOWLEDGE_MINUTES {
+            log::trace!(
+                "could not set acknowledge minutes: {} is out of range",
+                minutes
+            );
+            return Err(Error::BadRequest);
+        }
+    }
+
+    let settings = find_settings::execute(
+        repo.clone(),
+        find_settings::Request {
+            team_id: req.team_id,
+        },
+    )
+    .await
+    .map_err(|_| Error::Unknown)?;
+
+    repo.save_settings(TeamSettings {
+        acknowledge_minutes: req.minutes,
+        ..settings
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the acknowledge minutes: {:?}", err);
+        Error::Unknown
+    })
+}
+
+/// The picks are repicked within a day at most.
+pub const MAX_ACKNOWLEDGE_MINUTES: u32 = 24 * 60;
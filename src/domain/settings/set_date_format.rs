@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::domain::date_format::{Clock, DateFormat, DateOrder};
+use crate::domain::entities::TeamSettings;
+use crate::domain::settings::find_settings;
+use crate::repository::settings::Repository;
+
+/// Sets the clock and the order of the dates, keeping the ones that are `None`.
+pub struct Request {
+    pub team_id: String,
+    pub clock: Option<Clock>,
+    pub order: Option<DateOrder>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    let settings = find_settings::execute(
+        repo.clone(),
+        find_settings::Request {
+            team_id: req.team_id,
+        },
+    )
+    .await
+    .map_err(|_| Error::Unknown)?;
+
+    repo.save_settings(TeamSettings {
+        date_format: DateFormat {
+            clock: req.clock.unwrap_or(settings.date_format.clock),
+            order: req.order.unwrap_or(settings.date_format.order),
+        },
+        ..settings
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the date format: {:?}", err);
+        Error::Unknown
+    })
+}
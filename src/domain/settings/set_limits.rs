@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{TeamLimits, TeamSettings};
+use crate::domain::settings::find_settings;
+use crate::repository::settings::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    pub limits: TeamLimits,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+/// Sets the limits of the team, in place of the ones of its plan.
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    if req.limits.max_events == Some(0) || req.limits.max_participants == Some(0) {
+        return Err(Error::BadRequest);
+    }
+
+    let settings = find_settings::execute(
+        repo.clone(),
+        find_settings::Request {
+            team_id: req.team_id,
+        },
+    )
+    .await
+    .map_err(|_| Error::Unknown)?;
+
+    repo.save_settings(TeamSettings {
+        limits: req.limits,
+        ..settings
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the limits: {:?}", err);
+        Error::Unknown
+    })
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::domain::entities::TeamSettings;
+use crate::domain::locale::Locale;
+use crate::domain::settings::find_settings;
+use crate::repository::settings::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    pub locale: Locale,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    let settings = find_settings::execute(
+        repo.clone(),
+        find_settings::Request {
+            team_id: req.team_id,
+        },
+    )
+    .await
+    .map_err(|_| Error::Unknown)?;
+
+    repo.save_settings(TeamSettings {
+        locale: req.locale,
+        ..settings
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the locale: {:?}", err);
+        Error::Unknown
+    })
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::domain::entities::TeamSettings;
+use crate::domain::settings::find_settings;
+use crate::repository::settings::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    /// The message the picks are posted with, or `None` to go back to the default one.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    let pick_message = match req.message {
+        Some(message) => Some(validate_message(message)?),
+        None => None,
+    };
+
+    let settings = find_settings::execute(
+        repo.clone(),
+        find_settings::Request {
+            team_id: req.team_id,
+        },
+    )
+    .await
+    .map_err(|_| Error::Unknown)?;
+
+    repo.save_settings(TeamSettings {
+        pick_message,
+        ..settings
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the pick message: {:?}", err);
+        Error::Unknown
+    })
+}
+
+/// Validates that the message mentions the picked participant, so that the picks are never posted
+/// without it.
+fn validate_message(message: String) -> Result<String, Error> {
+    let message = message.trim().to_string();
+    if !message.contains(USER_PLACEHOLDER) {
+        log::trace!("could not set pick message: it has no {}", USER_PLACEHOLDER);
+        return Err(Error::BadRequest);
+    }
+    if message.chars().count() > MAX_MESSAGE_LENGTH {
+        log::trace!("could not set pick message: it is too long");
+        return Err(Error::BadRequest);
+    }
+    Ok(message)
+}
+
+const USER_PLACEHOLDER: &str = "{user}";
+
+const MAX_MESSAGE_LENGTH: usize = 500;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_message() {
+        assert_eq!(
+            validate_message(String::from(" {user} is on duty for {event} ")),
+            Ok(String::from("{user} is on duty for {event}"))
+        );
+        assert_eq!(
+            validate_message(String::from("Someone is on duty")),
+            Err(Error::BadRequest)
+        );
+        assert_eq!(
+            validate_message(format!("{{user}} {}", "a".repeat(500))),
+            Err(Error::BadRequest)
+        );
+    }
+}
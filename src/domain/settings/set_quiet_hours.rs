@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use chrono::{NaiveTime, Timelike};
+
+use crate::domain::entities::{QuietHours, TeamSettings};
+use crate::domain::settings::find_settings;
+use crate::domain::timezone::Timezone;
+use crate::repository::settings::Repository;
+
+pub struct Request {
+    pub team_id: String,
+    /// The start and end times of the quiet hours, formatted as `HH:MM`, or `None` to remove them.
+    pub hours: Option<(String, String)>,
+    pub timezone: Timezone,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub async fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<TeamSettings, Error> {
+    let quiet_hours = match req.hours {
+        Some((start, end)) => {
+            let start = parse_minute(&start)?;
+            let end = parse_minute(&end)?;
+            if start == end {
+                log::trace!("could not set quiet hours: start and end are the same");
+                return Err(Error::BadRequest);
+            }
+            Some(QuietHours {
+                start,
+                end,
+                timezone: req.timezone,
+            })
+        }
+        None => None,
+    };
+
+    let settings = find_settings::execute(
+        repo.clone(),
+        find_settings::Request {
+            team_id: req.team_id,
+        },
+    )
+    .await
+    .map_err(|_| Error::Unknown)?;
+
+    repo.save_settings(TeamSettings {
+        quiet_hours,
+        ..settings
+    })
+    .await
+    .map_err(|err| {
+        log::error!("could not save the quiet hours: {:?}", err);
+        Error::Unknown
+    })
+}
+
+fn parse_minute(time: &str) -> Result<u32, Error> {
+    let time = NaiveTime::parse_from_str(time, "%H:%M").map_err(|err| {
+        log::trace!("could not parse quiet hours time {}: {}", time, err);
+        Error::BadRequest
+    })?;
+    Ok(time.hour() * 60 + time.minute())
+}
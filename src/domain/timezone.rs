@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use chrono::{Offset, TimeZone, Utc};
 use chrono_tz::{Africa, America, Asia, Australia, Europe, Pacific, Tz};
 use serde::{Deserialize, Serialize};
 
@@ -123,10 +124,29 @@ impl Timezone {
             Timezone::AGT => America::Argentina::Buenos_Aires,
             Timezone::BET => America::Sao_Paulo,
             Timezone::CAT => Africa::Harare,
-            Timezone::UTC => Europe::Lisbon,
+            Timezone::UTC => chrono_tz::UTC,
         }
     }
 
+    /// Finds the timezone for an IANA name (e.g. `America/New_York`), falling back to the first
+    /// timezone currently at the same offset from UTC, in seconds.
+    pub fn find(name: &str, offset: i32) -> Option<Timezone> {
+        let now = Utc::now().naive_utc();
+        let all = Timezone::all();
+        all.iter()
+            .find(|t| t.tz().name() == name)
+            .or_else(|| {
+                all.iter().find(|t| {
+                    t.tz()
+                        .offset_from_utc_datetime(&now)
+                        .fix()
+                        .local_minus_utc()
+                        == offset
+                })
+            })
+            .cloned()
+    }
+
     pub fn options() -> [TimezoneOption; 32] {
         Timezone::all().map(|t| TimezoneOption {
             label: t.to_string(),
@@ -254,42 +274,14 @@ impl From<Timezone> for String {
     }
 }
 
-impl From<Timezone> for i32 {
-    fn from(value: Timezone) -> Self {
-        let hours: f64 = match value {
-            Timezone::UTC => 0.0,
-            Timezone::GMT => 0.0,
-            Timezone::ECT => 1.0,
-            Timezone::EET => 2.0,
-            Timezone::ART => 2.0,
-            Timezone::EAT => 3.0,
-            Timezone::MET => 3.5,
-            Timezone::NET => 4.0,
-            Timezone::PLT => 5.0,
-            Timezone::IST => 5.5,
-            Timezone::BST => 6.0,
-            Timezone::VST => 7.0,
-            Timezone::CTT => 8.0,
-            Timezone::JST => 9.0,
-            Timezone::ACT => 9.5,
-            Timezone::AET => 10.0,
-            Timezone::SST => 11.0,
-            Timezone::NST => 12.0,
-            Timezone::MIT => -11.0,
-            Timezone::HST => -10.0,
-            Timezone::AST => -9.0,
-            Timezone::PST => -8.0,
-            Timezone::PNT => -7.0,
-            Timezone::MST => -7.0,
-            Timezone::CST => -6.0,
-            Timezone::EST => -5.0,
-            Timezone::IET => -5.0,
-            Timezone::PRT => -4.0,
-            Timezone::CNT => -3.5,
-            Timezone::AGT => -3.0,
-            Timezone::BET => -3.0,
-            Timezone::CAT => -1.0,
-        };
-        (hours * 60.0 * 60.0) as i32
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(Timezone::find("Asia/Tokyo", 32400), Some(Timezone::JST));
+        assert_eq!(Timezone::find("Asia/Seoul", 32400), Some(Timezone::JST));
+        assert_eq!(Timezone::find("Asia/Kathmandu", 20700), None);
     }
 }
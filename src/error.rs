@@ -0,0 +1,236 @@
+use std::fmt::{self, Display};
+
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use serde_json::json;
+
+use crate::repository::errors::{
+    CountError, DeleteError, FindAllError, FindError, InsertError, StoreError, UpdateError,
+};
+
+/// The errors of the application, mapped to a status code once they reach the handlers.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("unauthorized")]
+    Unauthorized,
+    #[error("not found")]
+    NotFound,
+    #[error("conflict")]
+    Conflict,
+    /// The request is valid, but cannot be served on the current state (e.g. picking from an
+    /// event without participants).
+    #[error("not acceptable: {0}")]
+    NotAcceptable(String),
+    #[error("invalid request: {0}")]
+    BadRequest(String),
+    #[error("repository error: {0}")]
+    Repository(String),
+    #[error("template error: {0}")]
+    Template(String),
+    #[error("slack error: {0}")]
+    Slack(String),
+    #[error("internal error: {0}")]
+    Internal(String),
+    /// The error, along with the team, channel and event it happened on.
+    #[error("{source} ({context})")]
+    Context {
+        context: Context,
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+/// The team, channel and event an error happened on, when known.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context {
+    pub team_id: Option<String>,
+    pub channel: Option<String>,
+    pub event_id: Option<u32>,
+}
+
+impl Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(team_id) = &self.team_id {
+            parts.push(format!("team {}", team_id));
+        }
+        if let Some(channel) = &self.channel {
+            parts.push(format!("channel {}", channel));
+        }
+        if let Some(event_id) = &self.event_id {
+            parts.push(format!("event {}", event_id));
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+impl Error {
+    pub fn with_team(self, team_id: impl Into<String>) -> Self {
+        self.with_context(|context| context.team_id = Some(team_id.into()))
+    }
+
+    pub fn with_channel(self, channel: impl Into<String>) -> Self {
+        self.with_context(|context| context.channel = Some(channel.into()))
+    }
+
+    pub fn with_event(self, event_id: u32) -> Self {
+        self.with_context(|context| context.event_id = Some(event_id))
+    }
+
+    /// The context of the error, when any.
+    pub fn context(&self) -> Option<&Context> {
+        match self {
+            Error::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The error without its context.
+    pub fn kind(&self) -> &Error {
+        match self {
+            Error::Context { source, .. } => source.kind(),
+            err => err,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self.kind() {
+            Error::Unauthorized => StatusCode::UNAUTHORIZED,
+            Error::NotFound => StatusCode::NOT_FOUND,
+            Error::Conflict => StatusCode::CONFLICT,
+            Error::NotAcceptable(..) => StatusCode::NOT_ACCEPTABLE,
+            Error::BadRequest(..) => StatusCode::BAD_REQUEST,
+            Error::Repository(..)
+            | Error::Template(..)
+            | Error::Slack(..)
+            | Error::Internal(..)
+            | Error::Context { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn with_context(self, update: impl FnOnce(&mut Context)) -> Self {
+        let (mut context, source) = match self {
+            Error::Context { context, source } => (context, source),
+            err => (Context::default(), Box::new(err)),
+        };
+        update(&mut context);
+        Error::Context { context, source }
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        // The internal errors are only detailed on the logs.
+        let message = if status.is_server_error() {
+            log::error!("request failed: {}", self);
+            String::from("internal error")
+        } else {
+            log::trace!("request rejected: {}", self);
+            self.kind().to_string()
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+/// Lets the handlers that still reply with a bare status code propagate the errors.
+impl From<Error> for StatusCode {
+    fn from(value: Error) -> Self {
+        if value.status().is_server_error() {
+            log::error!("request failed: {}", value);
+        }
+        value.status()
+    }
+}
+
+impl From<FindError> for Error {
+    fn from(value: FindError) -> Self {
+        match value {
+            FindError::NotFound => Self::NotFound,
+            FindError::Unknown => Self::Repository(String::from("could not find the entity")),
+        }
+    }
+}
+
+impl From<FindAllError> for Error {
+    fn from(value: FindAllError) -> Self {
+        match value {
+            FindAllError::Unknown => Self::Repository(String::from("could not find the entities")),
+        }
+    }
+}
+
+impl From<InsertError> for Error {
+    fn from(value: InsertError) -> Self {
+        match value {
+            InsertError::Conflict => Self::Conflict,
+            InsertError::Unknown => Self::Repository(String::from("could not insert the entity")),
+        }
+    }
+}
+
+impl From<UpdateError> for Error {
+    fn from(value: UpdateError) -> Self {
+        match value {
+            UpdateError::Conflict => Self::Conflict,
+            UpdateError::NotFound => Self::NotFound,
+            UpdateError::Unknown => Self::Repository(String::from("could not update the entity")),
+        }
+    }
+}
+
+impl From<DeleteError> for Error {
+    fn from(value: DeleteError) -> Self {
+        match value {
+            DeleteError::NotFound => Self::NotFound,
+            DeleteError::Unknown => Self::Repository(String::from("could not delete the entity")),
+        }
+    }
+}
+
+impl From<CountError> for Error {
+    fn from(value: CountError) -> Self {
+        match value {
+            CountError::Unknown => Self::Repository(String::from("could not count the entities")),
+        }
+    }
+}
+
+impl From<StoreError> for Error {
+    fn from(value: StoreError) -> Self {
+        match value {
+            StoreError::Unknown => Self::Repository(String::from("could not access the store")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context() {
+        let err = Error::from(FindError::NotFound)
+            .with_team("T1")
+            .with_channel("C1")
+            .with_event(3);
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert_eq!(err.to_string(), "not found (team T1, channel C1, event 3)");
+        assert_eq!(err.kind().to_string(), "not found");
+        assert_eq!(
+            err.context(),
+            Some(&Context {
+                team_id: Some(String::from("T1")),
+                channel: Some(String::from("C1")),
+                event_id: Some(3),
+            })
+        );
+
+        let err = Error::Slack(String::from("is_archived")).with_team("T1");
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.to_string(), "slack error: is_archived (team T1)");
+    }
+}
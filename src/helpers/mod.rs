@@ -1 +1,4 @@
 pub mod date;
+pub mod logging;
+pub mod reporter;
+pub mod secret;
@@ -0,0 +1,152 @@
+use std::sync::OnceLock;
+
+use hyper::{Body, Request};
+use hyper_tls::HttpsConnector;
+use serde_json::{json, Value};
+
+use super::date::Date;
+
+static REPORT_URL: OnceLock<String> = OnceLock::new();
+
+/// An unexpected error, with the team and channel it happened on when known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// Where the error happened (e.g. `commands`, `actions`, `scheduler`, `panic`).
+    pub source: &'static str,
+    pub message: String,
+    pub team_id: Option<String>,
+    pub channel: Option<String>,
+    pub event_id: Option<u32>,
+}
+
+impl Report {
+    pub fn new(source: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            source,
+            message: message.into(),
+            team_id: None,
+            channel: None,
+            event_id: None,
+        }
+    }
+
+    pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    pub fn with_event(mut self, event_id: u32) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
+    fn to_json(&self, timestamp: i64) -> Value {
+        json!({
+            "source": self.source,
+            "message": self.message,
+            "timestamp": timestamp,
+            "version": env!("CARGO_PKG_VERSION"),
+            "context": {
+                "team_id": self.team_id,
+                "channel": self.channel,
+                "event_id": self.event_id,
+            },
+        })
+    }
+}
+
+/// Sets the URL the errors are reported to. It can only be set once, on startup, and the errors
+/// are only logged when it is not set.
+pub fn set_report_url(url: String) {
+    if REPORT_URL.set(url).is_err() {
+        log::warn!("error report url was already set");
+    }
+}
+
+/// Logs the error and posts it to the report URL, without waiting for the delivery.
+pub fn report(report: Report) {
+    log::error!(
+        "reporting error from {} (team {:?}, channel {:?}, event {:?}): {}",
+        report.source,
+        report.team_id,
+        report.channel,
+        report.event_id,
+        report.message
+    );
+    let Some(url) = REPORT_URL.get() else {
+        return;
+    };
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        log::warn!("could not report error: no runtime available");
+        return;
+    };
+    let body = report.to_json(Date::now().timestamp()).to_string();
+    handle.spawn(async move {
+        if let Err(err) = send(url, body).await {
+            log::warn!("could not report error: {}", err);
+        }
+    });
+}
+
+/// Reports the panics, on top of the default hook printing them.
+pub fn set_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let message = match info.location() {
+            Some(location) => format!("{} at {}", panic_message(info), location),
+            None => panic_message(info),
+        };
+        report(Report::new("panic", message));
+    }));
+}
+
+fn panic_message(info: &std::panic::PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = info.payload().downcast_ref::<String>() {
+        return message.clone();
+    }
+    String::from("unknown panic")
+}
+
+async fn send(url: &str, body: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let req = Request::builder()
+        .method(hyper::Method::POST)
+        .uri(url)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))?;
+    let client = hyper::Client::builder().build(HttpsConnector::new());
+    let response = client.request(req).await?;
+    if !response.status().is_success() {
+        return Err(format!("unexpected status {}", response.status()).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let report = Report::new("scheduler", "could not pick")
+            .with_team("T1")
+            .with_channel("C1")
+            .with_event(3);
+        let value = report.to_json(1700000000);
+        assert_eq!(value["source"], "scheduler");
+        assert_eq!(value["message"], "could not pick");
+        assert_eq!(value["timestamp"], 1700000000);
+        assert_eq!(
+            value["context"],
+            json!({"team_id": "T1", "channel": "C1", "event_id": 3})
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt::{Debug, Display};
+
+use serde::{Deserialize, Serialize};
+
+const REDACTED: &str = "[redacted]";
+
+/// A secret (e.g. an access token or a signing secret) that is redacted when formatted, so that it
+/// never reaches the logs. The value is only read through [`SecretString::expose`].
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    /// The value of the secret, to be sent where it is needed and never logged.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretString({})", REDACTED)
+    }
+}
+
+impl Display for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted() {
+        let secret = SecretString::from("xoxb-1234");
+        assert_eq!(secret.expose(), "xoxb-1234");
+        assert_eq!(format!("{}", secret), "[redacted]");
+        assert_eq!(format!("{:?}", secret), "SecretString([redacted])");
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"xoxb-1234\"");
+    }
+}
@@ -0,0 +1,511 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Event forms
+    ("form_add_header", "Create your own event!  📅"),
+    ("form_edit_header", "Editing event"),
+    ("form_add_submit", "Add event"),
+    ("form_edit_submit", "Save"),
+    ("form_cancel", "Cancel"),
+    ("form_name_label", "Event's name"),
+    ("form_name_placeholder", "Enter a name for your event"),
+    ("form_date_label", "Pick the date & time"),
+    (
+        "form_date_hint",
+        "Ignore any hint regarding timezone here.\nPlease select your timezone bellow!",
+    ),
+    ("form_ends_at_label", "When does the event end?"),
+    ("form_ends_at_hint", "Leave it empty if the event never ends."),
+    ("form_repeat_label", "What is your event's frequency?"),
+    ("form_days_label", "Weekdays"),
+    ("form_days_hint", "Only used with the specific weekdays frequency."),
+    ("form_cron_label", "Cron expression"),
+    (
+        "form_cron_hint",
+        "Only used with the custom frequency. Format: minute hour day-of-month month day-of-week.",
+    ),
+    ("form_pick_mode_label", "How should participants be picked?"),
+    ("form_reminder_label", "Remind the channel before the pick"),
+    (
+        "form_reminder_hint",
+        "Minutes before the pick. Leave it empty to skip the reminder.",
+    ),
+    ("form_seed_label", "Seed of the random picks"),
+    (
+        "form_seed_hint",
+        "Makes the random picks reproducible: the same seed picks the same participants on the same day. Leave it empty for unpredictable picks.",
+    ),
+    ("form_description_label", "Description"),
+    (
+        "form_description_hint",
+        "What the participants are picked for (e.g. the agenda). It is shown along with the picks.",
+    ),
+    ("form_url_label", "Link"),
+    ("form_url_hint", "The link of the event (e.g. the meeting or its agenda)."),
+    ("form_no_repeat_label", "Consecutive picks"),
+    (
+        "form_no_repeat_option",
+        "Never pick the same person twice in a row",
+    ),
+    ("form_anchor_label", "Picks thread"),
+    (
+        "form_anchor_option",
+        "Post the automatic picks as replies on a pinned message",
+    ),
+    ("form_visibility_label", "Private picks"),
+    ("form_visibility_option", "Send the picks only by direct message to the participants"),
+    ("form_out_of_office_label", "Out of office"),
+    ("form_out_of_office_option", "Skip the participants whose Slack status is out of office"),
+    ("form_skip_weekends_label", "Weekends"),
+    (
+        "form_skip_weekends_option",
+        "Skip the picks on Saturdays and Sundays",
+    ),
+    (
+        "form_skip_weekends_hint",
+        "Applies to the daily and weekly events",
+    ),
+    ("form_participants_label", "Pick the participants"),
+    ("form_user_group_label", "Or sync them with a user group"),
+    ("form_user_group_placeholder", "@backend-team"),
+    (
+        "form_user_group_hint",
+        "The participants are kept in sync with the members of the group before each pick",
+    ),
+    (
+        "form_part_time_label",
+        "Part-time participants _(picked half as often)_",
+    ),
+    (
+        "form_notify_channels_label",
+        "Announce the picks on other channels too",
+    ),
+    ("form_users_placeholder", "Select users"),
+    ("form_channels_placeholder", "Select channels"),
+    ("repeat_none", "None"),
+    ("repeat_daily", "Daily"),
+    ("repeat_weekly", "Weekly"),
+    ("repeat_weekly_two", "Bi-weekly"),
+    ("repeat_monthly", "Monthly"),
+    ("repeat_monthly_two", "Bi-monthly"),
+    ("repeat_yearly", "Yearly"),
+    ("repeat_days", "Specific weekdays"),
+    ("repeat_cron", "Custom (cron expression)"),
+    ("weekday_mon", "Monday"),
+    ("weekday_tue", "Tuesday"),
+    ("weekday_wed", "Wednesday"),
+    ("weekday_thu", "Thursday"),
+    ("weekday_fri", "Friday"),
+    ("weekday_sat", "Saturday"),
+    ("weekday_sun", "Sunday"),
+    ("pick_mode_random", "Random"),
+    ("pick_mode_round_robin", "Round-robin"),
+    ("pick_mode_pairs", "Pairs"),
+    // Picks
+    (
+        "pick_manual",
+        "{picker} randomly picked {user} for the event *{event}* ({left} left)",
+    ),
+    (
+        "pick_repick",
+        "{picker} repicked {user} for the event *{event}* ({left} left)",
+    ),
+    (
+        "pick_scheduler",
+        "{picker} automatically picked {user} for the event *{event}* ({left} left)",
+    ),
+    (
+        "pick_skip",
+        "{picker} skipped and now {user} was randomly picked for the event *{event}* ({left} left)",
+    ),
+    (
+        "pick_timeout",
+        "{picker} automatically repicked {user} for the event *{event}* ({left} left)",
+    ),
+    ("pick_volunteer", "{picker} volunteered for the event *{event}* ({left} left)"),
+    ("source_manual", "Source: Manual Pick"),
+    ("source_repick", "Source: Repick"),
+    ("source_scheduler", "Source: Automatic scheduler"),
+    ("source_skip", "Source: Skip"),
+    ("source_timeout", "Source: Unacknowledged pick"),
+    ("source_volunteer", "Source: Volunteer"),
+    ("pick_pair", "{user} and {partner}"),
+    ("pick_replaced", "Previously picked: {users}"),
+    ("pick_skip_button", "Skip"),
+    ("pick_repick_button", "Repick"),
+    ("pick_cancel_button", "Cancel"),
+    ("pick_acknowledge_button", "Acknowledge"),
+    (
+        "pick_acknowledge_hint",
+        "Please acknowledge this pick within {minutes}, or someone else will be picked.",
+    ),
+    ("acknowledge_done", "{user} acknowledged the pick of the event *{event}*. ✅"),
+    ("acknowledge_forbidden", "Only {user} can acknowledge this pick."),
+    ("acknowledge_missing", "This pick has no pending acknowledgment."),
+    ("skip_next_none", "The event has no upcoming occurrence to skip."),
+    (
+        "reminder",
+        ":alarm_clock: *{event}* starts in {minutes}\n\t\t_Source: Reminder_",
+    ),
+    ("reminder_minute", "1 minute"),
+    ("reminder_minutes", "{minutes} minutes"),
+    (
+        "announcement",
+        ":loudspeaker: {user} was picked for the event *{event}* on <#{channel}>\n\t\t_Source: Announcement_",
+    ),
+    // Event views
+    ("close_button", "Close"),
+    ("next_button", "Next"),
+    ("previous_button", "Previous"),
+    ("confirm_title", "Are you sure?"),
+    ("confirm_yes", "Do it!"),
+    ("confirm_no", "Stop, I've changed my mind!"),
+    ("repeat_every", "Every {days}"),
+    ("repeat_custom", "Custom ({expression})"),
+    ("event_details_header", "Details for \"{name}\"!  📅"),
+    ("event_name_field", "*Name*"),
+    ("event_participants_field", "*Participants*"),
+    ("event_date_field", "*Date & Time*"),
+    ("event_repeat_field", "*Frequency*"),
+    ("event_description_field", "*Description* 📝"),
+    ("event_url_field", "*Link* 🔗"),
+    ("event_pick_mode_field", "*Pick mode*"),
+    ("event_ends_field", "*Ends*"),
+    ("event_ended_field", "*Ended*"),
+    ("event_reminder_field", "*Reminder*"),
+    ("event_reminder", "{minutes} before the pick"),
+    ("event_seed_field", "*Seed* 🎲"),
+    ("event_user_group_field", "*Synced with*"),
+    ("event_skipped_field", "*Skipped* ⏭️"),
+    ("event_weekends_field", "*Weekends*"),
+    ("event_weekends", "No picks happen on Saturdays and Sundays"),
+    ("event_anchor_field", "*Picks thread*"),
+    (
+        "event_anchor",
+        "The automatic picks are posted as replies on a pinned message",
+    ),
+    ("event_private_field", "*Private* 🔒"),
+    (
+        "event_private",
+        "The automatic picks are only sent by direct message to the participants",
+    ),
+    ("event_out_of_office_field", "*Out of office* 🌴"),
+    (
+        "event_out_of_office",
+        "The participants whose Slack status is out of office are skipped on the automatic picks",
+    ),
+    ("event_notify_channels_field", "*Also announced on*"),
+    ("event_away_field", "*Away* 🌴"),
+    ("event_away_until", "{user} until {date}"),
+    ("event_pick_button", "Pick"),
+    ("event_skip_next_button", "Skip next"),
+    ("event_rotation_button", "Rotation"),
+    ("event_edit_button", "Edit"),
+    ("event_delete_button", "Delete"),
+    ("add_event_success_header", "Event created with success!  🎉"),
+    (
+        "add_event_success_intro",
+        "You may use it now to randomly *pick* members of your team 🤩",
+    ),
+    ("edit_event_success_header", "Event updated with success!  🎉"),
+    (
+        "edit_event_success_intro",
+        "You can continue using it to randomly *pick* members of your team 🤩",
+    ),
+    ("delete_event_header", "Are you sure?  ❌"),
+    (
+        "delete_event_confirm",
+        "Are you sure you want to delete the event *{name}*?\nThis action cannot be undone.",
+    ),
+    ("delete_event_success", "Event deleted with success! 👍"),
+    ("select_show_header", "Select event to show  🔎"),
+    ("select_show_prompt", "Select the event you want to view the details:"),
+    ("select_pick_header", "Select the event  🔎"),
+    (
+        "select_pick_prompt",
+        "Select the event you want to randomly pick a participant:",
+    ),
+    ("select_edit_header", "Edit event  🔎"),
+    ("select_edit_prompt", "Select the event you want to edit:"),
+    ("select_delete_header", "Delete event  🔎"),
+    ("select_delete_prompt", "Select the event you want to delete:"),
+    ("select_placeholder", "Type to search the events"),
+    ("select_empty", "No events found! 🤷"),
+    ("list_events_header", "Checkout your events!"),
+    ("list_events_intro", "Here, you can manage all of your events with ease."),
+    ("list_events_pick", "Pick randomly"),
+    ("list_events_skip_next", "Skip next occurrence"),
+    ("list_events_show", "Show details"),
+    ("list_events_edit", "Edit event"),
+    ("list_events_duplicate", "Duplicate event"),
+    ("list_events_delete", "Delete event"),
+    ("list_events_create_button", "Create a new event"),
+    ("stats_header", "Statistics for \"{event}\"  📊"),
+    ("stats_picks", "*Picks:* {count}"),
+    ("stats_skips", "*Skips:* {count}"),
+    ("stats_no_shows", "*No-shows:* {count}"),
+    ("stats_last_picked", "*Last picked:* {date}"),
+    ("stats_never", "Never"),
+    ("history_header", "Pick history for \"{event}\"  📜"),
+    ("history_empty", "No picks recorded yet for the event [{id}] 🤷"),
+    ("history_replacing", "replacing {user}"),
+    ("history_no_show", "No-show"),
+    ("audit_header", "Recent activity  🕵️"),
+    ("audit_empty", "No activity recorded yet for this channel 🤷"),
+    ("audit_someone", "Someone"),
+    ("audit_event_created", "created the event"),
+    ("audit_event_updated", "edited the event"),
+    ("audit_event_deleted", "deleted the event"),
+    ("audit_pick", "picked on the event"),
+    ("audit_skip", "skipped the pick of the event"),
+    ("audit_repick", "repicked on the event"),
+    ("audit_cancel_pick", "cancelled the pick of the event"),
+    ("audit_skip_occurrence", "skipped the next occurrence of the event"),
+    ("audit_event_paused", "paused the event"),
+    ("audit_acknowledge", "acknowledged the pick of the event"),
+    (
+        "audit_acknowledgment_expired",
+        "repicked the unacknowledged pick of the event",
+    ),
+    ("audit_no_show", "marked a no-show on the event"),
+    ("audit_volunteer", "volunteered for the pick of the event"),
+    ("audit_install", "installed the application"),
+    ("audit_api_key_created", "created an API key"),
+    ("audit_api_key_revoked", "revoked an API key"),
+    (
+        "cancel_pick",
+        "{user} cancelled previous pick for the event *{event}*\n\t\t_Source: Cancel_",
+    ),
+    ("cancel_pick_again_button", "Pick again"),
+    ("rotation_header", "Rotation of \"{event}\"  🔁"),
+    (
+        "rotation_intro",
+        "The participants are picked in this order. Move them to change who is next.",
+    ),
+    ("rotation_next", "next"),
+    ("rotation_move_up", "Move up"),
+    ("rotation_move_down", "Move down"),
+    ("schedule_header", "Upcoming picks  🗓️"),
+    (
+        "schedule_empty",
+        "No automatic picks are scheduled for the next {days} days.",
+    ),
+    (
+        "schedule_intro",
+        "The automatic picks of the next {days} days, on {timezone} time.",
+    ),
+    // Event form errors
+    ("form_error_name", "Please enter a name for the event."),
+    ("form_error_date", "Please pick the date & time of the event."),
+    ("form_error_date_past", "The date of the event is in the past."),
+    ("form_error_ends_at", "The event must end after its date."),
+    (
+        "form_error_name_length",
+        "The name of the event is too long. Please keep it under 80 characters.",
+    ),
+    (
+        "form_error_participants",
+        "Please select the participants of the event, or enter a user group.",
+    ),
+    (
+        "form_error_participants_limit",
+        "Events can have up to {max} participants on your plan. Please remove some of them.",
+    ),
+    (
+        "form_error_user_group",
+        "The user group was not found. Please check its handle (e.g. `@team`).",
+    ),
+    ("form_error_cron", "Please enter the cron expression of the custom frequency."),
+    ("form_error_days", "Please select the weekdays of the specific weekdays frequency."),
+    ("form_error_reminder", "The reminder must be a number of minutes."),
+    ("form_error_seed", "The seed must be a positive number."),
+    ("form_error_description", "The description is too long. Please keep it under 500 characters."),
+    ("form_error_url", "Please enter a link starting with https://."),
+    (
+        "form_error_invalid",
+        "Some of the fields are not valid. Please review the event and try again.",
+    ),
+    // Commands
+    ("error", "Error {code}: {reason}."),
+    (
+        "unknown_command",
+        "Sorry but we couldn't find any match command. Please type `/picker help` for all available commands",
+    ),
+    (
+        "free_plan_limit",
+        "The free plan allows a single event per channel. Type `/picker upgrade` to create more events. 🚀",
+    ),
+    (
+        "plan_limit",
+        "This channel reached the maximum number of events.",
+    ),
+    (
+        "away_not_participant",
+        "You are not a participant of any event in this channel.",
+    ),
+    ("away_invalid_days", "The days must be a number between 0 and {max}."),
+    ("next_picks_left", "Left to pick for *{event}*:"),
+    (
+        "next_picks_new_round",
+        "Everyone was picked for *{event}*, so the next round starts with:",
+    ),
+    ("next_picks_none", "No one is available to pick."),
+    ("next_picks_away", "Away: {users}"),
+    (
+        "preview_pick",
+        "🔮 {user} would be picked now, leaving {left} to pick. Nothing was saved.",
+    ),
+    (
+        "preview_pick_last",
+        "🔮 {user} would be picked now, closing the round. Nothing was saved.",
+    ),
+    ("preview_pick_empty", "There is no one available to pick for this event."),
+    ("participant_joined", "{user} joined *{event}* 👋"),
+    ("participant_left", "{user} left *{event}*"),
+    (
+        "participant_removed",
+        "{user} left the channel, so they were removed from the participants of {events}",
+    ),
+    ("participant_already", "You are already a participant of this event."),
+    ("participant_missing", "You are not a participant of this event."),
+    ("participant_invalid", "Only the Slack users can join or leave the events."),
+    (
+        "calendar_unavailable",
+        "The calendar subscriptions are not available yet.",
+    ),
+    (
+        "calendar_connect",
+        "📅 Connect your Google calendar on <{url}|this link> to be skipped on the automatic picks of the days you are out of office. The link expires in 10 minutes.",
+    ),
+    ("calendar_connect_unavailable", "The calendar connections are not available yet."),
+    ("calendar_disconnected", "Your calendar was disconnected 👋"),
+    ("calendar_not_connected", "You have not connected your calendar."),
+    (
+        "export_empty",
+        "There are no events to export in this channel yet.",
+    ),
+    ("holidays_empty", "There are no holidays for your team yet."),
+    (
+        "import_invalid",
+        "The content is not a valid export. Please paste the whole content of the file shared by `/picker export`.",
+    ),
+    (
+        "quiet_hours_empty",
+        "There are no quiet hours for your team yet.",
+    ),
+    ("upgrade_already", "Your team is already on the Pro plan. 🎉"),
+    ("upgrade_unavailable", "The upgrades are not available yet."),
+    ("locale_current", "The messages of your team are shown in {locale}."),
+    (
+        "locale_set",
+        "The messages of your team will now be shown in {locale}. 🌍",
+    ),
+    (
+        "locale_invalid",
+        "Unknown language. Please use one of: {locales}.",
+    ),
+    ("config_message_current", "The picks of your team are announced with:\n>{message}"),
+    ("config_message_default", "The picks of your team are announced with the default message."),
+    ("config_message_set", "The picks of your team will now be announced with:\n>{message}"),
+    (
+        "config_message_reset",
+        "The picks of your team will now be announced with the default message. 👋",
+    ),
+    (
+        "config_message_invalid",
+        "The message must mention the picked participant with `{user}` and have at most 500 characters.",
+    ),
+    (
+        "config_acknowledge_current",
+        "The automatic picks of your team have to be acknowledged within {minutes}.",
+    ),
+    ("config_acknowledge_off", "The automatic picks of your team need no acknowledgment."),
+    (
+        "config_acknowledge_set",
+        "The automatic picks of your team will now have to be acknowledged within {minutes}, or someone else is picked. ⏰",
+    ),
+    (
+        "config_acknowledge_reset",
+        "The automatic picks of your team will no longer need an acknowledgment.",
+    ),
+    ("config_acknowledge_invalid", "The minutes must be a number between 1 and {max}."),
+    (
+        "channel_invite_bot",
+        "The bot cannot join this private channel by itself. Invite it with `/invite @Team Event Picker`, or the picks will not be posted here.",
+    ),
+    ("channel_access_lost", "I could not post the pick of the event *{event}*, as I am no longer on {channel}. Add me back to the channel, or pause the event until then."),
+    ("channel_access_rejoin_button", "Join the channel"),
+    ("channel_access_pause_button", "Pause the event"),
+    ("channel_access_rejoined", "I joined {channel} again, so the next picks will be posted there."),
+    ("channel_access_paused", "The event *{event}* is paused. Edit it to resume its picks."),
+    ("onboarding_welcome", "👋 Thanks for installing *Team Event Picker*, <@{user}>! I pick who runs the events of your team, like the daily standup or the weekly retro, and tell the channel when it is their turn."),
+    ("onboarding_choose_channel", "Choose the channel of your team to get started. I will join it and leave a button there to create your first event."),
+    ("onboarding_channel_placeholder", "Choose a channel"),
+    ("onboarding_tour_button", "Take the tour"),
+    ("onboarding_tour", "*1.* Create an event on a channel with `/picker create`, choosing its participants and how often it repeats.\n*2.* On each of its dates, I pick one of the participants and post it on the channel. 🎯\n*3.* Anyone can pick again, skip or volunteer from the buttons of the pick.\n*4.* Tune the picks of your team with `/picker config`, or see every subcommand with `/picker help`."),
+    ("onboarding_channel_welcome", "👋 Hi everyone! <@{user}> added me to pick who runs the events of this channel. Create the first one below, or see what I can do with `/picker help`."),
+    ("onboarding_create_button", "Create event"),
+    ("onboarding_channel_posted", "I joined {channel} and left a button there to create your first event. 🎉"),
+    ("recap_title", "📊 Recap of {month}"),
+    ("recap_picks", "*Picks:* {count}"),
+    ("recap_skips", "*Skips:* {count}"),
+    ("recap_acknowledgment", "*Acknowledged:* {rate}%"),
+    ("recap_leaderboard", "*Most picked*"),
+    ("recap_leader", "{position} {user} ({count})"),
+    ("config_recap_on", "The picks of each channel are recapped at the end of each month."),
+    ("config_recap_off", "The picks are not recapped at the end of the months."),
+    ("pick_no_show_button", "Mark as no-show"),
+    ("no_show_done", "{user} was marked as a no-show on the event *{event}*."),
+    ("no_show_missing", "This participant was not picked for the event."),
+    ("no_show_already", "This pick was already marked as a no-show."),
+    ("config_noshow_on", "The participants marked as no-shows are picked more often."),
+    ("config_noshow_off", "The no-shows are kept on the history only."),
+    ("config_clock", "The times are shown on a {clock}-hour clock. 🕒"),
+    ("config_dates", "The dates are shown as {example}. 📅"),
+    ("pick_event_link", "Event link"),
+    ("private_pick", "🔒 {user} was picked for the event *{event}* of <#{channel}> ({left} left)."),
+    (
+        "private_pick_you",
+        "🔒 You were picked for the event *{event}* of <#{channel}> ({left} left).",
+    ),
+    ("pick_volunteer_button", "I'll take it"),
+    ("volunteer_not_participant", "Only the participants of the event can take its picks."),
+    ("volunteer_already_picked", "You are already picked for this event."),
+    ("volunteer_stale", "This pick is no longer the latest one of the event."),
+    (
+        "export_done",
+        "*Export* 📦\nThe {count} events of this channel were shared as `{file}`. Type `/picker import` followed by its content to create them on another channel.",
+    ),
+    ("import_title", "*Import* 📥"),
+    ("import_created", "Created {count} events: {events}."),
+    ("import_skipped", "Skipped {count} events: {events}."),
+    ("import_error_invalid", "invalid settings"),
+    ("import_error_limit", "maximum number of events reached"),
+    ("import_error_conflict", "an event with the same name already exists"),
+    ("import_error_unknown", "unexpected error"),
+    (
+        "away_set",
+        "You won't be picked for the next {days} day(s) in: *{events}* 🌴",
+    ),
+    ("away_back", "Welcome back! You can be picked again in: *{events}* 👋"),
+    ("holidays_title", "*Holidays* 🏖️\n{holidays}"),
+    ("holiday_added", "No picks will happen on {date} 🏖️"),
+    ("holiday_removed", "Picks will happen again on {date} 👋"),
+    ("quiet_hours_current", "*Quiet hours* 🤫\n{hours}"),
+    ("quiet_hours_set", "No automatic posts will happen during {hours} 🤫"),
+    ("quiet_hours_reset", "Automatic posts will happen at any time again 👋"),
+    (
+        "calendar_link",
+        "*Calendar* 📅\nSubscribe to the events of this channel from your calendar application with the link below. Keep it private, as anyone with it can see the events.\n{url}",
+    ),
+    (
+        "calendar_reset",
+        "*Calendar* 📅\nThe previous link no longer works. Subscribe to the events of this channel with the new link below.\n{url}",
+    ),
+    (
+        "token_issued",
+        "*API tokens* 🔑\nUse the access token as a bearer token to pick the events of this channel through the API (e.g. `POST /api/v1/events/<id>/pick`), and the refresh token to renew it once it expires (`POST /api/auth/refresh`). Keep them private, as anyone with them can pick on your behalf.\nAccess token: `{access}`\nRefresh token: `{refresh}`",
+    ),
+    (
+        "upgrade_link",
+        "*Upgrade* 🚀\nSubscribe to the Pro plan with the link below to create up to {max} events on each channel. Your team is upgraded as soon as the payment is complete.\n{url}",
+    ),
+];
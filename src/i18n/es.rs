@@ -0,0 +1,559 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Event forms
+    ("form_add_header", "¡Crea tu propio evento!  📅"),
+    ("form_edit_header", "Editando el evento"),
+    ("form_add_submit", "Añadir evento"),
+    ("form_edit_submit", "Guardar"),
+    ("form_cancel", "Cancelar"),
+    ("form_name_label", "Nombre del evento"),
+    ("form_name_placeholder", "Introduce un nombre para tu evento"),
+    ("form_date_label", "Elige la fecha y la hora"),
+    (
+        "form_date_hint",
+        "Ignora aquí cualquier indicación sobre la zona horaria.\n¡Selecciona tu zona horaria abajo!",
+    ),
+    ("form_ends_at_label", "¿Cuándo termina el evento?"),
+    ("form_ends_at_hint", "Déjalo vacío si el evento nunca termina."),
+    ("form_repeat_label", "¿Con qué frecuencia se repite tu evento?"),
+    ("form_days_label", "Días de la semana"),
+    (
+        "form_days_hint",
+        "Solo se usa con la frecuencia de días específicos.",
+    ),
+    ("form_cron_label", "Expresión cron"),
+    (
+        "form_cron_hint",
+        "Solo se usa con la frecuencia personalizada. Formato: minuto hora día-del-mes mes día-de-la-semana.",
+    ),
+    ("form_pick_mode_label", "¿Cómo se eligen los participantes?"),
+    ("form_reminder_label", "Recordar al canal antes de la elección"),
+    (
+        "form_reminder_hint",
+        "Minutos antes de la elección. Déjalo vacío para omitir el recordatorio.",
+    ),
+    ("form_seed_label", "Semilla de las elecciones aleatorias"),
+    (
+        "form_seed_hint",
+        "Hace las elecciones aleatorias reproducibles: la misma semilla elige a los mismos participantes el mismo día. Déjalo vacío para elecciones impredecibles.",
+    ),
+    ("form_description_label", "Descripción"),
+    (
+        "form_description_hint",
+        "Para qué se elige a los participantes (p. ej. la agenda). Se muestra junto con las elecciones.",
+    ),
+    ("form_url_label", "Enlace"),
+    ("form_url_hint", "El enlace del evento (p. ej. la reunión o su agenda)."),
+    ("form_no_repeat_label", "Elecciones consecutivas"),
+    (
+        "form_no_repeat_option",
+        "Nunca elegir a la misma persona dos veces seguidas",
+    ),
+    ("form_anchor_label", "Hilo de elecciones"),
+    (
+        "form_anchor_option",
+        "Publicar las elecciones automáticas como respuestas a un mensaje fijado",
+    ),
+    ("form_visibility_label", "Elecciones privadas"),
+    (
+        "form_visibility_option",
+        "Enviar las elecciones solo por mensaje directo a los participantes",
+    ),
+    ("form_out_of_office_label", "Fuera de la oficina"),
+    (
+        "form_out_of_office_option",
+        "Saltar a los participantes cuyo estado de Slack es fuera de la oficina",
+    ),
+    ("form_skip_weekends_label", "Fines de semana"),
+    (
+        "form_skip_weekends_option",
+        "Saltar las elecciones los sábados y domingos",
+    ),
+    (
+        "form_skip_weekends_hint",
+        "Se aplica a los eventos diarios y semanales",
+    ),
+    ("form_participants_label", "Elige a los participantes"),
+    ("form_user_group_label", "O sincronízalos con un grupo de usuarios"),
+    ("form_user_group_placeholder", "@backend-team"),
+    (
+        "form_user_group_hint",
+        "Los participantes se sincronizan con los miembros del grupo antes de cada elección",
+    ),
+    (
+        "form_part_time_label",
+        "Participantes a tiempo parcial _(elegidos la mitad de veces)_",
+    ),
+    (
+        "form_notify_channels_label",
+        "Anunciar las elecciones también en otros canales",
+    ),
+    ("form_users_placeholder", "Selecciona usuarios"),
+    ("form_channels_placeholder", "Selecciona canales"),
+    ("repeat_none", "Ninguna"),
+    ("repeat_daily", "Diaria"),
+    ("repeat_weekly", "Semanal"),
+    ("repeat_weekly_two", "Quincenal"),
+    ("repeat_monthly", "Mensual"),
+    ("repeat_monthly_two", "Bimestral"),
+    ("repeat_yearly", "Anual"),
+    ("repeat_days", "Días específicos"),
+    ("repeat_cron", "Personalizada (expresión cron)"),
+    ("weekday_mon", "Lunes"),
+    ("weekday_tue", "Martes"),
+    ("weekday_wed", "Miércoles"),
+    ("weekday_thu", "Jueves"),
+    ("weekday_fri", "Viernes"),
+    ("weekday_sat", "Sábado"),
+    ("weekday_sun", "Domingo"),
+    ("pick_mode_random", "Aleatorio"),
+    ("pick_mode_round_robin", "Por turnos"),
+    ("pick_mode_pairs", "Por parejas"),
+    // Picks
+    (
+        "pick_manual",
+        "{picker} eligió al azar a {user} para el evento *{event}* (quedan {left})",
+    ),
+    (
+        "pick_repick",
+        "{picker} volvió a elegir a {user} para el evento *{event}* (quedan {left})",
+    ),
+    (
+        "pick_scheduler",
+        "{picker} eligió automáticamente a {user} para el evento *{event}* (quedan {left})",
+    ),
+    (
+        "pick_skip",
+        "{picker} pasó y ahora {user} fue elegido al azar para el evento *{event}* (quedan {left})",
+    ),
+    (
+        "pick_timeout",
+        "{picker} volvió a elegir automáticamente a {user} para el evento *{event}* (quedan {left})",
+    ),
+    ("pick_volunteer", "{picker} se ofreció para el evento *{event}* (quedan {left})"),
+    ("source_manual", "Origen: Elección manual"),
+    ("source_repick", "Origen: Nueva elección"),
+    ("source_scheduler", "Origen: Programador automático"),
+    ("source_skip", "Origen: Pasar"),
+    ("source_timeout", "Origen: Elección sin confirmar"),
+    ("source_volunteer", "Origen: Voluntario"),
+    ("pick_pair", "{user} y {partner}"),
+    ("pick_replaced", "Elegidos antes: {users}"),
+    ("pick_skip_button", "Pasar"),
+    ("pick_repick_button", "Volver a elegir"),
+    ("pick_cancel_button", "Cancelar"),
+    ("pick_acknowledge_button", "Confirmar"),
+    (
+        "pick_acknowledge_hint",
+        "Confirma esta elección en menos de {minutes}, o se elegirá a otra persona.",
+    ),
+    ("acknowledge_done", "{user} confirmó la elección del evento *{event}*. ✅"),
+    ("acknowledge_forbidden", "Solo {user} puede confirmar esta elección."),
+    ("acknowledge_missing", "Esta elección no tiene ninguna confirmación pendiente."),
+    ("skip_next_none", "El evento no tiene ninguna próxima ocurrencia que saltar."),
+    (
+        "reminder",
+        ":alarm_clock: *{event}* empieza en {minutes}\n\t\t_Origen: Recordatorio_",
+    ),
+    ("reminder_minute", "1 minuto"),
+    ("reminder_minutes", "{minutes} minutos"),
+    (
+        "announcement",
+        ":loudspeaker: {user} fue elegido para el evento *{event}* en <#{channel}>\n\t\t_Origen: Anuncio_",
+    ),
+    // Event views
+    ("close_button", "Cerrar"),
+    ("next_button", "Siguiente"),
+    ("previous_button", "Anterior"),
+    ("confirm_title", "¿Estás seguro?"),
+    ("confirm_yes", "¡Hazlo!"),
+    ("confirm_no", "¡Para, he cambiado de opinión!"),
+    ("repeat_every", "Cada {days}"),
+    ("repeat_custom", "Personalizada ({expression})"),
+    ("event_details_header", "Detalles de \"{name}\"  📅"),
+    ("event_name_field", "*Nombre*"),
+    ("event_participants_field", "*Participantes*"),
+    ("event_date_field", "*Fecha y hora*"),
+    ("event_repeat_field", "*Frecuencia*"),
+    ("event_description_field", "*Descripción* 📝"),
+    ("event_url_field", "*Enlace* 🔗"),
+    ("event_pick_mode_field", "*Modo de elección*"),
+    ("event_ends_field", "*Termina*"),
+    ("event_ended_field", "*Terminó*"),
+    ("event_reminder_field", "*Recordatorio*"),
+    ("event_reminder", "{minutes} antes de la elección"),
+    ("event_seed_field", "*Semilla* 🎲"),
+    ("event_user_group_field", "*Sincronizado con*"),
+    ("event_skipped_field", "*Saltadas* ⏭️"),
+    ("event_weekends_field", "*Fines de semana*"),
+    ("event_weekends", "No hay elecciones los sábados ni los domingos"),
+    ("event_anchor_field", "*Hilo de elecciones*"),
+    (
+        "event_anchor",
+        "Las elecciones automáticas se publican como respuestas a un mensaje fijado",
+    ),
+    ("event_private_field", "*Privado* 🔒"),
+    (
+        "event_private",
+        "Las elecciones automáticas solo se envían por mensaje directo a los participantes",
+    ),
+    ("event_out_of_office_field", "*Fuera de la oficina* 🌴"),
+    (
+        "event_out_of_office",
+        "Se salta a los participantes cuyo estado de Slack es fuera de la oficina en las elecciones automáticas",
+    ),
+    ("event_notify_channels_field", "*También se anuncia en*"),
+    ("event_away_field", "*Ausentes* 🌴"),
+    ("event_away_until", "{user} hasta {date}"),
+    ("event_pick_button", "Elegir"),
+    ("event_skip_next_button", "Saltar la siguiente"),
+    ("event_rotation_button", "Rotación"),
+    ("event_edit_button", "Editar"),
+    ("event_delete_button", "Eliminar"),
+    ("add_event_success_header", "¡Evento creado con éxito!  🎉"),
+    (
+        "add_event_success_intro",
+        "Ya puedes usarlo para *elegir* aleatoriamente a miembros de tu equipo 🤩",
+    ),
+    ("edit_event_success_header", "¡Evento actualizado con éxito!  🎉"),
+    (
+        "edit_event_success_intro",
+        "Puedes seguir usándolo para *elegir* aleatoriamente a miembros de tu equipo 🤩",
+    ),
+    ("delete_event_header", "¿Estás seguro?  ❌"),
+    (
+        "delete_event_confirm",
+        "¿Seguro que quieres eliminar el evento *{name}*?\nEsta acción no se puede deshacer.",
+    ),
+    ("delete_event_success", "¡Evento eliminado con éxito! 👍"),
+    ("select_show_header", "Selecciona el evento a mostrar  🔎"),
+    ("select_show_prompt", "Selecciona el evento del que quieres ver los detalles:"),
+    ("select_pick_header", "Selecciona el evento  🔎"),
+    (
+        "select_pick_prompt",
+        "Selecciona el evento en el que quieres elegir aleatoriamente a un participante:",
+    ),
+    ("select_edit_header", "Editar evento  🔎"),
+    ("select_edit_prompt", "Selecciona el evento que quieres editar:"),
+    ("select_delete_header", "Eliminar evento  🔎"),
+    ("select_delete_prompt", "Selecciona el evento que quieres eliminar:"),
+    ("select_placeholder", "Escribe para buscar los eventos"),
+    ("select_empty", "¡No se encontraron eventos! 🤷"),
+    ("list_events_header", "¡Echa un vistazo a tus eventos!"),
+    ("list_events_intro", "Aquí puedes gestionar todos tus eventos con facilidad."),
+    ("list_events_pick", "Elegir aleatoriamente"),
+    ("list_events_skip_next", "Saltar la siguiente fecha"),
+    ("list_events_show", "Ver detalles"),
+    ("list_events_edit", "Editar evento"),
+    ("list_events_duplicate", "Duplicar evento"),
+    ("list_events_delete", "Eliminar evento"),
+    ("list_events_create_button", "Crear un nuevo evento"),
+    ("stats_header", "Estadísticas de \"{event}\"  📊"),
+    ("stats_picks", "*Elecciones:* {count}"),
+    ("stats_skips", "*Turnos pasados:* {count}"),
+    ("stats_no_shows", "*Ausencias:* {count}"),
+    ("stats_last_picked", "*Última elección:* {date}"),
+    ("stats_never", "Nunca"),
+    ("history_header", "Historial de elecciones de \"{event}\"  📜"),
+    ("history_empty", "Todavía no hay elecciones registradas en el evento [{id}] 🤷"),
+    ("history_replacing", "en lugar de {user}"),
+    ("history_no_show", "Ausente"),
+    ("audit_header", "Actividad reciente  🕵️"),
+    ("audit_empty", "Todavía no hay actividad registrada en este canal 🤷"),
+    ("audit_someone", "Alguien"),
+    ("audit_event_created", "creó el evento"),
+    ("audit_event_updated", "editó el evento"),
+    ("audit_event_deleted", "eliminó el evento"),
+    ("audit_pick", "eligió en el evento"),
+    ("audit_skip", "pasó el turno en el evento"),
+    ("audit_repick", "volvió a elegir en el evento"),
+    ("audit_cancel_pick", "canceló la elección del evento"),
+    ("audit_skip_occurrence", "saltó la siguiente fecha del evento"),
+    ("audit_event_paused", "pausó el evento"),
+    ("audit_acknowledge", "confirmó la elección del evento"),
+    (
+        "audit_acknowledgment_expired",
+        "volvió a elegir tras la elección no confirmada del evento",
+    ),
+    ("audit_no_show", "marcó una ausencia en el evento"),
+    ("audit_volunteer", "se ofreció para la elección del evento"),
+    ("audit_install", "instaló la aplicación"),
+    ("audit_api_key_created", "creó una clave de API"),
+    ("audit_api_key_revoked", "revocó una clave de API"),
+    (
+        "cancel_pick",
+        "{user} canceló la elección anterior del evento *{event}*\n\t\t_Origen: Cancelación_",
+    ),
+    ("cancel_pick_again_button", "Elegir de nuevo"),
+    ("rotation_header", "Rotación de \"{event}\"  🔁"),
+    (
+        "rotation_intro",
+        "Los participantes se eligen en este orden. Muévelos para cambiar quién es el siguiente.",
+    ),
+    ("rotation_next", "siguiente"),
+    ("rotation_move_up", "Subir"),
+    ("rotation_move_down", "Bajar"),
+    ("schedule_header", "Próximas elecciones  🗓️"),
+    (
+        "schedule_empty",
+        "No hay elecciones automáticas programadas para los próximos {days} días.",
+    ),
+    (
+        "schedule_intro",
+        "Las elecciones automáticas de los próximos {days} días, en hora de {timezone}.",
+    ),
+    // Event form errors
+    ("form_error_name", "Por favor, introduce un nombre para el evento."),
+    ("form_error_date", "Por favor, elige la fecha y hora del evento."),
+    ("form_error_date_past", "La fecha del evento ya ha pasado."),
+    ("form_error_ends_at", "El evento debe terminar después de su fecha."),
+    (
+        "form_error_name_length",
+        "El nombre del evento es demasiado largo. Por favor, usa menos de 80 caracteres.",
+    ),
+    (
+        "form_error_participants",
+        "Por favor, selecciona los participantes del evento o introduce un grupo de usuarios.",
+    ),
+    (
+        "form_error_participants_limit",
+        "Los eventos pueden tener hasta {max} participantes en tu plan. Por favor, quita algunos.",
+    ),
+    (
+        "form_error_user_group",
+        "No se encontró el grupo de usuarios. Por favor, revisa su identificador (p. ej. `@team`).",
+    ),
+    (
+        "form_error_cron",
+        "Por favor, introduce la expresión cron de la frecuencia personalizada.",
+    ),
+    (
+        "form_error_days",
+        "Por favor, selecciona los días de la frecuencia de días específicos.",
+    ),
+    ("form_error_reminder", "El recordatorio debe ser un número de minutos."),
+    ("form_error_seed", "La semilla debe ser un número positivo."),
+    (
+        "form_error_description",
+        "La descripción es demasiado larga. Por favor, mantenla por debajo de 500 caracteres.",
+    ),
+    ("form_error_url", "Por favor, introduce un enlace que empiece por https://."),
+    (
+        "form_error_invalid",
+        "Algunos campos no son válidos. Por favor, revisa el evento e inténtalo de nuevo.",
+    ),
+    // Commands
+    ("error", "Error {code}: {reason}."),
+    (
+        "unknown_command",
+        "Lo sentimos, no encontramos ese comando. Escribe `/picker help` para ver todos los comandos disponibles",
+    ),
+    (
+        "free_plan_limit",
+        "El plan gratuito permite un solo evento por canal. Escribe `/picker upgrade` para crear más eventos. 🚀",
+    ),
+    (
+        "plan_limit",
+        "Este canal alcanzó el número máximo de eventos.",
+    ),
+    (
+        "away_not_participant",
+        "No participas en ningún evento de este canal.",
+    ),
+    ("away_invalid_days", "Los días deben ser un número entre 0 y {max}."),
+    ("next_picks_left", "Quedan por elegir en *{event}*:"),
+    (
+        "next_picks_new_round",
+        "Ya se eligió a todos en *{event}*, así que la siguiente ronda empieza con:",
+    ),
+    ("next_picks_none", "No hay nadie disponible para elegir."),
+    ("next_picks_away", "Ausentes: {users}"),
+    (
+        "preview_pick",
+        "🔮 Ahora se elegiría a {user}, quedando {left} por elegir. No se guardó nada.",
+    ),
+    (
+        "preview_pick_last",
+        "🔮 Ahora se elegiría a {user}, cerrando la ronda. No se guardó nada.",
+    ),
+    ("preview_pick_empty", "No hay nadie disponible para elegir en este evento."),
+    ("participant_joined", "{user} se unió a *{event}* 👋"),
+    ("participant_left", "{user} salió de *{event}*"),
+    (
+        "participant_removed",
+        "{user} salió del canal, por lo que se eliminó de los participantes de {events}",
+    ),
+    ("participant_already", "Ya participas en este evento."),
+    ("participant_missing", "No participas en este evento."),
+    ("participant_invalid", "Solo los usuarios de Slack pueden unirse a los eventos o salir de ellos."),
+    (
+        "calendar_unavailable",
+        "Las suscripciones al calendario aún no están disponibles.",
+    ),
+    (
+        "calendar_connect",
+        "📅 Conecta tu calendario de Google en <{url}|este enlace> para que se te salte en las elecciones automáticas de los días en los que estás fuera de la oficina. El enlace caduca en 10 minutos.",
+    ),
+    (
+        "calendar_connect_unavailable",
+        "Las conexiones de calendarios aún no están disponibles.",
+    ),
+    ("calendar_disconnected", "Tu calendario fue desconectado 👋"),
+    ("calendar_not_connected", "No has conectado tu calendario."),
+    (
+        "export_empty",
+        "Todavía no hay eventos para exportar en este canal.",
+    ),
+    (
+        "holidays_empty",
+        "Tu equipo todavía no tiene días festivos.",
+    ),
+    (
+        "import_invalid",
+        "El contenido no es una exportación válida. Pega todo el contenido del archivo compartido por `/picker export`.",
+    ),
+    (
+        "quiet_hours_empty",
+        "Tu equipo todavía no tiene horas de silencio.",
+    ),
+    ("upgrade_already", "Tu equipo ya tiene el plan Pro. 🎉"),
+    (
+        "upgrade_unavailable",
+        "Las mejoras de plan aún no están disponibles.",
+    ),
+    (
+        "locale_current",
+        "Los mensajes de tu equipo se muestran en {locale}.",
+    ),
+    (
+        "locale_set",
+        "Los mensajes de tu equipo ahora se mostrarán en {locale}. 🌍",
+    ),
+    (
+        "locale_invalid",
+        "Idioma desconocido. Usa uno de estos: {locales}.",
+    ),
+    ("config_message_current", "Las elecciones de tu equipo se anuncian con:\n>{message}"),
+    (
+        "config_message_default",
+        "Las elecciones de tu equipo se anuncian con el mensaje predeterminado.",
+    ),
+    ("config_message_set", "Las elecciones de tu equipo ahora se anunciarán con:\n>{message}"),
+    (
+        "config_message_reset",
+        "Las elecciones de tu equipo ahora se anunciarán con el mensaje predeterminado. 👋",
+    ),
+    (
+        "config_message_invalid",
+        "El mensaje debe mencionar al participante elegido con `{user}` y tener como máximo 500 caracteres.",
+    ),
+    (
+        "config_acknowledge_current",
+        "Las elecciones automáticas de tu equipo deben confirmarse en menos de {minutes}.",
+    ),
+    (
+        "config_acknowledge_off",
+        "Las elecciones automáticas de tu equipo no necesitan confirmación.",
+    ),
+    (
+        "config_acknowledge_set",
+        "Las elecciones automáticas de tu equipo ahora deben confirmarse en menos de {minutes}, o se elige a otra persona. ⏰",
+    ),
+    (
+        "config_acknowledge_reset",
+        "Las elecciones automáticas de tu equipo ya no necesitan confirmación.",
+    ),
+    ("config_acknowledge_invalid", "Los minutos deben ser un número entre 1 y {max}."),
+    (
+        "channel_invite_bot",
+        "El bot no puede unirse por sí mismo a este canal privado. Invítalo con `/invite @Team Event Picker`, o las elecciones no se publicarán aquí.",
+    ),
+    ("channel_access_lost", "No pude publicar la elección del evento *{event}*, porque ya no estoy en {channel}. Vuelve a añadirme al canal, o pausa el evento hasta entonces."),
+    ("channel_access_rejoin_button", "Unirse al canal"),
+    ("channel_access_pause_button", "Pausar el evento"),
+    ("channel_access_rejoined", "Volví a unirme a {channel}, así que las próximas elecciones se publicarán allí."),
+    ("channel_access_paused", "El evento *{event}* está en pausa. Edítalo para reanudar sus elecciones."),
+    ("onboarding_welcome", "👋 ¡Gracias por instalar *Team Event Picker*, <@{user}>! Elijo quién dirige los eventos de tu equipo, como la daily o la retro semanal, y aviso al canal cuando le toca."),
+    ("onboarding_choose_channel", "Elige el canal de tu equipo para empezar. Me uniré a él y dejaré allí un botón para crear tu primer evento."),
+    ("onboarding_channel_placeholder", "Elige un canal"),
+    ("onboarding_tour_button", "Hacer el recorrido"),
+    ("onboarding_tour", "*1.* Crea un evento en un canal con `/picker create`, eligiendo sus participantes y con qué frecuencia se repite.\n*2.* En cada una de sus fechas, elijo a uno de los participantes y lo publico en el canal. 🎯\n*3.* Cualquiera puede volver a elegir, saltar o presentarse voluntario desde los botones de la elección.\n*4.* Ajusta las elecciones de tu equipo con `/picker config`, o consulta todos los subcomandos con `/picker help`."),
+    ("onboarding_channel_welcome", "👋 ¡Hola a todos! <@{user}> me añadió para elegir quién dirige los eventos de este canal. Crea el primero abajo, o mira lo que puedo hacer con `/picker help`."),
+    ("onboarding_create_button", "Crear evento"),
+    ("onboarding_channel_posted", "Me uní a {channel} y dejé allí un botón para crear tu primer evento. 🎉"),
+    ("recap_title", "📊 Resumen de {month}"),
+    ("recap_picks", "*Elecciones:* {count}"),
+    ("recap_skips", "*Turnos pasados:* {count}"),
+    ("recap_acknowledgment", "*Confirmadas:* {rate}%"),
+    ("recap_leaderboard", "*Más elegidos*"),
+    ("recap_leader", "{position} {user} ({count})"),
+    ("config_recap_on", "Las elecciones de cada canal se resumen al final de cada mes."),
+    ("config_recap_off", "Las elecciones no se resumen al final de los meses."),
+    ("pick_no_show_button", "Marcar como ausente"),
+    ("no_show_done", "{user} fue marcado como ausente en el evento *{event}*."),
+    ("no_show_missing", "Este participante no fue elegido para el evento."),
+    ("no_show_already", "Esta elección ya fue marcada como ausente."),
+    ("config_noshow_on", "Los participantes marcados como ausentes son elegidos más a menudo."),
+    ("config_noshow_off", "Las ausencias solo se guardan en el historial."),
+    ("config_clock", "Las horas se muestran en formato de {clock} horas. 🕒"),
+    ("config_dates", "Las fechas se muestran como {example}. 📅"),
+    ("pick_event_link", "Enlace del evento"),
+    (
+        "private_pick",
+        "🔒 {user} fue elegido para el evento *{event}* de <#{channel}> (quedan {left}).",
+    ),
+    (
+        "private_pick_you",
+        "🔒 Fuiste elegido para el evento *{event}* de <#{channel}> (quedan {left}).",
+    ),
+    ("pick_volunteer_button", "Me encargo yo"),
+    (
+        "volunteer_not_participant",
+        "Solo los participantes del evento pueden encargarse de sus elecciones.",
+    ),
+    ("volunteer_already_picked", "Ya fuiste elegido para este evento."),
+    ("volunteer_stale", "Esta elección ya no es la última del evento."),
+    (
+        "export_done",
+        "*Exportar* 📦\nLos {count} eventos de este canal se compartieron como `{file}`. Escribe `/picker import` seguido de su contenido para crearlos en otro canal.",
+    ),
+    ("import_title", "*Importar* 📥"),
+    ("import_created", "Se crearon {count} eventos: {events}."),
+    ("import_skipped", "Se omitieron {count} eventos: {events}."),
+    ("import_error_invalid", "configuración no válida"),
+    ("import_error_limit", "se alcanzó el número máximo de eventos"),
+    ("import_error_conflict", "ya existe un evento con el mismo nombre"),
+    ("import_error_unknown", "error inesperado"),
+    (
+        "away_set",
+        "No se te elegirá durante los próximos {days} día(s) en: *{events}* 🌴",
+    ),
+    ("away_back", "¡Bienvenido de nuevo! Ya se te puede elegir en: *{events}* 👋"),
+    ("holidays_title", "*Días festivos* 🏖️\n{holidays}"),
+    ("holiday_added", "No habrá elecciones el {date} 🏖️"),
+    ("holiday_removed", "Volverá a haber elecciones el {date} 👋"),
+    ("quiet_hours_current", "*Horas de silencio* 🤫\n{hours}"),
+    (
+        "quiet_hours_set",
+        "No habrá publicaciones automáticas durante {hours} 🤫",
+    ),
+    (
+        "quiet_hours_reset",
+        "Las publicaciones automáticas volverán a ocurrir a cualquier hora 👋",
+    ),
+    (
+        "calendar_link",
+        "*Calendario* 📅\nSuscríbete a los eventos de este canal desde tu aplicación de calendario con el enlace de abajo. Mantenlo en privado, ya que cualquiera que lo tenga puede ver los eventos.\n{url}",
+    ),
+    (
+        "calendar_reset",
+        "*Calendario* 📅\nEl enlace anterior ya no funciona. Suscríbete a los eventos de este canal con el nuevo enlace de abajo.\n{url}",
+    ),
+    (
+        "token_issued",
+        "*Tokens de API* 🔑\nUsa el token de acceso como bearer token para elegir en los eventos de este canal a través de la API (p. ej. `POST /api/v1/events/<id>/pick`), y el token de actualización para renovarlo cuando caduque (`POST /api/auth/refresh`). Mantenlos en privado, ya que cualquiera que los tenga puede elegir en tu nombre.\nToken de acceso: `{access}`\nToken de actualización: `{refresh}`",
+    ),
+    (
+        "upgrade_link",
+        "*Mejorar* 🚀\nSuscríbete al plan Pro con el enlace de abajo para crear hasta {max} eventos en cada canal. Tu equipo se mejora en cuanto se complete el pago.\n{url}",
+    ),
+];
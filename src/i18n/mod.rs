@@ -0,0 +1,96 @@
+use serde_json::{Map, Value};
+
+use crate::domain::locale::Locale;
+
+mod en;
+mod es;
+mod pt;
+
+fn messages(locale: Locale) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        Locale::En => en::MESSAGES,
+        Locale::Es => es::MESSAGES,
+        Locale::Pt => pt::MESSAGES,
+    }
+}
+
+fn find(locale: Locale, key: &str) -> Option<&'static str> {
+    messages(locale)
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// Translates the message of the key, falling back to English when the locale misses it, and to
+/// the key itself when no catalog has it.
+pub fn t(locale: Locale, key: &str) -> &str {
+    find(locale, key)
+        .or_else(|| {
+            log::warn!("missing {} translation for {}", locale, key);
+            find(Locale::En, key)
+        })
+        .unwrap_or(key)
+}
+
+/// Translates the message of the key, replacing each `{name}` of it by the value of the argument.
+pub fn format(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    fill(t(locale, key), args)
+}
+
+/// Replaces each `{name}` of the message by the value of the argument.
+pub fn fill(message: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(message.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// The whole catalog of the locale, to be given to the templates.
+///
+/// The messages are escaped to be embedded on the JSON strings of the templates as they are (i.e.
+/// `{{{t.key}}}`).
+pub fn catalog(locale: Locale) -> Value {
+    let catalog = en::MESSAGES
+        .iter()
+        .map(|(key, _)| {
+            let message = Value::String(t(locale, key).to_string()).to_string();
+            let escaped = message[1..message.len() - 1].to_string();
+            (key.to_string(), Value::String(escaped))
+        })
+        .collect::<Map<String, Value>>();
+    Value::Object(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_t() {
+        assert_eq!(t(Locale::En, "form_edit_submit"), "Save");
+        assert_eq!(t(Locale::Pt, "form_edit_submit"), "Guardar");
+        assert_eq!(t(Locale::Es, "missing_key"), "missing_key");
+        assert_eq!(
+            format(
+                Locale::En,
+                "reminder_minutes",
+                &[("minutes", "15"), ("other", "1")]
+            ),
+            "15 minutes"
+        );
+    }
+
+    #[test]
+    fn test_catalogs_match() {
+        for locale in Locale::all() {
+            for (key, _) in en::MESSAGES {
+                assert!(find(locale, key).is_some(), "{} misses {}", locale, key);
+            }
+            assert_eq!(messages(locale).len(), en::MESSAGES.len());
+        }
+        assert_eq!(
+            catalog(Locale::En)["form_date_hint"],
+            "Ignore any hint regarding timezone here.\\nPlease select your timezone bellow!"
+        );
+    }
+}
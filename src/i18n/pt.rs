@@ -0,0 +1,553 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Event forms
+    ("form_add_header", "Cria o teu próprio evento!  📅"),
+    ("form_edit_header", "A editar o evento"),
+    ("form_add_submit", "Adicionar evento"),
+    ("form_edit_submit", "Guardar"),
+    ("form_cancel", "Cancelar"),
+    ("form_name_label", "Nome do evento"),
+    ("form_name_placeholder", "Escreve um nome para o teu evento"),
+    ("form_date_label", "Escolhe a data e a hora"),
+    (
+        "form_date_hint",
+        "Ignora aqui qualquer indicação sobre o fuso horário.\nSeleciona o teu fuso horário abaixo!",
+    ),
+    ("form_ends_at_label", "Quando termina o evento?"),
+    ("form_ends_at_hint", "Deixa vazio se o evento nunca terminar."),
+    ("form_repeat_label", "Com que frequência se repete o teu evento?"),
+    ("form_days_label", "Dias da semana"),
+    (
+        "form_days_hint",
+        "Apenas usado com a frequência de dias específicos.",
+    ),
+    ("form_cron_label", "Expressão cron"),
+    (
+        "form_cron_hint",
+        "Apenas usado com a frequência personalizada. Formato: minuto hora dia-do-mês mês dia-da-semana.",
+    ),
+    (
+        "form_pick_mode_label",
+        "Como devem ser escolhidos os participantes?",
+    ),
+    ("form_reminder_label", "Lembrar o canal antes da escolha"),
+    (
+        "form_reminder_hint",
+        "Minutos antes da escolha. Deixa vazio para não enviar o lembrete.",
+    ),
+    ("form_seed_label", "Semente das escolhas aleatórias"),
+    (
+        "form_seed_hint",
+        "Torna as escolhas aleatórias reproduzíveis: a mesma semente escolhe os mesmos participantes no mesmo dia. Deixa vazio para escolhas imprevisíveis.",
+    ),
+    ("form_description_label", "Descrição"),
+    (
+        "form_description_hint",
+        "Para que são escolhidos os participantes (p. ex. a agenda). É mostrada junto com as escolhas.",
+    ),
+    ("form_url_label", "Link"),
+    ("form_url_hint", "O link do evento (p. ex. a reunião ou a sua agenda)."),
+    ("form_no_repeat_label", "Escolhas consecutivas"),
+    (
+        "form_no_repeat_option",
+        "Nunca escolher a mesma pessoa duas vezes seguidas",
+    ),
+    ("form_anchor_label", "Tópico das escolhas"),
+    (
+        "form_anchor_option",
+        "Publicar as escolhas automáticas como respostas a uma mensagem fixada",
+    ),
+    ("form_visibility_label", "Escolhas privadas"),
+    ("form_visibility_option", "Enviar as escolhas apenas por mensagem direta aos participantes"),
+    ("form_out_of_office_label", "Fora do escritório"),
+    ("form_out_of_office_option", "Saltar os participantes com o estado do Slack fora do escritório"),
+    ("form_skip_weekends_label", "Fins de semana"),
+    (
+        "form_skip_weekends_option",
+        "Saltar as escolhas aos sábados e domingos",
+    ),
+    (
+        "form_skip_weekends_hint",
+        "Aplica-se aos eventos diários e semanais",
+    ),
+    ("form_participants_label", "Escolhe os participantes"),
+    ("form_user_group_label", "Ou sincroniza-os com um grupo de utilizadores"),
+    ("form_user_group_placeholder", "@backend-team"),
+    (
+        "form_user_group_hint",
+        "Os participantes são sincronizados com os membros do grupo antes de cada escolha",
+    ),
+    (
+        "form_part_time_label",
+        "Participantes a tempo parcial _(escolhidos metade das vezes)_",
+    ),
+    (
+        "form_notify_channels_label",
+        "Anunciar as escolhas também noutros canais",
+    ),
+    ("form_users_placeholder", "Seleciona utilizadores"),
+    ("form_channels_placeholder", "Seleciona canais"),
+    ("repeat_none", "Nenhuma"),
+    ("repeat_daily", "Diária"),
+    ("repeat_weekly", "Semanal"),
+    ("repeat_weekly_two", "Quinzenal"),
+    ("repeat_monthly", "Mensal"),
+    ("repeat_monthly_two", "Bimestral"),
+    ("repeat_yearly", "Anual"),
+    ("repeat_days", "Dias específicos"),
+    ("repeat_cron", "Personalizada (expressão cron)"),
+    ("weekday_mon", "Segunda-feira"),
+    ("weekday_tue", "Terça-feira"),
+    ("weekday_wed", "Quarta-feira"),
+    ("weekday_thu", "Quinta-feira"),
+    ("weekday_fri", "Sexta-feira"),
+    ("weekday_sat", "Sábado"),
+    ("weekday_sun", "Domingo"),
+    ("pick_mode_random", "Aleatório"),
+    ("pick_mode_round_robin", "Por turnos"),
+    ("pick_mode_pairs", "Em pares"),
+    // Picks
+    (
+        "pick_manual",
+        "{picker} escolheu aleatoriamente {user} para o evento *{event}* (faltam {left})",
+    ),
+    (
+        "pick_repick",
+        "{picker} voltou a escolher {user} para o evento *{event}* (faltam {left})",
+    ),
+    (
+        "pick_scheduler",
+        "{picker} escolheu automaticamente {user} para o evento *{event}* (faltam {left})",
+    ),
+    (
+        "pick_skip",
+        "{picker} passou a vez e agora {user} foi escolhido aleatoriamente para o evento *{event}* (faltam {left})",
+    ),
+    (
+        "pick_timeout",
+        "{picker} voltou a escolher automaticamente {user} para o evento *{event}* (faltam {left})",
+    ),
+    ("pick_volunteer", "{picker} ofereceu-se para o evento *{event}* (faltam {left})"),
+    ("source_manual", "Origem: Escolha manual"),
+    ("source_repick", "Origem: Nova escolha"),
+    ("source_scheduler", "Origem: Agendamento automático"),
+    ("source_skip", "Origem: Passar"),
+    ("source_timeout", "Origem: Escolha não confirmada"),
+    ("source_volunteer", "Origem: Voluntário"),
+    ("pick_pair", "{user} e {partner}"),
+    ("pick_replaced", "Escolhidos antes: {users}"),
+    ("pick_skip_button", "Passar"),
+    ("pick_repick_button", "Escolher de novo"),
+    ("pick_cancel_button", "Cancelar"),
+    ("pick_acknowledge_button", "Confirmar"),
+    (
+        "pick_acknowledge_hint",
+        "Confirma esta escolha em menos de {minutes}, ou será escolhida outra pessoa.",
+    ),
+    ("acknowledge_done", "{user} confirmou a escolha do evento *{event}*. ✅"),
+    ("acknowledge_forbidden", "Só {user} pode confirmar esta escolha."),
+    ("acknowledge_missing", "Esta escolha não tem nenhuma confirmação pendente."),
+    ("skip_next_none", "O evento não tem nenhuma próxima ocorrência para saltar."),
+    (
+        "reminder",
+        ":alarm_clock: *{event}* começa em {minutes}\n\t\t_Origem: Lembrete_",
+    ),
+    ("reminder_minute", "1 minuto"),
+    ("reminder_minutes", "{minutes} minutos"),
+    (
+        "announcement",
+        ":loudspeaker: {user} foi escolhido para o evento *{event}* em <#{channel}>\n\t\t_Origem: Anúncio_",
+    ),
+    // Event views
+    ("close_button", "Fechar"),
+    ("next_button", "Seguinte"),
+    ("previous_button", "Anterior"),
+    ("confirm_title", "Tens a certeza?"),
+    ("confirm_yes", "Avançar!"),
+    ("confirm_no", "Para, mudei de ideias!"),
+    ("repeat_every", "Todas as semanas: {days}"),
+    ("repeat_custom", "Personalizada ({expression})"),
+    ("event_details_header", "Detalhes de \"{name}\"  📅"),
+    ("event_name_field", "*Nome*"),
+    ("event_participants_field", "*Participantes*"),
+    ("event_date_field", "*Data e hora*"),
+    ("event_repeat_field", "*Frequência*"),
+    ("event_description_field", "*Descrição* 📝"),
+    ("event_url_field", "*Ligação* 🔗"),
+    ("event_pick_mode_field", "*Modo de escolha*"),
+    ("event_ends_field", "*Termina*"),
+    ("event_ended_field", "*Terminou*"),
+    ("event_reminder_field", "*Lembrete*"),
+    ("event_reminder", "{minutes} antes da escolha"),
+    ("event_seed_field", "*Semente* 🎲"),
+    ("event_user_group_field", "*Sincronizado com*"),
+    ("event_skipped_field", "*Saltadas* ⏭️"),
+    ("event_weekends_field", "*Fins de semana*"),
+    ("event_weekends", "Não há escolhas aos sábados e domingos"),
+    ("event_anchor_field", "*Fio de escolhas*"),
+    (
+        "event_anchor",
+        "As escolhas automáticas são publicadas como respostas a uma mensagem afixada",
+    ),
+    ("event_private_field", "*Privado* 🔒"),
+    (
+        "event_private",
+        "As escolhas automáticas só são enviadas por mensagem direta aos participantes",
+    ),
+    ("event_out_of_office_field", "*Fora do escritório* 🌴"),
+    (
+        "event_out_of_office",
+        "Os participantes cujo estado do Slack é fora do escritório são saltados nas escolhas automáticas",
+    ),
+    ("event_notify_channels_field", "*Também anunciado em*"),
+    ("event_away_field", "*Ausentes* 🌴"),
+    ("event_away_until", "{user} até {date}"),
+    ("event_pick_button", "Escolher"),
+    ("event_skip_next_button", "Saltar a próxima"),
+    ("event_rotation_button", "Rotação"),
+    ("event_edit_button", "Editar"),
+    ("event_delete_button", "Eliminar"),
+    ("add_event_success_header", "Evento criado com sucesso!  🎉"),
+    (
+        "add_event_success_intro",
+        "Já o podes usar para *escolher* aleatoriamente membros da tua equipa 🤩",
+    ),
+    ("edit_event_success_header", "Evento atualizado com sucesso!  🎉"),
+    (
+        "edit_event_success_intro",
+        "Podes continuar a usá-lo para *escolher* aleatoriamente membros da tua equipa 🤩",
+    ),
+    ("delete_event_header", "Tens a certeza?  ❌"),
+    (
+        "delete_event_confirm",
+        "Tens a certeza de que queres eliminar o evento *{name}*?\nEsta ação não pode ser desfeita.",
+    ),
+    ("delete_event_success", "Evento eliminado com sucesso! 👍"),
+    ("select_show_header", "Seleciona o evento a mostrar  🔎"),
+    ("select_show_prompt", "Seleciona o evento de que queres ver os detalhes:"),
+    ("select_pick_header", "Seleciona o evento  🔎"),
+    (
+        "select_pick_prompt",
+        "Seleciona o evento em que queres escolher aleatoriamente um participante:",
+    ),
+    ("select_edit_header", "Editar evento  🔎"),
+    ("select_edit_prompt", "Seleciona o evento que queres editar:"),
+    ("select_delete_header", "Eliminar evento  🔎"),
+    ("select_delete_prompt", "Seleciona o evento que queres eliminar:"),
+    ("select_placeholder", "Escreve para procurar os eventos"),
+    ("select_empty", "Nenhum evento encontrado! 🤷"),
+    ("list_events_header", "Vê os teus eventos!"),
+    ("list_events_intro", "Aqui podes gerir todos os teus eventos com facilidade."),
+    ("list_events_pick", "Escolher aleatoriamente"),
+    ("list_events_skip_next", "Saltar a próxima data"),
+    ("list_events_show", "Ver detalhes"),
+    ("list_events_edit", "Editar evento"),
+    ("list_events_duplicate", "Duplicar evento"),
+    ("list_events_delete", "Eliminar evento"),
+    ("list_events_create_button", "Criar um novo evento"),
+    ("stats_header", "Estatísticas de \"{event}\"  📊"),
+    ("stats_picks", "*Escolhas:* {count}"),
+    ("stats_skips", "*Vezes passadas:* {count}"),
+    ("stats_no_shows", "*Faltas:* {count}"),
+    ("stats_last_picked", "*Última escolha:* {date}"),
+    ("stats_never", "Nunca"),
+    ("history_header", "Histórico de escolhas de \"{event}\"  📜"),
+    ("history_empty", "Ainda não há escolhas registadas no evento [{id}] 🤷"),
+    ("history_replacing", "em vez de {user}"),
+    ("history_no_show", "Falta"),
+    ("audit_header", "Atividade recente  🕵️"),
+    ("audit_empty", "Ainda não há atividade registada neste canal 🤷"),
+    ("audit_someone", "Alguém"),
+    ("audit_event_created", "criou o evento"),
+    ("audit_event_updated", "editou o evento"),
+    ("audit_event_deleted", "eliminou o evento"),
+    ("audit_pick", "escolheu no evento"),
+    ("audit_skip", "passou a vez no evento"),
+    ("audit_repick", "voltou a escolher no evento"),
+    ("audit_cancel_pick", "cancelou a escolha do evento"),
+    ("audit_skip_occurrence", "saltou a próxima data do evento"),
+    ("audit_event_paused", "pausou o evento"),
+    ("audit_acknowledge", "confirmou a escolha do evento"),
+    (
+        "audit_acknowledgment_expired",
+        "voltou a escolher após a escolha não confirmada do evento",
+    ),
+    ("audit_no_show", "marcou uma falta no evento"),
+    ("audit_volunteer", "ofereceu-se para a escolha do evento"),
+    ("audit_install", "instalou a aplicação"),
+    ("audit_api_key_created", "criou uma chave de API"),
+    ("audit_api_key_revoked", "revogou uma chave de API"),
+    (
+        "cancel_pick",
+        "{user} cancelou a escolha anterior do evento *{event}*\n\t\t_Origem: Cancelamento_",
+    ),
+    ("cancel_pick_again_button", "Escolher de novo"),
+    ("rotation_header", "Rotação de \"{event}\"  🔁"),
+    (
+        "rotation_intro",
+        "Os participantes são escolhidos por esta ordem. Move-os para mudar quem é o próximo.",
+    ),
+    ("rotation_next", "próximo"),
+    ("rotation_move_up", "Mover para cima"),
+    ("rotation_move_down", "Mover para baixo"),
+    ("schedule_header", "Próximas escolhas  🗓️"),
+    (
+        "schedule_empty",
+        "Não há escolhas automáticas agendadas para os próximos {days} dias.",
+    ),
+    (
+        "schedule_intro",
+        "As escolhas automáticas dos próximos {days} dias, na hora de {timezone}.",
+    ),
+    // Event form errors
+    ("form_error_name", "Por favor, introduz um nome para o evento."),
+    ("form_error_date", "Por favor, escolhe a data e hora do evento."),
+    ("form_error_date_past", "A data do evento já passou."),
+    ("form_error_ends_at", "O evento deve terminar depois da sua data."),
+    (
+        "form_error_name_length",
+        "O nome do evento é demasiado longo. Por favor, usa menos de 80 caracteres.",
+    ),
+    (
+        "form_error_participants",
+        "Por favor, seleciona os participantes do evento ou introduz um grupo de utilizadores.",
+    ),
+    (
+        "form_error_participants_limit",
+        "Os eventos podem ter até {max} participantes no teu plano. Por favor, remove alguns.",
+    ),
+    (
+        "form_error_user_group",
+        "O grupo de utilizadores não foi encontrado. Por favor, verifica o seu identificador (p. ex. `@team`).",
+    ),
+    (
+        "form_error_cron",
+        "Por favor, introduz a expressão cron da frequência personalizada.",
+    ),
+    (
+        "form_error_days",
+        "Por favor, seleciona os dias da frequência de dias específicos.",
+    ),
+    ("form_error_reminder", "O lembrete deve ser um número de minutos."),
+    ("form_error_seed", "A semente deve ser um número positivo."),
+    (
+        "form_error_description",
+        "A descrição é demasiado longa. Por favor, mantém-na abaixo de 500 caracteres.",
+    ),
+    ("form_error_url", "Por favor, introduz um link que comece por https://."),
+    (
+        "form_error_invalid",
+        "Alguns campos não são válidos. Por favor, revê o evento e tenta novamente.",
+    ),
+    // Commands
+    ("error", "Erro {code}: {reason}."),
+    (
+        "unknown_command",
+        "Desculpa, mas não encontrámos esse comando. Escreve `/picker help` para ver todos os comandos disponíveis",
+    ),
+    (
+        "free_plan_limit",
+        "O plano gratuito permite um único evento por canal. Escreve `/picker upgrade` para criar mais eventos. 🚀",
+    ),
+    (
+        "plan_limit",
+        "Este canal atingiu o número máximo de eventos.",
+    ),
+    (
+        "away_not_participant",
+        "Não participas em nenhum evento deste canal.",
+    ),
+    ("away_invalid_days", "Os dias devem ser um número entre 0 e {max}."),
+    ("next_picks_left", "Faltam escolher em *{event}*:"),
+    (
+        "next_picks_new_round",
+        "Já foram todos escolhidos em *{event}*, por isso a próxima ronda começa com:",
+    ),
+    ("next_picks_none", "Não há ninguém disponível para escolher."),
+    ("next_picks_away", "Ausentes: {users}"),
+    (
+        "preview_pick",
+        "🔮 Agora seria escolhido {user}, ficando {left} por escolher. Nada foi guardado.",
+    ),
+    (
+        "preview_pick_last",
+        "🔮 Agora seria escolhido {user}, fechando a ronda. Nada foi guardado.",
+    ),
+    ("preview_pick_empty", "Não há ninguém disponível para escolher neste evento."),
+    ("participant_joined", "{user} juntou-se a *{event}* 👋"),
+    ("participant_left", "{user} saiu de *{event}*"),
+    (
+        "participant_removed",
+        "{user} saiu do canal, pelo que foi removido dos participantes de {events}",
+    ),
+    ("participant_already", "Já participas neste evento."),
+    ("participant_missing", "Não participas neste evento."),
+    ("participant_invalid", "Só os utilizadores do Slack podem juntar-se aos eventos ou sair deles."),
+    (
+        "calendar_unavailable",
+        "As subscrições do calendário ainda não estão disponíveis.",
+    ),
+    (
+        "calendar_connect",
+        "📅 Liga o teu calendário do Google <{url}|nesta ligação> para seres saltado nas escolhas automáticas dos dias em que estás fora do escritório. A ligação expira em 10 minutos.",
+    ),
+    (
+        "calendar_connect_unavailable",
+        "As ligações de calendários ainda não estão disponíveis.",
+    ),
+    ("calendar_disconnected", "O teu calendário foi desligado 👋"),
+    ("calendar_not_connected", "Não ligaste o teu calendário."),
+    (
+        "export_empty",
+        "Ainda não há eventos para exportar neste canal.",
+    ),
+    ("holidays_empty", "A tua equipa ainda não tem feriados."),
+    (
+        "import_invalid",
+        "O conteúdo não é uma exportação válida. Cola todo o conteúdo do ficheiro partilhado por `/picker export`.",
+    ),
+    (
+        "quiet_hours_empty",
+        "A tua equipa ainda não tem horas de silêncio.",
+    ),
+    ("upgrade_already", "A tua equipa já tem o plano Pro. 🎉"),
+    (
+        "upgrade_unavailable",
+        "As atualizações de plano ainda não estão disponíveis.",
+    ),
+    (
+        "locale_current",
+        "As mensagens da tua equipa são mostradas em {locale}.",
+    ),
+    (
+        "locale_set",
+        "As mensagens da tua equipa passam a ser mostradas em {locale}. 🌍",
+    ),
+    (
+        "locale_invalid",
+        "Idioma desconhecido. Usa um destes: {locales}.",
+    ),
+    ("config_message_current", "As escolhas da tua equipa são anunciadas com:\n>{message}"),
+    (
+        "config_message_default",
+        "As escolhas da tua equipa são anunciadas com a mensagem predefinida.",
+    ),
+    ("config_message_set", "As escolhas da tua equipa passam a ser anunciadas com:\n>{message}"),
+    (
+        "config_message_reset",
+        "As escolhas da tua equipa passam a ser anunciadas com a mensagem predefinida. 👋",
+    ),
+    (
+        "config_message_invalid",
+        "A mensagem tem de mencionar o participante escolhido com `{user}` e ter no máximo 500 caracteres.",
+    ),
+    (
+        "config_acknowledge_current",
+        "As escolhas automáticas da tua equipa têm de ser confirmadas em menos de {minutes}.",
+    ),
+    (
+        "config_acknowledge_off",
+        "As escolhas automáticas da tua equipa não precisam de confirmação.",
+    ),
+    (
+        "config_acknowledge_set",
+        "As escolhas automáticas da tua equipa passam a ter de ser confirmadas em menos de {minutes}, ou é escolhida outra pessoa. ⏰",
+    ),
+    (
+        "config_acknowledge_reset",
+        "As escolhas automáticas da tua equipa deixam de precisar de confirmação.",
+    ),
+    ("config_acknowledge_invalid", "Os minutos têm de ser um número entre 1 e {max}."),
+    (
+        "channel_invite_bot",
+        "O bot não consegue entrar sozinho neste canal privado. Convida-o com `/invite @Team Event Picker`, ou as escolhas não serão publicadas aqui.",
+    ),
+    ("channel_access_lost", "Não consegui publicar a escolha do evento *{event}*, porque já não estou em {channel}. Adiciona-me de novo ao canal, ou pausa o evento até lá."),
+    ("channel_access_rejoin_button", "Entrar no canal"),
+    ("channel_access_pause_button", "Pausar o evento"),
+    ("channel_access_rejoined", "Voltei a entrar em {channel}, por isso as próximas escolhas serão publicadas lá."),
+    ("channel_access_paused", "O evento *{event}* está em pausa. Edita-o para retomar as suas escolhas."),
+    ("onboarding_welcome", "👋 Obrigado por instalares a *Team Event Picker*, <@{user}>! Escolho quem conduz os eventos da tua equipa, como a daily ou a retro semanal, e aviso o canal quando é a sua vez."),
+    ("onboarding_choose_channel", "Escolhe o canal da tua equipa para começar. Junto-me a ele e deixo lá um botão para criar o teu primeiro evento."),
+    ("onboarding_channel_placeholder", "Escolhe um canal"),
+    ("onboarding_tour_button", "Fazer a visita guiada"),
+    ("onboarding_tour", "*1.* Cria um evento num canal com `/picker create`, escolhendo os seus participantes e com que frequência se repete.\n*2.* Em cada uma das suas datas, escolho um dos participantes e publico-o no canal. 🎯\n*3.* Qualquer pessoa pode escolher de novo, saltar ou voluntariar-se a partir dos botões da escolha.\n*4.* Ajusta as escolhas da tua equipa com `/picker config`, ou vê todos os subcomandos com `/picker help`."),
+    ("onboarding_channel_welcome", "👋 Olá a todos! <@{user}> adicionou-me para escolher quem conduz os eventos deste canal. Cria o primeiro abaixo, ou vê o que consigo fazer com `/picker help`."),
+    ("onboarding_create_button", "Criar evento"),
+    ("onboarding_channel_posted", "Juntei-me a {channel} e deixei lá um botão para criar o teu primeiro evento. 🎉"),
+    ("recap_title", "📊 Resumo de {month}"),
+    ("recap_picks", "*Escolhas:* {count}"),
+    ("recap_skips", "*Vezes passadas:* {count}"),
+    ("recap_acknowledgment", "*Confirmadas:* {rate}%"),
+    ("recap_leaderboard", "*Mais escolhidos*"),
+    ("recap_leader", "{position} {user} ({count})"),
+    ("config_recap_on", "As escolhas de cada canal são resumidas no fim de cada mês."),
+    ("config_recap_off", "As escolhas não são resumidas no fim dos meses."),
+    ("pick_no_show_button", "Marcar como ausente"),
+    ("no_show_done", "{user} foi marcado como ausente no evento *{event}*."),
+    ("no_show_missing", "Este participante não foi escolhido para o evento."),
+    ("no_show_already", "Esta escolha já foi marcada como ausente."),
+    ("config_noshow_on", "Os participantes marcados como ausentes são escolhidos mais vezes."),
+    ("config_noshow_off", "As ausências são guardadas apenas no histórico."),
+    ("config_clock", "As horas são mostradas no formato de {clock} horas. 🕒"),
+    ("config_dates", "As datas são mostradas como {example}. 📅"),
+    ("pick_event_link", "Link do evento"),
+    (
+        "private_pick",
+        "🔒 {user} foi escolhido para o evento *{event}* de <#{channel}> (faltam {left}).",
+    ),
+    (
+        "private_pick_you",
+        "🔒 Foste escolhido para o evento *{event}* de <#{channel}> (faltam {left}).",
+    ),
+    ("pick_volunteer_button", "Fico eu"),
+    (
+        "volunteer_not_participant",
+        "Apenas os participantes do evento podem ficar com as suas escolhas.",
+    ),
+    ("volunteer_already_picked", "Já foste escolhido para este evento."),
+    ("volunteer_stale", "Esta escolha já não é a última do evento."),
+    (
+        "export_done",
+        "*Exportar* 📦\nOs {count} eventos deste canal foram partilhados como `{file}`. Escreve `/picker import` seguido do seu conteúdo para os criar noutro canal.",
+    ),
+    ("import_title", "*Importar* 📥"),
+    ("import_created", "Foram criados {count} eventos: {events}."),
+    ("import_skipped", "Foram ignorados {count} eventos: {events}."),
+    ("import_error_invalid", "definições inválidas"),
+    ("import_error_limit", "foi atingido o número máximo de eventos"),
+    ("import_error_conflict", "já existe um evento com o mesmo nome"),
+    ("import_error_unknown", "erro inesperado"),
+    (
+        "away_set",
+        "Não vais ser escolhido durante os próximos {days} dia(s) em: *{events}* 🌴",
+    ),
+    ("away_back", "Bem-vindo de volta! Já podes ser escolhido em: *{events}* 👋"),
+    ("holidays_title", "*Feriados* 🏖️\n{holidays}"),
+    ("holiday_added", "Não vai haver escolhas a {date} 🏖️"),
+    ("holiday_removed", "Vai voltar a haver escolhas a {date} 👋"),
+    ("quiet_hours_current", "*Horas de silêncio* 🤫\n{hours}"),
+    (
+        "quiet_hours_set",
+        "Não vai haver publicações automáticas durante {hours} 🤫",
+    ),
+    (
+        "quiet_hours_reset",
+        "As publicações automáticas vão voltar a acontecer a qualquer hora 👋",
+    ),
+    (
+        "calendar_link",
+        "*Calendário* 📅\nSubscreve os eventos deste canal a partir da tua aplicação de calendário com a ligação abaixo. Mantém-na privada, já que qualquer pessoa com ela pode ver os eventos.\n{url}",
+    ),
+    (
+        "calendar_reset",
+        "*Calendário* 📅\nA ligação anterior já não funciona. Subscreve os eventos deste canal com a nova ligação abaixo.\n{url}",
+    ),
+    (
+        "token_issued",
+        "*Tokens da API* 🔑\nUsa o token de acesso como bearer token para escolher nos eventos deste canal através da API (p. ex. `POST /api/v1/events/<id>/pick`), e o token de atualização para o renovar quando expirar (`POST /api/auth/refresh`). Mantém-nos privados, já que qualquer pessoa com eles pode escolher em teu nome.\nToken de acesso: `{access}`\nToken de atualização: `{refresh}`",
+    ),
+    (
+        "upgrade_link",
+        "*Upgrade* 🚀\nSubscreve o plano Pro com a ligação abaixo para criar até {max} eventos em cada canal. A tua equipa passa para o plano Pro assim que o pagamento estiver concluído.\n{url}",
+    ),
+];
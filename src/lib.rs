@@ -1,6 +1,8 @@
 pub mod config;
 pub mod domain;
+pub mod error;
 pub mod helpers;
+pub mod i18n;
 pub mod repository;
 pub mod scheduler;
 pub mod slack;
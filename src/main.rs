@@ -1,7 +1,7 @@
 use anyhow::Result;
-use clap::Parser;
 use log::LevelFilter;
 use team_event_picker::config::Config;
+use team_event_picker::helpers::logging;
 use team_event_picker::slack;
 
 #[tokio::main]
@@ -10,8 +10,12 @@ async fn main() -> Result<()> {
     // since we're not going to use a `.env` file if we deploy this application.
     let dotenv_result = dotenv::dotenv();
 
-    // Initialize the logger.
-    tracing_subscriber::fmt::init();
+    // Load our configuration from the command line, the environment and the config file, if any.
+    // This will exit with a help message if something is wrong.
+    let config = Config::load(std::env::args_os()).map_err(anyhow::Error::msg)?;
+
+    // Initialize the logger, on the format of the configuration.
+    logging::init(config.log_format);
     log::set_max_level(LevelFilter::Trace);
 
     if let Err(err) = dotenv_result {
@@ -19,10 +23,25 @@ async fn main() -> Result<()> {
     } else {
         log::info!("loaded .env file");
     };
-
-    // Parse our configuration from the environment.
-    // This will exit with a help message if something is wrong.
-    let config = Config::parse();
+    if let Some(path) = &config.config {
+        log::info!("loaded config file {}", path);
+    }
+    if let Err(errors) = config.validate() {
+        for err in errors {
+            log::error!("invalid configuration: {}", err);
+        }
+        anyhow::bail!("invalid configuration");
+    }
+    if config.check {
+        if let Err(errors) = slack::check(&config).await {
+            for err in errors {
+                log::error!("configuration check failed: {}", err);
+            }
+            anyhow::bail!("configuration check failed");
+        }
+        log::info!("configuration is valid");
+        return Ok(());
+    }
 
     // We spin up our API.
     slack::serve(config).await?;
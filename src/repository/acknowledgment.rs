@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use bson::doc;
+use mongodb::options::ReplaceOptions;
+
+use crate::domain::entities::PendingAcknowledgment;
+
+use super::errors::{DeleteError, FindAllError, FindError, InsertError};
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Saves the pending acknowledgment, replacing the previous one of its event.
+    async fn save_acknowledgment(
+        &self,
+        acknowledgment: PendingAcknowledgment,
+    ) -> Result<PendingAcknowledgment, InsertError>;
+    async fn find_acknowledgment(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<PendingAcknowledgment, FindError>;
+    async fn delete_acknowledgment(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<PendingAcknowledgment, DeleteError>;
+    /// Finds the pending acknowledgments expired by the timestamp (in seconds), of all the teams.
+    async fn find_expired_acknowledgments_unprotected(
+        &self,
+        timestamp: i64,
+    ) -> Result<Vec<PendingAcknowledgment>, FindAllError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn save_acknowledgment(
+        &self,
+        acknowledgment: PendingAcknowledgment,
+    ) -> Result<PendingAcknowledgment, InsertError> {
+        let filter =
+            doc! { "event": acknowledgment.event, "channel": acknowledgment.channel.clone() };
+        let options = ReplaceOptions::builder().upsert(true).build();
+        self.db
+            .collection::<PendingAcknowledgment>("acknowledgments")
+            .replace_one(filter, &acknowledgment, options)
+            .await?;
+        Ok(acknowledgment)
+    }
+
+    async fn find_acknowledgment(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<PendingAcknowledgment, FindError> {
+        self.db
+            .collection::<PendingAcknowledgment>("acknowledgments")
+            .find_one(doc! { "event": event, "channel": channel }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn delete_acknowledgment(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<PendingAcknowledgment, DeleteError> {
+        self.db
+            .collection::<PendingAcknowledgment>("acknowledgments")
+            .find_one_and_delete(doc! { "event": event, "channel": channel }, None)
+            .await?
+            .ok_or(DeleteError::NotFound)
+    }
+
+    async fn find_expired_acknowledgments_unprotected(
+        &self,
+        timestamp: i64,
+    ) -> Result<Vec<PendingAcknowledgment>, FindAllError> {
+        let mut cursor = self
+            .db
+            .collection::<PendingAcknowledgment>("acknowledgments")
+            .find(doc! { "expires_at": { "$lte": timestamp } }, None)
+            .await?;
+
+        let mut result: Vec<PendingAcknowledgment> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use bson::doc;
+
+use crate::domain::entities::ApiKey;
+
+use super::counter;
+use super::errors::{DeleteError, FindAllError, FindError, InsertError};
+use super::helpers::index;
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError>;
+    async fn find_key_by_hash(&self, hash: String) -> Result<ApiKey, FindError>;
+    /// Finds the keys of the channel of the team.
+    async fn find_keys(&self, team: String, channel: String) -> Result<Vec<ApiKey>, FindAllError>;
+    /// Deletes a key of the channel of the team.
+    async fn delete_key(
+        &self,
+        id: u32,
+        team: String,
+        channel: String,
+    ) -> Result<ApiKey, DeleteError>;
+    /// Deletes all the keys of the teams, returning how many were deleted.
+    async fn delete_team_keys(&self, teams: Vec<String>) -> Result<u64, DeleteError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+        counter::init(&db, &db.collection::<ApiKey>("api_keys")).await?;
+        db.collection::<ApiKey>("api_keys")
+            .create_indexes(
+                vec![
+                    index(doc! { "id": 1 }, "id_unique", true),
+                    index(doc! { "hash": 1 }, "hash_unique", true),
+                    index(doc! { "team": 1 }, "team", false),
+                ],
+                None,
+            )
+            .await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError> {
+        let mut result = key;
+        let collection = self.db.collection::<ApiKey>("api_keys");
+        collection
+            .insert_one(
+                counter::fill_with_id(&self.db, &collection, &mut result).await?,
+                None,
+            )
+            .await?;
+        Ok(result)
+    }
+
+    async fn find_key_by_hash(&self, hash: String) -> Result<ApiKey, FindError> {
+        self.db
+            .collection::<ApiKey>("api_keys")
+            .find_one(doc! { "hash": hash }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_keys(&self, team: String, channel: String) -> Result<Vec<ApiKey>, FindAllError> {
+        let mut cursor = self
+            .db
+            .collection::<ApiKey>("api_keys")
+            .find(doc! { "team": team, "channel": channel }, None)
+            .await?;
+
+        let mut result: Vec<ApiKey> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn delete_key(
+        &self,
+        id: u32,
+        team: String,
+        channel: String,
+    ) -> Result<ApiKey, DeleteError> {
+        self.db
+            .collection::<ApiKey>("api_keys")
+            .find_one_and_delete(doc! { "id": id, "team": team, "channel": channel }, None)
+            .await?
+            .ok_or(DeleteError::NotFound)
+    }
+
+    async fn delete_team_keys(&self, teams: Vec<String>) -> Result<u64, DeleteError> {
+        let teams = teams
+            .iter()
+            .map(bson::Bson::from)
+            .collect::<Vec<bson::Bson>>();
+        let result = self
+            .db
+            .collection::<ApiKey>("api_keys")
+            .delete_many(doc! { "team": { "$in": teams } }, None)
+            .await?;
+        Ok(result.deleted_count)
+    }
+}
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use bson::doc;
+
+use crate::domain::entities::{AuditAction, AuditRecord};
+
+use super::errors::{FindAllError, InsertError};
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn insert_record(&self, record: AuditRecord) -> Result<AuditRecord, InsertError>;
+    /// Finds the latest records of a channel, including the ones of its team that concern no
+    /// channel, newest first.
+    async fn find_records(
+        &self,
+        team_id: String,
+        channel: String,
+        limit: i64,
+    ) -> Result<Vec<AuditRecord>, FindAllError>;
+    /// Finds the records of the action performed on the events of a channel.
+    async fn find_action_records(
+        &self,
+        team_id: String,
+        channel: String,
+        action: AuditAction,
+        events: Vec<u32>,
+    ) -> Result<Vec<AuditRecord>, FindAllError>;
+    /// Finds the records of a channel, from the `from` timestamp (inclusive) to the `to` one
+    /// (exclusive).
+    async fn find_records_between(
+        &self,
+        team_id: String,
+        channel: String,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<AuditRecord>, FindAllError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn insert_record(&self, record: AuditRecord) -> Result<AuditRecord, InsertError> {
+        self.db
+            .collection::<AuditRecord>("audit")
+            .insert_one(&record, None)
+            .await?;
+
+        Ok(record)
+    }
+
+    async fn find_records(
+        &self,
+        team_id: String,
+        channel: String,
+        limit: i64,
+    ) -> Result<Vec<AuditRecord>, FindAllError> {
+        let filter = doc! {
+            "team_id": team_id,
+            "$or": [{ "channel": channel }, { "channel": null }]
+        };
+        let options = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .limit(limit)
+            .build();
+        let mut cursor = self
+            .db
+            .collection::<AuditRecord>("audit")
+            .find(filter, options)
+            .await?;
+
+        let mut result: Vec<AuditRecord> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn find_action_records(
+        &self,
+        team_id: String,
+        channel: String,
+        action: AuditAction,
+        events: Vec<u32>,
+    ) -> Result<Vec<AuditRecord>, FindAllError> {
+        let action = bson::to_bson(&action).map_err(|err| {
+            log::error!("could not serialize audit action: {}", err);
+            FindAllError::Unknown
+        })?;
+        let filter = doc! {
+            "team_id": team_id,
+            "channel": channel,
+            "action": action,
+            "event": { "$in": events },
+        };
+        let mut cursor = self
+            .db
+            .collection::<AuditRecord>("audit")
+            .find(filter, None)
+            .await?;
+
+        let mut result: Vec<AuditRecord> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn find_records_between(
+        &self,
+        team_id: String,
+        channel: String,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<AuditRecord>, FindAllError> {
+        let filter = doc! {
+            "team_id": team_id,
+            "channel": channel,
+            "timestamp": { "$gte": from, "$lt": to },
+        };
+        let mut cursor = self
+            .db
+            .collection::<AuditRecord>("audit")
+            .find(filter, None)
+            .await?;
+
+        let mut result: Vec<AuditRecord> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+}
@@ -1,16 +1,30 @@
 use async_trait::async_trait;
 use bson::doc;
 
-use crate::domain::entities::{Auth, HasId};
+use crate::domain::entities::{Auth, RefreshToken};
 
-use super::errors::{self, FindAllError, FindError, InsertError, UpdateError};
+use super::counter;
+use super::errors::{self, DeleteError, FindAllError, FindError, InsertError, UpdateError};
+use super::helpers::index;
 
 #[async_trait]
 pub trait Repository: Send + Sync {
     async fn insert(&self, auth: Auth) -> Result<Auth, InsertError>;
     async fn update(&self, auth: Auth) -> Result<Auth, UpdateError>;
     async fn find_by_team(&self, team: String) -> Result<Auth, FindError>;
+    /// Finds the organization-wide install a team of the organization was mapped to.
+    async fn find_by_enterprise_team(&self, team: String) -> Result<Auth, FindError>;
+    /// Finds the tokens of the teams, including the organization-wide installs mapped to them.
     async fn find_all_by_team(&self, teams: Vec<String>) -> Result<Vec<Auth>, FindAllError>;
+    /// Maps a team of the organization to its organization-wide install.
+    async fn add_enterprise_team(&self, id: u32, team: String) -> Result<(), UpdateError>;
+    async fn insert_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, InsertError>;
+    /// Finds and deletes a refresh token, so that it is used only once.
+    async fn take_refresh_token(&self, hash: String) -> Result<RefreshToken, FindError>;
+    /// Deletes the refresh tokens of the teams, returning how many were deleted.
+    async fn delete_refresh_tokens(&self, teams: Vec<String>) -> Result<u64, DeleteError>;
+    /// Checks the connection to the database.
+    async fn ping(&self) -> Result<(), FindError>;
 }
 
 pub struct MongoDbRepository {
@@ -31,33 +45,34 @@ impl MongoDbRepository {
         let db = client.database(database);
 
         db.run_command(doc! {"ping": 1}, None).await?;
+        counter::init(&db, &db.collection::<Auth>("tokens")).await?;
+        Self::ensure_indexes(&db).await?;
 
         Ok(MongoDbRepository { db })
     }
-}
-
-impl MongoDbRepository {
-    async fn fill_with_id<'a, T>(
-        collection: &'a mongodb::Collection<T>,
-        value: &'a mut T,
-    ) -> Result<&'a mut T, mongodb::error::Error>
-    where
-        T: HasId + serde::de::DeserializeOwned + Unpin + Send + Sync,
-    {
-        let options = mongodb::options::FindOneOptions::builder()
-            .sort(doc! { "id": -1 })
-            .build();
-
-        // Get the highest ID in the collection
-        let highest_id = match collection.find_one(None, options).await? {
-            Some(result) => result.get_id(),
-            None => 0,
-        };
-
-        // Assign the next available ID to the event
-        value.set_id(highest_id + 1);
 
-        Ok(value)
+    /// Creates the indexes of the lookups of the tokens. Existing indexes are kept as they are.
+    async fn ensure_indexes(db: &mongodb::Database) -> Result<(), mongodb::error::Error> {
+        db.collection::<Auth>("tokens")
+            .create_indexes(
+                vec![
+                    index(doc! { "id": 1 }, "id_unique", true),
+                    index(doc! { "team": 1, "deleted": 1 }, "team_deleted", false),
+                    index(doc! { "teams": 1, "deleted": 1 }, "teams_deleted", false),
+                ],
+                None,
+            )
+            .await?;
+        db.collection::<RefreshToken>("refresh_tokens")
+            .create_indexes(
+                vec![
+                    index(doc! { "hash": 1 }, "hash_unique", true),
+                    index(doc! { "team": 1 }, "team", false),
+                ],
+                None,
+            )
+            .await?;
+        Ok(())
     }
 }
 
@@ -74,7 +89,10 @@ impl Repository for MongoDbRepository {
         let collection = self.db.collection::<Auth>("tokens");
 
         collection
-            .insert_one(Self::fill_with_id(&collection, &mut result).await?, None)
+            .insert_one(
+                counter::fill_with_id(&self.db, &collection, &mut result).await?,
+                None,
+            )
             .await?;
 
         Ok(result)
@@ -109,14 +127,26 @@ impl Repository for MongoDbRepository {
         }
     }
 
+    async fn find_by_enterprise_team(&self, team: String) -> Result<Auth, errors::FindError> {
+        let filter = doc! { "teams": team, "deleted": false };
+        self.db
+            .collection::<Auth>("tokens")
+            .find_one(filter, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
     async fn find_all_by_team(&self, teams: Vec<String>) -> Result<Vec<Auth>, FindAllError> {
+        let teams = teams
+            .iter()
+            .map(bson::Bson::from)
+            .collect::<Vec<bson::Bson>>();
         let filter = doc! {
-            "team": {
-                "$in": teams
-                    .iter()
-                    .map(|team| bson::Bson::from(team))
-                    .collect::<Vec<bson::Bson>>()
-            }
+            "$or": [
+                { "team": { "$in": teams.clone() } },
+                { "teams": { "$in": teams } },
+            ],
+            "deleted": false
         };
         let mut cursor = self
             .db
@@ -130,4 +160,169 @@ impl Repository for MongoDbRepository {
         }
         Ok(result)
     }
+
+    async fn add_enterprise_team(&self, id: u32, team: String) -> Result<(), UpdateError> {
+        let result = self
+            .db
+            .collection::<Auth>("tokens")
+            .update_one(
+                doc! { "id": id },
+                doc! { "$addToSet": { "teams": team } },
+                None,
+            )
+            .await?;
+
+        if result.matched_count == 0 {
+            return Err(UpdateError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn insert_refresh_token(
+        &self,
+        token: RefreshToken,
+    ) -> Result<RefreshToken, errors::InsertError> {
+        self.db
+            .collection::<RefreshToken>("refresh_tokens")
+            .insert_one(&token, None)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn take_refresh_token(&self, hash: String) -> Result<RefreshToken, errors::FindError> {
+        let filter = doc! { "hash": hash };
+        let result = self
+            .db
+            .collection::<RefreshToken>("refresh_tokens")
+            .find_one_and_delete(filter, None)
+            .await?;
+
+        match result {
+            Some(token) => Ok(token),
+            None => Err(FindError::NotFound),
+        }
+    }
+
+    async fn delete_refresh_tokens(&self, teams: Vec<String>) -> Result<u64, DeleteError> {
+        let teams = teams
+            .iter()
+            .map(bson::Bson::from)
+            .collect::<Vec<bson::Bson>>();
+        let result = self
+            .db
+            .collection::<RefreshToken>("refresh_tokens")
+            .delete_many(doc! { "team": { "$in": teams } }, None)
+            .await?;
+        Ok(result.deleted_count)
+    }
+
+    async fn ping(&self) -> Result<(), errors::FindError> {
+        self.db.run_command(doc! {"ping": 1}, None).await?;
+        Ok(())
+    }
+}
+
+/// The tokens of the tests, kept in memory.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryRepository {
+    pub auths: std::sync::Mutex<Vec<Auth>>,
+    pub refresh_tokens: std::sync::Mutex<Vec<RefreshToken>>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Repository for MemoryRepository {
+    async fn insert(&self, auth: Auth) -> Result<Auth, InsertError> {
+        let mut auths = self.auths.lock().unwrap();
+        if auths.iter().any(|a| a.team == auth.team && !a.deleted) {
+            return Err(InsertError::Conflict);
+        }
+        let auth = Auth {
+            id: auths.len() as u32 + 1,
+            ..auth
+        };
+        auths.push(auth.clone());
+        Ok(auth)
+    }
+
+    async fn update(&self, auth: Auth) -> Result<Auth, UpdateError> {
+        let mut auths = self.auths.lock().unwrap();
+        match auths.iter_mut().find(|a| a.id == auth.id) {
+            Some(existing) => {
+                *existing = auth.clone();
+                Ok(auth)
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    async fn find_by_team(&self, team: String) -> Result<Auth, FindError> {
+        self.auths
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|auth| auth.team == team && !auth.deleted)
+            .cloned()
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_by_enterprise_team(&self, team: String) -> Result<Auth, FindError> {
+        self.auths
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|auth| auth.teams.contains(&team) && !auth.deleted)
+            .cloned()
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_all_by_team(&self, teams: Vec<String>) -> Result<Vec<Auth>, FindAllError> {
+        Ok(self
+            .auths
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|auth| !auth.deleted && auth.team_ids().iter().any(|team| teams.contains(team)))
+            .cloned()
+            .collect())
+    }
+
+    async fn add_enterprise_team(&self, id: u32, team: String) -> Result<(), UpdateError> {
+        let mut auths = self.auths.lock().unwrap();
+        let auth = auths
+            .iter_mut()
+            .find(|auth| auth.id == id)
+            .ok_or(UpdateError::NotFound)?;
+        if !auth.teams.contains(&team) {
+            auth.teams.push(team);
+        }
+        Ok(())
+    }
+
+    async fn insert_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, InsertError> {
+        self.refresh_tokens.lock().unwrap().push(token.clone());
+        Ok(token)
+    }
+
+    async fn take_refresh_token(&self, hash: String) -> Result<RefreshToken, FindError> {
+        let mut tokens = self.refresh_tokens.lock().unwrap();
+        let index = tokens
+            .iter()
+            .position(|token| token.hash == hash)
+            .ok_or(FindError::NotFound)?;
+        Ok(tokens.remove(index))
+    }
+
+    async fn delete_refresh_tokens(&self, teams: Vec<String>) -> Result<u64, DeleteError> {
+        let mut tokens = self.refresh_tokens.lock().unwrap();
+        let count = tokens.len();
+        tokens.retain(|token| !teams.contains(&token.team));
+        Ok((count - tokens.len()) as u64)
+    }
+
+    async fn ping(&self) -> Result<(), FindError> {
+        Ok(())
+    }
 }
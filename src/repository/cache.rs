@@ -0,0 +1,350 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+
+use crate::domain::entities::Event;
+
+use super::errors::{CountError, DeleteError, FindAllError, FindError, InsertError, UpdateError};
+use super::event::{EventFilter, EventSort, Repository};
+use super::store::Store;
+
+/// Caches the events of each channel for a short time in front of another repository, so that the
+/// several lookups of a single Slack request (e.g. the guard and the command handler) share a
+/// round-trip to the database.
+///
+/// The events of a channel are dropped from the cache whenever one of them changes, bumping the
+/// version of the channel. They are kept on the store, so that all the instances share them.
+pub struct CachedEventRepository {
+    inner: Arc<dyn Repository>,
+    store: Arc<dyn Store>,
+    ttl: Duration,
+}
+
+impl CachedEventRepository {
+    pub fn new(inner: Arc<dyn Repository>, store: Arc<dyn Store>, ttl: Duration) -> Self {
+        Self { inner, store, ttl }
+    }
+
+    async fn cached(&self, channel: &str) -> Option<Vec<Event>> {
+        let value = self.store.get(&cache_key(channel)).await.ok()??;
+        serde_json::from_str(&value)
+            .map_err(|err| log::error!("could not parse cached events of {}: {}", channel, err))
+            .ok()
+    }
+
+    /// The version of the events of a channel, which changes whenever they are invalidated.
+    async fn version(&self, channel: &str) -> Option<String> {
+        self.store.get(&version_key(channel)).await.ok()?
+    }
+
+    /// Caches the events read at the `version` of the channel. They are dropped right away when
+    /// they were invalidated in the meantime (e.g. by another instance), so that an outdated read
+    /// is not cached for the whole TTL.
+    async fn save(&self, channel: &str, events: &[Event], version: Option<String>) {
+        let value = match serde_json::to_string(events) {
+            Ok(value) => value,
+            Err(err) => {
+                log::error!("could not serialize events of {}: {}", channel, err);
+                return;
+            }
+        };
+        if let Err(err) = self.store.set(&cache_key(channel), value, self.ttl).await {
+            log::error!("could not cache events of {}: {:?}", channel, err);
+            return;
+        }
+        if self.version(channel).await != version {
+            self.drop_cached(channel).await;
+        }
+    }
+
+    async fn invalidate(&self, channel: &str) {
+        if let Err(err) = self
+            .store
+            .increment(&version_key(channel), VERSION_TTL)
+            .await
+        {
+            log::error!(
+                "could not bump the version of the events of {}: {:?}",
+                channel,
+                err
+            );
+        }
+        self.drop_cached(channel).await;
+    }
+
+    async fn drop_cached(&self, channel: &str) {
+        if let Err(err) = self.store.delete(&cache_key(channel)).await {
+            log::error!(
+                "could not invalidate cached events of {}: {:?}",
+                channel,
+                err
+            );
+        }
+    }
+
+    async fn channel_events(&self, channel: String) -> Result<Vec<Event>, FindAllError> {
+        if let Some(events) = self.cached(&channel).await {
+            return Ok(events);
+        }
+        let version = self.version(&channel).await;
+        let events = self
+            .inner
+            .find_events_page(channel.clone(), 0, None, EventFilter::default())
+            .await?;
+        self.save(&channel, &events, version).await;
+        Ok(events)
+    }
+}
+
+fn cache_key(channel: &str) -> String {
+    format!("events:{}", channel)
+}
+
+fn version_key(channel: &str) -> String {
+    format!("events-version:{}", channel)
+}
+
+/// How long the versions of the events are kept, well past the reads they are checked around.
+const VERSION_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[async_trait]
+impl Repository for CachedEventRepository {
+    async fn find_event(&self, id: u32, channel: String) -> Result<Event, FindError> {
+        let events = self
+            .channel_events(channel)
+            .await
+            .map_err(|_| FindError::Unknown)?;
+        events
+            .into_iter()
+            .find(|event| event.id == id)
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_event_by_name(&self, name: String, channel: String) -> Result<Event, FindError> {
+        let events = self
+            .channel_events(channel)
+            .await
+            .map_err(|_| FindError::Unknown)?;
+        events
+            .into_iter()
+            .find(|event| event.name == name)
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_all_events(&self, channel: String) -> Result<Vec<Event>, FindAllError> {
+        self.channel_events(channel).await
+    }
+
+    async fn find_events_page(
+        &self,
+        channel: String,
+        cursor: u64,
+        limit: Option<u64>,
+        filter: EventFilter,
+    ) -> Result<Vec<Event>, FindAllError> {
+        let events = match self.cached(&channel).await {
+            Some(events) => events,
+            None if cursor == 0
+                && limit.is_none()
+                && filter.query.is_none()
+                && filter.sort == EventSort::Id =>
+            {
+                self.channel_events(channel).await?
+            }
+            // Pages of channels that are not cached are not worth loading all their events.
+            None => {
+                return self
+                    .inner
+                    .find_events_page(channel, cursor, limit, filter)
+                    .await
+            }
+        };
+        let mut events: Vec<Event> = events
+            .into_iter()
+            .filter(|event| filter.matches(event))
+            .collect();
+        events.sort_by(|a, b| filter.sort.compare(a, b));
+        Ok(events
+            .into_iter()
+            .skip(cursor as usize)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect())
+    }
+
+    async fn find_all_events_unprotected(&self) -> Result<Vec<Event>, FindAllError> {
+        self.inner.find_all_events_unprotected().await
+    }
+
+    async fn find_all_events_by_id_unprotected(
+        &self,
+        ids: Vec<u32>,
+    ) -> Result<Vec<Event>, FindAllError> {
+        self.inner.find_all_events_by_id_unprotected(ids).await
+    }
+
+    async fn insert_event(&self, event: Event) -> Result<Event, InsertError> {
+        let channel = event.channel.clone();
+        let result = self.inner.insert_event(event).await;
+        self.invalidate(&channel).await;
+        result
+    }
+
+    async fn update_event(&self, event: Event) -> Result<(), UpdateError> {
+        let channel = event.channel.clone();
+        let result = self.inner.update_event(event).await;
+        self.invalidate(&channel).await;
+        result
+    }
+
+    async fn update_event_anchor(&self, id: u32, anchor_ts: String) -> Result<(), UpdateError> {
+        let result = self.inner.update_event_anchor(id, anchor_ts).await;
+        // The channel of the event is not known, so it is looked up.
+        if let Ok(events) = self.inner.find_all_events_by_id_unprotected(vec![id]).await {
+            for event in events {
+                self.invalidate(&event.channel).await;
+            }
+        }
+        result
+    }
+
+    async fn delete_event(&self, id: u32, channel: String) -> Result<Event, DeleteError> {
+        let result = self.inner.delete_event(id, channel.clone()).await;
+        self.invalidate(&channel).await;
+        result
+    }
+
+    async fn count_events(&self, channel: String) -> Result<u32, CountError> {
+        match self.cached(&channel).await {
+            Some(events) => Ok(events.len() as u32),
+            None => self.inner.count_events(channel).await,
+        }
+    }
+
+    // The deleted events are never cached.
+    async fn purge_deleted_before(&self, timestamp: i64) -> Result<u64, DeleteError> {
+        self.inner.purge_deleted_before(timestamp).await
+    }
+
+    async fn ping(&self) -> Result<(), FindError> {
+        self.inner.ping().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::domain::entities::Participant;
+    use crate::repository::event::{sample_event, MemoryRepository};
+    use crate::repository::store::MemoryStore;
+
+    use super::*;
+
+    fn cache(events: Vec<Event>) -> (Arc<MemoryRepository>, CachedEventRepository) {
+        let inner = Arc::new(MemoryRepository::with_events(events));
+        let cache = CachedEventRepository::new(
+            inner.clone(),
+            Arc::new(MemoryStore::default()),
+            Duration::from_secs(60),
+        );
+        (inner, cache)
+    }
+
+    fn reads(inner: &MemoryRepository) -> usize {
+        inner.reads.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_hit_and_miss() {
+        let (inner, cache) = cache(vec![sample_event(1, "Standup", vec![])]);
+        let channel = String::from("C1");
+
+        assert_eq!(cache.find_event(1, channel.clone()).await.unwrap().id, 1);
+        assert_eq!(reads(&inner), 1);
+        // The other lookups of the channel are served from the cache.
+        assert_eq!(
+            cache
+                .find_event_by_name(String::from("Standup"), channel.clone())
+                .await
+                .unwrap()
+                .id,
+            1
+        );
+        assert_eq!(cache.count_events(channel.clone()).await, Ok(1));
+        assert_eq!(
+            cache.find_event(2, channel.clone()).await.unwrap_err(),
+            FindError::NotFound
+        );
+        assert_eq!(reads(&inner), 1);
+
+        // The channels are cached apart.
+        assert!(cache
+            .find_all_events(String::from("C2"))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(reads(&inner), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalidation() {
+        let (inner, cache) = cache(vec![sample_event(1, "Standup", vec![])]);
+        let channel = String::from("C1");
+        cache.find_all_events(channel.clone()).await.unwrap();
+
+        let event = cache
+            .insert_event(sample_event(0, "Retro", vec![]))
+            .await
+            .unwrap();
+        assert_eq!(
+            cache.find_all_events(channel.clone()).await.unwrap().len(),
+            2
+        );
+        assert_eq!(reads(&inner), 2);
+
+        cache
+            .update_event(sample_event(
+                event.id,
+                "Retro",
+                vec![Participant::from(String::from("U1"))],
+            ))
+            .await
+            .unwrap();
+        let updated = cache.find_event(event.id, channel.clone()).await.unwrap();
+        assert_eq!(updated.participants.len(), 1);
+        assert_eq!(reads(&inner), 3);
+
+        cache.delete_event(1, channel.clone()).await.unwrap();
+        assert_eq!(
+            cache.find_all_events(channel.clone()).await.unwrap().len(),
+            1
+        );
+        assert_eq!(reads(&inner), 4);
+    }
+
+    #[tokio::test]
+    async fn test_outdated_read_is_not_cached() {
+        let (_, cache) = cache(vec![sample_event(1, "Standup", vec![])]);
+        let channel = "C1";
+
+        // The events are read before an update (e.g. from another instance) invalidates them, and
+        // cached after it.
+        let version = cache.version(channel).await;
+        let outdated = cache
+            .inner
+            .find_all_events(channel.to_string())
+            .await
+            .unwrap();
+        cache
+            .update_event(sample_event(1, "Standup (renamed)", vec![]))
+            .await
+            .unwrap();
+        cache.save(channel, &outdated, version).await;
+
+        assert!(cache.cached(channel).await.is_none());
+        assert_eq!(
+            cache.find_event(1, channel.to_string()).await.unwrap().name,
+            "Standup (renamed)"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::errors::FindError;
+
+/// The calendars the users connected to the app.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Finds which of the users of the team are out of office for the whole day on their
+    /// calendars. The users without a connected calendar are never out of office.
+    async fn find_out_of_office(
+        &self,
+        team: &str,
+        users: &[String],
+        date: NaiveDate,
+    ) -> Result<Vec<String>, FindError>;
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument, UpdateOptions};
+
+use crate::domain::entities::CalendarFeed;
+
+use super::errors::{FindError, UpdateError};
+use super::helpers::index;
+
+/// The tokens of the calendar feeds of the channels.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn find_feed(&self, channel: String) -> Result<CalendarFeed, FindError>;
+    /// Finds the feed of the channel of the given one, saving it when the channel has none.
+    async fn find_or_insert_feed(&self, feed: CalendarFeed) -> Result<CalendarFeed, UpdateError>;
+    /// Saves the feed, replacing the one of the same channel.
+    async fn save_feed(&self, feed: CalendarFeed) -> Result<CalendarFeed, UpdateError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+        db.collection::<CalendarFeed>("calendar_feeds")
+            .create_indexes(
+                vec![index(doc! { "channel": 1 }, "channel_unique", true)],
+                None,
+            )
+            .await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn find_feed(&self, channel: String) -> Result<CalendarFeed, FindError> {
+        self.db
+            .collection::<CalendarFeed>("calendar_feeds")
+            .find_one(doc! { "channel": channel }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_or_insert_feed(&self, feed: CalendarFeed) -> Result<CalendarFeed, UpdateError> {
+        let filter = doc! { "channel": feed.channel.clone() };
+        let update = doc! { "$setOnInsert": bson::to_document(&feed)? };
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+        self.db
+            .collection::<CalendarFeed>("calendar_feeds")
+            .find_one_and_update(filter, update, options)
+            .await?
+            .ok_or(UpdateError::Unknown)
+    }
+
+    async fn save_feed(&self, feed: CalendarFeed) -> Result<CalendarFeed, UpdateError> {
+        let filter = doc! { "channel": feed.channel.clone() };
+        let update = doc! { "$set": bson::to_document(&feed)? };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<CalendarFeed>("calendar_feeds")
+            .update_one(filter, update, options)
+            .await?;
+        Ok(feed)
+    }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryRepository {
+    pub feeds: std::sync::Mutex<Vec<CalendarFeed>>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Repository for MemoryRepository {
+    async fn find_feed(&self, channel: String) -> Result<CalendarFeed, FindError> {
+        self.feeds
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|feed| feed.channel == channel)
+            .cloned()
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_or_insert_feed(&self, feed: CalendarFeed) -> Result<CalendarFeed, UpdateError> {
+        let mut feeds = self.feeds.lock().unwrap();
+        match feeds.iter().find(|saved| saved.channel == feed.channel) {
+            Some(saved) => Ok(saved.clone()),
+            None => {
+                feeds.push(feed.clone());
+                Ok(feed)
+            }
+        }
+    }
+
+    async fn save_feed(&self, feed: CalendarFeed) -> Result<CalendarFeed, UpdateError> {
+        let mut feeds = self.feeds.lock().unwrap();
+        feeds.retain(|saved| saved.channel != feed.channel);
+        feeds.push(feed.clone());
+        Ok(feed)
+    }
+}
@@ -0,0 +1,68 @@
+use bson::{doc, Document};
+use mongodb::options::{FindOneAndUpdateOptions, FindOneOptions, ReturnDocument, UpdateOptions};
+use serde::de::DeserializeOwned;
+
+use crate::domain::entities::HasId;
+
+/// Aligns the counter of the collection with its highest id, so that the records inserted before
+/// the counter existed keep their ids. It never moves the counter back.
+pub async fn init<T>(
+    db: &mongodb::Database,
+    collection: &mongodb::Collection<T>,
+) -> Result<(), mongodb::error::Error>
+where
+    T: HasId + DeserializeOwned + Unpin + Send + Sync,
+{
+    let options = FindOneOptions::builder().sort(doc! { "id": -1 }).build();
+    let highest_id = match collection.find_one(None, options).await? {
+        Some(result) => result.get_id(),
+        None => 0,
+    };
+
+    let options = UpdateOptions::builder().upsert(true).build();
+    db.collection::<Document>(COUNTERS_COLLECTION)
+        .update_one(
+            doc! { "_id": collection.name() },
+            doc! { "$max": { "seq": highest_id as i64 } },
+            options,
+        )
+        .await?;
+    Ok(())
+}
+
+/// Assigns the next id of the collection to the value.
+///
+/// The id is taken from an atomic counter, so that concurrent inserts never share the same one.
+pub async fn fill_with_id<'a, T>(
+    db: &mongodb::Database,
+    collection: &mongodb::Collection<T>,
+    value: &'a mut T,
+) -> Result<&'a mut T, mongodb::error::Error>
+where
+    T: HasId,
+{
+    let options = FindOneAndUpdateOptions::builder()
+        .upsert(true)
+        .return_document(ReturnDocument::After)
+        .build();
+    let counter = db
+        .collection::<Document>(COUNTERS_COLLECTION)
+        .find_one_and_update(
+            doc! { "_id": collection.name() },
+            doc! { "$inc": { "seq": 1_i64 } },
+            options,
+        )
+        .await?
+        .ok_or_else(|| {
+            mongodb::error::Error::custom(format!("no id counter for {}", collection.name()))
+        })?;
+
+    let id = counter
+        .get_i64("seq")
+        .map_err(mongodb::error::Error::custom)?;
+    value.set_id(u32::try_from(id).map_err(mongodb::error::Error::custom)?);
+
+    Ok(value)
+}
+
+const COUNTERS_COLLECTION: &str = "counters";
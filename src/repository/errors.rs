@@ -104,3 +104,15 @@ impl From<mongodb::error::Error> for CountError {
         }
     }
 }
+
+#[derive(Debug, PartialEq)]
+pub enum StoreError {
+    Unknown,
+}
+
+impl From<redis::RedisError> for StoreError {
+    fn from(value: redis::RedisError) -> Self {
+        log::error!("occurred an error in redis: {}", value);
+        Self::Unknown
+    }
+}
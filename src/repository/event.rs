@@ -1,20 +1,33 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use async_trait::async_trait;
 use mongodb::bson::doc;
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::domain::entities::{Channel, Event, HasId, OldEvent};
+use crate::helpers::date::Date;
+use crate::repository::counter;
 use crate::repository::errors::{
     CountError, DeleteError, FindAllError, FindError, InsertError, UpdateError,
 };
+use crate::repository::helpers::index;
 
 #[async_trait]
 pub trait Repository: Send + Sync {
     async fn find_event(&self, id: u32, channel: String) -> Result<Event, FindError>;
     async fn find_event_by_name(&self, name: String, channel: String) -> Result<Event, FindError>;
     async fn find_all_events(&self, channel: String) -> Result<Vec<Event>, FindAllError>;
+    /// Finds the channel events matching the filter, skipping the `cursor` first ones. All the
+    /// remaining events are found when there is no limit.
+    async fn find_events_page(
+        &self,
+        channel: String,
+        cursor: u64,
+        limit: Option<u64>,
+        filter: EventFilter,
+    ) -> Result<Vec<Event>, FindAllError>;
     async fn find_all_events_unprotected(&self) -> Result<Vec<Event>, FindAllError>;
     async fn find_all_events_by_id_unprotected(
         &self,
@@ -22,8 +35,89 @@ pub trait Repository: Send + Sync {
     ) -> Result<Vec<Event>, FindAllError>;
     async fn insert_event(&self, event: Event) -> Result<Event, InsertError>;
     async fn update_event(&self, event: Event) -> Result<(), UpdateError>;
+    async fn update_event_anchor(&self, id: u32, anchor_ts: String) -> Result<(), UpdateError>;
     async fn delete_event(&self, id: u32, channel: String) -> Result<Event, DeleteError>;
     async fn count_events(&self, channel: String) -> Result<u32, CountError>;
+    /// Removes for good the events deleted before the timestamp, returning how many were removed.
+    async fn purge_deleted_before(&self, timestamp: i64) -> Result<u64, DeleteError>;
+    /// Checks the connection to the database.
+    async fn ping(&self) -> Result<(), FindError>;
+}
+
+/// The filter and order of the found events.
+#[derive(Default, Clone, Debug)]
+pub struct EventFilter {
+    /// The text to find on the event names (ignoring the case) or IDs.
+    pub query: Option<String>,
+    pub sort: EventSort,
+}
+
+/// The order of the found events. The events with the same name or date are ordered by ID.
+#[derive(Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSort {
+    #[default]
+    Id,
+    Name,
+    Timestamp,
+}
+
+impl EventSort {
+    /// Compares the events, as on the database queries.
+    pub fn compare(&self, a: &Event, b: &Event) -> Ordering {
+        match self {
+            EventSort::Id => a.id.cmp(&b.id),
+            EventSort::Name => a.name.cmp(&b.name).then(a.id.cmp(&b.id)),
+            EventSort::Timestamp => a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)),
+        }
+    }
+
+    fn to_document(self) -> bson::Document {
+        match self {
+            EventSort::Id => doc! { "id": 1 },
+            EventSort::Name => doc! { "name": 1, "id": 1 },
+            EventSort::Timestamp => doc! { "timestamp": 1, "id": 1 },
+        }
+    }
+}
+
+impl EventFilter {
+    /// Whether the event matches the filter, as on the database queries.
+    pub fn matches(&self, event: &Event) -> bool {
+        let query = self.query.as_deref().map(str::trim).unwrap_or_default();
+        query.is_empty()
+            || event.name.to_lowercase().contains(&query.to_lowercase())
+            || event.id.to_string() == query
+    }
+
+    fn to_document(&self, channel: String) -> bson::Document {
+        let mut filter = doc! { "channel": channel, "deleted": false };
+        let query = self.query.as_deref().map(str::trim).unwrap_or_default();
+        if query.is_empty() {
+            return filter;
+        }
+
+        let mut conditions =
+            vec![doc! { "name": { "$regex": escape_regex(query), "$options": "i" } }];
+        if let Ok(id) = query.parse::<u32>() {
+            conditions.push(doc! { "id": id });
+        }
+        filter.insert("$or", conditions);
+        filter
+    }
+}
+
+/// Escapes the characters with a special meaning on regular expressions, so that the text is
+/// matched literally.
+fn escape_regex(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$#&-~".contains(c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
 }
 
 pub struct MongoDbRepository {
@@ -46,6 +140,8 @@ impl MongoDbRepository {
         let db = client.database(database);
 
         db.run_command(doc! {"ping": 1}, None).await?;
+        counter::init(&db, &db.collection::<Event>("events")).await?;
+        Self::ensure_indexes(&db).await?;
 
         Ok(MongoDbRepository {
             client,
@@ -54,27 +150,35 @@ impl MongoDbRepository {
         })
     }
 
-    async fn fill_with_id<'a, T>(
-        collection: &'a mongodb::Collection<T>,
-        value: &'a mut T,
-    ) -> Result<&'a mut T, mongodb::error::Error>
-    where
-        T: HasId + DeserializeOwned + Unpin + Send + Sync,
-    {
-        let options = mongodb::options::FindOneOptions::builder()
-            .sort(doc! { "id": -1 })
-            .build();
-
-        // Get the highest ID in the collection
-        let highest_id = match collection.find_one(None, options).await? {
-            Some(result) => result.get_id(),
-            None => 0,
-        };
-
-        // Assign the next available ID to the event
-        value.set_id(highest_id + 1);
-
-        Ok(value)
+    /// Creates the indexes of the lookups of the events. Existing indexes are kept as they are.
+    async fn ensure_indexes(db: &mongodb::Database) -> Result<(), mongodb::error::Error> {
+        let indexes = vec![
+            index(doc! { "id": 1 }, "id_unique", true),
+            index(
+                doc! { "id": 1, "channel": 1, "deleted": 1 },
+                "id_channel_deleted",
+                false,
+            ),
+            index(
+                doc! { "name": 1, "channel": 1, "deleted": 1 },
+                "name_channel_deleted",
+                false,
+            ),
+            index(
+                doc! { "channel": 1, "deleted": 1, "id": 1 },
+                "channel_deleted_id",
+                false,
+            ),
+            index(
+                doc! { "deleted": 1, "deleted_at": 1 },
+                "deleted_deleted_at",
+                false,
+            ),
+        ];
+        db.collection::<Event>("events")
+            .create_indexes(indexes, None)
+            .await?;
+        Ok(())
     }
 
     async fn find_events_by_name(
@@ -140,13 +244,16 @@ impl MongoDbRepository {
             .collect::<Vec<Event>>();
 
         log::debug!("Migrating {} events", new_events.len());
+        let collection = self.db.collection::<Event>("events_2");
+        counter::init(&self.db, &collection).await?;
         for mut event in new_events {
             let id = event.id;
 
-            let collection = self.db.collection::<Event>("events_2");
-
             collection
-                .insert_one(Self::fill_with_id(&collection, &mut event).await?, None)
+                .insert_one(
+                    counter::fill_with_id(&self.db, &collection, &mut event).await?,
+                    None,
+                )
                 .await
                 .map_err(|err| {
                     log::error!("Error migrating event with ID {}: {:?}", id, err);
@@ -178,11 +285,14 @@ impl MongoDbRepository {
         while cursor.advance().await? {
             events.push(cursor.deserialize_current()?);
         }
+        let collection = self.db.collection::<T>(tablename);
+        counter::init(&self.db, &collection).await?;
         for mut event in events {
-            let collection = self.db.collection::<T>(tablename);
-
             collection
-                .insert_one(Self::fill_with_id(&collection, &mut event).await?, None)
+                .insert_one(
+                    counter::fill_with_id(&self.db, &collection, &mut event).await?,
+                    None,
+                )
                 .await
                 .map_err(|err| {
                     log::error!("Error inserting event: {:?}: {:?}", event, err);
@@ -239,6 +349,31 @@ impl Repository for MongoDbRepository {
         Ok(result)
     }
 
+    async fn find_events_page(
+        &self,
+        channel: String,
+        cursor: u64,
+        limit: Option<u64>,
+        filter: EventFilter,
+    ) -> Result<Vec<Event>, FindAllError> {
+        let options = mongodb::options::FindOptions::builder()
+            .sort(filter.sort.to_document())
+            .skip(cursor)
+            .limit(limit.map(|limit| limit as i64))
+            .build();
+        let mut cursor = self
+            .db
+            .collection::<Event>("events")
+            .find(filter.to_document(channel), options)
+            .await?;
+
+        let mut result: Vec<Event> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
     async fn find_all_events_unprotected(&self) -> Result<Vec<Event>, FindAllError> {
         let filter = doc! { "deleted": false };
         let mut cursor = self
@@ -295,7 +430,10 @@ impl Repository for MongoDbRepository {
         let collection = self.db.collection::<Event>("events");
 
         collection
-            .insert_one(Self::fill_with_id(&collection, &mut result).await?, None)
+            .insert_one(
+                counter::fill_with_id(&self.db, &collection, &mut result).await?,
+                None,
+            )
             .await?;
 
         Ok(result)
@@ -328,11 +466,28 @@ impl Repository for MongoDbRepository {
         Ok(())
     }
 
+    async fn update_event_anchor(&self, id: u32, anchor_ts: String) -> Result<(), UpdateError> {
+        let filter = doc! {"id": id};
+        let update = doc! {"$set": {"anchor_ts": anchor_ts}};
+        let result = self
+            .db
+            .collection::<Event>("events")
+            .update_one(filter, update, None)
+            .await?;
+
+        if result.matched_count == 0 {
+            return Err(UpdateError::NotFound);
+        }
+
+        Ok(())
+    }
+
     async fn delete_event(&self, id: u32, channel: String) -> Result<Event, DeleteError> {
         let collection = self.db.collection::<Event>("events");
 
         let filter = doc! { "id": id, "channel": channel, "deleted": false };
-        let update = doc! {"$set": {"deleted": true}};
+        // The deletion time is only kept on the database, to purge the events once they expire.
+        let update = doc! {"$set": {"deleted": true, "deleted_at": Date::now().timestamp()}};
         let result = collection.update_one(filter, update, None).await?;
 
         if result.matched_count == 0 {
@@ -358,6 +513,229 @@ impl Repository for MongoDbRepository {
 
         Ok(count as u32)
     }
+
+    async fn purge_deleted_before(&self, timestamp: i64) -> Result<u64, DeleteError> {
+        let collection = self.db.collection::<Event>("events");
+
+        // The events deleted before their deletion time was kept expire from now on.
+        collection
+            .update_many(
+                doc! { "deleted": true, "deleted_at": null },
+                doc! { "$set": { "deleted_at": Date::now().timestamp() } },
+                None,
+            )
+            .await?;
+
+        let filter = doc! { "deleted": true, "deleted_at": { "$lt": timestamp } };
+        let result = collection.delete_many(filter, None).await?;
+        Ok(result.deleted_count)
+    }
+
+    async fn ping(&self) -> Result<(), FindError> {
+        self.db.run_command(doc! {"ping": 1}, None).await?;
+        Ok(())
+    }
+}
+
+/// The events of the tests, kept in memory. It counts the reads of the channel events, so that the
+/// tests can tell whether they were served by a cache.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryRepository {
+    pub events: std::sync::Mutex<Vec<Event>>,
+    pub reads: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(test)]
+impl MemoryRepository {
+    pub fn with_events(events: Vec<Event>) -> Self {
+        Self {
+            events: std::sync::Mutex::new(events),
+            ..Default::default()
+        }
+    }
+
+    fn channel_events(&self, channel: &str) -> Vec<Event> {
+        self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.channel == channel && !event.deleted)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Repository for MemoryRepository {
+    async fn find_event(&self, id: u32, channel: String) -> Result<Event, FindError> {
+        self.channel_events(&channel)
+            .into_iter()
+            .find(|event| event.id == id)
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_event_by_name(&self, name: String, channel: String) -> Result<Event, FindError> {
+        self.channel_events(&channel)
+            .into_iter()
+            .find(|event| event.name == name)
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_all_events(&self, channel: String) -> Result<Vec<Event>, FindAllError> {
+        Ok(self.channel_events(&channel))
+    }
+
+    async fn find_events_page(
+        &self,
+        channel: String,
+        cursor: u64,
+        limit: Option<u64>,
+        filter: EventFilter,
+    ) -> Result<Vec<Event>, FindAllError> {
+        let mut events: Vec<Event> = self
+            .channel_events(&channel)
+            .into_iter()
+            .filter(|event| filter.matches(event))
+            .collect();
+        events.sort_by(|a, b| filter.sort.compare(a, b));
+        Ok(events
+            .into_iter()
+            .skip(cursor as usize)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect())
+    }
+
+    async fn find_all_events_unprotected(&self) -> Result<Vec<Event>, FindAllError> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .filter(|event| !event.deleted)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_all_events_by_id_unprotected(
+        &self,
+        ids: Vec<u32>,
+    ) -> Result<Vec<Event>, FindAllError> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .filter(|event| ids.contains(&event.id) && !event.deleted)
+            .cloned()
+            .collect())
+    }
+
+    async fn insert_event(&self, event: Event) -> Result<Event, InsertError> {
+        let mut events = self.events.lock().unwrap();
+        if events
+            .iter()
+            .any(|e| e.name == event.name && e.channel == event.channel && !e.deleted)
+        {
+            return Err(InsertError::Conflict);
+        }
+        let event = Event {
+            id: events.iter().map(|event| event.id).max().unwrap_or(0) + 1,
+            ..event
+        };
+        events.push(event.clone());
+        Ok(event)
+    }
+
+    async fn update_event(&self, event: Event) -> Result<(), UpdateError> {
+        let mut events = self.events.lock().unwrap();
+        match events.iter_mut().find(|e| e.id == event.id) {
+            Some(existing) => {
+                *existing = event;
+                Ok(())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    async fn update_event_anchor(&self, id: u32, anchor_ts: String) -> Result<(), UpdateError> {
+        let mut events = self.events.lock().unwrap();
+        match events.iter_mut().find(|e| e.id == id) {
+            Some(existing) => {
+                existing.anchor_ts = Some(anchor_ts);
+                Ok(())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    async fn delete_event(&self, id: u32, channel: String) -> Result<Event, DeleteError> {
+        let mut events = self.events.lock().unwrap();
+        match events
+            .iter_mut()
+            .find(|e| e.id == id && e.channel == channel && !e.deleted)
+        {
+            Some(existing) => {
+                existing.deleted = true;
+                Ok(existing.clone())
+            }
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    async fn count_events(&self, channel: String) -> Result<u32, CountError> {
+        Ok(self.channel_events(&channel).len() as u32)
+    }
+
+    async fn purge_deleted_before(&self, _timestamp: i64) -> Result<u64, DeleteError> {
+        let mut events = self.events.lock().unwrap();
+        let count = events.len();
+        events.retain(|event| !event.deleted);
+        Ok((count - events.len()) as u64)
+    }
+
+    async fn ping(&self) -> Result<(), FindError> {
+        Ok(())
+    }
+}
+
+/// An event of the tests, repeated daily on the channel `C1` of the team `T1`.
+#[cfg(test)]
+pub fn sample_event(
+    id: u32,
+    name: &str,
+    participants: Vec<crate::domain::entities::Participant>,
+) -> Event {
+    use crate::domain::entities::{PickMode, RepeatPeriod, Visibility};
+    use crate::domain::timezone::Timezone;
+
+    Event {
+        id,
+        name: String::from(name),
+        timestamp: 1723822080,
+        timezone: Timezone::UTC,
+        repeat: RepeatPeriod::Daily,
+        participants,
+        channel: String::from("C1"),
+        team_id: String::from("T1"),
+        deleted: false,
+        pick_mode: PickMode::Random,
+        no_repeat_consecutive: false,
+        ends_at: None,
+        reminder_minutes: None,
+        anchor: false,
+        anchor_ts: None,
+        notify_channels: vec![],
+        inactive: false,
+        skip_weekends: None,
+        skipped_dates: vec![],
+        user_group: None,
+        seed: None,
+        created_by: None,
+        paused: false,
+        description: None,
+        url: None,
+        visibility: Visibility::Channel,
+        skip_out_of_office: false,
+    }
 }
 
 #[cfg(test)]
@@ -366,6 +744,55 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_event_filter() {
+        let filter = EventFilter::default().to_document(String::from("C1"));
+        assert_eq!(filter, doc! { "channel": "C1", "deleted": false });
+
+        let filter = EventFilter {
+            query: Some(String::from(" 1.5 ")),
+            ..Default::default()
+        }
+        .to_document(String::from("C1"));
+        assert_eq!(
+            filter,
+            doc! {
+                "channel": "C1",
+                "deleted": false,
+                "$or": [{ "name": { "$regex": "1\\.5", "$options": "i" } }]
+            }
+        );
+
+        let filter = EventFilter {
+            query: Some(String::from("12")),
+            ..Default::default()
+        }
+        .to_document(String::from("C1"));
+        assert_eq!(
+            filter,
+            doc! {
+                "channel": "C1",
+                "deleted": false,
+                "$or": [{ "name": { "$regex": "12", "$options": "i" } }, { "id": 12 }]
+            }
+        );
+    }
+
+    #[test]
+    fn test_event_sort() {
+        assert_eq!(EventSort::default(), EventSort::Id);
+        assert_eq!(EventSort::Id.to_document(), doc! { "id": 1 });
+        assert_eq!(EventSort::Name.to_document(), doc! { "name": 1, "id": 1 });
+        assert_eq!(
+            EventSort::Timestamp.to_document(),
+            doc! { "timestamp": 1, "id": 1 }
+        );
+        assert_eq!(
+            serde_json::from_str::<EventSort>("\"timestamp\"").unwrap(),
+            EventSort::Timestamp
+        );
+    }
+
     #[tokio::test]
     async fn test_migration() {
         let db_tool_url =
@@ -0,0 +1,15 @@
+use bson::Document;
+use mongodb::{options::IndexOptions, IndexModel};
+
+/// Builds the model of a named index on the keys.
+pub fn index(keys: Document, name: &str, unique: bool) -> IndexModel {
+    IndexModel::builder()
+        .keys(keys)
+        .options(
+            IndexOptions::builder()
+                .name(name.to_string())
+                .unique(unique)
+                .build(),
+        )
+        .build()
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use bson::doc;
+
+use crate::domain::entities::Holiday;
+
+use super::errors::{DeleteError, FindAllError, InsertError};
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn insert_holiday(&self, holiday: Holiday) -> Result<Holiday, InsertError>;
+    async fn delete_holiday(&self, team_id: String, date: String) -> Result<Holiday, DeleteError>;
+    async fn find_holidays(&self, team_id: String) -> Result<Vec<Holiday>, FindAllError>;
+    async fn find_all_holidays_unprotected(&self) -> Result<Vec<Holiday>, FindAllError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        Ok(MongoDbRepository { db })
+    }
+
+    async fn find_holidays_by_filter(
+        &self,
+        filter: bson::Document,
+    ) -> Result<Vec<Holiday>, FindAllError> {
+        let options = mongodb::options::FindOptions::builder()
+            .sort(doc! { "date": 1 })
+            .build();
+        let mut cursor = self
+            .db
+            .collection::<Holiday>("holidays")
+            .find(filter, options)
+            .await?;
+
+        let mut result: Vec<Holiday> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn insert_holiday(&self, holiday: Holiday) -> Result<Holiday, InsertError> {
+        let collection = self.db.collection::<Holiday>("holidays");
+
+        let filter = doc! { "team_id": holiday.team_id.clone(), "date": holiday.date.clone() };
+        if collection.find_one(filter, None).await?.is_some() {
+            log::error!(
+                "insert_holiday: holiday on {} already exists for team {}",
+                holiday.date,
+                holiday.team_id
+            );
+            return Err(InsertError::Conflict);
+        }
+
+        collection.insert_one(&holiday, None).await?;
+
+        Ok(holiday)
+    }
+
+    async fn delete_holiday(&self, team_id: String, date: String) -> Result<Holiday, DeleteError> {
+        let filter = doc! { "team_id": team_id, "date": date };
+        match self
+            .db
+            .collection::<Holiday>("holidays")
+            .find_one_and_delete(filter, None)
+            .await?
+        {
+            Some(holiday) => Ok(holiday),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    async fn find_holidays(&self, team_id: String) -> Result<Vec<Holiday>, FindAllError> {
+        self.find_holidays_by_filter(doc! { "team_id": team_id })
+            .await
+    }
+
+    async fn find_all_holidays_unprotected(&self) -> Result<Vec<Holiday>, FindAllError> {
+        self.find_holidays_by_filter(doc! {}).await
+    }
+}
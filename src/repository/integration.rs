@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use bson::doc;
+use mongodb::options::UpdateOptions;
+
+use crate::domain::entities::{Integration, IntegrationProvider};
+
+use super::errors::{DeleteError, FindAllError, UpdateError};
+use super::helpers::index;
+
+/// The services the users connected to the app, along with their tokens.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Saves the integration, replacing the one of the same user and provider.
+    async fn save_integration(&self, integration: Integration) -> Result<Integration, UpdateError>;
+    /// Finds the integrations with the provider of the users of the team that have one.
+    async fn find_integrations(
+        &self,
+        team: String,
+        users: Vec<String>,
+        provider: IntegrationProvider,
+    ) -> Result<Vec<Integration>, FindAllError>;
+    async fn delete_integration(
+        &self,
+        team: String,
+        user: String,
+        provider: IntegrationProvider,
+    ) -> Result<Integration, DeleteError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+        db.collection::<Integration>("integrations")
+            .create_indexes(
+                vec![index(
+                    doc! { "team": 1, "user": 1, "provider": 1 },
+                    "team_user_provider_unique",
+                    true,
+                )],
+                None,
+            )
+            .await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn save_integration(&self, integration: Integration) -> Result<Integration, UpdateError> {
+        let filter = doc! {
+            "team": integration.team.clone(),
+            "user": integration.user.clone(),
+            "provider": String::from(integration.provider),
+        };
+        let update = doc! { "$set": bson::to_document(&integration)? };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<Integration>("integrations")
+            .update_one(filter, update, options)
+            .await?;
+        Ok(integration)
+    }
+
+    async fn find_integrations(
+        &self,
+        team: String,
+        users: Vec<String>,
+        provider: IntegrationProvider,
+    ) -> Result<Vec<Integration>, FindAllError> {
+        let mut cursor = self
+            .db
+            .collection::<Integration>("integrations")
+            .find(
+                doc! {
+                    "team": team,
+                    "user": { "$in": users },
+                    "provider": String::from(provider),
+                },
+                None,
+            )
+            .await?;
+
+        let mut result: Vec<Integration> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn delete_integration(
+        &self,
+        team: String,
+        user: String,
+        provider: IntegrationProvider,
+    ) -> Result<Integration, DeleteError> {
+        self.db
+            .collection::<Integration>("integrations")
+            .find_one_and_delete(
+                doc! { "team": team, "user": user, "provider": String::from(provider) },
+                None,
+            )
+            .await?
+            .ok_or(DeleteError::NotFound)
+    }
+}
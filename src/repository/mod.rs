@@ -1,3 +1,22 @@
+pub mod acknowledgment;
+pub mod api_key;
+pub mod audit;
 pub mod auth;
+pub mod cache;
+pub mod calendar;
+pub mod calendar_feed;
+mod counter;
 pub mod errors;
 pub mod event;
+mod helpers;
+pub mod holiday;
+pub mod integration;
+pub mod notification;
+pub mod pick_history;
+pub mod plan;
+pub mod redis;
+pub mod scheduler;
+pub mod settings;
+pub mod store;
+pub mod user_group;
+pub mod user_status;
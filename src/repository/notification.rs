@@ -0,0 +1,215 @@
+use async_trait::async_trait;
+use bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+
+use crate::domain::entities::{FailedNotification, NotificationJob};
+
+use super::counter;
+use super::errors::{DeleteError, FindAllError, FindError, InsertError, UpdateError};
+use super::helpers::index;
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn insert_job(&self, job: NotificationJob) -> Result<NotificationJob, InsertError>;
+    /// Claims the next job due by the timestamp (in seconds), holding it until `locked_until` so
+    /// that no other worker runs it meanwhile. The jobs held by the workers that stopped are
+    /// claimed again once their hold expires.
+    async fn claim_job(
+        &self,
+        timestamp: i64,
+        locked_until: i64,
+    ) -> Result<Option<NotificationJob>, FindError>;
+    async fn update_job(&self, job: NotificationJob) -> Result<NotificationJob, UpdateError>;
+    async fn delete_job(&self, id: u32) -> Result<(), DeleteError>;
+    /// Moves the job to the dead-letter collection, where it is not run anymore.
+    async fn fail_job(&self, failed: FailedNotification)
+        -> Result<FailedNotification, InsertError>;
+    async fn find_failed(&self, id: u32) -> Result<FailedNotification, FindError>;
+    /// Finds the latest failed notifications, of a team when set, newest first.
+    async fn find_all_failed(
+        &self,
+        team_id: Option<String>,
+        limit: i64,
+    ) -> Result<Vec<FailedNotification>, FindAllError>;
+    async fn delete_failed(&self, id: u32) -> Result<(), DeleteError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+        counter::init(&db, &db.collection::<NotificationJob>("notifications")).await?;
+        db.collection::<NotificationJob>("notifications")
+            .create_indexes(
+                vec![
+                    index(doc! { "id": 1 }, "id_unique", true),
+                    index(doc! { "run_at": 1 }, "run_at", false),
+                ],
+                None,
+            )
+            .await?;
+        db.collection::<FailedNotification>("failed_notifications")
+            .create_indexes(
+                vec![
+                    index(doc! { "id": 1 }, "id_unique", true),
+                    index(
+                        doc! { "team_id": 1, "failed_at": -1 },
+                        "team_id_failed_at",
+                        false,
+                    ),
+                ],
+                None,
+            )
+            .await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn insert_job(&self, job: NotificationJob) -> Result<NotificationJob, InsertError> {
+        let mut result = job;
+        let collection = self.db.collection::<NotificationJob>("notifications");
+        collection
+            .insert_one(
+                counter::fill_with_id(&self.db, &collection, &mut result).await?,
+                None,
+            )
+            .await?;
+        Ok(result)
+    }
+
+    async fn claim_job(
+        &self,
+        timestamp: i64,
+        locked_until: i64,
+    ) -> Result<Option<NotificationJob>, FindError> {
+        let filter = doc! {
+            "run_at": { "$lte": timestamp },
+            "$or": [
+                { "locked_until": null },
+                { "locked_until": { "$lte": timestamp } },
+            ],
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .sort(doc! { "run_at": 1 })
+            .return_document(ReturnDocument::After)
+            .build();
+        Ok(self
+            .db
+            .collection::<NotificationJob>("notifications")
+            .find_one_and_update(
+                filter,
+                doc! { "$set": { "locked_until": locked_until } },
+                options,
+            )
+            .await?)
+    }
+
+    async fn update_job(&self, job: NotificationJob) -> Result<NotificationJob, UpdateError> {
+        let result = self
+            .db
+            .collection::<NotificationJob>("notifications")
+            .replace_one(doc! { "id": job.id }, &job, None)
+            .await?;
+        if result.matched_count == 0 {
+            return Err(UpdateError::NotFound);
+        }
+        Ok(job)
+    }
+
+    async fn delete_job(&self, id: u32) -> Result<(), DeleteError> {
+        let result = self
+            .db
+            .collection::<NotificationJob>("notifications")
+            .delete_one(doc! { "id": id }, None)
+            .await?;
+        if result.deleted_count == 0 {
+            return Err(DeleteError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn fail_job(
+        &self,
+        failed: FailedNotification,
+    ) -> Result<FailedNotification, InsertError> {
+        self.db
+            .collection::<FailedNotification>("failed_notifications")
+            .insert_one(&failed, None)
+            .await?;
+        if let Err(err) = self.delete_job(failed.id).await {
+            log::error!(
+                "could not remove failed notification {}: {:?}",
+                failed.id,
+                err
+            );
+        }
+        Ok(failed)
+    }
+
+    async fn find_failed(&self, id: u32) -> Result<FailedNotification, FindError> {
+        match self
+            .db
+            .collection::<FailedNotification>("failed_notifications")
+            .find_one(doc! { "id": id }, None)
+            .await?
+        {
+            Some(failed) => Ok(failed),
+            None => Err(FindError::NotFound),
+        }
+    }
+
+    async fn find_all_failed(
+        &self,
+        team_id: Option<String>,
+        limit: i64,
+    ) -> Result<Vec<FailedNotification>, FindAllError> {
+        let filter = match team_id {
+            Some(team_id) => doc! { "team_id": team_id },
+            None => doc! {},
+        };
+        let options = mongodb::options::FindOptions::builder()
+            .sort(doc! { "failed_at": -1 })
+            .limit(limit)
+            .build();
+        let mut cursor = self
+            .db
+            .collection::<FailedNotification>("failed_notifications")
+            .find(filter, options)
+            .await?;
+
+        let mut result: Vec<FailedNotification> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn delete_failed(&self, id: u32) -> Result<(), DeleteError> {
+        let result = self
+            .db
+            .collection::<FailedNotification>("failed_notifications")
+            .delete_one(doc! { "id": id }, None)
+            .await?;
+        if result.deleted_count == 0 {
+            return Err(DeleteError::NotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+use bson::doc;
+
+use crate::domain::entities::PickRecord;
+
+use super::errors::{FindAllError, InsertError, UpdateError};
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn insert_pick(&self, pick: PickRecord) -> Result<PickRecord, InsertError>;
+    async fn find_picks(
+        &self,
+        event: u32,
+        channel: String,
+        limit: u32,
+    ) -> Result<Vec<PickRecord>, FindAllError>;
+    async fn find_all_picks(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<Vec<PickRecord>, FindAllError>;
+    /// Marks the latest pick of the user on the event as a no-show, returning it as it was before.
+    async fn mark_no_show(
+        &self,
+        event: u32,
+        channel: String,
+        user: String,
+    ) -> Result<PickRecord, UpdateError>;
+    /// Finds the picks of all the events of a channel, made from the `from` timestamp (inclusive)
+    /// to the `to` one (exclusive).
+    async fn find_channel_picks(
+        &self,
+        channel: String,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<PickRecord>, FindAllError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn insert_pick(&self, pick: PickRecord) -> Result<PickRecord, InsertError> {
+        self.db
+            .collection::<PickRecord>("picks")
+            .insert_one(&pick, None)
+            .await?;
+
+        Ok(pick)
+    }
+
+    async fn find_picks(
+        &self,
+        event: u32,
+        channel: String,
+        limit: u32,
+    ) -> Result<Vec<PickRecord>, FindAllError> {
+        let filter = doc! { "event": event, "channel": channel };
+        let options = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .limit(limit as i64)
+            .build();
+        let mut cursor = self
+            .db
+            .collection::<PickRecord>("picks")
+            .find(filter, options)
+            .await?;
+
+        let mut result: Vec<PickRecord> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn find_all_picks(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<Vec<PickRecord>, FindAllError> {
+        let filter = doc! { "event": event, "channel": channel };
+        let mut cursor = self
+            .db
+            .collection::<PickRecord>("picks")
+            .find(filter, None)
+            .await?;
+
+        let mut result: Vec<PickRecord> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn find_channel_picks(
+        &self,
+        channel: String,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<PickRecord>, FindAllError> {
+        let filter = doc! { "channel": channel, "timestamp": { "$gte": from, "$lt": to } };
+        let mut cursor = self
+            .db
+            .collection::<PickRecord>("picks")
+            .find(filter, None)
+            .await?;
+
+        let mut result: Vec<PickRecord> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn mark_no_show(
+        &self,
+        event: u32,
+        channel: String,
+        user: String,
+    ) -> Result<PickRecord, UpdateError> {
+        let filter = doc! { "event": event, "channel": channel, "user": user };
+        let options = mongodb::options::FindOneAndUpdateOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .build();
+        self.db
+            .collection::<PickRecord>("picks")
+            .find_one_and_update(filter, doc! { "$set": { "no_show": true } }, options)
+            .await?
+            .ok_or(UpdateError::NotFound)
+    }
+}
+
+/// The picks of the tests, kept in memory, newest last.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryRepository {
+    pub picks: std::sync::Mutex<Vec<PickRecord>>,
+}
+
+#[cfg(test)]
+impl MemoryRepository {
+    fn event_picks(&self, event: u32, channel: &str) -> Vec<PickRecord> {
+        self.picks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|pick| pick.event == event && pick.channel == channel)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Repository for MemoryRepository {
+    async fn insert_pick(&self, pick: PickRecord) -> Result<PickRecord, InsertError> {
+        self.picks.lock().unwrap().push(pick.clone());
+        Ok(pick)
+    }
+
+    async fn find_picks(
+        &self,
+        event: u32,
+        channel: String,
+        limit: u32,
+    ) -> Result<Vec<PickRecord>, FindAllError> {
+        Ok(self
+            .event_picks(event, &channel)
+            .into_iter()
+            .rev()
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn find_all_picks(
+        &self,
+        event: u32,
+        channel: String,
+    ) -> Result<Vec<PickRecord>, FindAllError> {
+        Ok(self.event_picks(event, &channel))
+    }
+
+    async fn mark_no_show(
+        &self,
+        event: u32,
+        channel: String,
+        user: String,
+    ) -> Result<PickRecord, UpdateError> {
+        let mut picks = self.picks.lock().unwrap();
+        let pick = picks
+            .iter_mut()
+            .rev()
+            .find(|pick| pick.event == event && pick.channel == channel && pick.user == user)
+            .ok_or(UpdateError::NotFound)?;
+        let before = pick.clone();
+        pick.no_show = true;
+        Ok(before)
+    }
+
+    async fn find_channel_picks(
+        &self,
+        channel: String,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<PickRecord>, FindAllError> {
+        Ok(self
+            .picks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|pick| pick.channel == channel && (from..to).contains(&pick.timestamp))
+            .cloned()
+            .collect())
+    }
+}
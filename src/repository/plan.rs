@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use bson::doc;
+use mongodb::options::UpdateOptions;
+
+use crate::domain::entities::TeamPlan;
+
+use super::errors::{FindError, UpdateError};
+use super::helpers::index;
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn find_plan(&self, team_id: String) -> Result<TeamPlan, FindError>;
+    async fn find_plan_by_subscription(
+        &self,
+        subscription_id: String,
+    ) -> Result<TeamPlan, FindError>;
+    /// Saves the plan of the team, creating it when the team has none yet.
+    async fn save_plan(&self, plan: TeamPlan) -> Result<TeamPlan, UpdateError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        db.collection::<TeamPlan>("plans")
+            .create_indexes(
+                vec![
+                    index(doc! { "team_id": 1 }, "team_id_unique", true),
+                    index(doc! { "subscription_id": 1 }, "subscription_id", false),
+                ],
+                None,
+            )
+            .await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn find_plan(&self, team_id: String) -> Result<TeamPlan, FindError> {
+        self.db
+            .collection::<TeamPlan>("plans")
+            .find_one(doc! { "team_id": team_id }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_plan_by_subscription(
+        &self,
+        subscription_id: String,
+    ) -> Result<TeamPlan, FindError> {
+        self.db
+            .collection::<TeamPlan>("plans")
+            .find_one(doc! { "subscription_id": subscription_id }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn save_plan(&self, plan: TeamPlan) -> Result<TeamPlan, UpdateError> {
+        let filter = doc! { "team_id": plan.team_id.clone() };
+        let update = doc! { "$set": bson::to_document(&plan)? };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<TeamPlan>("plans")
+            .update_one(filter, update, options)
+            .await?;
+        Ok(plan)
+    }
+}
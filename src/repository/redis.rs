@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{
+    aio::{ConnectionManager, ConnectionManagerConfig},
+    AsyncCommands, Script,
+};
+
+use super::errors::StoreError;
+use super::store::Store;
+
+/// The store shared by all the instances of the application, backed by Redis.
+///
+/// The commands are multiplexed over a single connection, which is reopened in the background
+/// whenever it fails.
+pub struct RedisStore {
+    connection: ConnectionManager,
+    increment: Script,
+}
+
+impl RedisStore {
+    /// Connects to the Redis server of the URL (i.e.
+    /// `redis://[[username]:password@]host[:port][/database]`, or `rediss://` over TLS).
+    pub async fn new(url: &str) -> Result<Self, StoreError> {
+        let client = redis::Client::open(url)?;
+        let config = ConnectionManagerConfig::new()
+            .set_connection_timeout(CONNECTION_TIMEOUT)
+            .set_response_timeout(RESPONSE_TIMEOUT)
+            .set_number_of_retries(CONNECTION_RETRIES);
+        Ok(Self {
+            connection: ConnectionManager::new_with_config(client, config).await?,
+            increment: Script::new(INCREMENT_SCRIPT),
+        })
+    }
+}
+
+#[async_trait]
+impl Store for RedisStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, StoreError> {
+        Ok(self.connection.clone().get(key).await?)
+    }
+
+    async fn set(&self, key: &str, value: String, ttl: Duration) -> Result<(), StoreError> {
+        Ok(redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("PX")
+            .arg(ttl_millis(ttl))
+            .query_async(&mut self.connection.clone())
+            .await?)
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        ttl: Duration,
+    ) -> Result<bool, StoreError> {
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("PX")
+            .arg(ttl_millis(ttl))
+            .arg("NX")
+            .query_async(&mut self.connection.clone())
+            .await?;
+        Ok(reply.is_some())
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<u64, StoreError> {
+        Ok(self
+            .increment
+            .key(key)
+            .arg(ttl_millis(ttl))
+            .invoke_async(&mut self.connection.clone())
+            .await?)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StoreError> {
+        Ok(self.connection.clone().del(key).await?)
+    }
+}
+
+fn ttl_millis(ttl: Duration) -> u64 {
+    ttl.as_millis().max(1) as u64
+}
+
+/// Increments the counter and sets its expiration at once, so that no counter is left without
+/// one. The counters found without an expiration are given one as well.
+const INCREMENT_SCRIPT: &str = r#"
+local count = redis.call('INCR', KEYS[1])
+if redis.call('PTTL', KEYS[1]) == -1 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[1])
+end
+return count
+"#;
+
+/// The time to open a connection to the server, before giving up.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The time to wait for the reply of a command, before failing it.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The number of times a connection is reopened, with an exponential backoff.
+const CONNECTION_RETRIES: usize = 3;
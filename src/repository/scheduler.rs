@@ -0,0 +1,283 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bson::{doc, Document};
+use mongodb::error::{ErrorKind, WriteError, WriteFailure};
+use mongodb::options::{FindOptions, IndexOptions, UpdateOptions};
+use mongodb::IndexModel;
+
+use crate::domain::entities::ScheduledEvent;
+
+use super::errors::{DeleteError, FindAllError, FindError, UpdateError};
+
+const LAST_RUN_KEY: &str = "last_run";
+const LEADER_LEASE_ID: &str = "leader";
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Finds the timestamp (in seconds) of the last minute processed by the scheduler.
+    async fn find_last_run(&self) -> Result<i64, FindError>;
+    /// Saves the timestamp (in seconds) of the last minute processed by the scheduler.
+    async fn save_last_run(&self, timestamp: i64) -> Result<(), UpdateError>;
+    /// Acquires or renews the scheduler leadership lease for the owner until `expires_at`.
+    ///
+    /// Returns `false` when the lease is held by another owner and did not expire before `now`.
+    async fn acquire_lease(
+        &self,
+        owner: String,
+        now: i64,
+        expires_at: i64,
+    ) -> Result<bool, UpdateError>;
+    async fn save_scheduled_event(&self, scheduled: ScheduledEvent) -> Result<(), UpdateError>;
+    async fn delete_scheduled_event(&self, event: u32) -> Result<(), DeleteError>;
+    async fn find_scheduled_event(&self, event: u32) -> Result<ScheduledEvent, FindError>;
+    async fn find_scheduled_events(&self) -> Result<Vec<ScheduledEvent>, FindAllError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        // Expired leases are removed by mongo, in case the leader stops without renewing it.
+        let ttl_index = IndexModel::builder()
+            .keys(doc! { "expires_at": 1 })
+            .options(
+                IndexOptions::builder()
+                    .expire_after(Duration::from_secs(0))
+                    .build(),
+            )
+            .build();
+        db.collection::<Document>("leases")
+            .create_index(ttl_index, None)
+            .await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn find_last_run(&self) -> Result<i64, FindError> {
+        let filter = doc! { "key": LAST_RUN_KEY };
+        let document = self
+            .db
+            .collection::<Document>("scheduler")
+            .find_one(filter, None)
+            .await?
+            .ok_or(FindError::NotFound)?;
+
+        document.get_i64("timestamp").map_err(|err| {
+            log::error!("find_last_run: invalid last run document: {}", err);
+            FindError::Unknown
+        })
+    }
+
+    async fn save_last_run(&self, timestamp: i64) -> Result<(), UpdateError> {
+        let filter = doc! { "key": LAST_RUN_KEY };
+        let update = doc! { "$set": { "timestamp": timestamp } };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<Document>("scheduler")
+            .update_one(filter, update, options)
+            .await?;
+        Ok(())
+    }
+
+    async fn acquire_lease(
+        &self,
+        owner: String,
+        now: i64,
+        expires_at: i64,
+    ) -> Result<bool, UpdateError> {
+        let update = doc! {
+            "$set": {
+                "owner": owner.clone(),
+                "expires_at": bson::DateTime::from_millis(expires_at * 1000),
+            }
+        };
+        let options = UpdateOptions::builder().upsert(true).build();
+        match self
+            .db
+            .collection::<Document>("leases")
+            .update_one(lease_filter(owner, now), update, options)
+            .await
+        {
+            Ok(..) => Ok(true),
+            // The upsert conflicts with the lease held by another owner.
+            Err(err) if is_duplicate_key(&err) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn save_scheduled_event(&self, scheduled: ScheduledEvent) -> Result<(), UpdateError> {
+        let filter = doc! { "event": scheduled.event };
+        let update = doc! { "$set": bson::to_document(&scheduled)? };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<ScheduledEvent>("scheduled_events")
+            .update_one(filter, update, options)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_scheduled_event(&self, event: u32) -> Result<(), DeleteError> {
+        self.db
+            .collection::<ScheduledEvent>("scheduled_events")
+            .delete_one(doc! { "event": event }, None)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_scheduled_event(&self, event: u32) -> Result<ScheduledEvent, FindError> {
+        self.db
+            .collection::<ScheduledEvent>("scheduled_events")
+            .find_one(doc! { "event": event }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_scheduled_events(&self) -> Result<Vec<ScheduledEvent>, FindAllError> {
+        let options = FindOptions::builder().sort(doc! { "next_at": 1 }).build();
+        let mut cursor = self
+            .db
+            .collection::<ScheduledEvent>("scheduled_events")
+            .find(doc! {}, options)
+            .await?;
+
+        let mut result: Vec<ScheduledEvent> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+}
+
+/// Matches the lease when it is held by the owner or expired before `now`. Otherwise, the upsert
+/// conflicts with the lease held by another owner.
+fn lease_filter(owner: String, now: i64) -> Document {
+    doc! {
+        "_id": LEADER_LEASE_ID,
+        "$or": [
+            { "owner": owner },
+            { "expires_at": { "$lt": bson::DateTime::from_millis(now * 1000) } },
+        ],
+    }
+}
+
+fn is_duplicate_key(err: &mongodb::error::Error) -> bool {
+    matches!(
+        err.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(WriteError {
+            code: DUPLICATE_KEY_CODE,
+            ..
+        }))
+    )
+}
+
+/// The scheduler state of the tests, kept in memory. The lease follows the same rules as on the
+/// database.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryRepository {
+    pub last_run: std::sync::Mutex<Option<i64>>,
+    /// The owner of the lease and its expiration.
+    pub lease: std::sync::Mutex<Option<(String, i64)>>,
+    pub scheduled: std::sync::Mutex<Vec<ScheduledEvent>>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Repository for MemoryRepository {
+    async fn find_last_run(&self) -> Result<i64, FindError> {
+        self.last_run.lock().unwrap().ok_or(FindError::NotFound)
+    }
+
+    async fn save_last_run(&self, timestamp: i64) -> Result<(), UpdateError> {
+        *self.last_run.lock().unwrap() = Some(timestamp);
+        Ok(())
+    }
+
+    async fn acquire_lease(
+        &self,
+        owner: String,
+        now: i64,
+        expires_at: i64,
+    ) -> Result<bool, UpdateError> {
+        let mut lease = self.lease.lock().unwrap();
+        match lease.as_ref() {
+            Some((holder, holder_expires_at)) if *holder != owner && *holder_expires_at >= now => {
+                Ok(false)
+            }
+            _ => {
+                *lease = Some((owner, expires_at));
+                Ok(true)
+            }
+        }
+    }
+
+    async fn save_scheduled_event(&self, scheduled: ScheduledEvent) -> Result<(), UpdateError> {
+        let mut events = self.scheduled.lock().unwrap();
+        events.retain(|event| event.event != scheduled.event);
+        events.push(scheduled);
+        Ok(())
+    }
+
+    async fn delete_scheduled_event(&self, event: u32) -> Result<(), DeleteError> {
+        self.scheduled
+            .lock()
+            .unwrap()
+            .retain(|scheduled| scheduled.event != event);
+        Ok(())
+    }
+
+    async fn find_scheduled_event(&self, event: u32) -> Result<ScheduledEvent, FindError> {
+        self.scheduled
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|scheduled| scheduled.event == event)
+            .cloned()
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_scheduled_events(&self) -> Result<Vec<ScheduledEvent>, FindAllError> {
+        let mut events = self.scheduled.lock().unwrap().clone();
+        events.sort_by_key(|scheduled| scheduled.next_at);
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lease_filter() {
+        assert_eq!(
+            lease_filter(String::from("a1"), 1686661200),
+            doc! {
+                "_id": "leader",
+                "$or": [
+                    { "owner": "a1" },
+                    { "expires_at": { "$lt": bson::DateTime::from_millis(1686661200000) } },
+                ],
+            }
+        );
+    }
+}
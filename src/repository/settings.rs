@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use bson::doc;
+use mongodb::options::UpdateOptions;
+
+use crate::domain::entities::TeamSettings;
+
+use super::errors::{FindAllError, FindError, UpdateError};
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn find_settings(&self, team_id: String) -> Result<TeamSettings, FindError>;
+    async fn find_all_settings_unprotected(&self) -> Result<Vec<TeamSettings>, FindAllError>;
+    /// Saves the settings of the team, creating them when the team has none yet.
+    async fn save_settings(&self, settings: TeamSettings) -> Result<TeamSettings, UpdateError>;
+}
+
+pub struct MongoDbRepository {
+    db: mongodb::Database,
+}
+
+impl MongoDbRepository {
+    pub async fn new(
+        uri: &str,
+        database: &str,
+        pool_size: u32,
+    ) -> Result<MongoDbRepository, mongodb::error::Error> {
+        // Parse a connection string into an options struct.
+        let mut client_options = mongodb::options::ClientOptions::parse(uri).await?;
+        client_options.max_pool_size = Some(pool_size);
+
+        let client = mongodb::Client::with_options(client_options)?;
+        let db = client.database(database);
+
+        db.run_command(doc! {"ping": 1}, None).await?;
+
+        Ok(MongoDbRepository { db })
+    }
+}
+
+#[async_trait]
+impl Repository for MongoDbRepository {
+    async fn find_settings(&self, team_id: String) -> Result<TeamSettings, FindError> {
+        self.db
+            .collection::<TeamSettings>("settings")
+            .find_one(doc! { "team_id": team_id }, None)
+            .await?
+            .ok_or(FindError::NotFound)
+    }
+
+    async fn find_all_settings_unprotected(&self) -> Result<Vec<TeamSettings>, FindAllError> {
+        let mut cursor = self
+            .db
+            .collection::<TeamSettings>("settings")
+            .find(doc! {}, None)
+            .await?;
+
+        let mut result: Vec<TeamSettings> = vec![];
+        while cursor.advance().await? {
+            result.push(cursor.deserialize_current()?);
+        }
+        Ok(result)
+    }
+
+    async fn save_settings(&self, settings: TeamSettings) -> Result<TeamSettings, UpdateError> {
+        let filter = doc! { "team_id": settings.team_id.clone() };
+        let update = doc! { "$set": bson::to_document(&settings)? };
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<TeamSettings>("settings")
+            .update_one(filter, update, options)
+            .await?;
+        Ok(settings)
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use super::errors::StoreError;
+
+/// A key-value store of short-lived state (e.g. caches, idempotency keys and rate limit counters).
+///
+/// The state is shared by all the instances of the application when it is backed by Redis.
+#[async_trait]
+pub trait Store: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<String>, StoreError>;
+    async fn set(&self, key: &str, value: String, ttl: Duration) -> Result<(), StoreError>;
+    /// Sets the value only when the key is not set yet, returning whether it was set.
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        ttl: Duration,
+    ) -> Result<bool, StoreError>;
+    /// Increments the counter of the key, returning its new value. The counter expires after the
+    /// `ttl` of its creation.
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<u64, StoreError>;
+    async fn delete(&self, key: &str) -> Result<(), StoreError>;
+}
+
+/// The store of a single instance, keeping the state in memory.
+#[derive(Default)]
+pub struct MemoryStore {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    /// When all the expired entries were last dropped.
+    swept_at: Option<Instant>,
+}
+
+struct Entry {
+    value: String,
+    expires_at: Instant,
+}
+
+impl MemoryStore {
+    /// Runs `f` on the entries, dropping the expired entry of the key before. All the expired
+    /// entries are dropped once there are too many, at most once per sweep interval, so that the
+    /// calls do not scan the whole map while holding the lock.
+    fn with_entries<T>(&self, key: &str, f: impl FnOnce(&mut HashMap<String, Entry>) -> T) -> T {
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("memory store lock is poisoned");
+        if state.entries.len() >= CLEANUP_THRESHOLD
+            && state
+                .swept_at
+                .is_none_or(|swept_at| now.duration_since(swept_at) >= SWEEP_INTERVAL)
+        {
+            state.entries.retain(|_, entry| entry.expires_at > now);
+            state.swept_at = Some(now);
+        } else if state
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.expires_at <= now)
+        {
+            state.entries.remove(key);
+        }
+        f(&mut state.entries)
+    }
+}
+
+#[async_trait]
+impl Store for MemoryStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, StoreError> {
+        Ok(self.with_entries(key, |entries| {
+            entries.get(key).map(|entry| entry.value.clone())
+        }))
+    }
+
+    async fn set(&self, key: &str, value: String, ttl: Duration) -> Result<(), StoreError> {
+        self.with_entries(key, |entries| {
+            entries.insert(
+                key.to_string(),
+                Entry {
+                    value,
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+        });
+        Ok(())
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: String,
+        ttl: Duration,
+    ) -> Result<bool, StoreError> {
+        Ok(self.with_entries(key, |entries| {
+            if entries.contains_key(key) {
+                return false;
+            }
+            entries.insert(
+                key.to_string(),
+                Entry {
+                    value,
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+            true
+        }))
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<u64, StoreError> {
+        Ok(self.with_entries(key, |entries| {
+            let entry = entries.entry(key.to_string()).or_insert(Entry {
+                value: String::from("0"),
+                expires_at: Instant::now() + ttl,
+            });
+            let count = entry.value.parse::<u64>().unwrap_or(0) + 1;
+            entry.value = count.to_string();
+            count
+        }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StoreError> {
+        self.with_entries(key, |entries| entries.remove(key));
+        Ok(())
+    }
+}
+
+/// The number of entries kept in memory before dropping the expired ones.
+const CLEANUP_THRESHOLD: usize = 10_000;
+/// The minimum time between the sweeps of the expired entries.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        let store = MemoryStore::default();
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(store.get("key").await, Ok(None));
+        assert_eq!(store.set_if_absent("key", "a".into(), ttl).await, Ok(true));
+        assert_eq!(store.set_if_absent("key", "b".into(), ttl).await, Ok(false));
+        assert_eq!(store.get("key").await, Ok(Some(String::from("a"))));
+        assert_eq!(store.delete("key").await, Ok(()));
+        assert_eq!(store.get("key").await, Ok(None));
+
+        assert_eq!(store.increment("counter", ttl).await, Ok(1));
+        assert_eq!(store.increment("counter", ttl).await, Ok(2));
+
+        store
+            .set("expired", "a".into(), Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(store.get("expired").await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_sweep() {
+        let store = MemoryStore::default();
+        let len = |store: &MemoryStore| store.state.lock().unwrap().entries.len();
+
+        for i in 0..CLEANUP_THRESHOLD {
+            store
+                .set(&i.to_string(), "a".into(), Duration::ZERO)
+                .await
+                .unwrap();
+        }
+        // The expired entries are dropped once there are too many.
+        store
+            .set("key", "a".into(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(len(&store), 1);
+
+        for i in 0..CLEANUP_THRESHOLD {
+            store
+                .set(&i.to_string(), "a".into(), Duration::ZERO)
+                .await
+                .unwrap();
+        }
+        // They are not dropped again before the sweep interval, but the expired keys read still
+        // are.
+        assert_eq!(store.get("0").await, Ok(None));
+        assert_eq!(len(&store), CLEANUP_THRESHOLD);
+        assert_eq!(store.get("key").await, Ok(Some(String::from("a"))));
+    }
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use super::errors::FindError;
+
+/// The user groups of the workspaces, managed on Slack.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Finds the IDs of the users of the group, with the access token of its team.
+    async fn find_members(&self, token: &str, group: &str) -> Result<Vec<String>, FindError>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use super::errors::FindError;
+
+/// The statuses the users set on their Slack profiles.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Finds which of the users are out of office, with the access token of their team.
+    async fn find_out_of_office(
+        &self,
+        token: &str,
+        users: &[String],
+    ) -> Result<Vec<String>, FindError>;
+}
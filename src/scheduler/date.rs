@@ -1,232 +1,259 @@
-use std::{
-    ops::{Add, Div, Mul, Sub},
-    vec,
-};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Weekday};
 
-use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
-
-use crate::domain::{entities::RepeatPeriod, timezone::Timezone};
+use crate::domain::{entities::RepeatPeriod, helpers::cron::parse_schedule, timezone::Timezone};
 use crate::helpers::date::Date;
 
-use super::helpers;
-
-#[derive(Clone, Copy)]
-struct Milliseconds(i64);
+use super::entities::EventSchedule;
 
-#[derive(Clone, Copy)]
-struct Minutes(i64);
+/// Maximum number of candidate occurrences checked when looking for the next one, so that the
+/// lookup ends for schedules whose occurrences are all skipped (e.g. by holidays).
+const MAX_OCCURRENCES_LOOKUP: usize = 1000;
 
-impl Milliseconds {
-    fn from_timestamp(timestamp: i64) -> Self {
-        Self(timestamp * 1000)
-    }
+#[derive(Clone)]
+pub struct SchedulerDate {
+    date: Date,
+    frequency: RepeatPeriod,
+    ends_at: Option<i64>,
+    holidays: Vec<NaiveDate>,
+    skip_weekends: bool,
+    skipped_dates: Vec<NaiveDate>,
 }
 
-impl From<Milliseconds> for Minutes {
-    fn from(value: Milliseconds) -> Self {
-        Minutes(value.0 / 60_000)
+impl SchedulerDate {
+    pub fn new(timestamp: i64, timezone: Timezone, frequency: RepeatPeriod) -> Self {
+        Self {
+            date: Date::new(timestamp).with_timezone(timezone),
+            frequency,
+            ends_at: None,
+            holidays: vec![],
+            skip_weekends: false,
+            skipped_dates: vec![],
+        }
     }
-}
 
-impl From<Minutes> for Milliseconds {
-    fn from(value: Minutes) -> Self {
-        Milliseconds(value.0 * 60_000)
+    /// The schedule of the event, skipping the holidays of its team.
+    pub fn from_event(event: &EventSchedule, holidays: Vec<NaiveDate>) -> Self {
+        Self::new(
+            event.timestamp,
+            event.timezone.clone(),
+            event.repeat.clone(),
+        )
+        .with_end(event.ends_at)
+        .with_holidays(holidays)
+        .with_skip_weekends(event.skip_weekends)
+        .with_skipped_dates(event.skipped_dates.clone())
     }
-}
 
-impl Add<Milliseconds> for Milliseconds {
-    type Output = Self;
-
-    fn add(self, rhs: Milliseconds) -> Self::Output {
-        Milliseconds(self.0 + rhs.0)
+    pub fn with_end(self, ends_at: Option<i64>) -> Self {
+        Self { ends_at, ..self }
     }
-}
-
-impl Sub<Milliseconds> for Milliseconds {
-    type Output = Self;
 
-    fn sub(self, rhs: Milliseconds) -> Self::Output {
-        Milliseconds(self.0 - rhs.0)
+    pub fn with_holidays(self, holidays: Vec<NaiveDate>) -> Self {
+        Self { holidays, ..self }
     }
-}
-
-impl Div<Milliseconds> for Milliseconds {
-    type Output = u32;
 
-    fn div(self, rhs: Milliseconds) -> Self::Output {
-        (self.0 / rhs.0) as u32
+    /// Skips the occurrences on the dates, besides the holidays.
+    pub fn with_skipped_dates(self, skipped_dates: Vec<NaiveDate>) -> Self {
+        Self {
+            skipped_dates,
+            ..self
+        }
     }
-}
-
-impl Mul<u32> for Milliseconds {
-    type Output = Milliseconds;
 
-    fn mul(self, rhs: u32) -> Self::Output {
-        Milliseconds(self.0 * (rhs as i64))
+    /// Skips the Saturday and Sunday occurrences of the daily and weekly schedules.
+    pub fn with_skip_weekends(self, skip_weekends: bool) -> Self {
+        Self {
+            skip_weekends,
+            ..self
+        }
     }
-}
 
-trait DateUtils: Send + Sync {
-    fn now(&self) -> DateTime<Utc>;
-    fn clone(&self) -> Box<dyn DateUtils>;
-}
-
-struct ChronoUtils();
-
-impl DateUtils for ChronoUtils {
-    fn now(&self) -> DateTime<Utc> {
-        Utc::now()
+    /// Finds the timestamp (in seconds) of the first occurrence strictly after `after`.
+    ///
+    /// Returns `None` when the event does not happen again.
+    pub fn find_next(&self, after: i64) -> Option<i64> {
+        match self.frequency {
+            RepeatPeriod::None => {
+                let time = self.date.timestamp();
+                (time > after && self.is_before_end(time) && !self.is_skipped(time)).then_some(time)
+            }
+            RepeatPeriod::Daily => self.find_next_by_interval(after, 1),
+            RepeatPeriod::Weekly(n) => self.find_next_by_interval(after, (n as u32) * 7),
+            RepeatPeriod::Monthly(n) => {
+                self.find_next_by_week_day(after, n as u32, self.find_week_day())
+            }
+            RepeatPeriod::Days(ref weekdays) => self.find_next_by_weekdays(after, weekdays),
+            RepeatPeriod::Cron(ref expression) => self.find_next_by_cron(after, expression),
+            RepeatPeriod::Yearly => self.find_next_by_year(after),
+        }
     }
 
-    fn clone(&self) -> Box<dyn DateUtils> {
-        Box::new(Self {})
+    /// Finds the timestamps (in seconds) of the occurrences strictly after `after`, up to `until`.
+    pub fn find_until(&self, after: i64, until: i64) -> Vec<i64> {
+        let mut occurrences = vec![];
+        let mut after = after;
+        while let Some(next) = self.find_next(after).filter(|&next| next <= until) {
+            occurrences.push(next);
+            after = next;
+        }
+        occurrences
     }
-}
-
-pub struct SchedulerDate {
-    date: Date,
-    frequency: RepeatPeriod,
-    utils: Box<dyn DateUtils>,
-}
 
-impl SchedulerDate {
-    pub fn new(timestamp: i64, timezone: Timezone, repeat: RepeatPeriod) -> Self {
-        Self::new_date(timestamp, timezone, repeat, Box::new(ChronoUtils()))
+    fn find_next_by_interval(&self, after: i64, interval: u32) -> Option<i64> {
+        self.find_next_by_interval_and_weekday(after, interval, |weekday| {
+            !self.skip_weekends || (weekday != Weekday::Sat && weekday != Weekday::Sun)
+        })
     }
 
-    fn new_date(
-        timestamp: i64,
-        timezone: Timezone,
-        frequency: RepeatPeriod,
-        utils: Box<dyn DateUtils>,
-    ) -> Self {
-        Self {
-            date: Date::new(timestamp).with_timezone(timezone),
-            frequency,
-            utils,
-        }
+    fn find_next_by_weekdays(&self, after: i64, weekdays: &[Weekday]) -> Option<i64> {
+        self.find_next_by_interval_and_weekday(after, 1, |weekday| weekdays.contains(&weekday))
     }
 
-    pub fn clone(&self) -> Self {
-        Self {
-            date: self.date.clone(),
-            frequency: self.frequency.clone(),
-            utils: self.utils.clone(),
+    fn find_next_by_interval_and_weekday(
+        &self,
+        after: i64,
+        interval: u32,
+        is_weekday_included: impl Fn(Weekday) -> bool,
+    ) -> Option<i64> {
+        let interval = (interval as i64).max(1);
+        let start_local = self.to_local(self.date.timestamp());
+
+        // Skip the intervals before the `after` date, keeping the event's cadence. Iterate over
+        // the local date & time, so the occurrences keep the same wall clock time across daylight
+        // saving time transitions.
+        let elapsed_days = (self.to_local(after).date() - start_local.date()).num_days();
+        let skipped = (elapsed_days / interval - 1).max(0);
+        let mut position_local = start_local + Duration::days(skipped * interval);
+        for _ in 0..MAX_OCCURRENCES_LOOKUP {
+            let position = self.local_to_utc(position_local)?;
+            if !self.is_before_end(position) {
+                return None;
+            }
+            if position > after
+                && is_weekday_included(position_local.weekday())
+                && !self.is_skipped_date(position_local.date())
+            {
+                return Some(position);
+            }
+            position_local += Duration::days(interval);
         }
+        None
     }
 
-    pub fn find_minutes(&self) -> Vec<i64> {
-        let time = Milliseconds::from_timestamp(self.date.timestamp());
-        match self.frequency {
-            RepeatPeriod::None => {
-                let year_start = Milliseconds::from_timestamp(
-                    helpers::find_first_day_of_year_timestamp(self.date.to_datetime().year()),
-                );
-                if self.date.to_datetime().year() == self.utils.now().year() {
-                    vec![Minutes::from(time - year_start).0]
-                } else {
-                    vec![]
-                }
-            }
-            RepeatPeriod::Daily => self.find_minutes_by_interval(time, 1),
-            RepeatPeriod::Weekly(n) => self.find_minutes_by_interval(time, (n as u32) * 7),
-            RepeatPeriod::Monthly(n) => {
-                self.find_minutes_by_week_day(n as u32, self.find_week_day())
+    fn find_next_by_year(&self, after: i64) -> Option<i64> {
+        let start_local = self.to_local(self.date.timestamp());
+        let first_year = (self.to_local(after).year() - 1).max(start_local.year());
+        for year in first_year..first_year + MAX_OCCURRENCES_LOOKUP as i32 {
+            // Skips the years without the event's day, i.e. the 29th of February.
+            let position_local = match start_local.with_year(year) {
+                Some(position_local) => position_local,
+                None => continue,
+            };
+            let position = self.local_to_utc(position_local)?;
+            if !self.is_before_end(position) {
+                return None;
             }
-            RepeatPeriod::Yearly => {
-                let year_start = Milliseconds::from_timestamp(
-                    helpers::find_first_day_of_year_timestamp(self.date.to_datetime().year()),
-                );
-                vec![Minutes::from(time - year_start).0]
+            if position > after && !self.is_skipped_date(position_local.date()) {
+                return Some(position);
             }
         }
+        None
     }
 
-    fn find_minutes_by_interval(&self, time: Milliseconds, interval: u32) -> Vec<i64> {
-        let year_start = Milliseconds::from_timestamp(helpers::find_first_day_of_year_timestamp(
-            self.date.to_datetime().year(),
-        ));
-        let year_end = Milliseconds::from_timestamp(helpers::find_first_day_of_year_timestamp(
-            self.date.to_datetime().year() + 1,
-        ));
-        let interval_duration = Duration::days(interval as i64);
-
-        let mut position_time = time;
-        let mut minutes = vec![];
-        while position_time.0 < year_end.0 {
-            let position_date = Date::new(position_time.0 / 1000)
-                .with_timezone(self.date.timezone())
-                .to_datetime();
-            let position_weekday = position_date.weekday();
-            if interval != 1
-                || (position_weekday != Weekday::Sat && position_weekday != Weekday::Sun)
-            {
-                let position = Milliseconds::from_timestamp(
-                    self.date
-                        .timezone()
-                        .tz()
-                        .from_local_datetime(&position_date.naive_local())
-                        .unwrap()
-                        .timestamp(),
-                ) - year_start;
-                minutes.push(Minutes::from(position).0);
+    fn find_next_by_cron(&self, after: i64, expression: &str) -> Option<i64> {
+        let schedule = match parse_schedule(expression) {
+            Ok(schedule) => schedule,
+            Err(err) => {
+                log::error!("could not parse cron expression {}: {}", expression, err);
+                return None;
+            }
+        };
+
+        // Occurrences start at the event date, looking back one second so it is included.
+        let start = Date::new((self.date.timestamp() - 1).max(after))
+            .with_timezone(self.date.timezone())
+            .to_datetime();
+        for occurrence in schedule.after(&start).take(MAX_OCCURRENCES_LOOKUP) {
+            let position = occurrence.timestamp() / 60 * 60;
+            if !self.is_before_end(position) {
+                return None;
+            }
+            if position > after && !self.is_skipped(position) {
+                return Some(position);
             }
-            let next_position_date = position_date + interval_duration;
-            position_time = Milliseconds::from_timestamp(next_position_date.timestamp());
         }
-
-        minutes
+        None
     }
 
-    fn find_minutes_by_week_day(
+    fn find_next_by_week_day(
         &self,
+        after: i64,
         monthly_interval: u32,
         (num_days_from_monday, week_number_of_month): (i64, i64),
-    ) -> Vec<i64> {
-        let today = self.utils.now();
-        let year_start = Milliseconds::from_timestamp(
-            NaiveDate::from_ymd_opt(today.year(), 1, 1)
-                .unwrap()
-                .and_hms_milli_opt(0, 0, 0, 0)
-                .unwrap()
-                .and_utc()
-                .timestamp(),
-        );
+    ) -> Option<i64> {
+        let interval = (monthly_interval as i32).max(1);
+        let start_local = self.to_local(self.date.timestamp());
+        let after_local = self.to_local(after);
+
+        // Months are counted from the year 0, skipping the ones before the `after` date.
+        let start_month = start_local.year() * 12 + start_local.month0() as i32;
+        let elapsed_months = after_local.year() * 12 + after_local.month0() as i32 - start_month;
+        let mut month = start_month + (elapsed_months / interval - 1).max(0) * interval;
+        for _ in 0..MAX_OCCURRENCES_LOOKUP {
+            let target_day = find_month_week_day(
+                month / 12,
+                (month % 12) as u32 + 1,
+                num_days_from_monday,
+                week_number_of_month,
+            )?;
+            let position = self.local_to_utc(target_day.and_time(start_local.time()))?;
+            if !self.is_before_end(position) {
+                return None;
+            }
+            if position > after && !self.is_skipped_date(target_day) {
+                return Some(position);
+            }
+            month += interval;
+        }
+        None
+    }
 
-        let year = today.year();
-        let mut month = self.date.to_datetime().month();
-        let mut minutes = vec![];
+    /// Converts a timestamp to the date & time in the event's timezone.
+    fn to_local(&self, timestamp: i64) -> NaiveDateTime {
+        Date::new(timestamp)
+            .with_timezone(self.date.timezone())
+            .to_datetime()
+            .naive_local()
+    }
 
-        while month <= 12 {
-            let first_day_of_month = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-            let first_weekday = first_day_of_month.weekday();
-            let diff_week_days_from_monday =
-                (num_days_from_monday + 7 - (first_weekday.num_days_from_monday() as i64)) % 7;
-
-            let diff_days_from_first_day_of_month =
-                (7 * week_number_of_month + diff_week_days_from_monday) as i64;
-            let mut target_day =
-                first_day_of_month + Duration::days(diff_days_from_first_day_of_month);
-
-            let target_month = target_day.month();
-            let target_year = target_day.year();
-            if target_month < month && target_year == year || target_year < year {
-                target_day = target_day + Duration::days(7);
-            } else if target_month > month && target_year == year || target_year > year {
-                target_day = target_day - Duration::days(7);
-            }
+    /// Converts a date & time in the event's timezone to UTC.
+    ///
+    /// Local times skipped by a daylight saving time transition are moved forward one hour and
+    /// repeated local times resolve to their first occurrence.
+    fn local_to_utc(&self, local: NaiveDateTime) -> Option<i64> {
+        let tz = self.date.timezone().tz();
+        tz.from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(local + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|datetime| datetime.timestamp())
+    }
 
-            let millis = Milliseconds::from_timestamp(
-                target_day
-                    .and_time(self.date.to_datetime().time())
-                    .and_utc()
-                    .timestamp(),
-            ) - year_start;
-            let minute = Minutes::from(millis);
-            minutes.push(minute.0);
-            month += monthly_interval;
+    fn is_before_end(&self, time: i64) -> bool {
+        match self.ends_at {
+            Some(ends_at) => time <= ends_at,
+            None => true,
         }
-        minutes
+    }
+
+    fn is_skipped(&self, time: i64) -> bool {
+        self.is_skipped_date(self.to_local(time).date())
+    }
+
+    fn is_skipped_date(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date) || self.skipped_dates.contains(&date)
     }
 
     fn find_week_day(&self) -> (i64, i64) {
@@ -246,6 +273,33 @@ impl SchedulerDate {
     }
 }
 
+/// Finds the day of the month on the given weekday and week number, falling back to the closest
+/// week within the month (e.g. the fifth Monday becomes the last Monday).
+fn find_month_week_day(
+    year: i32,
+    month: u32,
+    num_days_from_monday: i64,
+    week_number_of_month: i64,
+) -> Option<NaiveDate> {
+    let first_day_of_month = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let first_weekday = first_day_of_month.weekday();
+    let diff_week_days_from_monday =
+        (num_days_from_monday + 7 - (first_weekday.num_days_from_monday() as i64)) % 7;
+
+    let diff_days_from_first_day_of_month = 7 * week_number_of_month + diff_week_days_from_monday;
+    let target_day = first_day_of_month + Duration::days(diff_days_from_first_day_of_month);
+
+    let target_month = target_day.month();
+    let target_year = target_day.year();
+    if target_month < month && target_year == year || target_year < year {
+        Some(target_day + Duration::days(7))
+    } else if target_month > month && target_year == year || target_year > year {
+        Some(target_day - Duration::days(7))
+    } else {
+        Some(target_day)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::NaiveTime;
@@ -254,6 +308,30 @@ mod tests {
 
     const MINUTES_IN_A_DAY: i64 = 24 * 60;
 
+    /// Finds all the occurrences within the year, as minutes from the start of the year in UTC.
+    fn find_minutes(date: &SchedulerDate, year: i32) -> Vec<i64> {
+        let year_start = NaiveDate::from_ymd_opt(year, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        let year_end = NaiveDate::from_ymd_opt(year + 1, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+
+        let mut minutes = vec![];
+        let mut after = year_start - 1;
+        while let Some(next) = date.find_next(after).filter(|&next| next < year_end) {
+            minutes.push((next - year_start) / 60);
+            after = next;
+        }
+        minutes
+    }
+
     #[test]
     fn it_should_create_date_instance() {
         let date = 978310860; // String::from("2001-01-01 01:01:00.000 UTC")
@@ -273,19 +351,14 @@ mod tests {
     }
 
     #[test]
-    fn it_should_return_no_minutes_when_frequency_is_none_and_year_is_different() {
+    fn it_should_return_no_occurrence_when_frequency_is_none_and_date_has_passed() {
         let date = 1672617660; // String::from("2023-01-02 00:01:00.000 UTC")
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::None;
 
-        let result = SchedulerDate::new_date(
-            date,
-            timezone,
-            repeat,
-            Box::new(MockDateUtils::from_ymd(2000, 1, 1)),
-        );
-        let result = result.find_minutes();
-        assert_eq!(result.len(), 0);
+        let result = SchedulerDate::new(date, timezone, repeat);
+        assert_eq!(result.find_next(date - 60), Some(date));
+        assert_eq!(result.find_next(date), None);
     }
 
     #[test]
@@ -294,13 +367,8 @@ mod tests {
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::None;
 
-        let result = SchedulerDate::new_date(
-            date,
-            timezone,
-            repeat,
-            Box::new(MockDateUtils::from_ymd(2023, 1, 1)),
-        );
-        let result = result.find_minutes();
+        let result = SchedulerDate::new(date, timezone, repeat);
+        let result = find_minutes(&result, 2023);
         assert_eq!(result, vec![MINUTES_IN_A_DAY + 1]);
     }
 
@@ -310,13 +378,8 @@ mod tests {
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::Yearly;
 
-        let result = SchedulerDate::new_date(
-            date,
-            timezone,
-            repeat,
-            Box::new(MockDateUtils::from_ymd(2023, 1, 1)),
-        );
-        let result = result.find_minutes();
+        let result = SchedulerDate::new(date, timezone, repeat);
+        let result = find_minutes(&result, 2023);
         assert_eq!(result, vec![MINUTES_IN_A_DAY + 1]);
     }
 
@@ -326,8 +389,8 @@ mod tests {
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::Daily;
 
-        let result = SchedulerDate::new(date, timezone, repeat);
-        let result = result.find_minutes();
+        let result = SchedulerDate::new(date, timezone, repeat).with_skip_weekends(true);
+        let result = find_minutes(&result, 2023);
         assert_eq!(result.len(), 260);
 
         let minutes: Vec<i64> = vec![2..7, 9..14, 16..21, 23..28, 30..32]
@@ -347,6 +410,139 @@ mod tests {
         assert_eq!(result[result.len() - minutes.len()..], minutes);
     }
 
+    #[test]
+    fn it_should_return_the_minutes_until_the_end_date() {
+        let date = 1672531260; // String::from("2023-01-01 00:01:00.000 UTC");
+        let ends_at = 1673308860; // String::from("2023-01-10 00:01:00.000 UTC");
+        let timezone = Timezone::UTC;
+        let repeat = RepeatPeriod::Daily;
+
+        let result = SchedulerDate::new(date, timezone, repeat)
+            .with_end(Some(ends_at))
+            .with_skip_weekends(true);
+        let result = find_minutes(&result, 2023);
+
+        let minutes: Vec<i64> = vec![2..7, 9..11]
+            .into_iter()
+            .flat_map(|range| range.collect::<Vec<i64>>())
+            .map(|day| (day - 1) * (24 * 60) + 1)
+            .collect();
+        assert_eq!(result, minutes);
+    }
+
+    #[test]
+    fn it_should_keep_the_weekends_unless_skipped() {
+        let date = 1672531260; // String::from("2023-01-01 00:01:00.000 UTC");
+        let ends_at = 1673308860; // String::from("2023-01-10 00:01:00.000 UTC");
+        let timezone = Timezone::UTC;
+
+        let result =
+            SchedulerDate::new(date, timezone.clone(), RepeatPeriod::Daily).with_end(Some(ends_at));
+        let result = find_minutes(&result, 2023);
+        let minutes: Vec<i64> = (1..11).map(|day| (day - 1) * (24 * 60) + 1).collect();
+        assert_eq!(result, minutes);
+
+        // The first day of 2023 is a Sunday, so the weekly occurrences are all skipped.
+        let result = SchedulerDate::new(date, timezone.clone(), RepeatPeriod::Weekly(1))
+            .with_end(Some(ends_at))
+            .with_skip_weekends(true);
+        assert_eq!(find_minutes(&result, 2023), Vec::<i64>::new());
+
+        let result =
+            SchedulerDate::new(date, timezone, RepeatPeriod::Weekly(1)).with_end(Some(ends_at));
+        assert_eq!(find_minutes(&result, 2023), vec![1, 7 * 24 * 60 + 1]);
+    }
+
+    #[test]
+    fn it_should_skip_the_minutes_on_holidays() {
+        let date = 1672531260; // String::from("2023-01-01 00:01:00.000 UTC");
+        let ends_at = 1673308860; // String::from("2023-01-10 00:01:00.000 UTC");
+        let timezone = Timezone::UTC;
+        let repeat = RepeatPeriod::Daily;
+
+        let result = SchedulerDate::new(date, timezone, repeat)
+            .with_end(Some(ends_at))
+            .with_skip_weekends(true)
+            .with_holidays(vec![
+                NaiveDate::from_ymd_opt(2023, 1, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2023, 1, 6).unwrap(),
+            ]);
+        let result = find_minutes(&result, 2023);
+
+        let minutes: Vec<i64> = vec![3..6, 9..11]
+            .into_iter()
+            .flat_map(|range| range.collect::<Vec<i64>>())
+            .map(|day| (day - 1) * (24 * 60) + 1)
+            .collect();
+        assert_eq!(result, minutes);
+    }
+
+    #[test]
+    fn it_should_skip_the_minutes_on_skipped_dates() {
+        let date = 1672531260; // String::from("2023-01-01 00:01:00.000 UTC");
+        let ends_at = 1673308860; // String::from("2023-01-10 00:01:00.000 UTC");
+        let timezone = Timezone::UTC;
+        let repeat = RepeatPeriod::Daily;
+
+        let result = SchedulerDate::new(date, timezone, repeat)
+            .with_end(Some(ends_at))
+            .with_holidays(vec![NaiveDate::from_ymd_opt(2023, 1, 2).unwrap()])
+            .with_skipped_dates(vec![NaiveDate::from_ymd_opt(2023, 1, 3).unwrap()]);
+        let result = find_minutes(&result, 2023);
+
+        let minutes: Vec<i64> = (4..11).map(|day| (day - 1) * (24 * 60) + 1).collect();
+        assert_eq!(result[1..], minutes);
+        assert_eq!(result[0], 1);
+    }
+
+    #[test]
+    fn it_should_return_the_minutes_for_the_selected_weekdays() {
+        let date = 1672531260; // String::from("2023-01-01 00:01:00.000 UTC");
+        let ends_at = 1673913660; // String::from("2023-01-17 00:01:00.000 UTC");
+        let timezone = Timezone::UTC;
+        let repeat = RepeatPeriod::Days(vec![Weekday::Mon, Weekday::Wed, Weekday::Sun]);
+
+        let result = SchedulerDate::new(date, timezone, repeat).with_end(Some(ends_at));
+        let result = find_minutes(&result, 2023);
+
+        let minutes: Vec<i64> = vec![1, 2, 4, 8, 9, 11, 15, 16]
+            .into_iter()
+            .map(|day| (day - 1) * (24 * 60) + 1)
+            .collect();
+        assert_eq!(result, minutes);
+    }
+
+    #[test]
+    fn it_should_return_the_minutes_for_cron_frequency() {
+        let date = 1672531260; // String::from("2023-01-01 00:01:00.000 UTC");
+        let ends_at = 1673308860; // String::from("2023-01-10 00:01:00.000 UTC");
+        let timezone = Timezone::UTC;
+        let repeat = RepeatPeriod::Cron(String::from("30 9 * * Mon,Wed,Fri"));
+
+        let result = SchedulerDate::new(date, timezone, repeat).with_end(Some(ends_at));
+        let result = find_minutes(&result, 2023);
+
+        let minutes: Vec<i64> = vec![2, 4, 6, 9]
+            .into_iter()
+            .map(|day| (day - 1) * (24 * 60) + 9 * 60 + 30)
+            .collect();
+        assert_eq!(result, minutes);
+    }
+
+    #[test]
+    fn it_should_find_the_next_occurrence_in_the_following_year() {
+        let date = 1672617660; // String::from("2023-01-02 00:01:00.000 UTC")
+        let after = 1703980800; // String::from("2023-12-31 00:00:00.000 UTC")
+        let timezone = Timezone::UTC;
+
+        // The first Monday of the month.
+        let result = SchedulerDate::new(date, timezone.clone(), RepeatPeriod::Monthly(1));
+        assert_eq!(result.find_next(after), Some(1704067260)); // 2024-01-01 00:01:00 UTC
+
+        let result = SchedulerDate::new(date, timezone, RepeatPeriod::Yearly);
+        assert_eq!(result.find_next(after), Some(1704153660)); // 2024-01-02 00:01:00 UTC
+    }
+
     #[test]
     fn it_should_return_all_the_minutes_for_weekly_frequency_until_end_of_the_year() {
         let date = 1672617660; // String::from("2023-01-02 00:01:00.000 UTC")
@@ -354,16 +550,13 @@ mod tests {
         let repeat = RepeatPeriod::Weekly(1);
 
         let result = SchedulerDate::new(date, timezone, repeat);
-        let result = result.find_minutes();
+        let result = find_minutes(&result, 2023);
         assert_eq!(result.len(), 52);
 
         let minutes: Vec<i64> = (0..52)
             .into_iter()
             .map(|index| 2 + index * 7)
-            .enumerate()
-            .map(|(index, day)| {
-                (day - 1) * (24 * 60) + 1 - (if index < 12 || index > 42 { 0 } else { 60 })
-            })
+            .map(|day| (day - 1) * (24 * 60) + 1)
             .collect();
         assert_eq!(result, minutes);
     }
@@ -375,16 +568,13 @@ mod tests {
         let repeat = RepeatPeriod::Weekly(2);
 
         let result = SchedulerDate::new(date, timezone, repeat);
-        let result = result.find_minutes();
+        let result = find_minutes(&result, 2023);
         assert_eq!(result.len(), 26);
 
         let minutes: Vec<i64> = (0..26)
             .into_iter()
             .map(|index| 2 + index * 7 * 2)
-            .enumerate()
-            .map(|(index, day)| {
-                (day - 1) * (24 * 60) + 1 - (if index < 6 || index > 21 { 0 } else { 60 })
-            })
+            .map(|day| (day - 1) * (24 * 60) + 1)
             .collect();
         assert_eq!(result, minutes);
     }
@@ -395,9 +585,8 @@ mod tests {
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::Monthly(1);
 
-        let result =
-            SchedulerDate::new_date(date, timezone, repeat, Box::new(MockDateUtils::new()));
-        let result = result.find_minutes();
+        let result = SchedulerDate::new(date, timezone, repeat);
+        let result = find_minutes(&result, 2023);
         assert_eq!(result.len(), 12);
 
         let days = vec![2, 6, 6, 3, 1, 5, 3, 7, 4, 2, 6, 4];
@@ -418,9 +607,8 @@ mod tests {
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::Monthly(1);
 
-        let result =
-            SchedulerDate::new_date(date, timezone, repeat, Box::new(MockDateUtils::new()));
-        let result = result.find_minutes();
+        let result = SchedulerDate::new(date, timezone, repeat);
+        let result = find_minutes(&result, 2023);
         assert_eq!(result.len(), 12);
 
         let days = vec![31, 28, 28, 25, 30, 27, 25, 29, 26, 31, 28, 26];
@@ -440,9 +628,8 @@ mod tests {
         let timezone = Timezone::UTC;
         let repeat = RepeatPeriod::Monthly(2);
 
-        let result =
-            SchedulerDate::new_date(date, timezone, repeat, Box::new(MockDateUtils::new()));
-        let result = result.find_minutes();
+        let result = SchedulerDate::new(date, timezone, repeat);
+        let result = find_minutes(&result, 2023);
         assert_eq!(result.len(), 6);
 
         let days = vec![2, 6, 1, 3, 4, 6];
@@ -456,37 +643,25 @@ mod tests {
         assert_eq!(result, minutes);
     }
 
-    struct MockDateUtils {
-        now_date: DateTime<Utc>,
-    }
-
-    impl MockDateUtils {
-        fn new() -> Self {
-            Self::from_ymd(2023, 3, 9)
-        }
-
-        fn from_ymd(year: i32, month: u32, day: u32) -> Self {
-            Self {
-                now_date: DateTime::from_naive_utc_and_offset(
-                    NaiveDate::from_ymd_opt(year, month, day)
-                        .unwrap()
-                        .and_hms_opt(0, 0, 0)
-                        .unwrap(),
-                    Utc,
-                ),
-            }
-        }
-    }
-
-    impl DateUtils for MockDateUtils {
-        fn now(&self) -> DateTime<Utc> {
-            self.now_date
-        }
+    #[test]
+    fn it_should_find_the_occurrences_until_the_given_date() {
+        let date = 1672617660; // String::from("2023-01-02 00:01:00.000 UTC")
+        let result = SchedulerDate::new(date, Timezone::UTC, RepeatPeriod::Daily)
+            .with_skip_weekends(true)
+            .with_skipped_dates(vec![NaiveDate::from_ymd_opt(2023, 1, 4).unwrap()]);
 
-        fn clone(&self) -> Box<dyn DateUtils> {
-            Box::new(Self {
-                now_date: self.now_date.clone(),
-            })
-        }
+        let day = 24 * 60 * 60;
+        let occurrences = result.find_until(date - 1, date + 7 * day);
+        assert_eq!(
+            occurrences,
+            vec![
+                date,
+                date + day,
+                date + 3 * day,
+                date + 4 * day,
+                date + 7 * day
+            ]
+        );
+        assert!(result.find_until(date, date + day - 1).is_empty());
     }
 }
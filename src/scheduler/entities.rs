@@ -1,8 +1,26 @@
+use chrono::NaiveDate;
+
 use crate::domain::{entities::RepeatPeriod, timezone::Timezone};
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct EventSchedule {
     pub id: u32,
+    pub team_id: String,
     pub timestamp: i64,
     pub timezone: Timezone,
     pub repeat: RepeatPeriod,
+    pub ends_at: Option<i64>,
+    pub reminder_minutes: Option<u32>,
+    pub skip_weekends: bool,
+    pub skipped_dates: Vec<NaiveDate>,
+}
+
+pub struct SchedulerConfigs {
+    /// The maximum number of missed minutes caught up with when becoming the leader.
+    pub catch_up_minutes: i64,
+    /// The number of seconds the leadership lease lasts without being renewed.
+    pub lease_seconds: i64,
+    /// The number of days the deleted events are kept for before being purged. They are kept
+    /// forever when zero.
+    pub deleted_events_retention_days: u32,
 }
@@ -1,77 +1,167 @@
-use std::{collections::HashMap, fmt::Display, sync::Arc, vec};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, Ordering},
+        Arc,
+    },
+    vec,
+};
 
+use chrono::NaiveDate;
 use tokio::{
     sync::{mpsc::Sender, Mutex},
     task::yield_now,
 };
 
-use super::{date::SchedulerDate, entities::EventSchedule, helpers};
+use super::{
+    date::SchedulerDate,
+    entities::{EventSchedule, SchedulerConfigs},
+    helpers,
+};
 use crate::{
-    domain::events::pick_auto_participants,
-    helpers::date::Date,
-    repository::{auth, event},
+    domain::{
+        acknowledgments::repick_unacknowledged,
+        audit::save_record,
+        entities::{AuditAction, QuietHours},
+        events::{
+            deactivate_ended_events, find_all_events_and_dates, find_event_reminders,
+            pick_auto_participants, purge_deleted_events,
+        },
+        history::find_monthly_recaps,
+        holidays::find_all_holidays,
+        participants::sync_group,
+        scheduler::{acquire_leadership, find_last_run, save_last_run, save_next_occurrence},
+        settings::find_all_settings,
+    },
+    helpers::{
+        date::Date,
+        reporter::{self, Report},
+    },
+    repository::{
+        acknowledgment, audit, auth, calendar, event, holiday, pick_history, scheduler, settings,
+        user_group, user_status,
+    },
 };
 
+/// The scheduled events, queued by their next occurrence.
+///
+/// Rescheduling an event does not remove its previous occurrence from the queue, which is skipped
+/// once it does not match the event's next occurrence anymore. The reminders are queued apart, by
+/// the minute they are due before their event's occurrence.
+///
+/// Occurrences within the quiet hours of their team are deferred to the end of the quiet hours,
+/// while the reminders within them are skipped.
 struct DateRecords {
-    events_per_minute: HashMap<i64, Vec<u32>>,
+    queue: BinaryHeap<Reverse<(i64, u32)>>,
+    next_events_date: HashMap<u32, i64>,
+    reminders: BinaryHeap<Reverse<(i64, u32)>>,
+    next_reminders_date: HashMap<u32, i64>,
     saved_events_date: HashMap<u32, SchedulerDate>,
+    saved_events: HashMap<u32, EventSchedule>,
+    team_holidays: HashMap<String, Vec<NaiveDate>>,
+    team_quiet_hours: HashMap<String, QuietHours>,
+    /// The timestamp after which the next occurrences are searched for.
+    last_checked: i64,
+    /// The next occurrences changed since they were last persisted.
+    changes: HashMap<u32, Option<i64>>,
 }
 
 impl DateRecords {
     fn new() -> Self {
         Self {
-            events_per_minute: HashMap::new(),
+            queue: BinaryHeap::new(),
+            next_events_date: HashMap::new(),
+            reminders: BinaryHeap::new(),
+            next_reminders_date: HashMap::new(),
             saved_events_date: HashMap::new(),
+            saved_events: HashMap::new(),
+            team_holidays: HashMap::new(),
+            team_quiet_hours: HashMap::new(),
+            last_checked: helpers::find_current_minute(),
+            changes: HashMap::new(),
         }
     }
 
-    async fn check(
-        &self,
-        event_repo: Arc<dyn event::Repository>,
-        auth_repo: Arc<dyn auth::Repository>,
-        minute: i64,
-    ) -> Vec<pick_auto_participants::Pick> {
-        if let Some(events) = self.events_per_minute.get(&minute) {
-            if let Some(response) = self.pick_for_events(event_repo, auth_repo, events).await {
-                return response.picks.into_iter().map(|(_, picks)| picks).collect();
+    /// Takes the events with occurrences up to `until`, scheduling their following occurrence.
+    ///
+    /// Events with several occurrences up to `until` are only taken once.
+    fn take_due(&mut self, until: i64) -> Vec<u32> {
+        let mut events: Vec<u32> = vec![];
+        while let Some(&Reverse((time, event_id))) = self.queue.peek() {
+            if time > until {
+                break;
+            }
+            self.queue.pop();
+            if self.next_events_date.get(&event_id) == Some(&time) {
+                events.push(event_id);
             }
         }
-        vec![]
+
+        self.last_checked = until;
+        events.sort_unstable();
+        events.dedup();
+        for &event_id in events.iter() {
+            self.schedule(event_id);
+        }
+        self.compact();
+        events
     }
 
-    async fn pick_for_events(
-        &self,
-        event_repo: Arc<dyn event::Repository>,
-        auth_repo: Arc<dyn auth::Repository>,
-        events: &Vec<u32>,
-    ) -> Option<pick_auto_participants::Response> {
-        let req = pick_auto_participants::Request {
-            events: events.clone(),
-        };
-        let res = match pick_auto_participants::execute(event_repo.clone(), auth_repo, req).await {
+    /// Takes the events with reminders due up to `until`, scheduling the reminders of the
+    /// following occurrences.
+    ///
+    /// Reminders due before the current minute are dropped, as their occurrence is about to be
+    /// picked or was picked already.
+    fn take_due_reminders(&mut self, until: i64) -> Vec<u32> {
+        let mut events: Vec<u32> = vec![];
+        while let Some(&Reverse((time, event_id))) = self.reminders.peek() {
+            if time > until {
+                break;
+            }
+            self.reminders.pop();
+            if self.next_reminders_date.get(&event_id) != Some(&time) {
+                continue;
+            }
+            if time <= until - 60 {
+                log::trace!("skipping missed reminder of event {}: {}", event_id, time);
+            } else if self.is_quiet(event_id, time) {
+                log::trace!("skipping reminder of event {} in quiet hours", event_id);
+            } else {
+                events.push(event_id);
+            }
+            self.next_reminders_date.remove(&event_id);
+            self.schedule_reminder(event_id, until);
+        }
+        events
+    }
+
+    async fn deactivate_ended(&mut self, event_repo: Arc<dyn event::Repository>) {
+        let res = match deactivate_ended_events::execute(event_repo).await {
             Ok(res) => res,
             Err(err) => {
-                log::error!("could not automatically pick participants: {:?}", err);
-                return None;
+                log::error!("could not deactivate ended events: {:?}", err);
+                return;
             }
         };
-        log::trace!(
-            "automatically picked participants for events {:?}: {:?}",
-            events,
-            res
-        );
-        Some(res)
+        for event_id in res.events.into_iter() {
+            log::trace!("event {} has ended: removing it from scheduler", event_id);
+            self.remove(event_id);
+        }
     }
 
     fn insert(&mut self, event: EventSchedule) {
-        if self.saved_events_date.contains_key(&event.id) {
-            log::trace!("removing saved event before adding the new event to scheduler");
-            self.clear_event(event.id);
-        }
-
-        let date = SchedulerDate::new(event.timestamp, event.timezone.clone(), event.repeat);
-        self.set_event_minutes(event.id, &date);
+        let holidays = self
+            .team_holidays
+            .get(&event.team_id)
+            .cloned()
+            .unwrap_or_default();
+        let date = SchedulerDate::from_event(&event, holidays);
         self.saved_events_date.insert(event.id, date);
+        self.saved_events.insert(event.id, event.clone());
+        self.schedule(event.id);
+        self.schedule_reminder(event.id, self.last_checked);
         let date_str = Date::new(event.timestamp)
             .with_timezone(event.timezone)
             .to_string();
@@ -88,58 +178,220 @@ impl DateRecords {
             log::trace!("trying to remove inexistent event from scheduler");
             return;
         }
-        self.clear_event(event_id);
+        self.next_events_date.remove(&event_id);
+        self.next_reminders_date.remove(&event_id);
+        self.saved_events_date.remove(&event_id);
+        self.saved_events.remove(&event_id);
+        self.changes.insert(event_id, None);
         log::trace!("removed event from scheduler: {}", event_id);
     }
 
-    fn reset_minutes(&mut self) {
-        self.events_per_minute = HashMap::new();
+    fn set_holidays(&mut self, team_id: String, holidays: Vec<NaiveDate>) {
+        let event_ids: Vec<u32> = self
+            .saved_events
+            .values()
+            .filter(|event| event.team_id == team_id)
+            .map(|event| event.id)
+            .collect();
+        for event_id in event_ids.into_iter() {
+            if let Some(date) = self.saved_events_date.remove(&event_id) {
+                let date = date.with_holidays(holidays.clone());
+                self.saved_events_date.insert(event_id, date);
+                self.schedule(event_id);
+                self.schedule_reminder(event_id, self.last_checked);
+            }
+        }
+        log::trace!("updated {} holidays for team {}", holidays.len(), team_id);
+        self.team_holidays.insert(team_id, holidays);
+    }
 
-        let mut saved_events_date: HashMap<u32, SchedulerDate> = HashMap::new();
-        for (&event_id, date) in self.saved_events_date.iter() {
-            saved_events_date.insert(event_id, date.clone());
+    fn set_quiet_hours(&mut self, team_id: String, quiet_hours: Option<QuietHours>) {
+        match quiet_hours {
+            Some(quiet_hours) => {
+                log::trace!("updated quiet hours for team {}: {}", team_id, quiet_hours);
+                self.team_quiet_hours.insert(team_id.clone(), quiet_hours);
+            }
+            None => {
+                log::trace!("removed quiet hours for team {}", team_id);
+                self.team_quiet_hours.remove(&team_id);
+            }
         }
-        for (&event_id, date) in saved_events_date.iter() {
-            self.set_event_minutes(event_id, date);
+        let event_ids: Vec<u32> = self
+            .saved_events
+            .values()
+            .filter(|event| event.team_id == team_id)
+            .map(|event| event.id)
+            .collect();
+        for event_id in event_ids.into_iter() {
+            self.schedule(event_id);
+            self.schedule_reminder(event_id, self.last_checked);
         }
     }
 
-    fn set_event_minutes(&mut self, event_id: u32, date: &SchedulerDate) {
-        let minutes = date.find_minutes();
-        log::trace!(
-            "calculated minutes for the event {}: {}",
-            event_id,
-            minutes
-                .iter()
-                .map(|v| v.to_string())
-                .collect::<Vec<String>>()
-                .join("|")
-        );
-        for minute in minutes.iter() {
-            match self.events_per_minute.get_mut(&minute) {
-                Some(events_per_minute) => {
-                    events_per_minute.push(event_id);
-                }
-                None => {
-                    self.events_per_minute.insert(*minute, vec![event_id]);
-                }
+    /// Updates the scheduler with the given events, holidays and quiet hours, only recalculating
+    /// the minutes of the events that changed.
+    fn sync(
+        &mut self,
+        events: Vec<EventSchedule>,
+        mut team_holidays: HashMap<String, Vec<NaiveDate>>,
+        mut team_quiet_hours: HashMap<String, QuietHours>,
+    ) {
+        let teams: HashSet<String> = self
+            .team_quiet_hours
+            .keys()
+            .chain(team_quiet_hours.keys())
+            .cloned()
+            .collect();
+        for team_id in teams.into_iter() {
+            let quiet_hours = team_quiet_hours.remove(&team_id);
+            if self.team_quiet_hours.get(&team_id) != quiet_hours.as_ref() {
+                self.set_quiet_hours(team_id, quiet_hours);
+            }
+        }
+
+        let teams: HashSet<String> = self
+            .team_holidays
+            .keys()
+            .chain(team_holidays.keys())
+            .cloned()
+            .collect();
+        for team_id in teams.into_iter() {
+            let mut holidays = team_holidays.remove(&team_id).unwrap_or_default();
+            holidays.sort_unstable();
+            if self.team_holidays.get(&team_id) != Some(&holidays) {
+                self.set_holidays(team_id, holidays);
+            }
+        }
+
+        let removed: Vec<u32> = self
+            .saved_events
+            .keys()
+            .filter(|id| !events.iter().any(|event| event.id == **id))
+            .copied()
+            .collect();
+        for event_id in removed.into_iter() {
+            self.remove(event_id);
+        }
+        for event in events.into_iter() {
+            if self.saved_events.get(&event.id) != Some(&event) {
+                self.insert(event);
+            }
+        }
+    }
+
+    /// Schedules all the events again, from their first occurrence after `after`.
+    fn reschedule(&mut self, after: i64) {
+        self.last_checked = after;
+        self.queue.clear();
+        self.next_events_date.clear();
+        self.reminders.clear();
+        self.next_reminders_date.clear();
+        let event_ids: Vec<u32> = self.saved_events_date.keys().copied().collect();
+        for event_id in event_ids.into_iter() {
+            self.schedule(event_id);
+            self.schedule_reminder(event_id, after);
+        }
+    }
+
+    fn schedule(&mut self, event_id: u32) {
+        let next = self
+            .saved_events_date
+            .get(&event_id)
+            .and_then(|date| date.find_next(self.last_checked))
+            .map(|time| self.defer_quiet_hours(event_id, time));
+        match next {
+            Some(time) => {
+                log::trace!("next occurrence of event {}: {}", event_id, time);
+                self.next_events_date.insert(event_id, time);
+                self.queue.push(Reverse((time, event_id)));
+                self.changes.insert(event_id, Some(time));
+            }
+            None => {
+                log::trace!("event {} has no next occurrence", event_id);
+                self.next_events_date.remove(&event_id);
+                self.changes.insert(event_id, None);
             }
         }
     }
 
-    fn clear_event(&mut self, event_id: u32) {
-        let date = match self.saved_events_date.get(&event_id) {
-            Some(date) => date,
-            None => return,
+    /// Schedules the reminder before the first occurrence of the event whose reminder is due after
+    /// `after`, when the event has a reminder.
+    fn schedule_reminder(&mut self, event_id: u32, after: i64) {
+        let offset = match self
+            .saved_events
+            .get(&event_id)
+            .and_then(|event| event.reminder_minutes)
+        {
+            Some(minutes) => minutes as i64 * 60,
+            None => {
+                self.next_reminders_date.remove(&event_id);
+                return;
+            }
         };
-        for minute in date.find_minutes().into_iter() {
-            let events = match self.events_per_minute.get_mut(&minute) {
-                Some(events) => events,
-                None => continue,
-            };
-            if let Some(index) = events.iter().position(|&event| event == event_id) {
-                events.remove(index);
+        let next = self
+            .saved_events_date
+            .get(&event_id)
+            .and_then(|date| date.find_next(after + offset))
+            .map(|time| time - offset);
+        match next {
+            Some(time) => {
+                log::trace!("next reminder of event {}: {}", event_id, time);
+                self.next_reminders_date.insert(event_id, time);
+                self.reminders.push(Reverse((time, event_id)));
+            }
+            None => {
+                self.next_reminders_date.remove(&event_id);
+            }
+        }
+    }
+
+    /// Moves an occurrence within the quiet hours of the event's team to the end of them.
+    fn defer_quiet_hours(&self, event_id: u32, time: i64) -> i64 {
+        match self.find_quiet_hours(event_id) {
+            Some(quiet_hours) if quiet_hours.contains(time) => {
+                let deferred = quiet_hours.next_allowed(time);
+                log::trace!(
+                    "deferring occurrence of event {} in quiet hours from {} to {}",
+                    event_id,
+                    time,
+                    deferred
+                );
+                deferred
             }
+            _ => time,
+        }
+    }
+
+    fn is_quiet(&self, event_id: u32, time: i64) -> bool {
+        self.find_quiet_hours(event_id)
+            .is_some_and(|quiet_hours| quiet_hours.contains(time))
+    }
+
+    fn find_quiet_hours(&self, event_id: u32) -> Option<&QuietHours> {
+        self.saved_events
+            .get(&event_id)
+            .and_then(|event| self.team_quiet_hours.get(&event.team_id))
+    }
+
+    fn take_changes(&mut self) -> HashMap<u32, Option<i64>> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Drops the outdated occurrences from the queue once they outnumber the scheduled ones.
+    fn compact(&mut self) {
+        if self.queue.len() > 2 * self.next_events_date.len() {
+            self.queue = self
+                .next_events_date
+                .iter()
+                .map(|(&event_id, &time)| Reverse((time, event_id)))
+                .collect();
+        }
+        if self.reminders.len() > 2 * self.next_reminders_date.len() {
+            self.reminders = self
+                .next_reminders_date
+                .iter()
+                .map(|(&event_id, &time)| Reverse((time, event_id)))
+                .collect();
         }
     }
 }
@@ -148,69 +400,759 @@ impl Display for DateRecords {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "total_events={}, total_minutes={}",
+            "total_events={}, scheduled_events={}, next_occurrence={:?}",
             self.saved_events_date.len(),
-            self.events_per_minute.len()
+            self.next_events_date.len(),
+            self.next_events_date.values().min()
         )
     }
 }
 
 pub struct Scheduler {
+    instance_id: String,
+    is_leader: AtomicBool,
+    /// The minute of the latest run of the scheduler loop, or when it was created before the
+    /// first one.
+    last_tick: AtomicI64,
+    /// Set once the scheduler was filled up with the saved events, so it does not miss their picks.
+    synced: AtomicBool,
     pick_sender: Sender<Vec<pick_auto_participants::Pick>>,
+    reminder_sender: Sender<Vec<find_event_reminders::Reminder>>,
+    repick_sender: Sender<Vec<repick_unacknowledged::Repick>>,
+    recap_sender: Sender<Vec<find_monthly_recaps::Recap>>,
+    scheduler_repo: Arc<dyn scheduler::Repository>,
     mutex: Mutex<DateRecords>,
 }
 
 impl Scheduler {
-    pub fn new(pick_tx: Sender<Vec<pick_auto_participants::Pick>>) -> Self {
+    pub fn new(
+        pick_tx: Sender<Vec<pick_auto_participants::Pick>>,
+        reminder_tx: Sender<Vec<find_event_reminders::Reminder>>,
+        repick_tx: Sender<Vec<repick_unacknowledged::Repick>>,
+        recap_tx: Sender<Vec<find_monthly_recaps::Recap>>,
+        scheduler_repo: Arc<dyn scheduler::Repository>,
+    ) -> Self {
         Self {
+            instance_id: format!("{:016x}", rand::random::<u64>()),
+            is_leader: AtomicBool::new(false),
+            last_tick: AtomicI64::new(Date::now().timestamp()),
+            synced: AtomicBool::new(false),
             pick_sender: pick_tx,
+            reminder_sender: reminder_tx,
+            repick_sender: repick_tx,
+            recap_sender: recap_tx,
+            scheduler_repo,
             mutex: Mutex::new(DateRecords::new()),
         }
     }
 
+    /// Runs the scheduler, picking the participants of the events on their scheduled minutes and
+    /// reminding the channels ahead of them. The picks that were not acknowledged in time are
+    /// repicked, and the picks of each month are recapped once it is over.
+    ///
+    /// Every instance runs this loop, but only the one holding the leadership lease picks. The
+    /// leader syncs the events from the repository every minute, so it also schedules the events
+    /// changed through other instances.
+    #[allow(clippy::too_many_arguments)]
     pub async fn start(
         &self,
         event_repo: Arc<dyn event::Repository>,
         auth_repo: Arc<dyn auth::Repository>,
+        history_repo: Arc<dyn pick_history::Repository>,
+        holiday_repo: Arc<dyn holiday::Repository>,
+        settings_repo: Arc<dyn settings::Repository>,
+        acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+        user_group_repo: Arc<dyn user_group::Repository>,
+        user_status_repo: Arc<dyn user_status::Repository>,
+        calendar_repo: Arc<dyn calendar::Repository>,
+        audit_repo: Arc<dyn audit::Repository>,
+        configs: SchedulerConfigs,
     ) {
+        let mut is_leader = false;
         loop {
             helpers::sleep_until_next_minute();
 
-            let current_minute = helpers::find_current_minute();
-            let ending_minute = helpers::find_ending_minute();
-            for minute in current_minute..ending_minute {
-                {
-                    let records = self.mutex.lock().await;
-                    if minute % 20 == 0 {
-                        log::trace!("scheduler state: minute={}, {}", minute, records);
-                    }
-                    let picks = records
-                        .check(event_repo.clone(), auth_repo.clone(), minute)
-                        .await;
-                    if let Err(err) = self.pick_sender.send(picks).await {
-                        log::error!("failed to notify pick results: {}", err);
-                    }
-                    yield_now().await;
+            let minute = helpers::find_current_minute();
+            self.last_tick.store(minute, Ordering::Relaxed);
+            let was_leader = is_leader;
+            is_leader = self.acquire_leadership(configs.lease_seconds).await;
+            self.is_leader.store(is_leader, Ordering::Relaxed);
+            if is_leader != was_leader {
+                log::info!(
+                    "scheduler instance {} is leader: {}",
+                    self.instance_id,
+                    is_leader
+                );
+            }
+            if !is_leader {
+                // The instances not leading only sync to recover from a failed first sync.
+                if !self.is_synced() {
+                    self.sync(
+                        event_repo.clone(),
+                        auth_repo.clone(),
+                        holiday_repo.clone(),
+                        settings_repo.clone(),
+                    )
+                    .await;
                 }
-                helpers::sleep_until_next_minute();
+                continue;
             }
 
-            {
-                log::trace!("finished year round: inserting a new round of events");
+            self.sync(
+                event_repo.clone(),
+                auth_repo.clone(),
+                holiday_repo.clone(),
+                settings_repo.clone(),
+            )
+            .await;
+            // The occurrences scheduled while not leading are outdated, so schedule them again
+            // from the last run of any scheduler instance.
+            let first_minute = if was_leader {
+                None
+            } else {
+                Some(
+                    self.find_catch_up_minute(minute, configs.catch_up_minutes)
+                        .await,
+                )
+            };
+            // The due events are taken while holding the records, but picked and sent after
+            // releasing them, so that the handlers scheduling the events are not kept waiting.
+            let (reminders, events, changes) = {
                 let mut records = self.mutex.lock().await;
-                records.reset_minutes();
+                if let Some(first_minute) = first_minute {
+                    records.reschedule(first_minute - 1);
+                }
+                if minute % (20 * 60) == 0 {
+                    log::trace!("scheduler state: minute={}, {}", minute, records);
+                }
+                if minute % (60 * 60) == 0 {
+                    records.deactivate_ended(event_repo.clone()).await;
+                }
+                let reminders = records.take_due_reminders(minute);
+                let events = records.take_due(minute);
                 yield_now().await;
+                (reminders, events, records.take_changes())
+            };
+            self.remind_due(event_repo.clone(), auth_repo.clone(), reminders)
+                .await;
+            self.pick_due(
+                event_repo.clone(),
+                auth_repo.clone(),
+                history_repo.clone(),
+                user_group_repo.clone(),
+                user_status_repo.clone(),
+                calendar_repo.clone(),
+                events,
+            )
+            .await;
+            self.save_changes(changes).await;
+            self.repick_unacknowledged(
+                acknowledgment_repo.clone(),
+                event_repo.clone(),
+                auth_repo.clone(),
+                history_repo.clone(),
+                audit_repo.clone(),
+                minute,
+            )
+            .await;
+            if let Some(month) = find_monthly_recaps::month_ended_at(minute) {
+                self.recap_month(
+                    settings_repo.clone(),
+                    event_repo.clone(),
+                    history_repo.clone(),
+                    audit_repo.clone(),
+                    month,
+                )
+                .await;
+            }
+            self.save_last_run(minute).await;
+
+            if minute % (24 * 60 * 60) == 0 {
+                self.purge_deleted(event_repo.clone(), configs.deleted_events_retention_days)
+                    .await;
+            }
+        }
+    }
+
+    /// Picks the participants of the due events, syncing their user groups first, and sends the
+    /// picks.
+    ///
+    /// It runs without holding the records, as it goes to Slack and to the calendars of the
+    /// participants.
+    #[allow(clippy::too_many_arguments)]
+    async fn pick_due(
+        &self,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        history_repo: Arc<dyn pick_history::Repository>,
+        user_group_repo: Arc<dyn user_group::Repository>,
+        user_status_repo: Arc<dyn user_status::Repository>,
+        calendar_repo: Arc<dyn calendar::Repository>,
+        events: Vec<u32>,
+    ) {
+        let picks = if events.is_empty() {
+            vec![]
+        } else {
+            self.sync_user_groups(
+                event_repo.clone(),
+                auth_repo.clone(),
+                user_group_repo,
+                &events,
+            )
+            .await;
+            self.pick_for_events(
+                event_repo,
+                auth_repo,
+                history_repo,
+                user_status_repo,
+                calendar_repo,
+                &events,
+            )
+            .await
+            .map(|response| response.picks.into_values().collect())
+            .unwrap_or_default()
+        };
+        if let Err(err) = self.pick_sender.send(picks).await {
+            reporter::report(Report::new(
+                "scheduler",
+                format!("failed to notify pick results: {}", err),
+            ));
+        }
+    }
+
+    /// Finds the reminders of the events with reminders due and sends them.
+    async fn remind_due(
+        &self,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        events: Vec<u32>,
+    ) {
+        if events.is_empty() {
+            return;
+        }
+        let req = find_event_reminders::Request {
+            events: events.clone(),
+        };
+        let reminders = match find_event_reminders::execute(event_repo, auth_repo, req).await {
+            Ok(res) => {
+                log::trace!("reminding events {:?}: {:?}", events, res);
+                res.reminders
             }
+            Err(err) => {
+                log::error!("could not find the reminders of events: {:?}", err);
+                return;
+            }
+        };
+        if reminders.is_empty() {
+            return;
+        }
+        if let Err(err) = self.reminder_sender.send(reminders).await {
+            reporter::report(Report::new(
+                "scheduler",
+                format!("failed to notify reminders: {}", err),
+            ));
         }
     }
 
+    async fn pick_for_events(
+        &self,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        history_repo: Arc<dyn pick_history::Repository>,
+        user_status_repo: Arc<dyn user_status::Repository>,
+        calendar_repo: Arc<dyn calendar::Repository>,
+        events: &Vec<u32>,
+    ) -> Option<pick_auto_participants::Response> {
+        let req = pick_auto_participants::Request {
+            events: events.clone(),
+        };
+        let res = match pick_auto_participants::execute(
+            event_repo.clone(),
+            auth_repo,
+            history_repo,
+            user_status_repo,
+            calendar_repo,
+            req,
+        )
+        .await
+        {
+            Ok(res) => res,
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not automatically pick participants: {:?}", err),
+                ));
+                return None;
+            }
+        };
+        for failure in res.failures.iter() {
+            reporter::report(
+                Report::new(
+                    "scheduler",
+                    format!("could not pick automatically: {}", failure.reason),
+                )
+                .with_team(failure.team_id.clone())
+                .with_channel(failure.channel_id.clone())
+                .with_event(failure.event_id),
+            );
+        }
+        log::trace!(
+            "automatically picked participants for events {:?}: {:?}",
+            events,
+            res
+        );
+        Some(res)
+    }
+
+    /// Syncs the participants of the events with their user groups, so that the members who joined
+    /// or left the groups are handled by the picks.
+    async fn sync_user_groups(
+        &self,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        user_group_repo: Arc<dyn user_group::Repository>,
+        events: &[u32],
+    ) {
+        let req = sync_group::Request {
+            events: events.to_vec(),
+        };
+        match sync_group::execute(event_repo, auth_repo, user_group_repo, req).await {
+            Ok(res) if !res.synced.is_empty() => {
+                log::trace!("synced participants of events {:?}", res.synced)
+            }
+            Ok(..) => (),
+            Err(err) => log::error!("could not sync the user groups of events: {:?}", err),
+        }
+    }
+
+    /// Repicks the picks not acknowledged by `minute`, sending the repicks to update their
+    /// messages. The repicks are recorded on the audit log, to recap how many picks were missed.
+    async fn repick_unacknowledged(
+        &self,
+        acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        history_repo: Arc<dyn pick_history::Repository>,
+        audit_repo: Arc<dyn audit::Repository>,
+        minute: i64,
+    ) {
+        let req = repick_unacknowledged::Request { timestamp: minute };
+        let repicks = match repick_unacknowledged::execute(
+            acknowledgment_repo,
+            event_repo,
+            auth_repo,
+            history_repo,
+            req,
+        )
+        .await
+        {
+            Ok(res) => res.repicks,
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not repick the unacknowledged picks: {:?}", err),
+                ));
+                return;
+            }
+        };
+        if repicks.is_empty() {
+            return;
+        }
+        log::trace!("repicked unacknowledged picks: {:?}", repicks);
+        for repick in repicks.iter() {
+            let req = save_record::Request {
+                team_id: repick.team_id.clone(),
+                channel: Some(repick.channel_id.clone()),
+                user: None,
+                action: AuditAction::AcknowledgmentExpired,
+                event: Some(repick.event_id),
+            };
+            if let Err(err) = save_record::execute(audit_repo.clone(), req).await {
+                log::error!(
+                    "could not record the repick of event {}: {:?}",
+                    repick.event_id,
+                    err
+                );
+            }
+        }
+        if let Err(err) = self.repick_sender.send(repicks).await {
+            reporter::report(Report::new(
+                "scheduler",
+                format!("failed to notify repicks: {}", err),
+            ));
+        }
+    }
+
+    /// Recaps the picks of the month on the channels of the teams that enabled it, sending the
+    /// recaps to be posted.
+    async fn recap_month(
+        &self,
+        settings_repo: Arc<dyn settings::Repository>,
+        event_repo: Arc<dyn event::Repository>,
+        history_repo: Arc<dyn pick_history::Repository>,
+        audit_repo: Arc<dyn audit::Repository>,
+        month: NaiveDate,
+    ) {
+        let req = find_monthly_recaps::Request { month };
+        let recaps = match find_monthly_recaps::execute(
+            settings_repo,
+            event_repo,
+            history_repo,
+            audit_repo,
+            req,
+        )
+        .await
+        {
+            Ok(res) => res.recaps,
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not recap the month {}: {:?}", month, err),
+                ));
+                return;
+            }
+        };
+        log::info!("recapped the month {} on {} channels", month, recaps.len());
+        if recaps.is_empty() {
+            return;
+        }
+        if let Err(err) = self.recap_sender.send(recaps).await {
+            reporter::report(Report::new(
+                "scheduler",
+                format!("failed to notify recaps: {}", err),
+            ));
+        }
+    }
+
+    /// Removes for good the events deleted longer ago than the retention period.
+    async fn purge_deleted(&self, event_repo: Arc<dyn event::Repository>, retention_days: u32) {
+        if retention_days == 0 {
+            return;
+        }
+        let request = purge_deleted_events::Request { retention_days };
+        match purge_deleted_events::execute(event_repo, request).await {
+            Ok(res) => log::info!(
+                "purged {} events deleted more than {} days ago",
+                res.count,
+                retention_days
+            ),
+            Err(err) => log::error!("could not purge deleted events: {:?}", err),
+        }
+    }
+
+    /// Fills up the scheduler with the events, holidays and settings saved in the repositories.
+    pub async fn sync(
+        &self,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        holiday_repo: Arc<dyn holiday::Repository>,
+        settings_repo: Arc<dyn settings::Repository>,
+    ) {
+        let holidays = match find_all_holidays::execute(holiday_repo).await {
+            Ok(holidays) => holidays.data,
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not fetch holidays for scheduling: {:?}", err),
+                ));
+                return;
+            }
+        };
+        let settings = match find_all_settings::execute(settings_repo).await {
+            Ok(settings) => settings.data,
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not fetch settings for scheduling: {:?}", err),
+                ));
+                return;
+            }
+        };
+        let events = match find_all_events_and_dates::execute(event_repo).await {
+            Ok(events) => events.data,
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not fetch events for scheduling: {:?}", err),
+                ));
+                return;
+            }
+        };
+        // The events of the teams that uninstalled the application are not scheduled anymore.
+        let teams: HashSet<String> = events.iter().map(|event| event.team_id.clone()).collect();
+        let installed: HashSet<String> = match auth_repo
+            .find_all_by_team(teams.into_iter().collect())
+            .await
+        {
+            Ok(auths) => auths.iter().flat_map(|auth| auth.team_ids()).collect(),
+            Err(err) => {
+                reporter::report(Report::new(
+                    "scheduler",
+                    format!("could not fetch tokens for scheduling: {:?}", err),
+                ));
+                return;
+            }
+        };
+
+        let mut team_holidays: HashMap<String, Vec<NaiveDate>> = HashMap::new();
+        for holiday in holidays.into_iter() {
+            if let Some(date) = holiday.naive_date() {
+                team_holidays.entry(holiday.team_id).or_default().push(date);
+            }
+        }
+        let team_quiet_hours: HashMap<String, QuietHours> = settings
+            .into_iter()
+            .filter_map(|settings| {
+                settings
+                    .quiet_hours
+                    .map(|quiet_hours| (settings.team_id, quiet_hours))
+            })
+            .collect();
+        let events = events
+            .into_iter()
+            .filter(|event| installed.contains(&event.team_id))
+            .map(|event| EventSchedule {
+                id: event.id,
+                team_id: event.team_id,
+                timestamp: event.timestamp,
+                timezone: event.timezone,
+                repeat: event.repeat,
+                ends_at: event.ends_at,
+                reminder_minutes: event.reminder_minutes,
+                skip_weekends: event.skip_weekends,
+                skipped_dates: event.skipped_dates,
+            })
+            .collect();
+
+        let changes = {
+            let mut records = self.mutex.lock().await;
+            records.sync(events, team_holidays, team_quiet_hours);
+            records.take_changes()
+        };
+        self.save_changes(changes).await;
+        self.synced.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the scheduler was filled up with the saved events at least once.
+    pub fn is_synced(&self) -> bool {
+        self.synced.load(Ordering::Relaxed)
+    }
+
+    async fn acquire_leadership(&self, lease_seconds: i64) -> bool {
+        let req = acquire_leadership::Request {
+            owner: self.instance_id.clone(),
+            lease_seconds,
+        };
+        match acquire_leadership::execute(self.scheduler_repo.clone(), req).await {
+            Ok(is_leader) => is_leader,
+            Err(err) => {
+                log::error!("could not acquire the scheduler leadership: {:?}", err);
+                false
+            }
+        }
+    }
+
+    /// Finds the timestamp of the first minute to pick for, catching up with the minutes missed
+    /// since the last run of any scheduler instance, up to `window` minutes ago.
+    async fn find_catch_up_minute(&self, current_minute: i64, window: i64) -> i64 {
+        let last_run = match find_last_run::execute(self.scheduler_repo.clone()).await {
+            Ok(last_run) => last_run,
+            Err(find_last_run::Error::NotFound) => return current_minute,
+            Err(err) => {
+                log::error!("could not find the scheduler last run: {:?}", err);
+                return current_minute;
+            }
+        };
+
+        let first_minute = (last_run + 60)
+            .max(current_minute - window * 60)
+            .min(current_minute);
+        if first_minute < current_minute {
+            log::info!(
+                "catching up scheduler from minute {} to {}",
+                first_minute,
+                current_minute
+            );
+        }
+        first_minute
+    }
+
+    async fn save_last_run(&self, minute: i64) {
+        let req = save_last_run::Request { timestamp: minute };
+        if let Err(err) = save_last_run::execute(self.scheduler_repo.clone(), req).await {
+            log::error!("could not save the scheduler last run: {:?}", err);
+        }
+    }
+
+    /// Persists the changed next occurrences, so they can be reported without the scheduler.
+    ///
+    /// Only the leader persists them, as the other instances do not keep their occurrences up to
+    /// date.
+    async fn save_changes(&self, changes: HashMap<u32, Option<i64>>) {
+        if !self.is_leader.load(Ordering::Relaxed) {
+            return;
+        }
+        for (event, next_at) in changes.into_iter() {
+            let req = save_next_occurrence::Request { event, next_at };
+            if let Err(err) = save_next_occurrence::execute(self.scheduler_repo.clone(), req).await
+            {
+                log::error!(
+                    "could not save the next occurrence of event {}: {:?}",
+                    event,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Whether this instance holds the leadership lease, so it is the one picking.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Relaxed)
+    }
+
+    /// The timestamp (in seconds) of the latest minute processed by the scheduler loop of this
+    /// instance.
+    pub fn last_tick(&self) -> i64 {
+        self.last_tick.load(Ordering::Relaxed)
+    }
+
+    /// The number of automatic picks batches waiting to be posted.
+    pub fn picks_backlog(&self) -> usize {
+        self.pick_sender.max_capacity() - self.pick_sender.capacity()
+    }
+
     pub async fn insert(&self, event: EventSchedule) {
-        let mut records = self.mutex.lock().await;
-        records.insert(event);
+        let changes = {
+            let mut records = self.mutex.lock().await;
+            records.insert(event);
+            records.take_changes()
+        };
+        self.save_changes(changes).await;
     }
 
     pub async fn remove(&self, event_id: u32) {
-        let mut records = self.mutex.lock().await;
-        records.remove(event_id);
+        let changes = {
+            let mut records = self.mutex.lock().await;
+            records.remove(event_id);
+            records.take_changes()
+        };
+        self.save_changes(changes).await;
+    }
+
+    pub async fn set_holidays(&self, team_id: String, holidays: Vec<NaiveDate>) {
+        let changes = {
+            let mut records = self.mutex.lock().await;
+            records.set_holidays(team_id, holidays);
+            records.take_changes()
+        };
+        self.save_changes(changes).await;
+    }
+
+    pub async fn set_quiet_hours(&self, team_id: String, quiet_hours: Option<QuietHours>) {
+        let changes = {
+            let mut records = self.mutex.lock().await;
+            records.set_quiet_hours(team_id, quiet_hours);
+            records.take_changes()
+        };
+        self.save_changes(changes).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::channel;
+
+    use crate::{
+        domain::{entities::RepeatPeriod, timezone::Timezone},
+        repository::scheduler::MemoryRepository,
+    };
+
+    use super::*;
+
+    fn scheduler(repo: Arc<MemoryRepository>) -> Scheduler {
+        Scheduler::new(channel(1).0, channel(1).0, channel(1).0, channel(1).0, repo)
+    }
+
+    #[test]
+    fn test_take_due() {
+        let mut records = DateRecords::new();
+        let timestamp = records.last_checked + 2 * 60;
+        records.insert(EventSchedule {
+            id: 1,
+            team_id: String::from("T1"),
+            timestamp,
+            timezone: Timezone::UTC,
+            repeat: RepeatPeriod::Daily,
+            ends_at: None,
+            reminder_minutes: Some(1),
+            skip_weekends: false,
+            skipped_dates: vec![],
+        });
+
+        assert_eq!(
+            records.take_due_reminders(timestamp - 2 * 60),
+            Vec::<u32>::new()
+        );
+        assert_eq!(records.take_due_reminders(timestamp - 60), vec![1]);
+        assert_eq!(records.take_due(timestamp - 60), Vec::<u32>::new());
+        assert_eq!(records.take_due(timestamp), vec![1]);
+        // The following occurrence is scheduled when taken.
+        assert_eq!(
+            records.next_events_date.get(&1),
+            Some(&(timestamp + 24 * 60 * 60))
+        );
+        assert_eq!(records.take_due(timestamp + 60), Vec::<u32>::new());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_leadership() {
+        let repo = Arc::new(MemoryRepository::default());
+        let first = scheduler(repo.clone());
+        let second = scheduler(repo.clone());
+
+        assert!(first.acquire_leadership(60).await);
+        // The lease is refused to the other instances while it is held.
+        assert!(!second.acquire_leadership(60).await);
+        // The leader renews its lease.
+        let (_, expires_at) = repo.lease.lock().unwrap().clone().unwrap();
+        *repo.lease.lock().unwrap() = Some((first.instance_id.clone(), expires_at - 30));
+        assert!(first.acquire_leadership(60).await);
+        assert!(repo.lease.lock().unwrap().clone().unwrap().1 >= expires_at);
+        assert!(!second.acquire_leadership(60).await);
+
+        // Another instance takes over once the lease expires.
+        *repo.lease.lock().unwrap() =
+            Some((first.instance_id.clone(), Date::now().timestamp() - 1));
+        assert!(second.acquire_leadership(60).await);
+        assert!(!first.acquire_leadership(60).await);
+    }
+
+    #[tokio::test]
+    async fn test_find_catch_up_minute() {
+        let repo = Arc::new(MemoryRepository::default());
+        let scheduler = scheduler(repo.clone());
+        // 2023-06-13 09:00 UTC.
+        let minute = 1686646800;
+
+        // Nothing is caught up without a last run.
+        assert_eq!(scheduler.find_catch_up_minute(minute, 30).await, minute);
+
+        // The minutes are caught up from the one after the last run.
+        *repo.last_run.lock().unwrap() = Some(minute - 5 * 60);
+        assert_eq!(
+            scheduler.find_catch_up_minute(minute, 30).await,
+            minute - 4 * 60
+        );
+
+        // Up to the window.
+        *repo.last_run.lock().unwrap() = Some(minute - 120 * 60);
+        assert_eq!(
+            scheduler.find_catch_up_minute(minute, 30).await,
+            minute - 30 * 60
+        );
+
+        // The current minute is never skipped, even when it already ran.
+        *repo.last_run.lock().unwrap() = Some(minute);
+        assert_eq!(scheduler.find_catch_up_minute(minute, 30).await, minute);
     }
 }
@@ -3,4 +3,5 @@ pub mod entities;
 mod executor;
 mod helpers;
 
+pub use date::SchedulerDate;
 pub use executor::Scheduler;
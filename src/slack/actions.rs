@@ -3,18 +3,40 @@ use std::{collections::HashMap, sync::Arc};
 use axum::extract::{Form, State};
 use hyper::HeaderMap;
 use serde::{Deserialize, Serialize};
-use serde_json::from_str;
+use serde_json::{from_str, json};
 
-use super::state::AppConfigs;
-use super::{templates, AppState};
+use super::sender::{self, PickAnnouncer};
+use super::{
+    activity,
+    client::{self, SlackClient},
+    templates, user_groups, AppState,
+};
+use crate::domain::acknowledgments::acknowledge_pick;
 use crate::domain::commands::cancel_pick;
-use crate::domain::entities::RepeatPeriod;
+use crate::domain::date_format::DateFormat;
+use crate::domain::entities::{
+    AuditAction, PickMode, RepeatPeriod, TeamSettings, Visibility, CRON_PREFIX, DAYS_PREFIX,
+    PART_TIME_WEIGHT,
+};
+use crate::domain::helpers::participant::{pick_new, rotation};
+use crate::domain::locale::Locale;
+use crate::domain::participants::move_participant;
 use crate::domain::timezone::Timezone;
 use crate::scheduler::{entities::EventSchedule, Scheduler};
 use crate::{
-    domain::commands::{pick_participant, repick_participant},
-    domain::events::{create_event, delete_event, find_event, update_event},
-    repository::event::Repository,
+    domain::commands::{self, pick_participant, repick_participant},
+    domain::events::{
+        clone_event, create_event, delete_event, find_event, mark_no_show, pause_event,
+        skip_next_occurrence, update_event, validation::FieldError, volunteer,
+    },
+    helpers::reporter::{self, Report},
+    i18n,
+    repository::{acknowledgment, audit, event::Repository, pick_history, scheduler},
+    views::{
+        channel_access, help, list_events, onboarding,
+        pick_participant::{self as pick_view, ACKNOWLEDGE_ACTION_ID},
+        rotation::{self as rotation_view, RotationView},
+    },
 };
 
 #[derive(Serialize, Deserialize)]
@@ -51,6 +73,8 @@ pub struct Action {
     block_id: Option<String>,
     value: Option<String>,
     selected_option: Option<SelectedOption>,
+    /// The channel chosen on the conversations selects.
+    selected_conversation: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -63,9 +87,25 @@ type FormStateValues = HashMap<String, FormStateValue>;
 #[derive(Deserialize, Debug, Clone)]
 pub struct FormStateValue {
     name_input: Option<InputText>,
+    description_input: Option<InputText>,
+    url_input: Option<InputText>,
     date_input: Option<DateTimePicker>,
+    ends_at_input: Option<DateTimePicker>,
     repeat_input: Option<RadioButton>,
+    cron_input: Option<InputText>,
+    days_input: Option<Checkboxes>,
     participants_input: Option<MultiUsersSelect>,
+    user_group_input: Option<InputText>,
+    part_time_input: Option<MultiUsersSelect>,
+    pick_mode_input: Option<RadioButton>,
+    no_repeat_input: Option<Checkboxes>,
+    reminder_input: Option<InputText>,
+    seed_input: Option<InputText>,
+    anchor_input: Option<Checkboxes>,
+    visibility_input: Option<Checkboxes>,
+    out_of_office_input: Option<Checkboxes>,
+    skip_weekends_input: Option<Checkboxes>,
+    notify_channels_input: Option<MultiChannelsSelect>,
     timezone_input: Option<StaticSelect>,
     select_event: Option<StaticSelect>,
 }
@@ -74,9 +114,25 @@ impl FormStateValue {
     fn new() -> FormStateValue {
         Self {
             name_input: None,
+            description_input: None,
+            url_input: None,
             date_input: None,
+            ends_at_input: None,
             repeat_input: None,
+            cron_input: None,
+            days_input: None,
             participants_input: None,
+            user_group_input: None,
+            part_time_input: None,
+            pick_mode_input: None,
+            no_repeat_input: None,
+            reminder_input: None,
+            seed_input: None,
+            anchor_input: None,
+            visibility_input: None,
+            out_of_office_input: None,
+            skip_weekends_input: None,
+            notify_channels_input: None,
             timezone_input: None,
             select_event: None,
         }
@@ -85,9 +141,28 @@ impl FormStateValue {
     fn merge(self, v: FormStateValue) -> FormStateValue {
         Self {
             name_input: merge_option(self.name_input, v.name_input),
+            description_input: merge_option(self.description_input, v.description_input),
+            url_input: merge_option(self.url_input, v.url_input),
             date_input: merge_option(self.date_input, v.date_input),
+            ends_at_input: merge_option(self.ends_at_input, v.ends_at_input),
             repeat_input: merge_option(self.repeat_input, v.repeat_input),
+            cron_input: merge_option(self.cron_input, v.cron_input),
+            days_input: merge_option(self.days_input, v.days_input),
             participants_input: merge_option(self.participants_input, v.participants_input),
+            user_group_input: merge_option(self.user_group_input, v.user_group_input),
+            part_time_input: merge_option(self.part_time_input, v.part_time_input),
+            pick_mode_input: merge_option(self.pick_mode_input, v.pick_mode_input),
+            no_repeat_input: merge_option(self.no_repeat_input, v.no_repeat_input),
+            reminder_input: merge_option(self.reminder_input, v.reminder_input),
+            seed_input: merge_option(self.seed_input, v.seed_input),
+            anchor_input: merge_option(self.anchor_input, v.anchor_input),
+            visibility_input: merge_option(self.visibility_input, v.visibility_input),
+            out_of_office_input: merge_option(self.out_of_office_input, v.out_of_office_input),
+            skip_weekends_input: merge_option(self.skip_weekends_input, v.skip_weekends_input),
+            notify_channels_input: merge_option(
+                self.notify_channels_input,
+                v.notify_channels_input,
+            ),
             timezone_input: merge_option(self.timezone_input, v.timezone_input),
             select_event: merge_option(self.select_event, v.select_event),
         }
@@ -124,6 +199,20 @@ pub struct RadioButton {
     selected_option: Option<SelectedOption>,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct Checkboxes {
+    selected_options: Vec<SelectedOption>,
+}
+
+impl Checkboxes {
+    fn values(self) -> Vec<String> {
+        self.selected_options
+            .into_iter()
+            .filter_map(|option| option.value)
+            .collect()
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct SelectedOption {
     value: Option<String>,
@@ -134,6 +223,11 @@ pub struct MultiUsersSelect {
     selected_users: Vec<String>,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct MultiChannelsSelect {
+    selected_channels: Vec<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct StaticSelect {
     selected_option: Option<SelectedOption>,
@@ -146,69 +240,197 @@ pub struct CommandActionResponse {
     text: String,
 }
 
+/// An invalid field of the event forms, told to the user so that they can fix it.
+#[derive(Debug, PartialEq)]
+struct FormError {
+    /// The key of the message telling the user what to fix.
+    key: &'static str,
+    /// The values filled in the message.
+    args: Vec<(&'static str, String)>,
+    detail: String,
+}
+
+impl FormError {
+    fn new(key: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            key,
+            args: vec![],
+            detail: detail.into(),
+        }
+    }
+
+    fn with_arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+}
+
+/// The errors of the form that the user cannot fix (e.g. a missing input), which are still told.
+impl From<&str> for FormError {
+    fn from(value: &str) -> Self {
+        Self::new("form_error_invalid", value)
+    }
+}
+
+impl From<String> for FormError {
+    fn from(value: String) -> Self {
+        Self::new("form_error_invalid", value)
+    }
+}
+
+/// Tells the first of the fields the event was rejected for.
+impl From<Vec<FieldError>> for FormError {
+    fn from(value: Vec<FieldError>) -> Self {
+        let detail = format!("{:?}", value);
+        let key = match value.first().map(|err| err.field) {
+            Some("name") => "form_error_name_length",
+            Some("timestamp") => "form_error_date_past",
+            Some("participants") => "form_error_participants",
+            Some("ends_at") => "form_error_ends_at",
+            Some("reminder_minutes") => "form_error_reminder",
+            Some("description") => "form_error_description",
+            Some("url") => "form_error_url",
+            _ => "form_error_invalid",
+        };
+        Self::new(key, detail)
+    }
+}
+
 #[derive(Clone)]
 struct AddEventData {
     channel: String,
     team_id: String,
+    user: String,
     form: FormStateValue,
+    timezone: Timezone,
     max_events: u32,
+    max_participants: u32,
 }
 
 impl AddEventData {
-    fn new(value: CommandAction, max_events: u32) -> Self {
+    fn new(value: CommandAction, max_events: u32, max_participants: u32) -> Self {
         Self {
             channel: value.channel.id,
             team_id: value.user.team_id,
+            user: value.user.id,
             form: value.state.into(),
+            timezone: Timezone::UTC,
             max_events,
+            max_participants,
         }
     }
+
+    fn has_timezone(&self) -> bool {
+        self.form
+            .timezone_input
+            .as_ref()
+            .is_some_and(|d| d.selected_option.is_some())
+    }
 }
 
 impl TryFrom<AddEventData> for create_event::Request {
-    type Error = String;
+    type Error = FormError;
 
     fn try_from(data: AddEventData) -> Result<Self, Self::Error> {
         let participants = data
             .form
             .participants_input
-            .ok_or("no participants input")?
-            .selected_users;
-        if participants.len() == 0 {
-            return Err(String::from("participants is empty"));
+            .map_or(vec![], |d| d.selected_users);
+        // The handle of the user group is resolved into its id and members when creating the event.
+        let user_group = data
+            .form
+            .user_group_input
+            .and_then(|d| d.value)
+            .map(|handle| handle.trim().to_string())
+            .filter(|handle| !handle.is_empty());
+        if participants.is_empty() && user_group.is_none() {
+            return Err(FormError::new(
+                "form_error_participants",
+                "participants is empty",
+            ));
         }
+        let weights = part_time_weights(
+            data.form
+                .part_time_input
+                .map_or(vec![], |d| d.selected_users),
+        );
         Ok(create_event::Request {
             max_events: data.max_events,
+            max_participants: data.max_participants,
             channel: data.channel,
             team_id: data.team_id,
+            created_by: Some(data.user),
             name: data
                 .form
                 .name_input
                 .ok_or("no name input")?
                 .value
-                .ok_or("no name value")?,
+                .filter(|name| !name.trim().is_empty())
+                .ok_or(FormError::new("form_error_name", "no name value"))?,
+            description: data.form.description_input.and_then(|d| d.value),
+            url: data.form.url_input.and_then(|d| d.value),
             timestamp: data
                 .form
                 .date_input
                 .ok_or("no date input")?
                 .selected_date_time
-                .ok_or("no date value")?,
+                .ok_or(FormError::new("form_error_date", "no date value"))?,
             timezone: data
                 .form
                 .timezone_input
                 .and_then(|d| d.selected_option)
                 .and_then(|d| d.value)
-                .unwrap_or(Timezone::UTC.into()),
-            repeat: match data.form.repeat_input {
-                Some(input) => input
-                    .clone()
-                    .selected_option
-                    .ok_or("no repeat option")?
-                    .value
-                    .ok_or("no repeat value")?,
-                None => String::try_from(RepeatPeriod::None)?,
-            },
+                .unwrap_or(data.timezone.into()),
+            repeat: repeat_value(
+                match data.form.repeat_input {
+                    Some(input) => input
+                        .clone()
+                        .selected_option
+                        .ok_or("no repeat option")?
+                        .value
+                        .ok_or("no repeat value")?,
+                    None => String::try_from(RepeatPeriod::None)?,
+                },
+                data.form.cron_input.and_then(|d| d.value),
+                data.form.days_input.map_or(vec![], |d| d.values()),
+            )?,
+            pick_mode: data
+                .form
+                .pick_mode_input
+                .and_then(|d| d.selected_option)
+                .and_then(|d| d.value),
+            no_repeat_consecutive: data
+                .form
+                .no_repeat_input
+                .is_some_and(|d| !d.selected_options.is_empty()),
+            ends_at: data.form.ends_at_input.and_then(|d| d.selected_date_time),
+            reminder_minutes: reminder_value(data.form.reminder_input)?,
+            seed: seed_value(data.form.seed_input)?,
+            anchor: data
+                .form
+                .anchor_input
+                .is_some_and(|d| !d.selected_options.is_empty()),
+            visibility: Some(
+                data.form
+                    .visibility_input
+                    .map_or(Visibility::Channel, visibility_value)
+                    .into(),
+            ),
+            skip_out_of_office: data
+                .form
+                .out_of_office_input
+                .is_some_and(|d| !d.selected_options.is_empty()),
+            notify_channels: data
+                .form
+                .notify_channels_input
+                .map_or(vec![], |d| d.selected_channels),
+            skip_weekends: data
+                .form
+                .skip_weekends_input
+                .map(|d| !d.selected_options.is_empty()),
+            user_group,
             participants,
+            weights,
         })
     }
 }
@@ -221,6 +443,19 @@ struct UpdateEventDetails {
     timezone: Timezone,
     repeat: RepeatPeriod,
     participants: Vec<String>,
+    part_timers: Vec<String>,
+    pick_mode: PickMode,
+    no_repeat_consecutive: bool,
+    ends_at: Option<i64>,
+    reminder_minutes: Option<u32>,
+    anchor: bool,
+    notify_channels: Vec<String>,
+    skip_weekends: bool,
+    seed: Option<u32>,
+    description: Option<String>,
+    url: Option<String>,
+    visibility: Visibility,
+    skip_out_of_office: bool,
 }
 
 impl From<find_event::Response> for UpdateEventDetails {
@@ -231,6 +466,24 @@ impl From<find_event::Response> for UpdateEventDetails {
             timestamp: value.timestamp,
             timezone: value.timezone,
             repeat: value.repeat,
+            pick_mode: value.pick_mode,
+            no_repeat_consecutive: value.no_repeat_consecutive,
+            ends_at: value.ends_at,
+            reminder_minutes: value.reminder_minutes,
+            anchor: value.anchor,
+            notify_channels: value.notify_channels,
+            skip_weekends: value.skip_weekends,
+            seed: value.seed,
+            description: value.description,
+            url: value.url,
+            visibility: value.visibility,
+            skip_out_of_office: value.skip_out_of_office,
+            part_timers: value
+                .participants
+                .iter()
+                .filter(|p| p.is_part_time())
+                .map(|p| p.user.clone())
+                .collect(),
             participants: value.participants.into_iter().map(|p| p.user).collect(),
         }
     }
@@ -241,20 +494,22 @@ struct UpdateEventData {
     event: UpdateEventDetails,
     channel: String,
     form: FormStateValue,
+    max_participants: u32,
 }
 
 impl UpdateEventData {
-    fn new(event: UpdateEventDetails, value: CommandAction) -> Self {
+    fn new(event: UpdateEventDetails, value: CommandAction, max_participants: u32) -> Self {
         Self {
             event,
             channel: value.channel.id,
             form: value.state.into(),
+            max_participants,
         }
     }
 }
 
 impl TryFrom<UpdateEventData> for update_event::Request {
-    type Error = String;
+    type Error = FormError;
 
     fn try_from(data: UpdateEventData) -> Result<Self, Self::Error> {
         let participants = data
@@ -262,17 +517,31 @@ impl TryFrom<UpdateEventData> for update_event::Request {
             .participants_input
             .map_or(data.event.participants, |d| d.selected_users);
         if participants.len() == 0 {
-            return Err(String::from("participants is empty"));
+            return Err(FormError::new(
+                "form_error_participants",
+                "participants is empty",
+            ));
         }
+        let weights = part_time_weights(
+            data.form
+                .part_time_input
+                .map_or(data.event.part_timers, |d| d.selected_users),
+        );
 
         Ok(update_event::Request {
             id: data.event.id,
             channel: data.channel,
+            max_participants: data.max_participants,
             name: data
                 .form
                 .name_input
                 .and_then(|d| d.value)
                 .unwrap_or(data.event.name),
+            description: data
+                .form
+                .description_input
+                .map_or(data.event.description, |d| d.value),
+            url: data.form.url_input.map_or(data.event.url, |d| d.value),
             timestamp: data
                 .form
                 .date_input
@@ -284,17 +553,142 @@ impl TryFrom<UpdateEventData> for update_event::Request {
                 .and_then(|d| d.selected_option)
                 .and_then(|d| d.value)
                 .unwrap_or(data.event.timezone.into()),
-            repeat: data
+            repeat: match data
                 .form
                 .repeat_input
                 .and_then(|d| d.selected_option)
                 .and_then(|d| d.value)
-                .unwrap_or(String::try_from(data.event.repeat).unwrap_or(String::from("none"))),
+            {
+                Some(repeat) => repeat_value(
+                    repeat,
+                    data.form.cron_input.and_then(|d| d.value),
+                    data.form.days_input.map_or(vec![], |d| d.values()),
+                )?,
+                None => String::try_from(data.event.repeat).unwrap_or(String::from("none")),
+            },
+            pick_mode: Some(
+                data.form
+                    .pick_mode_input
+                    .and_then(|d| d.selected_option)
+                    .and_then(|d| d.value)
+                    .unwrap_or(data.event.pick_mode.into()),
+            ),
+            no_repeat_consecutive: Some(
+                data.form
+                    .no_repeat_input
+                    .map_or(data.event.no_repeat_consecutive, |d| {
+                        !d.selected_options.is_empty()
+                    }),
+            ),
+            ends_at: data
+                .form
+                .ends_at_input
+                .map_or(data.event.ends_at, |d| d.selected_date_time),
+            reminder_minutes: match data.form.reminder_input {
+                Some(input) => reminder_value(Some(input))?,
+                None => data.event.reminder_minutes,
+            },
+            seed: match data.form.seed_input {
+                Some(input) => seed_value(Some(input))?,
+                None => data.event.seed,
+            },
+            anchor: Some(
+                data.form
+                    .anchor_input
+                    .map_or(data.event.anchor, |d| !d.selected_options.is_empty()),
+            ),
+            visibility: Some(
+                data.form
+                    .visibility_input
+                    .map_or(data.event.visibility, visibility_value)
+                    .into(),
+            ),
+            skip_out_of_office: Some(
+                data.form
+                    .out_of_office_input
+                    .map_or(data.event.skip_out_of_office, |d| {
+                        !d.selected_options.is_empty()
+                    }),
+            ),
+            notify_channels: Some(
+                data.form
+                    .notify_channels_input
+                    .map_or(data.event.notify_channels, |d| d.selected_channels),
+            ),
+            skip_weekends: Some(
+                data.form
+                    .skip_weekends_input
+                    .map_or(data.event.skip_weekends, |d| !d.selected_options.is_empty()),
+            ),
             participants,
+            weights,
         })
     }
 }
 
+/// Combines the selected frequency with the cron expression or the weekdays inputs, when the
+/// custom or the specific weekdays frequencies are selected.
+fn repeat_value(
+    repeat: String,
+    cron: Option<String>,
+    days: Vec<String>,
+) -> Result<String, FormError> {
+    match repeat.as_str() {
+        "cron" => match cron {
+            Some(expression) if !expression.trim().is_empty() => {
+                Ok(format!("{}{}", CRON_PREFIX, expression.trim()))
+            }
+            _ => Err(FormError::new("form_error_cron", "no cron expression")),
+        },
+        "days" if days.is_empty() => Err(FormError::new("form_error_days", "no weekdays selected")),
+        "days" => Ok(format!("{}{}", DAYS_PREFIX, days.join(","))),
+        _ => Ok(repeat),
+    }
+}
+
+/// Parses the minutes before the pick to remind the channel, when the reminder input is filled.
+fn reminder_value(input: Option<InputText>) -> Result<Option<u32>, FormError> {
+    match input.and_then(|d| d.value) {
+        Some(value) if !value.trim().is_empty() => {
+            value.trim().parse::<u32>().map(Some).map_err(|_| {
+                FormError::new(
+                    "form_error_reminder",
+                    format!("invalid reminder minutes {}", value),
+                )
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The picks are only sent by direct message when the private option is checked.
+fn visibility_value(input: Checkboxes) -> Visibility {
+    if input.selected_options.is_empty() {
+        Visibility::Channel
+    } else {
+        Visibility::Direct
+    }
+}
+
+/// Parses the seed of the random picks, when the seed input is filled.
+fn seed_value(input: Option<InputText>) -> Result<Option<u32>, FormError> {
+    match input.and_then(|d| d.value) {
+        Some(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse::<u32>()
+            .map(Some)
+            .map_err(|_| FormError::new("form_error_seed", format!("invalid seed {}", value))),
+        _ => Ok(None),
+    }
+}
+
+fn part_time_weights(part_timers: Vec<String>) -> HashMap<String, f64> {
+    part_timers
+        .into_iter()
+        .map(|user| (user, PART_TIME_WEIGHT))
+        .collect()
+}
+
 struct SelectEventData {
     id: u32,
 }
@@ -321,30 +715,162 @@ pub async fn execute(
     State(state): State<Arc<AppState>>,
     Form(payload): Form<CommandActionBody>,
 ) -> Result<(), hyper::StatusCode> {
-    let body = serde_urlencoded::to_string(&payload).unwrap();
-    log::trace!(
-        "received action: \n{:?} \n{:?}",
-        headers,
-        from_str(&body).unwrap_or(body)
-    );
-
-    // let token = super::find_token(&headers)?;
+    let token = super::find_token(&headers)?;
 
     let payload: CommandAction = from_str(&payload.payload).unwrap();
 
+    let span = tracing::Span::current();
+    span.record("team_id", payload.user.team_id.as_str());
+    span.record("channel", payload.channel.id.as_str());
+    tracing::trace!(
+        request_type = payload.request_type.as_str(),
+        user_id = payload.user.id.as_str(),
+        actions = ?payload
+            .actions
+            .iter()
+            .filter_map(|action| action.action_id.as_deref())
+            .collect::<Vec<&str>>(),
+        "received action"
+    );
+
     if payload.request_type != "block_actions" {
         log::trace!("unknown action type: {}", payload.request_type);
         return Ok(());
     }
 
+    // Slack shows a timeout for the actions not acknowledged within 3 seconds, so they are run on
+    // the background, replying through their response URL.
+    let report = Report::new("actions", "failed to execute action")
+        .with_team(payload.user.team_id.clone())
+        .with_channel(payload.channel.id.clone());
+    let response_url = payload.response_url.clone();
+    let locale = super::find_locale(&headers);
+    super::spawn_with_reply(response_url, locale, async move {
+        let result = handle_actions(headers, state, token, payload).await;
+        if result == Err(hyper::StatusCode::INTERNAL_SERVER_ERROR) {
+            reporter::report(report);
+        }
+        result
+    });
+    Ok(())
+}
+
+async fn handle_actions(
+    headers: HeaderMap,
+    state: Arc<AppState>,
+    token: String,
+    payload: CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    // The picks are posted with the message of the team, so its whole settings are needed.
+    let settings = sender::find_settings(state.settings_repo.clone(), &payload.user.team_id).await;
+    let locale = settings.locale;
+    let announcer = PickAnnouncer::new(&state, settings.team_id.clone(), token.clone(), locale);
+
     for action in payload.actions.iter() {
         if let Some(action_id) = action.action_id.as_deref() {
+            if action_id == ACKNOWLEDGE_ACTION_ID {
+                return handle_acknowledge_event(
+                    state.acknowledgment_repo.clone(),
+                    state.audit_repo.clone(),
+                    locale,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
+            if action_id == channel_access::REJOIN_ACTION_ID {
+                return handle_rejoin_channel(
+                    state.slack_client.clone(),
+                    &token,
+                    locale,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
+            if action_id == channel_access::PAUSE_ACTION_ID {
+                return handle_pause_event(
+                    state.event_repo.clone(),
+                    state.audit_repo.clone(),
+                    state.scheduler.clone(),
+                    locale,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
+            if action_id == onboarding::TOUR_ACTION_ID {
+                return handle_onboarding_tour(locale, &payload).await;
+            }
+            if action_id == onboarding::CHANNEL_ACTION_ID {
+                return handle_onboarding_channel(
+                    state.slack_client.clone(),
+                    &token,
+                    locale,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
+            if action_id == pick_view::NO_SHOW_ACTION_ID {
+                return handle_no_show(
+                    state.event_repo.clone(),
+                    state.history_repo.clone(),
+                    state.audit_repo.clone(),
+                    &settings,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
+            if action_id == pick_view::VOLUNTEER_ACTION_ID {
+                return handle_volunteer(
+                    state.event_repo.clone(),
+                    state.history_repo.clone(),
+                    state.audit_repo.clone(),
+                    &announcer,
+                    &settings,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
             if action_id.starts_with("pick_participant_actions:") {
-                return handle_pick_participant_event(state.event_repo.clone(), action, &payload)
-                    .await;
+                return handle_pick_participant_event(
+                    state.event_repo.clone(),
+                    state.history_repo.clone(),
+                    state.audit_repo.clone(),
+                    &announcer,
+                    &settings,
+                    action,
+                    &payload,
+                )
+                .await;
+            }
+            if action_id == rotation_view::MOVE_ACTION_ID {
+                return handle_move_participant(
+                    state.event_repo.clone(),
+                    state.audit_repo.clone(),
+                    action,
+                    &payload,
+                    locale,
+                )
+                .await;
+            }
+            if let Some(name) = action_id.strip_prefix(help::COMMAND_ACTION_PREFIX) {
+                return handle_help_command(name, &payload).await;
             }
             if action_id.starts_with("cancel_pick_actions:") {
-                return handle_cancel_pick_event(state.event_repo.clone(), action, &payload).await;
+                return handle_cancel_pick_event(
+                    state.event_repo.clone(),
+                    state.history_repo.clone(),
+                    state.audit_repo.clone(),
+                    &announcer,
+                    &settings,
+                    action,
+                    &payload,
+                )
+                .await;
             }
         }
         if let None = action.block_id {
@@ -355,47 +881,139 @@ pub async fn execute(
             "add_event_actions" => {
                 handle_add_event(
                     state.event_repo.clone(),
+                    state.audit_repo.clone(),
                     state.scheduler.clone(),
-                    state.configs.clone(),
-                    // token,
+                    state.slack_client.clone(),
+                    &token,
+                    super::find_max_events(&headers)?,
+                    super::find_max_participants(&headers)?,
                     action,
                     &payload,
+                    locale,
+                    settings.date_format,
                 )
                 .await
             }
             "edit_event_actions" => {
                 handle_edit_event(
                     state.event_repo.clone(),
+                    state.audit_repo.clone(),
                     state.scheduler.clone(),
+                    super::find_max_participants(&headers)?,
                     action,
                     &payload,
+                    locale,
+                    settings.date_format,
                 )
                 .await
             }
             "select_event_edit_actions" => {
-                handle_edit_select_event(state.event_repo.clone(), action, &payload).await
+                handle_edit_select_event(state.event_repo.clone(), action, &payload, locale).await
             }
             "delete_event_actions" => {
                 handle_delete_event(
                     state.event_repo.clone(),
+                    state.audit_repo.clone(),
                     state.scheduler.clone(),
                     action,
                     &payload,
+                    locale,
                 )
                 .await
             }
             "select_event_delete_actions" => {
-                handle_delete_select_event(state.event_repo.clone(), action, &payload).await
+                handle_delete_select_event(state.event_repo.clone(), action, &payload, locale).await
             }
             "select_event_pick_actions" => {
-                handle_pick_select_event(state.event_repo.clone(), action, &payload).await
+                handle_pick_select_event(
+                    state.event_repo.clone(),
+                    state.history_repo.clone(),
+                    state.audit_repo.clone(),
+                    &announcer,
+                    &settings,
+                    action,
+                    &payload,
+                )
+                .await
             }
             "select_event_show_actions" => {
-                handle_show_select_event(state.event_repo.clone(), action, &payload).await
+                handle_show_select_event(
+                    state.event_repo.clone(),
+                    action,
+                    &payload,
+                    settings.date_format,
+                    locale,
+                )
+                .await
+            }
+            "list_events_actions" => {
+                handle_list_event(
+                    state.event_repo.clone(),
+                    state.slack_client.clone(),
+                    &token,
+                    super::find_reached_limit(&headers)?,
+                    action,
+                    &payload,
+                    locale,
+                    settings.date_format,
+                )
+                .await
+            }
+            help::ACTIONS_BLOCK_ID => {
+                handle_help_event(
+                    state.event_repo.clone(),
+                    state.slack_client.clone(),
+                    &token,
+                    super::find_reached_limit(&headers)?,
+                    action,
+                    &payload,
+                    locale,
+                    settings.date_format,
+                )
+                .await
+            }
+            "show_event_actions" if action.action_id.as_deref() == Some(SKIP_NEXT_ACTION) => {
+                let id = match action.value.as_deref().map(|value| value.parse::<u32>()) {
+                    Some(Ok(id)) => id,
+                    _ => return Err(hyper::StatusCode::BAD_REQUEST),
+                };
+                handle_skip_next_occurrence(
+                    state.event_repo.clone(),
+                    state.scheduler_repo.clone(),
+                    state.audit_repo.clone(),
+                    state.scheduler.clone(),
+                    locale,
+                    settings.date_format,
+                    &payload,
+                    id,
+                )
+                .await
+            }
+            "show_event_actions" if action.action_id.as_deref() == Some(ROTATION_ACTION) => {
+                let id = match action.value.as_deref().map(|value| value.parse::<u32>()) {
+                    Some(Ok(id)) => id,
+                    _ => return Err(hyper::StatusCode::BAD_REQUEST),
+                };
+                handle_show_rotation(
+                    state.event_repo.clone(),
+                    &payload.response_url,
+                    payload.channel.id.clone(),
+                    id,
+                    locale,
+                )
+                .await
             }
-            "list_events_actions" => handle_list_event(action, &payload).await,
             "show_event_actions" | "add_event_success_action" | "edit_event_success_action" => {
-                handle_show_event(state.event_repo.clone(), action, &payload).await
+                handle_show_event(
+                    state.event_repo.clone(),
+                    state.history_repo.clone(),
+                    state.audit_repo.clone(),
+                    &announcer,
+                    &settings,
+                    action,
+                    &payload,
+                )
+                .await
             }
             id => {
                 let id = match id.parse::<u32>() {
@@ -406,18 +1024,69 @@ pub async fn execute(
                     continue;
                 }
                 match action.action_id.as_deref().unwrap() {
+                    "list_event_actions" if is_selected(action, "duplicate") => {
+                        handle_duplicate_selected_event(
+                            state.event_repo.clone(),
+                            state.audit_repo.clone(),
+                            state.scheduler.clone(),
+                            super::find_max_events(&headers)?,
+                            &payload,
+                            id,
+                            settings.date_format,
+                            locale,
+                        )
+                        .await
+                    }
+                    "list_event_actions" if is_selected(action, SKIP_NEXT_ACTION) => {
+                        handle_skip_next_occurrence(
+                            state.event_repo.clone(),
+                            state.scheduler_repo.clone(),
+                            state.audit_repo.clone(),
+                            state.scheduler.clone(),
+                            locale,
+                            settings.date_format,
+                            &payload,
+                            id,
+                        )
+                        .await
+                    }
                     "list_event_actions" => {
-                        handle_list_item_event(state.event_repo.clone(), action, &payload, id).await
+                        handle_list_item_event(
+                            state.event_repo.clone(),
+                            state.history_repo.clone(),
+                            state.audit_repo.clone(),
+                            &announcer,
+                            &settings,
+                            action,
+                            &payload,
+                            id,
+                        )
+                        .await
                     }
                     "repick_event" => {
-                        handle_repick_event(
+                        let result = handle_repick_event(
                             state.event_repo.clone(),
-                            payload.response_url,
-                            payload.channel.id,
-                            payload.user.id,
+                            state.history_repo.clone(),
+                            payload.response_url.clone(),
+                            payload.channel.id.clone(),
+                            payload.user.id.clone(),
                             id,
+                            &settings,
                         )
-                        .await
+                        .await;
+                        if result.is_ok() {
+                            record_action(
+                                state.audit_repo.clone(),
+                                &payload,
+                                AuditAction::Repick,
+                                id,
+                            )
+                            .await;
+                            announcer
+                                .announce_latest(id, payload.channel.id.clone())
+                                .await;
+                        }
+                        result
                     }
                     _ => continue,
                 }
@@ -430,18 +1099,31 @@ pub async fn execute(
         return Ok(());
     }
 
-    log::trace!("unknown action: {:?}", payload);
+    tracing::trace!(
+        actions = ?payload
+            .actions
+            .iter()
+            .map(|action| (action.block_id.as_deref(), action.action_id.as_deref()))
+            .collect::<Vec<(Option<&str>, Option<&str>)>>(),
+        "unknown action"
+    );
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_add_event(
     repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
     scheduler: Arc<Scheduler>,
-    configs: Arc<AppConfigs>,
-    // token: String,
+    slack_client: Arc<dyn SlackClient>,
+    token: &str,
+    max_events: u32,
+    max_participants: u32,
     action: &Action,
     command_action: &CommandAction,
+    locale: Locale,
+    date_format: DateFormat,
 ) -> Result<(), hyper::StatusCode> {
     if let None = action.value {
         return Err(hyper::StatusCode::BAD_REQUEST);
@@ -450,60 +1132,80 @@ async fn handle_add_event(
         return handle_close(&command_action.response_url).await;
     }
 
-    let request: create_event::Request =
-        match AddEventData::new(command_action.clone(), configs.max_events).try_into() {
-            Ok(data) => data,
+    let mut data = AddEventData::new(command_action.clone(), max_events, max_participants);
+    if !data.has_timezone() {
+        if let Some(timezone) =
+            client::get_user_timezone(slack_client.as_ref(), token, &command_action.user.id).await
+        {
+            data.timezone = timezone;
+        }
+    }
+    let mut request: create_event::Request = match data.try_into() {
+        Ok(data) => data,
+        Err(err) => {
+            log::trace!("error parsing data to create event request: {:?}", err);
+            return send_form_error(&command_action.response_url, locale, err).await;
+        }
+    };
+    if let Some(handle) = request.user_group.take() {
+        let group = match user_groups::find_by_handle(slack_client.as_ref(), token, &handle).await {
+            Ok(Some(group)) => group,
+            Ok(None) => {
+                let err = FormError::new(
+                    "form_error_user_group",
+                    format!("user group {} not found", handle),
+                );
+                return send_form_error(&command_action.response_url, locale, err).await;
+            }
             Err(err) => {
-                log::trace!("error parsing data to create event request: {}", err);
-                return Err(hyper::StatusCode::BAD_REQUEST);
+                log::error!("failed to find user group {}: {}", handle, err);
+                return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR);
             }
         };
+        request.user_group = Some(group.id);
+        request.participants = group.users;
+    }
+    if let Err(err) = check_participants_count(&request.participants, max_participants) {
+        return send_form_error(&command_action.response_url, locale, err).await;
+    }
     let response = match create_event::execute(repo.clone(), request).await {
         Ok(res) => res,
-        Err(create_event::Error::BadRequest) => return Err(hyper::StatusCode::BAD_REQUEST),
+        Err(create_event::Error::Invalid(errors)) => {
+            return send_form_error(&command_action.response_url, locale, errors.into()).await
+        }
         Err(create_event::Error::Conflict) => return Err(hyper::StatusCode::CONFLICT),
         _ => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
     };
 
-    // TODO: Check if needed this extra complexity.
-    // let added_to_channel = match response.created_channel {
-    //     Some(channel) => {
-    //         match sender::join_channel(&token, &channel).await {
-    //             Ok(res) => {
-    //                 /* TODO: find why this gives error, and putting outside don't.
-    //                 if let Err(err) = task::spawn(async move {
-    //                     scheduler.insert(EventSchedule {
-    //                         id: response.id,
-    //                         date: response.date,
-    //                         repeat: response.repeat,
-    //                     }).await;
-    //                 }).await {
-    //                     log::error!("unable to insert event into scheduler: {}", err)
-    //                 } */
-    //                 Some(res)
-    //             }
-    //             Err(err) => {
-    //                 log::error!("unable to send slack error response: {}", err);
-    //                 None
-    //             }
-    //         }
-    //     }
-    //     None => Some(()),
-    // };
-    //
-    // if let Some(..) = added_to_channel {
     scheduler
         .insert(EventSchedule {
             id: response.id,
+            team_id: response.team_id,
             timestamp: response.timestamp,
             timezone: response.timezone,
             repeat: response.repeat,
+            ends_at: response.ends_at,
+            reminder_minutes: response.reminder_minutes,
+            skip_weekends: response.skip_weekends,
+            skipped_dates: response.skipped_dates,
         })
         .await;
-    // }
+    record_action(
+        audit_repo,
+        command_action,
+        AuditAction::EventCreated,
+        response.id,
+    )
+    .await;
 
-    let body =
-        templates::add_event_success(repo, command_action.channel.id.clone(), response.id).await?;
+    let body = templates::add_event_success(
+        repo,
+        command_action.channel.id.clone(),
+        response.id,
+        date_format,
+        locale,
+    )
+    .await?;
     super::send_post(&command_action.response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -511,14 +1213,42 @@ async fn handle_add_event(
             hyper::StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
+    // The picks are posted by the bot, which has to be on the channel of the event.
+    match sender::join_channel(slack_client.as_ref(), token, &command_action.channel.id).await {
+        Ok(sender::ChannelAccess::Member) => {}
+        Ok(sender::ChannelAccess::NeedsInvite) => {
+            let body = json!({
+                "text": format!("⚠️ {}", i18n::t(locale, "channel_invite_bot")),
+                "response_type": "ephemeral",
+                "replace_original": false,
+            })
+            .to_string();
+            if let Err(err) =
+                super::send_post(&command_action.response_url, hyper::Body::from(body)).await
+            {
+                log::error!("unable to send slack invite response: {}", err);
+            }
+        }
+        Err(err) => log::error!(
+            "unable to join channel {}: {}",
+            command_action.channel.id,
+            err
+        ),
+    }
+
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_edit_event(
     repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
     scheduler: Arc<Scheduler>,
+    max_participants: u32,
     action: &Action,
     command_action: &CommandAction,
+    locale: Locale,
+    date_format: DateFormat,
 ) -> Result<(), hyper::StatusCode> {
     if let None = action.value {
         return Err(hyper::StatusCode::BAD_REQUEST);
@@ -551,16 +1281,21 @@ async fn handle_edit_event(
     };
 
     let request: update_event::Request =
-        match UpdateEventData::new(event, command_action.clone()).try_into() {
+        match UpdateEventData::new(event, command_action.clone(), max_participants).try_into() {
             Ok(data) => data,
             Err(err) => {
-                log::trace!("error parsing data to update event request: {}", err);
-                return Err(hyper::StatusCode::BAD_REQUEST);
+                log::trace!("error parsing data to update event request: {:?}", err);
+                return send_form_error(&command_action.response_url, locale, err).await;
             }
         };
+    if let Err(err) = check_participants_count(&request.participants, max_participants) {
+        return send_form_error(&command_action.response_url, locale, err).await;
+    }
     let response = match update_event::execute(repo.clone(), request).await {
         Ok(res) => res,
-        Err(update_event::Error::BadRequest) => return Err(hyper::StatusCode::BAD_REQUEST),
+        Err(update_event::Error::Invalid(errors)) => {
+            return send_form_error(&command_action.response_url, locale, errors.into()).await
+        }
         Err(update_event::Error::Conflict) => return Err(hyper::StatusCode::CONFLICT),
         Err(update_event::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
         _ => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
@@ -569,14 +1304,32 @@ async fn handle_edit_event(
     scheduler
         .insert(EventSchedule {
             id: response.id,
+            team_id: response.team_id,
             timestamp: response.timestamp,
             timezone: response.timezone,
             repeat: response.repeat,
+            ends_at: response.ends_at,
+            reminder_minutes: response.reminder_minutes,
+            skip_weekends: response.skip_weekends,
+            skipped_dates: response.skipped_dates,
         })
         .await;
+    record_action(
+        audit_repo,
+        command_action,
+        AuditAction::EventUpdated,
+        response.id,
+    )
+    .await;
 
-    let body =
-        templates::edit_event_success(repo, command_action.channel.id.clone(), response.id).await?;
+    let body = templates::edit_event_success(
+        repo,
+        command_action.channel.id.clone(),
+        response.id,
+        date_format,
+        locale,
+    )
+    .await?;
     super::send_post(&command_action.response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -591,6 +1344,7 @@ async fn handle_edit_select_event(
     repo: Arc<dyn Repository>,
     action: &Action,
     command_action: &CommandAction,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
     if let None = action.value {
         return Err(hyper::StatusCode::BAD_REQUEST);
@@ -612,15 +1366,18 @@ async fn handle_edit_select_event(
         command_action.response_url.clone(),
         command_action.channel.id.clone(),
         event_id,
+        locale,
     )
     .await
 }
 
 async fn handle_delete_event(
     repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
     scheduler: Arc<Scheduler>,
     action: &Action,
     command_action: &CommandAction,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
     if let None = action.value {
         return Err(hyper::StatusCode::BAD_REQUEST);
@@ -648,8 +1405,15 @@ async fn handle_delete_event(
     };
 
     scheduler.remove(event_id).await;
+    record_action(
+        audit_repo,
+        command_action,
+        AuditAction::EventDeleted,
+        event_id,
+    )
+    .await;
 
-    let body = templates::delete_event_success().await?;
+    let body = templates::delete_event_success(locale).await?;
     super::send_post(&command_action.response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -664,6 +1428,7 @@ async fn handle_delete_select_event(
     repo: Arc<dyn Repository>,
     action: &Action,
     command_action: &CommandAction,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
     if let None = action.value {
         return Err(hyper::StatusCode::BAD_REQUEST);
@@ -685,12 +1450,17 @@ async fn handle_delete_select_event(
         command_action.response_url.clone(),
         command_action.channel.id.clone(),
         event_id,
+        locale,
     )
     .await
 }
 
 async fn handle_pick_select_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    announcer: &PickAnnouncer,
+    settings: &TeamSettings,
     action: &Action,
     command_action: &CommandAction,
 ) -> Result<(), hyper::StatusCode> {
@@ -709,24 +1479,74 @@ async fn handle_pick_select_event(
         }
     };
 
-    handle_pick_event(
+    let result = handle_pick_event(
         repo,
+        history_repo,
         command_action.response_url.clone(),
         command_action.channel.id.clone(),
         command_action.user.id.clone(),
         event_id,
+        settings,
     )
-    .await
+    .await;
+    if result.is_ok() {
+        record_action(audit_repo, command_action, AuditAction::Pick, event_id).await;
+        announcer
+            .announce_latest(event_id, command_action.channel.id.clone())
+            .await;
+    }
+    result
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_list_event(
+    repo: Arc<dyn Repository>,
+    slack_client: Arc<dyn SlackClient>,
+    token: &str,
+    reached_limit: bool,
     action: &Action,
     command_action: &CommandAction,
+    locale: Locale,
+    date_format: DateFormat,
 ) -> Result<(), hyper::StatusCode> {
     match action.value.clone() {
         Some(value) if value == "close" => handle_close(&command_action.response_url).await,
         Some(value) if value == "add_event" => {
-            handle_create_event(&command_action.response_url).await
+            handle_create_event(
+                slack_client.as_ref(),
+                token,
+                &command_action.user.id,
+                &command_action.response_url,
+                locale,
+            )
+            .await
+        }
+        Some(value) if value.starts_with(list_events::PAGE_PREFIX) => {
+            let cursor = value[list_events::PAGE_PREFIX.len()..]
+                .parse::<usize>()
+                .map_err(|err| {
+                    log::trace!("error retrieving cursor from action value: {}", err);
+                    hyper::StatusCode::BAD_REQUEST
+                })?;
+            let body = commands::list_events::execute(
+                repo,
+                command_action.channel.id.clone(),
+                reached_limit,
+                cursor,
+                date_format,
+                locale,
+            )
+            .await?;
+            super::send_post(
+                &command_action.response_url,
+                hyper::Body::from(body.to_string()),
+            )
+            .await
+            .map_err(|err| {
+                log::error!("unable to send slack response: {}", err);
+                hyper::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            Ok(())
         }
         _ => {
             log::trace!("unknown action value for list event: {:?}", action.value);
@@ -735,8 +1555,84 @@ async fn handle_list_event(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn handle_help_event(
+    repo: Arc<dyn Repository>,
+    slack_client: Arc<dyn SlackClient>,
+    token: &str,
+    reached_limit: bool,
+    action: &Action,
+    command_action: &CommandAction,
+    locale: Locale,
+    date_format: DateFormat,
+) -> Result<(), hyper::StatusCode> {
+    let body = match action.value.as_deref() {
+        Some("close") => return handle_close(&command_action.response_url).await,
+        Some("add_event") => {
+            return handle_create_event(
+                slack_client.as_ref(),
+                token,
+                &command_action.user.id,
+                &command_action.response_url,
+                locale,
+            )
+            .await
+        }
+        Some("list_events") => {
+            commands::list_events::execute(
+                repo,
+                command_action.channel.id.clone(),
+                reached_limit,
+                0,
+                date_format,
+                locale,
+            )
+            .await?
+        }
+        Some("help") => help::view(reached_limit),
+        _ => {
+            log::trace!("unknown action value for help: {:?}", action.value);
+            return Err(hyper::StatusCode::BAD_REQUEST);
+        }
+    };
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(())
+}
+
+async fn handle_help_command(
+    name: &str,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let command = help::find_command(name).ok_or_else(|| {
+        log::trace!("unknown help command: {}", name);
+        hyper::StatusCode::BAD_REQUEST
+    })?;
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(help::command_view(command).to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(())
+}
+
 async fn handle_pick_participant_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    announcer: &PickAnnouncer,
+    settings: &TeamSettings,
     action: &Action,
     command_action: &CommandAction,
 ) -> Result<(), hyper::StatusCode> {
@@ -753,21 +1649,42 @@ async fn handle_pick_participant_event(
         },
         None => return Err(hyper::StatusCode::BAD_REQUEST),
     };
-    match action.action_id.clone().map(|action_id| {
+    let (audit_action, result) = match action.action_id.clone().map(|action_id| {
         action_id
             .clone()
             .trim_start_matches("pick_participant_actions:")
             .to_string()
     }) {
-        Some(value) if value == "pick" => {
-            handle_skip_pick_event(repo, response_url, channel, user, event_id).await
-        }
-        Some(value) if value == "repick" => {
-            handle_repick_event(repo, response_url, channel, user, event_id).await
-        }
-        Some(value) if value == "cancel" => {
-            handle_cancel_pick(repo, response_url, channel, user, event_id).await
-        }
+        Some(value) if value == "pick" => (
+            AuditAction::Skip,
+            handle_skip_pick_event(
+                repo,
+                history_repo,
+                response_url,
+                channel,
+                user,
+                event_id,
+                settings,
+            )
+            .await,
+        ),
+        Some(value) if value == "repick" => (
+            AuditAction::Repick,
+            handle_repick_event(
+                repo,
+                history_repo,
+                response_url,
+                channel,
+                user,
+                event_id,
+                settings,
+            )
+            .await,
+        ),
+        Some(value) if value == "cancel" => (
+            AuditAction::CancelPick,
+            handle_cancel_pick(repo, response_url, channel, user, event_id, settings.locale).await,
+        ),
         _ => {
             log::trace!(
                 "unknown action value for pick participant event: {:?}",
@@ -775,16 +1692,252 @@ async fn handle_pick_participant_event(
             );
             return Err(hyper::StatusCode::BAD_REQUEST);
         }
+    };
+    if result.is_ok() {
+        let is_pick = audit_action != AuditAction::CancelPick;
+        record_action(audit_repo, command_action, audit_action, event_id).await;
+        if is_pick {
+            announcer
+                .announce_latest(event_id, command_action.channel.id.clone())
+                .await;
+        }
     }
+    result
 }
 
-async fn handle_cancel_pick_event(
-    repo: Arc<dyn Repository>,
+async fn handle_acknowledge_event(
+    repo: Arc<dyn acknowledgment::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    locale: Locale,
     action: &Action,
     command_action: &CommandAction,
 ) -> Result<(), hyper::StatusCode> {
-    let response_url = command_action.response_url.clone();
-    let channel = command_action.channel.id.clone();
+    let event_id = match action.value.clone() {
+        Some(value) => match value.parse() {
+            Ok(id) => id,
+            Err(err) => {
+                log::trace!("error retrieving event id from action value: {}", err);
+                return Err(hyper::StatusCode::BAD_REQUEST);
+            }
+        },
+        None => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+    let req = acknowledge_pick::Request {
+        event: event_id,
+        channel: command_action.channel.id.clone(),
+        user: command_action.user.id.clone(),
+    };
+    // The pick message is kept, so the acknowledgment is replied apart from it.
+    let body = match acknowledge_pick::execute(repo, req).await {
+        Ok(acknowledgment) => {
+            record_action(
+                audit_repo,
+                command_action,
+                AuditAction::Acknowledge,
+                event_id,
+            )
+            .await;
+            json!({
+                "text": i18n::format(
+                    locale,
+                    "acknowledge_done",
+                    &[
+                        ("user", &format!("<@{}>", acknowledgment.user)),
+                        ("event", &acknowledgment.event_name),
+                    ],
+                ),
+                "replace_original": false,
+                "response_type": "in_channel",
+            })
+        }
+        Err(acknowledge_pick::Error::Forbidden(user)) => json!({
+            "text": i18n::format(
+                locale,
+                "acknowledge_forbidden",
+                &[("user", &format!("<@{}>", user))],
+            ),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(acknowledge_pick::Error::NotFound) => json!({
+            "text": i18n::t(locale, "acknowledge_missing"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(acknowledge_pick::Error::Unknown) => {
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+async fn handle_no_show(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    settings: &TeamSettings,
+    action: &Action,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let locale = settings.locale;
+    let value = action.value.as_deref();
+    let (event_id, user) = match value.and_then(pick_view::parse_pick_value) {
+        Some(value) => value,
+        None => {
+            log::trace!("invalid no-show action value: {:?}", action.value);
+            return Err(hyper::StatusCode::BAD_REQUEST);
+        }
+    };
+    let req = mark_no_show::Request {
+        event: event_id,
+        channel: command_action.channel.id.clone(),
+        user,
+        boost: settings.no_show_boost,
+    };
+    // The pick message is kept, so the no-show is replied apart from it.
+    let body = match mark_no_show::execute(repo, history_repo, req).await {
+        Ok(no_show) => {
+            record_action(audit_repo, command_action, AuditAction::NoShow, event_id).await;
+            json!({
+                "text": i18n::format(
+                    locale,
+                    "no_show_done",
+                    &[
+                        ("user", &format!("<@{}>", no_show.user)),
+                        ("event", &no_show.event_name),
+                    ],
+                ),
+                "replace_original": false,
+                "response_type": "in_channel",
+            })
+        }
+        Err(mark_no_show::Error::NotPicked) => json!({
+            "text": i18n::t(locale, "no_show_missing"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(mark_no_show::Error::AlreadyMarked) => json!({
+            "text": i18n::t(locale, "no_show_already"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(mark_no_show::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(mark_no_show::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+async fn handle_volunteer(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    announcer: &PickAnnouncer,
+    settings: &TeamSettings,
+    action: &Action,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let locale = settings.locale;
+    let value = action.value.as_deref();
+    let (event_id, replaced) = match value.and_then(pick_view::parse_pick_value) {
+        Some(value) => value,
+        None => {
+            log::trace!("invalid volunteer action value: {:?}", action.value);
+            return Err(hyper::StatusCode::BAD_REQUEST);
+        }
+    };
+    let channel = command_action.channel.id.clone();
+    let req = volunteer::Request {
+        event: event_id,
+        channel: channel.clone(),
+        user: command_action.user.id.clone(),
+        replaced,
+    };
+    // The pick message is updated with the volunteer, while the errors are only told to them.
+    let body = match volunteer::execute(repo, history_repo, req).await {
+        Ok(response) => {
+            record_action(audit_repo, command_action, AuditAction::Volunteer, event_id).await;
+            announcer.announce_latest(event_id, channel.clone()).await;
+            pick_view::view(pick_view::PickParticipantView {
+                source: pick_view::PickParticipantSource::Volunteer,
+                event_id,
+                event_name: response.event_name,
+                user_id: response.user.clone(),
+                user_picked_id: response.user,
+                partner_picked_id: response.partner,
+                channel_id: channel,
+                left_count: response.left_count,
+                thread_ts: None,
+                replaced: vec![response.replaced],
+                locale,
+                message: settings.pick_message.clone(),
+                acknowledge_minutes: None,
+                description: response.description,
+                url: response.url,
+                private: response.visibility == Visibility::Direct,
+            })
+        }
+        Err(volunteer::Error::NotParticipant) => json!({
+            "text": i18n::t(locale, "volunteer_not_participant"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(volunteer::Error::AlreadyPicked) => json!({
+            "text": i18n::t(locale, "volunteer_already_picked"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(volunteer::Error::Stale) => json!({
+            "text": i18n::t(locale, "volunteer_stale"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        }),
+        Err(volunteer::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(volunteer::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+async fn handle_cancel_pick_event(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    announcer: &PickAnnouncer,
+    settings: &TeamSettings,
+    action: &Action,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let response_url = command_action.response_url.clone();
+    let channel = command_action.channel.id.clone();
     let user = command_action.user.id.clone();
     let event_id = match action.value.clone() {
         Some(value) => match value.parse() {
@@ -803,7 +1956,23 @@ async fn handle_cancel_pick_event(
             .to_string()
     }) {
         Some(value) if value == "pick" => {
-            handle_pick_event(repo, response_url, channel, user, event_id).await
+            let result = handle_pick_event(
+                repo,
+                history_repo,
+                response_url,
+                channel,
+                user,
+                event_id,
+                settings,
+            )
+            .await;
+            if result.is_ok() {
+                record_action(audit_repo, command_action, AuditAction::Pick, event_id).await;
+                announcer
+                    .announce_latest(event_id, command_action.channel.id.clone())
+                    .await;
+            }
+            result
         }
         _ => {
             log::trace!(
@@ -815,8 +1984,13 @@ async fn handle_cancel_pick_event(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_list_item_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    announcer: &PickAnnouncer,
+    settings: &TeamSettings,
     action: &Action,
     command_action: &CommandAction,
     event_id: u32,
@@ -832,16 +2006,47 @@ async fn handle_list_item_event(
         None => return Err(hyper::StatusCode::BAD_REQUEST),
     };
     match selected_option.as_str() {
-        "pick" => handle_pick_event(repo, response_url, channel, user, event_id).await,
-        "show" => handle_show_details_event(repo, response_url, channel, event_id).await,
-        "edit" => handle_edit_selected_event(repo, response_url, channel, event_id).await,
-        "delete" => handle_delete_selected_event(repo, response_url, channel, event_id).await,
+        "pick" => {
+            let result = handle_pick_event(
+                repo,
+                history_repo,
+                response_url,
+                channel,
+                user,
+                event_id,
+                settings,
+            )
+            .await;
+            if result.is_ok() {
+                record_action(audit_repo, command_action, AuditAction::Pick, event_id).await;
+                announcer
+                    .announce_latest(event_id, command_action.channel.id.clone())
+                    .await;
+            }
+            result
+        }
+        "show" => {
+            let (date_format, locale) = (settings.date_format, settings.locale);
+            handle_show_details_event(repo, response_url, channel, event_id, date_format, locale)
+                .await
+        }
+        "edit" => {
+            handle_edit_selected_event(repo, response_url, channel, event_id, settings.locale).await
+        }
+        "delete" => {
+            handle_delete_selected_event(repo, response_url, channel, event_id, settings.locale)
+                .await
+        }
         _ => return Err(hyper::StatusCode::BAD_REQUEST),
     }
 }
 
 async fn handle_show_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    announcer: &PickAnnouncer,
+    settings: &TeamSettings,
     action: &Action,
     command_action: &CommandAction,
 ) -> Result<(), hyper::StatusCode> {
@@ -868,9 +2073,32 @@ async fn handle_show_event(
     let channel = command_action.channel.id.clone();
     let user = command_action.user.id.clone();
     match action_type.as_str() {
-        "pick" => handle_pick_event(repo, response_url, channel, user, event_id).await,
-        "edit_event" => handle_edit_selected_event(repo, response_url, channel, event_id).await,
-        "delete_event" => handle_delete_selected_event(repo, response_url, channel, event_id).await,
+        "pick" => {
+            let result = handle_pick_event(
+                repo,
+                history_repo,
+                response_url,
+                channel,
+                user,
+                event_id,
+                settings,
+            )
+            .await;
+            if result.is_ok() {
+                record_action(audit_repo, command_action, AuditAction::Pick, event_id).await;
+                announcer
+                    .announce_latest(event_id, command_action.channel.id.clone())
+                    .await;
+            }
+            result
+        }
+        "edit_event" => {
+            handle_edit_selected_event(repo, response_url, channel, event_id, settings.locale).await
+        }
+        "delete_event" => {
+            handle_delete_selected_event(repo, response_url, channel, event_id, settings.locale)
+                .await
+        }
         _ => return Err(hyper::StatusCode::BAD_REQUEST),
     }
 }
@@ -879,6 +2107,8 @@ async fn handle_show_select_event(
     repo: Arc<dyn Repository>,
     action: &Action,
     command_action: &CommandAction,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
     if let None = action.value {
         return Err(hyper::StatusCode::BAD_REQUEST);
@@ -900,24 +2130,30 @@ async fn handle_show_select_event(
         command_action.response_url.clone(),
         command_action.channel.id.clone(),
         event_id,
+        date_format,
+        locale,
     )
     .await
 }
 
 async fn handle_pick_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     response_url: String,
     channel: String,
     user: String,
     event_id: u32,
+    settings: &TeamSettings,
 ) -> Result<(), hyper::StatusCode> {
     if let Some(response) = pick_participant::execute(
         repo.clone(),
+        history_repo,
         event_id,
         channel,
         user,
         response_url.clone(),
         false,
+        settings,
     )
     .await?
     {
@@ -933,18 +2169,22 @@ async fn handle_pick_event(
 
 async fn handle_skip_pick_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     response_url: String,
     channel: String,
     user: String,
     event_id: u32,
+    settings: &TeamSettings,
 ) -> Result<(), hyper::StatusCode> {
     if let Some(response) = pick_participant::execute(
         repo.clone(),
+        history_repo,
         event_id,
         channel,
         user,
         response_url.clone(),
         true,
+        settings,
     )
     .await?
     {
@@ -960,14 +2200,23 @@ async fn handle_skip_pick_event(
 
 async fn handle_repick_event(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     response_url: String,
     channel: String,
     user: String,
     event_id: u32,
+    settings: &TeamSettings,
 ) -> Result<(), hyper::StatusCode> {
-    if let Some(response) =
-        repick_participant::execute(repo.clone(), event_id, channel, user, response_url.clone())
-            .await?
+    if let Some(response) = repick_participant::execute(
+        repo.clone(),
+        history_repo,
+        event_id,
+        channel,
+        user,
+        response_url.clone(),
+        settings,
+    )
+    .await?
     {
         let body = hyper::Body::from(response.to_string());
         super::send_post(&response_url, body).await.map_err(|err| {
@@ -985,9 +2234,17 @@ async fn handle_cancel_pick(
     channel: String,
     user: String,
     event_id: u32,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
-    if let Some(response) =
-        cancel_pick::execute(repo.clone(), event_id, channel, user, response_url.clone()).await?
+    if let Some(response) = cancel_pick::execute(
+        repo.clone(),
+        event_id,
+        channel,
+        user,
+        response_url.clone(),
+        locale,
+    )
+    .await?
     {
         let body = hyper::Body::from(response.to_string());
         super::send_post(&response_url, body).await.map_err(|err| {
@@ -999,8 +2256,17 @@ async fn handle_cancel_pick(
     return Ok(());
 }
 
-async fn handle_create_event(response_url: &str) -> Result<(), hyper::StatusCode> {
-    let body = templates::add_event()?;
+async fn handle_create_event(
+    slack_client: &dyn SlackClient,
+    token: &str,
+    user: &str,
+    response_url: &str,
+    locale: Locale,
+) -> Result<(), hyper::StatusCode> {
+    let timezone = client::get_user_timezone(slack_client, token, user)
+        .await
+        .unwrap_or(Timezone::UTC);
+    let body = templates::add_event(timezone, locale)?;
     super::send_post(&response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -1016,8 +2282,9 @@ async fn handle_edit_selected_event(
     response_url: String,
     channel: String,
     event_id: u32,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
-    let body = templates::edit_event(repo, channel, event_id).await?;
+    let body = templates::edit_event(repo, channel, event_id, locale).await?;
     super::send_post(&response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -1033,8 +2300,9 @@ async fn handle_delete_selected_event(
     response_url: String,
     channel: String,
     event_id: u32,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
-    let body = templates::delete_event(repo, channel, event_id).await?;
+    let body = templates::delete_event(repo, channel, event_id, locale).await?;
     super::send_post(&response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -1045,13 +2313,313 @@ async fn handle_delete_selected_event(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn handle_duplicate_selected_event(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    scheduler: Arc<Scheduler>,
+    max_events: u32,
+    command_action: &CommandAction,
+    event_id: u32,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<(), hyper::StatusCode> {
+    let channel = command_action.channel.id.clone();
+    let response = match clone_event::execute(
+        repo.clone(),
+        clone_event::Request {
+            id: event_id,
+            channel: channel.clone(),
+            team_id: command_action.user.team_id.clone(),
+            max_events,
+        },
+    )
+    .await
+    {
+        Ok(res) => res,
+        Err(clone_event::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(clone_event::Error::Forbidden) => return Err(hyper::StatusCode::FORBIDDEN),
+        Err(clone_event::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    scheduler
+        .insert(EventSchedule {
+            id: response.id,
+            team_id: response.team_id,
+            timestamp: response.timestamp,
+            timezone: response.timezone,
+            repeat: response.repeat,
+            ends_at: response.ends_at,
+            reminder_minutes: response.reminder_minutes,
+            skip_weekends: response.skip_weekends,
+            skipped_dates: response.skipped_dates,
+        })
+        .await;
+    record_action(
+        audit_repo,
+        command_action,
+        AuditAction::EventCreated,
+        response.id,
+    )
+    .await;
+
+    let body = templates::show_event(repo, channel, response.id, date_format, locale).await?;
+    super::send_post(&command_action.response_url, hyper::Body::from(body))
+        .await
+        .map_err(|err| {
+            log::error!("unable to send slack error response: {}", err);
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(())
+}
+
+/// Skips the next occurrence of the event, showing its details with the skipped days.
+#[allow(clippy::too_many_arguments)]
+async fn handle_skip_next_occurrence(
+    repo: Arc<dyn Repository>,
+    scheduler_repo: Arc<dyn scheduler::Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    scheduler: Arc<Scheduler>,
+    locale: Locale,
+    date_format: DateFormat,
+    command_action: &CommandAction,
+    event_id: u32,
+) -> Result<(), hyper::StatusCode> {
+    let channel = command_action.channel.id.clone();
+    let req = skip_next_occurrence::Request {
+        id: event_id,
+        channel: channel.clone(),
+    };
+    let body = match skip_next_occurrence::execute(repo.clone(), scheduler_repo, req).await {
+        Ok(response) => {
+            log::trace!(
+                "skipped the occurrence of event {} on {}",
+                response.id,
+                response.skipped
+            );
+            scheduler
+                .insert(EventSchedule {
+                    id: response.id,
+                    team_id: response.team_id,
+                    timestamp: response.timestamp,
+                    timezone: response.timezone,
+                    repeat: response.repeat,
+                    ends_at: response.ends_at,
+                    reminder_minutes: response.reminder_minutes,
+                    skip_weekends: response.skip_weekends,
+                    skipped_dates: response.skipped_dates,
+                })
+                .await;
+            record_action(
+                audit_repo,
+                command_action,
+                AuditAction::SkipOccurrence,
+                event_id,
+            )
+            .await;
+            templates::show_event(repo, channel, event_id, date_format, locale).await?
+        }
+        Err(skip_next_occurrence::Error::NoOccurrence) => json!({
+            "text": i18n::t(locale, "skip_next_none"),
+            "replace_original": false,
+            "response_type": "ephemeral",
+        })
+        .to_string(),
+        Err(skip_next_occurrence::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(skip_next_occurrence::Error::Unknown) => {
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+    super::send_post(&command_action.response_url, hyper::Body::from(body))
+        .await
+        .map_err(|err| {
+            log::error!("unable to send slack response: {}", err);
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(())
+}
+
+/// Joins the channel of an event again, from the message telling its owner that the bot was
+/// removed from it.
+async fn handle_rejoin_channel(
+    slack_client: Arc<dyn SlackClient>,
+    token: &str,
+    locale: Locale,
+    action: &Action,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let (_, channel) = match action
+        .value
+        .as_deref()
+        .and_then(channel_access::parse_action_value)
+    {
+        Some(value) => value,
+        None => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+    let body = match sender::join_channel(slack_client.as_ref(), token, &channel).await {
+        Ok(sender::ChannelAccess::Member) => json!({
+            "text": i18n::format(
+                locale,
+                "channel_access_rejoined",
+                &[("channel", &format!("<#{}>", channel))],
+            ),
+            "replace_original": true,
+        }),
+        Ok(sender::ChannelAccess::NeedsInvite) => json!({
+            "text": i18n::t(locale, "channel_invite_bot"),
+            "replace_original": false,
+        }),
+        Err(err) => {
+            log::error!("unable to join channel {}: {}", channel, err);
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+/// Shows the tour of the app on the welcome message sent to the installing user.
+async fn handle_onboarding_tour(
+    locale: Locale,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(onboarding::tour_view(locale).to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+/// Joins the channel chosen from the welcome message sent to the installing user, posting there
+/// the button to create its first event.
+async fn handle_onboarding_channel(
+    slack_client: Arc<dyn SlackClient>,
+    token: &str,
+    locale: Locale,
+    action: &Action,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let channel = match action.selected_conversation.clone() {
+        Some(channel) => channel,
+        None => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+    let body = match sender::join_channel(slack_client.as_ref(), token, &channel).await {
+        Ok(sender::ChannelAccess::Member) => {
+            let message =
+                onboarding::channel_view(channel.clone(), &command_action.user.id, locale);
+            if let Err(err) = slack_client.post_message(token, message).await {
+                log::error!("unable to post welcome message on {}: {}", channel, err);
+                return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+            }
+            json!({
+                "text": i18n::format(
+                    locale,
+                    "onboarding_channel_posted",
+                    &[("channel", &format!("<#{}>", channel))],
+                ),
+                "replace_original": false,
+            })
+        }
+        Ok(sender::ChannelAccess::NeedsInvite) => json!({
+            "text": i18n::t(locale, "channel_invite_bot"),
+            "replace_original": false,
+        }),
+        Err(err) => {
+            log::error!("unable to join channel {}: {}", channel, err);
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+/// Pauses an event, from the message telling its owner that the bot was removed from its channel.
+async fn handle_pause_event(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    scheduler: Arc<Scheduler>,
+    locale: Locale,
+    action: &Action,
+    command_action: &CommandAction,
+) -> Result<(), hyper::StatusCode> {
+    let (event_id, channel) = match action
+        .value
+        .as_deref()
+        .and_then(channel_access::parse_action_value)
+    {
+        Some(value) => value,
+        None => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+    let req = pause_event::Request {
+        id: event_id,
+        channel: channel.clone(),
+    };
+    let response = match pause_event::execute(repo, req).await {
+        Ok(response) => response,
+        Err(pause_event::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(pause_event::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    scheduler.remove(event_id).await;
+    // The action is clicked from a direct message, so it is recorded on the channel of the event.
+    let actor = activity::Actor {
+        team_id: command_action.user.team_id.clone(),
+        channel: Some(channel),
+        user: Some(command_action.user.id.clone()),
+    };
+    activity::record(audit_repo, actor, AuditAction::EventPaused, Some(event_id)).await;
+
+    let body = json!({
+        "text": i18n::format(locale, "channel_access_paused", &[("event", &response.name)]),
+        "replace_original": true,
+    });
+    super::send_post(
+        &command_action.response_url,
+        hyper::Body::from(body.to_string()),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("unable to send slack response: {}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
 async fn handle_show_details_event(
     repo: Arc<dyn Repository>,
     response_url: String,
     channel: String,
     event_id: u32,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<(), hyper::StatusCode> {
-    let body = templates::show_event(repo, channel, event_id).await?;
+    let body = templates::show_event(repo, channel, event_id, date_format, locale).await?;
     super::send_post(&response_url, hyper::Body::from(body))
         .await
         .map_err(|err| {
@@ -1062,6 +2630,149 @@ async fn handle_show_details_event(
     Ok(())
 }
 
+/// Shows the rotation of a round-robin event in place of the message of the action.
+async fn handle_show_rotation(
+    repo: Arc<dyn Repository>,
+    response_url: &str,
+    channel: String,
+    event_id: u32,
+    locale: Locale,
+) -> Result<(), hyper::StatusCode> {
+    let event = match find_event::execute(
+        repo,
+        find_event::Request {
+            id: event_id,
+            channel,
+        },
+    )
+    .await
+    {
+        Ok(event) => event,
+        Err(find_event::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(find_event::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    // Once everyone was picked, the rotation starts over.
+    let next = pick_new(
+        &event.participants,
+        &PickMode::RoundRobin,
+        None,
+        &mut rand::thread_rng(),
+    )
+    .or_else(|| rotation(&event.participants).into_iter().next())
+    .map(|participant| participant.user.clone());
+    let body = rotation_view::view(RotationView {
+        event_id: event.id,
+        event_name: event.name,
+        participants: rotation(&event.participants)
+            .into_iter()
+            .map(|participant| participant.user.clone())
+            .collect(),
+        next,
+        locale,
+    });
+    super::send_post(response_url, hyper::Body::from(body.to_string()))
+        .await
+        .map_err(|err| {
+            log::error!("unable to send slack response: {}", err);
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(())
+}
+
+/// Moves a participant up or down on the rotation of the event, showing the updated rotation.
+async fn handle_move_participant(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    action: &Action,
+    command_action: &CommandAction,
+    locale: Locale,
+) -> Result<(), hyper::StatusCode> {
+    let value = action
+        .selected_option
+        .as_ref()
+        .and_then(|option| option.value.as_deref())
+        .unwrap_or_default();
+    let (direction, event_id, user) = match rotation_view::parse_move(value) {
+        Some(parsed) => parsed,
+        None => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+    let req = move_participant::Request {
+        event: event_id,
+        channel: command_action.channel.id.clone(),
+        user: user.to_string(),
+        direction: if direction == rotation_view::MOVE_UP {
+            move_participant::Direction::Up
+        } else {
+            move_participant::Direction::Down
+        },
+    };
+    match move_participant::execute(repo.clone(), req).await {
+        Ok(..) => (),
+        Err(move_participant::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(move_participant::Error::NotParticipant) => return Err(hyper::StatusCode::BAD_REQUEST),
+        Err(move_participant::Error::Unknown) => {
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+    record_action(
+        audit_repo,
+        command_action,
+        AuditAction::EventUpdated,
+        event_id,
+    )
+    .await;
+
+    handle_show_rotation(
+        repo,
+        &command_action.response_url,
+        command_action.channel.id.clone(),
+        event_id,
+        locale,
+    )
+    .await
+}
+
+/// Checks that the event has no more participants than the plan of the team allows, telling the
+/// limit to the user, as the users selects of Slack have none.
+fn check_participants_count(participants: &[String], max: u32) -> Result<(), FormError> {
+    if participants.len() > max as usize {
+        return Err(FormError::new(
+            "form_error_participants_limit",
+            format!("{} participants is above the limit", participants.len()),
+        )
+        .with_arg("max", max));
+    }
+    Ok(())
+}
+
+/// Tells the user which field of the event form to fix, keeping the form so that they can submit it
+/// again.
+async fn send_form_error(
+    response_url: &str,
+    locale: Locale,
+    err: FormError,
+) -> Result<(), hyper::StatusCode> {
+    let args: Vec<(&str, &str)> = err
+        .args
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    let body = json!({
+        "text": format!("⚠️ {}", i18n::format(locale, err.key, &args)),
+        "response_type": "ephemeral",
+        "replace_original": false,
+    })
+    .to_string();
+    super::send_post(response_url, hyper::Body::from(body))
+        .await
+        .map_err(|err| {
+            log::error!("unable to send slack form error response: {}", err);
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(())
+}
+
 async fn handle_close(response_url: &str) -> Result<(), hyper::StatusCode> {
     super::send_post(
         response_url,
@@ -1075,3 +2786,32 @@ async fn handle_close(response_url: &str) -> Result<(), hyper::StatusCode> {
 
     Ok(())
 }
+
+/// The action skipping the next occurrence of an event, on its details and on the events list.
+const SKIP_NEXT_ACTION: &str = "skip_next";
+
+/// The action showing the rotation of a round-robin event, on its details.
+const ROTATION_ACTION: &str = "rotation";
+
+/// Whether the option of the action (e.g. of an overflow menu) is the given one.
+fn is_selected(action: &Action, value: &str) -> bool {
+    action
+        .selected_option
+        .as_ref()
+        .and_then(|option| option.value.as_deref())
+        == Some(value)
+}
+
+async fn record_action(
+    audit_repo: Arc<dyn audit::Repository>,
+    command_action: &CommandAction,
+    action: AuditAction,
+    event_id: u32,
+) {
+    let actor = activity::Actor {
+        team_id: command_action.user.team_id.clone(),
+        channel: Some(command_action.channel.id.clone()),
+        user: Some(command_action.user.id.clone()),
+    };
+    activity::record(audit_repo, actor, action, Some(event_id)).await;
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{audit::save_record, entities::AuditAction},
+    repository::audit,
+};
+
+/// The user performing an action, and where it was performed.
+pub struct Actor {
+    pub team_id: String,
+    pub channel: Option<String>,
+    pub user: Option<String>,
+}
+
+/// Records an action on the audit log. Failing to record it does not fail the action.
+pub async fn record(
+    repo: Arc<dyn audit::Repository>,
+    actor: Actor,
+    action: AuditAction,
+    event: Option<u32>,
+) {
+    let request = save_record::Request {
+        team_id: actor.team_id,
+        channel: actor.channel,
+        user: actor.user,
+        action,
+        event,
+    };
+    if let Err(err) = save_record::execute(repo, request).await {
+        log::error!("could not record the audit of an action: {:?}", err);
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Path, Query, State},
+    http::request::Parts,
+    Json,
+};
+use hyper::header;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::domain::entities::{ScheduledEvent, TeamLimits};
+use crate::domain::notifications::{find_failed_notifications, redrive_notification};
+use crate::domain::scheduler::find_scheduled_events;
+use crate::domain::settings::{find_settings, set_limits};
+use crate::error::Error;
+
+use super::{jwt, AppState};
+
+/// An operator of the application, authenticated with a token granted the admin scope.
+pub struct Admin;
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for Admin {
+    type Rejection = Error;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| {
+                log::trace!("bearer token not provided on admin request");
+                Error::Unauthorized
+            })?;
+
+        // The API keys are bound to a channel, so they are never accepted here.
+        let claims = jwt::decode(token.trim(), &state.configs.jwt_keys).map_err(|err| {
+            log::trace!("invalid bearer token on admin request: {:?}", err);
+            Error::Unauthorized
+        })?;
+        if !claims.has_scope(jwt::ADMIN_SCOPE) {
+            log::warn!(
+                "token of user {} without the admin scope on admin request",
+                claims.sub
+            );
+            return Err(Error::Unauthorized);
+        }
+        Ok(Admin)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct FailedNotificationsQuery {
+    pub team_id: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Lists the notifications that could not be posted, newest first.
+pub async fn failed_notifications(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FailedNotificationsQuery>,
+    _: Admin,
+) -> Result<Json<Value>, Error> {
+    let notifications = find_failed_notifications::execute(
+        state.notification_repo.clone(),
+        find_failed_notifications::Request {
+            team_id: query.team_id,
+            limit: query
+                .limit
+                .unwrap_or(MAX_FAILED_NOTIFICATIONS)
+                .clamp(1, MAX_FAILED_NOTIFICATIONS),
+        },
+    )
+    .await
+    .map_err(|err| Error::Internal(format!("could not find failed notifications: {:?}", err)))?;
+
+    Ok(Json(json!(notifications)))
+}
+
+/// Queues a failed notification again, to be posted right away.
+pub async fn redrive_notification(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    _: Admin,
+) -> Result<Json<Value>, Error> {
+    let job = redrive_notification::execute(
+        state.notification_repo.clone(),
+        redrive_notification::Request { id },
+    )
+    .await
+    .map_err(|err| match err {
+        redrive_notification::Error::NotFound => Error::NotFound,
+        redrive_notification::Error::Unknown => {
+            Error::Internal(format!("could not re-drive notification {}", id))
+        }
+    })?;
+    log::info!("re-drove failed notification {} as {}", id, job.id);
+
+    Ok(Json(json!({ "id": job.id, "run_at": job.run_at })))
+}
+
+/// Shows the limits set for the team, which follows its plan on the ones that are `null`.
+pub async fn team_limits(
+    State(state): State<Arc<AppState>>,
+    Path(team_id): Path<String>,
+    _: Admin,
+) -> Result<Json<Value>, Error> {
+    let settings = find_settings::execute(
+        state.settings_repo.clone(),
+        find_settings::Request {
+            team_id: team_id.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        Error::Internal(format!("could not find the settings: {:?}", err)).with_team(&team_id)
+    })?;
+
+    Ok(Json(
+        json!({ "team_id": team_id, "limits": settings.limits }),
+    ))
+}
+
+/// Sets the limits of the team, in place of the ones of its plan. The limits that are `null` or
+/// left out follow the plan again.
+pub async fn set_team_limits(
+    State(state): State<Arc<AppState>>,
+    Path(team_id): Path<String>,
+    _: Admin,
+    Json(limits): Json<TeamLimits>,
+) -> Result<Json<Value>, Error> {
+    let settings = set_limits::execute(
+        state.settings_repo.clone(),
+        set_limits::Request {
+            team_id: team_id.clone(),
+            limits,
+        },
+    )
+    .await
+    .map_err(|err| match err {
+        set_limits::Error::BadRequest => {
+            Error::BadRequest(String::from("the limits must be greater than zero"))
+        }
+        set_limits::Error::Unknown => {
+            Error::Internal(String::from("could not save the limits")).with_team(&team_id)
+        }
+    })?;
+    log::info!("set the limits of team {}: {:?}", team_id, settings.limits);
+
+    Ok(Json(
+        json!({ "team_id": team_id, "limits": settings.limits }),
+    ))
+}
+
+/// Shows what the scheduler believes is coming: the number of scheduled events and their next
+/// occurrences, soonest first.
+pub async fn scheduler(State(state): State<Arc<AppState>>, _: Admin) -> Result<Json<Value>, Error> {
+    let scheduled = find_scheduled_events::execute(state.scheduler_repo.clone())
+        .await
+        .map_err(|err| {
+            Error::Internal(format!("could not find the scheduled events: {:?}", err))
+        })?;
+    let total = scheduled.data.len();
+    let next: Vec<ScheduledEvent> = scheduled.data.into_iter().take(NEXT_OCCURRENCES).collect();
+
+    Ok(Json(json!({
+        "scheduled_events": total,
+        "next_occurrences": next,
+    })))
+}
+
+const MAX_FAILED_NOTIFICATIONS: i64 = 100;
+const NEXT_OCCURRENCES: usize = 10;
@@ -0,0 +1,683 @@
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use axum::{
+    body::StreamBody,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::{header, HeaderMap, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    domain::{
+        audit::find_records,
+        auth::{
+            create_api_key, create_refresh_token, find_api_keys, revoke_api_key,
+            revoke_refresh_token, rotate_refresh_token, verify_auth,
+        },
+        dtos::PageRequest,
+        entities::{AuditAction, Auth, PickSource, Visibility},
+        events::{delete_event, find_event, pick_participant, search_events},
+        history::find_all_picks,
+    },
+    error::Error,
+    repository::event::EventSort,
+    views::{
+        pick_history_csv,
+        pick_participant::{
+            view as pick_participant_view, PickParticipantSource, PickParticipantView,
+        },
+    },
+};
+
+use super::{
+    activity, cached_client,
+    jwt::{self, Claims},
+    sender::{self, PickAnnouncer},
+    AppState,
+};
+
+#[derive(Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct ApiKeyRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct SearchEventsQuery {
+    #[serde(default)]
+    pub query: String,
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub cursor: usize,
+    #[serde(default)]
+    pub picked_only: bool,
+    #[serde(default)]
+    pub sort: EventSort,
+}
+
+#[derive(Deserialize)]
+pub struct ChannelsQuery {
+    /// Lists the channels from Slack, instead of the cached ones.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Deserialize)]
+pub struct AuditQuery {
+    pub limit: Option<i64>,
+}
+
+/// Picks a participant of an event for external systems, posting the pick on the event channel.
+///
+/// The requests retried with the same `Idempotency-Key` header pick only once, answering with the
+/// response of the first one. They are rejected while the first one is still picking, and picked
+/// again when it failed.
+pub async fn pick_event(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+    claims: Claims,
+) -> Result<Json<Value>, Error> {
+    let auth = verify_auth::execute(
+        state.auth_repo.clone(),
+        // The teams of the organizations were mapped to them when the tokens were issued.
+        verify_auth::Request {
+            team: claims.team.clone(),
+            enterprise: None,
+        },
+    )
+    .await
+    .map_err(|err| {
+        log::trace!(
+            "auth verification failed for team {}: {:?}",
+            claims.team,
+            err
+        );
+        match err {
+            verify_auth::Error::Unauthorized => Error::Unauthorized,
+            verify_auth::Error::Unknown => {
+                Error::Internal(String::from("could not verify the auth"))
+            }
+        }
+        .with_team(claims.team.clone())
+    })?;
+
+    let key = headers
+        .get("idempotency-key")
+        .and_then(|value| value.to_str().ok())
+        .map(|key| format!("idempotency:{}:{}", claims.team, key));
+    if let Some(key) = &key {
+        match state
+            .store
+            .set_if_absent(key, String::from(IDEMPOTENCY_PENDING), IDEMPOTENCY_KEY_TTL)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                log::trace!("pick request {} was already received", key);
+                return match state.store.get(key).await {
+                    Ok(Some(body)) if body != IDEMPOTENCY_PENDING => {
+                        serde_json::from_str(&body).map(Json).map_err(|err| {
+                            Error::Internal(format!("could not read the saved pick: {}", err))
+                        })
+                    }
+                    _ => Err(Error::Conflict.with_team(claims.team).with_event(id)),
+                };
+            }
+            Err(err) => log::error!("could not save the idempotency key {}: {:?}", key, err),
+        }
+    }
+
+    let result = pick(&state, id, claims, auth).await;
+    if let Some(key) = &key {
+        // The failed picks are released, so that they can be retried with the same key.
+        let saved = match &result {
+            Ok(Json(body)) => {
+                state
+                    .store
+                    .set(key, body.to_string(), IDEMPOTENCY_KEY_TTL)
+                    .await
+            }
+            Err(_) => state.store.delete(key).await,
+        };
+        if let Err(err) = saved {
+            log::error!("could not save the idempotency key {}: {:?}", key, err);
+        }
+    }
+    result
+}
+
+/// Picks a participant of the event and posts it, answering with the pick.
+async fn pick(state: &AppState, id: u32, claims: Claims, auth: Auth) -> Result<Json<Value>, Error> {
+    let result = pick_participant::execute(
+        state.event_repo.clone(),
+        state.history_repo.clone(),
+        pick_participant::Request {
+            event: id,
+            channel: claims.channel.clone(),
+            source: PickSource::Manual,
+            dry_run: false,
+            skipped: vec![],
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            pick_participant::Error::Empty => {
+                Error::NotAcceptable(String::from("no participants left to pick"))
+            }
+            pick_participant::Error::NotFound => Error::NotFound,
+            pick_participant::Error::Unknown => {
+                Error::Internal(String::from("could not pick a participant"))
+            }
+        }
+        .with_channel(claims.channel.clone())
+        .with_event(id)
+    })?;
+    let event = find_event::execute(
+        state.event_repo.clone(),
+        find_event::Request {
+            id,
+            channel: claims.channel,
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            find_event::Error::NotFound => Error::NotFound,
+            find_event::Error::Unknown => Error::Internal(String::from("could not find the event")),
+        }
+        .with_event(id)
+    })?;
+    let left_count =
+        event.participants.len() - event.participants.iter().filter(|p| p.picked).count();
+    log::trace!("picked new participant through the api: {:?}", result);
+    let settings = sender::find_settings(state.settings_repo.clone(), &claims.team).await;
+
+    let actor = activity::Actor {
+        team_id: claims.team,
+        channel: Some(event.channel.clone()),
+        user: Some(claims.sub.clone()),
+    };
+    activity::record(state.audit_repo.clone(), actor, AuditAction::Pick, Some(id)).await;
+
+    let body = pick_participant_view(PickParticipantView {
+        source: PickParticipantSource::Pick,
+        event_id: event.id,
+        event_name: event.name.clone(),
+        channel_id: event.channel.clone(),
+        user_picked_id: result.id.clone(),
+        partner_picked_id: result.partner.clone(),
+        user_id: claims.sub,
+        left_count,
+        thread_ts: None,
+        replaced: vec![],
+        locale: settings.locale,
+        message: settings.pick_message,
+        acknowledge_minutes: None,
+        description: event.description.clone(),
+        url: event.url.clone(),
+        private: false,
+    });
+    // The picks of the private events are only sent to their participants.
+    if event.visibility == Visibility::Direct {
+        let token = auth.access_token.expose().to_string();
+        PickAnnouncer::new(state, settings.team_id.clone(), token, settings.locale)
+            .announce_latest(id, event.channel.clone())
+            .await;
+    } else {
+        if let Err(err) = state
+            .slack_client
+            .post_message(auth.access_token.expose(), body)
+            .await
+        {
+            log::error!("failed to post the pick of event {}: {}", id, err);
+        }
+        sender::announce(
+            state.slack_client.as_ref(),
+            auth.access_token.expose(),
+            &event.notify_channels,
+            &event.channel,
+            &event.name,
+            &result.id,
+            result.partner.as_deref(),
+            settings.locale,
+        )
+        .await;
+    }
+
+    Ok(Json(json!({
+        "event_id": event.id,
+        "event_name": event.name,
+        "picked": result.id,
+        "partner": result.partner,
+        "left_count": left_count,
+    })))
+}
+
+/// Finds a page of the events of the channel of the token, along with the cursor of the next one.
+pub async fn search_events(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchEventsQuery>,
+    claims: Claims,
+) -> Result<Json<Value>, Error> {
+    let events = search_events::execute(
+        state.event_repo.clone(),
+        search_events::Request {
+            channel: claims.channel.clone(),
+            query: query.query,
+            page: PageRequest {
+                cursor: query.cursor,
+                limit: query
+                    .limit
+                    .unwrap_or(DEFAULT_EVENTS_PAGE)
+                    .clamp(1, MAX_EVENTS_PAGE),
+            },
+            picked_only: query.picked_only,
+            sort: query.sort,
+        },
+    )
+    .await
+    .map_err(|err| {
+        Error::Internal(format!("could not search the events: {:?}", err))
+            .with_channel(claims.channel)
+    })?;
+
+    Ok(Json(json!(events)))
+}
+
+/// Deletes an event of the channel of the token, unscheduling its picks.
+pub async fn delete_event(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    claims: Claims,
+) -> Result<StatusCode, Error> {
+    delete_event::execute(
+        state.event_repo.clone(),
+        delete_event::Request {
+            id,
+            channel: claims.channel.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            delete_event::Error::NotFound => Error::NotFound,
+            delete_event::Error::Unknown => {
+                Error::Internal(String::from("could not delete the event"))
+            }
+        }
+        .with_channel(claims.channel.clone())
+        .with_event(id)
+    })?;
+    state.scheduler.remove(id).await;
+
+    let actor = activity::Actor {
+        team_id: claims.team,
+        channel: Some(claims.channel),
+        user: Some(claims.sub),
+    };
+    activity::record(
+        state.audit_repo.clone(),
+        actor,
+        AuditAction::EventDeleted,
+        Some(id),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Serves all the picks of an event as CSV, so that they can be imported into spreadsheets.
+pub async fn pick_history_csv(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    claims: Claims,
+) -> Result<Response, Error> {
+    let picks = find_all_picks::execute(
+        state.history_repo.clone(),
+        find_all_picks::Request {
+            event: id,
+            channel: claims.channel.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        Error::Internal(format!("could not find the picks: {:?}", err))
+            .with_channel(claims.channel)
+            .with_event(id)
+    })?;
+
+    let lines = pick_history_csv::lines(picks).map(Ok::<_, Infallible>);
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                String::from("text/csv; charset=utf-8"),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"event-{}-history.csv\"", id),
+            ),
+        ],
+        StreamBody::new(futures::stream::iter(lines)),
+    )
+        .into_response())
+}
+
+/// Lists the channels of the workspace of the token, so that the events can be moved or announced
+/// on them.
+pub async fn channels(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ChannelsQuery>,
+    claims: Claims,
+) -> Result<Json<Value>, Error> {
+    let auth = verify_auth::execute(
+        state.auth_repo.clone(),
+        verify_auth::Request {
+            team: claims.team.clone(),
+            enterprise: None,
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            verify_auth::Error::Unauthorized => Error::Unauthorized,
+            verify_auth::Error::Unknown => {
+                Error::Internal(String::from("could not verify the auth"))
+            }
+        }
+        .with_team(claims.team.clone())
+    })?;
+
+    let token = auth.access_token.expose();
+    if query.refresh {
+        cached_client::forget_channels(state.store.as_ref(), token).await;
+    }
+    let channels = state
+        .slack_client
+        .conversations_list(token)
+        .await
+        .map_err(|err| Error::from(err).with_team(claims.team))?;
+
+    Ok(Json(json!({ "data": channels })))
+}
+
+/// Lists the latest actions performed on the channel of the token.
+pub async fn audit(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AuditQuery>,
+    claims: Claims,
+) -> Result<Json<Value>, Error> {
+    let records = find_records::execute(
+        state.audit_repo.clone(),
+        find_records::Request {
+            team_id: claims.team.clone(),
+            channel: claims.channel.clone(),
+            limit: query
+                .limit
+                .unwrap_or(MAX_AUDIT_RECORDS)
+                .clamp(1, MAX_AUDIT_RECORDS),
+        },
+    )
+    .await
+    .map_err(|err| {
+        Error::Internal(format!("could not find audit records: {:?}", err))
+            .with_team(claims.team)
+            .with_channel(claims.channel)
+    })?;
+
+    Ok(Json(json!(records)))
+}
+
+/// Issues a new access token and rotates the refresh token used for it.
+pub async fn refresh(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<RefreshRequest>,
+) -> Result<Json<Value>, Error> {
+    let request = rotate_refresh_token::Request {
+        token: body.refresh_token,
+    };
+    let refresh_token = rotate_refresh_token::execute(state.auth_repo.clone(), request)
+        .await
+        .map_err(|err| {
+            log::trace!("could not rotate refresh token: {:?}", err);
+            match err {
+                rotate_refresh_token::Error::Unauthorized => Error::Unauthorized,
+                rotate_refresh_token::Error::Unknown => {
+                    Error::Internal(String::from("could not rotate the refresh token"))
+                }
+            }
+        })?;
+
+    // Uninstalled teams cannot renew their tokens.
+    let request = verify_auth::Request {
+        team: refresh_token.team.clone(),
+        enterprise: None,
+    };
+    if let Err(err) = verify_auth::execute(state.auth_repo.clone(), request).await {
+        log::trace!(
+            "auth verification failed for team {}: {:?}",
+            refresh_token.team,
+            err
+        );
+        return Err(match err {
+            verify_auth::Error::Unauthorized => Error::Unauthorized,
+            verify_auth::Error::Unknown => {
+                Error::Internal(String::from("could not verify the auth"))
+            }
+        }
+        .with_team(refresh_token.team));
+    }
+
+    let claims = Claims::new(
+        refresh_token.user,
+        refresh_token.team,
+        refresh_token.channel,
+        &state.configs.jwt_keys,
+    );
+    Ok(Json(tokens_response(
+        &claims,
+        &state.configs.jwt_keys,
+        refresh_token.token,
+    )?))
+}
+
+/// Revokes a refresh token, so that it cannot issue new access tokens.
+pub async fn logout(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<RefreshRequest>,
+) -> Result<StatusCode, Error> {
+    let request = revoke_refresh_token::Request {
+        token: body.refresh_token,
+    };
+    revoke_refresh_token::execute(state.auth_repo.clone(), request)
+        .await
+        .map_err(|err| Error::Internal(format!("could not revoke refresh token: {:?}", err)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists the API keys of the channel of the token, without their values.
+pub async fn api_keys(
+    State(state): State<Arc<AppState>>,
+    claims: Claims,
+) -> Result<Json<Value>, Error> {
+    let keys = find_api_keys::execute(
+        state.api_key_repo.clone(),
+        find_api_keys::Request {
+            team: claims.team.clone(),
+            channel: claims.channel.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        Error::Internal(format!("could not find the api keys: {:?}", err)).with_team(claims.team)
+    })?;
+
+    Ok(Json(json!(keys)))
+}
+
+/// Creates an API key on the channel of the token, for the clients that cannot go through Slack
+/// to renew their tokens. Its value is only shared on the response.
+pub async fn create_api_key(
+    State(state): State<Arc<AppState>>,
+    claims: Claims,
+    Json(body): Json<ApiKeyRequest>,
+) -> Result<(StatusCode, Json<Value>), Error> {
+    // The keys cannot create other keys, which would outlive their revocation.
+    if claims.api_key.is_some() {
+        log::trace!("api key creation requested with an api key");
+        return Err(Error::Unauthorized.with_team(claims.team));
+    }
+
+    let key = create_api_key::execute(
+        state.api_key_repo.clone(),
+        create_api_key::Request {
+            name: body.name,
+            user: claims.sub.clone(),
+            team: claims.team.clone(),
+            channel: claims.channel.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            create_api_key::Error::BadRequest(message) => Error::BadRequest(message),
+            create_api_key::Error::Unknown => {
+                Error::Internal(String::from("could not create the api key"))
+            }
+        }
+        .with_team(claims.team.clone())
+    })?;
+
+    let actor = activity::Actor {
+        team_id: claims.team,
+        channel: Some(claims.channel),
+        user: Some(claims.sub),
+    };
+    activity::record(
+        state.audit_repo.clone(),
+        actor,
+        AuditAction::ApiKeyCreated,
+        None,
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(json!(key))))
+}
+
+/// Revokes an API key of the channel of the token.
+pub async fn revoke_api_key(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u32>,
+    claims: Claims,
+) -> Result<StatusCode, Error> {
+    // The keys cannot revoke other keys, so that a leaked key cannot lock out the others.
+    if claims.api_key.is_some() {
+        log::trace!("api key revocation requested with an api key");
+        return Err(Error::Unauthorized.with_team(claims.team));
+    }
+
+    revoke_api_key::execute(
+        state.api_key_repo.clone(),
+        revoke_api_key::Request {
+            id,
+            team: claims.team.clone(),
+            channel: claims.channel.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            revoke_api_key::Error::NotFound => Error::NotFound,
+            revoke_api_key::Error::Unknown => {
+                Error::Internal(String::from("could not revoke the api key"))
+            }
+        }
+        .with_team(claims.team.clone())
+    })?;
+
+    let actor = activity::Actor {
+        team_id: claims.team,
+        channel: Some(claims.channel),
+        user: Some(claims.sub),
+    };
+    activity::record(
+        state.audit_repo.clone(),
+        actor,
+        AuditAction::ApiKeyRevoked,
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Issues the access and refresh tokens of a user for the API.
+pub async fn issue_tokens(
+    state: Arc<AppState>,
+    user: String,
+    team: String,
+    channel: String,
+) -> Result<Value, Error> {
+    let request = create_refresh_token::Request {
+        user: user.clone(),
+        team: team.clone(),
+        channel: channel.clone(),
+    };
+    let refresh_token = create_refresh_token::execute(state.auth_repo.clone(), request)
+        .await
+        .map_err(|err| {
+            Error::Internal(format!("could not create refresh token: {:?}", err))
+                .with_team(team.clone())
+                .with_channel(channel.clone())
+        })?;
+
+    let claims = Claims::new(user, team, channel, &state.configs.jwt_keys);
+    tokens_response(&claims, &state.configs.jwt_keys, refresh_token.token)
+}
+
+/// Shares the public key the API tokens are verified with, when they are signed with a key pair.
+pub async fn public_key(State(state): State<Arc<AppState>>) -> Result<String, Error> {
+    state
+        .configs
+        .jwt_keys
+        .public_key()
+        .map(String::from)
+        .ok_or(Error::NotFound)
+}
+
+fn tokens_response(
+    claims: &Claims,
+    keys: &jwt::Keys,
+    refresh_token: String,
+) -> Result<Value, Error> {
+    let access_token = jwt::encode(claims, keys)
+        .map_err(|err| Error::Internal(format!("could not encode the access token: {:?}", err)))?;
+    Ok(json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": jwt::ACCESS_TOKEN_EXPIRATION_SECONDS,
+        "refresh_token": refresh_token,
+    }))
+}
+
+/// The number of events of the pages, unless requested otherwise, and the maximum.
+const DEFAULT_EVENTS_PAGE: usize = 20;
+const MAX_EVENTS_PAGE: usize = 100;
+
+/// The maximum number of audit records listed at once.
+const MAX_AUDIT_RECORDS: i64 = 100;
+
+/// The value of the idempotency keys whose pick is still running.
+const IDEMPOTENCY_PENDING: &str = "pending";
+/// The time the idempotency keys of the pick requests are kept for.
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use hyper::{HeaderMap, StatusCode};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::domain::billing::{cancel_plan, upgrade_plan};
+
+use super::{helpers, AppState, StripeConfigs};
+
+/// The Stripe webhook event, of which only the object it refers to is used.
+#[derive(Deserialize)]
+struct WebhookEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    data: WebhookEventData,
+}
+
+#[derive(Deserialize)]
+struct WebhookEventData {
+    object: WebhookEventObject,
+}
+
+/// The checkout session or the subscription of the event.
+#[derive(Deserialize)]
+struct WebhookEventObject {
+    id: String,
+    client_reference_id: Option<String>,
+    customer: Option<String>,
+    subscription: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CheckoutSession {
+    url: String,
+}
+
+/// Creates the Stripe checkout page on which the team subscribes to the paid plan, returning its
+/// link.
+pub async fn create_checkout(
+    stripe: &StripeConfigs,
+    app_id: &str,
+    team_id: &str,
+) -> Result<String, StatusCode> {
+    // Both the paid and the cancelled checkouts return to the app on Slack.
+    let return_url = format!("https://slack.com/app_redirect?app={}", app_id);
+    let body = serde_urlencoded::to_string([
+        ("mode", "subscription"),
+        ("line_items[0][price]", stripe.price_id.as_str()),
+        ("line_items[0][quantity]", "1"),
+        ("client_reference_id", team_id),
+        ("success_url", return_url.as_str()),
+        ("cancel_url", return_url.as_str()),
+    ])
+    .map_err(|err| {
+        log::error!("could not encode checkout request: {}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let response = helpers::send_authorized_form_post(
+        STRIPE_CHECKOUT_URL,
+        stripe.secret_key.expose(),
+        hyper::Body::from(body),
+    )
+    .await
+    .map_err(|err| {
+        log::error!("could not create checkout for team {}: {}", team_id, err);
+        StatusCode::BAD_GATEWAY
+    })?;
+
+    serde_json::from_str::<CheckoutSession>(&response)
+        .map(|session| session.url)
+        .map_err(|err| {
+            log::error!("invalid checkout response: {}: {}", err, response);
+            StatusCode::BAD_GATEWAY
+        })
+}
+
+/// Receives the Stripe events of the subscriptions, upgrading the team once its checkout is
+/// paid and downgrading it once its subscription ends.
+pub async fn webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<StatusCode, StatusCode> {
+    let stripe = state.configs.stripe.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
+    let signature = headers
+        .get("stripe-signature")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    if !verify_signature(
+        signature,
+        &body,
+        stripe.webhook_secret.expose(),
+        Utc::now().timestamp(),
+    ) {
+        log::trace!("stripe signature mismatch");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let event = serde_json::from_str::<WebhookEvent>(&body).map_err(|err| {
+        log::trace!("invalid stripe event: {}", err);
+        StatusCode::BAD_REQUEST
+    })?;
+    let object = event.data.object;
+
+    match event.kind.as_str() {
+        "checkout.session.completed" => {
+            let team_id = object.client_reference_id.ok_or_else(|| {
+                log::error!("checkout {} has no team", object.id);
+                StatusCode::BAD_REQUEST
+            })?;
+            upgrade_plan::execute(
+                state.plan_repo.clone(),
+                upgrade_plan::Request {
+                    team_id: team_id.clone(),
+                    customer_id: object.customer,
+                    subscription_id: object.subscription,
+                },
+            )
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            log::info!("team {} upgraded its plan", team_id);
+        }
+        "customer.subscription.deleted" => {
+            match cancel_plan::execute(
+                state.plan_repo.clone(),
+                cancel_plan::Request {
+                    subscription_id: object.id.clone(),
+                },
+            )
+            .await
+            {
+                Ok(plan) => log::info!("team {} cancelled its plan", plan.team_id),
+                Err(cancel_plan::Error::NotFound) => {
+                    log::warn!("no team has the cancelled subscription {}", object.id)
+                }
+                Err(cancel_plan::Error::Unknown) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+            }
+        }
+        kind => log::trace!("ignoring stripe event {}", kind),
+    }
+
+    Ok(StatusCode::OK)
+}
+
+/// Verifies the `Stripe-Signature` header (i.e. `t=<timestamp>,v1=<signature>`) of the payload,
+/// rejecting the ones signed more than five minutes before `now`.
+fn verify_signature(header: &str, payload: &str, secret: &str, now: i64) -> bool {
+    let mut timestamp = None;
+    let mut signatures = vec![];
+    for (key, value) in header.split(',').filter_map(|part| part.split_once('=')) {
+        match key.trim() {
+            "t" => timestamp = value.parse::<i64>().ok(),
+            "v1" => signatures.push(value),
+            _ => (),
+        }
+    }
+    let timestamp = match timestamp {
+        Some(timestamp) if (now - timestamp).abs() <= SIGNATURE_TOLERANCE_SECONDS => timestamp,
+        _ => return false,
+    };
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+    let expected = hex::encode(mac.finalize().into_bytes());
+
+    signatures.contains(&expected.as_str())
+}
+
+const STRIPE_CHECKOUT_URL: &str = "https://api.stripe.com/v1/checkout/sessions";
+const SIGNATURE_TOLERANCE_SECONDS: i64 = 300;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let payload = r#"{"type":"checkout.session.completed"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"whsec").unwrap();
+        mac.update(format!("1686661200.{}", payload).as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+        let header = format!("t=1686661200,v1=other,v1={}", signature);
+
+        assert!(verify_signature(&header, payload, "whsec", 1686661260));
+        assert!(!verify_signature(&header, payload, "other", 1686661260));
+        assert!(!verify_signature(&header, "{}", "whsec", 1686661260));
+        assert!(!verify_signature(&header, payload, "whsec", 1686662000));
+        assert!(!verify_signature(
+            "v1=signature",
+            payload,
+            "whsec",
+            1686661260
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::domain::helpers::token;
+use crate::repository::store::Store;
+
+use super::client::{Conversation, DndStatus, Error, SlackClient, User, UserGroup, UserProfile};
+
+/// Caches the users and channels of each workspace in front of another client, so that they are
+/// not fetched from Slack on every request. The listings of the users refresh the cached profiles
+/// of each of their users.
+///
+/// The workspaces are told apart by the hash of their tokens, which are never stored.
+pub struct CachedSlackClient {
+    inner: Arc<dyn SlackClient>,
+    store: Arc<dyn Store>,
+    ttl: Duration,
+}
+
+impl CachedSlackClient {
+    pub fn new(inner: Arc<dyn SlackClient>, store: Arc<dyn Store>, ttl: Duration) -> Self {
+        Self { inner, store, ttl }
+    }
+
+    async fn cached<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.store.get(key).await.ok()??;
+        serde_json::from_str(&value)
+            .map_err(|err| log::error!("could not parse cached {}: {}", key, err))
+            .ok()
+    }
+
+    async fn save<T: Serialize>(&self, key: &str, value: &T) {
+        let value = match serde_json::to_string(value) {
+            Ok(value) => value,
+            Err(err) => {
+                log::error!("could not serialize {}: {}", key, err);
+                return;
+            }
+        };
+        if let Err(err) = self.store.set(key, value, self.ttl).await {
+            log::error!("could not cache {}: {:?}", key, err);
+        }
+    }
+}
+
+fn workspace_key(token: &str) -> String {
+    token::hash(token)
+}
+
+fn user_key(token: &str, user: &str) -> String {
+    format!("slack:users:{}:{}", workspace_key(token), user)
+}
+
+fn users_key(token: &str) -> String {
+    format!("slack:users:{}", workspace_key(token))
+}
+
+fn channels_key(token: &str) -> String {
+    format!("slack:channels:{}", workspace_key(token))
+}
+
+/// Drops the cached channels of the workspace of the token, so that they are listed again from
+/// Slack (e.g. right after a channel was created).
+pub async fn forget_channels(store: &dyn Store, token: &str) {
+    if let Err(err) = store.delete(&channels_key(token)).await {
+        log::error!("could not forget the cached channels: {:?}", err);
+    }
+}
+
+#[async_trait]
+impl SlackClient for CachedSlackClient {
+    async fn post_message(&self, token: &str, message: Value) -> Result<String, Error> {
+        self.inner.post_message(token, message).await
+    }
+
+    async fn update_message(&self, token: &str, message: Value) -> Result<(), Error> {
+        self.inner.update_message(token, message).await
+    }
+
+    async fn post_ephemeral(&self, token: &str, message: Value) -> Result<(), Error> {
+        self.inner.post_ephemeral(token, message).await
+    }
+
+    async fn pin_message(&self, token: &str, channel: &str, ts: &str) -> Result<(), Error> {
+        self.inner.pin_message(token, channel, ts).await
+    }
+
+    async fn open_view(&self, token: &str, trigger_id: &str, view: Value) -> Result<(), Error> {
+        self.inner.open_view(token, trigger_id, view).await
+    }
+
+    async fn user_info(&self, token: &str, user: &str) -> Result<User, Error> {
+        let key = user_key(token, user);
+        if let Some(user) = self.cached(&key).await {
+            return Ok(user);
+        }
+        let user = self.inner.user_info(token, user).await?;
+        self.save(&key, &user).await;
+        Ok(user)
+    }
+
+    async fn user_profile(&self, token: &str, user: &str) -> Result<UserProfile, Error> {
+        self.inner.user_profile(token, user).await
+    }
+
+    async fn dnd_info(&self, token: &str, user: &str) -> Result<DndStatus, Error> {
+        self.inner.dnd_info(token, user).await
+    }
+
+    async fn users_list(&self, token: &str) -> Result<Vec<User>, Error> {
+        let key = users_key(token);
+        if let Some(users) = self.cached(&key).await {
+            return Ok(users);
+        }
+        let users = self.inner.users_list(token).await?;
+        self.save(&key, &users).await;
+        for user in users.iter() {
+            self.save(&user_key(token, &user.id), user).await;
+        }
+        Ok(users)
+    }
+
+    async fn usergroups_list(&self, token: &str) -> Result<Vec<UserGroup>, Error> {
+        self.inner.usergroups_list(token).await
+    }
+
+    async fn usergroups_users_list(
+        &self,
+        token: &str,
+        usergroup: &str,
+    ) -> Result<Vec<String>, Error> {
+        self.inner.usergroups_users_list(token, usergroup).await
+    }
+
+    async fn conversations_list(&self, token: &str) -> Result<Vec<Conversation>, Error> {
+        let key = channels_key(token);
+        if let Some(channels) = self.cached(&key).await {
+            return Ok(channels);
+        }
+        let channels = self.inner.conversations_list(token).await?;
+        self.save(&key, &channels).await;
+        Ok(channels)
+    }
+
+    async fn conversations_info(&self, token: &str, channel: &str) -> Result<Conversation, Error> {
+        self.inner.conversations_info(token, channel).await
+    }
+
+    async fn conversations_join(&self, token: &str, channel: &str) -> Result<(), Error> {
+        self.inner.conversations_join(token, channel).await?;
+        // The membership of the bot changed.
+        forget_channels(self.store.as_ref(), token).await;
+        Ok(())
+    }
+
+    async fn upload_file(
+        &self,
+        token: &str,
+        channel: &str,
+        filename: &str,
+        content: String,
+    ) -> Result<(), Error> {
+        self.inner
+            .upload_file(token, channel, filename, content)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::store::MemoryStore;
+    use crate::slack::client::mock::MockSlackClient;
+
+    use super::*;
+
+    fn user(id: &str) -> User {
+        User {
+            id: String::from(id),
+            name: String::from(id),
+            deleted: false,
+            is_bot: false,
+            tz: Some(String::from("Europe/Lisbon")),
+            tz_offset: Some(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_users() {
+        let inner = Arc::new(MockSlackClient {
+            users: vec![user("U1"), user("U2")],
+            ..Default::default()
+        });
+        let client = CachedSlackClient::new(
+            inner.clone(),
+            Arc::new(MemoryStore::default()),
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(client.user_info("xoxb-1", "U1").await.unwrap(), user("U1"));
+        assert_eq!(client.user_info("xoxb-1", "U1").await.unwrap(), user("U1"));
+        assert_eq!(inner.calls().len(), 1);
+
+        // The users are cached per workspace.
+        client.user_info("xoxb-2", "U1").await.unwrap();
+        assert_eq!(inner.calls().len(), 2);
+
+        // The listings refresh the profiles of their users.
+        assert_eq!(client.users_list("xoxb-1").await.unwrap().len(), 2);
+        assert_eq!(client.users_list("xoxb-1").await.unwrap().len(), 2);
+        assert_eq!(client.user_info("xoxb-1", "U2").await.unwrap(), user("U2"));
+        assert_eq!(inner.calls().len(), 3);
+
+        assert!(client.user_info("xoxb-1", "U3").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_channels() {
+        let inner = Arc::new(MockSlackClient {
+            channels: vec![Conversation {
+                id: String::from("C1"),
+                name: String::from("general"),
+                is_private: false,
+                is_member: false,
+            }],
+            ..Default::default()
+        });
+        let store = Arc::new(MemoryStore::default());
+        let client = CachedSlackClient::new(inner.clone(), store.clone(), Duration::from_secs(60));
+
+        assert_eq!(client.conversations_list("xoxb-1").await.unwrap().len(), 1);
+        assert_eq!(client.conversations_list("xoxb-1").await.unwrap().len(), 1);
+        assert_eq!(inner.calls().len(), 1);
+
+        forget_channels(store.as_ref(), "xoxb-1").await;
+        client.conversations_list("xoxb-1").await.unwrap();
+        assert_eq!(inner.calls().len(), 2);
+
+        // Joining a channel changes the membership of the bot.
+        client.conversations_join("xoxb-1", "C1").await.unwrap();
+        client.conversations_list("xoxb-1").await.unwrap();
+        assert_eq!(inner.calls().len(), 4);
+    }
+}
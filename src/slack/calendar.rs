@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use hyper::header;
+use serde::Deserialize;
+
+use crate::{
+    domain::{
+        calendar_feeds::verify_feed, entities::RepeatPeriod, events::find_all_events,
+        helpers::cron::parse_schedule,
+    },
+    error::Error,
+    helpers::date::Date,
+    views::calendar::{self, CalendarEvent},
+};
+
+use super::AppState;
+
+#[derive(Deserialize)]
+pub struct CalendarQuery {
+    pub token: String,
+}
+
+/// Serves the events of a channel as an iCalendar feed, so that they can be subscribed to from
+/// the calendar applications.
+pub async fn execute(
+    State(state): State<Arc<AppState>>,
+    Path(channel): Path<String>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<Response, Error> {
+    verify_feed::execute(
+        state.calendar_feed_repo.clone(),
+        verify_feed::Request {
+            channel: channel.clone(),
+            token: query.token,
+        },
+    )
+    .await
+    .map_err(|err| {
+        match err {
+            verify_feed::Error::Unauthorized => Error::Unauthorized,
+            verify_feed::Error::Unknown => {
+                Error::Internal(String::from("could not verify the calendar token"))
+            }
+        }
+        .with_channel(channel.clone())
+    })?;
+
+    let events = find_all_events::execute(
+        state.event_repo.clone(),
+        find_all_events::Request {
+            channel: channel.clone(),
+            page: None,
+        },
+    )
+    .await
+    .map_err(|err| {
+        Error::Internal(format!("could not find the calendar events: {:?}", err))
+            .with_channel(channel.clone())
+    })?
+    .data;
+
+    let now = Date::now().timestamp();
+    let events = events
+        .into_iter()
+        .flat_map(|event| match &event.repeat {
+            RepeatPeriod::Cron(expression) => {
+                // Cron expressions have no recurrence rule equivalent, so their next occurrences
+                // are listed one by one instead.
+                let schedule = match parse_schedule(expression) {
+                    Ok(schedule) => schedule,
+                    Err(err) => {
+                        log::error!("invalid cron expression on event {}: {}", event.id, err);
+                        return vec![];
+                    }
+                };
+                schedule
+                    .after(
+                        &Date::new(now.max(event.timestamp))
+                            .with_timezone(event.timezone.clone())
+                            .to_datetime(),
+                    )
+                    .map(|date| date.timestamp())
+                    .take_while(|timestamp| {
+                        event.ends_at.is_none_or(|ends_at| *timestamp <= ends_at)
+                    })
+                    .take(MAX_CRON_OCCURRENCES)
+                    .map(|timestamp| CalendarEvent {
+                        uid: format!("{}-{}", event.id, timestamp),
+                        name: event.name.clone(),
+                        start: timestamp,
+                        timezone: event.timezone.clone(),
+                        rrule: None,
+                    })
+                    .collect()
+            }
+            RepeatPeriod::Weekly(..)
+                if event.skip_weekends
+                    && calendar::is_weekend(event.timestamp, event.timezone.clone()) =>
+            {
+                vec![]
+            }
+            repeat => vec![CalendarEvent {
+                uid: event.id.to_string(),
+                name: event.name.clone(),
+                start: event.timestamp,
+                timezone: event.timezone.clone(),
+                rrule: calendar::rrule(
+                    repeat,
+                    event.timestamp,
+                    event.timezone.clone(),
+                    event.ends_at,
+                    event.skip_weekends,
+                ),
+            }],
+        })
+        .collect();
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        calendar::view("Team Event Picker", events),
+    )
+        .into_response())
+}
+
+/// Builds the subscription URL of the calendar of a channel, when the application has a public
+/// URL configured.
+pub fn calendar_url(public_url: &str, channel: &str, token: &str) -> String {
+    format!(
+        "{}/api/v1/channels/{}/events.ics?token={}",
+        public_url.trim_end_matches('/'),
+        channel,
+        token
+    )
+}
+
+/// The number of upcoming occurrences listed for the events repeating with a cron expression.
+const MAX_CRON_OCCURRENCES: usize = 30;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_url() {
+        assert_eq!(
+            calendar_url("https://picker.example.com/", "C123", "abc"),
+            "https://picker.example.com/api/v1/channels/C123/events.ics?token=abc"
+        );
+    }
+}
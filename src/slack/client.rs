@@ -0,0 +1,734 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::domain::timezone::Timezone;
+
+use super::helpers;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent.
+    Request(String),
+    /// The response is not the expected one.
+    Response(String),
+    /// Slack refused the request (e.g. `not_in_channel`).
+    Api(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Request(err) => write!(f, "request failed: {}", err),
+            Error::Response(err) => write!(f, "invalid response: {}", err),
+            Error::Api(err) => write!(f, "slack error: {}", err),
+        }
+    }
+}
+
+impl Error {
+    /// Whether sending the request again would fail the same way (e.g. the token was revoked or
+    /// the channel archived), so that it is not retried.
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, Error::Api(code) if PERMANENT_ERRORS.contains(&code.as_str()))
+    }
+}
+
+impl From<Error> for crate::error::Error {
+    fn from(value: Error) -> Self {
+        Self::Slack(value.to_string())
+    }
+}
+
+/// The errors of the Slack API that are not solved by retrying the request.
+const PERMANENT_ERRORS: [&str; 10] = [
+    "account_inactive",
+    "channel_not_found",
+    "invalid_auth",
+    "is_archived",
+    "missing_scope",
+    "not_authed",
+    "not_in_channel",
+    "team_access_not_granted",
+    "token_expired",
+    "token_revoked",
+];
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct User {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub is_bot: bool,
+    pub tz: Option<String>,
+    pub tz_offset: Option<i32>,
+}
+
+/// The status a user set on their profile.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct UserProfile {
+    #[serde(default)]
+    pub status_text: String,
+    /// The emoji of the status, by its name (e.g. `:palm_tree:`).
+    #[serde(default)]
+    pub status_emoji: String,
+    /// When the status is cleared, or zero when it is kept until the user changes it.
+    #[serde(default)]
+    pub status_expiration: i64,
+}
+
+/// The do not disturb window of a user, either the one scheduled on their preferences or the one
+/// they snoozed the notifications for.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DndStatus {
+    #[serde(default)]
+    pub dnd_enabled: bool,
+    /// The timestamps of the next (or current) scheduled window.
+    #[serde(default)]
+    pub next_dnd_start_ts: i64,
+    #[serde(default)]
+    pub next_dnd_end_ts: i64,
+    #[serde(default)]
+    pub snooze_enabled: bool,
+    #[serde(default)]
+    pub snooze_endtime: i64,
+}
+
+impl DndStatus {
+    /// When the user can be notified again, if they are not to be disturbed at the timestamp.
+    pub fn ends_at(&self, now: i64) -> Option<i64> {
+        let scheduled =
+            (self.dnd_enabled && self.next_dnd_start_ts <= now && now < self.next_dnd_end_ts)
+                .then_some(self.next_dnd_end_ts);
+        let snoozed =
+            (self.snooze_enabled && now < self.snooze_endtime).then_some(self.snooze_endtime);
+        scheduled.max(snoozed)
+    }
+}
+
+/// A channel of the workspace.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Conversation {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub is_private: bool,
+    /// Whether the bot is a member of the channel, and so can post on it.
+    #[serde(default)]
+    pub is_member: bool,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct UserGroup {
+    pub id: String,
+    /// The name the group is mentioned by (e.g. `backend-team` for `@backend-team`).
+    pub handle: String,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// The methods of the Slack Web API used by the application.
+#[allow(dead_code)]
+#[async_trait]
+pub trait SlackClient: Send + Sync {
+    /// Posts a message, returning its timestamp so it can be replied on in a thread.
+    async fn post_message(&self, token: &str, message: Value) -> Result<String, Error>;
+
+    /// Updates a message. The message holds the channel and the timestamp of the updated one.
+    async fn update_message(&self, token: &str, message: Value) -> Result<(), Error>;
+
+    /// Posts a message only visible to a user of its channel. The message holds both of them.
+    async fn post_ephemeral(&self, token: &str, message: Value) -> Result<(), Error>;
+
+    /// Pins a message on its channel.
+    async fn pin_message(&self, token: &str, channel: &str, ts: &str) -> Result<(), Error>;
+
+    /// Opens a modal view for the interaction of the trigger.
+    async fn open_view(&self, token: &str, trigger_id: &str, view: Value) -> Result<(), Error>;
+
+    /// Finds the profile of a user.
+    async fn user_info(&self, token: &str, user: &str) -> Result<User, Error>;
+
+    /// Finds the status a user set on their profile. Unlike their details, it changes often, so
+    /// it is always fetched from Slack.
+    async fn user_profile(&self, token: &str, user: &str) -> Result<UserProfile, Error>;
+
+    /// Finds the do not disturb window of a user, which is always fetched from Slack as well.
+    async fn dnd_info(&self, token: &str, user: &str) -> Result<DndStatus, Error>;
+
+    /// Lists all the users of the workspace.
+    async fn users_list(&self, token: &str) -> Result<Vec<User>, Error>;
+
+    /// Lists the user groups of the workspace, along with their users.
+    async fn usergroups_list(&self, token: &str) -> Result<Vec<UserGroup>, Error>;
+
+    /// Lists the IDs of the users of a user group.
+    async fn usergroups_users_list(
+        &self,
+        token: &str,
+        usergroup: &str,
+    ) -> Result<Vec<String>, Error>;
+
+    /// Lists all the channels of the workspace the bot can see, except the archived ones.
+    async fn conversations_list(&self, token: &str) -> Result<Vec<Conversation>, Error>;
+
+    /// Finds a channel. The private channels are only found when the bot is a member of them.
+    async fn conversations_info(&self, token: &str, channel: &str) -> Result<Conversation, Error>;
+
+    /// Joins a public channel, so that the bot can post on it.
+    async fn conversations_join(&self, token: &str, channel: &str) -> Result<(), Error>;
+
+    /// Shares a file with the content on a channel.
+    async fn upload_file(
+        &self,
+        token: &str,
+        channel: &str,
+        filename: &str,
+        content: String,
+    ) -> Result<(), Error>;
+}
+
+/// The client sending the requests to the Slack Web API.
+pub struct HttpSlackClient;
+
+#[derive(Deserialize)]
+struct PostMessageResponse {
+    ts: String,
+}
+
+#[derive(Deserialize)]
+struct UserInfoResponse {
+    user: User,
+}
+
+#[derive(Deserialize)]
+struct UserProfileResponse {
+    profile: UserProfile,
+}
+
+#[derive(Deserialize)]
+struct UsersListResponse {
+    members: Vec<User>,
+    response_metadata: Option<ResponseMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ConversationsListResponse {
+    channels: Vec<Conversation>,
+    response_metadata: Option<ResponseMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ConversationInfoResponse {
+    channel: Conversation,
+}
+
+#[derive(Deserialize)]
+struct UserGroupsListResponse {
+    usergroups: Vec<UserGroup>,
+}
+
+#[derive(Deserialize)]
+struct UserGroupUsersResponse {
+    users: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct UploadUrlResponse {
+    upload_url: String,
+    file_id: String,
+}
+
+#[derive(Deserialize)]
+struct ResponseMetadata {
+    next_cursor: Option<String>,
+}
+
+impl HttpSlackClient {
+    async fn post<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        token: &str,
+        body: Value,
+    ) -> Result<T, Error> {
+        let response = helpers::send_authorized_json_post(
+            &format!("{}/{}", SLACK_API_URL, method),
+            token,
+            hyper::Body::from(body.to_string()),
+        )
+        .await
+        .map_err(|err| Error::Request(err.to_string()))?;
+        parse_response(&response)
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        token: &str,
+        query: &str,
+    ) -> Result<T, Error> {
+        let response =
+            helpers::send_authorized_get(&format!("{}/{}?{}", SLACK_API_URL, method, query), token)
+                .await
+                .map_err(|err| Error::Request(err.to_string()))?;
+        parse_response(&response)
+    }
+}
+
+#[async_trait]
+impl SlackClient for HttpSlackClient {
+    async fn post_message(&self, token: &str, message: Value) -> Result<String, Error> {
+        let response: PostMessageResponse = self.post("chat.postMessage", token, message).await?;
+        Ok(response.ts)
+    }
+
+    async fn update_message(&self, token: &str, message: Value) -> Result<(), Error> {
+        self.post::<Value>("chat.update", token, message).await?;
+        Ok(())
+    }
+
+    async fn post_ephemeral(&self, token: &str, message: Value) -> Result<(), Error> {
+        self.post::<Value>("chat.postEphemeral", token, message)
+            .await?;
+        Ok(())
+    }
+
+    async fn pin_message(&self, token: &str, channel: &str, ts: &str) -> Result<(), Error> {
+        let body = json!({ "channel": channel, "timestamp": ts });
+        self.post::<Value>("pins.add", token, body).await?;
+        Ok(())
+    }
+
+    async fn open_view(&self, token: &str, trigger_id: &str, view: Value) -> Result<(), Error> {
+        let body = json!({ "trigger_id": trigger_id, "view": view });
+        self.post::<Value>("views.open", token, body).await?;
+        Ok(())
+    }
+
+    async fn user_info(&self, token: &str, user: &str) -> Result<User, Error> {
+        let response: UserInfoResponse = self
+            .get("users.info", token, &format!("user={}", user))
+            .await?;
+        Ok(response.user)
+    }
+
+    async fn user_profile(&self, token: &str, user: &str) -> Result<UserProfile, Error> {
+        let response: UserProfileResponse = self
+            .get("users.profile.get", token, &format!("user={}", user))
+            .await?;
+        Ok(response.profile)
+    }
+
+    async fn dnd_info(&self, token: &str, user: &str) -> Result<DndStatus, Error> {
+        self.get("dnd.info", token, &format!("user={}", user)).await
+    }
+
+    async fn users_list(&self, token: &str) -> Result<Vec<User>, Error> {
+        let mut users = vec![];
+        let mut cursor = String::new();
+        loop {
+            let response: UsersListResponse = self
+                .get(
+                    "users.list",
+                    token,
+                    &page_query(&[("limit", &USERS_PAGE_SIZE.to_string())], &cursor)?,
+                )
+                .await?;
+            users.extend(response.members);
+
+            cursor = next_cursor(response.response_metadata);
+            if cursor.is_empty() {
+                return Ok(users);
+            }
+        }
+    }
+
+    async fn usergroups_list(&self, token: &str) -> Result<Vec<UserGroup>, Error> {
+        let response: UserGroupsListResponse = self
+            .get("usergroups.list", token, "include_users=true")
+            .await?;
+        Ok(response.usergroups)
+    }
+
+    async fn usergroups_users_list(
+        &self,
+        token: &str,
+        usergroup: &str,
+    ) -> Result<Vec<String>, Error> {
+        let query = serde_urlencoded::to_string([("usergroup", usergroup)])
+            .map_err(|err| Error::Request(err.to_string()))?;
+        let response: UserGroupUsersResponse =
+            self.get("usergroups.users.list", token, &query).await?;
+        Ok(response.users)
+    }
+
+    async fn conversations_list(&self, token: &str) -> Result<Vec<Conversation>, Error> {
+        let mut channels = vec![];
+        let mut cursor = String::new();
+        loop {
+            let query = page_query(
+                &[
+                    ("types", "public_channel,private_channel"),
+                    ("exclude_archived", "true"),
+                    ("limit", &CONVERSATIONS_PAGE_SIZE.to_string()),
+                ],
+                &cursor,
+            )?;
+            let response: ConversationsListResponse =
+                self.get("conversations.list", token, &query).await?;
+            channels.extend(response.channels);
+
+            cursor = next_cursor(response.response_metadata);
+            if cursor.is_empty() {
+                return Ok(channels);
+            }
+        }
+    }
+
+    async fn conversations_info(&self, token: &str, channel: &str) -> Result<Conversation, Error> {
+        let query = serde_urlencoded::to_string([("channel", channel)])
+            .map_err(|err| Error::Request(err.to_string()))?;
+        let response: ConversationInfoResponse =
+            self.get("conversations.info", token, &query).await?;
+        Ok(response.channel)
+    }
+
+    async fn conversations_join(&self, token: &str, channel: &str) -> Result<(), Error> {
+        let body = json!({ "channel": channel });
+        self.post::<Value>("conversations.join", token, body)
+            .await?;
+        Ok(())
+    }
+
+    async fn upload_file(
+        &self,
+        token: &str,
+        channel: &str,
+        filename: &str,
+        content: String,
+    ) -> Result<(), Error> {
+        // The file is sent to the URL Slack reserves for it, and only then shared on the channel.
+        let query = serde_urlencoded::to_string([
+            ("filename", filename),
+            ("length", &content.len().to_string()),
+        ])
+        .map_err(|err| Error::Request(err.to_string()))?;
+        let upload: UploadUrlResponse = self
+            .get("files.getUploadURLExternal", token, &query)
+            .await?;
+
+        helpers::send_post_with_type(
+            &upload.upload_url,
+            hyper::Body::from(content),
+            String::from("application/octet-stream"),
+        )
+        .await
+        .map_err(|err| Error::Request(err.to_string()))?;
+
+        let body = json!({
+            "files": [{ "id": upload.file_id, "title": filename }],
+            "channel_id": channel,
+        });
+        self.post::<Value>("files.completeUploadExternal", token, body)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Parses the response of a Slack API method, failing with the error Slack replied with when it is
+/// not ok.
+/// Builds the query of a page of a listing, starting at the cursor of the previous page, if any.
+/// The cursors are encoded, as they may end with `=`.
+fn page_query(params: &[(&str, &str)], cursor: &str) -> Result<String, Error> {
+    let mut params = params.to_vec();
+    if !cursor.is_empty() {
+        params.push(("cursor", cursor));
+    }
+    serde_urlencoded::to_string(params).map_err(|err| Error::Request(err.to_string()))
+}
+
+/// The cursor of the next page of a listing, empty on the last page.
+fn next_cursor(metadata: Option<ResponseMetadata>) -> String {
+    metadata
+        .and_then(|metadata| metadata.next_cursor)
+        .unwrap_or_default()
+}
+
+fn parse_response<T: DeserializeOwned>(response: &str) -> Result<T, Error> {
+    let value: Value =
+        serde_json::from_str(response).map_err(|err| Error::Response(err.to_string()))?;
+    if !value["ok"].as_bool().unwrap_or(false) {
+        return Err(Error::Api(
+            value["error"]
+                .as_str()
+                .unwrap_or("unknown_error")
+                .to_string(),
+        ));
+    }
+    serde_json::from_value(value).map_err(|err| Error::Response(err.to_string()))
+}
+
+/// Reads the timezone set on the Slack profile of the user.
+///
+/// Returns `None` when the profile could not be fetched or has no matching timezone.
+pub async fn get_user_timezone(
+    client: &dyn SlackClient,
+    token: &str,
+    user: &str,
+) -> Option<Timezone> {
+    let info = client
+        .user_info(token, user)
+        .await
+        .map_err(|err| log::error!("unable to fetch user {} info: {}", user, err))
+        .ok()?;
+    Timezone::find(&info.tz.unwrap_or_default(), info.tz_offset?)
+}
+
+const SLACK_API_URL: &str = "https://slack.com/api";
+const USERS_PAGE_SIZE: u32 = 200;
+/// The number of channels of each page, the most Slack allows.
+const CONVERSATIONS_PAGE_SIZE: u32 = 1000;
+
+#[cfg(test)]
+pub mod mock {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// A client that records the calls instead of sending them, replying with the configured
+    /// users and user groups.
+    #[derive(Default)]
+    pub struct MockSlackClient {
+        pub users: Vec<User>,
+        /// The profiles of the users, by their IDs.
+        pub profiles: Vec<(String, UserProfile)>,
+        /// The do not disturb windows of the users, by their IDs.
+        pub dnd: Vec<(String, DndStatus)>,
+        pub user_groups: Vec<UserGroup>,
+        pub channels: Vec<Conversation>,
+        pub calls: Mutex<Vec<(String, Value)>>,
+    }
+
+    impl MockSlackClient {
+        pub fn calls(&self) -> Vec<(String, Value)> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn record(&self, method: &str, body: Value) {
+            self.calls.lock().unwrap().push((method.to_string(), body));
+        }
+    }
+
+    #[async_trait]
+    impl SlackClient for MockSlackClient {
+        async fn post_message(&self, _token: &str, message: Value) -> Result<String, Error> {
+            self.record("chat.postMessage", message);
+            Ok(String::from("1686661200.000100"))
+        }
+
+        async fn update_message(&self, _token: &str, message: Value) -> Result<(), Error> {
+            self.record("chat.update", message);
+            Ok(())
+        }
+
+        async fn post_ephemeral(&self, _token: &str, message: Value) -> Result<(), Error> {
+            self.record("chat.postEphemeral", message);
+            Ok(())
+        }
+
+        async fn pin_message(&self, _token: &str, channel: &str, ts: &str) -> Result<(), Error> {
+            self.record("pins.add", json!({ "channel": channel, "timestamp": ts }));
+            Ok(())
+        }
+
+        async fn open_view(
+            &self,
+            _token: &str,
+            trigger_id: &str,
+            view: Value,
+        ) -> Result<(), Error> {
+            self.record(
+                "views.open",
+                json!({ "trigger_id": trigger_id, "view": view }),
+            );
+            Ok(())
+        }
+
+        async fn user_info(&self, _token: &str, user: &str) -> Result<User, Error> {
+            self.record("users.info", json!({ "user": user }));
+            self.users
+                .iter()
+                .find(|u| u.id == user)
+                .cloned()
+                .ok_or(Error::Api(String::from("user_not_found")))
+        }
+
+        async fn user_profile(&self, _token: &str, user: &str) -> Result<UserProfile, Error> {
+            self.record("users.profile.get", json!({ "user": user }));
+            Ok(self
+                .profiles
+                .iter()
+                .find(|(id, _)| id == user)
+                .map(|(_, profile)| profile.clone())
+                .unwrap_or_default())
+        }
+
+        async fn dnd_info(&self, _token: &str, user: &str) -> Result<DndStatus, Error> {
+            self.record("dnd.info", json!({ "user": user }));
+            Ok(self
+                .dnd
+                .iter()
+                .find(|(id, _)| id == user)
+                .map(|(_, dnd)| dnd.clone())
+                .unwrap_or_default())
+        }
+
+        async fn users_list(&self, _token: &str) -> Result<Vec<User>, Error> {
+            self.record("users.list", json!({}));
+            Ok(self.users.clone())
+        }
+
+        async fn usergroups_list(&self, _token: &str) -> Result<Vec<UserGroup>, Error> {
+            self.record("usergroups.list", json!({}));
+            Ok(self.user_groups.clone())
+        }
+
+        async fn usergroups_users_list(
+            &self,
+            _token: &str,
+            usergroup: &str,
+        ) -> Result<Vec<String>, Error> {
+            self.record("usergroups.users.list", json!({ "usergroup": usergroup }));
+            self.user_groups
+                .iter()
+                .find(|group| group.id == usergroup)
+                .map(|group| group.users.clone())
+                .ok_or(Error::Api(String::from("no_such_subteam")))
+        }
+
+        async fn conversations_list(&self, _token: &str) -> Result<Vec<Conversation>, Error> {
+            self.record("conversations.list", json!({}));
+            Ok(self.channels.clone())
+        }
+
+        async fn conversations_info(
+            &self,
+            _token: &str,
+            channel: &str,
+        ) -> Result<Conversation, Error> {
+            self.record("conversations.info", json!({ "channel": channel }));
+            self.channels
+                .iter()
+                .find(|c| c.id == channel)
+                .cloned()
+                .ok_or(Error::Api(String::from("channel_not_found")))
+        }
+
+        async fn conversations_join(&self, _token: &str, channel: &str) -> Result<(), Error> {
+            self.record("conversations.join", json!({ "channel": channel }));
+            Ok(())
+        }
+
+        async fn upload_file(
+            &self,
+            _token: &str,
+            channel: &str,
+            filename: &str,
+            content: String,
+        ) -> Result<(), Error> {
+            self.record(
+                "files.upload",
+                json!({ "channel": channel, "filename": filename, "content": content }),
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mock::MockSlackClient, *};
+
+    #[test]
+    fn test_parse_response() {
+        let response: PostMessageResponse =
+            parse_response(r#"{"ok":true,"ts":"1686661200.000100"}"#).unwrap();
+        assert_eq!(response.ts, "1686661200.000100");
+
+        let err = parse_response::<Value>(r#"{"ok":false,"error":"not_in_channel"}"#).unwrap_err();
+        assert!(matches!(err, Error::Api(err) if err == "not_in_channel"));
+    }
+
+    #[test]
+    fn test_page_query() {
+        assert_eq!(page_query(&[("limit", "200")], "").unwrap(), "limit=200");
+        assert_eq!(
+            page_query(&[("limit", "200")], "dXNlcjpVMDYxTkZUVDI=").unwrap(),
+            "limit=200&cursor=dXNlcjpVMDYxTkZUVDI%3D"
+        );
+        assert_eq!(
+            next_cursor(Some(ResponseMetadata {
+                next_cursor: Some(String::from("abc="))
+            })),
+            "abc="
+        );
+        assert_eq!(next_cursor(None), "");
+    }
+
+    #[test]
+    fn test_is_permanent() {
+        assert!(Error::Api(String::from("token_revoked")).is_permanent());
+        assert!(Error::Api(String::from("is_archived")).is_permanent());
+        assert!(!Error::Api(String::from("ratelimited")).is_permanent());
+        assert!(!Error::Request(String::from("connection reset")).is_permanent());
+    }
+
+    #[test]
+    fn test_dnd_ends_at() {
+        let now = 1724681700;
+        let scheduled = DndStatus {
+            dnd_enabled: true,
+            next_dnd_start_ts: now - 3600,
+            next_dnd_end_ts: now + 3600,
+            ..Default::default()
+        };
+        assert_eq!(scheduled.ends_at(now), Some(now + 3600));
+        assert_eq!(scheduled.ends_at(now - 7200), None);
+        assert_eq!(scheduled.ends_at(now + 3600), None);
+
+        let snoozed = DndStatus {
+            snooze_enabled: true,
+            snooze_endtime: now + 7200,
+            ..scheduled.clone()
+        };
+        assert_eq!(snoozed.ends_at(now), Some(now + 7200));
+        assert_eq!(DndStatus::default().ends_at(now), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_timezone() {
+        let client = MockSlackClient {
+            users: vec![User {
+                id: String::from("U1"),
+                name: String::from("john"),
+                deleted: false,
+                is_bot: false,
+                tz: Some(String::from("Europe/Paris")),
+                tz_offset: Some(3600),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_user_timezone(&client, "token", "U1").await,
+            Some(Timezone::ECT)
+        );
+        assert_eq!(get_user_timezone(&client, "token", "U2").await, None);
+    }
+}
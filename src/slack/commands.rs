@@ -1,54 +1,139 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use axum::{
     extract::State,
     response::{IntoResponse, Response},
-    Json,
 };
+use chrono::NaiveDate;
 use hyper::HeaderMap;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::json;
 
 use crate::{
     domain::{
+        billing::find_plan,
+        calendar_feeds::{reset_feed, share_feed},
         commands::repick_participant,
         commands::{self, pick_participant},
+        date_format::{Clock, DateFormat, DateOrder},
+        entities::{AuditAction, IntegrationProvider, PickSource, PlanTier, TeamSettings},
+        events::{
+            clone_event, create_event, export_events, find_all_events, find_next_picks,
+            import_events, pick_participant as pick_event, set_participant_away,
+        },
+        holidays::{add_holiday, find_holidays, remove_holiday},
+        integrations::delete_integration,
+        locale::Locale,
+        participants::{join, leave},
+        settings::{
+            find_settings, set_acknowledge_minutes, set_date_format, set_locale, set_monthly_recap,
+            set_no_show_boost, set_pick_message, set_quiet_hours,
+        },
+        timezone::Timezone,
+    },
+    helpers::{
+        date::Date,
+        reporter::{self, Report},
+    },
+    i18n,
+    repository::{audit, event::Repository, holiday, integration, pick_history, plan, settings},
+    scheduler::{entities::EventSchedule, Scheduler, SchedulerDate},
+    views::{
+        help,
+        pick_participant::mention_picked,
+        reminder,
+        schedule::{self, ScheduleDay, ScheduleView, ScheduledPick},
     },
-    repository::event::Repository,
 };
 
-use super::{templates, AppState};
+use super::{
+    activity, api, billing, calendar,
+    client::{self, SlackClient},
+    google, sender, templates, AppConfigs, AppState,
+};
 
 /// Slack command
 #[derive(Deserialize, Debug)]
 pub struct CommandRequest {
+    pub team_id: String,
     pub channel_id: String,
     pub text: String,
     pub response_url: String,
     pub user_id: String,
+    /// The app the command was sent to, when several are served.
+    #[serde(default)]
+    pub api_app_id: Option<String>,
 }
 
+/// Acknowledges the command right away, running it on the background and replying through its
+/// response URL, since Slack shows a timeout for the commands not acknowledged within 3 seconds.
 pub async fn execute(
     headers: HeaderMap,
     State(state): State<Arc<AppState>>,
     body: String,
 ) -> Result<Response, hyper::StatusCode> {
-    log::trace!("received command: \n{:?} \n{}", headers, body);
+    let payload = serde_urlencoded::from_str::<CommandRequest>(&body).map_err(|err| {
+        log::trace!("could not parse command request: {}", err);
+        hyper::StatusCode::BAD_REQUEST
+    })?;
+
+    let span = tracing::Span::current();
+    span.record("team_id", payload.team_id.as_str());
+    span.record("channel", payload.channel_id.as_str());
+    tracing::trace!(
+        user_id = payload.user_id.as_str(),
+        command = payload.text.trim().split(' ').next(),
+        "received command"
+    );
 
-    let payload = serde_urlencoded::from_str::<CommandRequest>(&body).unwrap();
+    let response_url = payload.response_url.clone();
+    let locale = super::find_locale(&headers);
+    super::spawn_with_reply(response_url, locale, run(headers, state, payload));
+    Ok(().into_response())
+}
+
+async fn run(
+    headers: HeaderMap,
+    state: Arc<AppState>,
+    payload: CommandRequest,
+) -> Result<(), hyper::StatusCode> {
     let args = payload.text.trim();
     let space_idx = args.find(' ').unwrap_or(args.len());
 
     let reached_limit = super::find_reached_limit(&headers)?;
+    let locale = super::find_locale(&headers);
+    let date_format = super::find_date_format(&headers);
+    let report = Report::new("commands", format!("command {} failed", &args[..space_idx]))
+        .with_team(payload.team_id.clone())
+        .with_channel(payload.channel_id.clone());
 
     let result = match &args[..space_idx] {
-        "list" => handle_list(state.event_repo.clone(), payload.channel_id, reached_limit).await,
-        "create" => handle_create(),
+        "list" => {
+            handle_list(
+                state.event_repo.clone(),
+                payload.channel_id,
+                reached_limit,
+                date_format,
+                locale,
+            )
+            .await
+        }
+        "create" => {
+            handle_create(
+                state.slack_client.as_ref(),
+                super::find_token(&headers)?,
+                payload.user_id,
+                locale,
+            )
+            .await
+        }
         "edit" => {
             handle_edit(
                 state.event_repo.clone(),
                 payload.channel_id,
                 &args[space_idx..].trim(),
+                locale,
             )
             .await
         }
@@ -57,6 +142,43 @@ pub async fn execute(
                 state.event_repo.clone(),
                 payload.channel_id,
                 &args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "clone" => {
+            handle_clone(
+                state.event_repo.clone(),
+                state.audit_repo.clone(),
+                state.scheduler.clone(),
+                super::find_max_events(&headers)?,
+                &payload,
+                args[space_idx..].trim(),
+                date_format,
+                locale,
+            )
+            .await
+        }
+        "export" => {
+            handle_export(
+                state.event_repo.clone(),
+                state.slack_client.as_ref(),
+                super::find_token(&headers)?,
+                payload.channel_id,
+                locale,
+            )
+            .await
+        }
+        "import" => {
+            handle_import(
+                state.event_repo.clone(),
+                state.audit_repo.clone(),
+                state.scheduler.clone(),
+                super::find_max_events(&headers)?,
+                super::find_max_participants(&headers)?,
+                &payload,
+                args[space_idx..].trim(),
+                locale,
             )
             .await
         }
@@ -65,32 +187,235 @@ pub async fn execute(
                 state.event_repo.clone(),
                 payload.channel_id,
                 &args[space_idx..].trim(),
+                date_format,
+                locale,
             )
             .await
         }
         "pick" => {
-            handle_pick(
+            let settings =
+                sender::find_settings(state.settings_repo.clone(), &payload.team_id).await;
+            let result = handle_pick(
                 state.event_repo.clone(),
+                state.history_repo.clone(),
                 payload.response_url.clone(),
-                payload.channel_id,
-                payload.user_id,
+                payload.channel_id.clone(),
+                payload.user_id.clone(),
                 &args[space_idx..].trim(),
+                &settings,
             )
-            .await
+            .await;
+            if result.is_ok() {
+                record_action(
+                    state.audit_repo.clone(),
+                    &payload,
+                    AuditAction::Pick,
+                    args[space_idx..].trim(),
+                )
+                .await;
+                announce_pick(
+                    &state,
+                    super::find_token(&headers)?,
+                    &payload,
+                    args[space_idx..].trim(),
+                    locale,
+                )
+                .await;
+            }
+            result
         }
         "repick" => {
-            handle_repick(
+            let settings =
+                sender::find_settings(state.settings_repo.clone(), &payload.team_id).await;
+            let result = handle_repick(
                 state.event_repo.clone(),
+                state.history_repo.clone(),
                 payload.response_url.clone(),
+                payload.channel_id.clone(),
+                payload.user_id.clone(),
+                args[space_idx..].trim(),
+                &settings,
+            )
+            .await;
+            if result.is_ok() {
+                record_action(
+                    state.audit_repo.clone(),
+                    &payload,
+                    AuditAction::Repick,
+                    args[space_idx..].trim(),
+                )
+                .await;
+                announce_pick(
+                    &state,
+                    super::find_token(&headers)?,
+                    &payload,
+                    args[space_idx..].trim(),
+                    locale,
+                )
+                .await;
+            }
+            result
+        }
+        "next" => {
+            handle_next(
+                state.event_repo.clone(),
+                payload.channel_id,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "preview" => {
+            handle_preview(
+                state.event_repo.clone(),
+                state.history_repo.clone(),
+                payload.channel_id,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "schedule" => {
+            handle_schedule(
+                state.event_repo.clone(),
+                state.holiday_repo.clone(),
+                state.slack_client.as_ref(),
+                super::find_token(&headers)?,
+                &payload,
+                date_format,
+                locale,
+            )
+            .await
+        }
+        "audit" => {
+            handle_audit(
+                state.audit_repo.clone(),
+                payload.team_id,
+                payload.channel_id,
+                date_format,
+                locale,
+            )
+            .await
+        }
+        "history" => {
+            handle_history(
+                state.event_repo.clone(),
+                state.history_repo.clone(),
+                payload.channel_id,
+                args[space_idx..].trim(),
+                date_format,
+                locale,
+            )
+            .await
+        }
+        "stats" => {
+            handle_stats(
+                state.event_repo.clone(),
+                state.history_repo.clone(),
+                payload.channel_id,
+                args[space_idx..].trim(),
+                date_format,
+                locale,
+            )
+            .await
+        }
+        "away" => {
+            handle_away(
+                state.event_repo.clone(),
                 payload.channel_id,
                 payload.user_id,
-                &args[space_idx..].trim(),
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "join" => {
+            handle_join(
+                state.event_repo.clone(),
+                state.audit_repo.clone(),
+                &payload,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "leave" => {
+            handle_leave(
+                state.event_repo.clone(),
+                state.audit_repo.clone(),
+                &payload,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "holiday" => {
+            handle_holiday(
+                state.holiday_repo.clone(),
+                state.scheduler.clone(),
+                payload.team_id,
+                args[space_idx..].trim(),
+                locale,
             )
             .await
         }
-        "help" => handle_help(&args[space_idx..].trim()),
+        "quiet" => {
+            handle_quiet(
+                state.settings_repo.clone(),
+                state.scheduler.clone(),
+                state.slack_client.as_ref(),
+                super::find_token(&headers)?,
+                payload.team_id,
+                payload.user_id,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "locale" => {
+            handle_locale(
+                state.settings_repo.clone(),
+                payload.team_id,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "config" => {
+            handle_config(
+                state.settings_repo.clone(),
+                payload.team_id,
+                args[space_idx..].trim(),
+                locale,
+            )
+            .await
+        }
+        "calendar" => {
+            handle_calendar(state.clone(), &payload, args[space_idx..].trim(), locale).await
+        }
+        "token" => {
+            handle_token(
+                state.clone(),
+                payload.user_id,
+                payload.team_id,
+                payload.channel_id,
+                locale,
+            )
+            .await
+        }
+        "upgrade" => {
+            handle_upgrade(
+                state.plan_repo.clone(),
+                &state.configs,
+                payload.api_app_id.as_deref(),
+                payload.team_id,
+                locale,
+            )
+            .await
+        }
+        "help" => handle_help(&args[space_idx..], reached_limit),
         _ => {
-            let err = super::to_response_error(UNKNOWN_COMMAND_STR)?;
+            let err = super::to_response_error(i18n::t(locale, "unknown_command"))?;
 
             super::send_post(&payload.response_url, hyper::Body::from(err))
                 .await
@@ -99,117 +424,339 @@ pub async fn execute(
                     hyper::StatusCode::INTERNAL_SERVER_ERROR
                 })?;
 
-            return Err(hyper::StatusCode::BAD_REQUEST);
+            return Ok(());
         }
     };
 
     let result = match result {
         Ok(result) => result,
         Err(err) => {
-            let err = format!(
-                "Error {}: {}.",
-                err.as_str(),
-                err.canonical_reason().unwrap_or("Unknown")
-            );
-            let err = super::to_response_error(&err)?;
-
-            super::send_post(&payload.response_url, hyper::Body::from(err))
-                .await
-                .map_err(|err| {
-                    log::error!("unable to send slack error response: {}", err);
-                    hyper::StatusCode::INTERNAL_SERVER_ERROR
-                })?;
-            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+            if err == hyper::StatusCode::INTERNAL_SERVER_ERROR {
+                reporter::report(report);
+            }
+            return Err(err);
         }
     };
 
     if result.is_empty() {
-        return Ok((()).into_response());
+        return Ok(());
     }
 
-    match serde_json::from_str::<Value>(&result) {
-        Ok(result) => {
-            log::trace!("command response: {:?}", result);
-            Ok(Json(result).into_response())
-        }
-        Err(err) => {
+    tracing::trace!(bytes = result.len(), "sending command response");
+    super::send_post(&payload.response_url, hyper::Body::from(result))
+        .await
+        .map_err(|err| {
             log::error!("unable to send slack response: {}", err);
-            Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(())
 }
 
 async fn handle_list(
     repo: Arc<dyn Repository>,
     channel: String,
     reached_limit: bool,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<String, hyper::StatusCode> {
-    Ok(commands::list_events::execute(repo, channel, reached_limit)
-        .await?
-        .to_string())
+    Ok(
+        commands::list_events::execute(repo, channel, reached_limit, 0, date_format, locale)
+            .await?
+            .to_string(),
+    )
 }
 
-fn handle_create() -> Result<String, hyper::StatusCode> {
-    Ok(templates::add_event()?)
+async fn handle_create(
+    slack_client: &dyn SlackClient,
+    token: String,
+    user: String,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let timezone = client::get_user_timezone(slack_client, &token, &user)
+        .await
+        .unwrap_or(Timezone::UTC);
+    Ok(templates::add_event(timezone, locale)?)
 }
 
 async fn handle_edit(
     repo: Arc<dyn Repository>,
     channel: String,
     args: &str,
+    locale: Locale,
 ) -> Result<String, hyper::StatusCode> {
     if args.len() == 0 {
-        return Ok(templates::edit_select_event(repo, channel).await?);
+        return Ok(templates::edit_select_event(repo, channel, locale).await?);
     }
 
     let id: u32 = match args.parse() {
         Ok(id) => id,
         Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
     };
-    Ok(templates::edit_event(repo, channel, id).await?)
+    Ok(templates::edit_event(repo, channel, id, locale).await?)
 }
 
 async fn handle_delete(
     repo: Arc<dyn Repository>,
     channel: String,
     args: &str,
+    locale: Locale,
 ) -> Result<String, hyper::StatusCode> {
     if args.len() == 0 {
-        return Ok(templates::delete_select_event(repo, channel).await?);
+        return Ok(templates::delete_select_event(repo, channel, locale).await?);
     }
 
     let id: u32 = match args.parse() {
         Ok(id) => id,
         Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
     };
-    Ok(templates::delete_event(repo, channel, id).await?)
+    Ok(templates::delete_event(repo, channel, id, locale).await?)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_clone(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    scheduler: Arc<Scheduler>,
+    max_events: u32,
+    payload: &CommandRequest,
+    args: &str,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return super::to_response(help::usage("clone")),
+    };
+    let response = clone_event::execute(
+        repo.clone(),
+        clone_event::Request {
+            id,
+            channel: payload.channel_id.clone(),
+            team_id: payload.team_id.clone(),
+            max_events,
+        },
+    )
+    .await
+    .map_err(|err| match err {
+        clone_event::Error::NotFound => hyper::StatusCode::NOT_FOUND,
+        clone_event::Error::Forbidden => hyper::StatusCode::FORBIDDEN,
+        clone_event::Error::Unknown => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+    })?;
+
+    scheduler
+        .insert(EventSchedule {
+            id: response.id,
+            team_id: response.team_id,
+            timestamp: response.timestamp,
+            timezone: response.timezone,
+            repeat: response.repeat,
+            ends_at: response.ends_at,
+            reminder_minutes: response.reminder_minutes,
+            skip_weekends: response.skip_weekends,
+            skipped_dates: response.skipped_dates,
+        })
+        .await;
+    let actor = activity::Actor {
+        team_id: payload.team_id.clone(),
+        channel: Some(payload.channel_id.clone()),
+        user: Some(payload.user_id.clone()),
+    };
+    activity::record(
+        audit_repo,
+        actor,
+        AuditAction::EventCreated,
+        Some(response.id),
+    )
+    .await;
+
+    let channel = payload.channel_id.clone();
+    Ok(templates::show_event(repo, channel, response.id, date_format, locale).await?)
+}
+
+async fn handle_export(
+    repo: Arc<dyn Repository>,
+    slack_client: &dyn SlackClient,
+    token: String,
+    channel: String,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let snapshot = export_events::execute(
+        repo,
+        export_events::Request {
+            channel: channel.clone(),
+        },
+    )
+    .await
+    .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+    if snapshot.events.is_empty() {
+        return super::to_response(i18n::t(locale, "export_empty"));
+    }
+
+    let content = serde_json::to_string_pretty(&snapshot).map_err(|err| {
+        log::error!("could not serialize events of channel {}: {}", channel, err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    slack_client
+        .upload_file(&token, &channel, EXPORT_FILENAME, content)
+        .await
+        .map_err(|err| {
+            log::error!("could not upload events of channel {}: {}", channel, err);
+            hyper::StatusCode::BAD_GATEWAY
+        })?;
+
+    super::to_response(&i18n::format(
+        locale,
+        "export_done",
+        &[
+            ("count", &snapshot.events.len().to_string()),
+            ("file", EXPORT_FILENAME),
+        ],
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_import(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    scheduler: Arc<Scheduler>,
+    max_events: u32,
+    max_participants: u32,
+    payload: &CommandRequest,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    if args.is_empty() {
+        return super::to_response(help::usage("import"));
+    }
+    let response = import_events::execute(
+        repo,
+        import_events::Request {
+            // Slack escapes these characters on the text of the commands.
+            snapshot: args
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&amp;", "&"),
+            channel: payload.channel_id.clone(),
+            team_id: payload.team_id.clone(),
+            max_events,
+            max_participants,
+        },
+    )
+    .await;
+    let response = match response {
+        Ok(response) => response,
+        Err(import_events::Error::BadRequest) => {
+            return super::to_response(i18n::t(locale, "import_invalid"))
+        }
+    };
+
+    let mut message = i18n::t(locale, "import_title").to_string();
+    if !response.created.is_empty() {
+        let events = response
+            .created
+            .iter()
+            .map(|imported| format!("`{}`", imported.name))
+            .collect::<Vec<String>>()
+            .join(", ");
+        message.push('\n');
+        message.push_str(&i18n::format(
+            locale,
+            "import_created",
+            &[
+                ("count", &response.created.len().to_string()),
+                ("events", &events),
+            ],
+        ));
+    }
+    if !response.skipped.is_empty() {
+        let events = response
+            .skipped
+            .iter()
+            .map(|(name, err)| format!("`{}` ({})", name, import_error_reason(locale, err)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        message.push('\n');
+        message.push_str(&i18n::format(
+            locale,
+            "import_skipped",
+            &[
+                ("count", &response.skipped.len().to_string()),
+                ("events", &events),
+            ],
+        ));
+    }
+
+    for imported in response.created {
+        let event = imported.event;
+        scheduler
+            .insert(EventSchedule {
+                id: event.id,
+                team_id: event.team_id,
+                timestamp: event.timestamp,
+                timezone: event.timezone,
+                repeat: event.repeat,
+                ends_at: event.ends_at,
+                reminder_minutes: event.reminder_minutes,
+                skip_weekends: event.skip_weekends,
+                skipped_dates: event.skipped_dates,
+            })
+            .await;
+        let actor = activity::Actor {
+            team_id: payload.team_id.clone(),
+            channel: Some(payload.channel_id.clone()),
+            user: Some(payload.user_id.clone()),
+        };
+        activity::record(
+            audit_repo.clone(),
+            actor,
+            AuditAction::EventCreated,
+            Some(event.id),
+        )
+        .await;
+    }
+
+    super::to_response(&message)
+}
+
+fn import_error_reason(locale: Locale, err: &create_event::Error) -> &'static str {
+    let key = match err {
+        create_event::Error::Invalid(..) => "import_error_invalid",
+        create_event::Error::Forbidden => "import_error_limit",
+        create_event::Error::Conflict => "import_error_conflict",
+        create_event::Error::Unknown => "import_error_unknown",
+    };
+    i18n::t(locale, key)
 }
 
 async fn handle_show(
     repo: Arc<dyn Repository>,
     channel: String,
     args: &str,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<String, hyper::StatusCode> {
     if args.len() == 0 {
-        return Ok(templates::show_select_event(repo, channel).await?);
+        return Ok(templates::show_select_event(repo, channel, locale).await?);
     }
 
     let id: u32 = match args.parse() {
         Ok(id) => id,
         Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
     };
-    Ok(templates::show_event(repo, channel, id).await?)
+    Ok(templates::show_event(repo, channel, id, date_format, locale).await?)
 }
 
 async fn handle_pick(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     response_url: String,
     channel: String,
     user: String,
     args: &str,
+    settings: &TeamSettings,
 ) -> Result<String, hyper::StatusCode> {
     if args.len() == 0 {
-        return Ok(templates::pick_select_event(repo, channel).await?);
+        return Ok(templates::pick_select_event(repo, channel, settings.locale).await?);
     }
 
     let id: u32 = match args.parse() {
@@ -217,107 +764,1048 @@ async fn handle_pick(
         Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
     };
 
-    let response = pick_participant::execute(repo.clone(), id, channel, user, response_url, false)
-        .await?
-        .map_or(String::from(""), |r| r.to_string());
+    let response = pick_participant::execute(
+        repo.clone(),
+        history_repo,
+        id,
+        channel,
+        user,
+        response_url,
+        false,
+        settings,
+    )
+    .await?
+    .map_or(String::from(""), |r| r.to_string());
 
     return Ok(response);
 }
 
 async fn handle_repick(
     repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
     response_url: String,
     channel: String,
     user: String,
     args: &str,
+    settings: &TeamSettings,
 ) -> Result<String, hyper::StatusCode> {
     let id: u32 = match args.parse() {
         Ok(id) => id,
         Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
     };
 
-    let response = repick_participant::execute(repo.clone(), id, channel, user, response_url)
-        .await?
-        .map_or(String::from(""), |r| r.to_string());
+    let response = repick_participant::execute(
+        repo.clone(),
+        history_repo,
+        id,
+        channel,
+        user,
+        response_url,
+        settings,
+    )
+    .await?
+    .map_or(String::from(""), |r| r.to_string());
 
     return Ok(response);
 }
 
-fn handle_help(args: &str) -> Result<String, hyper::StatusCode> {
-    super::to_response(match &args.trim()[..] {
-        "create" => USAGE_ADD_STR,
-        "delete" => USAGE_DELETE_STR,
-        "edit" => USAGE_EDIT_STR,
-        "list" => USAGE_LIST_STR,
-        "pick" => USAGE_PICK_STR,
-        "show" => USAGE_SHOW_STR,
-        _ => USAGE_STR,
+/// Replies with the participants left to pick on the current round of the event, in the order they
+/// are picked in on the round-robin events.
+async fn handle_next(
+    repo: Arc<dyn Repository>,
+    channel: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return super::to_response(help::usage("next")),
+    };
+    let req = find_next_picks::Request { event: id, channel };
+    let response = match find_next_picks::execute(repo, req).await {
+        Ok(response) => response,
+        Err(find_next_picks::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(find_next_picks::Error::Unknown) => {
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+
+    let title = match response.new_round {
+        true => "next_picks_new_round",
+        false => "next_picks_left",
+    };
+    let mut text = i18n::format(locale, title, &[("event", &response.name)]);
+    text.push('\n');
+    if response.left.is_empty() {
+        text.push_str(i18n::t(locale, "next_picks_none"));
+    } else if response.round_robin {
+        let order = response
+            .left
+            .iter()
+            .enumerate()
+            .map(|(i, user)| format!("{}. <@{}>", i + 1, user))
+            .collect::<Vec<String>>();
+        text.push_str(&order.join("\n"));
+    } else {
+        text.push_str(&mentions(&response.left));
+    }
+    if !response.away.is_empty() {
+        text.push('\n');
+        text.push_str(&i18n::format(
+            locale,
+            "next_picks_away",
+            &[("users", &mentions(&response.away))],
+        ));
+    }
+    super::to_response(&text)
+}
+
+/// Replies with the automatic picks of the channel's events in the next weeks, grouped by day on
+/// the user's timezone.
+async fn handle_schedule(
+    repo: Arc<dyn Repository>,
+    holiday_repo: Arc<dyn holiday::Repository>,
+    slack_client: &dyn SlackClient,
+    token: String,
+    payload: &CommandRequest,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let events = find_all_events::execute(
+        repo,
+        find_all_events::Request {
+            channel: payload.channel_id.clone(),
+            page: None,
+        },
+    )
+    .await
+    .map_err(|err| {
+        log::error!("could not find the scheduled events: {:?}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .data;
+    let holidays: Vec<NaiveDate> = find_holidays::execute(
+        holiday_repo,
+        find_holidays::Request {
+            team_id: payload.team_id.clone(),
+        },
+    )
+    .await
+    .map_err(|err| {
+        log::error!("could not find the holidays of the schedule: {:?}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .data
+    .iter()
+    .filter_map(|holiday| holiday.naive_date())
+    .collect();
+    let timezone = client::get_user_timezone(slack_client, &token, &payload.user_id)
+        .await
+        .unwrap_or(Timezone::UTC);
+
+    let now = Date::now().timestamp();
+    let until = now + SCHEDULE_DAYS as i64 * 24 * 60 * 60;
+    let mut days: BTreeMap<NaiveDate, Vec<ScheduledPick>> = BTreeMap::new();
+    for event in events.into_iter() {
+        let schedule = EventSchedule {
+            id: event.id,
+            team_id: payload.team_id.clone(),
+            timestamp: event.timestamp,
+            timezone: event.timezone,
+            repeat: event.repeat,
+            ends_at: event.ends_at,
+            reminder_minutes: None,
+            skip_weekends: event.skip_weekends,
+            skipped_dates: event.skipped_dates,
+        };
+        let date = SchedulerDate::from_event(&schedule, holidays.clone());
+        for timestamp in date.find_until(now, until) {
+            let local = Date::new(timestamp)
+                .with_timezone(timezone.clone())
+                .to_datetime();
+            days.entry(local.date_naive())
+                .or_default()
+                .push(ScheduledPick {
+                    time: local.time(),
+                    event_id: event.id,
+                    event_name: event.name.clone(),
+                });
+        }
+    }
+
+    Ok(schedule::view(ScheduleView {
+        timezone: timezone.to_string(),
+        date_format,
+        days_count: SCHEDULE_DAYS,
+        locale,
+        days: days
+            .into_iter()
+            .map(|(date, mut picks)| {
+                picks.sort_by_key(|pick| pick.time);
+                ScheduleDay { date, picks }
+            })
+            .collect(),
     })
+    .to_string())
 }
 
-const USAGE_ADD_STR: &'static str = r#"
-`create`     Create a new event
-USAGE:
-    /picker create
-"#;
-
-const USAGE_EDIT_STR: &'static str = r#"
-`edit`    Edits an entity
-USAGE:
-    /picker edit <id>
-
-ARGS:
-    <id>    The ID of the event
-"#;
-
-const USAGE_DELETE_STR: &'static str = r#"
-`del`     Deletes an event
-USAGE:
-    /picker delete <id>
-
-ARGS:
-    <id>    The ID of the event
-"#;
-
-const USAGE_LIST_STR: &'static str = r#"
-`list`    Lists all the events
-USAGE:
-    /picker list channels
-    /picker list events
-"#;
-
-const USAGE_SHOW_STR: &'static str = r#"
-`show`    Shows the details of an event
-USAGE:
-    /picker show <id>
-
-ARGS:
-    <id>       The ID of the event
-"#;
-
-const USAGE_PICK_STR: &'static str = r#"
-`pick`    Picks a random participant for an event
-USAGE:
-    /picker pick <id>
-
-ARGS:
-    <id>       The ID of the event
-"#;
-
-const USAGE_STR: &'static str = r#"
-USAGE:
-`/picker` [SUBCOMMAND] [ARGS]
-
-SUBCOMMANDS:
-`create`      Create a new event
-`delete`      Deletes an existing event
-`edit`        Edits an existing event
-`help`        Prints this message or the help of the given subcommand(s)
-`list`        Lists all the events
-`pick`        Picks randomly a participant of an event
-`show`        Shows the details of the event
-
-For more information on a specific command, use `/picker help <command>`
-"#;
-
-const UNKNOWN_COMMAND_STR: &'static str = "Sorry but we couldn't find any match command. Please type `/picker help` for all available commands";
+/// Runs the pick of the event without saving it, replying with who would be picked and who would
+/// be left to pick.
+async fn handle_preview(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    channel: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return super::to_response(help::usage("preview")),
+    };
+    let req = pick_event::Request {
+        event: id,
+        channel,
+        source: PickSource::Manual,
+        dry_run: true,
+        skipped: vec![],
+    };
+    let response = match pick_event::execute(repo, history_repo, req).await {
+        Ok(response) => response,
+        Err(pick_event::Error::Empty) => {
+            return super::to_response_error(i18n::t(locale, "preview_pick_empty"))
+        }
+        Err(pick_event::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(pick_event::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let user = mention_picked(locale, &response.id, response.partner.as_deref());
+    super::to_response(&match response.left.is_empty() {
+        true => i18n::format(locale, "preview_pick_last", &[("user", &user)]),
+        false => i18n::format(
+            locale,
+            "preview_pick",
+            &[("user", &user), ("left", &mentions(&response.left))],
+        ),
+    })
+}
+
+fn mentions(users: &[String]) -> String {
+    users
+        .iter()
+        .map(|user| format!("<@{}>", user))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+async fn record_action(
+    audit_repo: Arc<dyn audit::Repository>,
+    payload: &CommandRequest,
+    action: AuditAction,
+    args: &str,
+) {
+    // Picking without an event only shows the event selection.
+    let event_id = match args.parse::<u32>() {
+        Ok(id) => id,
+        Err(..) => return,
+    };
+    let actor = activity::Actor {
+        team_id: payload.team_id.clone(),
+        channel: Some(payload.channel_id.clone()),
+        user: Some(payload.user_id.clone()),
+    };
+    activity::record(audit_repo, actor, action, Some(event_id)).await;
+}
+
+/// Announces the pick on the other channels of the event.
+async fn announce_pick(
+    state: &AppState,
+    token: String,
+    payload: &CommandRequest,
+    args: &str,
+    locale: Locale,
+) {
+    // Picking without an event only shows the event selection.
+    let event_id = match args.parse::<u32>() {
+        Ok(id) => id,
+        Err(..) => return,
+    };
+    sender::PickAnnouncer::new(state, payload.team_id.clone(), token, locale)
+        .announce_latest(event_id, payload.channel_id.clone())
+        .await;
+}
+
+async fn handle_audit(
+    audit_repo: Arc<dyn audit::Repository>,
+    team_id: String,
+    channel: String,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    Ok(
+        commands::audit_log::execute(audit_repo, team_id, channel, date_format, locale)
+            .await?
+            .to_string(),
+    )
+}
+
+async fn handle_history(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    channel: String,
+    args: &str,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+
+    Ok(
+        commands::pick_history::execute(repo, history_repo, id, channel, date_format, locale)
+            .await?
+            .to_string(),
+    )
+}
+
+async fn handle_stats(
+    repo: Arc<dyn Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    channel: String,
+    args: &str,
+    date_format: DateFormat,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+
+    Ok(
+        commands::event_stats::execute(repo, history_repo, id, channel, date_format, locale)
+            .await?
+            .to_string(),
+    )
+}
+
+async fn handle_away(
+    repo: Arc<dyn Repository>,
+    channel: String,
+    user: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let days: i64 = match args.parse() {
+        Ok(days) if (0..=set_participant_away::MAX_AWAY_DAYS).contains(&days) => days,
+        Ok(..) => {
+            return super::to_response_error(&i18n::format(
+                locale,
+                "away_invalid_days",
+                &[("max", &set_participant_away::MAX_AWAY_DAYS.to_string())],
+            ))
+        }
+        Err(..) => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+    let until = if days == 0 {
+        None
+    } else {
+        Some(Date::now().timestamp() + days * 24 * 60 * 60)
+    };
+
+    let response = match set_participant_away::execute(
+        repo,
+        set_participant_away::Request {
+            channel,
+            user,
+            until,
+        },
+    )
+    .await
+    {
+        Ok(response) => response,
+        Err(set_participant_away::Error::NotFound) => {
+            return super::to_response(i18n::t(locale, "away_not_participant"))
+        }
+        Err(set_participant_away::Error::Unknown) => {
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+
+    let events = response.events.join(", ");
+    super::to_response(&match until {
+        Some(..) => i18n::format(
+            locale,
+            "away_set",
+            &[("days", &days.to_string()), ("events", &events)],
+        ),
+        None => i18n::format(locale, "away_back", &[("events", &events)]),
+    })
+}
+
+/// Adds the user to the participants of the event, letting the channel know about it.
+async fn handle_join(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    payload: &CommandRequest,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return super::to_response(help::usage("join")),
+    };
+    let req = join::Request {
+        event: id,
+        channel: payload.channel_id.clone(),
+        user: payload.user_id.clone(),
+    };
+    let response = match join::execute(repo, req).await {
+        Ok(response) => response,
+        Err(join::Error::BadRequest) => {
+            return super::to_response_error(i18n::t(locale, "participant_invalid"))
+        }
+        Err(join::Error::AlreadyParticipant) => {
+            return super::to_response_error(i18n::t(locale, "participant_already"))
+        }
+        Err(join::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(join::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    record_action(audit_repo, payload, AuditAction::EventUpdated, args).await;
+    Ok(json!({
+        "text": i18n::format(
+            locale,
+            "participant_joined",
+            &[
+                ("user", &format!("<@{}>", payload.user_id)),
+                ("event", &response.name),
+            ],
+        ),
+        "response_type": "in_channel",
+    })
+    .to_string())
+}
+
+/// Removes the user from the participants of the event, letting the channel know about it.
+async fn handle_leave(
+    repo: Arc<dyn Repository>,
+    audit_repo: Arc<dyn audit::Repository>,
+    payload: &CommandRequest,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let id: u32 = match args.parse() {
+        Ok(id) => id,
+        Err(..) => return super::to_response(help::usage("leave")),
+    };
+    let req = leave::Request {
+        event: id,
+        channel: payload.channel_id.clone(),
+        user: payload.user_id.clone(),
+    };
+    let response = match leave::execute(repo, req).await {
+        Ok(response) => response,
+        Err(leave::Error::BadRequest) => {
+            return super::to_response_error(i18n::t(locale, "participant_invalid"))
+        }
+        Err(leave::Error::NotParticipant) => {
+            return super::to_response_error(i18n::t(locale, "participant_missing"))
+        }
+        Err(leave::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+        Err(leave::Error::Unknown) => return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    record_action(audit_repo, payload, AuditAction::EventUpdated, args).await;
+    Ok(json!({
+        "text": i18n::format(
+            locale,
+            "participant_left",
+            &[
+                ("user", &format!("<@{}>", payload.user_id)),
+                ("event", &response.name),
+            ],
+        ),
+        "response_type": "in_channel",
+    })
+    .to_string())
+}
+
+async fn handle_holiday(
+    repo: Arc<dyn holiday::Repository>,
+    scheduler: Arc<Scheduler>,
+    team_id: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let mut args = args.splitn(3, ' ').map(|arg| arg.trim());
+    let message = match (args.next(), args.next(), args.next()) {
+        (Some("list") | Some(""), None, None) => {
+            let holidays = find_holidays::execute(repo, find_holidays::Request { team_id })
+                .await
+                .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?
+                .data;
+            if holidays.is_empty() {
+                return super::to_response(i18n::t(locale, "holidays_empty"));
+            }
+            let holidays = holidays
+                .into_iter()
+                .map(|holiday| format!("• {} {}", holiday.date, holiday.name))
+                .collect::<Vec<String>>()
+                .join("\n");
+            return super::to_response(&i18n::format(
+                locale,
+                "holidays_title",
+                &[("holidays", &holidays)],
+            ));
+        }
+        (Some("add"), Some(date), name) => {
+            let request = add_holiday::Request {
+                team_id: team_id.clone(),
+                date: date.to_string(),
+                name: name.unwrap_or_default().to_string(),
+            };
+            match add_holiday::execute(repo.clone(), request).await {
+                Ok(holiday) => i18n::format(locale, "holiday_added", &[("date", &holiday.date)]),
+                Err(add_holiday::Error::BadRequest) => return Err(hyper::StatusCode::BAD_REQUEST),
+                Err(add_holiday::Error::Conflict) => return Err(hyper::StatusCode::CONFLICT),
+                Err(add_holiday::Error::Unknown) => {
+                    return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+                }
+            }
+        }
+        (Some("remove"), Some(date), None) => {
+            let request = remove_holiday::Request {
+                team_id: team_id.clone(),
+                date: date.to_string(),
+            };
+            match remove_holiday::execute(repo.clone(), request).await {
+                Ok(holiday) => i18n::format(locale, "holiday_removed", &[("date", &holiday.date)]),
+                Err(remove_holiday::Error::NotFound) => return Err(hyper::StatusCode::NOT_FOUND),
+                Err(remove_holiday::Error::Unknown) => {
+                    return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+                }
+            }
+        }
+        _ => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+
+    match find_holidays::execute(
+        repo,
+        find_holidays::Request {
+            team_id: team_id.clone(),
+        },
+    )
+    .await
+    {
+        Ok(holidays) => {
+            let dates = holidays
+                .data
+                .iter()
+                .filter_map(|holiday| holiday.naive_date())
+                .collect();
+            scheduler.set_holidays(team_id, dates).await;
+        }
+        Err(err) => log::error!(
+            "could not refresh the scheduler holidays for team {}: {:?}",
+            team_id,
+            err
+        ),
+    };
+
+    super::to_response(&message)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_quiet(
+    repo: Arc<dyn settings::Repository>,
+    scheduler: Arc<Scheduler>,
+    slack_client: &dyn SlackClient,
+    token: String,
+    team_id: String,
+    user_id: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let mut args = args.split_whitespace();
+    let hours = match (args.next(), args.next(), args.next(), args.next()) {
+        (None, ..) => {
+            let settings = find_settings::execute(repo, find_settings::Request { team_id })
+                .await
+                .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+            return super::to_response(&match settings.quiet_hours {
+                Some(quiet_hours) => i18n::format(
+                    locale,
+                    "quiet_hours_current",
+                    &[("hours", &quiet_hours.to_string())],
+                ),
+                None => i18n::t(locale, "quiet_hours_empty").to_string(),
+            });
+        }
+        (Some("off"), None, ..) => None,
+        (Some(start), Some(end), timezone, None) => Some((start, end, timezone)),
+        _ => return Err(hyper::StatusCode::BAD_REQUEST),
+    };
+
+    let timezone = match hours.and_then(|(_, _, timezone)| timezone) {
+        Some(timezone) => Timezone::all()
+            .into_iter()
+            .find(|t| String::from(t.clone()) == timezone.to_uppercase())
+            .ok_or(hyper::StatusCode::BAD_REQUEST)?,
+        None => client::get_user_timezone(slack_client, &token, &user_id)
+            .await
+            .unwrap_or(Timezone::UTC),
+    };
+    let request = set_quiet_hours::Request {
+        team_id: team_id.clone(),
+        hours: hours.map(|(start, end, _)| (start.to_string(), end.to_string())),
+        timezone,
+    };
+    let settings = match set_quiet_hours::execute(repo, request).await {
+        Ok(settings) => settings,
+        Err(set_quiet_hours::Error::BadRequest) => return Err(hyper::StatusCode::BAD_REQUEST),
+        Err(set_quiet_hours::Error::Unknown) => {
+            return Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    };
+
+    let message = match &settings.quiet_hours {
+        Some(quiet_hours) => i18n::format(
+            locale,
+            "quiet_hours_set",
+            &[("hours", &quiet_hours.to_string())],
+        ),
+        None => i18n::t(locale, "quiet_hours_reset").to_string(),
+    };
+    scheduler
+        .set_quiet_hours(team_id, settings.quiet_hours)
+        .await;
+
+    super::to_response(&message)
+}
+
+async fn handle_calendar(
+    state: Arc<AppState>,
+    payload: &CommandRequest,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    match args {
+        "" => handle_calendar_link(state, payload, false, locale).await,
+        "reset" => handle_calendar_link(state, payload, true, locale).await,
+        "connect" => handle_calendar_connect(&state.configs, payload, locale),
+        "disconnect" => {
+            handle_calendar_disconnect(state.integration_repo.clone(), payload, locale).await
+        }
+        _ => super::to_response(help::usage("calendar")),
+    }
+}
+
+/// Shares the subscription link of the calendar of the channel, replacing its token first when
+/// it is reset.
+async fn handle_calendar_link(
+    state: Arc<AppState>,
+    payload: &CommandRequest,
+    reset: bool,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let public_url = match &state.configs.public_url {
+        Some(public_url) => public_url,
+        None => return super::to_response(i18n::t(locale, "calendar_unavailable")),
+    };
+    let feed = if reset {
+        reset_feed::execute(
+            state.calendar_feed_repo.clone(),
+            reset_feed::Request {
+                team: payload.team_id.clone(),
+                channel: payload.channel_id.clone(),
+            },
+        )
+        .await
+        .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?
+    } else {
+        share_feed::execute(
+            state.calendar_feed_repo.clone(),
+            share_feed::Request {
+                team: payload.team_id.clone(),
+                channel: payload.channel_id.clone(),
+            },
+        )
+        .await
+        .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?
+    };
+    let url = calendar::calendar_url(public_url, &feed.channel, feed.token.expose());
+    let key = if reset {
+        "calendar_reset"
+    } else {
+        "calendar_link"
+    };
+    super::to_response(&i18n::format(locale, key, &[("url", &url)]))
+}
+
+/// Shares the link on which the user connects their calendar, only visible to them.
+fn handle_calendar_connect(
+    configs: &AppConfigs,
+    payload: &CommandRequest,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let (google, public_url) = match (&configs.google, &configs.public_url) {
+        (Some(google), Some(public_url)) => (google, public_url),
+        _ => return super::to_response(i18n::t(locale, "calendar_connect_unavailable")),
+    };
+    let url = google::authorize_url(
+        google,
+        public_url,
+        configs.app.secret.expose(),
+        &payload.team_id,
+        &payload.user_id,
+    );
+    super::to_response(&i18n::format(locale, "calendar_connect", &[("url", &url)]))
+}
+
+async fn handle_calendar_disconnect(
+    integration_repo: Arc<dyn integration::Repository>,
+    payload: &CommandRequest,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let req = delete_integration::Request {
+        team: payload.team_id.clone(),
+        user: payload.user_id.clone(),
+        provider: IntegrationProvider::GoogleCalendar,
+    };
+    match delete_integration::execute(integration_repo, req).await {
+        Ok(..) => super::to_response(i18n::t(locale, "calendar_disconnected")),
+        Err(delete_integration::Error::NotFound) => {
+            super::to_response(i18n::t(locale, "calendar_not_connected"))
+        }
+        Err(delete_integration::Error::Unknown) => Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn handle_token(
+    state: Arc<AppState>,
+    user_id: String,
+    team_id: String,
+    channel_id: String,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let tokens = api::issue_tokens(state, user_id, team_id, channel_id).await?;
+    super::to_response(&i18n::format(
+        locale,
+        "token_issued",
+        &[
+            (
+                "access",
+                tokens["access_token"].as_str().unwrap_or_default(),
+            ),
+            (
+                "refresh",
+                tokens["refresh_token"].as_str().unwrap_or_default(),
+            ),
+        ],
+    ))
+}
+
+async fn handle_upgrade(
+    repo: Arc<dyn plan::Repository>,
+    configs: &AppConfigs,
+    app_id: Option<&str>,
+    team_id: String,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let stripe = match &configs.stripe {
+        Some(stripe) => stripe,
+        None => return super::to_response(i18n::t(locale, "upgrade_unavailable")),
+    };
+    let plan = find_plan::execute(
+        repo,
+        find_plan::Request {
+            team_id: team_id.clone(),
+        },
+    )
+    .await
+    .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+    if plan.tier == PlanTier::Pro {
+        return super::to_response(i18n::t(locale, "upgrade_already"));
+    }
+
+    let url = billing::create_checkout(stripe, &configs.find_app(app_id).app_id, &team_id).await?;
+    super::to_response(&i18n::format(
+        locale,
+        "upgrade_link",
+        &[("max", &configs.max_events.to_string()), ("url", &url)],
+    ))
+}
+
+async fn handle_locale(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    if args.is_empty() {
+        return super::to_response(&i18n::format(
+            locale,
+            "locale_current",
+            &[("locale", locale.label())],
+        ));
+    }
+    let locale = match Locale::try_from(args.to_string()) {
+        Ok(locale) => locale,
+        Err(..) => {
+            let locales = Locale::all()
+                .iter()
+                .map(|locale| format!("`{}` ({})", locale.code(), locale.label()))
+                .collect::<Vec<String>>()
+                .join(", ");
+            return super::to_response(&i18n::format(
+                locale,
+                "locale_invalid",
+                &[("locales", &locales)],
+            ));
+        }
+    };
+
+    let settings = set_locale::execute(repo, set_locale::Request { team_id, locale })
+        .await
+        .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+    super::to_response(&i18n::format(
+        settings.locale,
+        "locale_set",
+        &[("locale", settings.locale.label())],
+    ))
+}
+
+async fn handle_config(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    args: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let (setting, value) = args.split_once(' ').unwrap_or((args, ""));
+    match setting {
+        "message" => handle_config_message(repo, team_id, value.trim(), locale).await,
+        "acknowledge" => handle_config_acknowledge(repo, team_id, value.trim(), locale).await,
+        "recap" => handle_config_recap(repo, team_id, value.trim(), locale).await,
+        "noshow" => handle_config_no_show(repo, team_id, value.trim(), locale).await,
+        "clock" | "dates" => {
+            handle_config_date_format(repo, team_id, setting, value.trim(), locale).await
+        }
+        _ => super::to_response(help::usage("config")),
+    }
+}
+
+async fn handle_config_message(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    value: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    if value.is_empty() {
+        let settings = find_settings::execute(repo, find_settings::Request { team_id })
+            .await
+            .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+        return super::to_response(&match settings.pick_message {
+            Some(message) => {
+                i18n::format(locale, "config_message_current", &[("message", &message)])
+            }
+            None => i18n::t(locale, "config_message_default").to_string(),
+        });
+    }
+
+    let message = if value == "reset" {
+        None
+    } else {
+        Some(value.to_string())
+    };
+    match set_pick_message::execute(repo, set_pick_message::Request { team_id, message }).await {
+        Ok(settings) => super::to_response(&match settings.pick_message {
+            Some(message) => i18n::format(locale, "config_message_set", &[("message", &message)]),
+            None => i18n::t(locale, "config_message_reset").to_string(),
+        }),
+        Err(set_pick_message::Error::BadRequest) => {
+            super::to_response(i18n::t(locale, "config_message_invalid"))
+        }
+        Err(set_pick_message::Error::Unknown) => Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn handle_config_acknowledge(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    value: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    if value.is_empty() {
+        let settings = find_settings::execute(repo, find_settings::Request { team_id })
+            .await
+            .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+        return super::to_response(&match settings.acknowledge_minutes {
+            Some(minutes) => i18n::format(
+                locale,
+                "config_acknowledge_current",
+                &[("minutes", &reminder::minutes_label(locale, minutes))],
+            ),
+            None => i18n::t(locale, "config_acknowledge_off").to_string(),
+        });
+    }
+
+    let invalid = i18n::format(
+        locale,
+        "config_acknowledge_invalid",
+        &[(
+            "max",
+            &set_acknowledge_minutes::MAX_ACKNOWLEDGE_MINUTES.to_string(),
+        )],
+    );
+    let minutes = if value == "off" {
+        None
+    } else {
+        match value.parse::<u32>() {
+            Ok(minutes) => Some(minutes),
+            Err(..) => return super::to_response(&invalid),
+        }
+    };
+    let req = set_acknowledge_minutes::Request { team_id, minutes };
+    match set_acknowledge_minutes::execute(repo, req).await {
+        Ok(settings) => super::to_response(&match settings.acknowledge_minutes {
+            Some(minutes) => i18n::format(
+                locale,
+                "config_acknowledge_set",
+                &[("minutes", &reminder::minutes_label(locale, minutes))],
+            ),
+            None => i18n::t(locale, "config_acknowledge_reset").to_string(),
+        }),
+        Err(set_acknowledge_minutes::Error::BadRequest) => super::to_response(&invalid),
+        Err(set_acknowledge_minutes::Error::Unknown) => {
+            Err(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+async fn handle_config_recap(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    value: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let enabled = match value {
+        "" => {
+            let settings = find_settings::execute(repo, find_settings::Request { team_id })
+                .await
+                .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+            return super::to_response(recap_label(locale, settings.monthly_recap));
+        }
+        "on" => true,
+        "off" => false,
+        _ => return super::to_response(help::usage("config")),
+    };
+    let req = set_monthly_recap::Request { team_id, enabled };
+    match set_monthly_recap::execute(repo, req).await {
+        Ok(settings) => super::to_response(recap_label(locale, settings.monthly_recap)),
+        Err(set_monthly_recap::Error::Unknown) => Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+fn recap_label(locale: Locale, enabled: bool) -> &'static str {
+    if enabled {
+        i18n::t(locale, "config_recap_on")
+    } else {
+        i18n::t(locale, "config_recap_off")
+    }
+}
+
+async fn handle_config_no_show(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    value: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    let enabled = match value {
+        "" => {
+            let settings = find_settings::execute(repo, find_settings::Request { team_id })
+                .await
+                .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+            return super::to_response(no_show_label(locale, settings.no_show_boost));
+        }
+        "on" => true,
+        "off" => false,
+        _ => return super::to_response(help::usage("config")),
+    };
+    let req = set_no_show_boost::Request { team_id, enabled };
+    match set_no_show_boost::execute(repo, req).await {
+        Ok(settings) => super::to_response(no_show_label(locale, settings.no_show_boost)),
+        Err(set_no_show_boost::Error::Unknown) => Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+fn no_show_label(locale: Locale, enabled: bool) -> &'static str {
+    if enabled {
+        i18n::t(locale, "config_noshow_on")
+    } else {
+        i18n::t(locale, "config_noshow_off")
+    }
+}
+
+async fn handle_config_date_format(
+    repo: Arc<dyn settings::Repository>,
+    team_id: String,
+    setting: &str,
+    value: &str,
+    locale: Locale,
+) -> Result<String, hyper::StatusCode> {
+    if value.is_empty() {
+        let settings = find_settings::execute(repo, find_settings::Request { team_id })
+            .await
+            .map_err(|_| hyper::StatusCode::INTERNAL_SERVER_ERROR)?;
+        return super::to_response(&date_format_label(locale, setting, settings.date_format));
+    }
+
+    let req = match setting {
+        "clock" => match Clock::try_from(value.to_string()) {
+            Ok(clock) => set_date_format::Request {
+                team_id,
+                clock: Some(clock),
+                order: None,
+            },
+            Err(..) => return super::to_response(help::usage("config")),
+        },
+        _ => match DateOrder::try_from(value.to_string()) {
+            Ok(order) => set_date_format::Request {
+                team_id,
+                clock: None,
+                order: Some(order),
+            },
+            Err(..) => return super::to_response(help::usage("config")),
+        },
+    };
+    match set_date_format::execute(repo, req).await {
+        Ok(settings) => {
+            super::to_response(&date_format_label(locale, setting, settings.date_format))
+        }
+        Err(set_date_format::Error::Unknown) => Err(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+fn date_format_label(locale: Locale, setting: &str, format: DateFormat) -> String {
+    if setting == "clock" {
+        return i18n::format(locale, "config_clock", &[("clock", format.clock.code())]);
+    }
+    let example = NaiveDate::from_ymd_opt(2024, 12, 31)
+        .expect("should be a valid date")
+        .format(format.order.date_pattern())
+        .to_string();
+    i18n::format(locale, "config_dates", &[("example", &example)])
+}
+
+fn handle_help(args: &str, reached_limit: bool) -> Result<String, hyper::StatusCode> {
+    let view = match help::find_command(args.trim()) {
+        Some(command) => help::command_view(command),
+        None => help::view(reached_limit),
+    };
+    Ok(view.to_string())
+}
+
+const EXPORT_FILENAME: &str = "events.json";
+
+/// The number of days shown on the schedule of the upcoming picks.
+const SCHEDULE_DAYS: u32 = 28;
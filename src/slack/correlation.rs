@@ -0,0 +1,64 @@
+use axum::{body::Body, http::Request, middleware::Next, response::Response};
+use hyper::header::HeaderValue;
+use rand::Rng;
+
+/// The header carrying the correlation ID of each request, both on the request and the response.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Assigns a correlation ID to the request, reusing the one of the caller when valid, so that the
+/// logs of a single request can be filtered together.
+pub async fn assign(mut request: Request<Body>, next: Next<Body>) -> Response {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_valid(id))
+        .map(String::from)
+        .unwrap_or_else(generate);
+    let value = HeaderValue::from_str(&id).expect("request id should be a valid header");
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, value.clone());
+
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    response
+}
+
+/// Finds the correlation ID assigned to the request.
+pub fn find_request_id<B>(request: &Request<B>) -> &str {
+    request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+}
+
+fn generate() -> String {
+    let bytes: [u8; 16] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid(&generate()));
+        assert!(is_valid("5f0c6e1a-7d1b-4a4e-9f1e-0c1d2e3f4a5b"));
+        assert!(!is_valid(""));
+        assert!(!is_valid("id with spaces"));
+        assert!(!is_valid(&"a".repeat(65)));
+    }
+}
@@ -0,0 +1,73 @@
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Response},
+};
+use hyper::{header, StatusCode};
+
+/// The assets of the dashboard, embedded on the binary so that it is served without any files.
+const ASSETS: &[(&str, &str, &str)] = &[
+    (
+        "index.html",
+        "text/html; charset=utf-8",
+        include_str!("../../assets/dashboard/index.html"),
+    ),
+    (
+        "app.js",
+        "text/javascript; charset=utf-8",
+        include_str!("../../assets/dashboard/app.js"),
+    ),
+    (
+        "style.css",
+        "text/css; charset=utf-8",
+        include_str!("../../assets/dashboard/style.css"),
+    ),
+];
+
+/// Serves the page of the dashboard, which loads the events through the API with the tokens of
+/// the `/picker token` command.
+pub async fn index() -> Response {
+    serve("index.html")
+}
+
+/// Serves an asset of the dashboard.
+pub async fn asset(Path(file): Path<String>) -> Response {
+    serve(&file)
+}
+
+fn serve(file: &str) -> Response {
+    match find_asset(file) {
+        Some((content_type, content)) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                // The assets change with the deployments, which are not versioned on their names.
+                (header::CACHE_CONTROL, "no-cache"),
+            ],
+            content,
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn find_asset(file: &str) -> Option<(&'static str, &'static str)> {
+    ASSETS
+        .iter()
+        .find(|(name, ..)| *name == file)
+        .map(|(_, content_type, content)| (*content_type, *content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_asset() {
+        let (content_type, content) = find_asset("index.html").unwrap();
+        assert_eq!(content_type, "text/html; charset=utf-8");
+        assert!(content.contains("app.js"));
+
+        assert!(find_asset("app.js").is_some());
+        assert!(find_asset("../Cargo.toml").is_none());
+        assert!(find_asset("").is_none());
+    }
+}
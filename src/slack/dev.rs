@@ -0,0 +1,290 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::{extract::State, Extension, Json};
+use hyper::{Body, Request, StatusCode};
+use hyper_tls::HttpsConnector;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::domain::auth::save_auth;
+use crate::error::Error;
+use crate::helpers::date::Date;
+use crate::repository::auth;
+
+use super::client::{self, Conversation, DndStatus, SlackClient, User, UserGroup, UserProfile};
+use super::AppState;
+
+/// The Slack API of the development mode, which logs and records the outbound messages instead of
+/// sending them, so that the commands can be run end to end without a Slack workspace.
+pub struct FakeSlack {
+    /// The URL the server is reached at, which the simulated commands are sent to.
+    base_url: String,
+    messages: Mutex<VecDeque<Value>>,
+    last_ts: AtomicU64,
+}
+
+impl FakeSlack {
+    pub fn new(port: u16) -> Self {
+        Self {
+            base_url: format!("http://127.0.0.1:{}", port),
+            messages: Mutex::new(VecDeque::new()),
+            last_ts: AtomicU64::new(0),
+        }
+    }
+
+    /// The latest messages sent to Slack, oldest first.
+    pub fn messages(&self) -> Vec<Value> {
+        self.messages.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn record(&self, method: &str, body: Value) {
+        log::info!("[dev] {}: {}", method, body);
+        let mut messages = self.messages.lock().unwrap();
+        if messages.len() == MAX_MESSAGES {
+            messages.pop_front();
+        }
+        messages.push_back(json!({ "method": method, "body": body }));
+    }
+
+    /// A unique timestamp for the posted messages, as Slack identifies the messages by them.
+    fn next_ts(&self) -> String {
+        let sequence = self.last_ts.fetch_add(1, Ordering::Relaxed) + 1;
+        format!("{}.{:06}", Date::now().timestamp(), sequence % 1_000_000)
+    }
+}
+
+#[async_trait]
+impl SlackClient for FakeSlack {
+    async fn post_message(&self, _token: &str, message: Value) -> Result<String, client::Error> {
+        self.record("chat.postMessage", message);
+        Ok(self.next_ts())
+    }
+
+    async fn update_message(&self, _token: &str, message: Value) -> Result<(), client::Error> {
+        self.record("chat.update", message);
+        Ok(())
+    }
+
+    async fn post_ephemeral(&self, _token: &str, message: Value) -> Result<(), client::Error> {
+        self.record("chat.postEphemeral", message);
+        Ok(())
+    }
+
+    async fn pin_message(
+        &self,
+        _token: &str,
+        channel: &str,
+        ts: &str,
+    ) -> Result<(), client::Error> {
+        self.record("pins.add", json!({ "channel": channel, "timestamp": ts }));
+        Ok(())
+    }
+
+    async fn open_view(
+        &self,
+        _token: &str,
+        trigger_id: &str,
+        view: Value,
+    ) -> Result<(), client::Error> {
+        self.record(
+            "views.open",
+            json!({ "trigger_id": trigger_id, "view": view }),
+        );
+        Ok(())
+    }
+
+    async fn user_info(&self, _token: &str, user: &str) -> Result<User, client::Error> {
+        Ok(User {
+            id: user.to_string(),
+            name: user.to_lowercase(),
+            deleted: false,
+            is_bot: false,
+            tz: Some(String::from("UTC")),
+            tz_offset: Some(0),
+        })
+    }
+
+    async fn user_profile(&self, _token: &str, _user: &str) -> Result<UserProfile, client::Error> {
+        Ok(UserProfile::default())
+    }
+
+    async fn dnd_info(&self, _token: &str, _user: &str) -> Result<DndStatus, client::Error> {
+        Ok(DndStatus::default())
+    }
+
+    async fn users_list(&self, _token: &str) -> Result<Vec<User>, client::Error> {
+        Ok(vec![])
+    }
+
+    async fn usergroups_list(&self, _token: &str) -> Result<Vec<UserGroup>, client::Error> {
+        Ok(vec![])
+    }
+
+    async fn usergroups_users_list(
+        &self,
+        _token: &str,
+        _usergroup: &str,
+    ) -> Result<Vec<String>, client::Error> {
+        Err(client::Error::Api(String::from("no_such_subteam")))
+    }
+
+    async fn conversations_list(&self, _token: &str) -> Result<Vec<Conversation>, client::Error> {
+        Ok(vec![Conversation {
+            id: String::from(DEV_CHANNEL),
+            name: String::from("dev"),
+            is_private: false,
+            is_member: true,
+        }])
+    }
+
+    async fn conversations_info(
+        &self,
+        _token: &str,
+        channel: &str,
+    ) -> Result<Conversation, client::Error> {
+        Ok(Conversation {
+            id: channel.to_string(),
+            name: channel.to_lowercase(),
+            is_private: false,
+            is_member: true,
+        })
+    }
+
+    async fn conversations_join(&self, _token: &str, channel: &str) -> Result<(), client::Error> {
+        self.record("conversations.join", json!({ "channel": channel }));
+        Ok(())
+    }
+
+    async fn upload_file(
+        &self,
+        _token: &str,
+        channel: &str,
+        filename: &str,
+        content: String,
+    ) -> Result<(), client::Error> {
+        self.record(
+            "files.upload",
+            json!({ "channel": channel, "filename": filename, "content": content }),
+        );
+        Ok(())
+    }
+}
+
+/// Installs the application on the development team, so that its commands are authorized.
+pub async fn install(auth_repo: Arc<dyn auth::Repository>) {
+    let req = save_auth::Request {
+        team: String::from(DEV_TEAM),
+        enterprise: None,
+        access_token: DEV_ACCESS_TOKEN.into(),
+    };
+    match save_auth::execute(auth_repo, req).await {
+        Ok(..) => log::info!("[dev] installed on team {}", DEV_TEAM),
+        Err(err) => log::error!("[dev] could not install on team {}: {:?}", DEV_TEAM, err),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DevCommand {
+    /// The text of the command (e.g. `list` for `/picker list`).
+    pub text: String,
+    pub team_id: Option<String>,
+    pub channel_id: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// Simulates a slash command posted by Slack, signing it as Slack does, so that it goes through
+/// the same checks as the real ones. Its reply is recorded along with the messages.
+pub async fn command(
+    State(state): State<Arc<AppState>>,
+    Extension(slack): Extension<Arc<FakeSlack>>,
+    Json(command): Json<DevCommand>,
+) -> Result<Json<Value>, Error> {
+    let body = serde_urlencoded::to_string([
+        ("team_id", command.team_id.as_deref().unwrap_or(DEV_TEAM)),
+        (
+            "channel_id",
+            command.channel_id.as_deref().unwrap_or(DEV_CHANNEL),
+        ),
+        ("user_id", command.user_id.as_deref().unwrap_or(DEV_USER)),
+        ("text", command.text.as_str()),
+        (
+            "response_url",
+            format!("{}/dev/responses", slack.base_url).as_str(),
+        ),
+    ])
+    .map_err(|err| Error::BadRequest(err.to_string()))?;
+
+    let timestamp = Date::now().timestamp();
+    let signature = super::guard::calculate_signature(
+        &format!("v0:{}:{}", timestamp, body),
+        state.configs.app.secret.expose(),
+    );
+    let req = Request::builder()
+        .method(hyper::Method::POST)
+        .uri(format!("{}/api/commands", slack.base_url))
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("x-slack-request-timestamp", timestamp.to_string())
+        .header("x-slack-signature", signature)
+        .body(Body::from(body))
+        .map_err(|err| Error::Internal(err.to_string()))?;
+    let response = hyper::Client::builder()
+        .build::<_, Body>(HttpsConnector::new())
+        .request(req)
+        .await
+        .map_err(|err| Error::Internal(format!("could not send the command: {}", err)))?;
+
+    Ok(Json(json!({
+        "status": response.status().as_u16(),
+        "messages": "/dev/messages",
+    })))
+}
+
+/// Records the replies posted to the response URL of the simulated commands.
+pub async fn responses(
+    Extension(slack): Extension<Arc<FakeSlack>>,
+    Json(body): Json<Value>,
+) -> StatusCode {
+    slack.record("response_url", body);
+    StatusCode::OK
+}
+
+/// Lists the latest messages sent to Slack, including the replies of the commands.
+pub async fn messages(Extension(slack): Extension<Arc<FakeSlack>>) -> Json<Value> {
+    Json(json!({ "data": slack.messages() }))
+}
+
+/// The team, channel and user the simulated commands are sent from, unless set.
+const DEV_TEAM: &str = "T0DEV";
+const DEV_CHANNEL: &str = "C0DEV";
+const DEV_USER: &str = "U0DEV";
+const DEV_ACCESS_TOKEN: &str = "xoxb-dev";
+
+/// The number of messages kept, dropping the oldest ones.
+const MAX_MESSAGES: usize = 200;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record() {
+        let slack = FakeSlack::new(8080);
+        let ts = slack
+            .post_message("token", json!({ "channel": "C1", "text": "hi" }))
+            .await
+            .unwrap();
+        assert_ne!(ts, slack.next_ts());
+
+        for _ in 0..MAX_MESSAGES {
+            slack.record("chat.update", json!({}));
+        }
+        let messages = slack.messages();
+        assert_eq!(messages.len(), MAX_MESSAGES);
+        assert!(messages
+            .iter()
+            .all(|message| message["method"] == "chat.update"));
+    }
+}
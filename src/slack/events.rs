@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::domain::{
+    auth::{revoke_auth, verify_auth},
+    events::find_all_events_and_dates,
+    participants::remove_member,
+};
+use crate::i18n;
+
+use super::{sender, AppState};
+
+/// Slack event request, sent for the events the application is subscribed to.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventRequest {
+    UrlVerification {
+        challenge: String,
+    },
+    EventCallback {
+        team_id: String,
+        enterprise_id: Option<String>,
+        event: Event,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    AppUninstalled,
+    TokensRevoked {
+        tokens: RevokedTokens,
+    },
+    MemberLeftChannel {
+        user: String,
+        channel: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RevokedTokens {
+    #[serde(default)]
+    bot: Vec<String>,
+}
+
+pub async fn execute(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<EventRequest>,
+) -> Result<Json<Value>, hyper::StatusCode> {
+    let (team_id, enterprise_id, event) = match payload {
+        EventRequest::UrlVerification { challenge } => {
+            return Ok(Json(json!({ "challenge": challenge })))
+        }
+        EventRequest::EventCallback {
+            team_id,
+            enterprise_id,
+            event,
+        } => (team_id, enterprise_id, event),
+    };
+    tracing::Span::current().record("team_id", team_id.as_str());
+    tracing::trace!(event = ?event, "received event");
+
+    match event {
+        Event::AppUninstalled => revoke(state, team_id, enterprise_id).await?,
+        // Only the bot tokens are used to post on behalf of the teams.
+        Event::TokensRevoked { tokens } if !tokens.bot.is_empty() => {
+            revoke(state, team_id, enterprise_id).await?
+        }
+        Event::MemberLeftChannel { user, channel } => {
+            remove_member(state, team_id, enterprise_id, user, channel).await?
+        }
+        event => log::trace!("ignoring event {:?} of team {}", event, team_id),
+    };
+
+    Ok(Json(json!({})))
+}
+
+/// Revokes the access of a team and removes its events from the scheduler, so that nothing is
+/// posted on its workspace anymore (the syncs of the scheduler skip the teams without an access
+/// token). The organization-wide installs remove the events of all the teams of the organization.
+async fn revoke(
+    state: Arc<AppState>,
+    team_id: String,
+    enterprise_id: Option<String>,
+) -> Result<(), hyper::StatusCode> {
+    let request = revoke_auth::Request {
+        team: team_id.clone(),
+        enterprise: enterprise_id,
+    };
+    let teams = revoke_auth::execute(state.auth_repo.clone(), state.api_key_repo.clone(), request)
+        .await
+        .map_err(|err| {
+            log::error!("could not revoke the access of team {}: {:?}", team_id, err);
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map_or_else(|| vec![team_id.clone()], |auth| auth.team_ids());
+
+    let events = find_all_events_and_dates::execute(state.event_repo.clone())
+        .await
+        .map_err(|err| {
+            log::error!("could not find the events of team {}: {:?}", team_id, err);
+            hyper::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .data;
+    for event in events
+        .into_iter()
+        .filter(|event| teams.contains(&event.team_id))
+    {
+        state.scheduler.remove(event.id).await;
+    }
+
+    log::info!("revoked the access of team {}", team_id);
+    Ok(())
+}
+
+/// Removes the member who left the channel from the participants of its events, so that they are
+/// not picked anymore, and lets the owners of the events know about it.
+async fn remove_member(
+    state: Arc<AppState>,
+    team_id: String,
+    enterprise_id: Option<String>,
+    user: String,
+    channel: String,
+) -> Result<(), hyper::StatusCode> {
+    let request = remove_member::Request {
+        team_id: team_id.clone(),
+        channel: channel.clone(),
+        user: user.clone(),
+    };
+    let response =
+        remove_member::execute(state.event_repo.clone(), state.audit_repo.clone(), request)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "could not remove member {} from the events of channel {}: {:?}",
+                    user,
+                    channel,
+                    err
+                );
+                hyper::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    if response.events.is_empty() {
+        return Ok(());
+    }
+    log::info!(
+        "removed member {} from {} events of channel {}",
+        user,
+        response.events.len(),
+        channel
+    );
+    if response.owners.is_empty() {
+        return Ok(());
+    }
+
+    let request = verify_auth::Request {
+        team: team_id.clone(),
+        enterprise: enterprise_id,
+    };
+    let auth = match verify_auth::execute(state.auth_repo.clone(), request).await {
+        Ok(auth) => auth,
+        Err(err) => {
+            log::error!("could not find the access of team {}: {:?}", team_id, err);
+            return Ok(());
+        }
+    };
+    let locale = sender::find_settings(state.settings_repo.clone(), &team_id)
+        .await
+        .locale;
+    let events = response
+        .events
+        .iter()
+        .map(|event| format!("*{}*", event.name))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let text = i18n::format(
+        locale,
+        "participant_removed",
+        &[("user", &format!("<@{}>", user)), ("events", &events)],
+    );
+    for owner in response.owners.into_iter() {
+        let message = json!({ "channel": channel, "user": owner, "text": text });
+        if let Err(err) = state
+            .slack_client
+            .post_ephemeral(auth.access_token.expose(), message)
+            .await
+        {
+            log::error!("could not notify {} of the removal: {}", owner, err);
+        }
+    }
+    Ok(())
+}
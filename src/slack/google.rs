@@ -0,0 +1,433 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Query, State},
+    response::Redirect,
+};
+use chrono::NaiveDate;
+use hmac::{Hmac, Mac};
+use hyper::StatusCode;
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::{
+    domain::{
+        entities::{Integration, IntegrationProvider},
+        integrations::save_integration,
+    },
+    helpers::date::Date,
+    repository::{calendar, errors::FindError, integration},
+};
+
+use super::{
+    helpers,
+    state::{AppState, GoogleConfigs},
+    user_statuses::is_out_of_office_text,
+};
+
+#[derive(Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub error: Option<String>,
+    pub state: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    /// The number of seconds the access token is valid for.
+    expires_in: i64,
+    /// Only given when the user consents to the access, and not when the access token is renewed.
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EventsResponse {
+    items: Vec<CalendarEvent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CalendarEvent {
+    /// The kind of the event (e.g. `outOfOffice`), which is `default` for the usual events.
+    #[serde(default)]
+    event_type: String,
+    #[serde(default)]
+    summary: String,
+    start: EventTime,
+    end: EventTime,
+}
+
+/// When an event starts or ends. The all-day events only have their date, while the other ones
+/// have their date and time instead.
+#[derive(Deserialize)]
+struct EventTime {
+    date: Option<NaiveDate>,
+}
+
+impl CalendarEvent {
+    /// Whether the event is an all-day out of office event (either by its kind or by its summary,
+    /// e.g. `OOO`) on the date.
+    fn is_out_of_office(&self, date: NaiveDate) -> bool {
+        // The end date of the all-day events is the day after their last day.
+        let all_day = match (self.start.date, self.end.date) {
+            (Some(start), Some(end)) => start <= date && date < end,
+            _ => false,
+        };
+        all_day && (self.event_type == "outOfOffice" || is_out_of_office_text(&self.summary))
+    }
+}
+
+/// The link on which the user connects their Google calendar, signed so that the connection is
+/// saved for them once they authorize it.
+pub fn authorize_url(
+    google: &GoogleConfigs,
+    public_url: &str,
+    secret: &str,
+    team: &str,
+    user: &str,
+) -> String {
+    let state = generate_state(team, user, secret, Date::now().timestamp());
+    let redirect_uri = redirect_uri(public_url);
+    let query = serde_urlencoded::to_string([
+        ("client_id", google.client_id.as_str()),
+        ("redirect_uri", redirect_uri.as_str()),
+        ("response_type", "code"),
+        ("scope", CALENDAR_SCOPE),
+        // The refresh token is only given on the consent, so it is asked on every connection.
+        ("access_type", "offline"),
+        ("prompt", "consent"),
+        ("state", state.as_str()),
+    ])
+    .unwrap_or_default();
+    format!("{}?{}", GOOGLE_AUTHORIZE_URL, query)
+}
+
+/// Receives the authorization of the Google calendar of a user, saving its tokens so that the
+/// calendar is checked on the automatic picks.
+pub async fn callback(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CallbackQuery>,
+) -> Result<Redirect, StatusCode> {
+    let google = state.configs.google.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let public_url = state
+        .configs
+        .public_url
+        .as_deref()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if query.error.is_some() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let code = query.code.ok_or(StatusCode::BAD_REQUEST)?;
+    let oauth_state = query.state.as_deref().unwrap_or_default();
+    let (team, user) = verify_state(
+        oauth_state,
+        state.configs.app.secret.expose(),
+        Date::now().timestamp(),
+    )
+    .ok_or_else(|| {
+        log::trace!("invalid google state: {}", oauth_state);
+        StatusCode::FORBIDDEN
+    })?;
+
+    let redirect_uri = redirect_uri(public_url);
+    let tokens = request_tokens(
+        google,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", redirect_uri.as_str()),
+        ],
+    )
+    .await
+    .map_err(|err| {
+        log::error!("could not connect the calendar of user {}: {}", user, err);
+        StatusCode::BAD_GATEWAY
+    })?;
+    let refresh_token = tokens.refresh_token.ok_or_else(|| {
+        log::error!(
+            "the calendar of user {} was connected without a refresh token",
+            user
+        );
+        StatusCode::BAD_GATEWAY
+    })?;
+
+    save_integration::execute(
+        state.integration_repo.clone(),
+        save_integration::Request {
+            team,
+            user: user.clone(),
+            provider: IntegrationProvider::GoogleCalendar,
+            access_token: tokens.access_token.into(),
+            refresh_token: refresh_token.into(),
+            expires_in: tokens.expires_in,
+        },
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    log::trace!("connected the calendar of user {}", user);
+
+    Ok(Redirect::to(&format!(
+        "https://slack.com/app_redirect?app={}",
+        state.configs.app.app_id
+    )))
+}
+
+/// Finds the users out of office on their Google calendars.
+pub struct GoogleCalendarRepository {
+    integration_repo: Arc<dyn integration::Repository>,
+    /// The calendars are not checked when the Google app is not configured.
+    google: Option<GoogleConfigs>,
+}
+
+impl GoogleCalendarRepository {
+    pub fn new(
+        integration_repo: Arc<dyn integration::Repository>,
+        google: Option<GoogleConfigs>,
+    ) -> Self {
+        Self {
+            integration_repo,
+            google,
+        }
+    }
+
+    async fn is_out_of_office(
+        &self,
+        google: &GoogleConfigs,
+        mut integration: Integration,
+        date: NaiveDate,
+    ) -> Result<bool, String> {
+        let now = Date::now().timestamp();
+        if integration.is_expired(now + EXPIRATION_MARGIN_SECONDS) {
+            let tokens = request_tokens(
+                google,
+                &[
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", integration.refresh_token.expose()),
+                ],
+            )
+            .await?;
+            integration.access_token = tokens.access_token.into();
+            integration.expires_at = now + tokens.expires_in;
+            if let Err(err) = self
+                .integration_repo
+                .save_integration(integration.clone())
+                .await
+            {
+                log::error!(
+                    "could not save the renewed token of user {}: {:?}",
+                    integration.user,
+                    err
+                );
+            }
+        }
+
+        // The all-day events of the date overlap its day on UTC, whatever the timezone of the
+        // calendar.
+        let query = serde_urlencoded::to_string([
+            ("timeMin", format!("{}T00:00:00Z", date)),
+            (
+                "timeMax",
+                format!("{}T00:00:00Z", date.succ_opt().unwrap_or(date)),
+            ),
+            ("singleEvents", String::from("true")),
+        ])
+        .map_err(|err| err.to_string())?;
+        let response = helpers::send_authorized_get(
+            &format!("{}?{}", GOOGLE_EVENTS_URL, query),
+            integration.access_token.expose(),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+        let events = serde_json::from_str::<EventsResponse>(&response)
+            .map_err(|err| format!("invalid events response: {}", err))?;
+
+        Ok(events
+            .items
+            .iter()
+            .any(|event| event.is_out_of_office(date)))
+    }
+}
+
+#[async_trait]
+impl calendar::Repository for GoogleCalendarRepository {
+    async fn find_out_of_office(
+        &self,
+        team: &str,
+        users: &[String],
+        date: NaiveDate,
+    ) -> Result<Vec<String>, FindError> {
+        let google = match &self.google {
+            Some(google) => google,
+            None => return Ok(vec![]),
+        };
+        let integrations = self
+            .integration_repo
+            .find_integrations(
+                team.to_string(),
+                users.to_vec(),
+                IntegrationProvider::GoogleCalendar,
+            )
+            .await
+            .map_err(|_| FindError::Unknown)?;
+
+        let mut result = vec![];
+        for integration in integrations {
+            let user = integration.user.clone();
+            match self.is_out_of_office(google, integration, date).await {
+                Ok(true) => result.push(user),
+                Ok(false) => (),
+                // The calendars that cannot be read do not skip their users.
+                Err(err) => log::error!("could not read the calendar of user {}: {}", user, err),
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Requests the tokens of the Google app, either for the authorization of a user or to renew them.
+async fn request_tokens(
+    google: &GoogleConfigs,
+    params: &[(&str, &str)],
+) -> Result<TokenResponse, String> {
+    let mut form = vec![
+        ("client_id", google.client_id.as_str()),
+        ("client_secret", google.client_secret.expose()),
+    ];
+    form.extend_from_slice(params);
+    let body = serde_urlencoded::to_string(form).map_err(|err| err.to_string())?;
+
+    let response = helpers::send_post_with_type(
+        GOOGLE_TOKEN_URL,
+        hyper::Body::from(body),
+        String::from("application/x-www-form-urlencoded"),
+    )
+    .await
+    .map_err(|err| err.to_string())?;
+    // The response is not logged, as it holds the tokens.
+    serde_json::from_str(&response).map_err(|err| format!("invalid token response: {}", err))
+}
+
+fn redirect_uri(public_url: &str) -> String {
+    format!("{}{}", public_url.trim_end_matches('/'), CALLBACK_PATH)
+}
+
+/// Generates the state of a connection, made of its expiration and the user connecting their
+/// calendar, signed with the application secret.
+fn generate_state(team: &str, user: &str, secret: &str, now: i64) -> String {
+    let data = format!("{}.{}.{}", now + STATE_EXPIRATION_SECONDS, team, user);
+    format!(
+        "{}.{}",
+        data,
+        hex::encode(state_mac(&data, secret).finalize().into_bytes())
+    )
+}
+
+/// Verifies the state of a connection, returning the team and the user connecting their calendar
+/// when valid.
+fn verify_state(oauth_state: &str, secret: &str, now: i64) -> Option<(String, String)> {
+    let (data, signature) = oauth_state.rsplit_once('.')?;
+    let mut parts = data.splitn(3, '.');
+    let expires_at = parts.next()?.parse::<i64>().ok()?;
+    let team = parts.next()?;
+    let user = parts.next()?;
+    let signature = hex::decode(signature).ok()?;
+    (expires_at > now && state_mac(data, secret).verify_slice(&signature).is_ok())
+        .then(|| (team.to_string(), user.to_string()))
+}
+
+fn state_mac(data: &str, secret: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(format!("google:{}", data).as_bytes());
+    mac
+}
+
+/// The path Google redirects the users to once they authorize the access to their calendars.
+pub const CALLBACK_PATH: &str = "/api/integrations/google/callback";
+
+const GOOGLE_AUTHORIZE_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_EVENTS_URL: &str = "https://www.googleapis.com/calendar/v3/calendars/primary/events";
+const CALENDAR_SCOPE: &str = "https://www.googleapis.com/auth/calendar.events.readonly";
+/// The number of seconds the users have to authorize the access to their calendars.
+const STATE_EXPIRATION_SECONDS: i64 = 10 * 60;
+/// The access tokens are renewed this many seconds before they expire, so that they do not expire
+/// while in use.
+const EXPIRATION_MARGIN_SECONDS: i64 = 60;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_state() {
+        let now = 1724681700;
+        let state = generate_state("T1", "U1", "secret", now);
+
+        assert_eq!(
+            verify_state(&state, "secret", now),
+            Some((String::from("T1"), String::from("U1")))
+        );
+        assert_eq!(verify_state(&state, "other", now), None);
+        assert_eq!(
+            verify_state(&state, "secret", now + STATE_EXPIRATION_SECONDS),
+            None
+        );
+        assert_eq!(
+            verify_state(&state.replace(".U1.", ".U2."), "secret", now),
+            None
+        );
+        assert_eq!(verify_state("", "secret", now), None);
+    }
+
+    #[test]
+    fn test_is_out_of_office() {
+        let events: EventsResponse = serde_json::from_str(
+            r#"{
+                "items": [
+                    {
+                        "eventType": "outOfOffice",
+                        "summary": "Away",
+                        "start": { "date": "2026-10-15" },
+                        "end": { "date": "2026-10-17" }
+                    },
+                    {
+                        "summary": "OOO",
+                        "start": { "date": "2026-10-19" },
+                        "end": { "date": "2026-10-20" }
+                    },
+                    {
+                        "eventType": "outOfOffice",
+                        "start": { "dateTime": "2026-10-21T09:00:00Z" },
+                        "end": { "dateTime": "2026-10-21T12:00:00Z" }
+                    },
+                    {
+                        "summary": "Team offsite",
+                        "start": { "date": "2026-10-22" },
+                        "end": { "date": "2026-10-23" }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let date = |day: u32| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        let is_out_of_office = |day: u32| {
+            events
+                .items
+                .iter()
+                .any(|event| event.is_out_of_office(date(day)))
+        };
+
+        assert!(is_out_of_office(15));
+        assert!(is_out_of_office(16));
+        // The end dates are exclusive.
+        assert!(!is_out_of_office(17));
+        assert!(is_out_of_office(19));
+        // Only the all-day events skip the users.
+        assert!(!is_out_of_office(21));
+        assert!(!is_out_of_office(22));
+    }
+}
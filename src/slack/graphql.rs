@@ -0,0 +1,339 @@
+use std::sync::{Arc, OnceLock};
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject,
+};
+use axum::{extract::State, Json};
+
+use crate::domain::entities::Participant;
+use crate::domain::events::{find_all_events, find_event};
+use crate::domain::history::find_picks;
+use crate::error::Error;
+use crate::repository::{event, pick_history};
+
+use super::{jwt::Claims, AppState};
+
+pub type ApiSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// Serves the GraphQL queries of the dashboards, scoped to the channel of the token.
+///
+/// The resolvers are only given the repositories they read from, along with the claims.
+pub async fn execute(
+    State(state): State<Arc<AppState>>,
+    claims: Claims,
+    Json(req): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let req = req
+        .data(state.event_repo.clone())
+        .data(state.history_repo.clone())
+        .data(claims);
+    Json(schema().execute(req).await)
+}
+
+/// The schema of the API, built once, as it holds no state.
+pub fn schema() -> &'static ApiSchema {
+    static SCHEMA: OnceLock<ApiSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_DEPTH)
+            .limit_complexity(MAX_COMPLEXITY)
+            .finish()
+    })
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// The channel the token was issued for.
+    async fn channel(&self, ctx: &Context<'_>) -> async_graphql::Result<Channel> {
+        let claims = ctx.data::<Claims>()?;
+        Ok(Channel {
+            id: claims.channel.clone(),
+        })
+    }
+
+    /// An event of the channel, if any.
+    async fn event(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<Option<Event>> {
+        let repo = ctx.data::<Arc<dyn event::Repository>>()?;
+        let claims = ctx.data::<Claims>()?;
+        let req = find_event::Request {
+            id,
+            channel: claims.channel.clone(),
+        };
+        match find_event::execute(repo.clone(), req).await {
+            Ok(event) => Ok(Some(Event {
+                id: event.id,
+                name: event.name,
+                timestamp: event.timestamp,
+                timezone: event.timezone.into(),
+                repeat: event.repeat.to_string(),
+                ends_at: event.ends_at,
+                participants: event.participants.into_iter().map(Into::into).collect(),
+                channel: event.channel,
+            })),
+            Err(find_event::Error::NotFound) => Ok(None),
+            Err(find_event::Error::Unknown) => Err(to_graphql(
+                Error::Internal(String::from("could not find the event")).with_event(id),
+            )),
+        }
+    }
+}
+
+pub struct Channel {
+    id: String,
+}
+
+#[Object]
+impl Channel {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The events of the channel, optionally with the text on their names.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        query: Option<String>,
+        #[graphql(default = 20)] first: usize,
+    ) -> async_graphql::Result<Vec<Event>> {
+        let repo = ctx.data::<Arc<dyn event::Repository>>()?;
+        let req = find_all_events::Request {
+            channel: self.id.clone(),
+            page: None,
+        };
+        let events = find_all_events::execute(repo.clone(), req)
+            .await
+            .map_err(|err| {
+                to_graphql(
+                    Error::Internal(format!("could not find the events: {:?}", err))
+                        .with_channel(self.id.clone()),
+                )
+            })?;
+
+        let query = query.unwrap_or_default().trim().to_lowercase();
+        Ok(events
+            .data
+            .into_iter()
+            .filter(|event| query.is_empty() || event.name.to_lowercase().contains(&query))
+            .take(first.min(MAX_EVENTS))
+            .map(|event| Event {
+                id: event.id,
+                name: event.name,
+                timestamp: event.timestamp,
+                timezone: event.timezone.into(),
+                repeat: event.repeat.to_string(),
+                ends_at: event.ends_at,
+                participants: event.participants.into_iter().map(Into::into).collect(),
+                channel: self.id.clone(),
+            })
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Event {
+    id: u32,
+    name: String,
+    timestamp: i64,
+    timezone: String,
+    repeat: String,
+    ends_at: Option<i64>,
+    participants: Vec<EventParticipant>,
+    #[graphql(skip)]
+    channel: String,
+}
+
+#[ComplexObject]
+impl Event {
+    /// The latest picks of the event, newest first.
+    async fn history(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: u32,
+    ) -> async_graphql::Result<Vec<Pick>> {
+        let repo = ctx.data::<Arc<dyn pick_history::Repository>>()?;
+        let req = find_picks::Request {
+            event: self.id,
+            channel: self.channel.clone(),
+            limit: limit.clamp(1, MAX_PICKS),
+        };
+        let picks = find_picks::execute(repo.clone(), req)
+            .await
+            .map_err(|err| {
+                to_graphql(
+                    Error::Internal(format!("could not find the picks: {:?}", err))
+                        .with_event(self.id),
+                )
+            })?;
+        Ok(picks
+            .data
+            .into_iter()
+            .map(|pick| Pick {
+                user: pick.user,
+                timestamp: pick.timestamp,
+                source: pick.source.to_string(),
+                replaced: pick.replaced,
+                partner: pick.partner,
+                no_show: pick.no_show,
+            })
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct EventParticipant {
+    user: String,
+    picked: bool,
+    picked_at: Option<i64>,
+    weight: f64,
+    /// The time the participant is away until, if away.
+    unavailable_until: Option<i64>,
+}
+
+impl From<Participant> for EventParticipant {
+    fn from(value: Participant) -> Self {
+        Self {
+            weight: value.weight(),
+            user: value.user,
+            picked: value.picked,
+            picked_at: value.picked_at,
+            unavailable_until: value.unavailable_until,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Pick {
+    user: String,
+    timestamp: i64,
+    source: String,
+    replaced: Option<String>,
+    partner: Option<String>,
+    /// Whether the picked participant did not show up.
+    no_show: bool,
+}
+
+/// Tells the clients the reason of the errors, leaving the details of the internal ones to the
+/// logs, as on the REST API.
+fn to_graphql(err: Error) -> async_graphql::Error {
+    if err.status().is_server_error() {
+        log::error!("graphql query failed: {}", err);
+        async_graphql::Error::new("internal error")
+    } else {
+        async_graphql::Error::new(err.kind().to_string())
+    }
+}
+
+/// The depth and complexity of the queries, so that a single query cannot load the whole database.
+const MAX_DEPTH: usize = 6;
+const MAX_COMPLEXITY: usize = 500;
+
+const MAX_EVENTS: usize = 100;
+const MAX_PICKS: u32 = 100;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::domain::entities::{PickRecord, PickSource};
+    use crate::repository::event::sample_event;
+
+    use super::*;
+
+    /// Runs the query with the token of the channel `C1`.
+    async fn query(
+        event_repo: Arc<dyn event::Repository>,
+        history_repo: Arc<dyn pick_history::Repository>,
+        query: &str,
+    ) -> Value {
+        let claims = Claims {
+            sub: String::from("U1"),
+            team: String::from("T1"),
+            channel: String::from("C1"),
+            iss: String::new(),
+            aud: String::new(),
+            iat: 0,
+            exp: 0,
+            scope: None,
+            api_key: None,
+        };
+        let req = async_graphql::Request::new(query)
+            .data(event_repo)
+            .data(history_repo)
+            .data(claims);
+        let response = schema().execute(req).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_channel_scope() {
+        let mut events: Vec<_> = (1..=MAX_EVENTS as u32 + 1)
+            .map(|id| sample_event(id, &format!("Event {}", id), vec![]))
+            .collect();
+        let mut other = sample_event(1000, "Other", vec![]);
+        other.channel = String::from("C2");
+        events.push(other);
+        let event_repo: Arc<dyn event::Repository> =
+            Arc::new(event::MemoryRepository::with_events(events));
+        let history = pick_history::MemoryRepository::default();
+        *history.picks.lock().unwrap() = (0..MAX_PICKS + 1)
+            .map(|i| PickRecord {
+                event: 1,
+                channel: String::from("C1"),
+                user: format!("U{}", i),
+                timestamp: i as i64,
+                source: PickSource::Manual,
+                replaced: None,
+                partner: None,
+                no_show: false,
+            })
+            .collect();
+        let history_repo: Arc<dyn pick_history::Repository> = Arc::new(history);
+        let run = |q| query(event_repo.clone(), history_repo.clone(), q);
+
+        // Only the events of the channel of the token are listed, up to the maximum.
+        let data = run("{ channel { events(first: 1000) { id } } }").await;
+        let ids: Vec<u64> = data["channel"]["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["id"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ids.len(), MAX_EVENTS);
+        assert!(!ids.contains(&1000));
+        let data = run(r#"{ channel { events(query: "other") { id } } }"#).await;
+        assert_eq!(data, json!({ "channel": { "events": [] } }));
+        let data = run("{ event(id: 1000) { id } }").await;
+        assert_eq!(data, json!({ "event": null }));
+
+        // The history is limited to between one pick and the maximum, newest first.
+        let data = run("{ event(id: 1) { history(limit: 1000) { user } } }").await;
+        assert_eq!(
+            data["event"]["history"].as_array().unwrap().len(),
+            MAX_PICKS as usize
+        );
+        let data = run("{ event(id: 1) { history(limit: 0) { user } } }").await;
+        assert_eq!(
+            data,
+            json!({ "event": { "history": [{ "user": format!("U{}", MAX_PICKS) }] } })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema() {
+        let sdl = schema().sdl();
+        assert!(sdl.contains("history(limit: Int! = 20): [Pick!]!"));
+
+        // The queries are rejected without a token.
+        let response = schema()
+            .execute("{ channel { events { history { user } } } }")
+            .await;
+        assert_eq!(response.errors.len(), 1);
+
+        let response = schema().execute("{ channel { channel { id } } }").await;
+        assert!(!response.errors.is_empty());
+    }
+}
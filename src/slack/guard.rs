@@ -11,16 +11,20 @@ use hmac::{Hmac, Mac};
 use hyper::{HeaderMap, StatusCode};
 use serde::Deserialize;
 use sha2::Sha256;
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, sync::Arc, time::Duration};
 
 use crate::domain::auth::verify_auth;
+use crate::domain::billing::find_plan;
+use crate::domain::entities::{PlanTier, TeamSettings};
 use crate::domain::events::find_all_events;
-use crate::domain::helpers::team::is_team_special;
+use crate::domain::settings::find_settings;
+use crate::i18n;
 use super::state::AppState;
 
 #[derive(Debug, Deserialize)]
 struct RequestData {
     pub team_id: String,
+    pub enterprise_id: Option<String>,
     pub response_url: String,
     pub channel_id: String,
     pub actions: Vec<String>,
@@ -29,20 +33,32 @@ struct RequestData {
 #[derive(Deserialize)]
 struct InboundRequest {
     pub team_id: Option<String>,
+    pub enterprise_id: Option<String>,
     pub channel_id: Option<String>,
     pub response_url: Option<String>,
     pub text: Option<String>,
     pub payload: Option<String>,
 }
 
+/// The payload of interactive requests. The options load requests have no response URL nor
+/// actions.
 #[derive(Deserialize)]
 struct InboundRequestPayload {
+    #[serde(default)]
     pub response_url: String,
     pub channel: InboundRequestChannel,
     pub user: InboundRequestUser,
+    #[serde(default)]
+    pub enterprise: Option<InboundRequestEnterprise>,
+    #[serde(default)]
     pub actions: Vec<InboundRequestAction>,
 }
 
+#[derive(Deserialize)]
+struct InboundRequestEnterprise {
+    pub id: String,
+}
+
 #[derive(Deserialize)]
 struct InboundRequestUser {
     pub team_id: String,
@@ -56,6 +72,21 @@ struct InboundRequestChannel {
 #[derive(Deserialize)]
 struct InboundRequestAction {
     pub block_id: Option<String>,
+    #[serde(default)]
+    pub selected_option: Option<InboundRequestOption>,
+}
+
+#[derive(Deserialize)]
+struct InboundRequestOption {
+    pub value: Option<String>,
+}
+
+/// The app a request was sent to, found on the form body, on its payload or on the JSON body of the
+/// events.
+#[derive(Deserialize)]
+struct InboundAppRequest {
+    pub api_app_id: Option<String>,
+    pub payload: Option<String>,
 }
 
 impl TryFrom<InboundRequest> for RequestData {
@@ -66,13 +97,18 @@ impl TryFrom<InboundRequest> for RequestData {
             let data = match serde_json::from_str::<InboundRequestPayload>(&payload) {
                 Ok(payload) => Self {
                     team_id: payload.user.team_id,
+                    enterprise_id: payload.enterprise.map(|enterprise| enterprise.id),
                     channel_id: payload.channel.id,
+                    // The selected options are kept along the blocks, as the list items only tell
+                    // the event apart (e.g. duplicating an event).
                     actions: payload
                         .actions
                         .into_iter()
-                        .map(|action| action.block_id)
-                        .filter(|action| action.is_some())
-                        .map(|block_id| block_id.unwrap())
+                        .flat_map(|action| {
+                            let option = action.selected_option.and_then(|option| option.value);
+                            [action.block_id, option]
+                        })
+                        .flatten()
                         .collect(),
                     response_url: payload.response_url,
                 },
@@ -82,6 +118,7 @@ impl TryFrom<InboundRequest> for RequestData {
         }
         Ok(RequestData {
             team_id: value.team_id.ok_or("no team_id")?,
+            enterprise_id: value.enterprise_id.filter(|id| !id.is_empty()),
             channel_id: value.channel_id.ok_or("no channel_id")?,
             actions: vec![value.text.ok_or("no command text")?],
             response_url: value.response_url.ok_or("no response_url")?,
@@ -152,8 +189,6 @@ impl Guard {
 
         let base_str = format!("v0:{}:{}", timestamp, self.body);
 
-        let expected_signature = calculate_signature(&base_str, &self.state.configs.secret);
-
         let received_signature: String = self
             .headers
             .get("x-slack-signature")
@@ -162,13 +197,74 @@ impl Guard {
             .unwrap_or("")
             .to_string();
 
-        // match the two signatures
-        if expected_signature != received_signature {
-            log::trace!("signature mismatch");
-            return Err(StatusCode::UNAUTHORIZED);
+        // The request is verified with the secrets of the app it was sent to. Both secrets are
+        // accepted while the signing secret is rotated.
+        let app = self
+            .state
+            .configs
+            .find_app(find_app_id(&self.body).as_deref());
+        let secrets = [
+            ("primary", Some(app.secret.expose())),
+            (
+                "secondary",
+                app.secondary_secret.as_ref().map(|secret| secret.expose()),
+            ),
+        ];
+        match matching_secret(&base_str, &received_signature, &secrets) {
+            Some("primary") => log::trace!(
+                "signature verified with the primary secret of app {}",
+                app.app_id
+            ),
+            Some(name) => log::info!(
+                "signature verified with the {} secret of app {}, which can be removed once unused",
+                name,
+                app.app_id
+            ),
+            None => {
+                log::trace!("signature mismatch");
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        }
+
+        // The signed requests are only accepted once, so that a captured request cannot be
+        // replayed while its timestamp is still valid.
+        let key = format!("slack-request:{}:{}", timestamp, received_signature);
+        match self
+            .state
+            .store
+            .set_if_absent(&key, String::new(), SEEN_SIGNATURE_TTL)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                log::warn!("rejected replayed request with timestamp {}", timestamp);
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+            // The requests are still served when the store is not available.
+            Err(err) => log::error!("could not save the request signature: {:?}", err),
+        }
+        Ok(())
+    }
+
+    async fn validate_rate_limit(&self) -> Result<(), StatusCode> {
+        let data = self.data()?;
+
+        if !self
+            .state
+            .rate_limiter
+            .check(&format!("team:{}", data.team_id), Utc::now().timestamp())
+            .await
+        {
+            log::trace!("rate limit reached for team {}", data.team_id);
+            return Guard::send_message(
+                &data.response_url,
+                RATE_LIMITED_STR,
+                StatusCode::TOO_MANY_REQUESTS,
+            )
+            .await;
         }
 
-        log::trace!("signature verified");
+        log::trace!("rate limit verified");
         Ok(())
     }
 
@@ -181,6 +277,7 @@ impl Guard {
             self.state.auth_repo.clone(),
             verify_auth::Request {
                 team: data.team_id.clone(),
+                enterprise: data.enterprise_id.clone(),
             },
         )
         .await
@@ -206,23 +303,68 @@ impl Guard {
             }
         };
 
-        let access_token_header: HeaderValue = auth.access_token.parse().map_err(|err| {
-            log::error!("could not parse access token: {}", err);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        let mut access_token_header: HeaderValue =
+            auth.access_token.expose().parse().map_err(|err| {
+                log::error!("could not parse access token: {}", err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        // Keeps the token out of the debug output of the headers.
+        access_token_header.set_sensitive(true);
         self.headers.append("x-access-token", access_token_header);
 
         log::trace!("user authenticated");
         Ok(())
     }
 
-    async fn validate_plan(&mut self) -> Result<(), StatusCode> {
+    /// Finds the settings of the team, telling the handlers the locale to show their messages in and
+    /// the format of their dates.
+    async fn find_settings(&mut self) -> Result<TeamSettings, StatusCode> {
+        let data = self.data()?;
+
+        let settings = match find_settings::execute(
+            self.state.settings_repo.clone(),
+            find_settings::Request {
+                team_id: data.team_id.clone(),
+            },
+        )
+        .await
+        {
+            Ok(settings) => settings,
+            Err(err) => {
+                // The messages are still shown, just not translated.
+                log::error!(
+                    "could not find the settings of team {}: {:?}",
+                    data.team_id,
+                    err
+                );
+                TeamSettings::new(data.team_id.clone())
+            }
+        };
+
+        let locale_header: HeaderValue = settings.locale.code().parse().map_err(|err| {
+            log::error!("could not parse locale: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        self.headers.append("x-locale", locale_header);
+
+        let date_format_header: HeaderValue =
+            settings.date_format.code().parse().map_err(|err| {
+                log::error!("could not parse date format: {}", err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        self.headers.append("x-date-format", date_format_header);
+
+        Ok(settings)
+    }
+
+    async fn validate_plan(&mut self, settings: &TeamSettings) -> Result<(), StatusCode> {
         let data = self.data()?;
 
         let events = match find_all_events::execute(
             self.state.event_repo.clone(),
             find_all_events::Request {
                 channel: data.channel_id.clone(),
+                page: None,
             },
         )
         .await
@@ -252,29 +394,54 @@ impl Guard {
             }
         };
 
-        if is_team_special(data.team_id.clone()) {
-            log::trace!("team {} is special", data.team_id);
-            self.headers.append(
-                "x-reached-limit",
-                "false".parse().map_err(|err| {
-                    log::error!("could not parse reached limit state: {}", err);
-                    StatusCode::INTERNAL_SERVER_ERROR
-            })?);
-            return Ok(());
-        }
-
-        let reached_limit = events.len() > 0;
-        if reached_limit
-            && (data.actions.contains(&String::from("create"))
-                || data.actions.contains(&String::from("add_event_actions")))
+        let plan = match find_plan::execute(
+            self.state.plan_repo.clone(),
+            find_plan::Request {
+                team_id: data.team_id.clone(),
+            },
+        )
+        .await
         {
+            Ok(plan) => plan,
+            Err(err) => {
+                log::trace!(
+                    "could not find the plan of team {}: {:?}",
+                    data.team_id,
+                    err
+                );
+                return Guard::send_error(&data.response_url, StatusCode::INTERNAL_SERVER_ERROR)
+                    .await;
+            }
+        };
+
+        // The limits set by the operators for the team take the place of the ones of its plan.
+        let limits = &settings.limits;
+        let max_events = limits.max_events.unwrap_or(match plan.tier {
+            PlanTier::Free => FREE_PLAN_MAX_EVENTS,
+            PlanTier::Pro => self.state.configs.max_events,
+        });
+        let max_participants = limits.max_participants.unwrap_or(match plan.tier {
+            PlanTier::Free => FREE_PLAN_MAX_PARTICIPANTS,
+            PlanTier::Pro => self.state.configs.max_participants,
+        });
+        self.set_limit("x-max-events", max_events)?;
+        self.set_limit("x-max-participants", max_participants)?;
+        let reached_limit = events.len() >= max_events as usize;
+        if reached_limit && data.actions.iter().any(|action| creates_event(action)) {
             log::trace!(
-                "cannot create more events on channel {} for team {} (current={})",
+                "cannot create more events on channel {} for team {} (current={}, plan={:?})",
                 data.channel_id,
                 data.team_id,
-                events.len()
+                events.len(),
+                plan.tier
             );
-            return Guard::send_error(&data.response_url, StatusCode::FORBIDDEN).await;
+            let message = match plan.tier {
+                PlanTier::Free if limits.max_events.is_none() => {
+                    i18n::t(settings.locale, "free_plan_limit")
+                }
+                _ => i18n::t(settings.locale, "plan_limit"),
+            };
+            return Guard::send_message(&data.response_url, message, StatusCode::FORBIDDEN).await;
         }
 
         let reached_limit_header: HeaderValue =
@@ -289,17 +456,23 @@ impl Guard {
         Ok(())
     }
 
+    /// Tells the handlers a limit of the team (e.g. the number of participants of each event).
+    fn set_limit(&mut self, name: &'static str, limit: u32) -> Result<(), StatusCode> {
+        let header: HeaderValue = limit.to_string().parse().map_err(|err| {
+            log::error!("could not parse {}: {}", name, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        self.headers.append(name, header);
+        Ok(())
+    }
+
     fn data(&self) -> Result<RequestData, StatusCode> {
         let data: InboundRequest = serde_urlencoded::from_str(&self.body).map_err(|err| {
-            log::trace!(
-                "failed to deserialize auth raw request: {}: {}",
-                err,
-                self.body
-            );
+            log::trace!("failed to deserialize auth raw request: {}", err);
             StatusCode::BAD_REQUEST
         })?;
         data.try_into().map_err(|err| {
-            log::trace!("failed to parse auth raw request: {}: {}", err, self.body);
+            log::trace!("failed to parse auth raw request: {}", err);
             StatusCode::BAD_REQUEST
         })
     }
@@ -311,11 +484,23 @@ impl Guard {
     }
 
     async fn send_error(response_url: &str, err: StatusCode) -> Result<(), StatusCode> {
-        let body = super::to_response_error(&format!(
+        let message = format!(
             "Error {}: {}.",
             err.as_str(),
             err.canonical_reason().unwrap_or("Unknown")
-        ))?;
+        );
+        Guard::send_message(response_url, &message, err).await
+    }
+
+    async fn send_message(
+        response_url: &str,
+        message: &str,
+        err: StatusCode,
+    ) -> Result<(), StatusCode> {
+        if response_url.is_empty() {
+            return Err(err);
+        }
+        let body = super::to_response_error(message)?;
         if let Err(err) = super::send_post(response_url, hyper::Body::from(body)).await {
             log::trace!(
                 "could not send slack response for unauthorized user: {}",
@@ -330,10 +515,26 @@ pub async fn validate(request: Request<Body>, next: Next<Body>) -> Result<Respon
     let mut guard = Guard::new(request).await?;
     log::trace!("auth guard: validating signature");
     guard.validate_signature().await?;
+    log::trace!("auth guard: validating rate limit");
+    guard.validate_rate_limit().await?;
     log::trace!("auth guard: validating token");
     guard.validate_token().await?;
+    log::trace!("auth guard: finding team settings");
+    let settings = guard.find_settings().await?;
     log::trace!("auth guard: validating team plan");
-    guard.validate_plan().await?;
+    guard.validate_plan(&settings).await?;
+    Ok(next.run(guard.request()).await)
+}
+
+/// Validates only the signature of the requests, for the ones that are not bound to a user (e.g.
+/// the Slack events).
+pub async fn validate_signature(
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Result<Response, StatusCode> {
+    let guard = Guard::new(request).await?;
+    log::trace!("signature guard: validating signature");
+    guard.validate_signature().await?;
     Ok(next.run(guard.request()).await)
 }
 
@@ -355,10 +556,105 @@ async fn response_to_string(stream: &mut Body) -> Result<String, StatusCode> {
     Ok(entire_body)
 }
 
-fn calculate_signature(base_str: &str, secret: &str) -> String {
+/// Whether the action adds an event to the channel, either a new one or a copy of another.
+fn creates_event(action: &str) -> bool {
+    matches!(action, "create" | "add_event_actions" | "duplicate")
+        || action.starts_with("clone ")
+        || action.starts_with("import ")
+}
+
+/// The number of events each channel of the teams on the free plan can have.
+const FREE_PLAN_MAX_EVENTS: u32 = 1;
+
+/// The number of participants each event of the teams on the free plan can have.
+const FREE_PLAN_MAX_PARTICIPANTS: u32 = 10;
+
+/// The time the signatures are remembered for, covering the five minutes the timestamps can
+/// differ from the local time, both ways.
+const SEEN_SIGNATURE_TTL: Duration = Duration::from_secs(10 * 60);
+
+const RATE_LIMITED_STR: &str =
+    "Too many requests were made by your team. Please wait a minute and try again. ⏳";
+
+/// Finds the app the request was sent to, if told.
+fn find_app_id(body: &str) -> Option<String> {
+    let request = serde_urlencoded::from_str::<InboundAppRequest>(body)
+        .ok()
+        .filter(|request| request.api_app_id.is_some() || request.payload.is_some())
+        .or_else(|| serde_json::from_str::<InboundAppRequest>(body).ok())?;
+    match (request.api_app_id, request.payload) {
+        (Some(app_id), _) => Some(app_id),
+        (None, Some(payload)) => serde_json::from_str::<InboundAppRequest>(&payload)
+            .ok()
+            .and_then(|payload| payload.api_app_id),
+        (None, None) => None,
+    }
+}
+
+/// Finds the name of the secret the signature was calculated with, if any.
+fn matching_secret(
+    base_str: &str,
+    signature: &str,
+    secrets: &[(&'static str, Option<&str>)],
+) -> Option<&'static str> {
+    secrets
+        .iter()
+        .filter_map(|(name, secret)| secret.map(|secret| (*name, secret)))
+        .find(|(_, secret)| calculate_signature(base_str, secret) == signature)
+        .map(|(name, _)| name)
+}
+
+pub fn calculate_signature(base_str: &str, secret: &str) -> String {
     let mut mac =
         Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
     mac.update(base_str.as_bytes());
     let result = mac.finalize().into_bytes();
     format!("v0={}", hex::encode(result))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_secret() {
+        let base_str = "v0:1700000000:text=list";
+        let secrets = [("primary", Some("new")), ("secondary", Some("old"))];
+
+        let signature = calculate_signature(base_str, "new");
+        assert_eq!(
+            matching_secret(base_str, &signature, &secrets),
+            Some("primary")
+        );
+        let signature = calculate_signature(base_str, "old");
+        assert_eq!(
+            matching_secret(base_str, &signature, &secrets),
+            Some("secondary")
+        );
+        assert_eq!(
+            matching_secret(
+                base_str,
+                &signature,
+                &[("primary", Some("new")), ("secondary", None)]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_app_id() {
+        assert_eq!(
+            find_app_id("team_id=T1&api_app_id=A1&text=list"),
+            Some(String::from("A1"))
+        );
+        assert_eq!(
+            find_app_id("payload=%7B%22api_app_id%22%3A%22A2%22%7D"),
+            Some(String::from("A2"))
+        );
+        assert_eq!(
+            find_app_id(r#"{"type":"event_callback","api_app_id":"A3"}"#),
+            Some(String::from("A3"))
+        );
+        assert_eq!(find_app_id("team_id=T1&text=list"), None);
+    }
+}
@@ -1,9 +1,18 @@
+use std::{future::Future, sync::OnceLock, time::Duration};
+
 use handlebars::Handlebars;
-use hyper::{Body, HeaderMap, Request};
+use hyper::{http::response::Parts, Body, HeaderMap, Request};
 use hyper_tls::HttpsConnector;
+use rand::Rng;
 use serde_json::json;
+use tokio::task::{self, JoinHandle};
+use tracing::Instrument;
 
-use crate::{domain::timezone::Timezone, helpers::date::Date};
+use crate::{
+    domain::{date_format::DateFormat, locale::Locale, timezone::Timezone},
+    helpers::date::Date,
+    i18n,
+};
 
 pub fn render_template(
     template: &str,
@@ -13,6 +22,44 @@ pub fn render_template(
     Ok(reg.render_template(&template, &context)?)
 }
 
+/// The policy the outbound requests are retried with when they fail.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts of each request, including the first one.
+    pub attempts: u32,
+    /// The delay before the first retry, doubled on each of the next ones.
+    pub base_delay_ms: u64,
+}
+
+impl RetryPolicy {
+    /// Calculates the delay before retrying the failed attempt, jittered between half and the whole
+    /// of the exponential backoff.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)));
+        Duration::from_millis(rand::thread_rng().gen_range(delay / 2..=delay))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay_ms: 500,
+        }
+    }
+}
+
+static RETRY_POLICY: OnceLock<RetryPolicy> = OnceLock::new();
+
+/// Sets the policy the outbound requests are retried with. It can only be set once, on startup.
+pub fn set_retry_policy(policy: RetryPolicy) {
+    if RETRY_POLICY.set(policy).is_err() {
+        log::warn!("retry policy was already set");
+    }
+}
+
 pub async fn send_post(
     url: &str,
     body: hyper::Body,
@@ -25,31 +72,116 @@ pub async fn send_authorized_post(
     token: &str,
     body: hyper::Body,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let https = HttpsConnector::new();
-    let client = hyper::Client::builder().build(https);
+    log::trace!("sending authorized request to {}", url);
 
-    let req = Request::builder()
-        .method(hyper::Method::POST)
-        .uri(url)
-        .header("Content-Type", "application/json")
-        .header("Authorization", String::from("Bearer ") + token)
-        .body(body)?;
+    let (parts, _) = send_request(
+        |body| {
+            Request::builder()
+                .method(hyper::Method::POST)
+                .uri(url)
+                .header("Content-Type", "application/json")
+                .header("Authorization", String::from("Bearer ") + token)
+                .body(body)
+        },
+        body,
+    )
+    .await
+    .map_err(|err| err as Box<dyn std::error::Error>)?;
 
-    log::trace!("sending authorized request to {}\n\t- {:?}", url, &req);
+    tracing::trace!(
+        url,
+        status = parts.status.as_u16(),
+        "authorized response received"
+    );
 
-    let res = client.request(req).await?;
+    Ok(())
+}
 
-    let res_str = format!("{:?}", res);
-    let body = hyper::body::to_bytes(res).await;
+pub async fn send_authorized_get(
+    url: &str,
+    token: &str,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    log::trace!("sending authorized request to {}", url);
+
+    let (parts, body) = send_request(
+        |body| {
+            Request::builder()
+                .method(hyper::Method::GET)
+                .uri(url)
+                .header("Authorization", String::from("Bearer ") + token)
+                .body(body)
+        },
+        Body::empty(),
+    )
+    .await?;
 
-    log::trace!(
-        "authorized response received from request to {}\n\t- {}\n\t- {:?}",
+    tracing::trace!(
         url,
-        res_str,
-        body
+        status = parts.status.as_u16(),
+        "authorized response received"
     );
 
-    Ok(())
+    Ok(body)
+}
+
+/// Sends an authorized JSON request to a Slack API method, returning the response body.
+pub async fn send_authorized_json_post(
+    url: &str,
+    token: &str,
+    body: hyper::Body,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    log::trace!("sending authorized request to {}", url);
+
+    let (parts, body) = send_request(
+        |body| {
+            Request::builder()
+                .method(hyper::Method::POST)
+                .uri(url)
+                .header("Content-Type", "application/json")
+                .header("Authorization", String::from("Bearer ") + token)
+                .body(body)
+        },
+        body,
+    )
+    .await?;
+
+    tracing::trace!(
+        url,
+        status = parts.status.as_u16(),
+        "authorized response received"
+    );
+
+    Ok(body)
+}
+
+/// Sends an authorized form request (e.g. to the Stripe API), returning the response body.
+pub async fn send_authorized_form_post(
+    url: &str,
+    token: &str,
+    body: hyper::Body,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    log::trace!("sending authorized request to {}", url);
+
+    let (parts, body) = send_request(
+        |body| {
+            Request::builder()
+                .method(hyper::Method::POST)
+                .uri(url)
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .header("Authorization", String::from("Bearer ") + token)
+                .body(body)
+        },
+        body,
+    )
+    .await?;
+
+    tracing::trace!(
+        url,
+        status = parts.status.as_u16(),
+        "authorized response received"
+    );
+
+    Ok(body)
 }
 
 pub async fn send_post_with_type(
@@ -57,29 +189,99 @@ pub async fn send_post_with_type(
     body: hyper::Body,
     content_type: String,
 ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    log::trace!("sending action response to {}", url);
+
+    let (parts, body) = send_request(
+        |body| {
+            Request::builder()
+                .method(hyper::Method::POST)
+                .uri(url)
+                .header("Content-Type", content_type.as_str())
+                .body(body)
+        },
+        body,
+    )
+    .await?;
+
+    tracing::trace!(url, status = parts.status.as_u16(), "response received");
+
+    Ok(body)
+}
+
+/// Sends the request built by `build`, retrying it with the configured policy when it fails to
+/// connect, is rate limited or fails on the server.
+///
+/// The rate limited requests are retried after the delay Slack asks for on `Retry-After`.
+async fn send_request<F>(
+    build: F,
+    body: Body,
+) -> Result<(Parts, String), Box<dyn std::error::Error + Send + Sync>>
+where
+    F: Fn(Body) -> Result<Request<Body>, hyper::http::Error>,
+{
+    let policy = RETRY_POLICY.get().copied().unwrap_or_default();
+    let body = hyper::body::to_bytes(body).await?;
+
     let https = HttpsConnector::new();
     let client = hyper::Client::builder().build(https);
 
-    let req = Request::builder()
-        .method(hyper::Method::POST)
-        .uri(url)
-        .header("Content-Type", content_type)
-        .body(body)?;
+    let mut attempt = 1;
+    loop {
+        let req = build(Body::from(body.clone()))?;
+        let uri = req.uri().clone();
 
-    log::trace!("sending action response to {}: {:?}", url, &req);
+        let result = client.request(req).await;
+        let delay = match &result {
+            Ok(response) if !is_retryable(response.status()) => None,
+            Ok(response) => {
+                log::warn!(
+                    "request to {} failed with status {} (attempt {}/{})",
+                    uri,
+                    response.status(),
+                    attempt,
+                    policy.attempts
+                );
+                Some(retry_after(response.headers()).unwrap_or_else(|| policy.backoff(attempt)))
+            }
+            Err(err) => {
+                log::warn!(
+                    "request to {} failed: {} (attempt {}/{})",
+                    uri,
+                    err,
+                    attempt,
+                    policy.attempts
+                );
+                Some(policy.backoff(attempt))
+            }
+        };
 
-    let response = client.request(req).await?;
-    let (parts, body) = response.into_parts();
-    let body = response_to_string(body).await?;
+        match delay {
+            Some(delay) if attempt < policy.attempts => {
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            _ => {
+                let (parts, body) = result?.into_parts();
+                return Ok((parts, response_to_string(body).await?));
+            }
+        }
+    }
+}
 
-    log::trace!(
-        "response received from request to {}: {:?}: {}",
-        url,
-        parts,
-        body
-    );
+fn is_retryable(status: hyper::StatusCode) -> bool {
+    status == hyper::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
 
-    Ok(body)
+/// Reads the number of seconds to wait before retrying a rate limited request.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(hyper::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
 }
 
 pub fn find_token(headers: &HeaderMap) -> Result<String, hyper::StatusCode> {
@@ -121,6 +323,36 @@ pub fn find_reached_limit(headers: &HeaderMap) -> Result<bool, hyper::StatusCode
     Ok(reached_limit)
 }
 
+/// Finds the number of events each channel of the team can have, set by the guard from its plan.
+pub fn find_max_events(headers: &HeaderMap) -> Result<u32, hyper::StatusCode> {
+    find_limit(headers, "x-max-events")
+}
+
+/// Finds the number of participants the events of the team can have, set by the guard from its plan.
+pub fn find_max_participants(headers: &HeaderMap) -> Result<u32, hyper::StatusCode> {
+    find_limit(headers, "x-max-participants")
+}
+
+fn find_limit(headers: &HeaderMap, name: &str) -> Result<u32, hyper::StatusCode> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| {
+            log::trace!("{} not provided on handler", name);
+            hyper::StatusCode::BAD_REQUEST
+        })
+}
+
+/// Finds the locale of the team the guard found, which is English when there is none.
+pub fn find_locale(headers: &HeaderMap) -> Locale {
+    headers
+        .get("x-locale")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Locale::try_from(value.to_string()).ok())
+        .unwrap_or_default()
+}
+
 pub fn to_response(value: &str) -> Result<String, hyper::StatusCode> {
     Ok(json!({ "text": value }).to_string())
 }
@@ -129,8 +361,65 @@ pub fn to_response_error(value: &str) -> Result<String, hyper::StatusCode> {
     Ok(json!({ "text": value, "response_type": "ephemeral" }).to_string())
 }
 
-pub fn fmt_timestamp(timestamp: i64, timezone: Timezone) -> String {
-    Date::new(timestamp).with_timezone(timezone).to_string()
+/// Runs the handler of a command or action on the background, so that Slack is acknowledged within
+/// its 3 seconds. Slack no longer shows the failures of the handler, so they are replied to the
+/// user as an ephemeral error on `response_url`.
+pub fn spawn_with_reply<F>(response_url: String, locale: Locale, handler: F) -> JoinHandle<()>
+where
+    F: Future<Output = Result<(), hyper::StatusCode>> + Send + 'static,
+{
+    task::spawn(
+        async move {
+            if let Err(err) = handler.await {
+                log::info!("failed to handle request on the background: {}", err);
+                send_error(&response_url, locale, err).await;
+            }
+        }
+        .instrument(tracing::Span::current()),
+    )
+}
+
+async fn send_error(response_url: &str, locale: Locale, status: hyper::StatusCode) {
+    let text = i18n::format(
+        locale,
+        "error",
+        &[
+            ("code", status.as_str()),
+            ("reason", status.canonical_reason().unwrap_or("Unknown")),
+        ],
+    );
+    let body = json!({ "text": text, "response_type": "ephemeral", "replace_original": false });
+
+    if let Err(err) = send_post(response_url, Body::from(body.to_string())).await {
+        log::error!("unable to send slack error response: {}", err);
+    }
+}
+
+/// Finds the date format of the team the guard found, which is the default one when there is
+/// none.
+pub fn find_date_format(headers: &HeaderMap) -> DateFormat {
+    headers
+        .get("x-date-format")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateFormat::try_from(value.to_string()).ok())
+        .unwrap_or_default()
+}
+
+pub fn fmt_timestamp(timestamp: i64, timezone: Timezone, format: DateFormat) -> String {
+    format.datetime(&Date::new(timestamp).with_timezone(timezone).to_datetime())
+}
+
+/// Formats the timestamp with the date tokens of Slack, so that each user sees it on their own
+/// timezone and relative to the current day (e.g. `Today at 2:15 PM`). The clients that cannot
+/// render them show the timestamp on the timezone of the event.
+///
+/// It is only rendered on the `mrkdwn` texts.
+pub fn fmt_slack_date(timestamp: i64, timezone: Timezone, format: DateFormat) -> String {
+    format!(
+        "<!date^{}^{{date_short_pretty}} {{time}}|{}>",
+        Date::new(timestamp).timestamp(),
+        fmt_timestamp(timestamp, timezone, format)
+    )
 }
 
 async fn response_to_string(res: Body) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
@@ -138,3 +427,92 @@ async fn response_to_string(res: Body) -> Result<String, Box<dyn std::error::Err
     let body_string = String::from_utf8(body_bytes.to_vec())?;
     Ok(body_string)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            attempts: 3,
+            base_delay_ms: 100,
+        };
+
+        assert!((50..=100).contains(&policy.backoff(1).as_millis()));
+        assert!((100..=200).contains(&policy.backoff(2).as_millis()));
+        assert!((200..=400).contains(&policy.backoff(3).as_millis()));
+    }
+
+    #[test]
+    fn test_fmt_slack_date() {
+        assert_eq!(
+            fmt_slack_date(1724678100, Timezone::ECT, DateFormat::default()),
+            "<!date^1724678100^{date_short_pretty} {time}|2024-08-26 15:15 CEST>"
+        );
+    }
+
+    /// Serves a response URL on a local port, forwarding the bodies posted to it.
+    fn response_url() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let service = hyper::service::make_service_fn(move |_| {
+            let tx = tx.clone();
+            async move {
+                Ok::<_, hyper::Error>(hyper::service::service_fn(move |req: Request<Body>| {
+                    let tx = tx.clone();
+                    async move {
+                        let body = response_to_string(req.into_body()).await.unwrap();
+                        tx.send(body).unwrap();
+                        Ok::<_, hyper::Error>(hyper::Response::new(Body::empty()))
+                    }
+                }))
+            }
+        });
+        let server = hyper::Server::bind(&([127, 0, 0, 1], 0).into()).serve(service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        (url, rx)
+    }
+
+    #[tokio::test]
+    async fn test_spawn_with_reply() {
+        let (url, mut posted) = response_url();
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let handle = spawn_with_reply(
+            url.clone(),
+            Locale::default(),
+            async move { rx.await.unwrap() },
+        );
+        assert!(
+            !handle.is_finished(),
+            "acknowledged before the handler finished"
+        );
+
+        tx.send(Err(hyper::StatusCode::NOT_FOUND)).unwrap();
+        handle.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&posted.recv().await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "text": "Error 404: Not Found.",
+                "response_type": "ephemeral",
+                "replace_original": false,
+            })
+        );
+
+        spawn_with_reply(url, Locale::default(), async { Ok(()) })
+            .await
+            .unwrap();
+        assert!(posted.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(hyper::header::RETRY_AFTER, "30".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(30)));
+    }
+}
@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{extract::FromRequestParts, http::request::Parts};
+use hyper::header;
+use jsonwebtoken::{errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    domain::auth::{create_api_key::API_KEY_PREFIX, verify_api_key},
+    error,
+    helpers::date::Date,
+};
+
+use super::AppState;
+
+/// The claims of the tokens that authenticate the API requests, scoped to the channel of the
+/// user that issued them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Claims {
+    /// The user that issued the token.
+    pub sub: String,
+    pub team: String,
+    pub channel: String,
+    pub iss: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    /// The scopes granted beyond the ones of the channel, separated by spaces (e.g. `admin`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// The API key the request was authenticated with, instead of a token.
+    #[serde(skip)]
+    pub api_key: Option<u32>,
+}
+
+impl Claims {
+    pub fn new(user: String, team: String, channel: String, keys: &Keys) -> Self {
+        let now = Date::now().timestamp();
+        Self {
+            sub: user,
+            team,
+            channel,
+            iss: keys.issuer.clone(),
+            aud: keys.audience.clone(),
+            iat: now,
+            exp: now + ACCESS_TOKEN_EXPIRATION_SECONDS,
+            scope: None,
+            api_key: None,
+        }
+    }
+
+    /// Whether the scope was granted to the token.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope
+            .as_deref()
+            .is_some_and(|scopes| scopes.split_whitespace().any(|granted| granted == scope))
+    }
+}
+
+/// The keys the API tokens are signed and verified with.
+pub struct Keys {
+    algorithm: Algorithm,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    /// The public key, shared with the services that verify the tokens, when signing with a key
+    /// pair.
+    public_key: Option<String>,
+    issuer: String,
+    audience: String,
+}
+
+impl Keys {
+    /// Loads the keys from the configuration, signing with RS256 when a key pair is configured and
+    /// with HS256 otherwise.
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let (algorithm, encoding, decoding, public_key) =
+            match (&config.jwt_private_key_file, &config.jwt_public_key_file) {
+                (Some(private_key_file), Some(public_key_file)) => {
+                    let private_key = read_key(private_key_file)?;
+                    let public_key = read_key(public_key_file)?;
+                    (
+                        Algorithm::RS256,
+                        EncodingKey::from_rsa_pem(private_key.as_bytes())
+                            .map_err(|err| format!("invalid jwt private key: {}", err))?,
+                        DecodingKey::from_rsa_pem(public_key.as_bytes())
+                            .map_err(|err| format!("invalid jwt public key: {}", err))?,
+                        Some(public_key),
+                    )
+                }
+                (None, None) => {
+                    // The secret is never shared with Slack, so that its signatures cannot issue
+                    // API tokens.
+                    let secret = config.jwt_secret.as_ref().ok_or_else(|| {
+                        String::from(
+                            "either the jwt secret or the jwt key files must be configured",
+                        )
+                    })?;
+                    (
+                        Algorithm::HS256,
+                        EncodingKey::from_secret(secret.as_bytes()),
+                        DecodingKey::from_secret(secret.as_bytes()),
+                        None,
+                    )
+                }
+                _ => {
+                    return Err(String::from(
+                        "both the jwt private and public key files must be configured",
+                    ))
+                }
+            };
+
+        Ok(Self {
+            algorithm,
+            encoding,
+            decoding,
+            public_key,
+            issuer: config.jwt_issuer.clone(),
+            audience: config.jwt_audience.clone(),
+        })
+    }
+
+    pub fn public_key(&self) -> Option<&str> {
+        self.public_key.as_deref()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Invalid,
+    Expired,
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for Claims {
+    type Rejection = error::Error;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| {
+                log::trace!("bearer token not provided on api request");
+                error::Error::Unauthorized
+            })?;
+
+        // The API keys of the machine clients are accepted in place of the tokens.
+        if token.trim().starts_with(API_KEY_PREFIX) {
+            let key = verify_api_key::execute(
+                state.api_key_repo.clone(),
+                verify_api_key::Request {
+                    key: token.trim().to_string(),
+                },
+            )
+            .await
+            .map_err(|err| match err {
+                verify_api_key::Error::Unauthorized => {
+                    log::trace!("unknown api key on api request");
+                    error::Error::Unauthorized
+                }
+                verify_api_key::Error::Unknown => {
+                    error::Error::Internal(String::from("could not verify the api key"))
+                }
+            })?;
+            return Ok(Self {
+                api_key: Some(key.id),
+                ..Self::new(key.user, key.team, key.channel, &state.configs.jwt_keys)
+            });
+        }
+
+        decode(token.trim(), &state.configs.jwt_keys).map_err(|err| {
+            log::trace!("invalid bearer token on api request: {:?}", err);
+            error::Error::Unauthorized
+        })
+    }
+}
+
+/// Encodes the claims as a JSON Web Token.
+pub fn encode(claims: &Claims, keys: &Keys) -> Result<String, Error> {
+    jsonwebtoken::encode(&Header::new(keys.algorithm), claims, &keys.encoding).map_err(|err| {
+        log::error!("could not encode jwt: {}", err);
+        Error::Invalid
+    })
+}
+
+/// Decodes the claims of a JSON Web Token, verifying its signature, expiration, issuer and
+/// audience.
+pub fn decode(token: &str, keys: &Keys) -> Result<Claims, Error> {
+    let mut validation = Validation::new(keys.algorithm);
+    validation.leeway = 0;
+    validation.set_issuer(&[&keys.issuer]);
+    validation.set_audience(&[&keys.audience]);
+
+    jsonwebtoken::decode::<Claims>(token, &keys.decoding, &validation)
+        .map(|data| data.claims)
+        .map_err(|err| match err.kind() {
+            ErrorKind::ExpiredSignature => Error::Expired,
+            _ => Error::Invalid,
+        })
+}
+
+fn read_key(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("could not read key {}: {}", path, err))
+}
+
+/// The scope of the operators on the admin endpoints. The application never grants it, so their
+/// tokens are signed with the keys of the configuration.
+pub const ADMIN_SCOPE: &str = "admin";
+
+/// The number of seconds the access tokens are valid for. They are renewed with the refresh
+/// tokens.
+pub const ACCESS_TOKEN_EXPIRATION_SECONDS: i64 = 60 * 60;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(secret: &str, audience: &str) -> Keys {
+        Keys {
+            algorithm: Algorithm::HS256,
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            public_key: None,
+            issuer: "team-event-picker".to_string(),
+            audience: audience.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let keys = keys("secret", "api");
+        let claims = Claims::new("U1".to_string(), "T1".to_string(), "C1".to_string(), &keys);
+        let token = encode(&claims, &keys).unwrap();
+
+        assert_eq!(decode(&token, &keys), Ok(claims.clone()));
+        assert_eq!(
+            decode(&token, &super::tests::keys("other", "api")),
+            Err(Error::Invalid)
+        );
+        assert_eq!(
+            decode(&token, &super::tests::keys("secret", "other")),
+            Err(Error::Invalid)
+        );
+        assert_eq!(decode("not.a.token", &keys), Err(Error::Invalid));
+
+        let admin = Claims {
+            scope: Some(format!("read {}", ADMIN_SCOPE)),
+            ..claims.clone()
+        };
+        let decoded = decode(&encode(&admin, &keys).unwrap(), &keys).unwrap();
+        assert!(decoded.has_scope(ADMIN_SCOPE));
+        assert!(!claims.has_scope(ADMIN_SCOPE));
+
+        let expired = Claims {
+            exp: claims.iat - 1,
+            ..claims
+        };
+        assert_eq!(
+            decode(&encode(&expired, &keys).unwrap(), &keys),
+            Err(Error::Expired)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use hyper::{HeaderMap, StatusCode};
+
+use crate::{helpers::date::Date, repository::store::Store};
+
+use super::AppState;
+
+/// Counts the requests of each client on fixed time windows, rejecting the ones over the limit.
+///
+/// The counters are kept on the store, so that they are shared by all the instances.
+pub struct RateLimiter {
+    limit: u32,
+    window_seconds: i64,
+    store: Arc<dyn Store>,
+    /// The number of proxies trusted to tell the address of their client.
+    trusted_proxy_hops: usize,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window_seconds: i64, store: Arc<dyn Store>) -> Self {
+        Self {
+            limit,
+            window_seconds,
+            store,
+            trusted_proxy_hops: 0,
+        }
+    }
+
+    pub fn with_trusted_proxy_hops(mut self, hops: usize) -> Self {
+        self.trusted_proxy_hops = hops;
+        self
+    }
+
+    /// Counts a request of the client, returning whether it is within the limit.
+    ///
+    /// The requests are let through when the store fails, not to block the clients on outages.
+    pub async fn check(&self, key: &str, now: i64) -> bool {
+        let start = now - now.rem_euclid(self.window_seconds);
+        let ttl = Duration::from_secs(self.window_seconds.max(1) as u64);
+        match self
+            .store
+            .increment(&format!("rate:{}:{}", key, start), ttl)
+            .await
+        {
+            Ok(count) => count <= self.limit as u64,
+            Err(err) => {
+                log::error!("could not count the requests of {}: {:?}", key, err);
+                true
+            }
+        }
+    }
+}
+
+/// Rate limits the requests by the IP address of the client, for the routes that are not bound to
+/// a team.
+pub async fn limit_by_ip(
+    Extension(state): Extension<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let ip = client_ip(
+        request.headers(),
+        peer,
+        state.rate_limiter.trusted_proxy_hops,
+    );
+
+    if let Some(ip) = ip {
+        if !state
+            .rate_limiter
+            .check(&format!("ip:{}", ip), Date::now().timestamp())
+            .await
+        {
+            log::trace!("rate limit reached for ip {}", ip);
+            return StatusCode::TOO_MANY_REQUESTS.into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Finds the address of the client, which is the one of the peer unless behind trusted proxies.
+///
+/// Each proxy appends the address of its own client to `X-Forwarded-For`, so the client is the
+/// entry as many hops from the right as there are proxies. The entries on its left are set by the
+/// client, and are never trusted.
+fn client_ip(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    trusted_proxy_hops: usize,
+) -> Option<IpAddr> {
+    if trusted_proxy_hops == 0 {
+        return peer;
+    }
+    let forwarded: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+    forwarded
+        .len()
+        .checked_sub(trusted_proxy_hops)
+        .and_then(|index| forwarded[index].parse().ok())
+        .or(peer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::store::MemoryStore;
+
+    #[tokio::test]
+    async fn test_check() {
+        let limiter = RateLimiter::new(2, 60, Arc::new(MemoryStore::default()));
+        let now = 1686661200;
+
+        assert!(limiter.check("T1", now).await);
+        assert!(limiter.check("T1", now + 1).await);
+        assert!(!limiter.check("T1", now + 2).await);
+        assert!(limiter.check("T2", now + 2).await);
+        assert!(limiter.check("T1", now + 60).await);
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let peer = Some(IpAddr::from([10, 0, 0, 1]));
+        let mut headers = HeaderMap::new();
+        headers.append("x-forwarded-for", "1.1.1.1, 2.2.2.2".parse().unwrap());
+        headers.append("x-forwarded-for", "3.3.3.3".parse().unwrap());
+
+        // The header is set by the clients themselves when there is no proxy.
+        assert_eq!(client_ip(&headers, peer, 0), peer);
+        assert_eq!(
+            client_ip(&headers, peer, 1),
+            Some(IpAddr::from([3, 3, 3, 3]))
+        );
+        assert_eq!(
+            client_ip(&headers, peer, 2),
+            Some(IpAddr::from([2, 2, 2, 2]))
+        );
+        assert_eq!(client_ip(&headers, peer, 4), peer);
+        assert_eq!(client_ip(&HeaderMap::new(), peer, 1), peer);
+    }
+}
@@ -3,11 +3,32 @@ mod state;
 pub mod templates; // <--- Temporarily public
 
 mod actions;
+mod activity;
+mod admin;
+mod api;
+mod billing;
+mod cached_client;
+mod calendar;
+mod client;
 mod commands;
+mod correlation;
+mod dashboard;
+mod dev;
+mod events;
+mod google;
+mod graphql;
 mod guard;
+mod jwt;
+mod limiter;
+mod notifier;
 mod oauth;
+mod options;
 mod sender;
 mod server;
+mod throttle;
+mod tls;
+mod user_groups;
+mod user_statuses;
 
 use helpers::*;
 use state::*;
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::domain::acknowledgments::repick_unacknowledged;
+use crate::domain::auth::verify_auth;
+use crate::domain::entities::{NotificationJob, NotificationKind};
+use crate::domain::events::{find_event_reminders, pick_auto_participants};
+use crate::domain::history::find_monthly_recaps;
+use crate::domain::notifications::{claim_notification, enqueue_notification, finish_notification};
+use crate::helpers::reporter::{self, Report};
+use crate::repository::{acknowledgment, auth, event, notification, settings};
+
+use super::client::SlackClient;
+use super::sender::{self, PostError};
+
+/// The policy the failed notifications are retried with.
+#[derive(Clone, Copy, Debug)]
+pub struct NotifierConfigs {
+    /// The number of notifications posted at the same time.
+    pub workers: usize,
+    /// The number of attempts of each notification, including the first one.
+    pub max_attempts: u32,
+    /// The seconds before retrying a failed notification, doubled on each retry.
+    pub base_delay_seconds: i64,
+}
+
+/// Posts the notifications queued on the repository, on a pool of workers.
+///
+/// The notifications are kept on the repository until they are posted, so that they survive the
+/// restarts, and retried with an exponential backoff when they fail. The ones that cannot be posted
+/// are moved to the dead-letter collection, to be re-driven by the operators.
+pub struct Notifier {
+    job_repo: Arc<dyn notification::Repository>,
+    event_repo: Arc<dyn event::Repository>,
+    auth_repo: Arc<dyn auth::Repository>,
+    settings_repo: Arc<dyn settings::Repository>,
+    acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+    client: Arc<dyn SlackClient>,
+    configs: NotifierConfigs,
+}
+
+impl Notifier {
+    pub fn new(
+        job_repo: Arc<dyn notification::Repository>,
+        event_repo: Arc<dyn event::Repository>,
+        auth_repo: Arc<dyn auth::Repository>,
+        settings_repo: Arc<dyn settings::Repository>,
+        acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+        client: Arc<dyn SlackClient>,
+        configs: NotifierConfigs,
+    ) -> Self {
+        Self {
+            job_repo,
+            event_repo,
+            auth_repo,
+            settings_repo,
+            acknowledgment_repo,
+            client,
+            configs,
+        }
+    }
+
+    /// Runs the workers until the application stops.
+    pub async fn run(self: Arc<Self>) {
+        let workers = (0..self.configs.workers.max(1)).map(|_| {
+            let notifier = self.clone();
+            tokio::spawn(async move { notifier.work().await })
+        });
+        futures::future::join_all(workers).await;
+    }
+
+    async fn work(&self) {
+        loop {
+            let req = claim_notification::Request {
+                lock_seconds: LOCK_SECONDS,
+            };
+            match claim_notification::execute(self.job_repo.clone(), req).await {
+                Ok(Some(job)) => self.process(job).await,
+                Ok(None) | Err(..) => tokio::time::sleep(POLL_INTERVAL).await,
+            }
+        }
+    }
+
+    async fn process(&self, job: NotificationJob) {
+        let error = self.post(&job).await.err();
+        if let Some(err) = &error {
+            log::warn!(
+                "could not post notification {} (attempt {}/{}, permanent: {}): {}",
+                job.id,
+                job.attempts + 1,
+                self.configs.max_attempts,
+                err.permanent,
+                err
+            );
+        }
+        let req = finish_notification::Request {
+            job,
+            permanent: error.as_ref().is_some_and(|err| err.permanent),
+            error: error.map(|err| err.message),
+            max_attempts: self.configs.max_attempts,
+            base_delay_seconds: self.configs.base_delay_seconds,
+        };
+        if let Ok(finish_notification::Response::Failed(failed)) =
+            finish_notification::execute(self.job_repo.clone(), req).await
+        {
+            reporter::report(
+                Report::new(
+                    "notifier",
+                    format!(
+                        "gave up posting notification {} after {} attempts: {}",
+                        failed.id, failed.attempts, failed.error
+                    ),
+                )
+                .with_team(failed.team_id),
+            );
+        }
+    }
+
+    async fn post(&self, job: &NotificationJob) -> Result<(), PostError> {
+        let req = verify_auth::Request {
+            team: job.team_id.clone(),
+            enterprise: None,
+        };
+        let access_token = verify_auth::execute(self.auth_repo.clone(), req)
+            .await
+            .map_err(|err| PostError {
+                message: format!("could not find the access token: {:?}", err),
+                // The team uninstalled the app.
+                permanent: matches!(err, verify_auth::Error::Unauthorized),
+            })?
+            .access_token;
+        let payload = job.payload.clone();
+        let invalid =
+            |err: serde_json::Error| PostError::permanent(format!("invalid notification: {}", err));
+        match job.kind {
+            NotificationKind::Pick => {
+                let mut pick: pick_auto_participants::Pick =
+                    serde_json::from_value(payload).map_err(invalid)?;
+                pick.access_token = access_token;
+                sender::post_pick(
+                    self.event_repo.clone(),
+                    self.settings_repo.clone(),
+                    self.acknowledgment_repo.clone(),
+                    self.job_repo.clone(),
+                    self.client.clone(),
+                    pick,
+                )
+                .await
+            }
+            NotificationKind::DirectPick => {
+                let mut pick: sender::DirectPick =
+                    serde_json::from_value(payload).map_err(invalid)?;
+                pick.access_token = access_token;
+                sender::post_direct_pick(self.settings_repo.clone(), self.client.clone(), pick)
+                    .await
+            }
+            NotificationKind::Reminder => {
+                let mut reminder: find_event_reminders::Reminder =
+                    serde_json::from_value(payload).map_err(invalid)?;
+                reminder.access_token = access_token;
+                sender::post_reminder(self.settings_repo.clone(), self.client.clone(), reminder)
+                    .await
+            }
+            NotificationKind::Repick => {
+                let mut repick: repick_unacknowledged::Repick =
+                    serde_json::from_value(payload).map_err(invalid)?;
+                repick.access_token = access_token;
+                sender::post_repick(
+                    self.settings_repo.clone(),
+                    self.acknowledgment_repo.clone(),
+                    self.client.clone(),
+                    repick,
+                )
+                .await
+            }
+            NotificationKind::Recap => {
+                let mut recap: find_monthly_recaps::Recap =
+                    serde_json::from_value(payload).map_err(invalid)?;
+                recap.access_token = access_token;
+                sender::post_recap(self.settings_repo.clone(), self.client.clone(), recap).await
+            }
+        }
+    }
+}
+
+/// Queues the notifications of a kind, to be posted by the workers.
+pub async fn queue<T: Serialize>(
+    job_repo: Arc<dyn notification::Repository>,
+    kind: NotificationKind,
+    notifications: Vec<T>,
+    team_id: impl Fn(&T) -> String,
+) {
+    for notification in notifications.into_iter() {
+        let team_id = team_id(&notification);
+        let payload = match serde_json::to_value(&notification) {
+            Ok(payload) => payload,
+            Err(err) => {
+                log::error!("could not serialize the notification: {}", err);
+                continue;
+            }
+        };
+        let req = enqueue_notification::Request {
+            kind,
+            team_id,
+            payload,
+            run_at: None,
+        };
+        if let Err(err) = enqueue_notification::execute(job_repo.clone(), req).await {
+            log::error!("could not queue the {:?} notification: {:?}", kind, err);
+        }
+    }
+}
+
+/// The seconds a worker holds a notification for, after which another worker can post it.
+const LOCK_SECONDS: i64 = 120;
+
+/// The time the workers wait for before looking up the queue again, when it is empty.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
@@ -1,37 +1,41 @@
-use std::{collections::HashSet, fmt::Display, sync::Arc};
+use std::{collections::HashSet, sync::Arc};
 
 use axum::{
     extract::{Query, State},
-    response::Redirect,
+    response::{IntoResponse, Redirect, Response},
 };
+use hmac::{Hmac, Mac};
+use hyper::{header, HeaderMap};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 
-use crate::{domain::auth::save_auth, slack::helpers};
+use crate::{
+    domain::{auth::save_auth, entities::AuditAction},
+    helpers::{date::Date, secret::SecretString},
+    slack::helpers,
+    views::onboarding::{self, OnboardingView},
+};
 
-use super::state::AppState;
+use super::{activity, sender, state::AppState};
 
 #[derive(Deserialize)]
 pub struct OAuthQuery {
     pub code: Option<String>,
     pub error: Option<String>,
+    pub state: Option<String>,
 }
 
-impl Display for OAuthQuery {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(err) = self.error.clone() {
-            return write!(f, "error={}", err);
-        }
-        if let Some(code) = self.code.clone() {
-            return write!(f, "code={}", code);
-        }
-        write!(f, "empty")
-    }
+#[derive(Deserialize)]
+pub struct InstallQuery {
+    /// The app to install, when other than the main one.
+    pub app_id: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct OAuthAccessRequest {
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: SecretString,
     pub code: String,
 }
 
@@ -40,7 +44,11 @@ pub struct OAuthAccessRawResponse {
     pub token_type: Option<String>,
     pub access_token: Option<String>,
     pub team: Option<OAuthTeamResponse>,
+    pub enterprise: Option<OAuthTeamResponse>,
+    #[serde(default)]
+    pub is_enterprise_install: bool,
     pub scope: Option<String>,
+    pub authed_user: Option<OAuthUserResponse>,
 }
 
 #[derive(Deserialize)]
@@ -48,12 +56,20 @@ pub struct OAuthTeamResponse {
     pub id: String,
 }
 
+#[derive(Deserialize)]
+pub struct OAuthUserResponse {
+    pub id: String,
+}
+
 #[derive(Debug)]
 pub struct OAuthAccessResponse {
     pub token_type: String,
-    pub access_token: String,
+    pub access_token: SecretString,
+    /// The team of the workspace, or the enterprise of the organization-wide installs.
     pub team_id: String,
+    pub enterprise_id: Option<String>,
     pub scope: String,
+    pub user_id: Option<String>,
 }
 
 impl TryFrom<OAuthAccessRawResponse> for OAuthAccessResponse {
@@ -61,11 +77,20 @@ impl TryFrom<OAuthAccessRawResponse> for OAuthAccessResponse {
 
     fn try_from(value: OAuthAccessRawResponse) -> Result<Self, Self::Error> {
         let result: Result<OAuthAccessResponse, String> = (move || {
+            let enterprise_id = value.enterprise.map(|enterprise| enterprise.id);
+            // The organization-wide installs are not bound to any team of the organization.
+            let team_id = if value.is_enterprise_install {
+                enterprise_id.clone().ok_or("no enterprise")?
+            } else {
+                value.team.ok_or("no team")?.id
+            };
             Ok(Self {
                 token_type: value.token_type.ok_or("no token type")?,
-                access_token: value.access_token.ok_or("no access token")?,
-                team_id: value.team.ok_or("no team")?.id,
+                access_token: value.access_token.ok_or("no access token")?.into(),
+                team_id,
+                enterprise_id,
                 scope: value.scope.ok_or("no scope")?,
+                user_id: value.authed_user.map(|user| user.id),
             })
         })();
         match result {
@@ -79,7 +104,7 @@ impl TryFrom<OAuthAccessRawResponse> for OAuthAccessResponse {
                 }
                 let scopes: HashSet<String> =
                     response.scope.split(",").map(|v| v.to_string()).collect();
-                for scope in vec!["commands", "channels:join", "chat:write"].into_iter() {
+                for scope in REQUIRED_SCOPES.into_iter() {
                     if !scopes.contains(scope) {
                         log::error!("oauth access does not contain scope {}", scope);
                         return Err(Self::Error::FORBIDDEN);
@@ -95,11 +120,40 @@ impl TryFrom<OAuthAccessRawResponse> for OAuthAccessResponse {
     }
 }
 
+/// Redirects to the Slack authorization page, binding the signed state of the installation to
+/// the browser through a cookie.
+pub async fn install(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<InstallQuery>,
+) -> Response {
+    let app = state.configs.find_app(query.app_id.as_deref());
+    let oauth_state = generate_state(
+        &app.app_id,
+        state.configs.app.secret.expose(),
+        Date::now().timestamp(),
+    );
+    let cookie = format!(
+        "{}={}; Max-Age={}; Path=/api/oauth; HttpOnly; Secure; SameSite=Lax",
+        STATE_COOKIE, oauth_state, STATE_EXPIRATION_SECONDS
+    );
+    let url = format!(
+        "https://slack.com/oauth/v2/authorize?client_id={}&scope={}&state={}",
+        app.client_id,
+        REQUIRED_SCOPES.join(","),
+        oauth_state
+    );
+    ([(header::SET_COOKIE, cookie)], Redirect::to(&url)).into_response()
+}
+
 pub async fn execute(
+    headers: HeaderMap,
     State(state): State<Arc<AppState>>,
     Query(query): Query<OAuthQuery>,
 ) -> Result<Redirect, hyper::StatusCode> {
-    log::trace!("received oauth authorization: {}", query);
+    tracing::trace!(
+        authorized = query.error.is_none(),
+        "received oauth authorization"
+    );
 
     if let Some(..) = query.error {
         return Err(hyper::StatusCode::UNAUTHORIZED);
@@ -107,9 +161,27 @@ pub async fn execute(
         return Err(hyper::StatusCode::BAD_REQUEST);
     }
 
+    let oauth_state = query.state.as_deref().unwrap_or_default();
+    let app_id = find_cookie(&headers, STATE_COOKIE)
+        .filter(|cookie| *cookie == oauth_state)
+        .and_then(|_| {
+            verify_state(
+                oauth_state,
+                state.configs.app.secret.expose(),
+                Date::now().timestamp(),
+            )
+        });
+    let app = match app_id {
+        Some(app_id) => state.configs.find_app(Some(&app_id)),
+        None => {
+            log::trace!("invalid oauth state: {}", oauth_state);
+            return Err(hyper::StatusCode::FORBIDDEN);
+        }
+    };
+
     let request_body = serde_urlencoded::to_string(&OAuthAccessRequest {
-        client_id: state.configs.client_id.clone(),
-        client_secret: state.configs.client_secret.clone(),
+        client_id: app.client_id.clone(),
+        client_secret: app.client_secret.clone(),
         code: query.code.unwrap(),
     })
     .map_err(|err| {
@@ -137,6 +209,7 @@ pub async fn execute(
 
     let request = save_auth::Request {
         team: response.team_id.clone(),
+        enterprise: response.enterprise_id.clone(),
         access_token: response.access_token.clone(),
     };
     if let Err(err) = save_auth::execute(state.auth_repo.clone(), request).await {
@@ -145,13 +218,177 @@ pub async fn execute(
     }
 
     log::trace!(
-        "saved oauth access token: token_id={}, access_token={}",
+        "saved oauth access token: team_id={}, app_id={}",
         response.team_id,
-        response.access_token
+        app.app_id
     );
 
+    if let Some(user_id) = response.user_id.clone() {
+        send_welcome(&state, &response, user_id).await;
+    }
+
+    let actor = activity::Actor {
+        team_id: response.team_id,
+        channel: None,
+        user: response.user_id,
+    };
+    activity::record(state.audit_repo.clone(), actor, AuditAction::Install, None).await;
+
     Ok(Redirect::to(&format!(
         "https://slack.com/app_redirect?app={}",
-        state.configs.app_id
+        app.app_id
     )))
 }
+
+/// Messages the installing user directly, to get their team started. The installation goes on
+/// when the message cannot be sent.
+async fn send_welcome(state: &AppState, response: &OAuthAccessResponse, user_id: String) {
+    let locale = sender::find_settings(state.settings_repo.clone(), &response.team_id)
+        .await
+        .locale;
+    let view = onboarding::view(OnboardingView { user_id, locale });
+    if let Err(err) = state
+        .slack_client
+        .post_message(response.access_token.expose(), view)
+        .await
+    {
+        log::error!(
+            "unable to send welcome message to team {}: {}",
+            response.team_id,
+            err
+        );
+    }
+}
+
+/// Generates the state of an installation, made of a random nonce, its expiration and the app being
+/// installed, signed with the application secret.
+fn generate_state(app_id: &str, secret: &str, now: i64) -> String {
+    let nonce: [u8; 16] = rand::thread_rng().gen();
+    let data = format!(
+        "{}.{}.{}",
+        hex::encode(nonce),
+        now + STATE_EXPIRATION_SECONDS,
+        app_id
+    );
+    format!("{}.{}", data, sign_state(&data, secret))
+}
+
+/// Verifies the state of an installation, returning the app being installed when valid.
+fn verify_state(oauth_state: &str, secret: &str, now: i64) -> Option<String> {
+    let (data, signature) = oauth_state.rsplit_once('.')?;
+    let mut parts = data.splitn(3, '.').skip(1);
+    let expires_at = parts.next()?.parse::<i64>().ok()?;
+    let app_id = parts.next()?;
+    let signature = hex::decode(signature).ok()?;
+    (expires_at > now && state_mac(data, secret).verify_slice(&signature).is_ok())
+        .then(|| app_id.to_string())
+}
+
+fn sign_state(data: &str, secret: &str) -> String {
+    hex::encode(state_mac(data, secret).finalize().into_bytes())
+}
+
+fn state_mac(data: &str, secret: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(format!("oauth:{}", data).as_bytes());
+    mac
+}
+
+fn find_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+const REQUIRED_SCOPES: [&str; 12] = [
+    "commands",
+    "channels:join",
+    "channels:read",
+    "chat:write",
+    "dnd:read",
+    // The snapshots of the exported events.
+    "files:write",
+    // The private channels the bot is checked to be a member of, when creating the events.
+    "groups:read",
+    // The direct messages of the private events and the welcome message of the installing user.
+    "im:write",
+    // The anchor messages of the events, pinned on their channels.
+    "pins:write",
+    "usergroups:read",
+    "users.profile:read",
+    // The time zones of the users, preselected on their new events.
+    "users:read",
+];
+const STATE_COOKIE: &str = "oauth_state";
+/// The number of seconds the users have to complete an installation.
+const STATE_EXPIRATION_SECONDS: i64 = 10 * 60;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_state() {
+        let now = 1686661200;
+        let oauth_state = generate_state("A1", "secret", now);
+
+        assert_eq!(
+            verify_state(&oauth_state, "secret", now),
+            Some(String::from("A1"))
+        );
+        assert_eq!(verify_state(&oauth_state, "other", now), None);
+        assert_eq!(
+            verify_state(&oauth_state, "secret", now + STATE_EXPIRATION_SECONDS),
+            None
+        );
+        assert_eq!(verify_state("", "secret", now), None);
+        assert_eq!(
+            verify_state(
+                &oauth_state.replace(&(now + STATE_EXPIRATION_SECONDS).to_string(), "9999999999"),
+                "secret",
+                now
+            ),
+            None
+        );
+        assert_eq!(
+            verify_state(&oauth_state.replace(".A1.", ".A2."), "secret", now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_enterprise_install() {
+        let response: OAuthAccessResponse = serde_json::from_str::<OAuthAccessRawResponse>(
+            r#"{
+                "ok": true,
+                "token_type": "bot",
+                "access_token": "xoxb-1",
+                "scope": "commands,channels:join,channels:read,chat:write,dnd:read,files:write,groups:read,im:write,pins:write,usergroups:read,users.profile:read,users:read",
+                "team": null,
+                "enterprise": { "id": "E1", "name": "Acme" },
+                "is_enterprise_install": true
+            }"#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        assert_eq!(response.team_id, "E1");
+        assert_eq!(response.enterprise_id, Some(String::from("E1")));
+    }
+
+    #[test]
+    fn test_find_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, "a=1; oauth_state=abc.1.f0".parse().unwrap());
+
+        assert_eq!(find_cookie(&headers, STATE_COOKIE), Some("abc.1.f0"));
+        assert_eq!(find_cookie(&headers, "b"), None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Form, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::domain::dtos::PageRequest;
+use crate::domain::events::search_events;
+use crate::repository::event::EventSort;
+
+use super::AppState;
+
+#[derive(Deserialize)]
+pub struct OptionsBody {
+    payload: String,
+}
+
+/// Slack options load request, sent while typing on an external select.
+#[derive(Deserialize, Debug)]
+pub struct OptionsRequest {
+    #[serde(rename = "type")]
+    request_type: String,
+    action_id: String,
+    #[serde(default)]
+    value: String,
+    channel: Channel,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Channel {
+    id: String,
+}
+
+pub async fn execute(
+    State(state): State<Arc<AppState>>,
+    Form(body): Form<OptionsBody>,
+) -> Result<Json<Value>, hyper::StatusCode> {
+    let payload: OptionsRequest = serde_json::from_str(&body.payload).map_err(|err| {
+        log::trace!("could not parse options request: {}", err);
+        hyper::StatusCode::BAD_REQUEST
+    })?;
+    tracing::Span::current().record("channel", payload.channel.id.as_str());
+    tracing::trace!(
+        request_type = payload.request_type.as_str(),
+        action_id = payload.action_id.as_str(),
+        "received options request"
+    );
+
+    if payload.request_type != "block_suggestion" || payload.action_id != "select_event" {
+        log::trace!(
+            "unknown options request: {} {}",
+            payload.request_type,
+            payload.action_id
+        );
+        return Err(hyper::StatusCode::BAD_REQUEST);
+    }
+
+    let events = search_events::execute(
+        state.event_repo.clone(),
+        search_events::Request {
+            channel: payload.channel.id,
+            query: payload.value,
+            page: PageRequest {
+                cursor: 0,
+                limit: MAX_OPTIONS,
+            },
+            picked_only: false,
+            sort: EventSort::Id,
+        },
+    )
+    .await
+    .map_err(|err| {
+        log::error!("could not search events for options: {:?}", err);
+        hyper::StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .data;
+
+    Ok(Json(json!({
+        "options": events
+            .into_iter()
+            .map(|event| json!({
+                "text": {
+                    "type": "plain_text",
+                    "text": format!("[{}]: {}", event.id, event.name)
+                },
+                "value": event.id.to_string()
+            }))
+            .collect::<Vec<Value>>()
+    })))
+}
+
+/// The maximum number of options Slack accepts on a select menu.
+const MAX_OPTIONS: usize = 100;
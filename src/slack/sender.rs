@@ -1,28 +1,980 @@
-use crate::domain::events::pick_auto_participants;
-use crate::views::pick_participant;
-
-use super::helpers;
-
-pub async fn post_picks(picks: Vec<pick_auto_participants::Pick>) {
-    for pick in picks.into_iter() {
-        let body = pick_participant::view(pick_participant::PickParticipantView {
-            source: pick_participant::PickParticipantSource::Scheduler,
-            event_id: pick.event_id,
-            event_name: pick.event_name,
-            channel_id: pick.channel_id,
-            user_id: dotenv::var("BOT_NAME").unwrap_or(String::from("Team Picker")),
-            user_picked_id: pick.user_id,
-            left_count: pick.left_count,
-        })
-        .to_string();
-        helpers::send_authorized_post(
-            "https://slack.com/api/chat.postMessage",
-            &pick.access_token,
-            hyper::Body::from(body),
+use std::fmt::Display;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::domain::acknowledgments::{repick_unacknowledged, save_acknowledgment};
+use crate::domain::entities::{NotificationKind, TeamSettings, Visibility};
+use crate::domain::events::{
+    find_event, find_event_reminders, pick_auto_participants, set_event_anchor,
+};
+use crate::domain::history::{find_monthly_recaps, find_picks};
+use crate::domain::locale::Locale;
+use crate::domain::notifications::enqueue_notification;
+use crate::domain::settings::find_settings;
+use crate::helpers::date::Date;
+use crate::helpers::secret::SecretString;
+use crate::repository::{acknowledgment, event, notification, pick_history, settings};
+use crate::views::{
+    anchor, announcement, channel_access, pick_participant, private_pick, recap, reminder,
+};
+
+use super::client::{self, SlackClient};
+use super::AppState;
+
+/// A notification that could not be posted.
+#[derive(Debug)]
+pub struct PostError {
+    pub message: String,
+    /// Whether posting it again would fail the same way (e.g. the channel was archived).
+    pub permanent: bool,
+}
+
+impl PostError {
+    pub fn permanent(message: String) -> Self {
+        Self {
+            message,
+            permanent: true,
+        }
+    }
+
+    fn client(context: String, err: client::Error) -> Self {
+        Self {
+            permanent: err.is_permanent(),
+            message: format!("{}: {}", context, err),
+        }
+    }
+}
+
+impl Display for PostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Posts the message of a scheduled pick, failing only when the message itself could not be
+/// posted, so that retrying it never posts the pick twice.
+pub async fn post_pick(
+    event_repo: Arc<dyn event::Repository>,
+    settings_repo: Arc<dyn settings::Repository>,
+    acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+    job_repo: Arc<dyn notification::Repository>,
+    client: Arc<dyn SlackClient>,
+    pick: pick_auto_participants::Pick,
+) -> Result<(), PostError> {
+    let settings = find_settings(settings_repo.clone(), &pick.team_id).await;
+    if pick.visibility == Visibility::Direct {
+        send_private_pick(
+            job_repo,
+            client.as_ref(),
+            pick.access_token.expose(),
+            settings.locale,
+            &pick.participants,
+            |user| DirectPick {
+                team_id: pick.team_id.clone(),
+                event_id: pick.event_id,
+                recipient_id: user,
+                channel_id: pick.channel_id.clone(),
+                event_name: pick.event_name.clone(),
+                user_picked_id: pick.user_id.clone(),
+                partner_picked_id: pick.partner_id.clone(),
+                left_count: pick.left_count,
+                description: pick.description.clone(),
+                url: pick.url.clone(),
+                access_token: SecretString::default(),
+            },
+        )
+        .await;
+        return Ok(());
+    }
+    let thread_ts = match &pick.anchor {
+        Some(pick_auto_participants::Anchor::Posted(ts)) => Some(ts.clone()),
+        Some(pick_auto_participants::Anchor::Missing) => {
+            post_anchor(event_repo.clone(), client.as_ref(), &pick).await
+        }
+        None => None,
+    };
+    let body = pick_participant::view(pick_participant::PickParticipantView {
+        source: pick_participant::PickParticipantSource::Scheduler,
+        event_id: pick.event_id,
+        event_name: pick.event_name.clone(),
+        channel_id: pick.channel_id.clone(),
+        user_id: dotenv::var("BOT_NAME").unwrap_or(String::from("Team Picker")),
+        user_picked_id: pick.user_id.clone(),
+        partner_picked_id: pick.partner_id.clone(),
+        left_count: pick.left_count,
+        thread_ts,
+        replaced: vec![],
+        locale: settings.locale,
+        message: settings.pick_message,
+        acknowledge_minutes: settings.acknowledge_minutes,
+        description: pick.description.clone(),
+        url: pick.url.clone(),
+        private: false,
+    });
+    let ts = match client.post_message(pick.access_token.expose(), body).await {
+        Ok(ts) => ts,
+        Err(client::Error::Api(err)) if err == "not_in_channel" => {
+            notify_channel_access(client.as_ref(), &pick, settings.locale).await;
+            return Err(PostError::permanent(format!(
+                "failed to notify pick results: the bot is not on channel {}",
+                pick.channel_id
+            )));
+        }
+        Err(err) => {
+            return Err(PostError::client(
+                String::from("failed to notify pick results"),
+                err,
+            ))
+        }
+    };
+    if let Some(minutes) = settings.acknowledge_minutes {
+        let req = save_acknowledgment::Request {
+            event: pick.event_id,
+            event_name: pick.event_name.clone(),
+            channel: pick.channel_id.clone(),
+            team_id: pick.team_id.clone(),
+            user: pick.user_id.clone(),
+            message_ts: ts,
+            minutes,
+        };
+        wait_acknowledgment(acknowledgment_repo.clone(), req).await;
+    }
+    announce(
+        client.as_ref(),
+        pick.access_token.expose(),
+        &pick.notify_channels,
+        &pick.channel_id,
+        &pick.event_name,
+        &pick.user_id,
+        pick.partner_id.as_deref(),
+        settings.locale,
+    )
+    .await;
+    Ok(())
+}
+
+/// Updates the message of a pick that was not acknowledged in time with its repick.
+pub async fn post_repick(
+    settings_repo: Arc<dyn settings::Repository>,
+    acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+    client: Arc<dyn SlackClient>,
+    repick: repick_unacknowledged::Repick,
+) -> Result<(), PostError> {
+    let settings = find_settings(settings_repo.clone(), &repick.team_id).await;
+    let mut body = pick_participant::view(pick_participant::PickParticipantView {
+        source: pick_participant::PickParticipantSource::Timeout,
+        event_id: repick.event_id,
+        event_name: repick.event_name.clone(),
+        channel_id: repick.channel_id.clone(),
+        user_id: dotenv::var("BOT_NAME").unwrap_or(String::from("Team Picker")),
+        user_picked_id: repick.user_id.clone(),
+        partner_picked_id: repick.partner_id.clone(),
+        left_count: repick.left_count,
+        thread_ts: None,
+        replaced: vec![repick.replaced],
+        locale: settings.locale,
+        message: settings.pick_message,
+        acknowledge_minutes: settings.acknowledge_minutes,
+        description: repick.description.clone(),
+        url: repick.url.clone(),
+        private: false,
+    });
+    body["ts"] = json!(repick.message_ts);
+    client
+        .update_message(repick.access_token.expose(), body)
+        .await
+        .map_err(|err| {
+            PostError::client(
+                format!(
+                    "failed to update the pick message of event {}",
+                    repick.event_id
+                ),
+                err,
+            )
+        })?;
+    // The repicked participant has to acknowledge the pick as well.
+    if let Some(minutes) = settings.acknowledge_minutes {
+        let req = save_acknowledgment::Request {
+            event: repick.event_id,
+            event_name: repick.event_name,
+            channel: repick.channel_id,
+            team_id: repick.team_id,
+            user: repick.user_id,
+            message_ts: repick.message_ts,
+            minutes,
+        };
+        wait_acknowledgment(acknowledgment_repo.clone(), req).await;
+    }
+    Ok(())
+}
+
+async fn wait_acknowledgment(
+    acknowledgment_repo: Arc<dyn acknowledgment::Repository>,
+    req: save_acknowledgment::Request,
+) {
+    let event = req.event;
+    if let Err(err) = save_acknowledgment::execute(acknowledgment_repo, req).await {
+        log::error!(
+            "could not wait for the acknowledgment of event {}: {:?}",
+            event,
+            err
+        );
+    }
+}
+
+/// Finds the settings of the team, which are the default ones when they cannot be found.
+pub async fn find_settings(
+    settings_repo: Arc<dyn settings::Repository>,
+    team_id: &str,
+) -> TeamSettings {
+    let request = find_settings::Request {
+        team_id: team_id.to_string(),
+    };
+    match find_settings::execute(settings_repo, request).await {
+        Ok(settings) => settings,
+        Err(err) => {
+            log::error!("could not find the settings of team {}: {:?}", team_id, err);
+            TeamSettings::new(team_id.to_string())
+        }
+    }
+}
+
+/// Announces the picks made from the channels of the events on their other channels.
+pub struct PickAnnouncer {
+    event_repo: Arc<dyn event::Repository>,
+    history_repo: Arc<dyn pick_history::Repository>,
+    job_repo: Arc<dyn notification::Repository>,
+    client: Arc<dyn SlackClient>,
+    team_id: String,
+    token: String,
+    locale: Locale,
+}
+
+impl PickAnnouncer {
+    pub fn new(state: &AppState, team_id: String, token: String, locale: Locale) -> Self {
+        Self {
+            event_repo: state.event_repo.clone(),
+            history_repo: state.history_repo.clone(),
+            job_repo: state.notification_repo.clone(),
+            client: state.slack_client.clone(),
+            team_id,
+            token,
+            locale,
+        }
+    }
+
+    /// Announces the latest pick of the event, once it is picked, skipped or repicked. The picks of
+    /// the private events are sent by direct message to their participants instead.
+    pub async fn announce_latest(&self, event_id: u32, channel: String) {
+        let event = match find_event::execute(
+            self.event_repo.clone(),
+            find_event::Request {
+                id: event_id,
+                channel: channel.clone(),
+            },
         )
         .await
-        .unwrap_or_else(|err| {
-            log::error!("failed to notify pick results: {}", err);
+        {
+            Ok(event)
+                if !event.notify_channels.is_empty() || event.visibility == Visibility::Direct =>
+            {
+                event
+            }
+            Ok(..) => return,
+            Err(err) => {
+                log::error!("could not find event {} to announce: {:?}", event_id, err);
+                return;
+            }
+        };
+        let request = find_picks::Request {
+            event: event_id,
+            channel,
+            limit: 1,
+        };
+        let pick = match find_picks::execute(self.history_repo.clone(), request).await {
+            Ok(picks) => match picks.data.into_iter().next() {
+                Some(pick) => pick,
+                None => return,
+            },
+            Err(err) => {
+                log::error!(
+                    "could not find the latest pick of event {}: {:?}",
+                    event_id,
+                    err
+                );
+                return;
+            }
+        };
+        if event.visibility == Visibility::Direct {
+            let participants: Vec<String> =
+                event.participants.iter().map(|p| p.user.clone()).collect();
+            let left_count = event.participants.iter().filter(|p| !p.picked).count();
+            send_private_pick(
+                self.job_repo.clone(),
+                self.client.as_ref(),
+                &self.token,
+                self.locale,
+                &participants,
+                |user| DirectPick {
+                    team_id: self.team_id.clone(),
+                    event_id,
+                    recipient_id: user,
+                    channel_id: event.channel.clone(),
+                    event_name: event.name.clone(),
+                    user_picked_id: pick.user.clone(),
+                    partner_picked_id: pick.partner.clone(),
+                    left_count,
+                    description: event.description.clone(),
+                    url: event.url.clone(),
+                    access_token: SecretString::default(),
+                },
+            )
+            .await;
+            return;
+        }
+        announce(
+            self.client.as_ref(),
+            &self.token,
+            &event.notify_channels,
+            &event.channel,
+            &event.name,
+            &pick.user,
+            pick.partner.as_deref(),
+            self.locale,
+        )
+        .await;
+    }
+}
+
+/// Announces a pick on the other channels of its event, joining the public channels the bot is
+/// not a member of yet.
+#[allow(clippy::too_many_arguments)]
+pub async fn announce(
+    client: &dyn SlackClient,
+    token: &str,
+    channels: &[String],
+    event_channel: &str,
+    event_name: &str,
+    user_picked_id: &str,
+    partner_picked_id: Option<&str>,
+    locale: Locale,
+) {
+    for channel in channels {
+        let body = announcement::view(announcement::AnnouncementView {
+            channel_id: channel.clone(),
+            event_channel_id: event_channel.to_string(),
+            event_name: event_name.to_string(),
+            user_picked_id: user_picked_id.to_string(),
+            partner_picked_id: partner_picked_id.map(String::from),
+            locale,
         });
+        let result = match client.post_message(token, body.clone()).await {
+            Err(client::Error::Api(err)) if err == "not_in_channel" => {
+                match client.conversations_join(token, channel).await {
+                    Ok(..) => client.post_message(token, body).await,
+                    Err(err) => Err(err),
+                }
+            }
+            result => result,
+        };
+        if let Err(err) = result {
+            log::error!("failed to announce pick on channel {}: {}", channel, err);
+        }
+    }
+}
+
+/// The pick of a private event, sent by direct message to one of its participants.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectPick {
+    pub team_id: String,
+    pub event_id: u32,
+    pub recipient_id: String,
+    pub channel_id: String,
+    pub event_name: String,
+    pub user_picked_id: String,
+    #[serde(default)]
+    pub partner_picked_id: Option<String>,
+    pub left_count: usize,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(skip)]
+    pub access_token: SecretString,
+}
+
+impl DirectPick {
+    fn view(&self, locale: Locale) -> Value {
+        private_pick::view(private_pick::PrivatePickView {
+            recipient_id: self.recipient_id.clone(),
+            channel_id: self.channel_id.clone(),
+            event_name: self.event_name.clone(),
+            user_picked_id: self.user_picked_id.clone(),
+            partner_picked_id: self.partner_picked_id.clone(),
+            left_count: self.left_count,
+            description: self.description.clone(),
+            url: self.url.clone(),
+            locale,
+        })
+    }
+}
+
+/// Sends the pick of a private event by direct message to each of its participants, instead of
+/// posting it on the channel. The participants the message could not be sent to are only logged,
+/// so that retrying it never sends the pick twice to the others.
+///
+/// The participants who are not to be disturbed are sent it once their window ends, through the
+/// queue of the notifications.
+async fn send_private_pick(
+    job_repo: Arc<dyn notification::Repository>,
+    client: &dyn SlackClient,
+    token: &str,
+    locale: Locale,
+    participants: &[String],
+    data: impl Fn(String) -> DirectPick,
+) {
+    let now = Date::now().timestamp();
+    for participant in participants.iter() {
+        let pick = data(participant.clone());
+        if let Some(run_at) = dnd_ends_at(client, token, participant, now).await {
+            if hold_direct_pick(job_repo.clone(), &pick, run_at).await {
+                continue;
+            }
+        }
+        if let Err(err) = client.post_message(token, pick.view(locale)).await {
+            log::error!(
+                "failed to send the pick of event {} to {}: {}",
+                pick.event_id,
+                participant,
+                err
+            );
+        }
+    }
+}
+
+/// Sends the pick of a private event held back until its participant could be disturbed.
+pub async fn post_direct_pick(
+    settings_repo: Arc<dyn settings::Repository>,
+    client: Arc<dyn SlackClient>,
+    pick: DirectPick,
+) -> Result<(), PostError> {
+    let locale = find_settings(settings_repo.clone(), &pick.team_id)
+        .await
+        .locale;
+    client
+        .post_message(pick.access_token.expose(), pick.view(locale))
+        .await
+        .map_err(|err| {
+            PostError::client(
+                format!(
+                    "failed to send the pick of event {} to {}",
+                    pick.event_id, pick.recipient_id
+                ),
+                err,
+            )
+        })?;
+    Ok(())
+}
+
+/// When the user can be disturbed again, if they cannot be at the timestamp. The users whose
+/// window could not be found are not held back.
+async fn dnd_ends_at(client: &dyn SlackClient, token: &str, user: &str, now: i64) -> Option<i64> {
+    match client.dnd_info(token, user).await {
+        Ok(dnd) => dnd.ends_at(now),
+        Err(err) => {
+            log::warn!(
+                "could not find the do not disturb window of user {}: {}",
+                user,
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Queues the pick to be sent once the do not disturb window of its participant ends, returning
+/// whether it was queued.
+async fn hold_direct_pick(
+    job_repo: Arc<dyn notification::Repository>,
+    pick: &DirectPick,
+    run_at: i64,
+) -> bool {
+    let payload = match serde_json::to_value(pick) {
+        Ok(payload) => payload,
+        Err(err) => {
+            log::error!("could not serialize the pick: {}", err);
+            return false;
+        }
+    };
+    let req = enqueue_notification::Request {
+        kind: NotificationKind::DirectPick,
+        team_id: pick.team_id.clone(),
+        payload,
+        run_at: Some(run_at),
+    };
+    match enqueue_notification::execute(job_repo, req).await {
+        Ok(..) => true,
+        Err(err) => {
+            log::error!(
+                "could not hold the pick of event {} for {}: {:?}",
+                pick.event_id,
+                pick.recipient_id,
+                err
+            );
+            false
+        }
+    }
+}
+
+/// Tells the owner of the event that its pick could not be posted, as the bot was removed from the
+/// channel, so that they either add it back or pause the event.
+async fn notify_channel_access(
+    client: &dyn SlackClient,
+    pick: &pick_auto_participants::Pick,
+    locale: Locale,
+) {
+    let Some(owner) = pick.created_by.clone() else {
+        log::warn!(
+            "could not post the pick of event {}: the bot is not on channel {}",
+            pick.event_id,
+            pick.channel_id
+        );
+        return;
+    };
+    let body = channel_access::view(channel_access::ChannelAccessView {
+        user_id: owner,
+        event_id: pick.event_id,
+        event_name: pick.event_name.clone(),
+        channel_id: pick.channel_id.clone(),
+        locale,
+    });
+    if let Err(err) = client.post_message(pick.access_token.expose(), body).await {
+        log::error!(
+            "could not tell the owner of event {} about its channel: {}",
+            pick.event_id,
+            err
+        );
+    }
+}
+
+/// Whether the bot can post on a channel.
+#[derive(Debug, PartialEq)]
+pub enum ChannelAccess {
+    Member,
+    /// The channel is private, so the bot has to be invited to it.
+    NeedsInvite,
+}
+
+/// Joins the channel of an event, so that its picks can be posted on it. The private channels
+/// cannot be joined, and are only found once the bot is invited to them.
+pub async fn join_channel(
+    client: &dyn SlackClient,
+    token: &str,
+    channel: &str,
+) -> Result<ChannelAccess, client::Error> {
+    let conversation = match client.conversations_info(token, channel).await {
+        Ok(conversation) => conversation,
+        Err(client::Error::Api(err)) if err == "channel_not_found" => {
+            return Ok(ChannelAccess::NeedsInvite)
+        }
+        Err(err) => return Err(err),
+    };
+    if conversation.is_member {
+        return Ok(ChannelAccess::Member);
+    }
+    if conversation.is_private {
+        return Ok(ChannelAccess::NeedsInvite);
+    }
+    client.conversations_join(token, channel).await?;
+    Ok(ChannelAccess::Member)
+}
+
+/// Posts and pins the anchor message of the event, returning its timestamp.
+///
+/// The picks are posted on the channel when the anchor message could not be posted.
+async fn post_anchor(
+    event_repo: Arc<dyn event::Repository>,
+    client: &dyn SlackClient,
+    pick: &pick_auto_participants::Pick,
+) -> Option<String> {
+    let body = anchor::view(anchor::AnchorView {
+        channel_id: pick.channel_id.clone(),
+        event_name: pick.event_name.clone(),
+    });
+    let ts = client
+        .post_message(pick.access_token.expose(), body)
+        .await
+        .map_err(|err| log::error!("unable to post the anchor message: {}", err))
+        .ok()?;
+    if let Err(err) = client
+        .pin_message(pick.access_token.expose(), &pick.channel_id, &ts)
+        .await
+    {
+        log::error!(
+            "unable to pin message {} on channel {}: {}",
+            ts,
+            pick.channel_id,
+            err
+        );
+    }
+
+    let req = set_event_anchor::Request {
+        id: pick.event_id,
+        anchor_ts: ts.clone(),
+    };
+    if let Err(err) = set_event_anchor::execute(event_repo, req).await {
+        log::error!(
+            "could not save the anchor message of event {}: {:?}",
+            pick.event_id,
+            err
+        );
+    }
+    Some(ts)
+}
+
+pub async fn post_reminder(
+    settings_repo: Arc<dyn settings::Repository>,
+    client: Arc<dyn SlackClient>,
+    data: find_event_reminders::Reminder,
+) -> Result<(), PostError> {
+    let body = reminder::view(reminder::ReminderView {
+        locale: find_settings(settings_repo.clone(), &data.team_id)
+            .await
+            .locale,
+        channel_id: data.channel_id,
+        event_name: data.event_name,
+        minutes: data.minutes,
+    });
+    client
+        .post_message(data.access_token.expose(), body)
+        .await
+        .map_err(|err| {
+            PostError::client(
+                format!("failed to post reminder of event {}", data.event_id),
+                err,
+            )
+        })?;
+    Ok(())
+}
+
+/// Posts the recap of the picks of the past month on its channel.
+pub async fn post_recap(
+    settings_repo: Arc<dyn settings::Repository>,
+    client: Arc<dyn SlackClient>,
+    data: find_monthly_recaps::Recap,
+) -> Result<(), PostError> {
+    let settings = find_settings(settings_repo.clone(), &data.team_id).await;
+    let body = recap::view(recap::RecapView {
+        locale: settings.locale,
+        date_format: settings.date_format,
+        channel_id: data.channel_id.clone(),
+        month: data.month,
+        picks: data.picks,
+        skips: data.skips,
+        leaderboard: data
+            .leaderboard
+            .into_iter()
+            .map(|leader| recap::LeaderView {
+                user: leader.user,
+                picks: leader.picks,
+            })
+            .collect(),
+        acknowledged: data.acknowledged,
+        unacknowledged: data.unacknowledged,
+    });
+    client
+        .post_message(data.access_token.expose(), body)
+        .await
+        .map_err(|err| {
+            PostError::client(
+                format!("failed to post the recap of channel {}", data.channel_id),
+                err,
+            )
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::domain::entities::{FailedNotification, NotificationJob, PendingAcknowledgment};
+    use crate::repository::errors::{
+        DeleteError, FindAllError, FindError, InsertError, UpdateError,
+    };
+    use crate::slack::client::{mock::MockSlackClient, DndStatus};
+
+    /// The settings of the teams that never set them.
+    struct MockSettingsRepository;
+
+    #[async_trait::async_trait]
+    impl settings::Repository for MockSettingsRepository {
+        async fn find_settings(&self, _team_id: String) -> Result<TeamSettings, FindError> {
+            Err(FindError::NotFound)
+        }
+
+        async fn find_all_settings_unprotected(&self) -> Result<Vec<TeamSettings>, FindAllError> {
+            Ok(vec![])
+        }
+
+        async fn save_settings(&self, settings: TeamSettings) -> Result<TeamSettings, UpdateError> {
+            Ok(settings)
+        }
+    }
+
+    /// The acknowledgments of the teams that never waited for one.
+    struct MockAcknowledgmentRepository;
+
+    #[async_trait::async_trait]
+    impl acknowledgment::Repository for MockAcknowledgmentRepository {
+        async fn save_acknowledgment(
+            &self,
+            acknowledgment: PendingAcknowledgment,
+        ) -> Result<PendingAcknowledgment, InsertError> {
+            Ok(acknowledgment)
+        }
+
+        async fn find_acknowledgment(
+            &self,
+            _event: u32,
+            _channel: String,
+        ) -> Result<PendingAcknowledgment, FindError> {
+            Err(FindError::NotFound)
+        }
+
+        async fn delete_acknowledgment(
+            &self,
+            _event: u32,
+            _channel: String,
+        ) -> Result<PendingAcknowledgment, DeleteError> {
+            Err(DeleteError::NotFound)
+        }
+
+        async fn find_expired_acknowledgments_unprotected(
+            &self,
+            _timestamp: i64,
+        ) -> Result<Vec<PendingAcknowledgment>, FindAllError> {
+            Ok(vec![])
+        }
+    }
+
+    /// The queue of the notifications, recording the queued jobs.
+    #[derive(Default)]
+    struct MockNotificationRepository {
+        jobs: Mutex<Vec<NotificationJob>>,
+    }
+
+    #[async_trait::async_trait]
+    impl notification::Repository for MockNotificationRepository {
+        async fn insert_job(&self, job: NotificationJob) -> Result<NotificationJob, InsertError> {
+            self.jobs.lock().unwrap().push(job.clone());
+            Ok(job)
+        }
+
+        async fn claim_job(
+            &self,
+            _timestamp: i64,
+            _locked_until: i64,
+        ) -> Result<Option<NotificationJob>, FindError> {
+            Ok(None)
+        }
+
+        async fn update_job(&self, job: NotificationJob) -> Result<NotificationJob, UpdateError> {
+            Ok(job)
+        }
+
+        async fn delete_job(&self, _id: u32) -> Result<(), DeleteError> {
+            Ok(())
+        }
+
+        async fn fail_job(
+            &self,
+            failed: FailedNotification,
+        ) -> Result<FailedNotification, InsertError> {
+            Ok(failed)
+        }
+
+        async fn find_failed(&self, _id: u32) -> Result<FailedNotification, FindError> {
+            Err(FindError::NotFound)
+        }
+
+        async fn find_all_failed(
+            &self,
+            _team_id: Option<String>,
+            _limit: i64,
+        ) -> Result<Vec<FailedNotification>, FindAllError> {
+            Ok(vec![])
+        }
+
+        async fn delete_failed(&self, _id: u32) -> Result<(), DeleteError> {
+            Err(DeleteError::NotFound)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_private_pick() {
+        let dnd_end = Date::now().timestamp() + 3600;
+        let client = MockSlackClient {
+            dnd: vec![(
+                String::from("U2"),
+                DndStatus {
+                    snooze_enabled: true,
+                    snooze_endtime: dnd_end,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let job_repo = Arc::new(MockNotificationRepository::default());
+        let participants = vec![String::from("U1"), String::from("U2")];
+
+        send_private_pick(
+            job_repo.clone(),
+            &client,
+            "token",
+            Locale::default(),
+            &participants,
+            |user| DirectPick {
+                team_id: String::from("T1"),
+                event_id: 1,
+                recipient_id: user,
+                channel_id: String::from("C1"),
+                event_name: String::from("Daily"),
+                user_picked_id: String::from("U2"),
+                partner_picked_id: None,
+                left_count: 1,
+                description: None,
+                url: None,
+                access_token: SecretString::default(),
+            },
+        )
+        .await;
+
+        // The participant who is not to be disturbed is sent the pick once their window ends.
+        let messages: Vec<_> = client
+            .calls()
+            .into_iter()
+            .filter(|(method, _)| method == "chat.postMessage")
+            .collect();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].1["channel"], "U1");
+        let jobs = job_repo.jobs.lock().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].kind, NotificationKind::DirectPick);
+        assert_eq!(jobs[0].run_at, dnd_end);
+        assert_eq!(jobs[0].payload["recipient_id"], "U2");
+    }
+
+    #[tokio::test]
+    async fn test_post_repick() {
+        let client = Arc::new(MockSlackClient::default());
+        let repick = repick_unacknowledged::Repick {
+            event_id: 1,
+            event_name: String::from("Daily"),
+            channel_id: String::from("C1"),
+            team_id: String::from("T1"),
+            user_id: String::from("U2"),
+            partner_id: None,
+            replaced: String::from("U1"),
+            left_count: 2,
+            message_ts: String::from("1686661200.000100"),
+            description: None,
+            url: None,
+            access_token: "token".into(),
+        };
+
+        post_repick(
+            Arc::new(MockSettingsRepository),
+            Arc::new(MockAcknowledgmentRepository),
+            client.clone(),
+            repick,
+        )
+        .await
+        .unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "chat.update");
+        assert_eq!(calls[0].1["channel"], "C1");
+        assert_eq!(calls[0].1["ts"], "1686661200.000100");
+    }
+
+    #[tokio::test]
+    async fn test_post_reminder() {
+        let client = Arc::new(MockSlackClient::default());
+        let reminder = find_event_reminders::Reminder {
+            event_id: 1,
+            event_name: String::from("Daily"),
+            channel_id: String::from("C1"),
+            team_id: String::from("T1"),
+            minutes: 15,
+            access_token: "token".into(),
+        };
+
+        post_reminder(Arc::new(MockSettingsRepository), client.clone(), reminder)
+            .await
+            .unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "chat.postMessage");
+        assert_eq!(calls[0].1["channel"], "C1");
+    }
+
+    #[tokio::test]
+    async fn test_announce() {
+        let client = MockSlackClient::default();
+        let channels = vec![String::from("C2"), String::from("C3")];
+
+        announce(
+            &client,
+            "token",
+            &channels,
+            "C1",
+            "Daily",
+            "U1",
+            None,
+            Locale::default(),
+        )
+        .await;
+
+        let calls = client.calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].1["channel"], "C2");
+        assert_eq!(calls[1].1["channel"], "C3");
+    }
+
+    #[tokio::test]
+    async fn test_join_channel() {
+        let channel = |id: &str, is_private, is_member| client::Conversation {
+            id: String::from(id),
+            name: String::from(id),
+            is_private,
+            is_member,
+        };
+        let client = MockSlackClient {
+            channels: vec![
+                channel("C1", false, false),
+                channel("C2", false, true),
+                channel("C3", true, false),
+            ],
+            ..Default::default()
+        };
+
+        let access = join_channel(&client, "token", "C1").await.unwrap();
+        assert_eq!(access, ChannelAccess::Member);
+        assert_eq!(client.calls().last().unwrap().0, "conversations.join");
+
+        let access = join_channel(&client, "token", "C2").await.unwrap();
+        assert_eq!(access, ChannelAccess::Member);
+        let access = join_channel(&client, "token", "C3").await.unwrap();
+        assert_eq!(access, ChannelAccess::NeedsInvite);
+        // The private channels are not found until the bot is invited to them.
+        let access = join_channel(&client, "token", "C4").await.unwrap();
+        assert_eq!(access, ChannelAccess::NeedsInvite);
+        assert_eq!(client.calls().len(), 5);
     }
 }
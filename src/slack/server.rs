@@ -1,37 +1,220 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
-use axum::extract::MatchedPath;
-use axum::{middleware, Extension, Router, Server};
-use hyper::{Body, Request, Result};
+use axum::extract::{MatchedPath, State};
+use axum::{middleware, Extension, Json, Router, Server};
+use hyper::{Body, Request, Result, StatusCode};
+use serde_json::{json, Value};
 use tokio::{join, sync::mpsc, task};
 use tower_http::trace::{DefaultOnResponse, TraceLayer};
 use tower_http::LatencyUnit;
 
 use crate::{
     config::Config,
-    domain::events::{find_all_events_and_dates, pick_auto_participants},
-    repository,
-    scheduler::{entities::EventSchedule, Scheduler},
-    slack::{sender, state::AppConfigs},
+    domain::{
+        acknowledgments::repick_unacknowledged,
+        entities::NotificationKind,
+        events::{find_event_reminders, pick_auto_participants},
+        history::find_monthly_recaps,
+    },
+    helpers::{date::Date, reporter},
+    repository::{
+        self,
+        redis::RedisStore,
+        store::{MemoryStore, Store},
+    },
+    scheduler::{entities::SchedulerConfigs, Scheduler},
+    slack::{
+        cached_client::CachedSlackClient,
+        client::{HttpSlackClient, SlackClient},
+        google::GoogleCalendarRepository,
+        helpers,
+        notifier::{self, Notifier, NotifierConfigs},
+        state::{AppConfigs, GoogleConfigs, SlackApp, StripeConfigs},
+        throttle::ThrottledSlackClient,
+        user_groups::SlackUserGroupRepository,
+        user_statuses::SlackUserStatusRepository,
+    },
 };
 
+/// Checks the connection to the databases and the API token keys, returning a message for each
+/// failed check.
+pub async fn check(config: &Config) -> std::result::Result<(), Vec<String>> {
+    let mut errors = vec![];
+
+    if let Err(err) = repository::event::MongoDbRepository::new(
+        &config.database_tool_url,
+        &config.database_tool_name,
+        1,
+    )
+    .await
+    {
+        errors.push(format!(
+            "could not connect to the tool database (DATABASE_TOOL_URL): {}",
+            err
+        ));
+    }
+    if let Err(err) = repository::auth::MongoDbRepository::new(
+        &config.database_auth_url,
+        &config.database_auth_name,
+        1,
+    )
+    .await
+    {
+        errors.push(format!(
+            "could not connect to the auth database (DATABASE_AUTH_URL): {}",
+            err
+        ));
+    }
+    if let Some(url) = &config.redis_url {
+        if let Err(err) = RedisStore::new(url).await {
+            errors.push(format!("could not connect to redis (REDIS_URL): {:?}", err));
+        }
+    }
+    if let Err(err) = super::jwt::Keys::from_config(config) {
+        errors.push(format!("could not load the jwt keys: {}", err));
+    }
+    if let (Some(cert_file), Some(key_file)) = (&config.tls_cert_file, &config.tls_key_file) {
+        if let Err(err) = super::tls::load_config(cert_file, key_file) {
+            errors.push(format!("could not load the tls certificate: {}", err));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub async fn serve(config: Config) -> Result<()> {
+    // The API tokens cannot be signed without their own keys, so the server does not start.
+    let jwt_keys = super::jwt::Keys::from_config(&config).expect("could not load jwt keys");
+
     let app = Router::new()
         .route(
             "/api/commands",
             axum::routing::post(super::commands::execute),
         )
         .route("/api/actions", axum::routing::post(super::actions::execute))
+        .route("/api/options", axum::routing::post(super::options::execute))
         .route_layer(middleware::from_fn(super::guard::validate))
-        .route("/api/oauth", axum::routing::get(super::oauth::execute))
+        .merge(
+            Router::new()
+                .route("/api/events", axum::routing::post(super::events::execute))
+                .route_layer(middleware::from_fn(super::guard::validate_signature)),
+        )
+        .route(
+            "/install",
+            axum::routing::get(super::oauth::install)
+                .layer(middleware::from_fn(super::limiter::limit_by_ip)),
+        )
+        .route(
+            "/api/oauth",
+            axum::routing::get(super::oauth::execute)
+                .layer(middleware::from_fn(super::limiter::limit_by_ip)),
+        )
+        .route(
+            super::google::CALLBACK_PATH,
+            axum::routing::get(super::google::callback)
+                .layer(middleware::from_fn(super::limiter::limit_by_ip)),
+        )
+        .route(
+            "/api/auth/refresh",
+            axum::routing::post(super::api::refresh),
+        )
+        .route("/api/auth/logout", axum::routing::post(super::api::logout))
+        .route(
+            "/api/auth/public-key",
+            axum::routing::get(super::api::public_key),
+        )
+        .route(
+            "/api/v1/events",
+            axum::routing::get(super::api::search_events),
+        )
+        .route(
+            "/api/v1/events/:id",
+            axum::routing::delete(super::api::delete_event),
+        )
+        .route(
+            "/api/v1/events/:id/pick",
+            axum::routing::post(super::api::pick_event),
+        )
+        .route(
+            "/api/v1/events/:id/history.csv",
+            axum::routing::get(super::api::pick_history_csv),
+        )
+        .route("/api/v1/audit", axum::routing::get(super::api::audit))
+        .route("/api/v1/channels", axum::routing::get(super::api::channels))
+        .route(
+            "/api/v1/keys",
+            axum::routing::get(super::api::api_keys).post(super::api::create_api_key),
+        )
+        .route(
+            "/api/v1/keys/:id",
+            axum::routing::delete(super::api::revoke_api_key),
+        )
+        .route(
+            "/api/v1/graphql",
+            axum::routing::post(super::graphql::execute),
+        )
+        .route(
+            "/api/v1/admin/failed-notifications",
+            axum::routing::get(super::admin::failed_notifications),
+        )
+        .route(
+            "/api/v1/admin/failed-notifications/:id/redrive",
+            axum::routing::post(super::admin::redrive_notification),
+        )
+        .route(
+            "/api/v1/admin/teams/:id/limits",
+            axum::routing::get(super::admin::team_limits).put(super::admin::set_team_limits),
+        )
+        .route(
+            "/api/v1/admin/scheduler",
+            axum::routing::get(super::admin::scheduler),
+        )
+        .route(
+            "/api/billing/webhook",
+            axum::routing::post(super::billing::webhook),
+        )
+        .route(
+            "/api/v1/channels/:id/events.ics",
+            axum::routing::get(super::calendar::execute),
+        )
+        .route("/app", axum::routing::get(super::dashboard::index))
+        .route("/app/", axum::routing::get(super::dashboard::index))
+        .route("/app/*file", axum::routing::get(super::dashboard::asset))
         .route("/health", axum::routing::get(health))
+        .route("/health/live", axum::routing::get(live))
+        .route("/health/ready", axum::routing::get(health));
+
+    // The development mode replaces Slack with a fake backend, simulating its commands.
+    let fake_slack = config
+        .dev
+        .then(|| Arc::new(super::dev::FakeSlack::new(config.port)));
+    let app = match &fake_slack {
+        Some(fake_slack) => app.merge(
+            Router::new()
+                .route("/dev/commands", axum::routing::post(super::dev::command))
+                .route("/dev/responses", axum::routing::post(super::dev::responses))
+                .route("/dev/messages", axum::routing::get(super::dev::messages))
+                .layer(Extension(fake_slack.clone())),
+        ),
+        None => app,
+    };
+
+    let app = app
         .layer(
             TraceLayer::new_for_http()
                 // Create our own span for the request and include the matched path. The matched
                 // path is useful for figuring out which handler the request was routed to.
                 .make_span_with(|req: &Request<Body>| {
                     let method = req.method();
-                    let uri = req.uri();
+                    // The query carries the tokens of the calendar feeds and the OAuth codes, so
+                    // only its names are recorded.
+                    let path = req.uri().path();
+                    let query = req.uri().query().map(redact_query);
+                    let request_id = super::correlation::find_request_id(req);
 
                     // axum automatically adds this extension.
                     let matched_path = req
@@ -39,12 +222,24 @@ pub async fn serve(config: Config) -> Result<()> {
                         .get::<MatchedPath>()
                         .map(|matched_path| matched_path.as_str());
 
-                    tracing::debug_span!("request", %method, %uri, matched_path)
+                    // The team and channel are recorded by the handlers once the payload is
+                    // parsed.
+                    tracing::info_span!(
+                        "request",
+                        request_id,
+                        %method,
+                        path,
+                        query,
+                        matched_path,
+                        team_id = tracing::field::Empty,
+                        channel = tracing::field::Empty,
+                    )
                 })
                 // By default `TraceLayer` will log 5xx responses but we're doing our specific
                 // logging of errors so disable that
                 .on_failure(()),
-        );
+        )
+        .layer(middleware::from_fn(super::correlation::assign));
 
     log::info!(
         "Connecting to database {}/{}",
@@ -52,15 +247,31 @@ pub async fn serve(config: Config) -> Result<()> {
         config.database_tool_name
     );
 
-    let event_repo = Arc::new(
-        repository::event::MongoDbRepository::new(
-            &config.database_tool_url,
-            &config.database_tool_name,
-            50,
-        )
-        .await
-        .expect("could not connect to tool database"),
-    );
+    let store: Arc<dyn Store> = match &config.redis_url {
+        Some(url) => {
+            log::info!("Connecting to redis");
+            Arc::new(
+                RedisStore::new(url)
+                    .await
+                    .expect("could not connect to redis"),
+            )
+        }
+        None => Arc::new(MemoryStore::default()),
+    };
+
+    let event_repo = Arc::new(repository::cache::CachedEventRepository::new(
+        Arc::new(
+            repository::event::MongoDbRepository::new(
+                &config.database_tool_url,
+                &config.database_tool_name,
+                50,
+            )
+            .await
+            .expect("could not connect to tool database"),
+        ),
+        store.clone(),
+        Duration::from_secs(config.event_cache_seconds),
+    ));
 
     log::info!(
         "Connecting to database {}/{}",
@@ -77,84 +288,519 @@ pub async fn serve(config: Config) -> Result<()> {
         .await
         .expect("could not connect to auth database"),
     );
+
+    let history_repo = Arc::new(
+        repository::pick_history::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let holiday_repo = Arc::new(
+        repository::holiday::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let scheduler_repo = Arc::new(
+        repository::scheduler::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let settings_repo = Arc::new(
+        repository::settings::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let plan_repo = Arc::new(
+        repository::plan::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let audit_repo = Arc::new(
+        repository::audit::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let acknowledgment_repo = Arc::new(
+        repository::acknowledgment::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let api_key_repo = Arc::new(
+        repository::api_key::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let notification_repo = Arc::new(
+        repository::notification::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let integration_repo = Arc::new(
+        repository::integration::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    let calendar_feed_repo = Arc::new(
+        repository::calendar_feed::MongoDbRepository::new(
+            &config.database_tool_url,
+            &config.database_tool_name,
+            50,
+        )
+        .await
+        .expect("could not connect to tool database"),
+    );
+
+    if let Some(url) = config.error_report_url.clone() {
+        reporter::set_report_url(url);
+    }
+    reporter::set_panic_hook();
+
+    helpers::set_retry_policy(helpers::RetryPolicy {
+        attempts: config.http_retry_attempts,
+        base_delay_ms: config.http_retry_base_delay_ms,
+    });
+
+    let slack_client: Arc<dyn SlackClient> = match fake_slack {
+        Some(fake_slack) => {
+            super::dev::install(auth_repo.clone()).await;
+            fake_slack
+        }
+        None => Arc::new(HttpSlackClient),
+    };
+    let slack_client: Arc<dyn SlackClient> = Arc::new(CachedSlackClient::new(
+        Arc::new(ThrottledSlackClient::new(
+            slack_client,
+            config.slack_requests_per_minute,
+        )),
+        store.clone(),
+        Duration::from_secs(config.user_cache_seconds),
+    ));
+    let user_group_repo = Arc::new(SlackUserGroupRepository::new(slack_client.clone()));
+    let user_status_repo = Arc::new(SlackUserStatusRepository::new(slack_client.clone()));
+    let google = match (&config.google_client_id, &config.google_client_secret) {
+        (Some(client_id), Some(client_secret)) => Some(GoogleConfigs {
+            client_id: client_id.clone(),
+            client_secret: client_secret.clone().into(),
+        }),
+        _ => None,
+    };
+    let calendar_repo = Arc::new(GoogleCalendarRepository::new(
+        integration_repo.clone(),
+        google.clone(),
+    ));
+
     let (tx, mut rx) = mpsc::channel::<Vec<pick_auto_participants::Pick>>(1);
-    let scheduler = Arc::new(Scheduler::new(tx));
+    let (reminder_tx, mut reminder_rx) = mpsc::channel::<Vec<find_event_reminders::Reminder>>(1);
+    let (repick_tx, mut repick_rx) = mpsc::channel::<Vec<repick_unacknowledged::Repick>>(1);
+    let (recap_tx, mut recap_rx) = mpsc::channel::<Vec<find_monthly_recaps::Recap>>(1);
+    let scheduler = Arc::new(Scheduler::new(
+        tx,
+        reminder_tx,
+        repick_tx,
+        recap_tx,
+        scheduler_repo.clone(),
+    ));
 
     // Initialize server thread.
     let app_scheduler = scheduler.clone();
     let app_event_repo = event_repo.clone();
     let app_auth_repo = auth_repo.clone();
+    let app_history_repo = history_repo.clone();
+    let app_holiday_repo = holiday_repo.clone();
+    let app_scheduler_repo = scheduler_repo.clone();
+    let app_settings_repo = settings_repo.clone();
+    let app_plan_repo = plan_repo.clone();
+    let app_audit_repo = audit_repo.clone();
+    let app_acknowledgment_repo = acknowledgment_repo.clone();
+    let app_api_key_repo = api_key_repo.clone();
+    let app_notification_repo = notification_repo.clone();
+    let app_integration_repo = integration_repo.clone();
+    let app_calendar_feed_repo = calendar_feed_repo.clone();
+    let app_slack_client = slack_client.clone();
+    let app_store = store.clone();
+    let tls_config = match (&config.tls_cert_file, &config.tls_key_file) {
+        (Some(cert_file), Some(key_file)) => Some(
+            super::tls::load_config(cert_file, key_file).expect("could not load tls certificate"),
+        ),
+        _ => None,
+    };
     let app_config = config.clone();
+    let other_apps = config
+        .other_apps()
+        .expect("could not load other apps")
+        .into_iter()
+        .map(|app| {
+            let app = SlackApp {
+                app_id: app.app_id,
+                client_id: app.client_id,
+                client_secret: app.client_secret.into(),
+                secret: app.signature.into(),
+                secondary_secret: app.secondary_signature.map(Into::into),
+            };
+            (app.app_id.clone(), app)
+        })
+        .collect();
+    let scheduler_configs = SchedulerConfigs {
+        catch_up_minutes: config.catch_up_minutes,
+        lease_seconds: config.scheduler_lease_seconds,
+        deleted_events_retention_days: config.deleted_events_retention_days,
+    };
+    let notifier_configs = NotifierConfigs {
+        workers: config.notification_workers,
+        max_attempts: config.notification_max_attempts,
+        base_delay_seconds: config.notification_retry_base_seconds,
+    };
     let server_task = task::spawn(async move {
-        log::info!("Listening on port {}", config.port);
+        log::info!(
+            "Listening on port {}{}",
+            config.port,
+            if tls_config.is_some() { " (https)" } else { "" }
+        );
 
         let state = Arc::new(super::AppState {
             configs: Arc::new(AppConfigs {
-                app_id: app_config.app_id,
-                secret: app_config.signature,
-                client_id: app_config.client_id,
-                client_secret: app_config.client_secret,
+                app: SlackApp {
+                    app_id: app_config.app_id,
+                    client_id: app_config.client_id,
+                    client_secret: app_config.client_secret.into(),
+                    secret: app_config.signature.into(),
+                    secondary_secret: app_config.secondary_signature.map(Into::into),
+                },
+                other_apps,
                 max_events: app_config.max_events,
+                max_participants: app_config.max_participants,
+                public_url: app_config.public_url,
+                jwt_keys,
+                stripe: match (
+                    app_config.stripe_secret_key,
+                    app_config.stripe_webhook_secret,
+                    app_config.stripe_price_id,
+                ) {
+                    (Some(secret_key), Some(webhook_secret), Some(price_id)) => {
+                        Some(StripeConfigs {
+                            secret_key: secret_key.into(),
+                            webhook_secret: webhook_secret.into(),
+                            price_id,
+                        })
+                    }
+                    _ => None,
+                },
+                google,
             }),
             event_repo: app_event_repo,
             auth_repo: app_auth_repo,
+            history_repo: app_history_repo,
+            holiday_repo: app_holiday_repo,
+            scheduler_repo: app_scheduler_repo,
+            settings_repo: app_settings_repo,
+            plan_repo: app_plan_repo,
+            audit_repo: app_audit_repo,
+            acknowledgment_repo: app_acknowledgment_repo,
+            api_key_repo: app_api_key_repo,
+            integration_repo: app_integration_repo,
+            calendar_feed_repo: app_calendar_feed_repo,
+            notification_repo: app_notification_repo,
             scheduler: app_scheduler,
+            slack_client: app_slack_client,
+            rate_limiter: Arc::new(
+                super::limiter::RateLimiter::new(
+                    app_config.rate_limit_requests,
+                    app_config.rate_limit_window_seconds,
+                    app_store.clone(),
+                )
+                .with_trusted_proxy_hops(app_config.trusted_proxy_hops),
+            ),
+            store: app_store,
         });
 
-        if let Err(err) = Server::bind(&format!("0.0.0.0:{}", app_config.port).parse().unwrap())
-            .serve(
-                app.layer(Extension(state.clone()))
-                    .with_state(state)
-                    .into_make_service(),
-            )
-            .await
-        {
+        let addr: SocketAddr = format!("0.0.0.0:{}", app_config.port).parse().unwrap();
+        let service = app
+            .layer(Extension(state.clone()))
+            .with_state(state)
+            .into_make_service_with_connect_info::<SocketAddr>();
+        let result = match tls_config {
+            Some(tls_config) => match super::tls::bind(addr, tls_config).await {
+                Ok(incoming) => Server::builder(incoming).serve(service).await,
+                Err(err) => {
+                    log::error!("error binding tls listener: {}", err);
+                    return;
+                }
+            },
+            None => Server::bind(&addr).serve(service).await,
+        };
+        if let Err(err) = result {
             log::error!("error initializing server: {}", err);
         }
     });
 
-    // Initialize scheduler thread.
-    let app_scheduler = scheduler.clone();
-    let app_event_repo = event_repo.clone();
-    let scheduler_task = task::spawn(async move {
-        log::info!("Scheduler is running");
-        app_scheduler.start(app_event_repo, auth_repo).await;
-    });
-
-    // Initialize auto-picker listener thread.
+    // Initialize auto-picker listener thread, queueing the picks to be posted.
+    let picker_notification_repo = notification_repo.clone();
     let auto_picker_task = task::spawn(async move {
         while let Some(picks) = rx.recv().await {
-            sender::post_picks(picks).await;
+            notifier::queue(
+                picker_notification_repo.clone(),
+                NotificationKind::Pick,
+                picks,
+                |pick| pick.team_id.clone(),
+            )
+            .await;
         }
     });
 
-    log::info!("Fetching events to fill up scheduler");
-    match find_all_events_and_dates::execute(event_repo).await {
-        Ok(events) => {
-            for event in events.data.into_iter() {
-                scheduler
-                    .insert(EventSchedule {
-                        id: event.id,
-                        timestamp: event.timestamp,
-                        timezone: event.timezone,
-                        repeat: event.repeat,
-                    })
-                    .await;
-            }
+    // Initialize reminder listener thread, queueing the reminders to be posted.
+    let reminder_notification_repo = notification_repo.clone();
+    let reminder_task = task::spawn(async move {
+        while let Some(reminders) = reminder_rx.recv().await {
+            notifier::queue(
+                reminder_notification_repo.clone(),
+                NotificationKind::Reminder,
+                reminders,
+                |reminder| reminder.team_id.clone(),
+            )
+            .await;
         }
-        Err(err) => {
-            log::error!("could no fetch events for scheduling: {:?}", err);
+    });
+
+    // Initialize unacknowledged picks listener thread, queueing the repicks to be posted.
+    let repicker_notification_repo = notification_repo.clone();
+    let repicker_task = task::spawn(async move {
+        while let Some(repicks) = repick_rx.recv().await {
+            notifier::queue(
+                repicker_notification_repo.clone(),
+                NotificationKind::Repick,
+                repicks,
+                |repick| repick.team_id.clone(),
+            )
+            .await;
         }
-    };
+    });
+
+    // Initialize monthly recaps listener thread, queueing the recaps to be posted.
+    let recap_notification_repo = notification_repo.clone();
+    let recap_task = task::spawn(async move {
+        while let Some(recaps) = recap_rx.recv().await {
+            notifier::queue(
+                recap_notification_repo.clone(),
+                NotificationKind::Recap,
+                recaps,
+                |recap| recap.team_id.clone(),
+            )
+            .await;
+        }
+    });
+
+    // Initialize the workers posting the queued notifications.
+    let notifier = Arc::new(Notifier::new(
+        notification_repo,
+        event_repo.clone(),
+        auth_repo.clone(),
+        settings_repo.clone(),
+        acknowledgment_repo.clone(),
+        slack_client,
+        notifier_configs,
+    ));
+    let notifier_task = task::spawn(notifier.run());
+
+    log::info!("Fetching events, holidays and settings to fill up scheduler");
+    scheduler
+        .sync(
+            event_repo.clone(),
+            auth_repo.clone(),
+            holiday_repo.clone(),
+            settings_repo.clone(),
+        )
+        .await;
 
-    let (server_result, scheduler_result, auto_picker_result) =
-        join!(server_task, scheduler_task, auto_picker_task);
+    // Initialize scheduler thread.
+    let app_scheduler = scheduler.clone();
+    let scheduler_task = task::spawn(async move {
+        log::info!("Scheduler is running");
+        app_scheduler
+            .start(
+                event_repo,
+                auth_repo,
+                history_repo,
+                holiday_repo,
+                settings_repo,
+                acknowledgment_repo,
+                user_group_repo,
+                user_status_repo,
+                calendar_repo,
+                audit_repo,
+                scheduler_configs,
+            )
+            .await;
+    });
+
+    let (
+        server_result,
+        scheduler_result,
+        auto_picker_result,
+        reminder_result,
+        repicker_result,
+        recap_result,
+        notifier_result,
+    ) = join!(
+        server_task,
+        scheduler_task,
+        auto_picker_task,
+        reminder_task,
+        repicker_task,
+        recap_task,
+        notifier_task
+    );
 
     scheduler_result.expect("failed running scheduler");
     auto_picker_result.expect("failed running auto-picker");
+    reminder_result.expect("failed running reminder");
+    repicker_result.expect("failed running repicker");
+    recap_result.expect("failed running recapper");
+    notifier_result.expect("failed running notifier");
     Ok(server_result.expect("failed running server"))
 }
 
-async fn health() -> String {
-    String::from("OK")
+/// Responds while the server is running, regardless of its dependencies.
+async fn live() -> Json<Value> {
+    Json(json!({ "status": "OK" }))
+}
+
+/// Checks the databases and the scheduler, responding with 503 when any of them is down. The
+/// instance is not ready until the scheduler is filled up with the saved events, so that the
+/// requests are not routed to an instance that would miss their picks.
+async fn health(State(state): State<Arc<super::AppState>>) -> (StatusCode, Json<Value>) {
+    if !state.scheduler.is_synced() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "STARTING" })),
+        );
+    }
+
+    let tool_database = ping(state.event_repo.ping()).await;
+    let auth_database = ping(state.auth_repo.ping()).await;
+
+    let last_tick = state.scheduler.last_tick();
+    let scheduler_running = Date::now().timestamp() - last_tick <= HEALTH_MAX_TICK_DELAY;
+    let scheduler = json!({
+        "running": scheduler_running,
+        "leader": state.scheduler.is_leader(),
+        "last_tick": last_tick,
+        "picks_backlog": state.scheduler.picks_backlog(),
+    });
+    let healthy = tool_database && auth_database && scheduler_running;
+    let status = |ok: bool| if ok { "OK" } else { "DOWN" };
+    (
+        match healthy {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        },
+        Json(json!({
+            "status": status(healthy),
+            "databases": {
+                "tool": status(tool_database),
+                "auth": status(auth_database),
+            },
+            "scheduler": scheduler,
+        })),
+    )
+}
+
+/// Whether the database answered the ping in time.
+async fn ping<F, E>(ping: F) -> bool
+where
+    F: std::future::Future<Output = std::result::Result<(), E>>,
+    E: std::fmt::Debug,
+{
+    match tokio::time::timeout(HEALTH_TIMEOUT, ping).await {
+        Ok(Ok(())) => true,
+        Ok(Err(err)) => {
+            log::error!("health check could not ping the database: {:?}", err);
+            false
+        }
+        Err(..) => {
+            log::error!("health check timed out pinging the database");
+            false
+        }
+    }
+}
+
+/// Redacts the values of the query of a request, keeping the names of its parameters.
+fn redact_query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((name, _)) => format!("{}=[redacted]", name),
+            None => param.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+/// The time the databases have to answer the health check in.
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The seconds since the latest scheduler run after which it is considered stuck. The scheduler
+/// runs every minute.
+const HEALTH_MAX_TICK_DELAY: i64 = 3 * 60;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_query() {
+        assert_eq!(redact_query("token=abc"), "token=[redacted]");
+        assert_eq!(
+            redact_query("code=abc&state=def&debug"),
+            "code=[redacted]&state=[redacted]&debug"
+        );
+    }
 }
@@ -1,19 +1,80 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use crate::{repository, scheduler::Scheduler};
+use crate::{
+    helpers::secret::SecretString,
+    repository::{self, store::Store},
+    scheduler::Scheduler,
+};
+
+use super::{client::SlackClient, jwt, limiter::RateLimiter};
 
 #[derive(Clone)]
 pub struct AppState {
     pub event_repo: Arc<dyn repository::event::Repository>,
     pub auth_repo: Arc<dyn repository::auth::Repository>,
+    pub history_repo: Arc<dyn repository::pick_history::Repository>,
+    pub holiday_repo: Arc<dyn repository::holiday::Repository>,
+    pub scheduler_repo: Arc<dyn repository::scheduler::Repository>,
+    pub settings_repo: Arc<dyn repository::settings::Repository>,
+    pub plan_repo: Arc<dyn repository::plan::Repository>,
+    pub audit_repo: Arc<dyn repository::audit::Repository>,
+    pub acknowledgment_repo: Arc<dyn repository::acknowledgment::Repository>,
+    pub api_key_repo: Arc<dyn repository::api_key::Repository>,
+    pub notification_repo: Arc<dyn repository::notification::Repository>,
+    pub integration_repo: Arc<dyn repository::integration::Repository>,
+    pub calendar_feed_repo: Arc<dyn repository::calendar_feed::Repository>,
     pub scheduler: Arc<Scheduler>,
+    pub slack_client: Arc<dyn SlackClient>,
+    pub store: Arc<dyn Store>,
+    pub rate_limiter: Arc<RateLimiter>,
     pub configs: Arc<AppConfigs>,
 }
 
 pub struct AppConfigs {
+    /// The app the requests are served for unless sent by another one. Its secret also signs the
+    /// installation states.
+    pub app: SlackApp,
+    /// The other apps served by the deployment (e.g. a staging app), by their ID.
+    pub other_apps: HashMap<String, SlackApp>,
+    pub max_events: u32,
+    /// The maximum number of participants of each event, on the pro plan.
+    pub max_participants: u32,
+    pub public_url: Option<String>,
+    pub jwt_keys: jwt::Keys,
+    /// The Stripe account the upgrades are paid to, when they are available.
+    pub stripe: Option<StripeConfigs>,
+    /// The Google app the users connect their calendars with, when they can.
+    pub google: Option<GoogleConfigs>,
+}
+
+impl AppConfigs {
+    /// Finds the app of the ID, falling back to the main one when unknown or not given.
+    pub fn find_app(&self, app_id: Option<&str>) -> &SlackApp {
+        app_id
+            .and_then(|app_id| self.other_apps.get(app_id))
+            .unwrap_or(&self.app)
+    }
+}
+
+/// The credentials of a Slack app.
+pub struct SlackApp {
     pub app_id: String,
-    pub secret: String,
     pub client_id: String,
-    pub client_secret: String,
-    pub max_events: u32,
+    pub client_secret: SecretString,
+    /// The secret the requests of the app are signed with.
+    pub secret: SecretString,
+    /// The previous signing secret, still accepted on the requests while the secret is rotated.
+    pub secondary_secret: Option<SecretString>,
+}
+
+pub struct StripeConfigs {
+    pub secret_key: SecretString,
+    pub webhook_secret: SecretString,
+    pub price_id: String,
+}
+
+#[derive(Clone)]
+pub struct GoogleConfigs {
+    pub client_id: String,
+    pub client_secret: SecretString,
 }
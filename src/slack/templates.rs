@@ -5,20 +5,33 @@ use serde_json::{json, Value};
 
 use crate::{
     domain::{
+        date_format::DateFormat,
+        entities::{PickMode, RepeatPeriod, Visibility},
         events::{find_all_events, find_event},
+        locale::Locale,
         timezone::Timezone,
     },
+    helpers::date::Date,
+    i18n,
     repository::event::Repository,
     slack::helpers,
+    views::{list_events::repeat_label, reminder::minutes_label},
 };
 
-pub fn add_event() -> Result<String, Error> {
+pub fn add_event(timezone: Timezone, locale: Locale) -> Result<String, Error> {
     let template = read_file(ADD_EVENT_HBS)?;
-    let result = super::render_template(&template, json!({ "timezones": Timezone::options() }))
-        .map_err(|err| {
-            log::error!("could not render template {}: {}", ADD_EVENT_HBS, err);
-            Error::ReadFile
-        })?;
+    let result = super::render_template(
+        &template,
+        json!({
+            "t": i18n::catalog(locale),
+            "timezone": timezone.option(),
+            "timezones": Timezone::options()
+        }),
+    )
+    .map_err(|err| {
+        log::error!("could not render template {}: {}", ADD_EVENT_HBS, err);
+        Error::ReadFile
+    })?;
 
     Ok(result)
 }
@@ -27,26 +40,75 @@ pub async fn add_event_success(
     repo: Arc<dyn Repository>,
     channel: String,
     id: u32,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<String, Error> {
-    event_action_success(repo, channel, id, ADD_EVENT_SUCCESS_HBS).await
+    event_action_success(
+        repo,
+        channel,
+        id,
+        date_format,
+        locale,
+        ADD_EVENT_SUCCESS_HBS,
+    )
+    .await
 }
 
 pub async fn edit_event(
     repo: Arc<dyn Repository>,
     channel: String,
     id: u32,
+    locale: Locale,
 ) -> Result<String, Error> {
     let event = find_event::execute(repo, find_event::Request { id, channel }).await?;
+    let repeat = match &event.repeat {
+        RepeatPeriod::Days(..) => String::from("days"),
+        RepeatPeriod::Cron(..) => String::from("cron"),
+        repeat => repeat.clone().try_into().unwrap_or(String::from("")),
+    };
+    let pick_mode = String::from(event.pick_mode.clone());
 
     let template = read_file(EDIT_EVENT_HBS)?;
     let result = super::render_template(
         &template,
         json!({
+            "t": i18n::catalog(locale),
             "id": event.id,
             "name": event.name,
             "date": event.timestamp,
-            "repeat": event.repeat.clone().try_into().unwrap_or(String::from("")),
-            "repeat_label": event.repeat.label(),
+            "repeat_label": i18n::t(locale, &format!("repeat_{}", repeat)),
+            "repeat": repeat,
+            "days": match &event.repeat {
+                RepeatPeriod::Days(weekdays) => weekdays
+                    .iter()
+                    .map(|weekday| {
+                        let value = weekday.to_string().to_lowercase();
+                        json!({
+                            "label": i18n::t(locale, &format!("weekday_{}", value)),
+                            "value": value,
+                        })
+                    })
+                    .collect::<Vec<Value>>(),
+                _ => vec![],
+            },
+            "cron": match &event.repeat {
+                RepeatPeriod::Cron(expression) => Some(expression.clone()),
+                _ => None,
+            },
+            "pick_mode_label": i18n::t(locale, &format!("pick_mode_{}", pick_mode)),
+            "pick_mode": pick_mode,
+            "no_repeat_consecutive": event.no_repeat_consecutive,
+            "ends_at": event.ends_at,
+            "reminder_minutes": event.reminder_minutes,
+            "seed": event.seed.map(|seed| seed.to_string()),
+            "description": event.description.as_deref().map(escape_json),
+            "url": event.url.as_deref().map(escape_json),
+            "anchor": event.anchor,
+            "private": event.visibility == Visibility::Direct,
+            "skip_out_of_office": event.skip_out_of_office,
+            "skip_weekends": event.skip_weekends,
+            "notify_channels": event.notify_channels,
+            "part_timers": event.participants.iter().filter(|p| p.is_part_time()).map(|p| p.user.clone()).collect::<Vec<String>>(),
             "participants": event.participants.into_iter().map(|p| p.user).collect::<Vec<String>>(),
             "timezone": event.timezone.clone().option(),
             "timezones": Timezone::options()
@@ -64,29 +126,43 @@ pub async fn edit_event_success(
     repo: Arc<dyn Repository>,
     channel: String,
     id: u32,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<String, Error> {
-    event_action_success(repo, channel, id, EDIT_EVENT_SUCCESS_HBS).await
+    event_action_success(
+        repo,
+        channel,
+        id,
+        date_format,
+        locale,
+        EDIT_EVENT_SUCCESS_HBS,
+    )
+    .await
 }
 
 pub async fn edit_select_event(
     repo: Arc<dyn Repository>,
     channel: String,
+    locale: Locale,
 ) -> Result<String, Error> {
-    select_event(repo, channel, EDIT_SELECT_EVENT_HBS).await
+    select_event(repo, channel, locale, EDIT_SELECT_EVENT_HBS).await
 }
 
 pub async fn delete_event(
     repo: Arc<dyn Repository>,
     channel: String,
     id: u32,
+    locale: Locale,
 ) -> Result<String, Error> {
     let event = find_event::execute(repo, find_event::Request { id, channel }).await?;
+    let message = i18n::format(locale, "delete_event_confirm", &[("name", &event.name)]);
 
     let template = read_file(DELETE_EVENT_HBS)?;
     let result = super::render_template(
         &template,
         json!({
-            "name": event.name,
+            "t": i18n::catalog(locale),
+            "message": escape_json(&message),
             "id": event.id
         }),
     )
@@ -98,9 +174,9 @@ pub async fn delete_event(
     Ok(result)
 }
 
-pub async fn delete_event_success() -> Result<String, Error> {
+pub async fn delete_event_success(locale: Locale) -> Result<String, Error> {
     Ok(json!({
-        "text": "Event deleted with success! 👍"
+        "text": i18n::t(locale, "delete_event_success")
     })
     .to_string())
 }
@@ -108,26 +184,69 @@ pub async fn delete_event_success() -> Result<String, Error> {
 pub async fn delete_select_event(
     repo: Arc<dyn Repository>,
     channel: String,
+    locale: Locale,
 ) -> Result<String, Error> {
-    select_event(repo, channel, DELETE_SELECT_EVENT_HBS).await
+    select_event(repo, channel, locale, DELETE_SELECT_EVENT_HBS).await
 }
 
 pub async fn show_event(
     repo: Arc<dyn Repository>,
     channel: String,
     id: u32,
+    date_format: DateFormat,
+    locale: Locale,
 ) -> Result<String, Error> {
     let event = find_event::execute(repo, find_event::Request { id, channel }).await?;
 
+    let now = Date::now().timestamp();
+    let away = event
+        .participants
+        .iter()
+        .filter(|p| p.is_away(now))
+        .map(|p| {
+            let until = helpers::fmt_slack_date(
+                p.unavailable_until.unwrap_or(now),
+                event.timezone.clone(),
+                date_format,
+            );
+            let user = format!("<@{}>", p.user);
+            escape_json(&i18n::format(
+                locale,
+                "event_away_until",
+                &[("user", &user), ("date", &until)],
+            ))
+        })
+        .collect::<Vec<String>>();
+    let header = i18n::format(locale, "event_details_header", &[("name", &event.name)]);
+    let pick_mode = String::from(event.pick_mode.clone());
+
     let template = read_file(SHOW_EVENT_HBS)?;
     let result = super::render_template(
         &template,
         json!({
+            "t": i18n::catalog(locale),
+            "header": escape_json(&header),
             "id": event.id,
             "name": event.name,
-            "date": helpers::fmt_timestamp(event.timestamp, event.timezone),
-            "repeat": event.repeat.to_string(),
-            "participants": event.participants.into_iter().map(|p| p.user).collect::<Vec<String>>()
+            "date": helpers::fmt_slack_date(event.timestamp, event.timezone.clone(), date_format),
+            "repeat": repeat_label(locale, &event.repeat),
+            "pick_mode": i18n::t(locale, &format!("pick_mode_{}", pick_mode)),
+            "round_robin": event.pick_mode == PickMode::RoundRobin,
+            "ends_at": event.ends_at.map(|ends_at| helpers::fmt_slack_date(ends_at, event.timezone.clone(), date_format)),
+            "reminder": event.reminder_minutes.map(|minutes| i18n::format(locale, "event_reminder", &[("minutes", &minutes_label(locale, minutes))])),
+            "anchor": event.anchor,
+            "private": event.visibility == Visibility::Direct,
+            "skip_out_of_office": event.skip_out_of_office,
+            "skip_weekends": event.skip_weekends,
+            "skipped_dates": event.skipped_dates,
+            "user_group": event.user_group,
+            "seed": event.seed.map(|seed| seed.to_string()),
+            "description": event.description.as_deref().map(escape_json),
+            "url": event.url.as_deref().map(escape_json),
+            "notify_channels": event.notify_channels,
+            "inactive": event.inactive,
+            "participants": event.participants.into_iter().map(|p| p.user).collect::<Vec<String>>(),
+            "away": away
         }),
     )
     .map_err(|err| {
@@ -141,39 +260,41 @@ pub async fn show_event(
 pub async fn show_select_event(
     repo: Arc<dyn Repository>,
     channel: String,
+    locale: Locale,
 ) -> Result<String, Error> {
-    select_event(repo, channel, SHOW_SELECT_EVENT_HBS).await
+    select_event(repo, channel, locale, SHOW_SELECT_EVENT_HBS).await
 }
 
 pub async fn pick_select_event(
     repo: Arc<dyn Repository>,
     channel: String,
+    locale: Locale,
 ) -> Result<String, Error> {
-    select_event(repo, channel, PICK_SELECT_EVENT_HBS).await
+    select_event(repo, channel, locale, PICK_SELECT_EVENT_HBS).await
 }
 
 async fn select_event(
     repo: Arc<dyn Repository>,
     channel: String,
+    locale: Locale,
     filename: &str,
 ) -> Result<String, Error> {
-    let events = find_all_events::execute(repo.clone(), find_all_events::Request { channel })
-        .await?
-        .data;
+    let events = find_all_events::execute(
+        repo.clone(),
+        find_all_events::Request {
+            channel,
+            page: None,
+        },
+    )
+    .await?
+    .data;
 
     let template = read_file(filename)?;
     let result = super::render_template(
         &template,
         json!({
-            "events": events
-                .into_iter()
-                .map(|event|
-                    json!({
-                        "text": format!("[{}]: {}", event.id, event.name),
-                        "id": event.id
-                    })
-                )
-                .collect::<Vec<Value>>(),
+            "t": i18n::catalog(locale),
+            "has_events": !events.is_empty(),
         }),
     )
     .map_err(|err| {
@@ -188,6 +309,8 @@ async fn event_action_success(
     repo: Arc<dyn Repository>,
     channel: String,
     id: u32,
+    date_format: DateFormat,
+    locale: Locale,
     filename: &str,
 ) -> Result<String, Error> {
     let event = find_event::execute(repo, find_event::Request { channel, id }).await?;
@@ -196,10 +319,11 @@ async fn event_action_success(
     let result = super::render_template(
         &template,
         json!({
+            "t": i18n::catalog(locale),
             "id": event.id,
             "name": event.name,
-            "date": helpers::fmt_timestamp(event.timestamp, event.timezone),
-            "repeat": event.repeat.to_string(),
+            "date": helpers::fmt_slack_date(event.timestamp, event.timezone, date_format),
+            "repeat": repeat_label(locale, &event.repeat),
             "participants": event.participants.into_iter().map(|p| p.user).collect::<Vec<String>>()
         }),
     )
@@ -211,6 +335,7 @@ async fn event_action_success(
     Ok(result)
 }
 
+#[derive(Debug)]
 pub enum Error {
     Query,
     QueryNotFound,
@@ -227,6 +352,17 @@ impl From<Error> for StatusCode {
     }
 }
 
+impl From<Error> for crate::error::Error {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::QueryNotFound => Self::NotFound,
+            Error::Query => Self::Repository(String::from("could not query the template data")),
+            Error::ReadFile => Self::Template(String::from("could not read the template")),
+            Error::RenderTemplate => Self::Template(String::from("could not render the template")),
+        }
+    }
+}
+
 impl From<find_event::Error> for Error {
     fn from(value: find_event::Error) -> Self {
         match value {
@@ -256,6 +392,13 @@ const SHOW_EVENT_HBS: &str = "show_event.json.hbs";
 const SHOW_SELECT_EVENT_HBS: &str = "show_select_event.json.hbs";
 const PICK_SELECT_EVENT_HBS: &str = "pick_select_event.json.hbs";
 
+/// Escapes the free texts of the events to be rendered unescaped inside the strings of the JSON
+/// templates, as they may have quotes and line breaks (e.g. the descriptions).
+fn escape_json(value: &str) -> String {
+    let quoted = Value::from(value).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
 fn hbs_path(filename: &str) -> String {
     format!("{}/{}", HBS_BASE_PATHS, filename)
 }
@@ -266,3 +409,29 @@ fn read_file(filename: &str) -> Result<String, Error> {
         Error::ReadFile
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_event_locales() {
+        for locale in Locale::all() {
+            let template = add_event(Timezone::UTC, locale).ok().unwrap();
+            let view: Value = serde_json::from_str(&template).unwrap();
+            assert_eq!(
+                view["blocks"][5]["hint"]["text"],
+                i18n::t(locale, "form_date_hint")
+            );
+        }
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(escape_json("Agenda"), "Agenda");
+        assert_eq!(
+            escape_json("The \"daily\"\nagenda"),
+            "The \\\"daily\\\"\\nagenda"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::domain::helpers::token;
+
+use super::client::{Conversation, DndStatus, Error, SlackClient, User, UserGroup, UserProfile};
+
+/// Throttles the requests sent to Slack for each workspace in front of another client, so that
+/// the bursts (e.g. several events picked on the same minute) are queued instead of rate limited.
+///
+/// The requests Slack still rate limits pause the whole workspace and are sent again once the
+/// pause is over, instead of failing.
+pub struct ThrottledSlackClient {
+    inner: Arc<dyn SlackClient>,
+    /// The buckets of the workspaces, told apart by the hash of their tokens.
+    buckets: Mutex<HashMap<String, Bucket>>,
+    /// The number of requests each workspace can send per second.
+    rate: f64,
+}
+
+impl ThrottledSlackClient {
+    pub fn new(inner: Arc<dyn SlackClient>, requests_per_minute: u32) -> Self {
+        Self {
+            inner,
+            buckets: Mutex::new(HashMap::new()),
+            rate: requests_per_minute as f64 / 60.0,
+        }
+    }
+
+    /// Waits for the turn of a request of the workspace of the token.
+    async fn acquire(&self, token: &str) {
+        let delay = self.with_bucket(token, |bucket, now, rate| bucket.reserve(now, rate));
+        if !delay.is_zero() {
+            log::trace!("throttling slack request for {:?}", delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Holds the requests of the workspace of the token for the delay.
+    fn pause(&self, token: &str, delay: Duration) {
+        self.with_bucket(token, |bucket, now, rate| bucket.pause(now, rate, delay));
+    }
+
+    fn with_bucket<T>(&self, token: &str, f: impl FnOnce(&mut Bucket, Instant, f64) -> T) -> T {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("throttle lock is poisoned");
+        let bucket = buckets
+            .entry(token::hash(token))
+            .or_insert_with(|| Bucket::new(now));
+        f(bucket, now, self.rate)
+    }
+
+    /// Sends the request once it is its turn, sending it again after a pause when rate limited.
+    async fn call<T, F, Fut>(&self, token: &str, send: F) -> Result<T, Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 1;
+        loop {
+            self.acquire(token).await;
+            match send().await {
+                Err(Error::Api(code)) if code == RATE_LIMITED && attempt < MAX_ATTEMPTS => {
+                    log::warn!(
+                        "slack request rate limited, pausing the workspace for {:?} (attempt {}/{})",
+                        RATE_LIMITED_PAUSE,
+                        attempt,
+                        MAX_ATTEMPTS
+                    );
+                    self.pause(token, RATE_LIMITED_PAUSE);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A token bucket, refilled at the rate of the workspace up to its capacity.
+///
+/// The requests take the tokens ahead of time, so that the bucket goes below zero and each
+/// request waits behind the ones before it.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: BUCKET_CAPACITY,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant, rate: f64) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(BUCKET_CAPACITY);
+        self.updated_at = now;
+    }
+
+    /// Takes a token, returning how long to wait before it is available.
+    fn reserve(&mut self, now: Instant, rate: f64) -> Duration {
+        self.refill(now, rate);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+
+    /// Empties the bucket, so that the next token is only available after the delay.
+    fn pause(&mut self, now: Instant, rate: f64, delay: Duration) {
+        self.refill(now, rate);
+        self.tokens = self.tokens.min(0.0) - delay.as_secs_f64() * rate;
+    }
+}
+
+#[async_trait]
+impl SlackClient for ThrottledSlackClient {
+    async fn post_message(&self, token: &str, message: Value) -> Result<String, Error> {
+        self.call(token, || self.inner.post_message(token, message.clone()))
+            .await
+    }
+
+    async fn update_message(&self, token: &str, message: Value) -> Result<(), Error> {
+        self.call(token, || self.inner.update_message(token, message.clone()))
+            .await
+    }
+
+    async fn post_ephemeral(&self, token: &str, message: Value) -> Result<(), Error> {
+        self.call(token, || self.inner.post_ephemeral(token, message.clone()))
+            .await
+    }
+
+    async fn pin_message(&self, token: &str, channel: &str, ts: &str) -> Result<(), Error> {
+        self.call(token, || self.inner.pin_message(token, channel, ts))
+            .await
+    }
+
+    async fn open_view(&self, token: &str, trigger_id: &str, view: Value) -> Result<(), Error> {
+        // The triggers expire after three seconds, so the views are not held back.
+        self.inner.open_view(token, trigger_id, view).await
+    }
+
+    async fn user_info(&self, token: &str, user: &str) -> Result<User, Error> {
+        self.call(token, || self.inner.user_info(token, user)).await
+    }
+
+    async fn user_profile(&self, token: &str, user: &str) -> Result<UserProfile, Error> {
+        self.call(token, || self.inner.user_profile(token, user))
+            .await
+    }
+
+    async fn dnd_info(&self, token: &str, user: &str) -> Result<DndStatus, Error> {
+        self.call(token, || self.inner.dnd_info(token, user)).await
+    }
+
+    async fn users_list(&self, token: &str) -> Result<Vec<User>, Error> {
+        self.call(token, || self.inner.users_list(token)).await
+    }
+
+    async fn usergroups_list(&self, token: &str) -> Result<Vec<UserGroup>, Error> {
+        self.call(token, || self.inner.usergroups_list(token)).await
+    }
+
+    async fn usergroups_users_list(
+        &self,
+        token: &str,
+        usergroup: &str,
+    ) -> Result<Vec<String>, Error> {
+        self.call(token, || self.inner.usergroups_users_list(token, usergroup))
+            .await
+    }
+
+    async fn conversations_list(&self, token: &str) -> Result<Vec<Conversation>, Error> {
+        self.call(token, || self.inner.conversations_list(token))
+            .await
+    }
+
+    async fn conversations_info(&self, token: &str, channel: &str) -> Result<Conversation, Error> {
+        self.call(token, || self.inner.conversations_info(token, channel))
+            .await
+    }
+
+    async fn conversations_join(&self, token: &str, channel: &str) -> Result<(), Error> {
+        self.call(token, || self.inner.conversations_join(token, channel))
+            .await
+    }
+
+    async fn upload_file(
+        &self,
+        token: &str,
+        channel: &str,
+        filename: &str,
+        content: String,
+    ) -> Result<(), Error> {
+        self.call(token, || {
+            self.inner
+                .upload_file(token, channel, filename, content.clone())
+        })
+        .await
+    }
+}
+
+/// The error Slack replies with once the retries of the rate limited requests are exhausted.
+const RATE_LIMITED: &str = "ratelimited";
+
+/// The time the workspaces are paused for when rate limited, as the rate limit windows of Slack
+/// are of a minute.
+const RATE_LIMITED_PAUSE: Duration = Duration::from_secs(30);
+
+/// The number of times a rate limited request is sent, including the first one.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// The number of requests a workspace can send at once, after being idle.
+const BUCKET_CAPACITY: f64 = 10.0;
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::client::mock::MockSlackClient;
+
+    use super::*;
+
+    #[test]
+    fn test_bucket() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(now);
+        for _ in 0..BUCKET_CAPACITY as usize {
+            assert_eq!(bucket.reserve(now, 1.0), Duration::ZERO);
+        }
+        // The next requests wait behind each other.
+        assert_eq!(bucket.reserve(now, 1.0), Duration::from_secs(1));
+        assert_eq!(bucket.reserve(now, 1.0), Duration::from_secs(2));
+
+        // The bucket is refilled over time, up to its capacity.
+        let later = now + Duration::from_secs(60);
+        assert_eq!(bucket.reserve(later, 1.0), Duration::ZERO);
+        assert_eq!(bucket.tokens, BUCKET_CAPACITY - 1.0);
+
+        bucket.pause(later, 1.0, Duration::from_secs(30));
+        assert_eq!(bucket.reserve(later, 1.0), Duration::from_secs(31));
+    }
+
+    #[tokio::test]
+    async fn test_throttled_client() {
+        let inner = Arc::new(MockSlackClient::default());
+        let client = ThrottledSlackClient::new(inner.clone(), 60);
+
+        client
+            .post_message("xoxb-1", serde_json::json!({ "channel": "C1" }))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls().len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use axum::extract::connect_info::Connected;
+use hyper::server::accept::{self, Accept};
+use rustls_pemfile::Item;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::rustls::{Certificate, PrivateKey, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+/// A connection accepted over TLS, along with the address of the peer, so that the requests are
+/// still limited by it.
+pub struct TlsConnection {
+    stream: TlsStream<TcpStream>,
+    remote_addr: SocketAddr,
+}
+
+impl Connected<&TlsConnection> for SocketAddr {
+    fn connect_info(target: &TlsConnection) -> Self {
+        target.remote_addr
+    }
+}
+
+impl AsyncRead for TlsConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TlsConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+/// Loads the PEM certificate chain and private key the connections are encrypted with.
+pub fn load_config(cert_file: &str, key_file: &str) -> Result<Arc<ServerConfig>, String> {
+    let certs = rustls_pemfile::certs(&mut open(cert_file)?)
+        .map_err(|err| format!("could not read certificates {}: {}", cert_file, err))?;
+    if certs.is_empty() {
+        return Err(format!("no certificates found in {}", cert_file));
+    }
+    let key = read_key(key_file)?;
+
+    let mut config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs.into_iter().map(Certificate).collect(), key)
+        .map_err(|err| format!("invalid certificate or private key: {}", err))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+/// Listens on the address, accepting the connections that complete the TLS handshake.
+///
+/// The handshakes run on their own tasks, so that the slow clients do not hold back the others.
+pub async fn bind(
+    addr: SocketAddr,
+    config: Arc<ServerConfig>,
+) -> io::Result<impl Accept<Conn = TlsConnection, Error = io::Error>> {
+    let listener = TcpListener::bind(addr).await?;
+    let acceptor = TlsAcceptor::from(config);
+    let (tx, rx) = mpsc::channel::<TlsConnection>(ACCEPT_BACKLOG);
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, remote_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    log::warn!("could not accept connection: {}", err);
+                    continue;
+                }
+            };
+            let acceptor = acceptor.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => {
+                        let _ = tx
+                            .send(TlsConnection {
+                                stream,
+                                remote_addr,
+                            })
+                            .await;
+                    }
+                    Ok(Err(err)) => {
+                        log::trace!("tls handshake with {} failed: {}", remote_addr, err)
+                    }
+                    Err(..) => log::trace!("tls handshake with {} timed out", remote_addr),
+                }
+            });
+        }
+    });
+
+    Ok(accept::from_stream(futures::stream::unfold(
+        rx,
+        |mut rx| async move { rx.recv().await.map(|connection| (Ok(connection), rx)) },
+    )))
+}
+
+fn open(path: &str) -> Result<BufReader<File>, String> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|err| format!("could not open {}: {}", path, err))
+}
+
+fn read_key(key_file: &str) -> Result<PrivateKey, String> {
+    let mut reader = open(key_file)?;
+    loop {
+        match rustls_pemfile::read_one(&mut reader)
+            .map_err(|err| format!("could not read private key {}: {}", key_file, err))?
+        {
+            Some(Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key)) => {
+                return Ok(PrivateKey(key))
+            }
+            Some(..) => continue,
+            None => return Err(format!("no private key found in {}", key_file)),
+        }
+    }
+}
+
+/// The time the clients have to complete the handshake before being disconnected.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of connections waiting to be served by the server.
+const ACCEPT_BACKLOG: usize = 128;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_config() {
+        let err = load_config("missing.crt", "missing.key").unwrap_err();
+        assert!(err.starts_with("could not open missing.crt"));
+
+        let err = load_config("Cargo.toml", "Cargo.toml").unwrap_err();
+        assert_eq!(err, "no certificates found in Cargo.toml");
+    }
+}
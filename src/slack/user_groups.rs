@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::repository::{errors::FindError, user_group};
+
+use super::client::{self, SlackClient, UserGroup};
+
+/// Finds the members of the user groups through the Slack Web API.
+pub struct SlackUserGroupRepository {
+    client: Arc<dyn SlackClient>,
+}
+
+impl SlackUserGroupRepository {
+    pub fn new(client: Arc<dyn SlackClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl user_group::Repository for SlackUserGroupRepository {
+    async fn find_members(&self, token: &str, group: &str) -> Result<Vec<String>, FindError> {
+        self.client
+            .usergroups_users_list(token, group)
+            .await
+            .map_err(|err| match err {
+                client::Error::Api(err) if err == "no_such_subteam" => FindError::NotFound,
+                err => {
+                    log::error!("could not list the users of group {}: {}", group, err);
+                    FindError::Unknown
+                }
+            })
+    }
+}
+
+/// Finds the user group mentioned by the handle, with or without the leading `@`.
+pub async fn find_by_handle(
+    client: &dyn SlackClient,
+    token: &str,
+    handle: &str,
+) -> Result<Option<UserGroup>, client::Error> {
+    let handle = handle.trim().trim_start_matches('@').to_lowercase();
+    Ok(client
+        .usergroups_list(token)
+        .await?
+        .into_iter()
+        .find(|group| group.handle.to_lowercase() == handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slack::client::mock::MockSlackClient;
+
+    #[tokio::test]
+    async fn test_find_by_handle() {
+        let client = MockSlackClient {
+            user_groups: vec![UserGroup {
+                id: String::from("S0614TZR7"),
+                handle: String::from("backend-team"),
+                users: vec![String::from("U0797QD5AJZ")],
+            }],
+            ..Default::default()
+        };
+
+        let group = find_by_handle(&client, "token", " @Backend-Team")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(group.id, "S0614TZR7");
+        assert!(find_by_handle(&client, "token", "@frontend-team")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
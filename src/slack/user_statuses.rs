@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::helpers::date::Date;
+use crate::repository::{errors::FindError, user_status};
+
+use super::client::{self, SlackClient, UserProfile};
+
+/// Finds the statuses of the users through the Slack Web API.
+pub struct SlackUserStatusRepository {
+    client: Arc<dyn SlackClient>,
+}
+
+impl SlackUserStatusRepository {
+    pub fn new(client: Arc<dyn SlackClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl user_status::Repository for SlackUserStatusRepository {
+    async fn find_out_of_office(
+        &self,
+        token: &str,
+        users: &[String],
+    ) -> Result<Vec<String>, FindError> {
+        let now = Date::now().timestamp();
+        let mut result = vec![];
+        for user in users.iter() {
+            let profile = match self.client.user_profile(token, user).await {
+                Ok(profile) => profile,
+                // The users who left the workspace have no status.
+                Err(client::Error::Api(err)) if err == "user_not_found" => continue,
+                Err(err) => {
+                    log::error!("could not find the profile of user {}: {}", user, err);
+                    return Err(FindError::Unknown);
+                }
+            };
+            if is_out_of_office(&profile, now) {
+                result.push(user.clone());
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Whether the status of the profile tells that the user is out of office, either by its emoji or
+/// by its text (e.g. `OOO until Monday`).
+fn is_out_of_office(profile: &UserProfile, now: i64) -> bool {
+    if profile.status_expiration != 0 && profile.status_expiration <= now {
+        return false;
+    }
+    OUT_OF_OFFICE_EMOJIS.contains(&profile.status_emoji.as_str())
+        || is_out_of_office_text(&profile.status_text)
+}
+
+/// Whether the text (e.g. of a status or of a calendar event) tells that its user is out of
+/// office.
+pub fn is_out_of_office_text(text: &str) -> bool {
+    let text = text.to_lowercase();
+    OUT_OF_OFFICE_PHRASES
+        .iter()
+        .any(|phrase| text.contains(phrase))
+        || text
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word == "ooo")
+}
+
+/// The emojis of the statuses of the users away from work (e.g. 🌴).
+const OUT_OF_OFFICE_EMOJIS: [&str; 3] = [":palm_tree:", ":desert_island:", ":beach_with_umbrella:"];
+
+const OUT_OF_OFFICE_PHRASES: [&str; 4] =
+    ["out of office", "out of the office", "vacation", "on leave"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(text: &str, emoji: &str, expiration: i64) -> UserProfile {
+        UserProfile {
+            status_text: String::from(text),
+            status_emoji: String::from(emoji),
+            status_expiration: expiration,
+        }
+    }
+
+    #[test]
+    fn test_is_out_of_office() {
+        let now = 1724681700;
+        assert!(is_out_of_office(&profile("", ":palm_tree:", 0), now));
+        assert!(is_out_of_office(&profile("OOO until Monday", "", 0), now));
+        assert!(is_out_of_office(
+            &profile("On vacation", ":sunny:", now + 60),
+            now
+        ));
+        assert!(!is_out_of_office(
+            &profile("In a meeting", ":calendar:", 0),
+            now
+        ));
+        assert!(!is_out_of_office(
+            &profile("Looking at the zoom", "", 0),
+            now
+        ));
+        // The expired statuses are not cleared right away.
+        assert!(!is_out_of_office(
+            &profile("", ":palm_tree:", now - 60),
+            now
+        ));
+        assert!(!is_out_of_office(&UserProfile::default(), now));
+    }
+}
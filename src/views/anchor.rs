@@ -0,0 +1,23 @@
+use serde_json::Value;
+use slack_blocks::blocks::Section;
+use slack_blocks::text;
+
+use super::entities::{BlockGroup, Response};
+
+pub struct AnchorView {
+    pub channel_id: String,
+    pub event_name: String,
+}
+
+pub fn view(data: AnchorView) -> Value {
+    let blocks = BlockGroup::empty().channel(data.channel_id).add(
+        Section::builder()
+            .text(text::Mrkdwn::from_text(format!(
+                ":pushpin: The automatic picks for the event *{}* are posted in this thread",
+                data.event_name
+            )))
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
@@ -0,0 +1,45 @@
+use serde_json::Value;
+use slack_blocks::blocks::Section;
+use slack_blocks::text;
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::entities::{BlockGroup, Response};
+use super::pick_participant::mention_picked;
+
+pub struct AnnouncementView {
+    /// The channel the pick is announced on.
+    pub channel_id: String,
+    /// The channel of the event.
+    pub event_channel_id: String,
+    pub event_name: String,
+    pub user_picked_id: String,
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner_picked_id: Option<String>,
+    pub locale: Locale,
+}
+
+pub fn view(data: AnnouncementView) -> Value {
+    let blocks = BlockGroup::empty().channel(data.channel_id).add(
+        Section::builder()
+            .text(text::Mrkdwn::from_text(i18n::format(
+                data.locale,
+                "announcement",
+                &[
+                    (
+                        "user",
+                        &mention_picked(
+                            data.locale,
+                            &data.user_picked_id,
+                            data.partner_picked_id.as_deref(),
+                        ),
+                    ),
+                    ("event", &data.event_name),
+                    ("channel", &data.event_channel_id),
+                ],
+            )))
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
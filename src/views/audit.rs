@@ -0,0 +1,99 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Header, Section},
+    elems::Button,
+    text,
+};
+
+use crate::{
+    domain::{entities::AuditAction, locale::Locale},
+    i18n,
+};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct AuditView {
+    pub records: Vec<AuditItemView>,
+    pub locale: Locale,
+}
+
+pub struct AuditItemView {
+    pub user: Option<String>,
+    pub action: AuditAction,
+    pub event: Option<u32>,
+    pub date: String,
+}
+
+pub fn view(data: AuditView) -> Value {
+    let locale = data.locale;
+    let mut blocks = BlockGroup::empty().add(
+        Header::builder()
+            .text(i18n::t(locale, "audit_header"))
+            .build()
+            .into(),
+    );
+    if data.records.is_empty() {
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(i18n::t(locale, "audit_empty")))
+                .build()
+                .into(),
+        );
+    }
+    for record in data.records {
+        let user = match record.user {
+            Some(user) => format!("<@{}>", user),
+            None => i18n::t(locale, "audit_someone").to_string(),
+        };
+        let description = match record.event {
+            Some(event) => format!("{} [{}]", action_label(locale, &record.action), event),
+            None => action_label(locale, &record.action).to_string(),
+        };
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "{} {}\n\t\t_{}_",
+                    user, description, record.date
+                )))
+                .build()
+                .into(),
+        );
+    }
+    blocks = blocks.add(
+        Actions::builder()
+            .element(
+                Button::builder()
+                    .text(i18n::t(locale, "close_button"))
+                    .value("close")
+                    .action_id("close")
+                    .build(),
+            )
+            .block_id("list_events_actions")
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
+
+/// The description of the action, following the user who did it (e.g. "created the event").
+fn action_label(locale: Locale, action: &AuditAction) -> &str {
+    let key = match action {
+        AuditAction::EventCreated => "audit_event_created",
+        AuditAction::EventUpdated => "audit_event_updated",
+        AuditAction::EventDeleted => "audit_event_deleted",
+        AuditAction::Pick => "audit_pick",
+        AuditAction::Skip => "audit_skip",
+        AuditAction::Repick => "audit_repick",
+        AuditAction::CancelPick => "audit_cancel_pick",
+        AuditAction::SkipOccurrence => "audit_skip_occurrence",
+        AuditAction::EventPaused => "audit_event_paused",
+        AuditAction::Acknowledge => "audit_acknowledge",
+        AuditAction::AcknowledgmentExpired => "audit_acknowledgment_expired",
+        AuditAction::NoShow => "audit_no_show",
+        AuditAction::Volunteer => "audit_volunteer",
+        AuditAction::Install => "audit_install",
+        AuditAction::ApiKeyCreated => "audit_api_key_created",
+        AuditAction::ApiKeyRevoked => "audit_api_key_revoked",
+    };
+    i18n::t(locale, key)
+}
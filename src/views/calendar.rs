@@ -0,0 +1,221 @@
+use chrono::{Datelike, Weekday};
+
+use crate::domain::{entities::RepeatPeriod, timezone::Timezone};
+use crate::helpers::date::Date;
+
+pub struct CalendarEvent {
+    pub uid: String,
+    pub name: String,
+    pub start: i64,
+    pub timezone: Timezone,
+    pub rrule: Option<String>,
+}
+
+/// Renders the events as an iCalendar feed.
+pub fn view(name: &str, events: Vec<CalendarEvent>) -> String {
+    let now = utc_date_time(Date::now().timestamp());
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//Team Event Picker//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+        format!("X-WR-CALNAME:{}", escape(name)),
+    ];
+    for event in events.into_iter() {
+        lines.push(String::from("BEGIN:VEVENT"));
+        lines.push(format!("UID:{}@team-event-picker", event.uid));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!(
+            "DTSTART;TZID={}:{}",
+            event.timezone.tz().name(),
+            Date::new(event.start)
+                .with_timezone(event.timezone)
+                .to_datetime()
+                .format(LOCAL_DATE_TIME_FORMAT)
+        ));
+        lines.push(format!("DURATION:{}", EVENT_DURATION));
+        lines.push(format!("SUMMARY:{}", escape(&event.name)));
+        if let Some(rrule) = event.rrule {
+            lines.push(format!("RRULE:{}", rrule));
+        }
+        lines.push(String::from("END:VEVENT"));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines.join("\r\n") + "\r\n"
+}
+
+/// Derives the recurrence rule of a repeat period starting at `start`. The daily events skipping
+/// the weekends only repeat on the weekdays.
+///
+/// Returns `None` for the events that do not repeat and for the cron expressions, which have no
+/// recurrence rule equivalent.
+pub fn rrule(
+    repeat: &RepeatPeriod,
+    start: i64,
+    timezone: Timezone,
+    ends_at: Option<i64>,
+    skip_weekends: bool,
+) -> Option<String> {
+    let rule = match repeat {
+        RepeatPeriod::None | RepeatPeriod::Cron(..) => return None,
+        RepeatPeriod::Daily if skip_weekends => String::from("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"),
+        RepeatPeriod::Daily => String::from("FREQ=DAILY"),
+        RepeatPeriod::Weekly(n) => format!("FREQ=WEEKLY;INTERVAL={}", n),
+        RepeatPeriod::Monthly(n) => {
+            let date = Date::new(start).with_timezone(timezone).to_datetime();
+            // The fifth week falls back to the last week of the month.
+            let week = match (date.day() - 1) / 7 + 1 {
+                5 => -1,
+                week => week as i32,
+            };
+            format!(
+                "FREQ=MONTHLY;INTERVAL={};BYDAY={}{}",
+                n,
+                week,
+                weekday_code(&date.weekday())
+            )
+        }
+        RepeatPeriod::Yearly => String::from("FREQ=YEARLY"),
+        RepeatPeriod::Days(weekdays) => format!(
+            "FREQ=WEEKLY;BYDAY={}",
+            weekdays
+                .iter()
+                .map(weekday_code)
+                .collect::<Vec<&str>>()
+                .join(",")
+        ),
+    };
+    Some(match ends_at {
+        Some(ends_at) => format!("{};UNTIL={}", rule, utc_date_time(ends_at)),
+        None => rule,
+    })
+}
+
+/// Whether `start` falls on a Saturday or a Sunday, on the timezone. The weekly events starting on
+/// a weekend never happen when they skip the weekends.
+pub fn is_weekend(start: i64, timezone: Timezone) -> bool {
+    matches!(
+        Date::new(start)
+            .with_timezone(timezone)
+            .to_datetime()
+            .weekday(),
+        Weekday::Sat | Weekday::Sun
+    )
+}
+
+fn weekday_code(weekday: &Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn utc_date_time(timestamp: i64) -> String {
+    Date::new(timestamp)
+        .to_datetime()
+        .naive_utc()
+        .format(UTC_DATE_TIME_FORMAT)
+        .to_string()
+}
+
+/// Escapes the characters with a special meaning on iCalendar text values.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+const LOCAL_DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+const UTC_DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+/// The events are shown on the calendars as short meetings, as they have no duration.
+const EVENT_DURATION: &str = "PT15M";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rrule() {
+        // 2023-06-13 09:00 EDT, the second Tuesday of the month.
+        let start = 1686661200;
+        let rrule = |repeat: RepeatPeriod, ends_at: Option<i64>| {
+            super::rrule(&repeat, start, Timezone::EST, ends_at, false)
+        };
+
+        assert_eq!(rrule(RepeatPeriod::None, None), None);
+        assert_eq!(
+            rrule(RepeatPeriod::Cron("0 9 * * *".to_string()), None),
+            None
+        );
+        assert_eq!(
+            rrule(RepeatPeriod::Daily, None),
+            Some("FREQ=DAILY".to_string())
+        );
+        assert_eq!(
+            rrule(RepeatPeriod::Weekly(2), Some(1688137200)),
+            Some("FREQ=WEEKLY;INTERVAL=2;UNTIL=20230630T150000Z".to_string())
+        );
+        assert_eq!(
+            rrule(RepeatPeriod::Monthly(1), None),
+            Some("FREQ=MONTHLY;INTERVAL=1;BYDAY=2TU".to_string())
+        );
+        assert_eq!(
+            rrule(RepeatPeriod::Days(vec![Weekday::Mon, Weekday::Thu]), None),
+            Some("FREQ=WEEKLY;BYDAY=MO,TH".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rrule_skipping_weekends() {
+        // 2023-06-13 09:00 EDT, a Tuesday.
+        let start = 1686661200;
+        let rrule = |repeat: RepeatPeriod| super::rrule(&repeat, start, Timezone::EST, None, true);
+
+        assert_eq!(
+            rrule(RepeatPeriod::Daily),
+            Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR".to_string())
+        );
+        // The weekly events starting on a weekday never fall on a weekend.
+        assert_eq!(
+            rrule(RepeatPeriod::Weekly(1)),
+            Some("FREQ=WEEKLY;INTERVAL=1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_weekend() {
+        // 2023-06-13 09:00 EDT, a Tuesday.
+        assert!(!is_weekend(1686661200, Timezone::EST));
+        // 2023-06-17 09:00 EDT, a Saturday.
+        assert!(is_weekend(1687006800, Timezone::EST));
+        // 2023-06-17 00:30 UTC is still Friday in New York.
+        assert!(!is_weekend(1686961800, Timezone::EST));
+        assert!(is_weekend(1686961800, Timezone::UTC));
+    }
+
+    #[test]
+    fn test_view() {
+        let calendar = view(
+            "general",
+            vec![CalendarEvent {
+                uid: "1".to_string(),
+                name: "Stand-up, daily".to_string(),
+                start: 1686661200,
+                timezone: Timezone::EST,
+                rrule: Some("FREQ=YEARLY".to_string()),
+            }],
+        );
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("DTSTART;TZID=America/New_York:20230613T090000\r\n"));
+        assert!(calendar.contains("SUMMARY:Stand-up\\, daily\r\n"));
+        assert!(calendar.contains("RRULE:FREQ=YEARLY\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    }
+}
@@ -3,6 +3,8 @@ use slack_blocks::blocks::Section;
 use slack_blocks::elems::Button;
 use slack_blocks::text;
 
+use crate::{domain::locale::Locale, i18n};
+
 use super::entities::{BlockGroup, Response};
 
 pub struct CancelPickView {
@@ -10,18 +12,24 @@ pub struct CancelPickView {
     pub user_id: String,
     pub event_id: u32,
     pub event_name: String,
+    pub locale: Locale,
 }
 
 pub fn view(data: CancelPickView) -> Value {
+    let locale = data.locale;
     let blocks = BlockGroup::empty().channel(data.channel_id).add(
         Section::builder()
-            .text(text::Mrkdwn::from_text(format!(
-                "<@{}> cancelled previous pick for the event *{}*\n\t\t_Source: Cancel_",
-                data.user_id, data.event_name
+            .text(text::Mrkdwn::from_text(i18n::format(
+                locale,
+                "cancel_pick",
+                &[
+                    ("user", &format!("<@{}>", data.user_id)),
+                    ("event", &data.event_name),
+                ],
             )))
             .accessory(
                 Button::builder()
-                    .text("Pick again")
+                    .text(i18n::t(locale, "cancel_pick_again_button"))
                     .action_id("cancel_pick_actions:pick")
                     .value(data.event_id.to_string())
                     .build(),
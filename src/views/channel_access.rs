@@ -0,0 +1,104 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Section},
+    elems::{button::Style, Button},
+    text,
+};
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::entities::{BlockGroup, Response};
+
+/// The message sent to the owner of an event whose pick could not be posted, as the bot is not on
+/// its channel anymore.
+pub struct ChannelAccessView {
+    /// The owner of the event, messaged directly.
+    pub user_id: String,
+    pub event_id: u32,
+    pub event_name: String,
+    pub channel_id: String,
+    pub locale: Locale,
+}
+
+pub fn view(data: ChannelAccessView) -> Value {
+    let locale = data.locale;
+    // The buttons are clicked from the direct message, so they carry the channel of the event.
+    let value = action_value(data.event_id, &data.channel_id);
+    let blocks = BlockGroup::empty()
+        .channel(data.user_id)
+        .add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(i18n::format(
+                    locale,
+                    "channel_access_lost",
+                    &[
+                        ("event", &data.event_name),
+                        ("channel", &format!("<#{}>", data.channel_id)),
+                    ],
+                )))
+                .build()
+                .into(),
+        )
+        .add(
+            Actions::builder()
+                .element(
+                    Button::builder()
+                        .text(i18n::t(locale, "channel_access_rejoin_button"))
+                        .action_id(REJOIN_ACTION_ID)
+                        .value(value.clone())
+                        .style(Style::Primary)
+                        .build(),
+                )
+                .element(
+                    Button::builder()
+                        .text(i18n::t(locale, "channel_access_pause_button"))
+                        .action_id(PAUSE_ACTION_ID)
+                        .value(value)
+                        .style(Style::Danger)
+                        .build(),
+                )
+                .build()
+                .into(),
+        );
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
+
+fn action_value(event_id: u32, channel_id: &str) -> String {
+    format!("{}:{}", event_id, channel_id)
+}
+
+/// Parses the event and its channel from the value of the buttons.
+pub fn parse_action_value(value: &str) -> Option<(u32, String)> {
+    let (event_id, channel_id) = value.split_once(':')?;
+    Some((event_id.parse().ok()?, channel_id.to_string()))
+}
+
+/// The action of the button joining the channel of the event again.
+pub const REJOIN_ACTION_ID: &str = "channel_access_actions:rejoin";
+
+/// The action of the button pausing the event.
+pub const PAUSE_ACTION_ID: &str = "channel_access_actions:pause";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let body = view(ChannelAccessView {
+            user_id: String::from("U1"),
+            event_id: 7,
+            event_name: String::from("Daily"),
+            channel_id: String::from("C1"),
+            locale: Locale::default(),
+        });
+        assert_eq!(body["channel"], "U1");
+        let buttons = &body["blocks"][1]["elements"];
+        assert_eq!(buttons[0]["action_id"], REJOIN_ACTION_ID);
+        assert_eq!(buttons[1]["action_id"], PAUSE_ACTION_ID);
+
+        let value = buttons[1]["value"].as_str().unwrap();
+        assert_eq!(parse_action_value(value), Some((7, String::from("C1"))));
+        assert_eq!(parse_action_value("7"), None);
+    }
+}
@@ -6,6 +6,8 @@ pub struct BlockGroup<'a> {
     replace_original: bool,
     #[serde(skip_serializing_if = "Option::is_none", rename = "channel")]
     channel_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<String>,
 }
 
 impl<'a> BlockGroup<'a> {
@@ -14,6 +16,7 @@ impl<'a> BlockGroup<'a> {
             blocks: vec![],
             replace_original: true,
             channel_id: None,
+            thread_ts: None,
         };
     }
 
@@ -26,6 +29,11 @@ impl<'a> BlockGroup<'a> {
         self.channel_id = Some(channel_id);
         return self;
     }
+
+    pub fn thread(mut self, thread_ts: Option<String>) -> Self {
+        self.thread_ts = thread_ts;
+        self
+    }
 }
 
 #[derive(Serialize)]
@@ -45,6 +53,15 @@ impl<'a> Response<'a> {
         };
     }
 
+    /// Replaces the original message in place, instead of deleting it and posting a new one.
+    pub fn in_place(data: BlockGroup<'a>) -> Self {
+        Self {
+            data,
+            delete_original: false,
+            response_type: "in_channel",
+        }
+    }
+
     pub fn ephemeral(data: BlockGroup<'a>) -> Self {
         return Self {
             data,
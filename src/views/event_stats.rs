@@ -0,0 +1,77 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Header, Section},
+    elems::Button,
+    text,
+};
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct EventStatsView {
+    pub event_name: String,
+    pub participants: Vec<ParticipantStatsView>,
+    pub locale: Locale,
+}
+
+pub struct ParticipantStatsView {
+    pub user: String,
+    pub picks: u32,
+    pub skips: u32,
+    pub no_shows: u32,
+    pub last_picked: Option<String>,
+}
+
+pub fn view(data: EventStatsView) -> Value {
+    let locale = data.locale;
+    let mut blocks = BlockGroup::empty().add(
+        Header::builder()
+            .text(i18n::format(
+                locale,
+                "stats_header",
+                &[("event", &data.event_name)],
+            ))
+            .build()
+            .into(),
+    );
+    for participant in data.participants {
+        let count = |key: &str, count: u32| {
+            text::Mrkdwn::from_text(i18n::format(locale, key, &[("count", &count.to_string())]))
+        };
+        let last_picked = participant
+            .last_picked
+            .unwrap_or_else(|| i18n::t(locale, "stats_never").to_string());
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!("<@{}>", participant.user)))
+                .fields(vec![
+                    count("stats_picks", participant.picks).into(),
+                    count("stats_skips", participant.skips).into(),
+                    count("stats_no_shows", participant.no_shows).into(),
+                    text::Mrkdwn::from_text(i18n::format(
+                        locale,
+                        "stats_last_picked",
+                        &[("date", &last_picked)],
+                    ))
+                    .into(),
+                ])
+                .build()
+                .into(),
+        );
+    }
+    blocks = blocks.add(
+        Actions::builder()
+            .element(
+                Button::builder()
+                    .text(i18n::t(locale, "close_button"))
+                    .value("close")
+                    .action_id("close")
+                    .build(),
+            )
+            .block_id("list_events_actions")
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
@@ -0,0 +1,507 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Header, Section},
+    elems::{button::Style, Button},
+    text,
+};
+
+use super::entities::{BlockGroup, Response};
+
+/// A subcommand of `/picker`, with the help shown for it.
+pub struct HelpCommand {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub usage: &'static str,
+}
+
+/// Renders the subcommands, each with a button to show its help, along with the buttons to
+/// start managing the events.
+pub fn view(reached_limit: bool) -> Value {
+    let mut blocks = BlockGroup::empty()
+        .add(Header::builder().text("How can I help?  📖").build().into())
+        .add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(
+                    "Pick the participants of your team events with `/picker [SUBCOMMAND] [ARGS]`. These are the available subcommands:",
+                ))
+                .build()
+                .into(),
+        );
+    for command in COMMANDS {
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "*{}*\n{}",
+                    command.name, command.summary
+                )))
+                .accessory(
+                    Button::builder()
+                        .text("Details")
+                        .value(command.name)
+                        .action_id(format!("{}{}", COMMAND_ACTION_PREFIX, command.name))
+                        .build(),
+                )
+                .build()
+                .into(),
+        );
+    }
+
+    let mut actions = vec![];
+    if !reached_limit {
+        actions.push(
+            Button::builder()
+                .text("Create event")
+                .value("add_event")
+                .action_id("add_event")
+                .style(Style::Primary)
+                .build(),
+        );
+    }
+    actions.push(
+        Button::builder()
+            .text("List events")
+            .value("list_events")
+            .action_id("list_events")
+            .build(),
+    );
+    actions.push(
+        Button::builder()
+            .text("Close")
+            .value("close")
+            .action_id("close")
+            .build(),
+    );
+    blocks = blocks.add(actions_block(actions));
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
+
+/// Renders the help of a subcommand, with a button to go back to all of them.
+pub fn command_view(command: &HelpCommand) -> Value {
+    let usage = command.usage.trim();
+    let (description, usage) = usage.split_once('\n').unwrap_or((usage, ""));
+    let blocks = BlockGroup::empty()
+        .add(
+            Header::builder()
+                .text(format!("/picker {}", command.name))
+                .build()
+                .into(),
+        )
+        .add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "{}\n```{}```",
+                    escape(description),
+                    escape(usage)
+                )))
+                .build()
+                .into(),
+        )
+        .add(actions_block(vec![
+            Button::builder()
+                .text("Back")
+                .value("help")
+                .action_id("help")
+                .build(),
+            Button::builder()
+                .text("Close")
+                .value("close")
+                .action_id("close")
+                .build(),
+        ]));
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
+
+/// Finds the subcommand with the name.
+pub fn find_command(name: &str) -> Option<&'static HelpCommand> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/// The usage of the subcommand with the name, shown when it is used with invalid arguments.
+pub fn usage(name: &str) -> &'static str {
+    find_command(name).map_or(USAGE_HINT_STR, |command| command.usage)
+}
+
+/// Escapes the characters with a special meaning on Slack texts (e.g. the `<id>` arguments).
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn actions_block(mut actions: Vec<Button>) -> slack_blocks::Block {
+    let mut block = Actions::builder()
+        .block_id(ACTIONS_BLOCK_ID)
+        .element(actions.remove(0));
+    for action in actions.into_iter() {
+        block = block.element(action);
+    }
+    block.build().into()
+}
+
+/// The block of the help buttons (e.g. creating or listing the events).
+pub const ACTIONS_BLOCK_ID: &str = "help_actions";
+
+/// The prefix of the action of the buttons showing the help of a subcommand, followed by its name.
+pub const COMMAND_ACTION_PREFIX: &str = "help_command:";
+
+const USAGE_HINT_STR: &str = "Type `/picker help` for all available commands.";
+
+pub const COMMANDS: &[HelpCommand] = &[
+    HelpCommand {
+        name: "audit",
+        summary: "Shows the latest actions performed on the events",
+        usage: r#"
+`audit`    Shows the latest actions performed on the events of this channel
+USAGE:
+    /picker audit
+"#,
+    },
+    HelpCommand {
+        name: "away",
+        summary: "Excludes yourself from the picks for a few days",
+        usage: r#"
+`away`    Excludes yourself from the picks of this channel's events for a few days
+USAGE:
+    /picker away <days>
+
+ARGS:
+    <days>     The number of days you will be away (use 0 to be picked again)
+"#,
+    },
+    HelpCommand {
+        name: "calendar",
+        summary: "Shares the calendar link of the events, or connects your calendar",
+        usage: r#"
+`calendar`    Shares the link to subscribe to the events of this channel from a calendar application, or connects your Google calendar so that you are skipped on the automatic picks of the days you are out of office
+USAGE:
+    /picker calendar
+    /picker calendar reset
+    /picker calendar connect
+    /picker calendar disconnect
+"#,
+    },
+    HelpCommand {
+        name: "clone",
+        summary: "Creates a copy of an existing event",
+        usage: r#"
+`clone`    Creates a copy of an event, with the same participants and schedule
+USAGE:
+    /picker clone <id>
+
+ARGS:
+    <id>    The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "config",
+        summary: "Shows or changes the settings of your team",
+        usage: r#"
+`config`    Shows or changes how the picks of your team are announced
+USAGE:
+    /picker config message
+    /picker config message <template>
+    /picker config message reset
+    /picker config acknowledge
+    /picker config acknowledge <minutes>
+    /picker config acknowledge off
+    /picker config recap
+    /picker config recap <on|off>
+    /picker config noshow
+    /picker config noshow <on|off>
+    /picker config clock
+    /picker config clock <12|24>
+    /picker config dates
+    /picker config dates <ymd|dmy|mdy>
+
+ARGS:
+    <template>    The message, mentioning the picked participant with {user}
+    <minutes>     The minutes the automatic picks have to be acknowledged in
+
+The message may also include {picker} (who picked), {event} (the event name) and {left} (the
+participants left to pick). The automatic picks not acknowledged in time are repicked. The recap
+of the picks of each channel is posted at the end of each month. The participants marked as
+no-shows may be picked more often from then on. The dates of the events are shown with the clock
+and in the order of the day, month and year set for the team.
+"#,
+    },
+    HelpCommand {
+        name: "create",
+        summary: "Create a new event",
+        usage: r#"
+`create`     Create a new event
+USAGE:
+    /picker create
+"#,
+    },
+    HelpCommand {
+        name: "delete",
+        summary: "Deletes an existing event",
+        usage: r#"
+`del`     Deletes an event
+USAGE:
+    /picker delete <id>
+
+ARGS:
+    <id>    The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "edit",
+        summary: "Edits an existing event",
+        usage: r#"
+`edit`    Edits an entity
+USAGE:
+    /picker edit <id>
+
+ARGS:
+    <id>    The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "export",
+        summary: "Exports the events to a file",
+        usage: r#"
+`export`    Shares a file with the settings of the events of this channel
+USAGE:
+    /picker export
+"#,
+    },
+    HelpCommand {
+        name: "history",
+        summary: "Shows the latest picks of an event",
+        usage: r#"
+`history`    Shows the latest picks of an event
+USAGE:
+    /picker history <id>
+
+ARGS:
+    <id>       The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "holiday",
+        summary: "Manages the holidays of your team",
+        usage: r#"
+`holiday`    Manages the days on which the events of your team are not automatically picked
+USAGE:
+    /picker holiday list
+    /picker holiday add <date> [name]
+    /picker holiday remove <date>
+
+ARGS:
+    <date>     The holiday date, formatted as YYYY-MM-DD
+    [name]     The name of the holiday
+"#,
+    },
+    HelpCommand {
+        name: "import",
+        summary: "Imports the events of an exported file",
+        usage: r#"
+`import`    Creates the events of an exported file on this channel
+USAGE:
+    /picker import <content>
+
+ARGS:
+    <content>    The content of the file shared by `/picker export`
+
+The events with the same name as an event of this channel are skipped.
+"#,
+    },
+    HelpCommand {
+        name: "join",
+        summary: "Joins the participants of an event",
+        usage: r#"
+`join`    Adds yourself to the participants of an event, letting the channel know
+USAGE:
+    /picker join <id>
+
+ARGS:
+    <id>       The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "leave",
+        summary: "Leaves the participants of an event",
+        usage: r#"
+`leave`    Removes yourself from the participants of an event, letting the channel know
+USAGE:
+    /picker leave <id>
+
+ARGS:
+    <id>       The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "list",
+        summary: "Lists all the events",
+        usage: r#"
+`list`    Lists all the events
+USAGE:
+    /picker list channels
+    /picker list events
+"#,
+    },
+    HelpCommand {
+        name: "locale",
+        summary: "Shows or changes the language of your team",
+        usage: r#"
+`locale`    Shows or changes the language of the messages and forms of your team
+USAGE:
+    /picker locale
+    /picker locale <language>
+
+ARGS:
+    <language>    The code of the language: en (English), es (Español) or pt (Português)
+"#,
+    },
+    HelpCommand {
+        name: "next",
+        summary: "Shows who is left to pick on an event",
+        usage: r#"
+`next`    Shows the participants not picked yet on the current round of an event
+USAGE:
+    /picker next <id>
+
+ARGS:
+    <id>       The ID of the event
+
+The round-robin events show the participants in the order they are picked in.
+"#,
+    },
+    HelpCommand {
+        name: "pick",
+        summary: "Picks randomly a participant of an event",
+        usage: r#"
+`pick`    Picks a random participant for an event
+USAGE:
+    /picker pick <id>
+
+ARGS:
+    <id>       The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "preview",
+        summary: "Previews the next pick of an event",
+        usage: r#"
+`preview`    Shows who would be picked for an event and who would be left, without picking anyone
+USAGE:
+    /picker preview <id>
+
+ARGS:
+    <id>       The ID of the event
+
+The random picks may differ from the preview, unless the event has a seed.
+"#,
+    },
+    HelpCommand {
+        name: "quiet",
+        summary: "Manages the quiet hours of your team",
+        usage: r#"
+`quiet`    Manages the hours of the day during which no automatic posts happen for your team
+USAGE:
+    /picker quiet
+    /picker quiet <start> <end> [timezone]
+    /picker quiet off
+
+ARGS:
+    <start>       The time the quiet hours start at, formatted as HH:MM (e.g. 19:00)
+    <end>         The time the quiet hours end at, formatted as HH:MM (e.g. 08:00)
+    [timezone]    The timezone of the quiet hours (e.g. EST), defaulting to yours
+
+Picks scheduled during the quiet hours happen once they end.
+"#,
+    },
+    HelpCommand {
+        name: "schedule",
+        summary: "Shows the upcoming automatic picks",
+        usage: r#"
+`schedule`    Shows the automatic picks of this channel's events for the next 4 weeks, grouped by day
+USAGE:
+    /picker schedule
+"#,
+    },
+    HelpCommand {
+        name: "show",
+        summary: "Shows the details of the event",
+        usage: r#"
+`show`    Shows the details of an event
+USAGE:
+    /picker show <id>
+
+ARGS:
+    <id>       The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "stats",
+        summary: "Shows the pick statistics of an event",
+        usage: r#"
+`stats`    Shows the pick statistics of each participant of an event
+USAGE:
+    /picker stats <id>
+
+ARGS:
+    <id>       The ID of the event
+"#,
+    },
+    HelpCommand {
+        name: "token",
+        summary: "Issues a token to pick the events through the API",
+        usage: r#"
+`token`    Issues the tokens to pick the events of this channel from external systems (e.g. CI pipelines)
+USAGE:
+    /picker token
+
+The access token is valid for an hour and is sent as a bearer token on the API requests. The
+refresh token renews it for 90 days through `POST /api/auth/refresh`, and is revoked through
+`POST /api/auth/logout`.
+"#,
+    },
+    HelpCommand {
+        name: "upgrade",
+        summary: "Upgrades your team to the Pro plan",
+        usage: r#"
+`upgrade`    Shares the link to subscribe your team to the Pro plan
+USAGE:
+    /picker upgrade
+
+The free plan allows a single event per channel, while the Pro plan allows many.
+"#,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let value = view(false);
+        let blocks = value["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), COMMANDS.len() + 3);
+        assert_eq!(
+            blocks[2]["accessory"]["action_id"],
+            format!("{}{}", COMMAND_ACTION_PREFIX, COMMANDS[0].name)
+        );
+        assert_eq!(blocks.last().unwrap()["elements"][0]["value"], "add_event");
+
+        let value = view(true);
+        let blocks = value["blocks"].as_array().unwrap();
+        assert_eq!(
+            blocks.last().unwrap()["elements"][0]["value"],
+            "list_events"
+        );
+    }
+
+    #[test]
+    fn test_command_view() {
+        let value = command_view(find_command("pick").unwrap());
+        assert_eq!(
+            value["blocks"][1]["text"]["text"],
+            "`pick`    Picks a random participant for an event\n```USAGE:\n    /picker pick &lt;id&gt;\n\nARGS:\n    &lt;id&gt;       The ID of the event```"
+        );
+        assert!(find_command("help").is_none());
+    }
+}
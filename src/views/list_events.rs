@@ -6,28 +6,46 @@ use slack_blocks::{
     text,
 };
 
+use crate::{
+    domain::{entities::RepeatPeriod, locale::Locale},
+    i18n,
+};
+
 use super::entities::{BlockGroup, Response};
 
 pub struct ListEventView {
     pub id: u32,
     pub name: String,
+    /// The date of the event, rendered by Slack on the timezone of each user.
     pub date: String,
     pub repeat: String,
 }
 
-pub fn view<'a>(events: Vec<ListEventView>, reached_limit: bool) -> Value {
+/// The cursors of the pages before and after the listed events, when there are any.
+pub struct ListEventsPage {
+    pub previous: Option<usize>,
+    pub next: Option<usize>,
+}
+
+pub fn view(
+    events: Vec<ListEventView>,
+    page: ListEventsPage,
+    reached_limit: bool,
+    locale: Locale,
+) -> Value {
     let mut blocks = BlockGroup::empty()
         .add(
             Header::builder()
-                .text("Checkout your events!")
+                .text(i18n::t(locale, "list_events_header"))
                 .build()
                 .into(),
         )
         .add(
             Section::builder()
-                .text(text::Mrkdwn::from_text(
-                    "Here, you can manage all of your events with ease.",
-                ))
+                .text(text::Mrkdwn::from_text(i18n::t(
+                    locale,
+                    "list_events_intro",
+                )))
                 .build()
                 .into(),
         );
@@ -39,26 +57,43 @@ pub fn view<'a>(events: Vec<ListEventView>, reached_limit: bool) -> Value {
                     event.id, event.name
                 )))
                 .fields(vec![
-                    text::Plain::from_text(event.date).into(),
+                    text::Mrkdwn::from_text(event.date).into(),
                     text::Plain::from_text(event.repeat).into(),
                 ])
                 .accessory(
                     slack_blocks::elems::overflow::Overflow::builder()
                         .options(vec![
                             Opt::builder()
-                                .text(text::Plain::from_text("Pick randomly"))
+                                .text(text::Plain::from_text(i18n::t(locale, "list_events_pick")))
                                 .value("pick")
                                 .build(),
                             Opt::builder()
-                                .text(text::Plain::from_text("Show details"))
+                                .text(text::Plain::from_text(i18n::t(
+                                    locale,
+                                    "list_events_skip_next",
+                                )))
+                                .value("skip_next")
+                                .build(),
+                            Opt::builder()
+                                .text(text::Plain::from_text(i18n::t(locale, "list_events_show")))
                                 .value("show")
                                 .build(),
                             Opt::builder()
-                                .text(text::Plain::from_text("Edit event"))
+                                .text(text::Plain::from_text(i18n::t(locale, "list_events_edit")))
                                 .value("edit")
                                 .build(),
                             Opt::builder()
-                                .text(text::Plain::from_text("Delete event"))
+                                .text(text::Plain::from_text(i18n::t(
+                                    locale,
+                                    "list_events_duplicate",
+                                )))
+                                .value("duplicate")
+                                .build(),
+                            Opt::builder()
+                                .text(text::Plain::from_text(i18n::t(
+                                    locale,
+                                    "list_events_delete",
+                                )))
                                 .value("delete")
                                 .build(),
                         ])
@@ -70,35 +105,105 @@ pub fn view<'a>(events: Vec<ListEventView>, reached_limit: bool) -> Value {
                 .into(),
         );
     }
-    let close_action = Button::builder()
-        .text("Close")
-        .value("close")
-        .action_id("close")
-        .build();
+    let mut actions = vec![];
+    if let Some(cursor) = page.previous {
+        actions.push(
+            Button::builder()
+                .text(i18n::t(locale, "previous_button"))
+                .value(format!("{}{}", PAGE_PREFIX, cursor))
+                .action_id("previous_page")
+                .build(),
+        );
+    }
+    if let Some(cursor) = page.next {
+        actions.push(
+            Button::builder()
+                .text(i18n::t(locale, "next_button"))
+                .value(format!("{}{}", PAGE_PREFIX, cursor))
+                .action_id("next_page")
+                .build(),
+        );
+    }
     if !reached_limit {
-        blocks = blocks.add(
-            Actions::builder()
-                .element(
-                    Button::builder()
-                        .text("Create a new event")
-                        .value("add_event")
-                        .action_id("add_event")
-                        .style(Style::Primary)
-                        .build(),
-                )
-                .element(close_action)
-                .block_id("list_events_actions")
-                .build()
-                .into(),
+        actions.push(
+            Button::builder()
+                .text(i18n::t(locale, "list_events_create_button"))
+                .value("add_event")
+                .action_id("add_event")
+                .style(Style::Primary)
+                .build(),
         );
-    } else {
-        blocks = blocks.add(
-            Actions::builder()
-                .element(close_action)
-                .block_id("list_events_actions")
-                .build()
-                .into(),
+    }
+    actions.push(
+        Button::builder()
+            .text(i18n::t(locale, "close_button"))
+            .value("close")
+            .action_id("close")
+            .build(),
+    );
+    let mut actions_block = Actions::builder()
+        .block_id("list_events_actions")
+        .element(actions.remove(0));
+    for action in actions.into_iter() {
+        actions_block = actions_block.element(action);
+    }
+    blocks = blocks.add(actions_block.build().into());
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
+
+/// The label of the frequency of the event (e.g. "Every Monday, Friday").
+pub fn repeat_label(locale: Locale, repeat: &RepeatPeriod) -> String {
+    match repeat {
+        RepeatPeriod::Days(weekdays) => {
+            let days = weekdays
+                .iter()
+                .map(|weekday| {
+                    let key = format!("weekday_{}", weekday.to_string().to_lowercase());
+                    i18n::t(locale, &key).to_string()
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            i18n::format(locale, "repeat_every", &[("days", &days)])
+        }
+        RepeatPeriod::Cron(expression) => {
+            i18n::format(locale, "repeat_custom", &[("expression", expression)])
+        }
+        repeat => {
+            let value: String = repeat
+                .clone()
+                .try_into()
+                .unwrap_or_else(|_| String::from("none"));
+            i18n::t(locale, &format!("repeat_{}", value)).to_string()
+        }
+    }
+}
+
+/// The prefix of the page buttons values, followed by the cursor of the page.
+pub const PAGE_PREFIX: &str = "page:";
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use super::*;
+
+    #[test]
+    fn test_repeat_label() {
+        assert_eq!(
+            repeat_label(Locale::En, &RepeatPeriod::Weekly(2)),
+            "Bi-weekly"
+        );
+        assert_eq!(repeat_label(Locale::Pt, &RepeatPeriod::Daily), "Diária");
+        assert_eq!(
+            repeat_label(
+                Locale::En,
+                &RepeatPeriod::Days(vec![Weekday::Mon, Weekday::Fri])
+            ),
+            "Every Monday, Friday"
+        );
+        assert_eq!(
+            repeat_label(Locale::Es, &RepeatPeriod::Cron(String::from("0 9 * * 1"))),
+            "Personalizada (0 9 * * 1)"
         );
     }
-    return serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize");
 }
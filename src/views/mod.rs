@@ -1,4 +1,19 @@
+pub mod anchor;
+pub mod announcement;
+pub mod audit;
+pub mod calendar;
 pub mod cancel_pick;
+pub mod channel_access;
 mod entities;
+pub mod event_stats;
+pub mod help;
 pub mod list_events;
+pub mod onboarding;
+pub mod pick_history;
+pub mod pick_history_csv;
 pub mod pick_participant;
+pub mod private_pick;
+pub mod recap;
+pub mod reminder;
+pub mod rotation;
+pub mod schedule;
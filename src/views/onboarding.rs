@@ -0,0 +1,139 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Section},
+    compose::{conversation_filter::ConversationKind, ConversationFilter},
+    elems::{button::Style, select, Button},
+    text, Block,
+};
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::{
+    entities::{BlockGroup, Response},
+    help,
+};
+
+/// The message sent to the user who installed the app, to get their team started.
+pub struct OnboardingView {
+    /// The installing user, messaged directly.
+    pub user_id: String,
+    pub locale: Locale,
+}
+
+pub fn view(data: OnboardingView) -> Value {
+    let locale = data.locale;
+    let blocks = BlockGroup::empty()
+        .channel(data.user_id.clone())
+        .add(mrkdwn_section(i18n::format(
+            locale,
+            "onboarding_welcome",
+            &[("user", &format!("<@{}>", data.user_id))],
+        )))
+        .add(mrkdwn_section(i18n::t(locale, "onboarding_choose_channel")))
+        .add(actions_block(locale, true));
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
+
+/// Replaces the welcome message with the steps of the tour, keeping the choice of the channel.
+pub fn tour_view(locale: Locale) -> Value {
+    let blocks = BlockGroup::empty()
+        .add(mrkdwn_section(i18n::t(locale, "onboarding_tour")))
+        .add(mrkdwn_section(i18n::t(locale, "onboarding_choose_channel")))
+        .add(actions_block(locale, false));
+    serde_json::to_value(Response::in_place(blocks)).expect("should serialize")
+}
+
+/// The message posted on the channel chosen by the installing user, with the button to create its
+/// first event.
+pub fn channel_view(channel_id: String, user_id: &str, locale: Locale) -> Value {
+    let blocks = BlockGroup::empty()
+        .channel(channel_id)
+        .add(mrkdwn_section(i18n::format(
+            locale,
+            "onboarding_channel_welcome",
+            &[("user", &format!("<@{}>", user_id))],
+        )))
+        .add(
+            // The button is handled as the one of the help, creating the event on the channel.
+            Actions::builder()
+                .element(
+                    Button::builder()
+                        .text(i18n::t(locale, "onboarding_create_button"))
+                        .value("add_event")
+                        .action_id("add_event")
+                        .style(Style::Primary)
+                        .build(),
+                )
+                .block_id(help::ACTIONS_BLOCK_ID)
+                .build()
+                .into(),
+        );
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
+
+fn mrkdwn_section<'a>(text: impl Into<String>) -> Block<'a> {
+    Section::builder()
+        .text(text::Mrkdwn::from_text(text.into()))
+        .build()
+        .into()
+}
+
+fn actions_block<'a>(locale: Locale, with_tour: bool) -> Block<'a> {
+    // The direct messages and the group ones cannot hold any event.
+    let filter = ConversationFilter::new()
+        .include_conversation_kinds([
+            ConversationKind::PublicChannel,
+            ConversationKind::PrivateChannel,
+        ])
+        .exclude_external_shared_channels();
+    let mut actions = Actions::builder().element(
+        select::Conversation::builder()
+            .placeholder(i18n::t(locale, "onboarding_channel_placeholder"))
+            .action_id(CHANNEL_ACTION_ID)
+            .filter(filter)
+            .build(),
+    );
+    if with_tour {
+        actions = actions.element(
+            Button::builder()
+                .text(i18n::t(locale, "onboarding_tour_button"))
+                .value("tour")
+                .action_id(TOUR_ACTION_ID)
+                .build(),
+        );
+    }
+    actions.build().into()
+}
+
+/// The action of the select choosing the channel to get started on.
+pub const CHANNEL_ACTION_ID: &str = "onboarding_actions:channel";
+
+/// The action of the button showing the tour.
+pub const TOUR_ACTION_ID: &str = "onboarding_actions:tour";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let body = view(OnboardingView {
+            user_id: String::from("U1"),
+            locale: Locale::default(),
+        });
+        assert_eq!(body["channel"], "U1");
+        let elements = &body["blocks"][2]["elements"];
+        assert_eq!(elements[0]["type"], "conversations_select");
+        assert_eq!(elements[0]["action_id"], CHANNEL_ACTION_ID);
+        assert_eq!(elements[1]["action_id"], TOUR_ACTION_ID);
+
+        let body = tour_view(Locale::default());
+        assert_eq!(body["delete_original"], false);
+        assert_eq!(body["blocks"][2]["elements"].as_array().unwrap().len(), 1);
+
+        let body = channel_view(String::from("C1"), "U1", Locale::default());
+        assert_eq!(body["channel"], "C1");
+        assert_eq!(body["blocks"][1]["block_id"], help::ACTIONS_BLOCK_ID);
+        assert_eq!(body["blocks"][1]["elements"][0]["value"], "add_event");
+    }
+}
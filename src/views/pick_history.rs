@@ -0,0 +1,116 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Header, Section},
+    elems::Button,
+    text,
+};
+
+use crate::{
+    domain::{entities::PickSource, locale::Locale},
+    i18n,
+};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct PickHistoryView {
+    pub event_id: u32,
+    pub event_name: String,
+    pub picks: Vec<PickHistoryItemView>,
+    pub locale: Locale,
+}
+
+pub struct PickHistoryItemView {
+    pub user: String,
+    pub date: String,
+    pub source: PickSource,
+    pub replaced: Option<String>,
+    pub partner: Option<String>,
+    pub no_show: bool,
+}
+
+pub fn view(data: PickHistoryView) -> Value {
+    let locale = data.locale;
+    let mut blocks = BlockGroup::empty().add(
+        Header::builder()
+            .text(i18n::format(
+                locale,
+                "history_header",
+                &[("event", &data.event_name)],
+            ))
+            .build()
+            .into(),
+    );
+    if data.picks.is_empty() {
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(i18n::format(
+                    locale,
+                    "history_empty",
+                    &[("id", &data.event_id.to_string())],
+                )))
+                .build()
+                .into(),
+        );
+    }
+    for pick in data.picks {
+        let partner = match pick.partner {
+            Some(user) => format!(" & <@{}>", user),
+            None => String::new(),
+        };
+        let replaced = match pick.replaced {
+            Some(user) => format!(
+                " ({})",
+                i18n::format(
+                    locale,
+                    "history_replacing",
+                    &[("user", &format!("<@{}>", user))]
+                )
+            ),
+            None => String::new(),
+        };
+        let no_show = match pick.no_show {
+            true => format!(" · {}", i18n::t(locale, "history_no_show")),
+            false => String::new(),
+        };
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "<@{}>{}{}\n\t\t_{} · {}{}_",
+                    pick.user,
+                    partner,
+                    replaced,
+                    pick.date,
+                    source_label(locale, &pick.source),
+                    no_show
+                )))
+                .build()
+                .into(),
+        );
+    }
+    blocks = blocks.add(
+        Actions::builder()
+            .element(
+                Button::builder()
+                    .text(i18n::t(locale, "close_button"))
+                    .value("close")
+                    .action_id("close")
+                    .build(),
+            )
+            .block_id("list_events_actions")
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
+
+/// The label of the source of the pick (e.g. "Source: Repick").
+fn source_label(locale: Locale, source: &PickSource) -> &str {
+    let key = match source {
+        PickSource::Manual => "source_manual",
+        PickSource::Auto => "source_scheduler",
+        PickSource::Repick => "source_repick",
+        PickSource::Skip => "source_skip",
+        PickSource::Volunteer => "source_volunteer",
+    };
+    i18n::t(locale, key)
+}
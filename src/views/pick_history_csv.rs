@@ -0,0 +1,72 @@
+use crate::domain::history::find_picks::Response as Pick;
+use crate::helpers::date::Date;
+
+/// Renders the picks as CSV lines, starting with the header one. Each line ends with a line break.
+pub fn lines(picks: Vec<Pick>) -> impl Iterator<Item = String> {
+    std::iter::once(format!("{}\r\n", HEADER)).chain(picks.into_iter().map(|pick| {
+        format!(
+            "{},{},{},{},{},{}\r\n",
+            escape(&pick.user),
+            Date::new(pick.timestamp)
+                .to_datetime()
+                .naive_utc()
+                .format(UTC_DATE_TIME_FORMAT),
+            escape(&pick.source.label()),
+            escape(&pick.replaced.unwrap_or_default()),
+            escape(&pick.partner.unwrap_or_default()),
+            pick.no_show
+        )
+    }))
+}
+
+/// Quotes the values with a special meaning on CSV.
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+const HEADER: &str = "user,timestamp,source,replaced,partner,no_show";
+const UTC_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::PickSource;
+
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let lines: Vec<String> = lines(vec![
+            Pick {
+                user: String::from("U1"),
+                timestamp: 1686661200,
+                source: PickSource::Auto,
+                replaced: None,
+                partner: None,
+                no_show: true,
+            },
+            Pick {
+                user: String::from("U2"),
+                timestamp: 1686664800,
+                source: PickSource::Repick,
+                replaced: Some(String::from("U1")),
+                partner: None,
+                no_show: false,
+            },
+        ])
+        .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "user,timestamp,source,replaced,partner,no_show\r\n",
+                "U1,2023-06-13T13:00:00Z,Automatic scheduler,,,true\r\n",
+                "U2,2023-06-13T14:00:00Z,Repick,U1,,false\r\n",
+            ]
+        );
+        assert_eq!(escape("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -5,16 +5,37 @@ use slack_blocks::{
     text,
 };
 
+use crate::{domain::locale::Locale, i18n};
+
 use super::entities::{BlockGroup, Response};
+use super::reminder::minutes_label;
 
 pub struct PickParticipantView {
     pub event_id: u32,
     pub event_name: String,
     pub user_id: String,
     pub user_picked_id: String,
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner_picked_id: Option<String>,
     pub channel_id: String,
     pub left_count: usize,
     pub source: PickParticipantSource,
+    /// The timestamp of the message to reply on, when posting in a thread.
+    pub thread_ts: Option<String>,
+    /// The participants picked before on this message and replaced by repicks, oldest first.
+    pub replaced: Vec<String>,
+    pub locale: Locale,
+    /// The message of the team the pick is posted with, in place of the default one.
+    pub message: Option<String>,
+    /// The minutes the picked participant has to acknowledge the pick in, when it needs it.
+    pub acknowledge_minutes: Option<u32>,
+    /// The description of the event, so that the picked participants know what they are picked
+    /// for.
+    pub description: Option<String>,
+    /// The link of the event (e.g. its meeting).
+    pub url: Option<String>,
+    /// Shows the pick only to whoever picked, on the events whose picks are private.
+    pub private: bool,
 }
 
 pub enum PickParticipantSource {
@@ -22,6 +43,10 @@ pub enum PickParticipantSource {
     Repick,
     Scheduler,
     Skip,
+    /// The scheduler repicked, as the previous participant did not acknowledge the pick.
+    Timeout,
+    /// A participant took the pick in place of the picked one.
+    Volunteer,
 }
 
 pub struct PickParticipantResult {
@@ -29,64 +54,318 @@ pub struct PickParticipantResult {
 }
 
 pub fn view(data: PickParticipantView) -> Value {
-    let blocks = BlockGroup::empty()
+    let locale = data.locale;
+    let replaced = if data.replaced.is_empty() {
+        String::new()
+    } else {
+        let users = data
+            .replaced
+            .iter()
+            .map(|user| format!("<@{}>", user))
+            .collect::<Vec<String>>()
+            .join(" → ");
+        format!(
+            "\n\t\t_{}_",
+            i18n::format(locale, "pick_replaced", &[("users", &users)])
+        )
+    };
+    // The scheduler is named after the bot instead of being mentioned.
+    let picker = match data.source {
+        PickParticipantSource::Scheduler | PickParticipantSource::Timeout => data.user_id.clone(),
+        _ => format!("<@{}>", data.user_id),
+    };
+    let user = mention_picked(
+        locale,
+        &data.user_picked_id,
+        data.partner_picked_id.as_deref(),
+    );
+    let left_count = data.left_count.to_string();
+    let args = [
+        ("picker", picker.as_str()),
+        ("user", user.as_str()),
+        ("event", data.event_name.as_str()),
+        ("left", left_count.as_str()),
+    ];
+    let (message_key, source_key) = match data.source {
+        PickParticipantSource::Pick => ("pick_manual", "source_manual"),
+        PickParticipantSource::Repick => ("pick_repick", "source_repick"),
+        PickParticipantSource::Scheduler => ("pick_scheduler", "source_scheduler"),
+        PickParticipantSource::Skip => ("pick_skip", "source_skip"),
+        PickParticipantSource::Timeout => ("pick_timeout", "source_timeout"),
+        PickParticipantSource::Volunteer => ("pick_volunteer", "source_volunteer"),
+    };
+    let message = match &data.message {
+        Some(message) => i18n::fill(message, &args),
+        None => i18n::format(locale, message_key, &args),
+    };
+    let acknowledge = match data.acknowledge_minutes {
+        Some(minutes) => format!(
+            "\n{}",
+            i18n::format(
+                locale,
+                "pick_acknowledge_hint",
+                &[("minutes", &minutes_label(locale, minutes))],
+            )
+        ),
+        None => String::new(),
+    };
+    let is_repick = matches!(
+        data.source,
+        PickParticipantSource::Repick | PickParticipantSource::Volunteer
+    );
+    let mut buttons = vec![];
+    if data.acknowledge_minutes.is_some() {
+        buttons.push(
+            Button::builder()
+                .text(i18n::t(locale, "pick_acknowledge_button"))
+                .action_id(ACKNOWLEDGE_ACTION_ID)
+                .value(data.event_id.to_string())
+                .style(Style::Primary)
+                .build(),
+        );
+    }
+    buttons.push(
+        Button::builder()
+            .text(i18n::t(locale, "pick_skip_button"))
+            .action_id("pick_participant_actions:pick")
+            .value(data.event_id.to_string())
+            .build(),
+    );
+    buttons.push(
+        Button::builder()
+            .text(text::Plain::from_text(i18n::t(
+                locale,
+                "pick_repick_button",
+            )))
+            .action_id("pick_participant_actions:repick")
+            .value(data.event_id.to_string())
+            .build(),
+    );
+    buttons.push(
+        Button::builder()
+            .text(i18n::t(locale, "pick_volunteer_button"))
+            .action_id(VOLUNTEER_ACTION_ID)
+            .value(format!("{}:{}", data.event_id, data.user_picked_id))
+            .build(),
+    );
+    buttons.push(
+        Button::builder()
+            .text(i18n::t(locale, "pick_no_show_button"))
+            .action_id(NO_SHOW_ACTION_ID)
+            .value(format!("{}:{}", data.event_id, data.user_picked_id))
+            .build(),
+    );
+    buttons.push(
+        Button::builder()
+            .text(text::Plain::from_text(i18n::t(
+                locale,
+                "pick_cancel_button",
+            )))
+            .action_id("pick_participant_actions:cancel")
+            .value(data.event_id.to_string())
+            .style(Style::Danger)
+            .build(),
+    );
+    let mut actions = Actions::builder().element(buttons.remove(0));
+    for button in buttons.into_iter() {
+        actions = actions.element(button);
+    }
+    let details = event_details(locale, data.description, data.url);
+    let mut blocks = BlockGroup::empty()
         .channel(data.channel_id)
+        .thread(data.thread_ts)
         .add(
             Section::builder()
-                .text(text::Mrkdwn::from_text(
-                    match data.source {
-                       PickParticipantSource::Pick =>
-                         format!(
-                            "<@{}> randomly picked <@{}> for the event *{}* ({} left)\n\t\t_Source: Manual Pick_",
-                             data.user_id, data.user_picked_id, data.event_name, data.left_count
-                            ),
-                       PickParticipantSource::Repick =>
-                         format!(
-                            "<@{}> repicked <@{}> for the event *{}* ({} left)\n\t\t_Source: Repick_",
-                             data.user_id, data.user_picked_id, data.event_name, data.left_count
-                            ),
-                       PickParticipantSource::Scheduler =>
-                         format!(
-                            "{} automatically picked <@{}> for the event *{}* ({} left)\n\t\t_Source: Automatic scheduler_",
-                             data.user_id, data.user_picked_id, data.event_name, data.left_count
-                            ),
-                       PickParticipantSource::Skip =>
-                         format!(
-                            "<@{}> skipped and now <@{}> was randomly picked for the event *{}* ({} left)\n\t\t_Source: Skip_",
-                             data.user_id, data.user_picked_id, data.event_name, data.left_count
-                            ),
-                    }
-                ))
+                .text(text::Mrkdwn::from_text(format!(
+                    "{}\n\t\t_{}_{}{}",
+                    message,
+                    i18n::t(locale, source_key),
+                    replaced,
+                    acknowledge
+                )))
                 .build()
                 .into(),
-        )
-        .add(
-            Actions::builder()
-                .element(
-                    Button::builder()
-                        .text("Skip")
-                        .action_id("pick_participant_actions:pick")
-                        .value(data.event_id.to_string())
-                        .build(),
-                )
-                .element(
-                    Button::builder()
-                        .text(text::Plain::from_text("Repick"))
-                        .action_id("pick_participant_actions:repick")
-                        .value(data.event_id.to_string())
-                        .build(),
-                )
-                .element(
-                    Button::builder()
-                        .text(text::Plain::from_text("Cancel"))
-                        .action_id("pick_participant_actions:cancel")
-                        .value(data.event_id.to_string())
-                        .style(Style::Danger)
-                        .build(),
-                )
+        );
+    if let Some(details) = details {
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(details))
                 .build()
                 .into(),
         );
+    }
+    let blocks = blocks.add(actions.build().into());
+
+    // Repicks and volunteers edit the pick message, so the channel is not left with stale picks.
+    let response = if is_repick {
+        Response::in_place(blocks)
+    } else if data.private {
+        Response::ephemeral(blocks)
+    } else {
+        Response::in_channel(blocks)
+    };
+    serde_json::to_value(response).expect("should serialize")
+}
+
+/// Joins the description and the link of the event, when it has any of them.
+pub fn event_details(
+    locale: Locale,
+    description: Option<String>,
+    url: Option<String>,
+) -> Option<String> {
+    let link = url.map(|url| format!("<{}|🔗 {}>", url, i18n::t(locale, "pick_event_link")));
+    match (description, link) {
+        (Some(description), Some(link)) => Some(format!("{}\n{}", description, link)),
+        (description, link) => description.or(link),
+    }
+}
+
+/// Mentions the picked participant, along with their partner on the events picking pairs.
+pub fn mention_picked(locale: Locale, user: &str, partner: Option<&str>) -> String {
+    let user = format!("<@{}>", user);
+    match partner {
+        Some(partner) => i18n::format(
+            locale,
+            "pick_pair",
+            &[("user", &user), ("partner", &format!("<@{}>", partner))],
+        ),
+        None => user,
+    }
+}
+
+/// The action of the button acknowledging the pick.
+pub const ACKNOWLEDGE_ACTION_ID: &str = "pick_participant_actions:acknowledge";
+
+/// The action of the button marking the picked participant as a no-show.
+pub const NO_SHOW_ACTION_ID: &str = "pick_participant_actions:no_show";
+
+/// The action of the button letting a participant take the pick in place of the picked one.
+pub const VOLUNTEER_ACTION_ID: &str = "pick_participant_actions:volunteer";
+
+/// Parses the value of the buttons about the picked participant, made of the event and the
+/// participant.
+pub fn parse_pick_value(value: &str) -> Option<(u32, String)> {
+    let (event_id, user) = value.split_once(':')?;
+    Some((event_id.parse().ok()?, user.to_string()))
+}
 
-    return serde_json::to_value(Response::in_channel(blocks)).expect("should serialize");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pick_view(message: Option<String>) -> PickParticipantView {
+        PickParticipantView {
+            event_id: 1,
+            event_name: String::from("Daily"),
+            user_id: String::from("U1"),
+            user_picked_id: String::from("U2"),
+            partner_picked_id: None,
+            channel_id: String::from("C1"),
+            left_count: 3,
+            source: PickParticipantSource::Pick,
+            thread_ts: None,
+            replaced: vec![],
+            locale: Locale::default(),
+            message,
+            acknowledge_minutes: None,
+            description: None,
+            url: None,
+            private: false,
+        }
+    }
+
+    #[test]
+    fn test_view_message() {
+        let body = view(pick_view(None));
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "<@U1> randomly picked <@U2> for the event *Daily* (3 left)\n\t\t_Source: Manual Pick_"
+        );
+
+        let message = String::from("{user} runs the {event} today 🎤 ({left} to go)");
+        let body = view(pick_view(Some(message)));
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "<@U2> runs the Daily today 🎤 (3 to go)\n\t\t_Source: Manual Pick_"
+        );
+    }
+
+    #[test]
+    fn test_view_acknowledge() {
+        let body = view(PickParticipantView {
+            source: PickParticipantSource::Scheduler,
+            user_id: String::from("Team Picker"),
+            acknowledge_minutes: Some(30),
+            ..pick_view(None)
+        });
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "Team Picker automatically picked <@U2> for the event *Daily* (3 left)\n\t\t_Source: Automatic scheduler_\nPlease acknowledge this pick within 30 minutes, or someone else will be picked."
+        );
+        assert_eq!(
+            body["blocks"][1]["elements"][0]["action_id"],
+            ACKNOWLEDGE_ACTION_ID
+        );
+        assert_eq!(body["blocks"][1]["elements"].as_array().unwrap().len(), 6);
+        assert_eq!(
+            body["blocks"][1]["elements"][3]["action_id"],
+            VOLUNTEER_ACTION_ID
+        );
+        assert_eq!(body["blocks"][1]["elements"][3]["value"], "1:U2");
+        assert_eq!(body["blocks"][1]["elements"][4]["value"], "1:U2");
+        assert_eq!(parse_pick_value("1:U2"), Some((1, String::from("U2"))));
+        assert_eq!(parse_pick_value("U2"), None);
+    }
+
+    #[test]
+    fn test_view_details() {
+        let body = view(PickParticipantView {
+            description: Some(String::from("Share the updates of yesterday")),
+            url: Some(String::from("https://meet.example.com/daily")),
+            ..pick_view(None)
+        });
+        assert_eq!(
+            body["blocks"][1]["text"]["text"],
+            "Share the updates of yesterday\n<https://meet.example.com/daily|🔗 Event link>"
+        );
+        assert_eq!(body["blocks"][2]["type"], "actions");
+
+        let body = view(pick_view(None));
+        assert_eq!(body["blocks"][1]["type"], "actions");
+        assert_eq!(body["response_type"], "in_channel");
+
+        let body = view(PickParticipantView {
+            private: true,
+            ..pick_view(None)
+        });
+        assert_eq!(body["response_type"], "ephemeral");
+    }
+
+    #[test]
+    fn test_view_volunteer() {
+        let body = view(PickParticipantView {
+            source: PickParticipantSource::Volunteer,
+            user_id: String::from("U3"),
+            user_picked_id: String::from("U3"),
+            replaced: vec![String::from("U2")],
+            ..pick_view(None)
+        });
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "<@U3> volunteered for the event *Daily* (3 left)\n\t\t_Source: Volunteer_\n\t\t_Previously picked: <@U2>_"
+        );
+        assert_eq!(body["delete_original"], false);
+    }
+
+    #[test]
+    fn test_view_pair() {
+        let body = view(PickParticipantView {
+            partner_picked_id: Some(String::from("U3")),
+            ..pick_view(None)
+        });
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "<@U1> randomly picked <@U2> and <@U3> for the event *Daily* (3 left)\n\t\t_Source: Manual Pick_"
+        );
+    }
 }
@@ -0,0 +1,99 @@
+use serde_json::Value;
+use slack_blocks::blocks::Section;
+use slack_blocks::text;
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::entities::{BlockGroup, Response};
+use super::pick_participant::{event_details, mention_picked};
+
+pub struct PrivatePickView {
+    /// The participant the pick is sent to by direct message.
+    pub recipient_id: String,
+    /// The channel of the event.
+    pub channel_id: String,
+    pub event_name: String,
+    pub user_picked_id: String,
+    /// The participant picked together with the other one, on the events picking pairs.
+    pub partner_picked_id: Option<String>,
+    pub left_count: usize,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub locale: Locale,
+}
+
+pub fn view(data: PrivatePickView) -> Value {
+    let locale = data.locale;
+    let is_picked = data.recipient_id == data.user_picked_id
+        || data.partner_picked_id.as_ref() == Some(&data.recipient_id);
+    let user = mention_picked(
+        locale,
+        &data.user_picked_id,
+        data.partner_picked_id.as_deref(),
+    );
+    let left_count = data.left_count.to_string();
+    let message = i18n::format(
+        locale,
+        if is_picked {
+            "private_pick_you"
+        } else {
+            "private_pick"
+        },
+        &[
+            ("user", &user),
+            ("event", &data.event_name),
+            ("channel", &data.channel_id),
+            ("left", &left_count),
+        ],
+    );
+    let mut blocks = BlockGroup::empty().channel(data.recipient_id).add(
+        Section::builder()
+            .text(text::Mrkdwn::from_text(message))
+            .build()
+            .into(),
+    );
+    if let Some(details) = event_details(locale, data.description, data.url) {
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(details))
+                .build()
+                .into(),
+        );
+    }
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let data = |recipient_id: &str| PrivatePickView {
+            recipient_id: String::from(recipient_id),
+            channel_id: String::from("C1"),
+            event_name: String::from("Incident backup"),
+            user_picked_id: String::from("U1"),
+            partner_picked_id: None,
+            left_count: 2,
+            description: None,
+            url: None,
+            locale: Locale::default(),
+        };
+
+        let body = view(data("U1"));
+        assert_eq!(body["channel"], "U1");
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "🔒 You were picked for the event *Incident backup* of <#C1> (2 left)."
+        );
+
+        let body = view(data("U2"));
+        assert_eq!(body["channel"], "U2");
+        assert_eq!(
+            body["blocks"][0]["text"]["text"],
+            "🔒 <@U1> was picked for the event *Incident backup* of <#C1> (2 left)."
+        );
+        assert_eq!(body["blocks"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::NaiveDate;
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Header, Section},
+    text,
+};
+
+use crate::{
+    domain::{date_format::DateFormat, locale::Locale},
+    i18n,
+};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct RecapView {
+    pub channel_id: String,
+    /// The first day of the recapped month.
+    pub month: NaiveDate,
+    pub picks: u32,
+    pub skips: u32,
+    pub leaderboard: Vec<LeaderView>,
+    pub acknowledged: u32,
+    pub unacknowledged: u32,
+    pub locale: Locale,
+    pub date_format: DateFormat,
+}
+
+pub struct LeaderView {
+    pub user: String,
+    pub picks: u32,
+}
+
+pub fn view(data: RecapView) -> Value {
+    let locale = data.locale;
+    let mut fields = vec![
+        text::Mrkdwn::from_text(i18n::format(
+            locale,
+            "recap_picks",
+            &[("count", &data.picks.to_string())],
+        ))
+        .into(),
+        text::Mrkdwn::from_text(i18n::format(
+            locale,
+            "recap_skips",
+            &[("count", &data.skips.to_string())],
+        ))
+        .into(),
+    ];
+    if let Some(rate) = acknowledgment_rate(data.acknowledged, data.unacknowledged) {
+        fields.push(
+            text::Mrkdwn::from_text(i18n::format(
+                locale,
+                "recap_acknowledgment",
+                &[("rate", &rate.to_string())],
+            ))
+            .into(),
+        );
+    }
+    let leaderboard = data
+        .leaderboard
+        .iter()
+        .enumerate()
+        .map(|(i, leader)| {
+            i18n::format(
+                locale,
+                "recap_leader",
+                &[
+                    ("position", &position(i)),
+                    ("user", &format!("<@{}>", leader.user)),
+                    ("count", &leader.picks.to_string()),
+                ],
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let blocks = BlockGroup::empty()
+        .channel(data.channel_id)
+        .add(
+            Header::builder()
+                .text(i18n::format(
+                    locale,
+                    "recap_title",
+                    &[(
+                        "month",
+                        &data
+                            .month
+                            .format(data.date_format.order.month_pattern())
+                            .to_string(),
+                    )],
+                ))
+                .build()
+                .into(),
+        )
+        .add(Section::builder().fields(fields).build().into())
+        .add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "{}\n{}",
+                    i18n::t(locale, "recap_leaderboard"),
+                    leaderboard
+                )))
+                .build()
+                .into(),
+        );
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
+
+/// The percentage of the automatic picks that were acknowledged, when any needed it.
+fn acknowledgment_rate(acknowledged: u32, unacknowledged: u32) -> Option<u32> {
+    let total = acknowledged + unacknowledged;
+    (total > 0).then(|| (acknowledged as f64 * 100.0 / total as f64).round() as u32)
+}
+
+/// The medals of the podium, followed by the positions of the others.
+fn position(index: usize) -> String {
+    match index {
+        0 => String::from("🥇"),
+        1 => String::from("🥈"),
+        2 => String::from("🥉"),
+        _ => format!("{}.", index + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let body = view(RecapView {
+            channel_id: String::from("C1"),
+            month: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            picks: 20,
+            skips: 2,
+            leaderboard: vec![
+                LeaderView {
+                    user: String::from("U1"),
+                    picks: 8,
+                },
+                LeaderView {
+                    user: String::from("U2"),
+                    picks: 7,
+                },
+            ],
+            acknowledged: 2,
+            unacknowledged: 1,
+            locale: Locale::default(),
+            date_format: DateFormat::default(),
+        });
+        assert_eq!(body["channel"], "C1");
+        assert_eq!(body["blocks"][1]["fields"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            body["blocks"][2]["text"]["text"],
+            "*Most picked*\n🥇 <@U1> (8)\n🥈 <@U2> (7)"
+        );
+
+        assert_eq!(acknowledgment_rate(2, 1), Some(67));
+        assert_eq!(acknowledgment_rate(0, 0), None);
+        assert_eq!(position(3), "4.");
+    }
+}
@@ -0,0 +1,43 @@
+use serde_json::Value;
+use slack_blocks::blocks::Section;
+use slack_blocks::text;
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct ReminderView {
+    pub channel_id: String,
+    pub event_name: String,
+    pub minutes: u32,
+    pub locale: Locale,
+}
+
+pub fn view(data: ReminderView) -> Value {
+    let blocks = BlockGroup::empty().channel(data.channel_id).add(
+        Section::builder()
+            .text(text::Mrkdwn::from_text(i18n::format(
+                data.locale,
+                "reminder",
+                &[
+                    ("event", &data.event_name),
+                    ("minutes", &minutes_label(data.locale, data.minutes)),
+                ],
+            )))
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::in_channel(blocks)).expect("should serialize")
+}
+
+/// The label of the minutes (e.g. "15 minutes").
+pub fn minutes_label(locale: Locale, minutes: u32) -> String {
+    if minutes == 1 {
+        return i18n::t(locale, "reminder_minute").to_string();
+    }
+    i18n::format(
+        locale,
+        "reminder_minutes",
+        &[("minutes", &minutes.to_string())],
+    )
+}
@@ -0,0 +1,136 @@
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Header, Section},
+    compose::Opt,
+    elems::{overflow::Overflow, Button},
+    text,
+};
+
+use crate::{domain::locale::Locale, i18n};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct RotationView {
+    pub event_id: u32,
+    pub event_name: String,
+    /// The participants, in the order they are picked in.
+    pub participants: Vec<String>,
+    /// The participant picked next, when there is anyone left to pick.
+    pub next: Option<String>,
+    pub locale: Locale,
+}
+
+/// Renders the rotation of a round-robin event, with a menu on each participant to move them up or
+/// down.
+pub fn view(data: RotationView) -> Value {
+    let locale = data.locale;
+    let mut blocks = BlockGroup::empty()
+        .add(
+            Header::builder()
+                .text(i18n::format(
+                    locale,
+                    "rotation_header",
+                    &[("event", &data.event_name)],
+                ))
+                .build()
+                .into(),
+        )
+        .add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(i18n::t(locale, "rotation_intro")))
+                .build()
+                .into(),
+        );
+    for (i, user) in data.participants.iter().enumerate() {
+        let next = match data.next.as_deref() == Some(user.as_str()) {
+            true => format!(" _({})_", i18n::t(locale, "rotation_next")),
+            false => String::new(),
+        };
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "{}. <@{}>{}",
+                    i + 1,
+                    user,
+                    next
+                )))
+                .accessory(
+                    Overflow::builder()
+                        .options(vec![
+                            Opt::builder()
+                                .text(text::Plain::from_text(i18n::t(locale, "rotation_move_up")))
+                                .value(move_value(MOVE_UP, data.event_id, user))
+                                .build(),
+                            Opt::builder()
+                                .text(text::Plain::from_text(i18n::t(
+                                    locale,
+                                    "rotation_move_down",
+                                )))
+                                .value(move_value(MOVE_DOWN, data.event_id, user))
+                                .build(),
+                        ])
+                        .action_id(MOVE_ACTION_ID)
+                        .build(),
+                )
+                .build()
+                .into(),
+        );
+    }
+    blocks = blocks.add(
+        Actions::builder()
+            .block_id("show_event_actions")
+            .element(
+                Button::builder()
+                    .text(i18n::t(locale, "close_button"))
+                    .value("close")
+                    .action_id("close")
+                    .build(),
+            )
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::in_place(blocks)).expect("should serialize")
+}
+
+/// Parses the value of the option moving a participant into its direction, event and user.
+pub fn parse_move(value: &str) -> Option<(&str, u32, &str)> {
+    let mut parts = value.splitn(3, ':');
+    let direction = parts.next().filter(|d| *d == MOVE_UP || *d == MOVE_DOWN)?;
+    let event_id = parts.next()?.parse().ok()?;
+    let user = parts.next()?;
+    Some((direction, event_id, user))
+}
+
+fn move_value(direction: &str, event_id: u32, user: &str) -> String {
+    format!("{}:{}:{}", direction, event_id, user)
+}
+
+/// The action of the menus moving the participants on the rotation.
+pub const MOVE_ACTION_ID: &str = "rotation_actions";
+
+pub const MOVE_UP: &str = "up";
+pub const MOVE_DOWN: &str = "down";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let value = view(RotationView {
+            event_id: 1,
+            event_name: String::from("Daily"),
+            participants: vec![String::from("U1"), String::from("U2")],
+            next: Some(String::from("U2")),
+            locale: Locale::default(),
+        });
+        let blocks = value["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(blocks[3]["text"]["text"], "2. <@U2> _(next)_");
+        let value = blocks[3]["accessory"]["options"][0]["value"]
+            .as_str()
+            .unwrap();
+        assert_eq!(parse_move(value), Some((MOVE_UP, 1, "U2")));
+        assert_eq!(parse_move("left:1:U2"), None);
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::{NaiveDate, NaiveTime};
+use serde_json::Value;
+use slack_blocks::{
+    blocks::{Actions, Header, Section},
+    elems::Button,
+    text,
+};
+
+use crate::{
+    domain::{date_format::DateFormat, locale::Locale},
+    i18n,
+};
+
+use super::entities::{BlockGroup, Response};
+
+pub struct ScheduleView {
+    /// The timezone the picks are shown on.
+    pub timezone: String,
+    pub date_format: DateFormat,
+    /// The number of days the schedule covers.
+    pub days_count: u32,
+    /// The days with scheduled picks, in order.
+    pub days: Vec<ScheduleDay>,
+    pub locale: Locale,
+}
+
+pub struct ScheduleDay {
+    pub date: NaiveDate,
+    /// The picks of the day, in order.
+    pub picks: Vec<ScheduledPick>,
+}
+
+pub struct ScheduledPick {
+    pub time: NaiveTime,
+    pub event_id: u32,
+    pub event_name: String,
+}
+
+/// Renders the upcoming automatic picks of the channel, grouped by day.
+pub fn view(data: ScheduleView) -> Value {
+    let locale = data.locale;
+    let days_count = data.days_count.to_string();
+    let mut blocks = BlockGroup::empty()
+        .add(
+            Header::builder()
+                .text(i18n::t(locale, "schedule_header"))
+                .build()
+                .into(),
+        )
+        .add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(match data.days.is_empty() {
+                    true => i18n::format(locale, "schedule_empty", &[("days", &days_count)]),
+                    false => i18n::format(
+                        locale,
+                        "schedule_intro",
+                        &[("days", &days_count), ("timezone", &data.timezone)],
+                    ),
+                }))
+                .build()
+                .into(),
+        );
+    for day in data.days.iter() {
+        let picks = day
+            .picks
+            .iter()
+            .map(|pick| {
+                format!(
+                    "`{}`  {} (#{})",
+                    pick.time.format(data.date_format.clock.time_pattern()),
+                    pick.event_name,
+                    pick.event_id
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        blocks = blocks.add(
+            Section::builder()
+                .text(text::Mrkdwn::from_text(format!(
+                    "*{}*\n{}",
+                    day.date
+                        .format_localized(data.date_format.order.day_pattern(), locale.chrono()),
+                    picks
+                )))
+                .build()
+                .into(),
+        );
+    }
+    blocks = blocks.add(
+        Actions::builder()
+            .block_id("show_event_actions")
+            .element(
+                Button::builder()
+                    .text(i18n::t(locale, "close_button"))
+                    .value("close")
+                    .action_id("close")
+                    .build(),
+            )
+            .build()
+            .into(),
+    );
+    serde_json::to_value(Response::ephemeral(blocks)).expect("should serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let value = view(ScheduleView {
+            timezone: String::from("WET"),
+            date_format: DateFormat::default(),
+            days_count: 28,
+            days: vec![ScheduleDay {
+                date: NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(),
+                picks: vec![
+                    ScheduledPick {
+                        time: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+                        event_id: 1,
+                        event_name: String::from("Daily"),
+                    },
+                    ScheduledPick {
+                        time: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+                        event_id: 2,
+                        event_name: String::from("Retro"),
+                    },
+                ],
+            }],
+            locale: Locale::default(),
+        });
+        let blocks = value["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(
+            blocks[2]["text"]["text"],
+            "*Thursday, March 14*\n`09:30`  Daily (#1)\n`14:00`  Retro (#2)"
+        );
+
+        let value = view(ScheduleView {
+            timezone: String::from("WET"),
+            date_format: DateFormat::default(),
+            days_count: 28,
+            days: vec![ScheduleDay {
+                date: NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(),
+                picks: vec![],
+            }],
+            locale: Locale::Pt,
+        });
+        assert_eq!(value["blocks"][2]["text"]["text"], "*quinta, março 14*\n");
+    }
+}